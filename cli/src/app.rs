@@ -55,6 +55,9 @@ pub enum Command {
     /// Manage active invocations
     #[clap(subcommand)]
     Invocations(invocations::Invocations),
+    /// Manage cron schedules invoking handlers periodically
+    #[clap(subcommand)]
+    Schedules(schedules::Schedules),
     /// Runs SQL queries against the data fusion service
     Sql(sql::Sql),
     /// Download one of Restate's examples in this directory.
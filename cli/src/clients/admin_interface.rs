@@ -13,6 +13,7 @@ use super::admin_client::Envelope;
 use super::AdminClient;
 
 use restate_admin_rest_model::deployments::*;
+use restate_admin_rest_model::schedules::*;
 use restate_admin_rest_model::services::*;
 use restate_admin_rest_model::version::VersionInformation;
 use restate_types::schema::service::ServiceMetadata;
@@ -43,12 +44,27 @@ pub trait AdminClientInterface {
 
     async fn cancel_invocation(&self, id: &str, kill: bool) -> reqwest::Result<Envelope<()>>;
 
+    async fn pause_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>>;
+
+    async fn resume_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>>;
+
     async fn patch_state(
         &self,
         service: &str,
         req: ModifyServiceStateRequest,
     ) -> reqwest::Result<Envelope<()>>;
 
+    async fn get_schedules(&self) -> reqwest::Result<Envelope<ListSchedulesResponse>>;
+
+    async fn get_schedule(&self, id: &str) -> reqwest::Result<Envelope<ScheduleResponse>>;
+
+    async fn create_schedule(
+        &self,
+        body: CreateScheduleRequest,
+    ) -> reqwest::Result<Envelope<ScheduleResponse>>;
+
+    async fn delete_schedule(&self, id: &str) -> reqwest::Result<Envelope<()>>;
+
     async fn version(&self) -> reqwest::Result<Envelope<VersionInformation>>;
 }
 
@@ -146,6 +162,24 @@ impl AdminClientInterface for AdminClient {
         self.run(reqwest::Method::DELETE, url).await
     }
 
+    async fn pause_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>> {
+        let url = self
+            .base_url
+            .join(&format!("/invocations/{}/pause", id))
+            .expect("Bad url!");
+
+        self.run(reqwest::Method::PATCH, url).await
+    }
+
+    async fn resume_invocation(&self, id: &str) -> reqwest::Result<Envelope<()>> {
+        let url = self
+            .base_url
+            .join(&format!("/invocations/{}/resume", id))
+            .expect("Bad url!");
+
+        self.run(reqwest::Method::PATCH, url).await
+    }
+
     async fn patch_state(
         &self,
         service: &str,
@@ -159,6 +193,37 @@ impl AdminClientInterface for AdminClient {
         self.run_with_body(reqwest::Method::POST, url, req).await
     }
 
+    async fn get_schedules(&self) -> reqwest::Result<Envelope<ListSchedulesResponse>> {
+        let url = self.base_url.join("/schedules").expect("Bad url!");
+        self.run(reqwest::Method::GET, url).await
+    }
+
+    async fn get_schedule(&self, id: &str) -> reqwest::Result<Envelope<ScheduleResponse>> {
+        let url = self
+            .base_url
+            .join(&format!("/schedules/{}", id))
+            .expect("Bad url!");
+
+        self.run(reqwest::Method::GET, url).await
+    }
+
+    async fn create_schedule(
+        &self,
+        body: CreateScheduleRequest,
+    ) -> reqwest::Result<Envelope<ScheduleResponse>> {
+        let url = self.base_url.join("/schedules").expect("Bad url!");
+        self.run_with_body(reqwest::Method::POST, url, body).await
+    }
+
+    async fn delete_schedule(&self, id: &str) -> reqwest::Result<Envelope<()>> {
+        let url = self
+            .base_url
+            .join(&format!("/schedules/{}", id))
+            .expect("Bad url!");
+
+        self.run(reqwest::Method::DELETE, url).await
+    }
+
     async fn version(&self) -> reqwest::Result<Envelope<VersionInformation>> {
         let url = self.base_url.join("/version").expect("Bad url!");
 
@@ -128,6 +128,7 @@ pub enum InvocationState {
     Running,
     Suspended,
     BackingOff,
+    Paused,
     Completed,
 }
 
@@ -141,6 +142,7 @@ impl FromStr for InvocationState {
             "running" => Self::Running,
             "suspended" => Self::Suspended,
             "backing-off" => Self::BackingOff,
+            "paused" => Self::Paused,
             "completed" => Self::Completed,
             _ => Self::Unknown,
         })
@@ -157,6 +159,7 @@ impl Display for InvocationState {
             InvocationState::Running => write!(f, "running"),
             InvocationState::Suspended => write!(f, "suspended"),
             InvocationState::BackingOff => write!(f, "backing-off"),
+            InvocationState::Paused => write!(f, "paused"),
             InvocationState::Completed => write!(f, "completed"),
         }
     }
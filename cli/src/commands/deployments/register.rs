@@ -10,6 +10,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::num::NonZeroUsize;
 use std::str::FromStr;
 
 use anyhow::Result;
@@ -58,6 +59,12 @@ pub struct Register {
     #[clap(long = "use-http1.1")]
     use_http_11: bool,
 
+    /// Maximum number of invocations executed concurrently against this deployment by each
+    /// invoker. Invocations over the limit stay queued. The limit is enforced per node, not
+    /// across the cluster.
+    #[clap(long)]
+    concurrency_limit: Option<NonZeroUsize>,
+
     /// The URL or ARN that Restate server needs to fetch service information from.
     ///
     /// The URL must be network-accessible from Restate server. In case of using
@@ -181,6 +188,7 @@ pub async fn run_register(State(env): State<CliEnv>, discover_opts: &Register) -
         DeploymentEndpoint::Uri(uri) => RegisterDeploymentRequest::Http {
             uri: uri.clone(),
             additional_headers: headers.clone().map(Into::into),
+            concurrency_limit: discover_opts.concurrency_limit,
             use_http_11: discover_opts.use_http_11,
             force,
             dry_run,
@@ -189,6 +197,7 @@ pub async fn run_register(State(env): State<CliEnv>, discover_opts: &Register) -
             arn: arn.to_string(),
             assume_role_arn: discover_opts.assume_role_arn.clone(),
             additional_headers: headers.clone().map(Into::into),
+            concurrency_limit: discover_opts.concurrency_limit,
             force,
             dry_run,
         },
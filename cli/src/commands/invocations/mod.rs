@@ -11,10 +11,14 @@
 mod cancel;
 mod describe;
 mod list;
+mod pause;
 mod purge;
+mod resume;
 
 use cling::prelude::*;
 
+use restate_types::identifiers::InvocationId;
+
 #[derive(Run, Subcommand, Clone)]
 pub enum Invocations {
     /// List invocations of a service
@@ -25,4 +29,26 @@ pub enum Invocations {
     Cancel(cancel::Cancel),
     /// Purge a completed invocation, or a set of invocations. This command affects only completed invocations.
     Purge(purge::Purge),
+    /// Pause a running invocation, or a set of invocations, until it is resumed
+    Pause(pause::Pause),
+    /// Resume a paused invocation, or a set of invocations
+    Resume(resume::Resume),
+}
+
+/// Builds the SQL filter matching the invocations of the given query, which is either an
+/// invocation id or a target string exact match or prefix.
+fn invocation_query_filter(query: &str) -> String {
+    let q = query.trim();
+    if let Ok(id) = q.parse::<InvocationId>() {
+        format!("id = '{}'", id)
+    } else {
+        match q.find('/').unwrap_or_default() {
+            0 => format!("target LIKE '{}/%'", q),
+            // If there's one slash, let's add the wildcard depending on the service type,
+            // so we discriminate correctly with serviceName/handlerName with workflowName/workflowKey
+            1 => format!("((target = '{}' AND target_service_ty = 'service') OR (target LIKE '{}/%' AND target_service_ty != 'service'))", q, q),
+            // Can only be exact match here
+            _ => format!("target LIKE '{}'", q),
+        }
+    }
 }
@@ -0,0 +1,81 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::{Context, Result};
+use cling::prelude::*;
+
+use restate_admin_rest_model::schedules::CreateScheduleRequest;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_println, c_success};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_create")]
+pub struct Create {
+    /// IANA name of the timezone used to evaluate the cron expression, e.g. Europe/Berlin.
+    /// Defaults to UTC.
+    #[clap(long)]
+    timezone: Option<String>,
+
+    /// JSON payload used as input of every invocation
+    #[clap(long)]
+    payload: Option<String>,
+
+    /// Schedule ID
+    schedule_id: String,
+
+    /// Cron expression, e.g. "0 2 * * *"
+    cron: String,
+
+    /// Handler to invoke, either `<service>/<handler>` or `<service>/<key>/<handler>`
+    target: String,
+}
+
+pub async fn run_create(State(env): State<CliEnv>, opts: &Create) -> Result<()> {
+    let payload = opts
+        .payload
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .context("The payload must be valid JSON")?;
+
+    let client = AdminClient::new(&env).await?;
+    if client
+        .get_schedule(&opts.schedule_id)
+        .await?
+        .success_or_error()
+        .is_ok()
+    {
+        confirm_or_exit(&format!(
+            "The schedule {} already exists, do you want to replace it?",
+            opts.schedule_id
+        ))?;
+    }
+
+    let schedule = client
+        .create_schedule(CreateScheduleRequest {
+            id: opts.schedule_id.clone(),
+            cron: opts.cron.clone(),
+            timezone: opts.timezone.clone(),
+            target: opts.target.clone(),
+            payload,
+        })
+        .await?
+        .into_body()
+        .await?;
+
+    c_println!("{}", super::schedule_kv_table(&schedule));
+    c_println!();
+    c_success!("Schedule {} was created", schedule.id);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+
+use restate_cli_util::ui::console::{confirm_or_exit, Styled};
+use restate_cli_util::ui::stylesheet::Style;
+use restate_cli_util::{c_println, c_success};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[clap(visible_alias = "rm")]
+#[cling(run = "run_delete")]
+pub struct Delete {
+    /// Schedule ID
+    schedule_id: String,
+}
+
+pub async fn run_delete(State(env): State<CliEnv>, opts: &Delete) -> Result<()> {
+    let client = AdminClient::new(&env).await?;
+    let schedule = client
+        .get_schedule(&opts.schedule_id)
+        .await?
+        .into_body()
+        .await?;
+
+    c_println!("{}", super::schedule_kv_table(&schedule));
+    c_println!("Invocations which have already been fired by the schedule are not affected.");
+    let prompt = format!(
+        "Are you sure you want to {} the schedule {}?",
+        Styled(Style::Danger, "delete"),
+        opts.schedule_id
+    );
+    confirm_or_exit(&prompt)?;
+
+    let _ = client
+        .delete_schedule(&opts.schedule_id)
+        .await?
+        .success_or_error()?;
+
+    c_println!();
+    c_success!("Schedule {} was deleted", opts.schedule_id);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_cli_util::ui::watcher::Watch;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_describe")]
+#[clap(visible_alias = "get")]
+pub struct Describe {
+    /// Schedule ID
+    schedule_id: String,
+
+    #[clap(flatten)]
+    watch: Watch,
+}
+
+pub async fn run_describe(State(env): State<CliEnv>, opts: &Describe) -> Result<()> {
+    opts.watch.run(|| describe(&env, opts)).await
+}
+
+async fn describe(env: &CliEnv, opts: &Describe) -> Result<()> {
+    let client = AdminClient::new(env).await?;
+    let schedule = client
+        .get_schedule(&opts.schedule_id)
+        .await?
+        .into_body()
+        .await?;
+
+    c_println!("{}", super::schedule_kv_table(&schedule));
+
+    Ok(())
+}
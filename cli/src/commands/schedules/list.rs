@@ -0,0 +1,58 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+use comfy_table::{Cell, Table};
+
+use restate_cli_util::ui::console::StyledTable;
+use restate_cli_util::ui::watcher::Watch;
+use restate_cli_util::{c_error, c_println};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[clap(visible_alias = "ls")]
+#[cling(run = "run_list")]
+pub struct List {
+    #[clap(flatten)]
+    watch: Watch,
+}
+
+pub async fn run_list(State(env): State<CliEnv>, opts: &List) -> Result<()> {
+    opts.watch.run(|| list(&env)).await
+}
+
+async fn list(env: &CliEnv) -> Result<()> {
+    let client = AdminClient::new(env).await?;
+    let mut schedules = client.get_schedules().await?.into_body().await?.schedules;
+
+    if schedules.is_empty() {
+        c_error!("No schedules were found! You can create one with 'restate schedules create'.");
+        return Ok(());
+    }
+    schedules.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+    let mut table = Table::new_styled();
+    table.set_styled_header(vec!["ID", "TARGET", "CRON", "TIMEZONE", "NEXT FIRE TIME"]);
+    for schedule in schedules {
+        table.add_row(vec![
+            Cell::new(schedule.id),
+            Cell::new(schedule.target),
+            Cell::new(schedule.cron),
+            Cell::new(schedule.timezone),
+            Cell::new(schedule.next_fire_time.unwrap_or_else(|| "-".to_owned())),
+        ]);
+    }
+    c_println!("{}", table);
+
+    Ok(())
+}
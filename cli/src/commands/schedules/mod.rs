@@ -0,0 +1,49 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod create;
+mod delete;
+mod describe;
+mod list;
+
+use cling::prelude::*;
+use comfy_table::Table;
+
+use restate_admin_rest_model::schedules::ScheduleResponse;
+use restate_cli_util::ui::console::StyledTable;
+
+#[derive(Run, Subcommand, Clone)]
+#[clap(visible_alias = "sc", alias = "schedule")]
+pub enum Schedules {
+    /// List the registered schedules
+    List(list::List),
+    /// Prints detailed information about a given schedule
+    Describe(describe::Describe),
+    /// Register a schedule, replacing any schedule with the same ID
+    Create(create::Create),
+    /// Remove a schedule
+    Delete(delete::Delete),
+}
+
+fn schedule_kv_table(schedule: &ScheduleResponse) -> Table {
+    let mut table = Table::new_styled();
+    table.add_kv_row("ID:", &schedule.id);
+    table.add_kv_row("Target:", &schedule.target);
+    table.add_kv_row("Cron:", &schedule.cron);
+    table.add_kv_row("Timezone:", &schedule.timezone);
+    table.add_kv_row(
+        "Next fire time:",
+        schedule.next_fire_time.as_deref().unwrap_or("-"),
+    );
+    if let Some(payload) = &schedule.payload {
+        table.add_kv_row("Payload:", payload);
+    }
+    table
+}
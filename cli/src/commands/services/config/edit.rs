@@ -95,6 +95,51 @@ fn write_out_edit_toml(w: &mut impl io::Write, service_type: ServiceType) -> Res
     writeln!(w, "# abort_timeout = \"10min\"")?;
     writeln!(w)?;
 
+    write_prefixed_lines(w, "# ", super::view::CONCURRENCY_LIMIT)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# concurrency_limit = 10")?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::JSON_SCHEMA_VALIDATION)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# json_schema_validation = true")?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::RETRY_POLICY)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# reset_handler_retry_policies = [\"otherHandler\"]")?;
+    writeln!(w, "#")?;
+    writeln!(w, "# [retry_policy]")?;
+    writeln!(w, "# on_max_attempts = \"pause\"")?;
+    writeln!(w, "# policy = {{ type = \"exponential\", initial-interval = \"100ms\", factor = 2.0, max-attempts = 5, max-interval = \"10s\" }}")?;
+    writeln!(w, "#")?;
+    writeln!(w, "# [handler_retry_policies.myHandler]")?;
+    writeln!(
+        w,
+        "# policy = {{ type = \"fixed-delay\", interval = \"1s\" }}"
+    )?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::INGRESS_AUTHORIZATION)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# [ingress_authorization]")?;
+    writeln!(w, "# allowed-principals = [\"billing\", \"reporting\"]")?;
+    writeln!(w, "#")?;
+    writeln!(w, "# [handler_ingress_authorizations.myHandler]")?;
+    writeln!(w, "# allowed-principals = [\"billing\"]")?;
+    writeln!(w)?;
+
+    write_prefixed_lines(w, "# ", super::view::RATE_LIMIT)?;
+    writeln!(w, "# Example:")?;
+    writeln!(w, "# [rate_limit]")?;
+    writeln!(w, "# requests-per-second = 100")?;
+    writeln!(w, "# burst = 200")?;
+    writeln!(w, "#")?;
+    writeln!(w, "# [handler_rate_limits.myHandler]")?;
+    writeln!(w, "# requests-per-second = 10")?;
+    writeln!(w, "# per-client = true")?;
+    writeln!(w)?;
+
     Ok(())
 }
 
@@ -18,6 +18,7 @@ use restate_admin_rest_model::services::ModifyServiceRequest;
 use restate_cli_util::c_println;
 use restate_cli_util::ui::console::{confirm_or_exit, StyledTable};
 use restate_serde_util::DurationString;
+use std::num::NonZeroUsize;
 
 pub(super) const DURATION_EDIT_DESCRIPTION: &str = "Can be configured using the humantime format (https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) or the ISO8601.";
 pub(super) const IDEMPOTENCY_RETENTION_EDIT_DESCRIPTION: &str = concatcp!(
@@ -56,6 +57,20 @@ pub struct Patch {
     #[clap(long, alias = "abort_retention", help = ABORT_TIMEOUT_EDIT_DESCRIPTION)]
     abort_timeout: Option<String>,
 
+    #[clap(long, alias = "concurrency_limit", help = super::view::CONCURRENCY_LIMIT)]
+    concurrency_limit: Option<NonZeroUsize>,
+
+    #[clap(long, alias = "json_schema_validation", help = super::view::JSON_SCHEMA_VALIDATION)]
+    json_schema_validation: Option<bool>,
+
+    /// Remove the retry policy of the service, falling back to the default retry policy
+    #[clap(long, alias = "reset_retry_policy")]
+    reset_retry_policy: bool,
+
+    /// Remove the retry policy of the given handler, falling back to the retry policy of the service
+    #[clap(long, alias = "reset_handler_retry_policy", value_name = "HANDLER")]
+    reset_handler_retry_policy: Vec<String>,
+
     /// Service name
     service: String,
 }
@@ -93,6 +108,17 @@ async fn patch(env: &CliEnv, opts: &Patch) -> Result<()> {
             .as_ref()
             .map(|s| DurationString::parse_duration(s).context("Cannot parse abort_timeout"))
             .transpose()?,
+        paused: None,
+        retry_policy: None,
+        handler_retry_policies: Default::default(),
+        reset_retry_policy: opts.reset_retry_policy,
+        reset_handler_retry_policies: opts.reset_handler_retry_policy.clone(),
+        ingress_authorization: None,
+        handler_ingress_authorizations: Default::default(),
+        rate_limit: None,
+        handler_rate_limits: Default::default(),
+        concurrency_limit: opts.concurrency_limit,
+        json_schema_validation: opts.json_schema_validation,
     };
 
     apply_service_configuration_patch(opts.service.clone(), admin_client, modify_request).await
@@ -109,6 +135,17 @@ pub(super) async fn apply_service_configuration_patch(
         && modify_request.idempotency_retention.is_none()
         && modify_request.inactivity_timeout.is_none()
         && modify_request.abort_timeout.is_none()
+        && modify_request.paused.is_none()
+        && modify_request.retry_policy.is_none()
+        && modify_request.handler_retry_policies.is_empty()
+        && !modify_request.reset_retry_policy
+        && modify_request.reset_handler_retry_policies.is_empty()
+        && modify_request.ingress_authorization.is_none()
+        && modify_request.handler_ingress_authorizations.is_empty()
+        && modify_request.rate_limit.is_none()
+        && modify_request.handler_rate_limits.is_empty()
+        && modify_request.concurrency_limit.is_none()
+        && modify_request.json_schema_validation.is_none()
     {
         c_println!("No changes requested");
         return Ok(());
@@ -140,6 +177,59 @@ pub(super) async fn apply_service_configuration_patch(
     if let Some(abort_timeout) = &modify_request.abort_timeout {
         table.add_kv_row("Abort timeout:", humantime::Duration::from(*abort_timeout));
     }
+    if let Some(paused) = &modify_request.paused {
+        table.add_kv_row("Paused:", paused);
+    }
+    if modify_request.reset_retry_policy {
+        table.add_kv_row("Retry policy:", "<reset>");
+    }
+    for handler_name in &modify_request.reset_handler_retry_policies {
+        table.add_kv_row(&format!("Retry policy of {handler_name}:"), "<reset>");
+    }
+    if let Some(retry_policy) = &modify_request.retry_policy {
+        table.add_kv_row(
+            "Retry policy:",
+            serde_json::to_string(retry_policy).context("Cannot serialize retry_policy")?,
+        );
+    }
+    for (handler_name, retry_policy) in &modify_request.handler_retry_policies {
+        table.add_kv_row(
+            &format!("Retry policy of {handler_name}:"),
+            serde_json::to_string(retry_policy).context("Cannot serialize retry_policy")?,
+        );
+    }
+    if let Some(ingress_authorization) = &modify_request.ingress_authorization {
+        table.add_kv_row(
+            "Ingress authorization:",
+            serde_json::to_string(ingress_authorization)
+                .context("Cannot serialize ingress_authorization")?,
+        );
+    }
+    for (handler_name, ingress_authorization) in &modify_request.handler_ingress_authorizations {
+        table.add_kv_row(
+            &format!("Ingress authorization of {handler_name}:"),
+            serde_json::to_string(ingress_authorization)
+                .context("Cannot serialize ingress_authorization")?,
+        );
+    }
+    if let Some(rate_limit) = &modify_request.rate_limit {
+        table.add_kv_row(
+            "Rate limit:",
+            serde_json::to_string(rate_limit).context("Cannot serialize rate_limit")?,
+        );
+    }
+    for (handler_name, rate_limit) in &modify_request.handler_rate_limits {
+        table.add_kv_row(
+            &format!("Rate limit of {handler_name}:"),
+            serde_json::to_string(rate_limit).context("Cannot serialize rate_limit")?,
+        );
+    }
+    if let Some(concurrency_limit) = &modify_request.concurrency_limit {
+        table.add_kv_row("Concurrency limit:", concurrency_limit);
+    }
+    if let Some(json_schema_validation) = &modify_request.json_schema_validation {
+        table.add_kv_row("JSON Schema validation:", json_schema_validation);
+    }
     c_println!("{table}");
     confirm_or_exit("Are you sure you want to apply these changes?")?;
 
@@ -55,6 +55,39 @@ pub(super) const ABORT_TIMEOUT: &str = indoc! {
 
     This overrides the default abort timeout set in invoker options."
 };
+pub(super) const RETRY_POLICY: &str = indoc! {
+    "The retry policy applied to the invocations of this service, and what to do
+    with an invocation once the max attempts are exhausted: kill it, pause it, or
+    kill it and record it in the dead letter table.
+    Handlers can override the retry policy of the service. Use reset_retry_policy
+    and reset_handler_retry_policies to remove them again.
+
+    This overrides the default retry policy set in invoker options."
+};
+pub(super) const INGRESS_AUTHORIZATION: &str = indoc! {
+    "The principals allowed to invoke this service through the ingress, once
+    authenticated with one of the methods configured in the ingress options.
+    Handlers can override the ingress authorization of the service.
+    If unset, every authenticated principal can invoke the service."
+};
+pub(super) const RATE_LIMIT: &str = indoc! {
+    "The token bucket rate limit applied by the ingress to the requests invoking
+    this service. Requests exceeding the limit are rejected with 429 Too Many Requests.
+    If per-client is true, the limit applies separately to each authenticated principal,
+    or to each client as identified by the header configured in the ingress options.
+    Handlers can override the rate limit of the service."
+};
+pub(super) const CONCURRENCY_LIMIT: &str = indoc! {
+    "The maximum number of invocations of this service executed concurrently by each invoker.
+    Invocations over the limit stay queued until one of the running invocations completes.
+    The limit is enforced per node, not across the cluster.
+    Deployments can additionally limit the invocations executed concurrently against them."
+};
+pub(super) const JSON_SCHEMA_VALIDATION: &str = indoc! {
+    "Whether the ingress validates the request bodies against the input JSON Schema
+    of the invoked handler. Requests which don't match the schema are rejected
+    with 400 Bad Request, before any invocation is created."
+};
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_view")]
@@ -129,5 +162,87 @@ async fn view(env: &CliEnv, opts: &View) -> Result<()> {
     c_tip!("{}", ABORT_TIMEOUT);
     c_println!();
 
+    let mut table = Table::new_styled();
+    table.add_kv_row(
+        "Retry policy:",
+        service
+            .retry_policy
+            .as_ref()
+            .map(|p| serde_json::to_string(p).expect("retry policy must be serializable"))
+            .unwrap_or("<DEFAULT>".to_string()),
+    );
+    for handler in &service.handlers {
+        if let Some(retry_policy) = &handler.retry_policy {
+            table.add_kv_row(
+                &format!("Retry policy of {}:", handler.name),
+                serde_json::to_string(retry_policy).expect("retry policy must be serializable"),
+            );
+        }
+    }
+    c_println!("{table}");
+    c_tip!("{}", RETRY_POLICY);
+    c_println!();
+
+    let mut table = Table::new_styled();
+    table.add_kv_row(
+        "Ingress authorization:",
+        service
+            .ingress_authorization
+            .as_ref()
+            .map(|a| serde_json::to_string(a).expect("ingress authorization must be serializable"))
+            .unwrap_or("<ANY AUTHENTICATED>".to_string()),
+    );
+    for handler in &service.handlers {
+        if let Some(ingress_authorization) = &handler.ingress_authorization {
+            table.add_kv_row(
+                &format!("Ingress authorization of {}:", handler.name),
+                serde_json::to_string(ingress_authorization)
+                    .expect("ingress authorization must be serializable"),
+            );
+        }
+    }
+    c_println!("{table}");
+    c_tip!("{}", INGRESS_AUTHORIZATION);
+    c_println!();
+
+    let mut table = Table::new_styled();
+    table.add_kv_row(
+        "Rate limit:",
+        service
+            .rate_limit
+            .as_ref()
+            .map(|l| serde_json::to_string(l).expect("rate limit must be serializable"))
+            .unwrap_or("<UNLIMITED>".to_string()),
+    );
+    for handler in &service.handlers {
+        if let Some(rate_limit) = &handler.rate_limit {
+            table.add_kv_row(
+                &format!("Rate limit of {}:", handler.name),
+                serde_json::to_string(rate_limit).expect("rate limit must be serializable"),
+            );
+        }
+    }
+    c_println!("{table}");
+    c_tip!("{}", RATE_LIMIT);
+    c_println!();
+
+    let mut table = Table::new_styled();
+    table.add_kv_row(
+        "Concurrency limit:",
+        service
+            .concurrency_limit
+            .map(|l| l.to_string())
+            .unwrap_or("<UNLIMITED>".to_string()),
+    );
+    c_println!("{table}");
+    c_tip!("{}", CONCURRENCY_LIMIT);
+    c_println!();
+
+    let mut table = Table::new_styled();
+    table.add_kv_row("JSON Schema validation:", service.json_schema_validation);
+    c_println!("{table}");
+    c_tip!("{}", JSON_SCHEMA_VALIDATION);
+    c_println!();
+
     Ok(())
 }
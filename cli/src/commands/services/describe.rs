@@ -50,6 +50,7 @@ async fn describe(env: &CliEnv, opts: &Describe) -> Result<()> {
     table.add_kv_row("Service type:", &format!("{:?}", service.ty));
     table.add_kv_row("Revision:", service.revision);
     table.add_kv_row("Public:", service.public);
+    table.add_kv_row("Paused:", service.paused);
     table.add_kv_row("Deployment ID:", service.deployment_id);
 
     let deployment = client
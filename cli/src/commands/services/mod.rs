@@ -11,6 +11,8 @@
 mod config;
 mod describe;
 mod list;
+mod pause;
+mod resume;
 mod status;
 
 use cling::prelude::*;
@@ -24,6 +26,10 @@ pub enum Services {
     Describe(describe::Describe),
     /// Prints activity information about a given service (and method)
     Status(status::Status),
+    /// Pause a service, holding back its new invocations until it is resumed
+    Pause(pause::Pause),
+    /// Resume a paused service
+    Resume(resume::Resume),
     /// Configure a service
     #[clap(name = "config", alias = "conf")]
     #[clap(subcommand)]
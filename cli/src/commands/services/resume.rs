@@ -0,0 +1,70 @@
+// Copyright (c) 2023 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+
+use restate_admin_rest_model::services::ModifyServiceRequest;
+use restate_cli_util::ui::console::{confirm_or_exit, Styled};
+use restate_cli_util::ui::stylesheet::Style;
+use restate_cli_util::{c_println, c_success};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_resume")]
+pub struct Resume {
+    /// Service name
+    service: String,
+}
+
+pub async fn run_resume(State(env): State<CliEnv>, opts: &Resume) -> Result<()> {
+    let admin_client = AdminClient::new(&env).await?;
+
+    c_println!("The invocations held back while the service was paused will be started.");
+    let prompt = format!(
+        "Are you sure you want to {} the service {}?",
+        Styled(Style::Success, "resume"),
+        opts.service
+    );
+    confirm_or_exit(&prompt)?;
+
+    let _ = admin_client
+        .patch_service(
+            &opts.service,
+            ModifyServiceRequest {
+                public: None,
+                idempotency_retention: None,
+                workflow_completion_retention: None,
+                inactivity_timeout: None,
+                abort_timeout: None,
+                paused: Some(false),
+                retry_policy: None,
+                handler_retry_policies: Default::default(),
+                reset_retry_policy: false,
+                reset_handler_retry_policies: Default::default(),
+                ingress_authorization: None,
+                handler_ingress_authorizations: Default::default(),
+                rate_limit: None,
+                handler_rate_limits: Default::default(),
+                concurrency_limit: None,
+                json_schema_validation: None,
+            },
+        )
+        .await?
+        .into_body()
+        .await?;
+
+    c_println!();
+    c_success!("Service {} was resumed", opts.service);
+
+    Ok(())
+}
@@ -100,6 +100,7 @@ pub fn add_deployment_to_kv_table(deployment: &Deployment, table: &mut Table) {
                 protocol_type,
                 http_version: _,
                 additional_headers,
+                concurrency_limit: _,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -123,6 +124,7 @@ pub fn add_deployment_to_kv_table(deployment: &Deployment, table: &mut Table) {
                 arn,
                 assume_role_arn,
                 additional_headers,
+                concurrency_limit: _,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -148,6 +150,19 @@ pub fn add_deployment_to_kv_table(deployment: &Deployment, table: &mut Table) {
         additional_headers.into();
 
     table.add_kv_row("Created at:", created_at);
+    let concurrency_limit = match deployment {
+        Deployment::Http {
+            concurrency_limit, ..
+        }
+        | Deployment::Lambda {
+            concurrency_limit, ..
+        } => concurrency_limit,
+    };
+    table.add_kv_row_if(
+        || concurrency_limit.is_some(),
+        "Concurrency Limit:",
+        || concurrency_limit.unwrap(),
+    );
     for (header, value) in additional_headers.iter() {
         table.add_kv_row(
             "Deployment Additional Header:",
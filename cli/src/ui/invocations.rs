@@ -112,6 +112,7 @@ pub fn invocation_status_style(status: InvocationState) -> Style {
         InvocationState::Running => DStyle::new().green(),
         InvocationState::Suspended => DStyle::new().dim(),
         InvocationState::BackingOff => DStyle::new().red(),
+        InvocationState::Paused => DStyle::new().yellow(),
         InvocationState::Completed => DStyle::new().blue(),
     }
 }
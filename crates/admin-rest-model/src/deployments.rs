@@ -18,6 +18,7 @@ use restate_types::schema::deployment::{DeploymentMetadata, ProtocolType};
 use restate_types::schema::service::ServiceMetadata;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::num::NonZeroUsize;
 use std::time::SystemTime;
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -36,6 +37,9 @@ pub enum Deployment {
         #[serde(skip_serializing_if = "SerdeableHeaderHashMap::is_empty")]
         #[serde(default)]
         additional_headers: SerdeableHeaderHashMap,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
         #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
         #[cfg_attr(feature = "schema", schemars(with = "String"))]
         created_at: humantime::Timestamp,
@@ -50,6 +54,9 @@ pub enum Deployment {
         #[serde(skip_serializing_if = "SerdeableHeaderHashMap::is_empty")]
         #[serde(default)]
         additional_headers: SerdeableHeaderHashMap,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
         #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
         #[cfg_attr(feature = "schema", schemars(with = "String"))]
         created_at: humantime::Timestamp,
@@ -72,6 +79,9 @@ enum DeploymentShadow {
         #[serde(skip_serializing_if = "SerdeableHeaderHashMap::is_empty")]
         #[serde(default)]
         additional_headers: SerdeableHeaderHashMap,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
         #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
         created_at: humantime::Timestamp,
         min_protocol_version: i32,
@@ -85,6 +95,9 @@ enum DeploymentShadow {
         #[serde(skip_serializing_if = "SerdeableHeaderHashMap::is_empty")]
         #[serde(default)]
         additional_headers: SerdeableHeaderHashMap,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
         #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
         created_at: humantime::Timestamp,
         min_protocol_version: i32,
@@ -100,6 +113,7 @@ impl From<DeploymentShadow> for Deployment {
                 protocol_type,
                 http_version,
                 additional_headers,
+                concurrency_limit,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -109,6 +123,7 @@ impl From<DeploymentShadow> for Deployment {
                 http_version: http_version
                     .unwrap_or_else(|| DeploymentType::backfill_http_version(protocol_type)),
                 additional_headers,
+                concurrency_limit,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -117,6 +132,7 @@ impl From<DeploymentShadow> for Deployment {
                 arn,
                 assume_role_arn,
                 additional_headers,
+                concurrency_limit,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -124,6 +140,7 @@ impl From<DeploymentShadow> for Deployment {
                 arn,
                 assume_role_arn,
                 additional_headers,
+                concurrency_limit,
                 created_at,
                 min_protocol_version,
                 max_protocol_version,
@@ -170,6 +187,7 @@ impl From<DeploymentMetadata> for Deployment {
                 protocol_type,
                 http_version,
                 additional_headers: value.delivery_options.additional_headers.into(),
+                concurrency_limit: value.delivery_options.concurrency_limit,
                 created_at: SystemTime::from(value.created_at).into(),
                 min_protocol_version: *value.supported_protocol_versions.start(),
                 max_protocol_version: *value.supported_protocol_versions.end(),
@@ -181,6 +199,7 @@ impl From<DeploymentMetadata> for Deployment {
                 arn,
                 assume_role_arn: assume_role_arn.map(Into::into),
                 additional_headers: value.delivery_options.additional_headers.into(),
+                concurrency_limit: value.delivery_options.concurrency_limit,
                 created_at: SystemTime::from(value.created_at).into(),
                 min_protocol_version: *value.supported_protocol_versions.start(),
                 max_protocol_version: *value.supported_protocol_versions.end(),
@@ -209,6 +228,13 @@ pub enum RegisterDeploymentRequest {
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
 
+        /// # Concurrency limit
+        ///
+        /// Maximum number of invocations executed concurrently against this deployment by
+        /// each worker node. Invocations over the limit stay queued.
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
+
         /// # Use http1.1
         ///
         /// If `true`, discovery will be attempted using a client that defaults to HTTP1.1
@@ -253,6 +279,14 @@ pub enum RegisterDeploymentRequest {
         /// Additional headers added to the discover/invoke requests to the deployment.
         ///
         additional_headers: Option<SerdeableHeaderHashMap>,
+
+        /// # Concurrency limit
+        ///
+        /// Maximum number of invocations executed concurrently against this deployment by
+        /// each worker node. Invocations over the limit stay queued.
+        #[serde(default)]
+        concurrency_limit: Option<NonZeroUsize>,
+
         /// # Force
         ///
         /// If `true`, it will override, if existing, any deployment using the same `uri`.
@@ -0,0 +1,73 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+
+use http::Uri;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use restate_types::schema::kafka_sinks::KafkaSink;
+
+#[serde_as]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateKafkaSinkRequest {
+    /// # Kafka sink ID
+    ///
+    /// Unique identifier of the Kafka sink. It can contain alphanumeric characters, `-`, `_` and `.`.
+    pub id: String,
+    /// # Source
+    ///
+    /// Handlers whose outputs are published. Accepted forms:
+    ///
+    /// * `service://<service_name>`, e.g. `service://Orders`, to publish the outputs of all the handlers of the service
+    /// * `service://<service_name>/<handler_name>`, e.g. `service://Orders/checkout`
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub source: Uri,
+    /// # Sink
+    ///
+    /// Kafka topic the outputs are published to, in the form `kafka://<cluster_name>/<topic_name>`,
+    /// e.g. `kafka://my-cluster/orders`. The cluster must be configured in the ingress options.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    pub sink: Uri,
+    /// # Options
+    ///
+    /// Additional options to apply to the Kafka producer, in the same form of rdkafka.
+    pub options: Option<HashMap<String, String>>,
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct KafkaSinkResponse {
+    pub id: String,
+    pub source: String,
+    pub sink: String,
+    pub options: HashMap<String, String>,
+}
+
+impl From<KafkaSink> for KafkaSinkResponse {
+    fn from(value: KafkaSink) -> Self {
+        Self {
+            id: value.id().to_owned(),
+            source: value.source().to_string(),
+            sink: value.destination(),
+            options: value.options().clone(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListKafkaSinksResponse {
+    pub kafka_sinks: Vec<KafkaSinkResponse>,
+}
@@ -10,6 +10,8 @@
 
 pub mod deployments;
 pub mod handlers;
+pub mod kafka_sinks;
+pub mod schedules;
 pub mod services;
 pub mod subscriptions;
 pub mod version;
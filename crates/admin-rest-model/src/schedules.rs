@@ -0,0 +1,87 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use restate_types::schema::schedules::Schedule;
+use restate_types::time::MillisSinceEpoch;
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateScheduleRequest {
+    /// # Schedule ID
+    ///
+    /// Unique identifier of the schedule. It can contain alphanumeric characters, `-`, `_` and `.`.
+    pub id: String,
+    /// # Cron expression
+    ///
+    /// Either the standard five fields form `<minute> <hour> <day of month> <month> <day of week>`,
+    /// e.g. `0 2 * * *`, or the same form with a leading seconds field.
+    pub cron: String,
+    /// # Timezone
+    ///
+    /// IANA name of the timezone used to evaluate the cron expression, e.g. `Europe/Berlin`.
+    /// Defaults to `UTC`.
+    pub timezone: Option<String>,
+    /// # Target
+    ///
+    /// Handler to invoke. Accepted forms:
+    ///
+    /// * `<service_name>/<handler_name>`, e.g. `Billing/run`
+    /// * `<service_name>/<key>/<handler_name>` for virtual objects and workflows, e.g. `Counter/my-key/reset`
+    pub target: String,
+    /// # Payload
+    ///
+    /// JSON value used as input of every invocation.
+    pub payload: Option<serde_json::Value>,
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScheduleResponse {
+    pub id: String,
+    pub cron: String,
+    pub timezone: String,
+    pub target: String,
+    pub payload: Option<serde_json::Value>,
+    /// # Next fire time
+    ///
+    /// RFC 3339 timestamp of the next occurrence, if any.
+    pub next_fire_time: Option<String>,
+}
+
+impl From<Schedule> for ScheduleResponse {
+    fn from(value: Schedule) -> Self {
+        let next_fire_time = value
+            .next_fire_time(MillisSinceEpoch::now())
+            .map(|next_fire_time| {
+                humantime::format_rfc3339_seconds(
+                    SystemTime::UNIX_EPOCH + Duration::from_millis(next_fire_time.as_u64()),
+                )
+                .to_string()
+            });
+        Self {
+            id: value.id().to_owned(),
+            cron: value.cron().to_owned(),
+            timezone: value.timezone().to_owned(),
+            target: value.target().to_string(),
+            payload: serde_json::from_slice(value.payload()).ok(),
+            next_fire_time,
+        }
+    }
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
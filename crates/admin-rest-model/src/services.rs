@@ -11,9 +11,12 @@
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::time::Duration;
 
-use restate_types::schema::service::ServiceMetadata;
+use restate_types::schema::service::{
+    IngressAuthorization, InvocationRetryPolicy, RateLimit, ServiceMetadata,
+};
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Debug, Serialize, Deserialize)]
@@ -93,6 +96,94 @@ pub struct ModifyServiceRequest {
     )]
     #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
     pub abort_timeout: Option<Duration>,
+
+    /// # Paused
+    ///
+    /// If true, the service is paused: new invocations are held back and the inboxes of its
+    /// virtual objects are not processed. If false, the service is resumed and the held
+    /// invocations are started.
+    #[serde(default)]
+    pub paused: Option<bool>,
+
+    /// # Retry policy
+    ///
+    /// Retry policy applied to the invocations of this service, including what to do once
+    /// the max attempts are exhausted.
+    ///
+    /// This overrides the default retry policy set in invoker options.
+    #[serde(default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+
+    /// # Handler retry policies
+    ///
+    /// Retry policies applied to the invocations of specific handlers of this service,
+    /// keyed by handler name.
+    ///
+    /// These override the retry policy of the service.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub handler_retry_policies: HashMap<String, InvocationRetryPolicy>,
+
+    /// # Reset retry policy
+    ///
+    /// If true, the retry policy of this service is removed, and the invocations of this
+    /// service fall back to the default retry policy set in invoker options.
+    ///
+    /// This is applied before `retry_policy`.
+    #[serde(default)]
+    pub reset_retry_policy: bool,
+
+    /// # Reset handler retry policies
+    ///
+    /// Names of the handlers of this service whose retry policy is removed. Their invocations
+    /// fall back to the retry policy of the service.
+    ///
+    /// This is applied before `handler_retry_policies`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reset_handler_retry_policies: Vec<String>,
+
+    /// # Ingress authorization
+    ///
+    /// Principals allowed to invoke this service through the ingress.
+    #[serde(default)]
+    pub ingress_authorization: Option<IngressAuthorization>,
+
+    /// # Handler ingress authorizations
+    ///
+    /// Principals allowed to invoke specific handlers of this service through the ingress,
+    /// keyed by handler name.
+    ///
+    /// These override the ingress authorization of the service.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub handler_ingress_authorizations: HashMap<String, IngressAuthorization>,
+
+    /// # Rate limit
+    ///
+    /// Rate limit applied by the ingress to the requests invoking this service.
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+
+    /// # Handler rate limits
+    ///
+    /// Rate limits applied by the ingress to the requests invoking specific handlers of this
+    /// service, keyed by handler name.
+    ///
+    /// These override the rate limit of the service.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub handler_rate_limits: HashMap<String, RateLimit>,
+
+    /// # Concurrency limit
+    ///
+    /// Maximum number of invocations of this service executed concurrently by each worker
+    /// node. Invocations over the limit stay queued.
+    #[serde(default)]
+    pub concurrency_limit: Option<NonZeroUsize>,
+
+    /// # JSON Schema validation
+    ///
+    /// If true, the ingress validates the request bodies against the input JSON Schema of the
+    /// invoked handler, and rejects the requests which don't match it with 400 Bad Request.
+    #[serde(default)]
+    pub json_schema_validation: Option<bool>,
 }
 
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -8,7 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use http::Uri;
 use serde::{Deserialize, Serialize};
@@ -39,7 +39,28 @@ pub struct CreateSubscriptionRequest {
     pub sink: Uri,
     /// # Options
     ///
-    /// Additional options to apply to the subscription.
+    /// Additional options to apply to the subscription. Options starting with `restate.` configure
+    /// Restate, all the other options are passed to the Kafka consumer:
+    ///
+    /// * `restate.headers.allow`: comma separated list of the Kafka record headers to forward as
+    ///   invocation headers, e.g. `tenant-id,schema-*`. Defaults to all headers.
+    /// * `restate.headers.deny`: comma separated list of the Kafka record headers not to forward.
+    /// * `restate.value.format`: decodes the record values to JSON, the format is one of `avro`,
+    ///   `protobuf` or `json-schema`. The values must be framed with the schema registry wire format.
+    /// * `restate.schema.registry.url`: URL of the schema registry resolving the schemas.
+    /// * `restate.schema.directory`: directory containing the schemas, named `<schema id>.avsc`,
+    ///   `<schema id>.proto` or `<schema id>.json`. Used when no registry URL is configured.
+    /// * `restate.value.protobuf.message`: fully qualified name of the Protobuf message, overriding
+    ///   the message indexes of the records.
+    /// * `restate.on-decode-error`: either `stop` (default), to stop consuming at the record which
+    ///   cannot be decoded, or `skip`.
+    /// * `restate.filter`: only the records matching the filter are dispatched, the others are
+    ///   skipped. The filter is made of clauses joined by `&&`, in the form `<selector>`,
+    ///   `<selector> == <JSON literal>` or `<selector> != <JSON literal>`, e.g.
+    ///   `header.event-type == "OrderCreated" && value.region != "eu"`. Selectors are `key`,
+    ///   `header.<name>` or `value.<field path>`, where the path selects a field of the JSON value.
+    /// * `restate.key`: selector of the virtual object or workflow key, e.g. `value.customer.id`.
+    ///   Defaults to the record key.
     pub options: Option<HashMap<String, String>>,
 }
 
@@ -50,6 +71,11 @@ pub struct SubscriptionResponse {
     pub source: String,
     pub sink: String,
     pub options: HashMap<String, String>,
+    /// # Paused
+    ///
+    /// Whether the consumption of the subscription is paused.
+    #[serde(default)]
+    pub paused: bool,
 }
 
 impl From<Subscription> for SubscriptionResponse {
@@ -59,6 +85,7 @@ impl From<Subscription> for SubscriptionResponse {
             source: value.source().to_string(),
             sink: value.sink().to_string(),
             options: value.metadata().clone(),
+            paused: value.is_paused(),
         }
     }
 }
@@ -75,3 +102,42 @@ pub struct ListSubscriptionsParams {
 pub struct ListSubscriptionsResponse {
     pub subscriptions: Vec<SubscriptionResponse>,
 }
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateSubscriptionRequest {
+    /// # Paused
+    ///
+    /// Pause or resume the consumption of the subscription. Records are consumed from the
+    /// committed offsets once resumed.
+    pub paused: Option<bool>,
+}
+
+/// # Offsets reset
+///
+/// Position the offsets of the subscription are reset to.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "to", rename_all = "snake_case")]
+pub enum ResetSubscriptionOffsetsRequest {
+    /// Beginning of every partition.
+    Earliest,
+    /// End of every partition, skipping the records not yet consumed.
+    Latest,
+    /// First record of every partition with a timestamp greater than or equal to the given one.
+    Timestamp {
+        /// Milliseconds since the Unix epoch.
+        timestamp: i64,
+    },
+    /// Given offset of every listed partition.
+    Offsets { offsets: HashMap<i32, i64> },
+}
+
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetSubscriptionOffsetsResponse {
+    /// # Offsets
+    ///
+    /// New offset of every reset partition.
+    pub offsets: BTreeMap<i32, i64>,
+}
@@ -330,6 +330,8 @@ fn try_provisioning(
             node_set_selector_hints.preferred_sequencer(&log_id),
         )
         .map(LogletConfiguration::Replicated),
+        // tiered storage segments are read-only and only created by offloading sealed segments
+        ProviderKind::TieredStorage => None,
     }
 }
 
@@ -382,10 +384,14 @@ fn build_new_replicated_loglet_configuration(
             write_set: None,
         }),
 
-        Err(NodeSelectionError::InsufficientWriteableNodes) => {
+        Err(
+            err @ (NodeSelectionError::InsufficientWriteableNodes
+            | NodeSelectionError::InsufficientFailureDomains(_)),
+        ) => {
             debug!(
                 ?loglet_id,
-                "Insufficient writeable nodes to select new nodeset for replicated loglet"
+                %err,
+                "Cannot select new nodeset for replicated loglet"
             );
 
             previous_configuration.map(|p| {
@@ -548,6 +554,9 @@ impl TryFrom<&LogletConfig> for LogletConfiguration {
                     .map(LogletConfiguration::Replicated)
                     .map_err(Into::into)
             }
+            ProviderKind::TieredStorage => {
+                Err("tiered-storage segments are read-only and can't be the tail of a log".into())
+            }
         }
     }
 }
@@ -1290,6 +1299,7 @@ pub mod tests {
     use std::num::NonZeroU8;
 
     use enumset::{enum_set, EnumSet};
+    use restate_types::locality::NodeLocation;
     use restate_types::nodes_config::{
         LogServerConfig, NodeConfig, NodesConfiguration, Role, StorageState,
     };
@@ -1467,6 +1477,7 @@ pub mod tests {
             format!("https://node-{}", id).parse().unwrap(),
             roles,
             LogServerConfig { storage_state },
+            NodeLocation::default(),
         )
     }
 
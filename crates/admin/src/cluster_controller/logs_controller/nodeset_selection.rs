@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use std::cmp::max;
+use std::collections::HashSet;
 
 use rand::prelude::IteratorRandom;
 use rand::Rng;
@@ -63,7 +64,11 @@ impl<'a> NodeSetSelector<'a> {
 
         // todo: we should check the current segment for sealability, otherwise we might propose
         //  reconfiguration when we are virtually certain to get stuck!
-        candidates.len() >= nodeset_min_size && candidates.len() > current_writable.len()
+        candidates.len() >= nodeset_min_size
+            && candidates.len() > current_writable.len()
+            && self
+                .check_failure_domains(&candidates.0, replication_property)
+                .is_ok()
     }
 
     /// Picks a set of storage nodes for a replicated loglet out of the available pool. Only alive,
@@ -76,11 +81,6 @@ impl<'a> NodeSetSelector<'a> {
         rng: &mut R,
         preferred_nodes: &NodeSet,
     ) -> Result<NodeSet, NodeSelectionError> {
-        if replication_property.at_greatest_scope().0 != &LocationScope::Node {
-            // todo: add support for other location scopes
-            unimplemented!("only node-scoped replication is currently supported");
-        }
-
         // Only consider alive, writable storage nodes.
         let candidates = WritableNodeSet::from_cluster(self.cluster_state, self.nodes_config);
 
@@ -127,7 +127,6 @@ impl<'a> NodeSetSelector<'a> {
             }
         };
 
-        // todo: implement location scope-aware selection
         if nodeset.len() < nodeset_min_size {
             trace!(
                 "Failed to place replicated loglet: insufficient writeable nodes to meet minimum size requirement {} < {}",
@@ -137,8 +136,50 @@ impl<'a> NodeSetSelector<'a> {
             return Err(NodeSelectionError::InsufficientWriteableNodes);
         }
 
+        self.check_failure_domains(&nodeset, replication_property)?;
+
         Ok(nodeset)
     }
+
+    /// Checks that the nodes span enough failure domains for every zone- or region-scoped
+    /// replication requirement. Like the nodeset size, the number of domains at a scope with
+    /// replication factor f+1 must be at least 2f+1, so that the loglet remains writeable after
+    /// losing f domains. Nodes without a location at the scope don't contribute to it.
+    fn check_failure_domains(
+        &self,
+        nodes: &NodeSet,
+        replication_property: &ReplicationProperty,
+    ) -> Result<(), NodeSelectionError> {
+        for (scope, replication_factor) in replication_property.iter() {
+            if *scope == LocationScope::Node {
+                continue;
+            }
+
+            let domains: HashSet<_> = nodes
+                .iter()
+                .filter_map(|node_id| self.nodes_config.find_node_by_id(*node_id).ok())
+                .filter_map(|node_config| node_config.location.domain(*scope))
+                .collect();
+
+            let min_domains = fault_tolerant_size(*replication_factor);
+            if domains.len() < min_domains {
+                trace!(
+                    "Failed to place replicated loglet: insufficient {} failure domains to meet minimum requirement {} < {}",
+                    scope,
+                    domains.len(),
+                    min_domains,
+                );
+                return Err(NodeSelectionError::InsufficientFailureDomains(*scope));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// ReplicationFactor(f+1) implies a minimum of 2f+1 nodes, or failure domains.
+fn fault_tolerant_size(replication_factor: u8) -> usize {
+    (usize::from(replication_factor) - 1) * 2 + 1
 }
 
 fn nodeset_size_range(
@@ -156,7 +197,7 @@ fn nodeset_size_range(
         "The replication factor implies a cluster size that exceeds the maximum supported size"
     );
 
-    let optimal_fault_tolerant_nodeset_size = (usize::from(min_copies) - 1) * 2 + 1;
+    let optimal_fault_tolerant_nodeset_size = fault_tolerant_size(min_copies);
     assert!(
         optimal_fault_tolerant_nodeset_size >= usize::from(min_copies),
         "The calculated minimum nodeset size can not be less than the replication factor"
@@ -199,6 +240,8 @@ pub enum NodeSetSelectionStrategy {
 pub enum NodeSelectionError {
     #[error("Insufficient writeable nodes in the nodeset")]
     InsufficientWriteableNodes,
+    #[error("Insufficient failure domains at {0} scope in the nodeset")]
+    InsufficientFailureDomains(LocationScope),
 }
 
 /// Utility for filtering only nodes suitable to be a nodeset member based on cluster configuration.
@@ -267,26 +310,61 @@ pub mod tests {
     use crate::cluster_controller::logs_controller::tests::{node, MockNodes};
     use crate::cluster_controller::observed_cluster_state::ObservedClusterState;
 
+    /// With zone-scoped replication factor of 2, the nodeset must span at least 3 zones so that it
+    /// can lose a whole zone and still place copies in two distinct zones.
     #[test]
-    #[should_panic(
-        expected = "not implemented: only node-scoped replication is currently supported"
-    )]
-    fn test_select_log_servers_rejects_unsupported_replication_scope() {
+    fn test_select_log_servers_respects_zone_replication() {
+        let mut nodes = MockNodes::builder()
+            .with_mixed_server_nodes([1, 2, 3, 4])
+            .build();
+        for (id, location) in [(1, "region.az1"), (2, "region.az1"), (3, "region.az2")] {
+            let mut node_config = nodes
+                .nodes_config
+                .find_node_by_id(PlainNodeId::from(id))
+                .unwrap()
+                .clone();
+            node_config.location = location.parse().unwrap();
+            nodes.nodes_config.upsert_node(node_config);
+        }
+
         let replication =
-            ReplicationProperty::with_scope(LocationScope::Zone, 1.try_into().unwrap());
+            ReplicationProperty::with_scope(LocationScope::Zone, 2.try_into().unwrap());
 
-        let nodes_config = NodesConfiguration::default();
-        let observed_state = ObservedClusterState::default();
+        // node 4 has no location, only two zones are available
+        let selector = NodeSetSelector::new(&nodes.nodes_config, &nodes.observed_state);
+        assert!(!selector.can_improve(
+            &NodeSet::empty(),
+            NodeSetSelectionStrategy::StrictFaultTolerantGreedy,
+            &replication,
+        ));
+        let selection = selector.select(
+            NodeSetSelectionStrategy::StrictFaultTolerantGreedy,
+            &replication,
+            &mut thread_rng(),
+            &NodeSet::empty(),
+        );
+        assert_eq!(
+            selection,
+            Err(NodeSelectionError::InsufficientFailureDomains(
+                LocationScope::Zone
+            ))
+        );
 
-        let preferred_nodes = NodeSet::empty();
-        NodeSetSelector::new(&nodes_config, &observed_state)
-            .select(
-                NodeSetSelectionStrategy::StrictFaultTolerantGreedy,
-                &replication,
-                &mut thread_rng(),
-                &preferred_nodes,
-            )
-            .unwrap(); // panics
+        let mut node_config = nodes
+            .nodes_config
+            .find_node_by_id(PlainNodeId::from(4))
+            .unwrap()
+            .clone();
+        node_config.location = "region.az3".parse().unwrap();
+        nodes.nodes_config.upsert_node(node_config);
+
+        let selection = NodeSetSelector::new(&nodes.nodes_config, &nodes.observed_state).select(
+            NodeSetSelectionStrategy::StrictFaultTolerantGreedy,
+            &replication,
+            &mut thread_rng(),
+            &NodeSet::empty(),
+        );
+        assert_eq!(selection.unwrap(), NodeSet::from([1, 2, 3, 4]));
     }
 
     #[test]
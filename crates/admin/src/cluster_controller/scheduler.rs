@@ -575,6 +575,7 @@ mod tests {
     use restate_types::cluster_controller::{ReplicationStrategy, SchedulingPlan};
     use restate_types::config::Configuration;
     use restate_types::identifiers::PartitionId;
+    use restate_types::locality::NodeLocation;
     use restate_types::metadata_store::keys::SCHEDULING_PLAN_KEY;
     use restate_types::net::codec::WireDecode;
     use restate_types::net::partition_processor_manager::{ControlProcessors, ProcessorCommand};
@@ -676,6 +677,7 @@ mod tests {
                 AdvertisedAddress::Http(Uri::default()),
                 Role::Worker.into(),
                 LogServerConfig::default(),
+                NodeLocation::default(),
             );
             nodes_config.upsert_node(node_config);
         }
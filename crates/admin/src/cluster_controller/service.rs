@@ -292,6 +292,18 @@ impl<T: TransportConnect> Service<T> {
         let mut find_logs_tail_interval =
             time::interval(configuration.admin.log_tail_update_interval.into());
         find_logs_tail_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // retired loglets are released once they are older than the trim delay, checking at the
+        // same pace releases them at most twice the delay after they have been offloaded
+        let mut release_retired_loglets_interval = time::interval(
+            Duration::from(
+                configuration
+                    .bifrost
+                    .tiered_storage
+                    .offloaded_loglet_trim_delay,
+            )
+            .max(Duration::from_secs(1)),
+        );
+        release_retired_loglets_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         self.health_status.update(AdminStatus::Ready);
 
@@ -311,6 +323,12 @@ impl<T: TransportConnect> Service<T> {
                         warn!("Could not trim the logs. This can lead to increased disk usage: {err}");
                     }
                 }
+                _ = release_retired_loglets_interval.tick() => {
+                    let grace_period = self.configuration.live_load().bifrost.tiered_storage.offloaded_loglet_trim_delay.into();
+                    if let Err(err) = bifrost_admin.release_retired_loglets(grace_period).await {
+                        warn!("Could not release the records of offloaded loglets. This can lead to increased disk usage: {err}");
+                    }
+                }
                 Ok(cluster_state) = cluster_state_watcher.next_cluster_state() => {
                     let nodes_config = &nodes_config.live_load();
                     observed_cluster_state.update(&cluster_state);
@@ -394,11 +412,21 @@ impl<T: TransportConnect> Service<T> {
         bifrost_admin: BifrostAdmin<'_>,
     ) -> Result<(), restate_bifrost::Error> {
         let cluster_state = self.cluster_state_refresher.get_cluster_state();
+        // Only partition snapshots uploaded to a repository shared by all nodes can be used to
+        // restore the partition stores of other nodes
+        let has_snapshot_repository = self
+            .configuration
+            .pinned()
+            .worker
+            .snapshots
+            .destination
+            .is_some();
 
         let mut persisted_lsns_per_partition: BTreeMap<
             PartitionId,
             BTreeMap<GenerationalNodeId, Lsn>,
         > = BTreeMap::default();
+        let mut archived_lsn_per_partition: BTreeMap<PartitionId, Lsn> = BTreeMap::default();
 
         for node_state in cluster_state.nodes.values() {
             match node_state {
@@ -415,6 +443,14 @@ impl<T: TransportConnect> Service<T> {
                             .entry(*partition_id)
                             .or_default()
                             .insert(*generational_node_id, lsn);
+
+                        if let Some(archived_lsn) = partition_processor_status.last_archived_log_lsn
+                        {
+                            let latest_archived_lsn = archived_lsn_per_partition
+                                .entry(*partition_id)
+                                .or_insert(archived_lsn);
+                            *latest_archived_lsn = (*latest_archived_lsn).max(archived_lsn);
+                        }
                     }
                 }
                 NodeState::Dead(_) => {
@@ -426,23 +462,36 @@ impl<T: TransportConnect> Service<T> {
         for (partition_id, persisted_lsns) in persisted_lsns_per_partition.into_iter() {
             let log_id = LogId::from(partition_id);
 
-            // todo: Remove once Restate nodes can share partition processor snapshots
-            // only try to trim if we know about the persisted lsns of all known nodes; otherwise we
-            // risk that a node cannot fully replay the log; this assumes that no new nodes join the
-            // cluster after the first trimming has happened
-            if persisted_lsns.len() >= cluster_state.nodes.len() {
-                let min_persisted_lsn = persisted_lsns.into_values().min().unwrap_or(Lsn::INVALID);
-                // trim point is before the oldest record
-                let current_trim_point = bifrost_admin.get_trim_point(log_id).await?;
-
-                if min_persisted_lsn >= current_trim_point + self.log_trim_threshold {
-                    debug!(
-                    "Automatic trim log '{log_id}' for all records before='{min_persisted_lsn}'"
-                );
-                    bifrost_admin.trim(log_id, min_persisted_lsn).await?
+            // lagging or new partition processors restore their partition store from the latest
+            // snapshot in the repository, so we only need to retain the records after its
+            // applied lsn
+            let archived_lsn = archived_lsn_per_partition
+                .get(&partition_id)
+                .filter(|_| has_snapshot_repository)
+                .copied();
+            // only trim up to the persisted lsns if we know about the persisted lsns of all known
+            // nodes; otherwise we risk that a node cannot fully replay the log; this assumes that
+            // no new nodes join the cluster after the first trimming has happened
+            let min_persisted_lsn = (persisted_lsns.len() >= cluster_state.nodes.len())
+                .then(|| persisted_lsns.into_values().min().unwrap_or(Lsn::INVALID));
+
+            let trim_point = match (archived_lsn, min_persisted_lsn) {
+                (Some(archived_lsn), Some(min_persisted_lsn)) => {
+                    archived_lsn.max(min_persisted_lsn)
                 }
-            } else {
-                warn!("Stop automatically trimming log '{log_id}' because not all nodes are running a partition processor applying this log.");
+                (Some(lsn), None) | (None, Some(lsn)) => lsn,
+                (None, None) => {
+                    warn!("Stop automatically trimming log '{log_id}' because not all nodes are running a partition processor applying this log and there is no partition snapshot in a shared repository to restore from.");
+                    continue;
+                }
+            };
+
+            // trim point is before the oldest record
+            let current_trim_point = bifrost_admin.get_trim_point(log_id).await?;
+
+            if trim_point >= current_trim_point + self.log_trim_threshold {
+                debug!("Automatic trim log '{log_id}' for all records before='{trim_point}'");
+                bifrost_admin.trim(log_id, trim_point).await?
             }
         }
 
@@ -697,6 +746,7 @@ mod tests {
     use restate_types::health::HealthStatus;
     use restate_types::identifiers::PartitionId;
     use restate_types::live::Live;
+    use restate_types::locality::NodeLocation;
     use restate_types::logs::{LogId, Lsn, SequenceNumber};
     use restate_types::net::partition_processor_manager::ControlProcessors;
     use restate_types::net::AdvertisedAddress;
@@ -757,6 +807,8 @@ mod tests {
 
     struct NodeStateHandler {
         persisted_lsn: Arc<AtomicU64>,
+        // the applied lsn of the latest partition snapshot, 0 if there is no snapshot
+        archived_lsn: Arc<AtomicU64>,
         // set of node ids for which the handler won't send a response to the caller, this allows to simulate
         // dead nodes
         block_list: BTreeSet<GenerationalNodeId>,
@@ -770,8 +822,10 @@ mod tests {
                 return;
             }
 
+            let archived_lsn = self.archived_lsn.load(Ordering::Relaxed);
             let partition_processor_status = PartitionProcessorStatus {
                 last_persisted_log_lsn: Some(Lsn::from(self.persisted_lsn.load(Ordering::Relaxed))),
+                last_archived_log_lsn: (archived_lsn > 0).then(|| Lsn::from(archived_lsn)),
                 ..PartitionProcessorStatus::new()
             };
 
@@ -802,6 +856,7 @@ mod tests {
         let persisted_lsn = Arc::new(AtomicU64::new(0));
         let get_node_state_handler = Arc::new(NodeStateHandler {
             persisted_lsn: Arc::clone(&persisted_lsn),
+            archived_lsn: Arc::default(),
             block_list: BTreeSet::new(),
         });
 
@@ -885,6 +940,7 @@ mod tests {
         let persisted_lsn = Arc::new(AtomicU64::new(0));
         let get_node_state_handler = Arc::new(NodeStateHandler {
             persisted_lsn: Arc::clone(&persisted_lsn),
+            archived_lsn: Arc::default(),
             block_list: BTreeSet::new(),
         });
         let (node_env, bifrost) = create_test_env(config, |builder| {
@@ -974,6 +1030,7 @@ mod tests {
 
             let get_node_state_handler = NodeStateHandler {
                 persisted_lsn: Arc::clone(&persisted_lsn),
+                archived_lsn: Arc::default(),
                 block_list: black_list,
             };
 
@@ -1004,6 +1061,128 @@ mod tests {
         Ok(())
     }
 
+    #[test(tokio::test(start_paused = true))]
+    async fn trim_up_to_snapshot_if_not_all_nodes_report_persisted_lsn() -> anyhow::Result<()> {
+        const LOG_ID: LogId = LogId::new(0);
+
+        let mut admin_options = AdminOptions::default();
+        admin_options.log_trim_threshold = 0;
+        let interval_duration = Duration::from_secs(10);
+        admin_options.log_trim_interval = Some(interval_duration.into());
+        let mut config = Configuration {
+            admin: admin_options,
+            ..Default::default()
+        };
+        config.worker.snapshots.destination = Some("file:///mnt/snapshots".to_owned());
+
+        let persisted_lsn = Arc::new(AtomicU64::new(0));
+        let archived_lsn = Arc::new(AtomicU64::new(0));
+
+        let (node_env, bifrost) = create_test_env(config, |builder| {
+            let black_list = builder
+                .nodes_config
+                .iter()
+                .next()
+                .map(|(_, node_config)| node_config.current_generation)
+                .into_iter()
+                .collect();
+
+            let get_node_state_handler = NodeStateHandler {
+                persisted_lsn: Arc::clone(&persisted_lsn),
+                archived_lsn: Arc::clone(&archived_lsn),
+                block_list: black_list,
+            };
+
+            builder.add_message_handler(get_node_state_handler)
+        })
+        .await?;
+
+        node_env
+            .tc
+            .run_in_scope("test", None, async move {
+                let mut appender = bifrost.create_appender(LOG_ID)?;
+                for i in 1..=5 {
+                    let lsn = appender.append(format!("record{}", i)).await?;
+                    assert_eq!(Lsn::from(i), lsn);
+                }
+
+                persisted_lsn.store(5, Ordering::Relaxed);
+
+                tokio::time::sleep(interval_duration * 10).await;
+                assert_eq!(Lsn::INVALID, bifrost.get_trim_point(LOG_ID).await?);
+
+                // the unresponsive node can restore the partition from the snapshot
+                archived_lsn.store(3, Ordering::Relaxed);
+
+                tokio::time::sleep(interval_duration * 10).await;
+                assert_eq!(Lsn::from(3), bifrost.get_trim_point(LOG_ID).await?);
+
+                Ok::<(), anyhow::Error>(())
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[test(tokio::test(start_paused = true))]
+    async fn do_not_trim_up_to_snapshot_without_snapshot_repository() -> anyhow::Result<()> {
+        const LOG_ID: LogId = LogId::new(0);
+
+        let mut admin_options = AdminOptions::default();
+        admin_options.log_trim_threshold = 0;
+        let interval_duration = Duration::from_secs(10);
+        admin_options.log_trim_interval = Some(interval_duration.into());
+        let config = Configuration {
+            admin: admin_options,
+            ..Default::default()
+        };
+
+        let persisted_lsn = Arc::new(AtomicU64::new(0));
+        let archived_lsn = Arc::new(AtomicU64::new(0));
+
+        let (node_env, bifrost) = create_test_env(config, |builder| {
+            let black_list = builder
+                .nodes_config
+                .iter()
+                .next()
+                .map(|(_, node_config)| node_config.current_generation)
+                .into_iter()
+                .collect();
+
+            let get_node_state_handler = NodeStateHandler {
+                persisted_lsn: Arc::clone(&persisted_lsn),
+                archived_lsn: Arc::clone(&archived_lsn),
+                block_list: black_list,
+            };
+
+            builder.add_message_handler(get_node_state_handler)
+        })
+        .await?;
+
+        node_env
+            .tc
+            .run_in_scope("test", None, async move {
+                let mut appender = bifrost.create_appender(LOG_ID)?;
+                for i in 1..=5 {
+                    let lsn = appender.append(format!("record{}", i)).await?;
+                    assert_eq!(Lsn::from(i), lsn);
+                }
+
+                persisted_lsn.store(5, Ordering::Relaxed);
+                // the snapshot is local to the node which created it, the unresponsive node
+                // cannot restore the partition from it
+                archived_lsn.store(3, Ordering::Relaxed);
+
+                tokio::time::sleep(interval_duration * 10).await;
+                assert_eq!(Lsn::INVALID, bifrost.get_trim_point(LOG_ID).await?);
+
+                Ok::<(), anyhow::Error>(())
+            })
+            .await?;
+
+        Ok(())
+    }
+
     async fn create_test_env<F>(
         config: Configuration,
         mut modify_builder: F,
@@ -1032,6 +1211,7 @@ mod tests {
             AdvertisedAddress::Uds("foobar".into()),
             Role::Worker.into(),
             LogServerConfig::default(),
+            NodeLocation::default(),
         ));
         nodes_config.upsert_node(NodeConfig::new(
             "node-2".to_owned(),
@@ -1039,6 +1219,7 @@ mod tests {
             AdvertisedAddress::Uds("bar".into()),
             Role::Worker.into(),
             LogServerConfig::default(),
+            NodeLocation::default(),
         ));
         let builder = modify_builder(builder.set_nodes_config(nodes_config));
 
@@ -45,10 +45,11 @@ pub async fn create_deployment<V>(
     State(state): State<AdminServiceState<V>>,
     #[request_body(required = true)] Json(payload): Json<RegisterDeploymentRequest>,
 ) -> Result<impl IntoResponse, MetaApiError> {
-    let (discover_endpoint, force, dry_run) = match payload {
+    let (discover_endpoint, concurrency_limit, force, dry_run) = match payload {
         RegisterDeploymentRequest::Http {
             uri,
             additional_headers,
+            concurrency_limit,
             use_http_11,
             force,
             dry_run,
@@ -81,6 +82,7 @@ pub async fn create_deployment<V>(
                     ),
                     additional_headers.unwrap_or_default().into(),
                 ),
+                concurrency_limit,
                 force,
                 dry_run,
             )
@@ -89,6 +91,7 @@ pub async fn create_deployment<V>(
             arn,
             assume_role_arn,
             additional_headers,
+            concurrency_limit,
             force,
             dry_run,
         } => (
@@ -101,6 +104,7 @@ pub async fn create_deployment<V>(
                 ),
                 additional_headers.unwrap_or_default().into(),
             ),
+            concurrency_limit,
             force,
             dry_run,
         ),
@@ -116,7 +120,7 @@ pub async fn create_deployment<V>(
 
     let (id, services) = state
         .schema_registry
-        .register_deployment(discover_endpoint, force, apply_mode)
+        .register_deployment(discover_endpoint, concurrency_limit, force, apply_mode)
         .await
         .inspect_err(|e| warn_it!(e))?;
 
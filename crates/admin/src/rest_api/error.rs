@@ -9,7 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use crate::schema_registry::error::{
-    DeploymentError, SchemaError, SchemaRegistryError, ServiceError,
+    DeploymentError, SchemaError, SchemaRegistryError, ServiceError, SubscriptionError,
 };
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -42,6 +42,10 @@ pub enum MetaApiError {
     },
     #[error("The requested subscription '{0}' does not exist")]
     SubscriptionNotFound(SubscriptionId),
+    #[error("The requested schedule '{0}' does not exist")]
+    ScheduleNotFound(String),
+    #[error("The requested Kafka sink '{0}' does not exist")]
+    KafkaSinkNotFound(String),
     #[error("Cannot {0} for service type {1}")]
     UnsupportedOperation(&'static str, ServiceType),
     #[error(transparent)]
@@ -70,13 +74,16 @@ impl IntoResponse for MetaApiError {
             MetaApiError::ServiceNotFound(_)
             | MetaApiError::HandlerNotFound { .. }
             | MetaApiError::DeploymentNotFound(_)
-            | MetaApiError::SubscriptionNotFound(_) => StatusCode::NOT_FOUND,
+            | MetaApiError::SubscriptionNotFound(_)
+            | MetaApiError::ScheduleNotFound(_)
+            | MetaApiError::KafkaSinkNotFound(_) => StatusCode::NOT_FOUND,
             MetaApiError::InvalidField(_, _) | MetaApiError::UnsupportedOperation(_, _) => {
                 StatusCode::BAD_REQUEST
             }
             MetaApiError::Schema(schema_error) => match schema_error {
                 SchemaError::NotFound(_) => StatusCode::NOT_FOUND,
                 SchemaError::Override(_)
+                | SchemaError::Subscription(SubscriptionError::NotPaused(_))
                 | SchemaError::Service(ServiceError::DifferentType { .. })
                 | SchemaError::Service(ServiceError::RemovedHandlers { .. })
                 | SchemaError::Deployment(DeploymentError::IncorrectId { .. }) => {
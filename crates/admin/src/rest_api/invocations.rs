@@ -17,7 +17,10 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use okapi_operation::*;
 use restate_types::identifiers::{InvocationId, WithPartitionKey};
-use restate_types::invocation::{InvocationTermination, PurgeInvocationRequest};
+use restate_types::invocation::{
+    DeadLetterRequest, InvocationPause, InvocationRetry, InvocationTermination,
+    PurgeInvocationRequest,
+};
 use restate_wal_protocol::{append_envelope_to_bifrost, Command, Envelope};
 use serde::Deserialize;
 use tracing::warn;
@@ -92,6 +95,251 @@ pub async fn delete_invocation<V>(
         DeletionMode::Purge => Command::PurgeInvocation(PurgeInvocationRequest { invocation_id }),
     };
 
+    append_invocation_command(&state, invocation_id, cmd, "invocation termination").await
+}
+
+/// Retry an invocation now
+#[openapi(
+    summary = "Retry an invocation now",
+    description = "Retry the given invocation immediately, skipping the remaining backoff \
+    of its current retry. This has no effect if the invocation is not waiting for a retry.",
+    operation_id = "retry_now_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn retry_now_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::RetryInvocation(InvocationRetry::retry_now(invocation_id)),
+        "invocation retry",
+    )
+    .await
+}
+
+/// Restart an invocation
+#[openapi(
+    summary = "Restart an invocation",
+    description = "Restart the given invocation from scratch. The journal is truncated back to \
+    the input entry and the invocation is re-invoked with the same input. State changes and \
+    messages sent by the previous attempts are not rolled back. An invocation which has \
+    journal entries still awaiting their completion, e.g. an ongoing call or sleep, is not restarted.",
+    operation_id = "restart_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn restart_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::RetryInvocation(InvocationRetry::restart(invocation_id)),
+        "invocation restart",
+    )
+    .await
+}
+
+/// Pause an invocation
+#[openapi(
+    summary = "Pause an invocation",
+    description = "Pause the given invocation. A running attempt is aborted without counting as a failed \
+    attempt, and the invocation is not invoked again until it is resumed. Completions arriving \
+    while the invocation is paused are stored in its journal.",
+    operation_id = "pause_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn pause_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::PauseInvocation(InvocationPause::pause(invocation_id)),
+        "invocation pause",
+    )
+    .await
+}
+
+/// Resume an invocation
+#[openapi(
+    summary = "Resume an invocation",
+    description = "Resume the given paused invocation. This has no effect if the invocation is not paused.",
+    operation_id = "resume_invocation",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Invocation identifier.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn resume_invocation<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::PauseInvocation(InvocationPause::resume(invocation_id)),
+        "invocation resume",
+    )
+    .await
+}
+
+/// Replay a dead lettered invocation
+#[openapi(
+    summary = "Replay a dead lettered invocation",
+    description = "Replay the given invocation from the dead letter table. The invocation is \
+    re-submitted with its original target, input and headers under a new invocation id, and removed \
+    from the dead letter table. Workflow runs are replayed under their original invocation id.",
+    operation_id = "replay_dead_letter",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Identifier of the dead lettered invocation.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn replay_dead_letter<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::DeadLetter(DeadLetterRequest::replay(invocation_id)),
+        "dead letter replay",
+    )
+    .await
+}
+
+/// Discard a dead lettered invocation
+#[openapi(
+    summary = "Discard a dead lettered invocation",
+    description = "Remove the given invocation from the dead letter table without replaying it.",
+    operation_id = "discard_dead_letter",
+    tags = "invocation",
+    parameters(path(
+        name = "invocation_id",
+        description = "Identifier of the dead lettered invocation.",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn discard_dead_letter<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| MetaApiError::InvalidField("invocation_id", e.to_string()))?;
+
+    append_invocation_command(
+        &state,
+        invocation_id,
+        Command::DeadLetter(DeadLetterRequest::discard(invocation_id)),
+        "dead letter discard",
+    )
+    .await
+}
+
+async fn append_invocation_command<V>(
+    state: &AdminServiceState<V>,
+    invocation_id: InvocationId,
+    cmd: Command,
+    command_description: &str,
+) -> Result<StatusCode, MetaApiError> {
     let partition_key = invocation_id.partition_key();
 
     let result = append_envelope_to_bifrost(
@@ -101,10 +349,10 @@ pub async fn delete_invocation<V>(
     .await;
 
     if let Err(err) = result {
-        warn!("Could not append invocation termination command to Bifrost: {err}");
-        Err(MetaApiError::Internal(
-            "Failed sending invocation termination to the cluster.".to_owned(),
-        ))
+        warn!("Could not append {command_description} command to Bifrost: {err}");
+        Err(MetaApiError::Internal(format!(
+            "Failed sending {command_description} to the cluster."
+        )))
     } else {
         Ok(StatusCode::ACCEPTED)
     }
@@ -0,0 +1,178 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::create_envelope_header;
+use super::error::*;
+use crate::state::AdminServiceState;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{http, Json};
+use okapi_operation::*;
+use restate_admin_rest_model::kafka_sinks::*;
+use restate_core::metadata;
+use restate_errors::warn_it;
+use restate_types::schema::kafka_sinks::KafkaSinkRequest;
+use restate_wal_protocol::{append_envelope_to_bifrost, Command, Envelope};
+use tracing::warn;
+
+/// Create Kafka sink.
+#[openapi(
+    summary = "Create Kafka sink",
+    description = "Create a Kafka sink publishing the outputs of the completed invocations of a service to a Kafka topic. Records are produced once the completion is committed, using the invocation key as record key.",
+    operation_id = "create_kafka_sink",
+    tags = "kafka_sink",
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "201",
+            description = "Created",
+            content = "Json<KafkaSinkResponse>",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn create_kafka_sink<V>(
+    State(state): State<AdminServiceState<V>>,
+    #[request_body(required = true)] Json(CreateKafkaSinkRequest {
+        id,
+        source,
+        sink,
+        options,
+    }): Json<CreateKafkaSinkRequest>,
+) -> Result<impl axum::response::IntoResponse, MetaApiError> {
+    let kafka_sink = state
+        .schema_registry
+        .create_kafka_sink(id, source, sink, options.unwrap_or_default())
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    send_kafka_sink_request(&state, KafkaSinkRequest::register(&kafka_sink)).await;
+
+    Ok((
+        StatusCode::CREATED,
+        [(
+            http::header::LOCATION,
+            format!("/kafka-sinks/{}", kafka_sink.id()),
+        )],
+        Json(KafkaSinkResponse::from(kafka_sink)),
+    ))
+}
+
+/// Get Kafka sink.
+#[openapi(
+    summary = "Get Kafka sink",
+    description = "Get Kafka sink",
+    operation_id = "get_kafka_sink",
+    tags = "kafka_sink",
+    parameters(path(
+        name = "kafka_sink",
+        description = "Kafka sink identifier",
+        schema = "std::string::String"
+    ))
+)]
+pub async fn get_kafka_sink<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(kafka_sink_id): Path<String>,
+) -> Result<Json<KafkaSinkResponse>, MetaApiError> {
+    let kafka_sink = state
+        .schema_registry
+        .get_kafka_sink(&kafka_sink_id)
+        .ok_or_else(|| MetaApiError::KafkaSinkNotFound(kafka_sink_id))?;
+
+    Ok(KafkaSinkResponse::from(kafka_sink).into())
+}
+
+/// List Kafka sinks.
+#[openapi(
+    summary = "List Kafka sinks",
+    description = "List all Kafka sinks.",
+    operation_id = "list_kafka_sinks",
+    tags = "kafka_sink"
+)]
+pub async fn list_kafka_sinks<V>(
+    State(state): State<AdminServiceState<V>>,
+) -> Json<ListKafkaSinksResponse> {
+    ListKafkaSinksResponse {
+        kafka_sinks: state
+            .schema_registry
+            .list_kafka_sinks()
+            .into_iter()
+            .map(KafkaSinkResponse::from)
+            .collect(),
+    }
+    .into()
+}
+
+/// Delete Kafka sink.
+#[openapi(
+    summary = "Delete Kafka sink",
+    description = "Delete Kafka sink. Outputs of invocations completed before the deletion may still be published.",
+    operation_id = "delete_kafka_sink",
+    tags = "kafka_sink",
+    parameters(path(
+        name = "kafka_sink",
+        description = "Kafka sink identifier",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn delete_kafka_sink<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(kafka_sink_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let kafka_sink = state
+        .schema_registry
+        .delete_kafka_sink(kafka_sink_id)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    send_kafka_sink_request(&state, KafkaSinkRequest::remove(kafka_sink.id().to_owned())).await;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Outputs are published by the partition processor completing the invocation, which doesn't
+/// have access to the schemas, hence every partition is told about the sinks via its log. This is
+/// best-effort: the schema is the source of truth and the partition leaders reconcile their sinks
+/// with it.
+async fn send_kafka_sink_request<V>(
+    state: &AdminServiceState<V>,
+    kafka_sink_request: KafkaSinkRequest,
+) {
+    let partition_table = metadata().partition_table_snapshot();
+
+    for (_, partition) in partition_table.partitions() {
+        let result = append_envelope_to_bifrost(
+            &state.bifrost,
+            Arc::new(Envelope::new(
+                create_envelope_header(*partition.key_range.start()),
+                Command::KafkaSink(kafka_sink_request.clone()),
+            )),
+        )
+        .await;
+
+        if let Err(err) = result {
+            warn!(
+                "Could not append Kafka sink command to Bifrost, the partition leader will \
+                 reconcile it from the schema: {err}"
+            );
+        }
+    }
+}
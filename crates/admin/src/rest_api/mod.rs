@@ -15,6 +15,8 @@ mod error;
 mod handlers;
 mod health;
 mod invocations;
+mod kafka_sinks;
+mod schedules;
 mod services;
 mod subscriptions;
 mod version;
@@ -74,6 +76,30 @@ where
             "/invocations/:invocation_id",
             delete(openapi_handler!(invocations::delete_invocation)),
         )
+        .route(
+            "/invocations/:invocation_id/retry-now",
+            patch(openapi_handler!(invocations::retry_now_invocation)),
+        )
+        .route(
+            "/invocations/:invocation_id/restart",
+            patch(openapi_handler!(invocations::restart_invocation)),
+        )
+        .route(
+            "/invocations/:invocation_id/pause",
+            patch(openapi_handler!(invocations::pause_invocation)),
+        )
+        .route(
+            "/invocations/:invocation_id/resume",
+            patch(openapi_handler!(invocations::resume_invocation)),
+        )
+        .route(
+            "/dead-letters/:invocation_id",
+            delete(openapi_handler!(invocations::discard_dead_letter)),
+        )
+        .route(
+            "/dead-letters/:invocation_id/replay",
+            patch(openapi_handler!(invocations::replay_dead_letter)),
+        )
         .route(
             "/subscriptions",
             post(openapi_handler!(subscriptions::create_subscription)),
@@ -90,6 +116,46 @@ where
             "/subscriptions/:subscription",
             delete(openapi_handler!(subscriptions::delete_subscription)),
         )
+        .route(
+            "/subscriptions/:subscription",
+            patch(openapi_handler!(subscriptions::modify_subscription)),
+        )
+        .route(
+            "/subscriptions/:subscription/reset-offsets",
+            post(openapi_handler!(subscriptions::reset_subscription_offsets)),
+        )
+        .route(
+            "/schedules",
+            post(openapi_handler!(schedules::create_schedule)),
+        )
+        .route(
+            "/schedules",
+            get(openapi_handler!(schedules::list_schedules)),
+        )
+        .route(
+            "/schedules/:schedule",
+            get(openapi_handler!(schedules::get_schedule)),
+        )
+        .route(
+            "/schedules/:schedule",
+            delete(openapi_handler!(schedules::delete_schedule)),
+        )
+        .route(
+            "/kafka-sinks",
+            post(openapi_handler!(kafka_sinks::create_kafka_sink)),
+        )
+        .route(
+            "/kafka-sinks",
+            get(openapi_handler!(kafka_sinks::list_kafka_sinks)),
+        )
+        .route(
+            "/kafka-sinks/:kafka_sink",
+            get(openapi_handler!(kafka_sinks::get_kafka_sink)),
+        )
+        .route(
+            "/kafka-sinks/:kafka_sink",
+            delete(openapi_handler!(kafka_sinks::delete_kafka_sink)),
+        )
         .route("/health", get(openapi_handler!(health::health)))
         .route("/version", get(openapi_handler!(version::version)))
         .finish_openapi("/openapi", "Admin API", env!("CARGO_PKG_VERSION"))
@@ -0,0 +1,191 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::create_envelope_header;
+use super::error::*;
+use crate::state::AdminServiceState;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{http, Json};
+use bytes::Bytes;
+use okapi_operation::*;
+use restate_admin_rest_model::schedules::*;
+use restate_errors::warn_it;
+use restate_types::schema::schedules::{ScheduleRequest, DEFAULT_TIMEZONE};
+use restate_types::time::MillisSinceEpoch;
+use restate_wal_protocol::{append_envelope_to_bifrost, Command, Envelope};
+use tracing::warn;
+
+/// Create schedule.
+#[openapi(
+    summary = "Create schedule",
+    description = "Create a schedule invoking a handler according to a cron expression. At most one invocation of a schedule is in flight: occurrences firing while the previous invocation is still running are skipped.",
+    operation_id = "create_schedule",
+    tags = "schedule",
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "201",
+            description = "Created",
+            content = "Json<ScheduleResponse>",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn create_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    #[request_body(required = true)] Json(CreateScheduleRequest {
+        id,
+        cron,
+        timezone,
+        target,
+        payload,
+    }): Json<CreateScheduleRequest>,
+) -> Result<impl axum::response::IntoResponse, MetaApiError> {
+    let payload = payload
+        .map(|payload| serde_json::to_vec(&payload).map(Bytes::from))
+        .transpose()
+        .map_err(|e| MetaApiError::InvalidField("payload", e.to_string()))?
+        .unwrap_or_default();
+
+    let schedule = state
+        .schema_registry
+        .create_schedule(
+            id,
+            cron,
+            timezone.unwrap_or_else(|| DEFAULT_TIMEZONE.to_owned()),
+            target,
+            payload,
+        )
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    // Cron expressions without further occurrences never need to be fired
+    if let Some(first_fire_time) = schedule.next_fire_time(MillisSinceEpoch::now()) {
+        send_schedule_request(
+            &state,
+            ScheduleRequest::create(schedule.clone(), first_fire_time),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        [(
+            http::header::LOCATION,
+            format!("/schedules/{}", schedule.id()),
+        )],
+        Json(ScheduleResponse::from(schedule)),
+    ))
+}
+
+/// Get schedule.
+#[openapi(
+    summary = "Get schedule",
+    description = "Get schedule",
+    operation_id = "get_schedule",
+    tags = "schedule",
+    parameters(path(
+        name = "schedule",
+        description = "Schedule identifier",
+        schema = "std::string::String"
+    ))
+)]
+pub async fn get_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(schedule_id): Path<String>,
+) -> Result<Json<ScheduleResponse>, MetaApiError> {
+    let schedule = state
+        .schema_registry
+        .get_schedule(&schedule_id)
+        .ok_or_else(|| MetaApiError::ScheduleNotFound(schedule_id))?;
+
+    Ok(ScheduleResponse::from(schedule).into())
+}
+
+/// List schedules.
+#[openapi(
+    summary = "List schedules",
+    description = "List all schedules.",
+    operation_id = "list_schedules",
+    tags = "schedule"
+)]
+pub async fn list_schedules<V>(
+    State(state): State<AdminServiceState<V>>,
+) -> Json<ListSchedulesResponse> {
+    ListSchedulesResponse {
+        schedules: state
+            .schema_registry
+            .list_schedules()
+            .into_iter()
+            .map(ScheduleResponse::from)
+            .collect(),
+    }
+    .into()
+}
+
+/// Delete schedule.
+#[openapi(
+    summary = "Delete schedule",
+    description = "Delete schedule. Invocations which are already running are not affected.",
+    operation_id = "delete_schedule",
+    tags = "schedule",
+    parameters(path(
+        name = "schedule",
+        description = "Schedule identifier",
+        schema = "std::string::String"
+    )),
+    responses(
+        ignore_return_type = true,
+        response(
+            status = "202",
+            description = "Accepted",
+            content = "okapi_operation::Empty",
+        ),
+        from_type = "MetaApiError",
+    )
+)]
+pub async fn delete_schedule<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(schedule_id): Path<String>,
+) -> Result<StatusCode, MetaApiError> {
+    let schedule = state
+        .schema_registry
+        .delete_schedule(schedule_id)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    send_schedule_request(&state, ScheduleRequest::delete(&schedule)).await;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Schedules are fired by the partition processor owning them, which doesn't have access to the
+/// schemas, hence it is told about the schedule changes via its log. This is best-effort: the
+/// schema is the source of truth and the partition leaders reconcile their schedules with it.
+async fn send_schedule_request<V>(state: &AdminServiceState<V>, schedule_request: ScheduleRequest) {
+    let result = append_envelope_to_bifrost(
+        &state.bifrost,
+        Arc::new(Envelope::new(
+            create_envelope_header(schedule_request.partition_key),
+            Command::Schedule(schedule_request),
+        )),
+    )
+    .await;
+
+    if let Err(err) = result {
+        warn!(
+            "Could not append schedule command to Bifrost, the partition leader will reconcile \
+             it from the schema: {err}"
+        );
+    }
+}
@@ -21,8 +21,10 @@ use http::StatusCode;
 use okapi_operation::*;
 use restate_admin_rest_model::services::ListServicesResponse;
 use restate_admin_rest_model::services::*;
+use restate_core::metadata;
 use restate_errors::warn_it;
 use restate_types::identifiers::{ServiceId, WithPartitionKey};
+use restate_types::invocation::ServicePause;
 use restate_types::schema::service::ServiceMetadata;
 use restate_types::state_mut::ExternalStateMutation;
 use restate_wal_protocol::{append_envelope_to_bifrost, Command, Envelope};
@@ -87,6 +89,17 @@ pub async fn modify_service<V>(
         workflow_completion_retention,
         inactivity_timeout,
         abort_timeout,
+        paused,
+        retry_policy,
+        handler_retry_policies,
+        reset_retry_policy,
+        reset_handler_retry_policies,
+        ingress_authorization,
+        handler_ingress_authorizations,
+        rate_limit,
+        handler_rate_limits,
+        concurrency_limit,
+        json_schema_validation,
     }): Json<ModifyServiceRequest>,
 ) -> Result<Json<ServiceMetadata>, MetaApiError> {
     let mut modify_request = vec![];
@@ -109,6 +122,52 @@ pub async fn modify_service<V>(
     if let Some(abort_timeout) = abort_timeout {
         modify_request.push(ModifyServiceChange::AbortTimeout(abort_timeout));
     }
+    if let Some(paused) = paused {
+        modify_request.push(ModifyServiceChange::Paused(paused));
+    }
+    if reset_retry_policy {
+        modify_request.push(ModifyServiceChange::RetryPolicy(None));
+    }
+    for handler_name in reset_handler_retry_policies {
+        modify_request.push(ModifyServiceChange::HandlerRetryPolicy(handler_name, None));
+    }
+    if let Some(retry_policy) = retry_policy {
+        modify_request.push(ModifyServiceChange::RetryPolicy(Some(retry_policy)));
+    }
+    for (handler_name, retry_policy) in handler_retry_policies {
+        modify_request.push(ModifyServiceChange::HandlerRetryPolicy(
+            handler_name,
+            Some(retry_policy),
+        ));
+    }
+    if let Some(ingress_authorization) = ingress_authorization {
+        modify_request.push(ModifyServiceChange::IngressAuthorization(
+            ingress_authorization,
+        ));
+    }
+    for (handler_name, ingress_authorization) in handler_ingress_authorizations {
+        modify_request.push(ModifyServiceChange::HandlerIngressAuthorization(
+            handler_name,
+            ingress_authorization,
+        ));
+    }
+    if let Some(rate_limit) = rate_limit {
+        modify_request.push(ModifyServiceChange::RateLimit(rate_limit));
+    }
+    for (handler_name, rate_limit) in handler_rate_limits {
+        modify_request.push(ModifyServiceChange::HandlerRateLimit(
+            handler_name,
+            rate_limit,
+        ));
+    }
+    if let Some(concurrency_limit) = concurrency_limit {
+        modify_request.push(ModifyServiceChange::ConcurrencyLimit(concurrency_limit));
+    }
+    if let Some(json_schema_validation) = json_schema_validation {
+        modify_request.push(ModifyServiceChange::JsonSchemaValidation(
+            json_schema_validation,
+        ));
+    }
 
     if modify_request.is_empty() {
         // No need to do anything
@@ -117,13 +176,47 @@ pub async fn modify_service<V>(
 
     let response = state
         .schema_registry
-        .modify_service(service_name, modify_request)
+        .modify_service(service_name.clone(), modify_request)
         .await
         .inspect_err(|e| warn_it!(e))?;
 
+    if let Some(paused) = paused {
+        let service_pause = if paused {
+            ServicePause::pause(service_name)
+        } else {
+            ServicePause::resume(service_name)
+        };
+        send_service_pause(&state, service_pause).await;
+    }
+
     Ok(response.into())
 }
 
+/// The partition processors don't have access to the schemas, hence every partition is told
+/// about the paused services via its log. This is best-effort: the schema is the source of truth
+/// and the partition leaders reconcile their paused services with it.
+async fn send_service_pause<V>(state: &AdminServiceState<V>, service_pause: ServicePause) {
+    let partition_table = metadata().partition_table_snapshot();
+
+    for (_, partition) in partition_table.partitions() {
+        let result = append_envelope_to_bifrost(
+            &state.bifrost,
+            Arc::new(Envelope::new(
+                create_envelope_header(*partition.key_range.start()),
+                Command::PauseService(service_pause.clone()),
+            )),
+        )
+        .await;
+
+        if let Err(err) = result {
+            warn!(
+                "Could not append service pause command to Bifrost, the partition leader will \
+                 reconcile it from the schema: {err}"
+            );
+        }
+    }
+}
+
 /// Modify a service state
 #[openapi(
     summary = "Modify a service state",
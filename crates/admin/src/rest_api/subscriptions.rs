@@ -20,6 +20,7 @@ use axum::http::StatusCode;
 use axum::{http, Json};
 use okapi_operation::*;
 use restate_errors::warn_it;
+use restate_ingress_kafka::OffsetsReset;
 use restate_types::identifiers::SubscriptionId;
 
 /// Create subscription.
@@ -166,3 +167,66 @@ pub async fn delete_subscription<V>(
         .inspect_err(|e| warn_it!(e))?;
     Ok(StatusCode::ACCEPTED)
 }
+
+/// Modify subscription.
+#[openapi(
+    summary = "Modify subscription",
+    description = "Pause or resume the consumption of a subscription.",
+    operation_id = "modify_subscription",
+    tags = "subscription",
+    parameters(path(
+        name = "subscription",
+        description = "Subscription identifier",
+        schema = "std::string::String"
+    ))
+)]
+pub async fn modify_subscription<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(subscription_id): Path<SubscriptionId>,
+    #[request_body(required = true)] Json(UpdateSubscriptionRequest { paused }): Json<
+        UpdateSubscriptionRequest,
+    >,
+) -> Result<Json<SubscriptionResponse>, MetaApiError> {
+    let subscription = state
+        .schema_registry
+        .update_subscription(subscription_id, paused)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(SubscriptionResponse::from(subscription).into())
+}
+
+/// Reset subscription offsets.
+#[openapi(
+    summary = "Reset subscription offsets",
+    description = "Reset the consumer offsets of a paused subscription. The records consumed again once the subscription is resumed are not deduplicated, hence rewinding the offsets replays them.",
+    operation_id = "reset_subscription_offsets",
+    tags = "subscription",
+    parameters(path(
+        name = "subscription",
+        description = "Subscription identifier",
+        schema = "std::string::String"
+    ))
+)]
+pub async fn reset_subscription_offsets<V>(
+    State(state): State<AdminServiceState<V>>,
+    Path(subscription_id): Path<SubscriptionId>,
+    #[request_body(required = true)] Json(payload): Json<ResetSubscriptionOffsetsRequest>,
+) -> Result<Json<ResetSubscriptionOffsetsResponse>, MetaApiError> {
+    let reset = match payload {
+        ResetSubscriptionOffsetsRequest::Earliest => OffsetsReset::Earliest,
+        ResetSubscriptionOffsetsRequest::Latest => OffsetsReset::Latest,
+        ResetSubscriptionOffsetsRequest::Timestamp { timestamp } => {
+            OffsetsReset::Timestamp(timestamp)
+        }
+        ResetSubscriptionOffsetsRequest::Offsets { offsets } => OffsetsReset::Offsets(offsets),
+    };
+
+    let offsets = state
+        .schema_registry
+        .reset_subscription_offsets(subscription_id, reset)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(ResetSubscriptionOffsetsResponse { offsets }.into())
+}
@@ -14,9 +14,10 @@ use restate_core::metadata_store::ReadModifyWriteError;
 use restate_core::ShutdownError;
 use restate_types::endpoint_manifest;
 use restate_types::errors::GenericError;
-use restate_types::identifiers::DeploymentId;
+use restate_types::identifiers::{DeploymentId, SubscriptionId};
 use restate_types::invocation::ServiceType;
 use restate_types::schema::invocation_target::BadInputContentType;
+use restate_types::schema::schedules::InvalidScheduleError;
 
 use crate::schema_registry::ServiceName;
 
@@ -71,6 +72,18 @@ pub enum SchemaError {
         #[code]
         SubscriptionError,
     ),
+    #[error(transparent)]
+    Schedule(
+        #[from]
+        #[code]
+        ScheduleError,
+    ),
+    #[error(transparent)]
+    KafkaSink(
+        #[from]
+        #[code]
+        KafkaSinkError,
+    ),
 }
 
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
@@ -127,12 +140,51 @@ pub enum SubscriptionError {
     SinkServiceNotFound(Uri),
     #[error("invalid sink URI '{0}': shared handlers cannot be used as sinks.")]
     InvalidSinkSharedHandler(Uri),
+    #[error("subscription '{0}' must be paused before resetting its offsets.")]
+    NotPaused(SubscriptionId),
+    #[error("cannot reset the offsets: {0}")]
+    #[code(unknown)]
+    ResetOffsets(#[from] restate_ingress_kafka::ResetOffsetsError),
 
     #[error(transparent)]
     #[code(unknown)]
     Validation(GenericError),
 }
 
+#[derive(Debug, thiserror::Error, codederror::CodedError)]
+#[code(unknown)]
+pub enum ScheduleError {
+    #[error("invalid schedule id '{0}': must be non-empty and contain only alphanumeric characters, '-', '_' and '.'")]
+    InvalidId(String),
+    #[error("invalid target '{0}': expected '<service>/<handler>' or '<service>/<key>/<handler>'")]
+    InvalidTarget(String),
+    #[error("invalid target '{0}': cannot find the service/handler")]
+    TargetNotFound(String),
+    #[error("invalid target '{0}': workflow run handlers cannot be scheduled")]
+    WorkflowRunTarget(String),
+    #[error(transparent)]
+    InvalidSchedule(#[from] InvalidScheduleError),
+}
+
+#[derive(Debug, thiserror::Error, codederror::CodedError)]
+#[code(unknown)]
+pub enum KafkaSinkError {
+    #[error("invalid Kafka sink id '{0}': must be non-empty and contain only alphanumeric characters, '-', '_' and '.'")]
+    InvalidId(String),
+    #[error(
+        "invalid source URI '{0}': must be 'service://<service_name>' or 'service://<service_name>/<handler_name>'."
+    )]
+    InvalidSource(Uri),
+    #[error(
+        "invalid source URI '{0}': cannot find the service/handler specified in the source URI."
+    )]
+    SourceServiceNotFound(Uri),
+    #[error("invalid sink URI '{0}': must be 'kafka://<cluster_name>/<topic_name>'.")]
+    InvalidSink(Uri),
+    #[error("invalid sink URI '{0}': the Kafka cluster '{1}' is not configured.")]
+    UnknownCluster(Uri, String),
+}
+
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
 pub enum DeploymentError {
     #[error("existing deployment id is different from requested (requested = {requested}, existing = {existing})")]
@@ -11,24 +11,35 @@
 pub mod error;
 mod updater;
 
-use crate::schema_registry::error::{SchemaError, SchemaRegistryError, ServiceError};
+use crate::schema_registry::error::{
+    KafkaSinkError, SchemaError, SchemaRegistryError, ServiceError, SubscriptionError,
+};
 use crate::schema_registry::updater::SchemaUpdater;
+use bytes::Bytes;
 use http::Uri;
 use restate_core::metadata_store::MetadataStoreClient;
 use restate_core::{metadata, MetadataWriter};
+use restate_ingress_kafka::OffsetsReset;
 use restate_service_protocol::discovery::{DiscoverEndpoint, DiscoveredEndpoint, ServiceDiscovery};
+use restate_types::config::Configuration;
 use restate_types::identifiers::{DeploymentId, ServiceRevision, SubscriptionId};
 use restate_types::metadata_store::keys::SCHEMA_INFORMATION_KEY;
 use restate_types::schema::deployment::{
     DeliveryOptions, Deployment, DeploymentMetadata, DeploymentResolver,
 };
-use restate_types::schema::service::{HandlerMetadata, ServiceMetadata, ServiceMetadataResolver};
+use restate_types::schema::kafka_sinks::{KafkaSink, KafkaSinkResolver};
+use restate_types::schema::schedules::{Schedule, ScheduleResolver};
+use restate_types::schema::service::{
+    HandlerMetadata, IngressAuthorization, InvocationRetryPolicy, RateLimit, ServiceMetadata,
+    ServiceMetadataResolver,
+};
 use restate_types::schema::subscriptions::{
     ListSubscriptionFilter, Subscription, SubscriptionResolver, SubscriptionValidator,
 };
 use restate_types::schema::Schema;
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::time::Duration;
 use tracing::subscriber::NoSubscriber;
@@ -67,6 +78,17 @@ pub enum ModifyServiceChange {
     WorkflowCompletionRetention(Duration),
     InactivityTimeout(Duration),
     AbortTimeout(Duration),
+    Paused(bool),
+    /// `None` removes the retry policy of the service.
+    RetryPolicy(Option<InvocationRetryPolicy>),
+    /// `None` removes the retry policy of the handler.
+    HandlerRetryPolicy(String, Option<InvocationRetryPolicy>),
+    IngressAuthorization(IngressAuthorization),
+    HandlerIngressAuthorization(String, IngressAuthorization),
+    RateLimit(RateLimit),
+    HandlerRateLimit(String, RateLimit),
+    ConcurrencyLimit(NonZeroUsize),
+    JsonSchemaValidation(bool),
 }
 
 /// Responsible for updating the registered schema information. This includes the discovery of
@@ -97,6 +119,7 @@ impl<V> SchemaRegistry<V> {
     pub async fn register_deployment(
         &self,
         discover_endpoint: DiscoverEndpoint,
+        concurrency_limit: Option<NonZeroUsize>,
         force: Force,
         apply_mode: ApplyMode,
     ) -> Result<(DeploymentId, Vec<ServiceMetadata>), SchemaRegistryError> {
@@ -111,13 +134,13 @@ impl<V> SchemaRegistry<V> {
                 uri.clone(),
                 discovered_metadata.protocol_type,
                 http_version,
-                DeliveryOptions::new(discovered_metadata.headers),
+                DeliveryOptions::new(discovered_metadata.headers, concurrency_limit),
                 discovered_metadata.supported_protocol_versions,
             ),
             DiscoveredEndpoint::Lambda(arn, assume_role_arn) => DeploymentMetadata::new_lambda(
                 arn,
                 assume_role_arn,
-                DeliveryOptions::new(discovered_metadata.headers),
+                DeliveryOptions::new(discovered_metadata.headers, concurrency_limit),
                 discovered_metadata.supported_protocol_versions,
             ),
         };
@@ -272,6 +295,205 @@ impl<V> SchemaRegistry<V> {
         Ok(())
     }
 
+    pub async fn update_subscription(
+        &self,
+        subscription_id: SubscriptionId,
+        paused: Option<bool>,
+    ) -> Result<Subscription, SchemaRegistryError> {
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    if let Some(paused) = paused {
+                        updater.set_subscription_paused(subscription_id, paused)?;
+                    }
+                    Ok::<_, SchemaError>(updater.into_inner())
+                },
+            )
+            .await?;
+
+        let subscription = schema_information
+            .get_subscription(subscription_id)
+            .expect("subscription was just updated");
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(subscription)
+    }
+
+    /// Resets the consumer offsets of a paused subscription. The records consumed again after
+    /// the reset are not deduplicated, hence rewinding the offsets replays them.
+    pub async fn reset_subscription_offsets(
+        &self,
+        subscription_id: SubscriptionId,
+        reset: OffsetsReset,
+    ) -> Result<BTreeMap<i32, i64>, SchemaRegistryError> {
+        let subscription = self.get_subscription(subscription_id).ok_or_else(|| {
+            SchemaError::NotFound(format!("subscription with id '{subscription_id}'"))
+        })?;
+        if !subscription.is_paused() {
+            return Err(SchemaError::from(SubscriptionError::NotPaused(subscription_id)).into());
+        }
+
+        let offsets = restate_ingress_kafka::reset_offsets(
+            &Configuration::pinned().ingress,
+            &subscription,
+            reset,
+        )
+        .await
+        .map_err(|e| SchemaError::from(SubscriptionError::from(e)))?;
+
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    updater.increment_subscription_offsets_generation(subscription_id)?;
+                    Ok::<_, SchemaError>(updater.into_inner())
+                },
+            )
+            .await?;
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(offsets)
+    }
+
+    pub async fn create_schedule(
+        &self,
+        id: String,
+        cron: String,
+        timezone: String,
+        target: String,
+        payload: Bytes,
+    ) -> Result<Schedule, SchemaRegistryError> {
+        let mut schedule = None;
+
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    schedule = Some(updater.add_schedule(
+                        id.clone(),
+                        cron.clone(),
+                        timezone.clone(),
+                        target.clone(),
+                        payload.clone(),
+                    )?);
+
+                    Ok::<_, SchemaError>(updater.into_inner())
+                },
+            )
+            .await?;
+
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(schedule.expect("schedule was just added"))
+    }
+
+    pub async fn delete_schedule(
+        &self,
+        schedule_id: String,
+    ) -> Result<Schedule, SchemaRegistryError> {
+        let mut schedule = None;
+
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    schedule = Some(updater.remove_schedule(&schedule_id).ok_or_else(|| {
+                        SchemaError::NotFound(format!("schedule with id '{schedule_id}'"))
+                    })?);
+
+                    Ok(updater.into_inner())
+                },
+            )
+            .await?;
+
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(schedule.expect("schedule was just removed"))
+    }
+
+    pub async fn create_kafka_sink(
+        &self,
+        id: String,
+        source: Uri,
+        sink: Uri,
+        options: HashMap<String, String>,
+    ) -> Result<KafkaSink, SchemaRegistryError> {
+        // Records are produced by the workers, which share the Kafka clusters configuration
+        if let Some(cluster) = sink.authority().map(|authority| authority.as_str()) {
+            if Configuration::pinned()
+                .ingress
+                .get_kafka_cluster(cluster)
+                .is_none()
+            {
+                return Err(SchemaError::from(KafkaSinkError::UnknownCluster(
+                    sink.clone(),
+                    cluster.to_owned(),
+                ))
+                .into());
+            }
+        }
+
+        let mut kafka_sink = None;
+
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    kafka_sink = Some(updater.add_kafka_sink(
+                        id.clone(),
+                        source.clone(),
+                        sink.clone(),
+                        options.clone(),
+                    )?);
+
+                    Ok::<_, SchemaError>(updater.into_inner())
+                },
+            )
+            .await?;
+
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(kafka_sink.expect("Kafka sink was just added"))
+    }
+
+    pub async fn delete_kafka_sink(
+        &self,
+        kafka_sink_id: String,
+    ) -> Result<KafkaSink, SchemaRegistryError> {
+        let mut kafka_sink = None;
+
+        let schema_information = self
+            .metadata_store_client
+            .read_modify_write(
+                SCHEMA_INFORMATION_KEY.clone(),
+                |schema_information: Option<Schema>| {
+                    let mut updater = SchemaUpdater::from(schema_information.unwrap_or_default());
+                    kafka_sink =
+                        Some(updater.remove_kafka_sink(&kafka_sink_id).ok_or_else(|| {
+                            SchemaError::NotFound(format!("Kafka sink with id '{kafka_sink_id}'"))
+                        })?);
+
+                    Ok(updater.into_inner())
+                },
+            )
+            .await?;
+
+        self.metadata_writer.update(schema_information).await?;
+
+        Ok(kafka_sink.expect("Kafka sink was just removed"))
+    }
+
     pub fn list_services(&self) -> Vec<ServiceMetadata> {
         metadata().schema().list_services()
     }
@@ -325,6 +547,22 @@ impl<V> SchemaRegistry<V> {
     pub fn list_subscriptions(&self, filters: &[ListSubscriptionFilter]) -> Vec<Subscription> {
         metadata().schema().list_subscriptions(filters)
     }
+
+    pub fn get_schedule(&self, schedule_id: &str) -> Option<Schedule> {
+        metadata().schema().get_schedule(schedule_id)
+    }
+
+    pub fn list_schedules(&self) -> Vec<Schedule> {
+        metadata().schema().list_schedules()
+    }
+
+    pub fn get_kafka_sink(&self, kafka_sink_id: &str) -> Option<KafkaSink> {
+        metadata().schema().get_kafka_sink(kafka_sink_id)
+    }
+
+    pub fn list_kafka_sinks(&self) -> Vec<KafkaSink> {
+        metadata().schema().list_kafka_sinks()
+    }
 }
 
 impl<V> SchemaRegistry<V>
@@ -9,14 +9,16 @@
 // by the Apache License, Version 2.0.
 
 use crate::schema_registry::error::{
-    DeploymentError, SchemaError, ServiceError, SubscriptionError,
+    DeploymentError, KafkaSinkError, ScheduleError, SchemaError, ServiceError, SubscriptionError,
 };
 use crate::schema_registry::{ModifyServiceChange, ServiceName};
+use bytes::Bytes;
 use http::{HeaderValue, Uri};
 use restate_types::endpoint_manifest;
 use restate_types::identifiers::{DeploymentId, SubscriptionId};
 use restate_types::invocation::{
-    InvocationTargetType, ServiceType, VirtualObjectHandlerType, WorkflowHandlerType,
+    InvocationTarget, InvocationTargetType, ServiceType, VirtualObjectHandlerType,
+    WorkflowHandlerType,
 };
 use restate_types::schema::deployment::DeploymentMetadata;
 use restate_types::schema::deployment::DeploymentSchemas;
@@ -24,6 +26,8 @@ use restate_types::schema::invocation_target::{
     InputRules, InputValidationRule, InvocationTargetMetadata, OutputContentTypeRule, OutputRules,
     DEFAULT_IDEMPOTENCY_RETENTION, DEFAULT_WORKFLOW_COMPLETION_RETENTION,
 };
+use restate_types::schema::kafka_sinks::{KafkaSink, KafkaSinkSource};
+use restate_types::schema::schedules::Schedule;
 use restate_types::schema::service::{HandlerSchemas, ServiceLocation, ServiceSchemas};
 use restate_types::schema::subscriptions::{
     EventReceiverServiceType, Sink, Source, Subscription, SubscriptionValidator,
@@ -128,7 +132,7 @@ impl SchemaUpdater {
         // Compute service schemas
         for (service_name, service) in proposed_services {
             let service_type = ServiceType::from(service.ty);
-            let handlers = DiscoveredHandlerMetadata::compute_handlers(
+            let mut handlers = DiscoveredHandlerMetadata::compute_handlers(
                 service
                     .handlers
                     .into_iter()
@@ -191,6 +195,17 @@ impl SchemaUpdater {
                     rpc.service = %service_name,
                     "Overwriting existing service schemas"
                 );
+                // Retry policies and ingress authorizations configured for handlers survive
+                // the new revision
+                for (handler_name, handler) in handlers.iter_mut() {
+                    if let Some(existing_handler) = existing_service.handlers.get(handler_name) {
+                        handler.retry_policy = existing_handler.retry_policy.clone();
+                        handler.ingress_authorization =
+                            existing_handler.ingress_authorization.clone();
+                        handler.rate_limit = existing_handler.rate_limit.clone();
+                    }
+                }
+
                 let mut service_schemas = existing_service.clone();
                 service_schemas.revision = existing_service.revision.wrapping_add(1);
                 service_schemas.ty = service_type;
@@ -215,6 +230,12 @@ impl SchemaUpdater {
                     },
                     inactivity_timeout: None,
                     abort_timeout: None,
+                    paused: false,
+                    retry_policy: None,
+                    ingress_authorization: None,
+                    rate_limit: None,
+                    concurrency_limit: None,
+                    json_schema_validation: false,
                 }
             };
 
@@ -399,6 +420,199 @@ impl SchemaUpdater {
         }
     }
 
+    pub fn set_subscription_paused(
+        &mut self,
+        subscription_id: SubscriptionId,
+        paused: bool,
+    ) -> Result<(), SchemaError> {
+        let subscription = self.subscription_mut(subscription_id)?;
+        if subscription.is_paused() != paused {
+            subscription.set_paused(paused);
+            self.modified = true;
+        }
+        Ok(())
+    }
+
+    /// Changes the deduplication ids of the subscription records after their offsets were reset.
+    pub fn increment_subscription_offsets_generation(
+        &mut self,
+        subscription_id: SubscriptionId,
+    ) -> Result<(), SchemaError> {
+        self.subscription_mut(subscription_id)?
+            .increment_offsets_generation();
+        self.modified = true;
+        Ok(())
+    }
+
+    fn subscription_mut(
+        &mut self,
+        subscription_id: SubscriptionId,
+    ) -> Result<&mut Subscription, SchemaError> {
+        self.schema_information
+            .subscriptions
+            .get_mut(&subscription_id)
+            .ok_or_else(|| {
+                SchemaError::NotFound(format!("subscription with id '{subscription_id}'"))
+            })
+    }
+
+    pub fn add_schedule(
+        &mut self,
+        id: String,
+        cron: String,
+        timezone: String,
+        target: String,
+        payload: Bytes,
+    ) -> Result<Schedule, SchemaError> {
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(SchemaError::Schedule(ScheduleError::InvalidId(id)));
+        }
+        if self.schema_information.schedules.contains_key(&id) {
+            return Err(SchemaError::Override(format!("schedule with id '{id}'")));
+        }
+
+        let target = self.resolve_schedule_target(&target)?;
+        let schedule = Schedule::new(id.clone(), cron, timezone, target, payload)
+            .map_err(ScheduleError::from)?;
+
+        self.schema_information
+            .schedules
+            .insert(id, schedule.clone());
+        self.modified = true;
+
+        Ok(schedule)
+    }
+
+    /// Parses targets in the form `<service>/<handler>` or `<service>/<key>/<handler>`.
+    fn resolve_schedule_target(&self, target: &str) -> Result<InvocationTarget, ScheduleError> {
+        let invalid_target = || ScheduleError::InvalidTarget(target.to_owned());
+
+        let (service_name, rest) = target.split_once('/').ok_or_else(invalid_target)?;
+        let (key, handler_name) = match rest.rsplit_once('/') {
+            Some((key, handler_name)) => (Some(key), handler_name),
+            None => (None, rest),
+        };
+        if service_name.is_empty() || handler_name.is_empty() {
+            return Err(invalid_target());
+        }
+
+        let target_ty = self
+            .schema_information
+            .services
+            .get(service_name)
+            .and_then(|service_schemas| service_schemas.handlers.get(handler_name))
+            .map(|handler_schemas| handler_schemas.target_meta.target_ty)
+            .ok_or_else(|| ScheduleError::TargetNotFound(target.to_owned()))?;
+
+        match (target_ty, key) {
+            (InvocationTargetType::Service, None) => {
+                Ok(InvocationTarget::service(service_name, handler_name))
+            }
+            (InvocationTargetType::VirtualObject(handler_ty), Some(key)) => Ok(
+                InvocationTarget::virtual_object(service_name, key, handler_name, handler_ty),
+            ),
+            (InvocationTargetType::Workflow(WorkflowHandlerType::Workflow), _) => {
+                // The run handler executes only once per workflow id
+                Err(ScheduleError::WorkflowRunTarget(target.to_owned()))
+            }
+            (InvocationTargetType::Workflow(handler_ty), Some(key)) => Ok(
+                InvocationTarget::workflow(service_name, key, handler_name, handler_ty),
+            ),
+            _ => Err(invalid_target()),
+        }
+    }
+
+    pub fn remove_schedule(&mut self, schedule_id: &str) -> Option<Schedule> {
+        let removed = self.schema_information.schedules.remove(schedule_id);
+        if removed.is_some() {
+            self.modified = true;
+        }
+        removed
+    }
+
+    pub fn add_kafka_sink(
+        &mut self,
+        id: String,
+        source: Uri,
+        sink: Uri,
+        options: HashMap<String, String>,
+    ) -> Result<KafkaSink, SchemaError> {
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(SchemaError::KafkaSink(KafkaSinkError::InvalidId(id)));
+        }
+        if self.schema_information.kafka_sinks.contains_key(&id) {
+            return Err(SchemaError::Override(format!("Kafka sink with id '{id}'")));
+        }
+
+        // Parse source
+        if source.scheme_str() != Some("service") {
+            return Err(KafkaSinkError::InvalidSource(source).into());
+        }
+        let service_name = source
+            .authority()
+            .ok_or_else(|| KafkaSinkError::InvalidSource(source.clone()))?
+            .as_str();
+        let handler_name = source.path().trim_start_matches('/');
+        let service_schemas = self
+            .schema_information
+            .services
+            .get(service_name)
+            .ok_or_else(|| KafkaSinkError::SourceServiceNotFound(source.clone()))?;
+        let handler_name = if handler_name.is_empty() {
+            None
+        } else if service_schemas.handlers.contains_key(handler_name) {
+            Some(handler_name.to_owned())
+        } else {
+            return Err(KafkaSinkError::SourceServiceNotFound(source).into());
+        };
+
+        // Parse sink
+        if sink.scheme_str() != Some("kafka") {
+            return Err(KafkaSinkError::InvalidSink(sink).into());
+        }
+        let cluster = sink
+            .authority()
+            .ok_or_else(|| KafkaSinkError::InvalidSink(sink.clone()))?
+            .as_str();
+        let topic = sink.path().trim_start_matches('/');
+        if topic.is_empty() {
+            return Err(KafkaSinkError::InvalidSink(sink).into());
+        }
+
+        let kafka_sink = KafkaSink::new(
+            id.clone(),
+            KafkaSinkSource::Service {
+                name: service_name.to_owned(),
+                handler: handler_name,
+            },
+            cluster.to_owned(),
+            topic.to_owned(),
+            options,
+        );
+        self.schema_information
+            .kafka_sinks
+            .insert(id, kafka_sink.clone());
+        self.modified = true;
+
+        Ok(kafka_sink)
+    }
+
+    pub fn remove_kafka_sink(&mut self, kafka_sink_id: &str) -> Option<KafkaSink> {
+        let removed = self.schema_information.kafka_sinks.remove(kafka_sink_id);
+        if removed.is_some() {
+            self.modified = true;
+        }
+        removed
+    }
+
     pub fn modify_service(
         &mut self,
         name: String,
@@ -443,6 +657,51 @@ impl SchemaUpdater {
                     ModifyServiceChange::AbortTimeout(abort_timeout) => {
                         schemas.abort_timeout = Some(abort_timeout);
                     }
+                    ModifyServiceChange::Paused(paused) => {
+                        schemas.paused = paused;
+                    }
+                    ModifyServiceChange::RetryPolicy(retry_policy) => {
+                        schemas.retry_policy = retry_policy;
+                    }
+                    ModifyServiceChange::HandlerRetryPolicy(handler_name, retry_policy) => {
+                        let Some(handler) = schemas.handlers.get_mut(&handler_name) else {
+                            return Err(SchemaError::NotFound(format!(
+                                "handler '{name}/{handler_name}'"
+                            )));
+                        };
+                        handler.retry_policy = retry_policy;
+                    }
+                    ModifyServiceChange::IngressAuthorization(ingress_authorization) => {
+                        schemas.ingress_authorization = Some(ingress_authorization);
+                    }
+                    ModifyServiceChange::HandlerIngressAuthorization(
+                        handler_name,
+                        ingress_authorization,
+                    ) => {
+                        let Some(handler) = schemas.handlers.get_mut(&handler_name) else {
+                            return Err(SchemaError::NotFound(format!(
+                                "handler '{name}/{handler_name}'"
+                            )));
+                        };
+                        handler.ingress_authorization = Some(ingress_authorization);
+                    }
+                    ModifyServiceChange::RateLimit(rate_limit) => {
+                        schemas.rate_limit = Some(rate_limit);
+                    }
+                    ModifyServiceChange::HandlerRateLimit(handler_name, rate_limit) => {
+                        let Some(handler) = schemas.handlers.get_mut(&handler_name) else {
+                            return Err(SchemaError::NotFound(format!(
+                                "handler '{name}/{handler_name}'"
+                            )));
+                        };
+                        handler.rate_limit = Some(rate_limit);
+                    }
+                    ModifyServiceChange::ConcurrencyLimit(concurrency_limit) => {
+                        schemas.concurrency_limit = Some(concurrency_limit);
+                    }
+                    ModifyServiceChange::JsonSchemaValidation(json_schema_validation) => {
+                        schemas.json_schema_validation = json_schema_validation;
+                    }
                 }
             }
         }
@@ -625,6 +884,9 @@ impl DiscoveredHandlerMetadata {
                             input_rules: handler.input,
                             output_rules: handler.output,
                         },
+                        retry_policy: None,
+                        ingress_authorization: None,
+                        rate_limit: None,
                     },
                 )
             })
@@ -637,8 +899,12 @@ mod tests {
     use super::*;
 
     use restate_test_util::{assert, assert_eq, let_assert};
+    use restate_types::retries::RetryPolicy;
     use restate_types::schema::deployment::{Deployment, DeploymentResolver};
-    use restate_types::schema::service::ServiceMetadataResolver;
+    use restate_types::schema::service::{
+        IngressAuthorization, InvocationRetryPolicy, OnMaxAttempts, ServiceMetadataResolver,
+    };
+    use std::time::Duration;
 
     use restate_types::Versioned;
     use test_log::test;
@@ -795,6 +1061,152 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn handler_retry_policy_survives_new_deployment() -> Result<(), SchemaError> {
+        let mut updater = SchemaUpdater::default();
+        let deployment = Deployment::mock();
+
+        updater.add_deployment(
+            Some(deployment.id),
+            deployment.metadata.clone(),
+            vec![greeter_service()],
+            false,
+        )?;
+
+        let retry_policy = InvocationRetryPolicy {
+            policy: RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3)),
+            on_max_attempts: OnMaxAttempts::Pause,
+        };
+        assert!(let Err(SchemaError::NotFound(_)) = updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![ModifyServiceChange::HandlerRetryPolicy(
+                "unknown".to_owned(),
+                Some(retry_policy.clone())
+            )],
+        ));
+        updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![ModifyServiceChange::HandlerRetryPolicy(
+                "greet".to_owned(),
+                Some(retry_policy),
+            )],
+        )?;
+
+        updater.add_deployment(
+            Some(deployment.id),
+            deployment.metadata.clone(),
+            vec![greeter_service()],
+            true,
+        )?;
+        let schemas = updater.into_inner();
+
+        schemas.assert_service_revision(GREETER_SERVICE_NAME, 2);
+        assert_eq!(
+            schemas
+                .resolve_invocation_retry_policy(GREETER_SERVICE_NAME, "greet")
+                .map(|retry_policy| retry_policy.on_max_attempts),
+            Some(OnMaxAttempts::Pause)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reset_retry_policies() -> Result<(), SchemaError> {
+        let mut updater = SchemaUpdater::default();
+        let deployment = Deployment::mock();
+
+        updater.add_deployment(
+            Some(deployment.id),
+            deployment.metadata.clone(),
+            vec![greeter_service()],
+            false,
+        )?;
+
+        let retry_policy = |on_max_attempts| InvocationRetryPolicy {
+            policy: RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3)),
+            on_max_attempts,
+        };
+        updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![
+                ModifyServiceChange::RetryPolicy(Some(retry_policy(OnMaxAttempts::Kill))),
+                ModifyServiceChange::HandlerRetryPolicy(
+                    "greet".to_owned(),
+                    Some(retry_policy(OnMaxAttempts::Pause)),
+                ),
+            ],
+        )?;
+
+        // Resetting the handler retry policy falls back to the one of the service
+        updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![ModifyServiceChange::HandlerRetryPolicy(
+                "greet".to_owned(),
+                None,
+            )],
+        )?;
+        assert_eq!(
+            updater
+                .schema_information
+                .resolve_invocation_retry_policy(GREETER_SERVICE_NAME, "greet")
+                .map(|retry_policy| retry_policy.on_max_attempts),
+            Some(OnMaxAttempts::Kill)
+        );
+
+        updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![ModifyServiceChange::RetryPolicy(None)],
+        )?;
+        let schemas = updater.into_inner();
+
+        assert!(schemas
+            .resolve_invocation_retry_policy(GREETER_SERVICE_NAME, "greet")
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn handler_ingress_authorization_survives_new_deployment() -> Result<(), SchemaError> {
+        let mut updater = SchemaUpdater::default();
+        let deployment = Deployment::mock();
+
+        updater.add_deployment(
+            Some(deployment.id),
+            deployment.metadata.clone(),
+            vec![greeter_service()],
+            false,
+        )?;
+
+        let ingress_authorization = IngressAuthorization {
+            allowed_principals: vec!["billing".to_owned()],
+        };
+        updater.modify_service(
+            GREETER_SERVICE_NAME.to_owned(),
+            vec![ModifyServiceChange::HandlerIngressAuthorization(
+                "greet".to_owned(),
+                ingress_authorization.clone(),
+            )],
+        )?;
+
+        updater.add_deployment(
+            Some(deployment.id),
+            deployment.metadata.clone(),
+            vec![greeter_service()],
+            true,
+        )?;
+        let schemas = updater.into_inner();
+
+        schemas.assert_service_revision(GREETER_SERVICE_NAME, 2);
+        assert_eq!(
+            schemas.resolve_ingress_authorization(GREETER_SERVICE_NAME, "greet"),
+            Some(ingress_authorization)
+        );
+
+        Ok(())
+    }
+
     mod change_instance_type {
         use super::*;
 
@@ -969,6 +1381,157 @@ mod tests {
         assert!(schemas.get_deployment(&deployment_1.id).is_none());
     }
 
+    #[test]
+    fn add_and_remove_schedule() {
+        let mut updater = SchemaUpdater::default();
+
+        let deployment = Deployment::mock();
+        updater
+            .add_deployment(
+                Some(deployment.id),
+                deployment.metadata,
+                vec![greeter_virtual_object()],
+                false,
+            )
+            .unwrap();
+
+        let schedule = updater
+            .add_schedule(
+                "nightly-greet".to_owned(),
+                "0 2 * * *".to_owned(),
+                "UTC".to_owned(),
+                format!("{GREETER_SERVICE_NAME}/my-key/greet"),
+                Bytes::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            schedule.target(),
+            &InvocationTarget::virtual_object(
+                GREETER_SERVICE_NAME,
+                "my-key",
+                "greet",
+                VirtualObjectHandlerType::Exclusive
+            )
+        );
+
+        // Virtual object targets require a key
+        let_assert!(
+            Err(SchemaError::Schedule(ScheduleError::InvalidTarget(_))) = updater.add_schedule(
+                "keyless-greet".to_owned(),
+                "0 2 * * *".to_owned(),
+                "UTC".to_owned(),
+                format!("{GREETER_SERVICE_NAME}/greet"),
+                Bytes::new(),
+            )
+        );
+        let_assert!(
+            Err(SchemaError::Override(_)) = updater.add_schedule(
+                "nightly-greet".to_owned(),
+                "0 3 * * *".to_owned(),
+                "UTC".to_owned(),
+                format!("{GREETER_SERVICE_NAME}/my-key/greet"),
+                Bytes::new(),
+            )
+        );
+
+        let schemas = updater.into_inner();
+        assert_eq!(schemas.schedules.len(), 1);
+
+        let mut updater = SchemaUpdater::from(schemas);
+        assert!(updater.remove_schedule("nightly-greet").is_some());
+        assert!(updater.into_inner().schedules.is_empty());
+    }
+
+    #[test]
+    fn add_and_remove_kafka_sink() {
+        let mut updater = SchemaUpdater::default();
+
+        let deployment = Deployment::mock();
+        updater
+            .add_deployment(
+                Some(deployment.id),
+                deployment.metadata,
+                vec![greeter_service()],
+                false,
+            )
+            .unwrap();
+
+        let kafka_sink = updater
+            .add_kafka_sink(
+                "greetings".to_owned(),
+                Uri::from_static("service://greeter.Greeter/greet"),
+                Uri::from_static("kafka://my-cluster/greetings"),
+                HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            kafka_sink.source(),
+            &KafkaSinkSource::Service {
+                name: GREETER_SERVICE_NAME.to_owned(),
+                handler: Some("greet".to_owned())
+            }
+        );
+        assert_eq!(kafka_sink.cluster(), "my-cluster");
+        assert_eq!(kafka_sink.topic(), "greetings");
+
+        let_assert!(
+            Err(SchemaError::KafkaSink(
+                KafkaSinkError::SourceServiceNotFound(_)
+            )) = updater.add_kafka_sink(
+                "farewells".to_owned(),
+                Uri::from_static("service://greeter.Greeter/farewell"),
+                Uri::from_static("kafka://my-cluster/farewells"),
+                HashMap::new(),
+            )
+        );
+        let_assert!(
+            Err(SchemaError::KafkaSink(KafkaSinkError::InvalidSink(_))) = updater.add_kafka_sink(
+                "no-topic".to_owned(),
+                Uri::from_static("service://greeter.Greeter"),
+                Uri::from_static("kafka://my-cluster"),
+                HashMap::new(),
+            )
+        );
+        let_assert!(
+            Err(SchemaError::Override(_)) = updater.add_kafka_sink(
+                "greetings".to_owned(),
+                Uri::from_static("service://greeter.Greeter"),
+                Uri::from_static("kafka://my-cluster/greetings"),
+                HashMap::new(),
+            )
+        );
+
+        let mut updater = SchemaUpdater::from(updater.into_inner());
+        assert!(updater.remove_kafka_sink("greetings").is_some());
+        assert!(updater.into_inner().kafka_sinks.is_empty());
+    }
+
+    #[test]
+    fn pause_subscription_and_increment_offsets_generation() {
+        let subscription = Subscription::mock();
+        let mut schema_information = Schema::default();
+        schema_information
+            .subscriptions
+            .insert(subscription.id(), subscription.clone());
+        let mut updater = SchemaUpdater::from(schema_information);
+
+        updater
+            .set_subscription_paused(subscription.id(), true)
+            .unwrap();
+        updater
+            .increment_subscription_offsets_generation(subscription.id())
+            .unwrap();
+        let_assert!(
+            Err(SchemaError::NotFound(_)) =
+                updater.set_subscription_paused(SubscriptionId::default(), true)
+        );
+
+        let schemas = updater.into_inner();
+        let updated_subscription = &schemas.subscriptions[&subscription.id()];
+        assert!(updated_subscription.is_paused());
+        assert_eq!(updated_subscription.offsets_generation(), 1);
+    }
+
     mod remove_method {
         use super::*;
 
@@ -19,8 +19,8 @@ use tonic::transport::Channel;
 use tower::ServiceBuilder;
 
 use restate_core::metadata_store::MetadataStoreClient;
-use restate_core::network::net_util;
 use restate_core::network::protobuf::node_svc::node_svc_client::NodeSvcClient;
+use restate_core::network::{net_util, TlsAcceptor};
 use restate_core::MetadataWriter;
 use restate_service_protocol::discovery::ServiceDiscovery;
 use restate_types::net::BindAddress;
@@ -88,8 +88,15 @@ where
 
         let service = hyper_util::service::TowerToHyperService::new(router.into_service());
 
+        let tls_acceptor = opts
+            .tls
+            .as_ref()
+            .map(TlsAcceptor::from_options)
+            .transpose()?;
+
         net_util::run_hyper_server(
             &BindAddress::Socket(opts.bind_address),
+            tls_acceptor,
             service,
             "admin-api-server",
             || (),
@@ -937,6 +937,100 @@ mod tests {
         .await
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn release_retired_loglets_after_grace_period() -> googletest::Result<()> {
+        const LOG_ID: LogId = LogId::new(0);
+        let node_env = TestCoreEnvBuilder::with_incoming_only_connector()
+            .set_partition_table(PartitionTable::with_equally_sized_partitions(
+                Version::MIN,
+                1,
+            ))
+            .build()
+            .await;
+        let tc = node_env.tc;
+        tc.run_in_scope("test", None, async {
+            let bifrost = Bifrost::init_in_memory(metadata()).await;
+            let bifrost_admin = BifrostAdmin::new(
+                &bifrost,
+                &node_env.metadata_writer,
+                &node_env.metadata_store_client,
+            );
+
+            let mut appender = bifrost.create_appender(LOG_ID)?;
+            for i in 1..=5 {
+                appender.append(format!("segment-1-{i}")).await?;
+            }
+            let segment_1 = bifrost
+                .inner
+                .find_loglet_for_lsn(LOG_ID, Lsn::OLDEST)
+                .await?
+                .unwrap();
+            bifrost_admin
+                .seal_and_extend_chain(
+                    LOG_ID,
+                    None,
+                    Version::MIN,
+                    ProviderKind::InMemory,
+                    new_single_node_loglet_params(ProviderKind::InMemory),
+                )
+                .await?;
+
+            // replace the sealed segment as if its records had been copied to another loglet
+            let old_metadata = bifrost.inner.metadata.logs_ref().clone();
+            let old_version = old_metadata.version();
+            let mut builder = old_metadata.into_builder();
+            builder.chain(&LOG_ID).unwrap().replace_sealed_segment(
+                segment_1.segment_index(),
+                ProviderKind::InMemory,
+                new_single_node_loglet_params(ProviderKind::InMemory),
+            )?;
+            node_env
+                .metadata_store_client
+                .put(
+                    BIFROST_CONFIG_KEY.clone(),
+                    &builder.build(),
+                    restate_metadata_store::Precondition::MatchesVersion(old_version),
+                )
+                .await?;
+            metadata()
+                .sync(MetadataKind::Logs, TargetVersion::Latest)
+                .await?;
+
+            // retired loglets are kept during the grace period
+            bifrost_admin
+                .release_retired_loglets(Duration::from_secs(3600))
+                .await?;
+            assert_eq!(
+                1,
+                bifrost
+                    .inner
+                    .metadata
+                    .logs_ref()
+                    .chain(&LOG_ID)
+                    .unwrap()
+                    .retired_loglets()
+                    .len()
+            );
+            assert_that!(segment_1.get_trim_point().await?, none());
+
+            bifrost_admin
+                .release_retired_loglets(Duration::ZERO)
+                .await?;
+            assert!(bifrost
+                .inner
+                .metadata
+                .logs_ref()
+                .chain(&LOG_ID)
+                .unwrap()
+                .retired_loglets()
+                .is_empty());
+            assert_that!(segment_1.get_trim_point().await?, some(eq(Lsn::new(5))));
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test(start_paused = true)]
     #[traced_test]
     async fn test_appends_correctly_handle_reconfiguration() -> googletest::Result<()> {
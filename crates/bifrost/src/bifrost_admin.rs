@@ -9,20 +9,22 @@
 // by the Apache License, Version 2.0.
 
 use std::ops::Deref;
+use std::time::Duration;
 
-use tracing::{info, instrument};
+use tracing::{debug, info, instrument};
 
 use restate_core::{MetadataKind, MetadataWriter};
 use restate_metadata_store::MetadataStoreClient;
 use restate_types::config::Configuration;
 use restate_types::logs::builder::BuilderError;
 use restate_types::logs::metadata::{LogletParams, Logs, ProviderKind, SegmentIndex};
-use restate_types::logs::{LogId, Lsn, TailState};
+use restate_types::logs::{LogId, LogletOffset, Lsn, SequenceNumber, TailState};
 use restate_types::metadata_store::keys::BIFROST_CONFIG_KEY;
 use restate_types::Version;
 
 use crate::error::AdminError;
 use crate::loglet_wrapper::LogletWrapper;
+use crate::providers::tiered_storage_loglet;
 use crate::{Bifrost, Error, Result};
 
 /// Bifrost's Admin API
@@ -92,6 +94,9 @@ impl<'a> BifrostAdmin<'a> {
         params: LogletParams,
     ) -> Result<SealedSegment> {
         self.bifrost.inner.fail_if_shutting_down()?;
+        if provider == ProviderKind::TieredStorage {
+            return Err(AdminError::ReadOnlyProvider(provider).into());
+        }
         let _ = self
             .bifrost
             .inner
@@ -174,6 +179,162 @@ impl<'a> BifrostAdmin<'a> {
         })
     }
 
+    /// Offloads a sealed segment of the log to tiered storage.
+    ///
+    /// The records of the segment are copied into an immutable segment file in the shared
+    /// segments directory, after which the segment is served by the tiered-storage provider.
+    /// The original loglet is retired in the chain, its records are released by
+    /// [`Self::release_retired_loglets`]. Offloading a segment which has already been offloaded
+    /// is a no-op.
+    #[instrument(level = "debug", skip(self), err)]
+    pub async fn offload_segment(&self, log_id: LogId, segment_index: SegmentIndex) -> Result<()> {
+        self.bifrost.inner.fail_if_shutting_down()?;
+        let options = Configuration::pinned().bifrost.tiered_storage.clone();
+        let Some(segments_dir) = options.segments_dir() else {
+            return Err(AdminError::SegmentsDirNotConfigured.into());
+        };
+        // the tiered storage provider must be enabled to serve the offloaded segment
+        let _ = self
+            .bifrost
+            .inner
+            .provider_for(ProviderKind::TieredStorage)?;
+
+        let config = {
+            let logs = self.bifrost.inner.metadata.logs_ref();
+            let chain = logs.chain(&log_id).ok_or(Error::UnknownLogId(log_id))?;
+            if chain.tail_index() == segment_index {
+                return Err(AdminError::SegmentNotSealed(segment_index).into());
+            }
+            chain
+                .iter()
+                .find(|segment| segment.index() == segment_index)
+                .map(|segment| segment.config.clone())
+                .ok_or(AdminError::UnknownSegment(segment_index))?
+        };
+        if config.kind == ProviderKind::TieredStorage {
+            return Ok(());
+        }
+
+        let loglet = self
+            .bifrost
+            .inner
+            .provider_for(config.kind)?
+            .get_loglet(log_id, segment_index, &config.params)
+            .await?;
+        if !loglet.find_tail().await?.is_sealed() {
+            return Err(AdminError::SegmentNotSealed(segment_index).into());
+        }
+
+        let params = tiered_storage_loglet::offload_loglet(
+            loglet,
+            log_id,
+            segment_index,
+            segments_dir,
+            options.compression_level,
+        )
+        .await?;
+
+        let logs = self
+            .metadata_store_client
+            .read_modify_write(BIFROST_CONFIG_KEY.clone(), move |logs: Option<Logs>| {
+                let logs = logs.ok_or(Error::UnknownLogId(log_id))?;
+
+                let mut builder = logs.into_builder();
+                let mut chain_builder =
+                    builder.chain(&log_id).ok_or(Error::UnknownLogId(log_id))?;
+
+                match chain_builder.replace_sealed_segment(
+                    segment_index,
+                    ProviderKind::TieredStorage,
+                    params.clone(),
+                ) {
+                    Err(e) => match e {
+                        BuilderError::UnknownSegment(index) => {
+                            Err(Error::from(AdminError::UnknownSegment(index)))
+                        }
+                        BuilderError::TailSegment(index) => {
+                            Err(Error::from(AdminError::SegmentNotSealed(index)))
+                        }
+                        _ => unreachable!("the log must exist at this point"),
+                    },
+                    Ok(_) => Ok(builder.build()),
+                }
+            })
+            .await
+            .map_err(|e| e.transpose())?;
+
+        self.metadata_writer.update(logs).await?;
+        info!(
+            log_id = %log_id,
+            segment = %segment_index,
+            "Offloaded sealed segment to tiered storage"
+        );
+        Ok(())
+    }
+
+    /// Trims the retired loglets which have been replaced in their chain at least `grace_period`
+    /// ago, and removes them from the logs metadata.
+    ///
+    /// Nodes which haven't observed the updated chain yet, and readers which have been created
+    /// before, keep reading from a retired loglet. Its records are therefore only released once
+    /// all nodes had the time to observe the replacement. Retired loglets are part of the logs
+    /// metadata, so they are released even if the node which replaced them went away.
+    #[instrument(level = "debug", skip(self), err)]
+    pub async fn release_retired_loglets(&self, grace_period: Duration) -> Result<()> {
+        self.bifrost.inner.fail_if_shutting_down()?;
+        let retired_loglets: Vec<_> = self
+            .bifrost
+            .inner
+            .metadata
+            .logs_ref()
+            .iter()
+            .flat_map(|(log_id, chain)| {
+                chain
+                    .retired_loglets()
+                    .iter()
+                    .filter(|retired| retired.retired_at.elapsed() >= grace_period)
+                    .map(|retired| (*log_id, retired.config.clone()))
+            })
+            .collect();
+        if retired_loglets.is_empty() {
+            return Ok(());
+        }
+
+        for (log_id, config) in &retired_loglets {
+            let loglet = self
+                .bifrost
+                .inner
+                .provider_for(config.kind)?
+                .get_loglet(*log_id, config.index(), &config.params)
+                .await?;
+            loglet.trim(LogletOffset::MAX).await?;
+            debug!(
+                log_id = %log_id,
+                segment = %config.index(),
+                "Released the records of a retired loglet"
+            );
+        }
+
+        let logs = self
+            .metadata_store_client
+            .read_modify_write(BIFROST_CONFIG_KEY.clone(), |logs: Option<Logs>| {
+                let logs = logs.ok_or(Error::UnknownLogId(retired_loglets[0].0))?;
+
+                let mut builder = logs.into_builder();
+                for (log_id, config) in &retired_loglets {
+                    if let Some(mut chain_builder) = builder.chain(log_id) {
+                        chain_builder.remove_retired_loglet(config.index());
+                    }
+                }
+                Ok::<_, Error>(builder.build())
+            })
+            .await
+            .map_err(|e| e.transpose())?;
+
+        self.metadata_writer.update(logs).await?;
+        Ok(())
+    }
+
     /// Adds a segment to the end of the chain
     ///
     /// The loglet must be sealed first. This operations assumes that the loglet with
@@ -12,7 +12,7 @@ use std::sync::Arc;
 
 use restate_core::{ShutdownError, SyncError};
 use restate_types::errors::MaybeRetryableError;
-use restate_types::logs::metadata::SegmentIndex;
+use restate_types::logs::metadata::{ProviderKind, SegmentIndex};
 use restate_types::logs::{LogId, Lsn};
 
 use crate::loglet::OperationError;
@@ -60,6 +60,14 @@ pub enum AdminError {
         expected: SegmentIndex,
         found: SegmentIndex,
     },
+    #[error("segment {0} doesn't exist in the chain")]
+    UnknownSegment(SegmentIndex),
+    #[error("segment {0} is not sealed")]
+    SegmentNotSealed(SegmentIndex),
+    #[error("loglet provider '{0}' is read-only and can't be used for new segments")]
+    ReadOnlyProvider(ProviderKind),
+    #[error("segments can't be offloaded without a segments directory shared by all nodes")]
+    SegmentsDirNotConfigured,
 }
 
 impl From<OperationError> for Error {
@@ -8,12 +8,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{hash_map, HashMap};
+use std::collections::{hash_map, HashMap, HashSet};
 use std::fmt::Debug;
 use std::fmt::Display;
 
+use restate_types::locality::NodeLocation;
 use restate_types::nodes_config::{NodesConfiguration, StorageState};
-use restate_types::replicated_loglet::{NodeSet, ReplicationProperty};
+use restate_types::replicated_loglet::{LocationScope, NodeSet, ReplicationProperty};
 use restate_types::Merge;
 use restate_types::PlainNodeId;
 
@@ -24,8 +25,9 @@ use restate_types::PlainNodeId;
 ///
 /// The checker is created with default value of Attribute set on all nodes.
 ///
-/// **NOTE:** Currently, this will not perform any failure-domain-aware quorum
-/// checks, this will be implemented in the near future.
+/// Failure domains are derived from the location of the nodes in the nodes configuration. Nodes
+/// that don't define a location at a zone or region scope don't count towards the replication
+/// requirement of that scope.
 ///
 /// The utility provides two methods:
 /// - `check_write_quorum()`: Can be used to check if it'd be possible to replicate a record on the
@@ -45,6 +47,8 @@ pub struct NodeSetChecker<'a, Attribute> {
     node_attribute: HashMap<PlainNodeId, Attribute>,
     /// Mapping between node-id and its log-server storage state
     storage_states: HashMap<PlainNodeId, StorageState>,
+    /// Mapping between node-id and its location
+    node_locations: HashMap<PlainNodeId, NodeLocation>,
     replication_property: &'a ReplicationProperty,
 }
 
@@ -87,9 +91,20 @@ impl<'a, Attribute> NodeSetChecker<'a, Attribute> {
             .map(|node_id| (*node_id, attribute_factory(*node_id)))
             .collect();
 
+        let node_locations: HashMap<_, _> = storage_states
+            .keys()
+            .filter_map(|node_id| {
+                nodes_config
+                    .find_node_by_id(*node_id)
+                    .ok()
+                    .map(|config| (*node_id, config.location.clone()))
+            })
+            .collect();
+
         Self {
             node_attribute,
             storage_states,
+            node_locations,
             replication_property,
         }
     }
@@ -183,17 +198,27 @@ impl<'a, Attribute> NodeSetChecker<'a, Attribute> {
     where
         Predicate: Fn(&Attribute) -> bool,
     {
-        let filtered = self.node_attribute.iter().filter(|(node_id, v)| {
-            predicate(v)
-                && self
-                    .storage_states
-                    .get(node_id)
-                    .expect("node must be in node-set")
-                    // only consider nodes that are writeable.
-                    .can_write_to()
-        });
-        // todo(asoli): Location-aware quorum check
-        filtered.count() >= self.replication_property.num_copies().into()
+        let filtered: Vec<_> = self
+            .node_attribute
+            .iter()
+            .filter(|(node_id, v)| {
+                predicate(v)
+                    && self
+                        .storage_states
+                        .get(node_id)
+                        .expect("node must be in node-set")
+                        // only consider nodes that are writeable.
+                        .can_write_to()
+            })
+            .map(|(node_id, _)| *node_id)
+            .collect();
+
+        self.replication_property
+            .iter()
+            .all(|(scope, replication_factor)| {
+                self.count_domains(*scope, filtered.iter().copied())
+                    >= usize::from(*replication_factor)
+            })
     }
 
     /// Does any node matches the predicate?
@@ -227,47 +252,79 @@ impl<'a, Attribute> NodeSetChecker<'a, Attribute> {
     where
         Predicate: Fn(&Attribute) -> bool,
     {
-        let filtered = self
+        let matching: HashSet<_> = self
             .node_attribute
             .iter()
-            .filter(|(_, v)| predicate(v))
-            // `node_attribute` nodes must be in storage_states
-            .map(|(node_id, _)| self.storage_states.get(node_id).unwrap());
-
-        let mut authoritative = 0;
-        let mut non_authoritative = 0;
-        for state in filtered {
-            // at the moment, data-loss is the only non-authoritative state
-            if state.is_data_loss() {
-                non_authoritative += 1;
-            } else {
-                authoritative += 1;
-            }
-        }
+            .filter(|(node_id, v)| predicate(v) && self.storage_states.contains_key(*node_id))
+            .map(|(node_id, _)| *node_id)
+            .collect();
 
-        if self.storage_states.len() < usize::from(self.replication_property.num_copies()) {
-            // short-circuit to avoid overflow on subtraction
+        if self
+            .replication_property
+            .iter()
+            .any(|(scope, replication_factor)| {
+                self.count_domains(*scope, self.storage_states.keys().copied())
+                    < usize::from(*replication_factor)
+            })
+        {
+            // the nodeset cannot satisfy the replication property to begin with
             return FMajorityResult::None;
         }
 
-        // todo(asoli): Location-aware quorum check
-        let fmajority_requires: usize =
-            self.storage_states.len() - usize::from(self.replication_property.num_copies()) + 1;
-
-        if non_authoritative + authoritative < fmajority_requires {
+        if !self.is_fmajority(&matching) {
             // not enough nodes to form an f-majority
             return FMajorityResult::None;
         }
 
-        if non_authoritative > 0 {
+        // at the moment, data-loss is the only non-authoritative state
+        let authoritative: HashSet<_> = matching
+            .iter()
+            .filter(|node_id| !self.storage_states[*node_id].is_data_loss())
+            .copied()
+            .collect();
+
+        if authoritative.len() < matching.len() {
             // either BestEffort or SuccessWithRisk depends on how many authoritative nodes
-            if authoritative >= fmajority_requires {
+            if self.is_fmajority(&authoritative) {
                 return FMajorityResult::SuccessWithRisk;
             }
             return FMajorityResult::BestEffort;
         }
         FMajorityResult::Success
     }
+
+    /// The nodes form an f-majority if, for at least one of the scopes of the replication
+    /// property, the rest of the nodeset spans fewer failure domains than the replication factor
+    /// of that scope. That is, no write quorum could have been formed without any of the nodes.
+    fn is_fmajority(&self, nodes: &HashSet<PlainNodeId>) -> bool {
+        self.replication_property
+            .iter()
+            .any(|(scope, replication_factor)| {
+                let rest = self
+                    .storage_states
+                    .keys()
+                    .filter(|node_id| !nodes.contains(*node_id))
+                    .copied();
+                self.count_domains(*scope, rest) < usize::from(*replication_factor)
+            })
+    }
+
+    /// Counts the distinct failure domains of the nodes at the given scope. Every node is its own
+    /// domain at the node scope, otherwise nodes without a location at that scope are ignored.
+    fn count_domains(
+        &self,
+        scope: LocationScope,
+        nodes: impl IntoIterator<Item = PlainNodeId>,
+    ) -> usize {
+        match scope {
+            LocationScope::Node => nodes.into_iter().count(),
+            scope => nodes
+                .into_iter()
+                .filter_map(|node_id| self.node_locations.get(&node_id)?.domain(scope))
+                .collect::<HashSet<_>>()
+                .len(),
+        }
+    }
 }
 
 impl<'a, Attribute: Debug> Debug for NodeSetChecker<'a, Attribute> {
@@ -459,6 +516,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_replication_checker_zone_scope() -> Result<()> {
+        let mut nodes_config = NodesConfiguration::new(Version::MIN, "test-cluster".to_owned());
+        for (id, location) in [
+            (1, "us-east-1.az1"),
+            (2, "us-east-1.az1"),
+            (3, "us-east-1.az2"),
+            (4, "us-east-1.az2"),
+            (5, "us-east-1.az3"),
+            (6, "us-east-1.az3"),
+            // no location, doesn't count towards zone replication
+            (7, ""),
+        ] {
+            let mut node = generate_logserver_node(id, StorageState::ReadWrite);
+            node.location = location.parse().unwrap();
+            nodes_config.upsert_node(node);
+        }
+
+        let nodeset: NodeSet = (1..=7).collect();
+        let replication =
+            ReplicationProperty::with_scope(LocationScope::Zone, 2.try_into().unwrap());
+        let mut checker: NodeSetChecker<bool> =
+            NodeSetChecker::new(&nodeset, &nodes_config, &replication);
+
+        // two copies in the same zone are not enough
+        checker.set_attribute_on_each(&[PlainNodeId::new(1), PlainNodeId::new(2)], || true);
+        assert_that!(checker.check_write_quorum(|attr| *attr), eq(false));
+        checker.set_attribute(PlainNodeId::new(7), true);
+        assert_that!(checker.check_write_quorum(|attr| *attr), eq(false));
+
+        checker.set_attribute(PlainNodeId::new(3), true);
+        assert_that!(checker.check_write_quorum(|attr| *attr), eq(true));
+
+        // the rest of the nodeset (4, 5, 6) spans 2 zones, a write quorum could have been
+        // formed without the nodes 1, 2, 3 and 7.
+        assert_that!(
+            checker.check_fmajority(|attr| *attr),
+            eq(FMajorityResult::None)
+        );
+
+        // the rest of the nodeset (5, 6) spans a single zone
+        checker.set_attribute(PlainNodeId::new(4), true);
+        assert_that!(
+            checker.check_fmajority(|attr| *attr),
+            eq(FMajorityResult::Success)
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_dont_panic_on_replication_factor_exceeding_nodeset_size() {
         let nodes_config = generate_logserver_nodes_config(3, StorageState::ReadWrite);
@@ -192,9 +192,12 @@ mod tests {
     use googletest::prelude::*;
 
     use restate_types::nodes_config::StorageState;
-    use restate_types::PlainNodeId;
+    use restate_types::replicated_loglet::LocationScope;
+    use restate_types::{PlainNodeId, Version};
 
-    use crate::providers::replicated_loglet::test_util::generate_logserver_nodes_config;
+    use crate::providers::replicated_loglet::test_util::{
+        generate_logserver_node, generate_logserver_nodes_config,
+    };
 
     #[test]
     fn test_with_fixed_spread_selector() -> Result<()> {
@@ -285,4 +288,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_flood_spread_selector_zone_scope() -> Result<()> {
+        let mut nodes_config = NodesConfiguration::new(Version::MIN, "test-cluster".to_owned());
+        for id in 1..=3 {
+            let mut node = generate_logserver_node(id, StorageState::ReadWrite);
+            node.location = "us-east-1.az1".parse().unwrap();
+            nodes_config.upsert_node(node);
+        }
+        let replication =
+            ReplicationProperty::with_scope(LocationScope::Zone, 2.try_into().unwrap());
+        let nodeset: NodeSet = (1..=3).collect();
+        let selector = SpreadSelector::new(nodeset, SelectorStrategy::Flood, replication);
+        let mut rng = rand::thread_rng();
+
+        // all nodes are in the same zone
+        let spread = selector.select(&mut rng, &nodes_config, &NodeSet::empty());
+        assert_that!(
+            spread,
+            err(pat!(SpreadSelectorError::InsufficientWriteableNodes))
+        );
+
+        let mut node = generate_logserver_node(3, StorageState::ReadWrite);
+        node.location = "us-east-1.az2".parse().unwrap();
+        nodes_config.upsert_node(node);
+
+        let spread = selector.select(&mut rng, &nodes_config, &NodeSet::empty())?;
+        assert_that!(spread.len(), eq(3));
+
+        // excluding the only node in az2 makes the spread impossible
+        let spread = selector.select(&mut rng, &nodes_config, &NodeSet::from([3]));
+        assert_that!(
+            spread,
+            err(pat!(SpreadSelectorError::InsufficientWriteableNodes))
+        );
+
+        Ok(())
+    }
 }
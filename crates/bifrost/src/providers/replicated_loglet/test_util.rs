@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use restate_types::locality::NodeLocation;
 use restate_types::nodes_config::{
     LogServerConfig, NodeConfig, NodesConfiguration, Role, StorageState,
 };
@@ -24,6 +25,7 @@ pub fn generate_logserver_node(
         format!("unix:/tmp/my_socket-{}", id).parse().unwrap(),
         Role::LogServer.into(),
         LogServerConfig { storage_state },
+        NodeLocation::default(),
     )
 }
 
@@ -0,0 +1,270 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A read-only loglet provider for sealed segments which have been offloaded from their original
+//! provider into immutable, compressed segment files. The files are stored in a directory which
+//! has to be shared between nodes, e.g. a mounted blob/object store. This keeps long log
+//! histories readable without holding them in the log store.
+
+mod provider;
+mod read_stream;
+mod segment_file;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use restate_types::logs::metadata::{LogletParams, SegmentIndex};
+use restate_types::logs::{KeyFilter, LogId, LogletOffset, Record, SequenceNumber, TailState};
+
+use self::read_stream::TieredStorageReadStream;
+use self::segment_file::{SegmentFileError, SegmentFileHeader, SegmentFileWriter};
+use crate::loglet::util::TailOffsetWatch;
+use crate::loglet::{Loglet, LogletCommit, OperationError, SendableLogletReadStream};
+
+pub use provider::Factory;
+
+#[derive(derive_more::Debug)]
+pub(crate) struct TieredStorageLoglet {
+    path: PathBuf,
+    header: SegmentFileHeader,
+    #[debug(skip)]
+    tail_watch: TailOffsetWatch,
+}
+
+impl TieredStorageLoglet {
+    fn open(path: PathBuf) -> Result<Self, SegmentFileError> {
+        let header = SegmentFileHeader::read(&path)?;
+        Ok(Self {
+            path,
+            header,
+            // segment files are only created for sealed segments
+            tail_watch: TailOffsetWatch::new(TailState::new(true, header.tail)),
+        })
+    }
+}
+
+#[async_trait]
+impl Loglet for TieredStorageLoglet {
+    async fn create_read_stream(
+        self: Arc<Self>,
+        filter: KeyFilter,
+        from: LogletOffset,
+        to: Option<LogletOffset>,
+    ) -> Result<SendableLogletReadStream, OperationError> {
+        Ok(Box::pin(TieredStorageReadStream::create(
+            self.path.clone(),
+            filter,
+            from,
+            to,
+        )))
+    }
+
+    fn watch_tail(&self) -> BoxStream<'static, TailState<LogletOffset>> {
+        Box::pin(self.tail_watch.to_stream())
+    }
+
+    async fn enqueue_batch(
+        &self,
+        _payloads: Arc<[Record]>,
+    ) -> Result<LogletCommit, OperationError> {
+        Ok(LogletCommit::sealed())
+    }
+
+    async fn find_tail(&self) -> Result<TailState<LogletOffset>, OperationError> {
+        Ok(TailState::Sealed(self.header.tail))
+    }
+
+    async fn get_trim_point(&self) -> Result<Option<LogletOffset>, OperationError> {
+        if self.header.trim_point == LogletOffset::INVALID {
+            Ok(None)
+        } else {
+            Ok(Some(self.header.trim_point))
+        }
+    }
+
+    /// Segment files are immutable and kept to retain the log history, trimming them is a no-op.
+    /// Segment files which are no longer needed have to be removed from the segments directory
+    /// together with their segment in the chain.
+    async fn trim(&self, trim_point: LogletOffset) -> Result<(), OperationError> {
+        debug!(
+            path = %self.path.display(),
+            %trim_point,
+            "Ignoring trim of offloaded segment"
+        );
+        Ok(())
+    }
+
+    async fn seal(&self) -> Result<(), OperationError> {
+        // segment files are always sealed
+        Ok(())
+    }
+}
+
+/// Copies the records of the sealed `loglet` into a new segment file in `segments_dir` and returns
+/// the params of the tiered storage loglet which serves them. The segment file only becomes
+/// visible once all records have been written.
+pub(crate) async fn offload_loglet(
+    loglet: Arc<dyn Loglet>,
+    log_id: LogId,
+    segment_index: SegmentIndex,
+    segments_dir: &Path,
+    compression_level: i32,
+) -> Result<LogletParams, OperationError> {
+    let tail = loglet.find_tail().await?;
+    debug_assert!(tail.is_sealed());
+    let trim_point = loglet
+        .get_trim_point()
+        .await?
+        .unwrap_or(LogletOffset::INVALID)
+        .min(tail.offset().prev());
+    let header = SegmentFileHeader::new(trim_point, tail.offset());
+
+    // tiered-storage loglet params are the path of the segment file within the segments directory
+    let params = format!("{log_id}/{segment_index}.segment");
+    let path = segments_dir.join(&params);
+
+    let (tx, mut rx) = mpsc::channel::<(LogletOffset, Record)>(128);
+    let writer_task = tokio::task::spawn_blocking(move || {
+        let mut writer = SegmentFileWriter::create(path, header, compression_level)?;
+        while let Some((offset, record)) = rx.blocking_recv() {
+            writer.append(offset, &record)?;
+        }
+        writer.finish()
+    });
+
+    if trim_point.next() < tail.offset() {
+        let mut read_stream = loglet
+            .create_read_stream(
+                KeyFilter::Any,
+                trim_point.next(),
+                Some(tail.offset().prev()),
+            )
+            .await?;
+        while let Some(entry) = read_stream.next().await {
+            let entry = entry?;
+            let offset = entry.sequence_number();
+            // A trim gap means that the loglet has been trimmed concurrently, the writer rejects
+            // the incomplete segment file in this case.
+            let Some(record) = entry.into_record() else {
+                break;
+            };
+            if tx.send((offset, record)).await.is_err() {
+                // writer has failed
+                break;
+            }
+        }
+    }
+    drop(tx);
+
+    writer_task
+        .await
+        .map_err(OperationError::terminal)?
+        .map_err(OperationError::other)?;
+
+    Ok(LogletParams::from(params))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use googletest::prelude::*;
+    use test_log::test;
+
+    use restate_types::logs::Keys;
+
+    use super::*;
+    use crate::providers::memory_loglet::MemoryLoglet;
+
+    #[test(tokio::test)]
+    async fn offload_and_read_segment() -> googletest::Result<()> {
+        let segments_dir = tempfile::tempdir()?;
+
+        let source = MemoryLoglet::new(LogletParams::from("1".to_owned()));
+        let records: Vec<_> = (1..=10)
+            .map(|i| Record::from((format!("record-{i}").as_str(), Keys::Single(i % 2))))
+            .collect();
+        source.enqueue_batch(records.into()).await?.await?;
+        source.trim(LogletOffset::new(3)).await?;
+        source.seal().await?;
+
+        let params = offload_loglet(
+            source,
+            LogId::new(1),
+            SegmentIndex::from(0),
+            segments_dir.path(),
+            3,
+        )
+        .await?;
+        assert_that!(params.to_string(), eq("1/0.segment"));
+
+        let loglet = Arc::new(TieredStorageLoglet::open(
+            segments_dir.path().join(params.to_string()),
+        )?);
+        assert_that!(
+            loglet.find_tail().await?,
+            eq(TailState::Sealed(LogletOffset::new(11)))
+        );
+        assert_that!(
+            loglet.get_trim_point().await?,
+            some(eq(LogletOffset::new(3)))
+        );
+        assert!(loglet
+            .enqueue_batch(Arc::from(Vec::new()))
+            .await?
+            .await
+            .is_err());
+
+        // reading from the start yields a trim gap followed by the offloaded records
+        let entries: Vec<_> = Arc::clone(&loglet)
+            .create_read_stream(KeyFilter::Any, LogletOffset::OLDEST, None)
+            .await?
+            .try_collect()
+            .await?;
+        assert_that!(entries, len(eq(8)));
+        assert!(entries[0].is_trim_gap());
+        assert_that!(
+            entries[0].trim_gap_to_sequence_number(),
+            some(eq(LogletOffset::new(3)))
+        );
+        let offsets: Vec<_> = entries[1..]
+            .iter()
+            .map(|entry| u32::from(entry.sequence_number()))
+            .collect();
+        assert_that!(
+            offsets,
+            elements_are![eq(4), eq(5), eq(6), eq(7), eq(8), eq(9), eq(10)]
+        );
+        let first_record = entries.into_iter().nth(1).unwrap();
+        assert_that!(first_record.decode_unchecked::<String>(), eq("record-4"));
+
+        // bounded and filtered reads
+        let mut read_stream = Arc::clone(&loglet)
+            .create_read_stream(
+                KeyFilter::Include(1),
+                LogletOffset::new(5),
+                Some(LogletOffset::new(8)),
+            )
+            .await?;
+        let mut offsets = Vec::new();
+        while let Some(entry) = read_stream.next().await {
+            offsets.push(u32::from(entry?.sequence_number()));
+        }
+        assert_that!(offsets, elements_are![eq(5), eq(7)]);
+        assert!(read_stream.is_terminated());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{hash_map, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex as AsyncMutex;
+use tracing::debug;
+
+use restate_types::config::TieredStorageLogletOptions;
+use restate_types::live::BoxedLiveLoad;
+use restate_types::logs::metadata::{LogletParams, ProviderKind, SegmentIndex};
+use restate_types::logs::LogId;
+
+use super::TieredStorageLoglet;
+use crate::loglet::{Loglet, LogletProvider, LogletProviderFactory, OperationError};
+use crate::Error;
+
+#[derive(Debug, thiserror::Error)]
+#[error("the tiered storage loglet provider requires a segments directory shared by all nodes")]
+struct SegmentsDirNotConfigured;
+
+pub struct Factory {
+    options: BoxedLiveLoad<TieredStorageLogletOptions>,
+}
+
+impl Factory {
+    pub fn new(options: BoxedLiveLoad<TieredStorageLogletOptions>) -> Self {
+        Self { options }
+    }
+}
+
+#[async_trait]
+impl LogletProviderFactory for Factory {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::TieredStorage
+    }
+
+    async fn create(self: Box<Self>) -> Result<Arc<dyn LogletProvider>, OperationError> {
+        let Factory { mut options } = *self;
+        let segments_dir = options
+            .live_load()
+            .segments_dir()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| OperationError::terminal(SegmentsDirNotConfigured))?;
+        tokio::fs::create_dir_all(&segments_dir)
+            .await
+            .map_err(OperationError::terminal)?;
+        debug!(
+            segments_dir = %segments_dir.display(),
+            "Started a bifrost tiered storage loglet provider"
+        );
+        Ok(Arc::new(TieredStorageLogletProvider {
+            segments_dir,
+            active_loglets: Default::default(),
+        }))
+    }
+}
+
+struct TieredStorageLogletProvider {
+    segments_dir: PathBuf,
+    active_loglets: AsyncMutex<HashMap<(LogId, SegmentIndex), Arc<TieredStorageLoglet>>>,
+}
+
+#[async_trait]
+impl LogletProvider for TieredStorageLogletProvider {
+    async fn get_loglet(
+        &self,
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        params: &LogletParams,
+    ) -> Result<Arc<dyn Loglet>, Error> {
+        let mut guard = self.active_loglets.lock().await;
+        let loglet = match guard.entry((log_id, segment_index)) {
+            hash_map::Entry::Vacant(entry) => {
+                // NOTE: tiered-storage loglet params are the path of the segment file relative to
+                // the segments directory.
+                let path = self.segments_dir.join(params.to_string());
+                let loglet = tokio::task::spawn_blocking(move || TieredStorageLoglet::open(path))
+                    .await
+                    .map_err(OperationError::terminal)?
+                    .map_err(OperationError::other)?;
+                let loglet = entry.insert(Arc::new(loglet));
+                Arc::clone(loglet)
+            }
+            hash_map::Entry::Occupied(entry) => entry.get().clone(),
+        };
+
+        Ok(loglet as Arc<dyn Loglet>)
+    }
+
+    async fn shutdown(&self) -> Result<(), OperationError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::{Path, PathBuf};
+use std::task::{ready, Poll};
+
+use futures::{FutureExt, Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+
+use restate_types::logs::{KeyFilter, LogletOffset, MatchKeyQuery, SequenceNumber};
+
+use super::segment_file::SegmentFileReader;
+use crate::loglet::{LogletReadStream, OperationError};
+use crate::LogEntry;
+
+/// Number of records which are decoded ahead of the consumer of the stream.
+const READ_AHEAD_RECORDS: usize = 128;
+
+/// Reads the records of a segment file on a blocking thread. Reading starts at the compressed
+/// frame of the segment file which contains `from`, the frames before it are not touched.
+pub(crate) struct TieredStorageReadStream {
+    // the next record this stream will attempt to return when polled
+    read_pointer: LogletOffset,
+    rx_stream: ReceiverStream<Result<LogEntry<LogletOffset>, OperationError>>,
+    // the reader stops on its own once the stream is dropped, since it can't send any more records
+    reader_task: JoinHandle<Result<(), OperationError>>,
+    terminated: bool,
+}
+
+impl TieredStorageReadStream {
+    pub fn create(
+        path: PathBuf,
+        filter: KeyFilter,
+        from: LogletOffset,
+        to: Option<LogletOffset>,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(READ_AHEAD_RECORDS);
+        let reader_task =
+            tokio::task::spawn_blocking(move || read_segment_file(&path, filter, from, to, &tx));
+
+        Self {
+            read_pointer: from,
+            rx_stream: ReceiverStream::new(rx),
+            reader_task,
+            terminated: false,
+        }
+    }
+}
+
+fn read_segment_file(
+    path: &Path,
+    filter: KeyFilter,
+    mut from: LogletOffset,
+    to: Option<LogletOffset>,
+    tx: &mpsc::Sender<Result<LogEntry<LogletOffset>, OperationError>>,
+) -> Result<(), OperationError> {
+    let mut reader = SegmentFileReader::open(path).map_err(OperationError::other)?;
+    let header = *reader.header();
+    // the segment is sealed, there is nothing to read after its last record
+    let read_to = to.map_or(header.tail.prev(), |to| to.min(header.tail.prev()));
+    if from > read_to {
+        return Ok(());
+    }
+
+    if from <= header.trim_point {
+        if tx
+            .blocking_send(Ok(LogEntry::new_trim_gap(from, header.trim_point)))
+            .is_err()
+        {
+            // read stream has been dropped
+            return Ok(());
+        }
+        from = header.trim_point.next();
+    }
+
+    reader.seek(from).map_err(OperationError::other)?;
+    while let Some((offset, record)) = reader.next_record().map_err(OperationError::other)? {
+        if offset > read_to {
+            break;
+        }
+        if !record.matches_key_query(&filter) {
+            continue;
+        }
+        if tx
+            .blocking_send(Ok(LogEntry::new_data(offset, record)))
+            .is_err()
+        {
+            // read stream has been dropped
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+impl LogletReadStream for TieredStorageReadStream {
+    /// Current read pointer. This points to the next offset to be read.
+    fn read_pointer(&self) -> LogletOffset {
+        self.read_pointer
+    }
+    /// Returns true if the stream is terminated.
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl Stream for TieredStorageReadStream {
+    type Item = Result<LogEntry<LogletOffset>, OperationError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        match ready!(self.rx_stream.poll_next_unpin(cx)) {
+            Some(Ok(entry)) => {
+                self.read_pointer = entry.next_sequence_number();
+                Poll::Ready(Some(Ok(entry)))
+            }
+            Some(Err(err)) => {
+                self.terminated = true;
+                self.rx_stream.close();
+                Poll::Ready(Some(Err(err)))
+            }
+            None => {
+                // all records have been read, but did the reader fail?
+                let reader_result = ready!(self.reader_task.poll_unpin(cx));
+                self.terminated = true;
+                match reader_result {
+                    Ok(Ok(())) => Poll::Ready(None),
+                    Ok(Err(err)) => Poll::Ready(Some(Err(err))),
+                    Err(join_err) => Poll::Ready(Some(Err(OperationError::terminal(join_err)))),
+                }
+            }
+        }
+    }
+}
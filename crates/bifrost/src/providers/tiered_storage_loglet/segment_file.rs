@@ -0,0 +1,453 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use restate_types::errors::MaybeRetryableError;
+use restate_types::logs::{LogletOffset, Record, SequenceNumber};
+use restate_types::storage::{decode_from_flexbuffers, encode_as_flexbuffers};
+
+const MAGIC: [u8; 4] = *b"RSGF";
+const FORMAT_VERSION: u16 = 2;
+const HEADER_SIZE: u64 = 16;
+const FOOTER_SIZE: u64 = 16;
+const FRAME_INDEX_ENTRY_SIZE: usize = 12;
+
+/// Uncompressed size after which a frame is completed. Reads start at the frame which contains
+/// the requested offset, so this bounds the records which have to be skipped.
+const TARGET_FRAME_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SegmentFileError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("not a segment file or unsupported format version")]
+    InvalidHeader,
+    #[error("invalid frame index in segment file")]
+    InvalidFrameIndex,
+    #[error("expected record at offset {expected} but got {found}")]
+    UnexpectedOffset {
+        expected: LogletOffset,
+        found: LogletOffset,
+    },
+    #[error(transparent)]
+    Encode(#[from] flexbuffers::SerializationError),
+    #[error(transparent)]
+    Decode(#[from] flexbuffers::DeserializationError),
+}
+
+impl MaybeRetryableError for SegmentFileError {
+    fn retryable(&self) -> bool {
+        match self {
+            SegmentFileError::Io(_) => true,
+            SegmentFileError::InvalidHeader => false,
+            SegmentFileError::InvalidFrameIndex => false,
+            SegmentFileError::UnexpectedOffset { .. } => false,
+            SegmentFileError::Encode(_) => false,
+            SegmentFileError::Decode(_) => false,
+        }
+    }
+}
+
+/// Uncompressed header at the beginning of every segment file. The records of the segment
+/// follow the header as a sequence of independent zstd frames, each record being
+/// length-prefixed flexbuffers. The frames are followed by an uncompressed index of the first
+/// offset and the file position of every frame, which allows reading from any offset by only
+/// decompressing the frame which contains it.
+///
+/// The file layout is (little-endian):
+///    [4 bytes]   Magic
+///    [2 bytes]   Format version
+///    [2 bytes]   Reserved
+///    [4 bytes]   Trim point, records up to and including it are not part of the file
+///    [4 bytes]   Tail, the offset after the last record in the file
+///    [n bytes]   zstd frames
+///    [12 bytes]  Per frame: first offset (4 bytes) and file position (8 bytes)
+///    [8 bytes]   File position of the frame index
+///    [4 bytes]   Number of frames
+///    [4 bytes]   Magic
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct SegmentFileHeader {
+    pub trim_point: LogletOffset,
+    pub tail: LogletOffset,
+}
+
+impl SegmentFileHeader {
+    pub fn new(trim_point: LogletOffset, tail: LogletOffset) -> Self {
+        debug_assert!(trim_point < tail);
+        Self { trim_point, tail }
+    }
+
+    /// Reads the header of the segment file at `path` without touching its records.
+    pub fn read(path: &Path) -> Result<Self, SegmentFileError> {
+        Self::read_from(&mut File::open(path)?)
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Self, SegmentFileError> {
+        let mut header = [0u8; HEADER_SIZE as usize];
+        reader.read_exact(&mut header)?;
+        if header[0..4] != MAGIC
+            || u16::from_le_bytes(header[4..6].try_into().unwrap()) != FORMAT_VERSION
+        {
+            return Err(SegmentFileError::InvalidHeader);
+        }
+        let trim_point = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let tail = u32::from_le_bytes(header[12..16].try_into().unwrap());
+        if trim_point >= tail {
+            return Err(SegmentFileError::InvalidHeader);
+        }
+
+        Ok(Self::new(
+            LogletOffset::new(trim_point),
+            LogletOffset::new(tail),
+        ))
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&[0u8; 2])?;
+        writer.write_all(&u32::from(self.trim_point).to_le_bytes())?;
+        writer.write_all(&u32::from(self.tail).to_le_bytes())
+    }
+}
+
+/// Location of a zstd frame in the segment file.
+#[derive(Debug, Clone, Copy)]
+struct FrameIndexEntry {
+    /// Offset of the first record in the frame
+    first_offset: LogletOffset,
+    /// File position at which the frame starts
+    position: u64,
+}
+
+/// Writes the records of a sealed segment to a new segment file. Records are written to a
+/// temporary file which only replaces the target file once all records have been written.
+pub(crate) struct SegmentFileWriter {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    header: SegmentFileHeader,
+    compression_level: i32,
+    writer: BufWriter<File>,
+    // file position after the last completed frame
+    position: u64,
+    frame_index: Vec<FrameIndexEntry>,
+    // uncompressed records of the current frame
+    frame_buffer: BytesMut,
+    next_offset: LogletOffset,
+}
+
+impl SegmentFileWriter {
+    pub fn create(
+        path: PathBuf,
+        header: SegmentFileHeader,
+        compression_level: i32,
+    ) -> Result<Self, SegmentFileError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = {
+            let mut tmp_path = OsString::from(path.as_os_str());
+            tmp_path.push(".tmp");
+            PathBuf::from(tmp_path)
+        };
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        header.write_to(&mut writer)?;
+
+        Ok(Self {
+            path,
+            tmp_path,
+            header,
+            compression_level,
+            writer,
+            position: HEADER_SIZE,
+            frame_index: Vec::new(),
+            frame_buffer: BytesMut::new(),
+            next_offset: header.trim_point.next(),
+        })
+    }
+
+    /// Appends the record at `offset`, records must be appended in offset order without gaps.
+    pub fn append(
+        &mut self,
+        offset: LogletOffset,
+        record: &Record,
+    ) -> Result<(), SegmentFileError> {
+        if offset != self.next_offset || offset >= self.header.tail {
+            return Err(SegmentFileError::UnexpectedOffset {
+                expected: self.next_offset,
+                found: offset,
+            });
+        }
+
+        if self.frame_buffer.is_empty() {
+            self.frame_index.push(FrameIndexEntry {
+                first_offset: offset,
+                position: self.position,
+            });
+        }
+        encode_as_flexbuffers(record, &mut self.frame_buffer)?;
+        self.next_offset = offset.next();
+
+        if self.frame_buffer.len() >= TARGET_FRAME_SIZE {
+            self.complete_frame()?;
+        }
+        Ok(())
+    }
+
+    fn complete_frame(&mut self) -> Result<(), SegmentFileError> {
+        if self.frame_buffer.is_empty() {
+            return Ok(());
+        }
+
+        let frame = zstd::bulk::compress(&self.frame_buffer, self.compression_level)?;
+        self.writer.write_all(&frame)?;
+        self.position += frame.len() as u64;
+        self.frame_buffer.clear();
+        Ok(())
+    }
+
+    /// Completes the segment file. Fails if not all records up to the tail have been appended.
+    pub fn finish(mut self) -> Result<(), SegmentFileError> {
+        if self.next_offset != self.header.tail {
+            return Err(SegmentFileError::UnexpectedOffset {
+                expected: self.next_offset,
+                found: self.header.tail,
+            });
+        }
+        self.complete_frame()?;
+
+        for entry in &self.frame_index {
+            self.writer
+                .write_all(&u32::from(entry.first_offset).to_le_bytes())?;
+            self.writer.write_all(&entry.position.to_le_bytes())?;
+        }
+        self.writer.write_all(&self.position.to_le_bytes())?;
+        self.writer
+            .write_all(&(self.frame_index.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&MAGIC)?;
+
+        let file = self.writer.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+        std::fs::rename(&self.tmp_path, &self.path)?;
+        if let Some(parent) = self.path.parent() {
+            File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the records of a segment file in offset order, starting from any offset.
+pub(crate) struct SegmentFileReader {
+    header: SegmentFileHeader,
+    file: File,
+    frame_index: Vec<FrameIndexEntry>,
+    // file position of the frame index, which is where the last frame ends
+    frame_index_position: u64,
+    // index of the frame which is read once the current frame is exhausted
+    next_frame: usize,
+    // decompressed records of the current frame which haven't been read yet
+    frame: Bytes,
+    next_offset: LogletOffset,
+}
+
+impl SegmentFileReader {
+    pub fn open(path: &Path) -> Result<Self, SegmentFileError> {
+        let mut file = File::open(path)?;
+        let header = SegmentFileHeader::read_from(&mut file)?;
+
+        let mut footer = [0u8; FOOTER_SIZE as usize];
+        let file_size = file.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))? + FOOTER_SIZE;
+        file.read_exact(&mut footer)?;
+        let frame_index_position = u64::from_le_bytes(footer[0..8].try_into().unwrap());
+        let num_frames = u32::from_le_bytes(footer[8..12].try_into().unwrap()) as usize;
+        if footer[12..16] != MAGIC
+            || frame_index_position < HEADER_SIZE
+            || frame_index_position + (num_frames * FRAME_INDEX_ENTRY_SIZE) as u64 + FOOTER_SIZE
+                != file_size
+        {
+            return Err(SegmentFileError::InvalidFrameIndex);
+        }
+
+        let mut frame_index_bytes = vec![0u8; num_frames * FRAME_INDEX_ENTRY_SIZE];
+        file.seek(SeekFrom::Start(frame_index_position))?;
+        file.read_exact(&mut frame_index_bytes)?;
+        let frame_index: Vec<_> = frame_index_bytes
+            .chunks_exact(FRAME_INDEX_ENTRY_SIZE)
+            .map(|entry| FrameIndexEntry {
+                first_offset: LogletOffset::new(u32::from_le_bytes(
+                    entry[0..4].try_into().unwrap(),
+                )),
+                position: u64::from_le_bytes(entry[4..12].try_into().unwrap()),
+            })
+            .collect();
+
+        // frames are stored in offset order, and the first one starts right after the trim point
+        let is_valid_frame_index = match (frame_index.first(), frame_index.last()) {
+            (Some(first), Some(last)) => {
+                first.first_offset == header.trim_point.next()
+                    && first.position == HEADER_SIZE
+                    && last.first_offset < header.tail
+                    && last.position < frame_index_position
+            }
+            _ => header.trim_point.next() == header.tail,
+        } && frame_index.windows(2).all(|frames| {
+            frames[0].first_offset < frames[1].first_offset
+                && frames[0].position < frames[1].position
+        });
+        if !is_valid_frame_index {
+            return Err(SegmentFileError::InvalidFrameIndex);
+        }
+
+        Ok(Self {
+            header,
+            file,
+            frame_index,
+            frame_index_position,
+            next_frame: 0,
+            frame: Bytes::new(),
+            next_offset: header.trim_point.next(),
+        })
+    }
+
+    pub fn header(&self) -> &SegmentFileHeader {
+        &self.header
+    }
+
+    /// Positions the reader at `offset`, only the frame which contains it is decompressed.
+    /// Offsets before the first record of the segment position it at the first record.
+    pub fn seek(&mut self, offset: LogletOffset) -> Result<(), SegmentFileError> {
+        let offset = offset.max(self.header.trim_point.next());
+        if offset >= self.header.tail {
+            self.next_offset = self.header.tail;
+            return Ok(());
+        }
+
+        let frame = self
+            .frame_index
+            .partition_point(|frame| frame.first_offset <= offset)
+            - 1;
+        self.read_frame(frame)?;
+
+        // skip the records of the frame which precede the offset
+        while self.next_offset < offset {
+            if self.frame.remaining() < 4 {
+                return Err(SegmentFileError::InvalidFrameIndex);
+            }
+            let length = 4 + u32::from_le_bytes(self.frame[0..4].try_into().unwrap()) as usize;
+            if self.frame.remaining() < length {
+                return Err(SegmentFileError::InvalidFrameIndex);
+            }
+            self.frame.advance(length);
+            self.next_offset = self.next_offset.next();
+        }
+        Ok(())
+    }
+
+    /// Reads the next record. Returns `None` once all records of the segment have been read.
+    pub fn next_record(&mut self) -> Result<Option<(LogletOffset, Record)>, SegmentFileError> {
+        if self.next_offset >= self.header.tail {
+            return Ok(None);
+        }
+
+        if !self.frame.has_remaining() {
+            if self.next_frame >= self.frame_index.len() {
+                return Err(SegmentFileError::InvalidFrameIndex);
+            }
+            self.read_frame(self.next_frame)?;
+        }
+
+        let record: Record = decode_from_flexbuffers(&mut self.frame)?;
+        let offset = self.next_offset;
+        self.next_offset = offset.next();
+        Ok(Some((offset, record)))
+    }
+
+    /// Decompresses the frame at `frame` of the frame index.
+    fn read_frame(&mut self, frame: usize) -> Result<(), SegmentFileError> {
+        let entry = self.frame_index[frame];
+        let frame_end = self
+            .frame_index
+            .get(frame + 1)
+            .map_or(self.frame_index_position, |next| next.position);
+
+        let mut compressed = vec![0u8; (frame_end - entry.position) as usize];
+        self.file.seek(SeekFrom::Start(entry.position))?;
+        self.file.read_exact(&mut compressed)?;
+
+        self.frame = zstd::stream::decode_all(compressed.as_slice())?.into();
+        self.next_frame = frame + 1;
+        self.next_offset = entry.first_offset;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use restate_types::logs::Keys;
+
+    use super::*;
+
+    #[test]
+    fn read_records_from_any_offset() -> googletest::Result<()> {
+        let segments_dir = tempfile::tempdir()?;
+        let path = segments_dir.path().join("0.segment");
+        let header = SegmentFileHeader::new(LogletOffset::new(10), LogletOffset::new(5011));
+        // large records so that the segment spans multiple frames
+        let padding = "x".repeat(1024);
+
+        let mut writer = SegmentFileWriter::create(path.clone(), header, 3)?;
+        for i in 11..5011 {
+            writer.append(
+                LogletOffset::new(i),
+                &Record::from((format!("{i}-{padding}").as_str(), Keys::None)),
+            )?;
+        }
+        writer.finish()?;
+        assert_that!(SegmentFileHeader::read(&path)?, eq(header));
+
+        let mut reader = SegmentFileReader::open(&path)?;
+        assert_that!(reader.frame_index.len(), ge(4));
+
+        for from in [1, 11, 12, 2000, 4095, 5010] {
+            reader.seek(LogletOffset::new(from))?;
+            let mut expected_offset = from.max(11);
+            for _ in 0..3 {
+                let Some((offset, record)) = reader.next_record()? else {
+                    break;
+                };
+                assert_that!(offset, eq(LogletOffset::new(expected_offset)));
+                assert_that!(
+                    record.decode::<String>()?,
+                    eq(format!("{expected_offset}-{padding}"))
+                );
+                expected_offset += 1;
+            }
+            assert_that!(expected_offset, eq((from.max(11) + 3).min(5011)));
+        }
+
+        // reading past the last frame ends the segment
+        reader.seek(LogletOffset::new(5010))?;
+        assert!(reader.next_record()?.is_some());
+        assert!(reader.next_record()?.is_none());
+        reader.seek(LogletOffset::new(6000))?;
+        assert!(reader.next_record()?.is_none());
+
+        Ok(())
+    }
+}
@@ -24,6 +24,7 @@ use crate::bifrost::BifrostInner;
 use crate::providers::local_loglet;
 #[cfg(any(test, feature = "memory-loglet"))]
 use crate::providers::memory_loglet;
+use crate::providers::tiered_storage_loglet;
 use crate::watchdog::{Watchdog, WatchdogCommand};
 use crate::{loglet::LogletProviderFactory, Bifrost};
 
@@ -76,6 +77,26 @@ impl BifrostService {
         self
     }
 
+    /// Enables the tiered storage loglet provider, if a segments directory has been configured.
+    /// Without it, this node can't read the offloaded segments of a log.
+    pub fn enable_tiered_storage_loglet(mut self, config: &Live<Configuration>) -> Self {
+        if config
+            .pinned()
+            .bifrost
+            .tiered_storage
+            .segments_dir()
+            .is_none()
+        {
+            return self;
+        }
+
+        let factory = tiered_storage_loglet::Factory::new(
+            config.clone().map(|c| &c.bifrost.tiered_storage).boxed(),
+        );
+        self.factories.insert(factory.kind(), Box::new(factory));
+        self
+    }
+
     pub fn handle(&self) -> Bifrost {
         self.bifrost.clone()
     }
@@ -575,6 +575,7 @@ mod tests {
     use test_log::test;
 
     use restate_test_util::assert_eq;
+    use restate_types::locality::NodeLocation;
     use restate_types::net::AdvertisedAddress;
     use restate_types::nodes_config::{LogServerConfig, NodeConfig, Role};
     use restate_types::{GenerationalNodeId, Version};
@@ -746,6 +747,7 @@ mod tests {
             address,
             roles,
             LogServerConfig::default(),
+            NodeLocation::default(),
         );
         nodes_config.upsert_node(my_node);
         nodes_config
@@ -768,6 +768,7 @@ mod tests {
     use tokio::sync::mpsc;
 
     use restate_test_util::{assert_eq, let_assert};
+    use restate_types::locality::NodeLocation;
     use restate_types::net::codec::WireDecode;
     use restate_types::net::metadata::{GetMetadataRequest, MetadataMessage};
     use restate_types::net::node::GetNodeState;
@@ -1019,6 +1020,7 @@ mod tests {
             AdvertisedAddress::Uds("foobar1".into()),
             Role::Worker.into(),
             LogServerConfig::default(),
+            NodeLocation::default(),
         );
         nodes_config.upsert_node(node_config);
 
@@ -19,6 +19,7 @@ mod network_sender;
 mod networking;
 pub mod protobuf;
 pub mod rpc_router;
+pub mod tls;
 pub mod transport_connector;
 mod types;
 
@@ -28,6 +29,7 @@ pub use error::*;
 pub use message_router::*;
 pub use network_sender::*;
 pub use networking::Networking;
+pub use tls::{TlsAcceptor, TlsError};
 pub use transport_connector::{GrpcConnector, TransportConnect};
 pub use types::*;
 
@@ -19,17 +19,28 @@ use http::Uri;
 use hyper::body::{Body, Incoming};
 use hyper::rt::{Read, Write};
 use hyper_util::rt::TokioIo;
-use tokio::io;
+use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::net::{TcpListener, UnixListener, UnixStream};
 use tokio_util::net::Listener;
-use tonic::transport::{Channel, Endpoint};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
 use tracing::{debug, info, instrument, Span};
 
 use restate_types::errors::GenericError;
 use restate_types::net::{AdvertisedAddress, BindAddress};
 
+use super::tls::TlsAcceptor;
+
 pub fn create_tonic_channel_from_advertised_address(address: AdvertisedAddress) -> Channel {
-    match address {
+    create_tonic_channel(address, None).expect("channel without TLS can be created")
+}
+
+/// Creates a channel to the given address. Connections to `https` addresses use the given TLS
+/// configuration, unix domain sockets are always connected without TLS.
+pub fn create_tonic_channel(
+    address: AdvertisedAddress,
+    tls_config: Option<ClientTlsConfig>,
+) -> Result<Channel, tonic::transport::Error> {
+    let channel = match address {
         AdvertisedAddress::Uds(uds_path) => {
             // dummy endpoint required to specify an uds connector, it is not used anywhere
             Endpoint::try_from("http://127.0.0.1")
@@ -43,13 +54,18 @@ pub fn create_tonic_channel_from_advertised_address(address: AdvertisedAddress)
         }
         AdvertisedAddress::Http(uri) => {
             // todo: Make the channel settings configurable
-            Channel::builder(uri)
+            let mut endpoint = Channel::builder(uri)
                 .connect_timeout(Duration::from_secs(5))
                 // todo: configure the channel from configuration file
-                .http2_adaptive_window(true)
-                .connect_lazy()
+                .http2_adaptive_window(true);
+            if let Some(tls_config) = tls_config {
+                endpoint = endpoint.tls_config(tls_config)?;
+            }
+            endpoint.connect_lazy()
         }
-    }
+    };
+
+    Ok(channel)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -66,6 +82,8 @@ pub enum Error {
         #[source]
         source: io::Error,
     },
+    #[error("failed TLS handshake: {0}")]
+    TlsHandshake(#[source] io::Error),
     #[error("failed handling hyper connection: {0}")]
     HandlingConnection(#[from] GenericError),
     #[error("failed listening on incoming connections: {0}")]
@@ -77,6 +95,7 @@ pub enum Error {
 #[instrument(level = "info", skip_all, fields(server_name = %server_name, uds.path = tracing::field::Empty, net.host.addr = tracing::field::Empty, net.host.port = tracing::field::Empty))]
 pub async fn run_hyper_server<S, B>(
     bind_address: &BindAddress,
+    tls_acceptor: Option<TlsAcceptor>,
     service: S,
     server_name: &'static str,
     on_bind: impl Fn(),
@@ -108,7 +127,8 @@ where
             info!("Server listening");
             on_bind();
 
-            run_listener_loop(unix_listener, service, server_name).await?;
+            // unix domain sockets are local to the node and never use TLS
+            run_listener_loop(unix_listener, None, service, server_name).await?;
         }
         BindAddress::Socket(socket_addr) => {
             let tcp_listener =
@@ -129,7 +149,7 @@ where
             info!("Server listening");
             on_bind();
 
-            run_listener_loop(tcp_listener, service, server_name).await?;
+            run_listener_loop(tcp_listener, tls_acceptor, service, server_name).await?;
         }
     }
     on_stop();
@@ -141,12 +161,13 @@ where
 
 async fn run_listener_loop<L, S, B>(
     mut listener: L,
+    tls_acceptor: Option<TlsAcceptor>,
     service: S,
     server_name: &'static str,
 ) -> Result<(), Error>
 where
     L: Listener,
-    L::Io: Send + Unpin + 'static,
+    L::Io: AsyncRead + AsyncWrite + Send + Unpin + 'static,
     L::Addr: Send + Debug + 'static,
     S: hyper::service::Service<http::Request<Incoming>, Response = hyper::Response<B>>
         + Send
@@ -169,11 +190,11 @@ where
             }
             incoming_connection = listener.accept() => {
                 let (stream, remote_addr) = incoming_connection?;
-                let io = TokioIo::new(stream);
                 debug!(?remote_addr, "Accepting incoming connection");
 
                 tc.spawn_child(TaskKind::RpcConnection, server_name, None, handle_connection(
-                    io,
+                    stream,
+                    tls_acceptor.clone(),
                     service.clone(),
                     executor.clone(),
                     remote_addr,
@@ -185,7 +206,39 @@ where
     Ok(())
 }
 
-async fn handle_connection<S, B, I, A>(
+async fn handle_connection<S, B, T, A>(
+    stream: T,
+    tls_acceptor: Option<TlsAcceptor>,
+    service: S,
+    executor: TaskCenterExecutor,
+    remote_addr: A,
+) -> anyhow::Result<()>
+where
+    S: hyper::service::Service<http::Request<Incoming>, Response = hyper::Response<B>>
+        + Send
+        + Clone
+        + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    S::Future: Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    T: AsyncRead + AsyncWrite + Unpin + 'static,
+    A: Send + Debug,
+{
+    match tls_acceptor {
+        Some(tls_acceptor) => {
+            let stream = tls_acceptor
+                .accept(stream)
+                .await
+                .map_err(Error::TlsHandshake)?;
+            serve_connection(TokioIo::new(stream), service, executor, remote_addr).await
+        }
+        None => serve_connection(TokioIo::new(stream), service, executor, remote_addr).await,
+    }
+}
+
+async fn serve_connection<S, B, I, A>(
     io: I,
     service: S,
     executor: TaskCenterExecutor,
@@ -0,0 +1,268 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use notify_debouncer_mini::{
+    new_debouncer, DebounceEventResult, DebouncedEvent, DebouncedEventKind,
+};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tracing::{error, info, warn};
+
+use restate_types::config::{TlsClientOptions, TlsServerOptions};
+
+const ALPN_H2: &[u8] = b"h2";
+const ALPN_HTTP_1_1: &[u8] = b"http/1.1";
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("failed reading '{path}': {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("no private key found in '{0}'")]
+    MissingPrivateKey(PathBuf),
+    #[error("invalid client CA certificates: {0}")]
+    ClientVerifier(#[from] rustls::server::VerifierBuilderError),
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Terminates TLS connections of a server.
+///
+/// The certificate, key and client CA files are watched and the acceptor picks up changes to
+/// them without restarting the server. Connections which are already established keep using the
+/// certificate they were accepted with.
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    config: Arc<ArcSwap<ServerConfig>>,
+    _watcher: Option<Arc<WatcherGuard>>,
+}
+
+impl TlsAcceptor {
+    pub fn from_options(options: &TlsServerOptions) -> Result<Self, TlsError> {
+        let config = Arc::new(ArcSwap::from_pointee(load_server_config(options)?));
+        let watcher = watch_server_config(options.clone(), Arc::downgrade(&config));
+        Ok(Self {
+            config,
+            _watcher: watcher.map(Arc::new),
+        })
+    }
+
+    pub async fn accept<IO>(&self, stream: IO) -> io::Result<TlsStream<IO>>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin,
+    {
+        tokio_rustls::TlsAcceptor::from(self.config.load_full())
+            .accept(stream)
+            .await
+    }
+}
+
+fn load_server_config(options: &TlsServerOptions) -> Result<ServerConfig, TlsError> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let builder = if let Some(client_ca_file) = &options.client_ca_file {
+        let mut roots = RootCertStore::empty();
+        for certificate in load_certificates(client_ca_file)? {
+            roots.add(certificate)?;
+        }
+        builder.with_client_cert_verifier(
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build()?,
+        )
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let mut config = builder.with_single_cert(
+        load_certificates(&options.cert_file)?,
+        load_private_key(&options.key_file)?,
+    )?;
+    config.alpn_protocols = vec![ALPN_H2.to_vec(), ALPN_HTTP_1_1.to_vec()];
+
+    Ok(config)
+}
+
+/// Loads the client TLS configuration used to connect to other nodes.
+pub fn load_client_tls_config(options: &TlsClientOptions) -> Result<ClientTlsConfig, TlsError> {
+    let mut config =
+        ClientTlsConfig::new().ca_certificate(Certificate::from_pem(read(&options.ca_file)?));
+
+    if let (Some(cert_file), Some(key_file)) = (&options.cert_file, &options.key_file) {
+        config = config.identity(Identity::from_pem(read(cert_file)?, read(key_file)?));
+    }
+
+    Ok(config)
+}
+
+enum WatcherEvent {
+    Changed(Vec<DebouncedEvent>),
+    Stop,
+}
+
+/// Stops the certificate watcher thread once the last clone of the acceptor has been dropped.
+struct WatcherGuard(Sender<WatcherEvent>);
+
+impl Drop for WatcherGuard {
+    fn drop(&mut self) {
+        // the watcher thread is gone if it failed
+        let _ = self.0.send(WatcherEvent::Stop);
+    }
+}
+
+fn watch_server_config(
+    options: TlsServerOptions,
+    config: Weak<ArcSwap<ServerConfig>>,
+) -> Option<WatcherGuard> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let guard = WatcherGuard(tx.clone());
+    let Ok(mut debouncer) = new_debouncer(
+        Duration::from_secs(3),
+        move |res: DebounceEventResult| match res {
+            Ok(events) => {
+                // the receiver is gone once the watcher thread has stopped
+                let _ = tx.send(WatcherEvent::Changed(events));
+            }
+            Err(e) => warn!("Error {:?}", e),
+        },
+    ) else {
+        warn!("Couldn't initialize certificate watcher, certificate changes will not be monitored");
+        return None;
+    };
+
+    // Certificates are commonly rotated by replacing the files, hence we watch the directories
+    // containing them.
+    let mut watched_files = vec![options.cert_file.clone(), options.key_file.clone()];
+    watched_files.extend(options.client_ca_file.clone());
+    let mut watched_dirs: Vec<&Path> = watched_files
+        .iter()
+        .map(|file| match file.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        })
+        .collect();
+    watched_dirs.sort();
+    watched_dirs.dedup();
+
+    for dir in watched_dirs {
+        info!(
+            "Installing watcher for certificate changes: {}",
+            dir.display()
+        );
+        if let Err(e) = debouncer
+            .watcher()
+            .watch(dir, notify::RecursiveMode::NonRecursive)
+        {
+            warn!("Couldn't install certificate watcher: {}", e);
+            return None;
+        }
+    }
+
+    std::thread::Builder::new()
+        .name("tls-cert-watcher".to_owned())
+        .spawn(move || {
+            // It's important that we capture the watcher in the thread,
+            // otherwise it'll be dropped and we won't be watching anything!
+            let _debouncer = debouncer;
+            loop {
+                let events = match rx.recv() {
+                    Ok(WatcherEvent::Changed(events)) => events,
+                    Ok(WatcherEvent::Stop) => {
+                        // acceptor has been dropped
+                        break;
+                    }
+                    Err(e) => {
+                        error!("Cannot continue watching certificate changes: '{}!", e);
+                        break;
+                    }
+                };
+
+                let Some(config) = config.upgrade() else {
+                    // acceptor has been dropped
+                    break;
+                };
+
+                // Any change in the watched directories triggers a reload, since mounted secrets
+                // are usually rotated by swapping symlinks rather than the files themselves.
+                if !events
+                    .iter()
+                    .any(|event| event.kind == DebouncedEventKind::Any)
+                {
+                    continue;
+                }
+
+                match load_server_config(&options) {
+                    Ok(new_config) => {
+                        info!(
+                            "Reloaded TLS certificate from '{}'",
+                            options.cert_file.display()
+                        );
+                        config.store(Arc::new(new_config));
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Error reloading TLS certificate, keeping the previous one: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        })
+        .expect("start certificate watcher thread");
+
+    Some(guard)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    rustls_pemfile::certs(&mut BufReader::new(open(path)?))
+        .collect::<Result<_, _>>()
+        .map_err(|source| TlsError::Read {
+            path: path.to_owned(),
+            source,
+        })
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, TlsError> {
+    rustls_pemfile::private_key(&mut BufReader::new(open(path)?))
+        .map_err(|source| TlsError::Read {
+            path: path.to_owned(),
+            source,
+        })?
+        .ok_or_else(|| TlsError::MissingPrivateKey(path.to_owned()))
+}
+
+fn open(path: &Path) -> Result<File, TlsError> {
+    File::open(path).map_err(|source| TlsError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, TlsError> {
+    std::fs::read(path).map_err(|source| TlsError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
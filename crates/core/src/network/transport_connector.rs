@@ -23,7 +23,8 @@ use restate_types::GenerationalNodeId;
 
 use super::protobuf::node_svc::node_svc_client::NodeSvcClient;
 use super::{NetworkError, ProtocolError};
-use crate::network::net_util::create_tonic_channel_from_advertised_address;
+use crate::network::net_util::create_tonic_channel;
+use crate::network::tls::load_client_tls_config;
 
 pub trait TransportConnect: Send + Sync + 'static {
     fn connect(
@@ -40,17 +41,35 @@ pub trait TransportConnect: Send + Sync + 'static {
 }
 
 pub struct GrpcConnector {
-    _networking_options: NetworkingOptions,
+    networking_options: NetworkingOptions,
     channel_cache: DashMap<AdvertisedAddress, Channel>,
 }
 
 impl GrpcConnector {
-    pub fn new(_networking_options: NetworkingOptions) -> Self {
+    pub fn new(networking_options: NetworkingOptions) -> Self {
         Self {
-            _networking_options,
+            networking_options,
             channel_cache: DashMap::new(),
         }
     }
+
+    fn create_channel(&self, address: AdvertisedAddress) -> Result<Channel, NetworkError> {
+        // The client certificate is loaded for every new channel, so that rotated certificates
+        // are picked up by new connections.
+        let tls_config = self
+            .networking_options
+            .tls
+            .as_ref()
+            .map(load_client_tls_config)
+            .transpose()
+            .map_err(|err| {
+                NetworkError::Unavailable(format!("cannot load TLS configuration: {err}"))
+            })?;
+
+        create_tonic_channel(address, tls_config).map_err(|err| {
+            NetworkError::Unavailable(format!("cannot configure TLS channel: {err}"))
+        })
+    }
 }
 
 impl TransportConnect for GrpcConnector {
@@ -69,11 +88,10 @@ impl TransportConnect for GrpcConnector {
         // Do we have a channel in cache for this address?
         let channel = match self.channel_cache.get(&address) {
             Some(channel) => channel.clone(),
-            None => self
-                .channel_cache
-                .entry(address.clone())
-                .or_insert_with(|| create_tonic_channel_from_advertised_address(address))
-                .clone(),
+            None => {
+                let channel = self.create_channel(address.clone())?;
+                self.channel_cache.entry(address).or_insert(channel).clone()
+            }
         };
 
         // Establish the connection
@@ -89,6 +89,10 @@ pub enum TaskKind {
     ConnectionReactor,
     Shuffle,
     Cleaner,
+    PausedServicesReconciler,
+    SchedulesReconciler,
+    KafkaSinksReconciler,
+    KafkaSinkEgress,
     MetadataStore,
     // -- Bifrost Tasks
     /// A background task that the system needs for its operation. The task requires a system
@@ -16,6 +16,7 @@ use futures::Stream;
 
 use restate_types::cluster_controller::{ReplicationStrategy, SchedulingPlan};
 use restate_types::config::NetworkingOptions;
+use restate_types::locality::NodeLocation;
 use restate_types::logs::metadata::{bootstrap_logs_metadata, ProviderKind};
 use restate_types::metadata_store::keys::{
     BIFROST_CONFIG_KEY, NODES_CONFIG_KEY, PARTITION_TABLE_KEY, SCHEDULING_PLAN_KEY,
@@ -287,6 +288,7 @@ pub fn create_mock_nodes_config(node_id: u32, generation: u32) -> NodesConfigura
         address,
         roles,
         LogServerConfig::default(),
+        NodeLocation::default(),
     );
     nodes_config.upsert_node(my_node);
     nodes_config
@@ -0,0 +1,137 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Publishes the records of the Kafka sinks. Records are written to the Kafka sink outbox of the
+//! partition when an invocation completes, and are produced by the leader once they are committed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rdkafka::error::KafkaError;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::ClientConfig;
+use tracing::debug;
+
+use restate_core::metadata;
+use restate_storage_api::kafka_sink_outbox_table::KafkaSinkRecord;
+use restate_types::config::Configuration;
+use restate_types::schema::kafka_sinks::{KafkaSink, KafkaSinkResolver};
+
+pub const INVOCATION_ID_HEADER: &str = "restate.invocation.id";
+pub const INVOCATION_TARGET_HEADER: &str = "restate.invocation.target";
+
+/// How long a record may wait in the producer queue before the delivery fails.
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to create the producer for Kafka sink '{0}': {1}")]
+    CreateProducer(String, #[source] KafkaError),
+    #[error("failed to produce the record of Kafka sink '{0}': {1}")]
+    Produce(String, #[source] KafkaError),
+    #[error("Kafka cluster '{1}' of Kafka sink '{0}' is not configured on this node")]
+    UnknownCluster(String, String),
+}
+
+/// Producers of the Kafka sinks, created lazily on the first record of every sink. Cloning is
+/// cheap and the clones share the producers.
+#[derive(Clone, Default)]
+pub struct KafkaSinkProducer {
+    producers: Arc<Mutex<HashMap<String, (KafkaSink, FutureProducer)>>>,
+}
+
+impl KafkaSinkProducer {
+    /// Produces the record, returning once it has been acknowledged by all the in-sync replicas.
+    ///
+    /// Records of sinks which have been removed in the meantime are dropped. Failing to produce
+    /// the record, including because the cluster of the sink is not configured on this node, is
+    /// retryable: the record is kept until the node configuration has been fixed.
+    pub async fn send(&self, record: &KafkaSinkRecord) -> Result<(), Error> {
+        let Some(sink) = metadata().schema().get_kafka_sink(&record.sink_id) else {
+            debug!(
+                restate.kafka_sink.id = %record.sink_id,
+                restate.invocation.id = %record.invocation_id,
+                "Dropping record of removed Kafka sink"
+            );
+            return Ok(());
+        };
+        let Some(producer) = self.producer(&sink)? else {
+            return Err(Error::UnknownCluster(
+                sink.id().to_owned(),
+                sink.cluster().to_owned(),
+            ));
+        };
+
+        let invocation_id = record.invocation_id.to_string();
+        let invocation_target = record.invocation_target.to_string();
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: INVOCATION_ID_HEADER,
+                value: Some(&invocation_id),
+            })
+            .insert(Header {
+                key: INVOCATION_TARGET_HEADER,
+                value: Some(&invocation_target),
+            });
+
+        let mut kafka_record = FutureRecord::<[u8], [u8]>::to(sink.topic())
+            .payload(record.value.as_ref())
+            .headers(headers);
+        if let Some(key) = record.invocation_target.key() {
+            kafka_record = kafka_record.key(key.as_bytes());
+        }
+
+        producer
+            .send(kafka_record, QUEUE_TIMEOUT)
+            .await
+            .map_err(|(err, _)| Error::Produce(sink.id().to_owned(), err))?;
+
+        Ok(())
+    }
+
+    /// Returns the producer of the sink, or `None` if its cluster is not configured. Producers
+    /// are re-created when the sink has been replaced.
+    fn producer(&self, sink: &KafkaSink) -> Result<Option<FutureProducer>, Error> {
+        let mut producers = self.producers.lock();
+        if let Some((cached_sink, producer)) = producers.get(sink.id()) {
+            if cached_sink == sink {
+                return Ok(Some(producer.clone()));
+            }
+        }
+
+        let configuration = Configuration::pinned();
+        let Some(cluster_options) = configuration.ingress.get_kafka_cluster(sink.cluster()) else {
+            return Ok(None);
+        };
+
+        let mut client_config = ClientConfig::new();
+        client_config.set("metadata.broker.list", cluster_options.brokers.join(","));
+        for (k, v) in &cluster_options.additional_options {
+            client_config.set(k, v);
+        }
+        for (k, v) in sink.options() {
+            client_config.set(k, v);
+        }
+        // The leader retries until the record has been acknowledged, the idempotent producer
+        // avoids duplicating the records retried by the producer itself.
+        client_config.set("enable.idempotence", "true");
+        client_config.set("acks", "all");
+
+        let producer: FutureProducer = client_config
+            .create()
+            .map_err(|err| Error::CreateProducer(sink.id().to_owned(), err))?;
+        producers.insert(sink.id().to_owned(), (sink.clone(), producer.clone()));
+
+        Ok(Some(producer))
+    }
+}
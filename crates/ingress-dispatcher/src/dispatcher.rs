@@ -25,7 +25,8 @@ use restate_types::net::codec::Targeted;
 use restate_types::net::ingress::IngressMessage;
 use restate_types::GenerationalNodeId;
 use restate_wal_protocol::{
-    append_envelope_to_bifrost, Command, Destination, Envelope, Header, Source,
+    append_envelope_to_bifrost, append_envelopes_to_bifrost, Command, Destination, Envelope,
+    Header, Source,
 };
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -39,6 +40,12 @@ pub trait DispatchIngressRequest {
         &self,
         ingress_request: IngressDispatcherRequest,
     ) -> impl std::future::Future<Output = Result<(), IngressDispatchError>> + Send;
+    /// Dispatches the requests, appending the requests of the same partition in batches. Returns
+    /// the result of every request, in the order of the given requests.
+    fn dispatch_ingress_requests(
+        &self,
+        ingress_requests: Vec<IngressDispatcherRequest>,
+    ) -> impl std::future::Future<Output = Vec<Result<(), IngressDispatchError>>> + Send;
 }
 
 #[derive(Default)]
@@ -91,6 +98,46 @@ impl DispatchIngressRequest for IngressDispatcher {
         &self,
         ingress_request: IngressDispatcherRequest,
     ) -> Result<(), IngressDispatchError> {
+        let envelope = self.prepare_envelope(ingress_request);
+        let (log_id, lsn) = append_envelope_to_bifrost(&self.bifrost, Arc::new(envelope)).await?;
+
+        debug!(
+            log_id = %log_id,
+            lsn = %lsn,
+            "Ingress request written to bifrost"
+        );
+        Ok(())
+    }
+
+    async fn dispatch_ingress_requests(
+        &self,
+        ingress_requests: Vec<IngressDispatcherRequest>,
+    ) -> Vec<Result<(), IngressDispatchError>> {
+        let envelopes = ingress_requests
+            .into_iter()
+            .map(|ingress_request| Arc::new(self.prepare_envelope(ingress_request)))
+            .collect();
+        let results: Vec<_> = append_envelopes_to_bifrost(&self.bifrost, envelopes)
+            .await
+            .into_iter()
+            .map(|result| {
+                result
+                    .map(|_| ())
+                    .map_err(IngressDispatchError::WalProtocolBatch)
+            })
+            .collect();
+
+        debug!(
+            "{} of {} ingress requests written to bifrost",
+            results.iter().filter(|result| result.is_ok()).count(),
+            results.len()
+        );
+        results
+    }
+}
+
+impl IngressDispatcher {
+    fn prepare_envelope(&self, ingress_request: IngressDispatcherRequest) -> Envelope {
         let IngressDispatcherRequest {
             inner,
             request_mode,
@@ -124,21 +171,13 @@ impl DispatchIngressRequest for IngressDispatcher {
 
         let partition_key = proxying_partition_key.unwrap_or_else(|| inner.partition_key());
 
-        let envelope = wrap_service_invocation_in_envelope(
+        wrap_service_invocation_in_envelope(
             partition_key,
             inner,
             metadata().my_node_id(),
             dedup_source,
             msg_index,
-        );
-        let (log_id, lsn) = append_envelope_to_bifrost(&self.bifrost, Arc::new(envelope)).await?;
-
-        debug!(
-            log_id = %log_id,
-            lsn = %lsn,
-            "Ingress request written to bifrost"
-        );
-        Ok(())
+        )
     }
 }
 
@@ -240,6 +279,9 @@ fn wrap_service_invocation_in_envelope(
             IngressDispatcherRequestInner::Attach(attach_invocation_req) => {
                 Command::AttachInvocation(attach_invocation_req)
             }
+            IngressDispatcherRequestInner::CompletePromise(promise_completion_req) => {
+                Command::CompletePromise(promise_completion_req)
+            }
         },
     )
 }
@@ -8,12 +8,17 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Arc;
+
 use restate_types::partition_table::PartitionTableError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum IngressDispatchError {
     #[error("bifrost error: {0}")]
     WalProtocol(#[from] restate_wal_protocol::Error),
+    /// Error shared by the requests which have been appended in the same batch.
+    #[error("bifrost error: {0}")]
+    WalProtocolBatch(Arc<restate_wal_protocol::Error>),
     #[error("partition routing error: {0}")]
     PartitionRoutingError(#[from] PartitionTableError),
 }
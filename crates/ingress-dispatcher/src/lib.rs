@@ -13,6 +13,7 @@ use std::future::Future;
 use std::hash::Hash;
 
 use bytes::Bytes;
+use bytestring::ByteString;
 use tokio::sync::oneshot;
 
 use restate_core::metadata;
@@ -22,8 +23,9 @@ use restate_types::identifiers::{
 use restate_types::ingress::IngressResponseResult;
 use restate_types::invocation::{
     AttachInvocationRequest, InvocationQuery, InvocationResponse, InvocationTarget,
-    InvocationTargetType, ServiceInvocation, ServiceInvocationResponseSink, SpanRelation,
-    SubmitNotificationSink, VirtualObjectHandlerType, WorkflowHandlerType,
+    InvocationTargetType, PromiseCompletionRequest, ResponseResult, ServiceInvocation,
+    ServiceInvocationResponseSink, SpanRelation, SubmitNotificationSink, VirtualObjectHandlerType,
+    WorkflowHandlerType,
 };
 use restate_types::message::MessageIndex;
 use restate_types::schema::subscriptions::{EventReceiverServiceType, Sink, Subscription};
@@ -49,6 +51,7 @@ enum IngressDispatcherRequestInner {
     ProxyThrough(ServiceInvocation),
     InvocationResponse(InvocationResponse),
     Attach(AttachInvocationRequest),
+    CompletePromise(PromiseCompletionRequest),
 }
 
 impl WithPartitionKey for IngressDispatcherRequestInner {
@@ -58,6 +61,7 @@ impl WithPartitionKey for IngressDispatcherRequestInner {
             IngressDispatcherRequestInner::ProxyThrough(si) => si.invocation_id.partition_key(),
             IngressDispatcherRequestInner::InvocationResponse(ir) => ir.id.partition_key(),
             IngressDispatcherRequestInner::Attach(iq) => iq.partition_key(),
+            IngressDispatcherRequestInner::CompletePromise(pc) => pc.partition_key(),
         }
     }
 }
@@ -147,6 +151,36 @@ impl IngressDispatcherRequest {
         )
     }
 
+    /// Completes the durable promise `key` of the workflow. The response is empty on success, or
+    /// a conflict failure if the promise has already been completed.
+    pub fn complete_promise(
+        workflow_target: InvocationTarget,
+        key: ByteString,
+        completion: ResponseResult,
+    ) -> (Self, IngressRequestId, IngressInvocationResponseReceiver) {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        let node_id = metadata().my_node_id();
+        let request_id = IngressRequestId::default();
+
+        (
+            IngressDispatcherRequest {
+                request_mode: IngressRequestMode::RequestResponse(request_id, result_tx),
+                inner: IngressDispatcherRequestInner::CompletePromise(PromiseCompletionRequest {
+                    workflow_target,
+                    key,
+                    completion,
+                    response_sink: ServiceInvocationResponseSink::Ingress {
+                        node_id,
+                        request_id,
+                    },
+                }),
+            },
+            request_id,
+            result_rx,
+        )
+    }
+
     pub fn one_way_invocation(
         mut service_invocation: ServiceInvocation,
     ) -> (
@@ -310,6 +344,19 @@ pub mod test_util {
             let _ = self.sender.send(ingress_request);
             Ok(())
         }
+
+        async fn dispatch_ingress_requests(
+            &self,
+            ingress_requests: Vec<IngressDispatcherRequest>,
+        ) -> Vec<Result<(), IngressDispatchError>> {
+            ingress_requests
+                .into_iter()
+                .map(|ingress_request| {
+                    let _ = self.sender.send(ingress_request);
+                    Ok(())
+                })
+                .collect()
+        }
     }
 
     impl IngressDispatcherRequest {
@@ -430,5 +477,18 @@ pub mod test_util {
                 ingress_response_sender,
             )
         }
+
+        pub fn expect_complete_promise(
+            self,
+        ) -> (PromiseCompletionRequest, IngressInvocationResponseSender) {
+            let_assert!(
+                IngressDispatcherRequest {
+                    inner: IngressDispatcherRequestInner::CompletePromise(promise_completion),
+                    request_mode: IngressRequestMode::RequestResponse(_, ingress_response_sender),
+                } = self
+            );
+
+            (promise_completion, ingress_response_sender)
+        }
     }
 }
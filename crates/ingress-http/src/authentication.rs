@@ -0,0 +1,480 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Authentication of the requests received by the ingress.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use http::{header, Extensions, HeaderMap, HeaderName};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{DecodingKey, Validation};
+use serde_json::{Map, Value};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use restate_types::config::{
+    ApiKeyOptions, IngressAuthenticationOptions, JwtAuthenticationOptions,
+};
+
+pub(crate) const X_RESTATE_API_KEY: HeaderName = HeaderName::from_static("x-restate-api-key");
+
+/// Principal of an authenticated request, stored in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Principal(String);
+
+impl Principal {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Identity of the verified TLS client certificate of a connection, to be stored in the request
+/// extensions by whoever terminates TLS.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateIdentity {
+    pub common_name: String,
+}
+
+impl ClientCertificateIdentity {
+    /// Extracts the identity from the subject common name of a DER encoded certificate.
+    pub fn from_der(certificate: &[u8]) -> Option<Self> {
+        let (_, certificate) = X509Certificate::from_der(certificate).ok()?;
+        let common_name = certificate
+            .subject()
+            .iter_common_name()
+            .next()?
+            .as_str()
+            .ok()?;
+        Some(Self {
+            common_name: common_name.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AuthenticationError {
+    #[error("missing credentials")]
+    MissingCredentials,
+    #[error("invalid API key")]
+    InvalidApiKey,
+    #[error("bad authorization header, expected a bearer token")]
+    BadAuthorizationHeader,
+    #[error("invalid token: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+    #[error("token is signed with an unknown key")]
+    UnknownSigningKey,
+    #[error("token has no string claim '{0}'")]
+    MissingPrincipalClaim(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticationOptionsError {
+    #[error("cannot read the JWKS file '{}': {source}", path.display())]
+    ReadJwks {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("cannot parse the JWKS file '{}': {source}", path.display())]
+    ParseJwks {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A method to authenticate the ingress requests.
+pub(crate) trait Authenticator: Send + Sync {
+    /// Returns `None` if the request carries no credentials for this method.
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Result<Option<Principal>, AuthenticationError>;
+}
+
+/// Authenticates the requests with the configured methods, in order.
+#[derive(Clone, Default)]
+pub(crate) struct RequestAuthenticator {
+    authenticators: Arc<Vec<Box<dyn Authenticator>>>,
+}
+
+impl RequestAuthenticator {
+    pub(crate) fn from_options(
+        options: &IngressAuthenticationOptions,
+    ) -> Result<Self, AuthenticationOptionsError> {
+        let mut authenticators: Vec<Box<dyn Authenticator>> = vec![];
+        if !options.api_keys.is_empty() {
+            authenticators.push(Box::new(ApiKeyAuthenticator::new(options.api_keys.clone())));
+        }
+        if let Some(jwt) = &options.jwt {
+            authenticators.push(Box::new(JwtAuthenticator::from_options(jwt)?));
+        }
+        if options.client_certificate {
+            authenticators.push(Box::new(ClientCertificateAuthenticator));
+        }
+
+        Ok(Self::from_authenticators(authenticators))
+    }
+
+    pub(crate) fn from_authenticators(authenticators: Vec<Box<dyn Authenticator>>) -> Self {
+        Self {
+            authenticators: Arc::new(authenticators),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.authenticators.is_empty()
+    }
+
+    pub(crate) fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Result<Principal, AuthenticationError> {
+        for authenticator in self.authenticators.iter() {
+            if let Some(principal) = authenticator.authenticate(headers, extensions)? {
+                return Ok(principal);
+            }
+        }
+        Err(AuthenticationError::MissingCredentials)
+    }
+}
+
+/// Removes the credentials consumed by the ingress from the headers, so that they are not
+/// forwarded to the invoked services nor stored in the journal.
+pub(crate) fn strip_credentials(headers: &mut HeaderMap) {
+    headers.remove(X_RESTATE_API_KEY);
+    if headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("Bearer "))
+    {
+        headers.remove(header::AUTHORIZATION);
+    }
+}
+
+pub(crate) struct ApiKeyAuthenticator {
+    api_keys: Vec<ApiKeyOptions>,
+}
+
+impl ApiKeyAuthenticator {
+    pub(crate) fn new(api_keys: Vec<ApiKeyOptions>) -> Self {
+        Self { api_keys }
+    }
+}
+
+impl Authenticator for ApiKeyAuthenticator {
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        _: &Extensions,
+    ) -> Result<Option<Principal>, AuthenticationError> {
+        let Some(key) = headers.get(X_RESTATE_API_KEY) else {
+            return Ok(None);
+        };
+
+        self.api_keys
+            .iter()
+            .find(|api_key| constant_time_eq(api_key.key.as_bytes(), key.as_bytes()))
+            .map(|api_key| Some(Principal::new(&api_key.principal)))
+            .ok_or(AuthenticationError::InvalidApiKey)
+    }
+}
+
+// Compares the keys without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub(crate) struct JwtAuthenticator {
+    jwks: JwkSet,
+    issuer: Option<String>,
+    audience: Option<String>,
+    principal_claim: String,
+}
+
+impl JwtAuthenticator {
+    pub(crate) fn from_options(
+        options: &JwtAuthenticationOptions,
+    ) -> Result<Self, AuthenticationOptionsError> {
+        let jwks = std::fs::read(&options.jwks_file).map_err(|source| {
+            AuthenticationOptionsError::ReadJwks {
+                path: options.jwks_file.clone(),
+                source,
+            }
+        })?;
+        let jwks = serde_json::from_slice(&jwks).map_err(|source| {
+            AuthenticationOptionsError::ParseJwks {
+                path: options.jwks_file.clone(),
+                source,
+            }
+        })?;
+
+        Ok(Self::new(
+            jwks,
+            options.issuer.clone(),
+            options.audience.clone(),
+            options.principal_claim.clone(),
+        ))
+    }
+
+    pub(crate) fn new(
+        jwks: JwkSet,
+        issuer: Option<String>,
+        audience: Option<String>,
+        principal_claim: String,
+    ) -> Self {
+        Self {
+            jwks,
+            issuer,
+            audience,
+            principal_claim,
+        }
+    }
+}
+
+impl Authenticator for JwtAuthenticator {
+    fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        _: &Extensions,
+    ) -> Result<Option<Principal>, AuthenticationError> {
+        let Some(authorization) = headers.get(header::AUTHORIZATION) else {
+            return Ok(None);
+        };
+        let token = authorization
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthenticationError::BadAuthorizationHeader)?;
+
+        let token_header = jsonwebtoken::decode_header(token)?;
+        let jwk = match &token_header.kid {
+            Some(kid) => self.jwks.find(kid),
+            None if self.jwks.keys.len() == 1 => self.jwks.keys.first(),
+            None => None,
+        }
+        .ok_or(AuthenticationError::UnknownSigningKey)?;
+        let decoding_key = DecodingKey::from_jwk(jwk)?;
+
+        // The algorithm must belong to the family of the decoding key, which is checked when
+        // verifying the signature
+        let mut validation = Validation::new(token_header.alg);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+        } else {
+            validation.validate_aud = false;
+        }
+
+        let claims =
+            jsonwebtoken::decode::<Map<String, Value>>(token, &decoding_key, &validation)?.claims;
+        claims
+            .get(&self.principal_claim)
+            .and_then(Value::as_str)
+            .map(|principal| Some(Principal::new(principal)))
+            .ok_or_else(|| AuthenticationError::MissingPrincipalClaim(self.principal_claim.clone()))
+    }
+}
+
+pub(crate) struct ClientCertificateAuthenticator;
+
+impl Authenticator for ClientCertificateAuthenticator {
+    fn authenticate(
+        &self,
+        _: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Result<Option<Principal>, AuthenticationError> {
+        Ok(extensions
+            .get::<ClientCertificateIdentity>()
+            .map(|identity| Principal::new(&identity.common_name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use http::HeaderValue;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use restate_test_util::{assert, assert_eq, let_assert};
+    use serde_json::json;
+
+    const SECRET: &[u8] = b"my-very-secret-signing-key";
+
+    fn jwks() -> JwkSet {
+        serde_json::from_value(json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "test-key",
+                "alg": "HS256",
+                // base64url of SECRET
+                "k": "bXktdmVyeS1zZWNyZXQtc2lnbmluZy1rZXk"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn bearer(claims: Value, kid: Option<&str>) -> HeaderMap {
+        let token = encode(
+            &Header {
+                kid: kid.map(ToOwned::to_owned),
+                ..Header::new(Algorithm::HS256)
+            },
+            &claims,
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    fn expiration() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 60
+    }
+
+    #[test]
+    fn strip_credentials_keeps_other_authorization_schemes() {
+        let mut headers = bearer(json!({ "sub": "billing" }), None);
+        headers.insert(X_RESTATE_API_KEY, HeaderValue::from_static("secret"));
+        strip_credentials(&mut headers);
+        assert!(headers.is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic dXNlcjpwYXNz"),
+        );
+        strip_credentials(&mut headers);
+        assert!(headers.contains_key(header::AUTHORIZATION));
+    }
+
+    #[test]
+    fn api_key() {
+        let authenticator = ApiKeyAuthenticator::new(vec![ApiKeyOptions {
+            principal: "billing".to_owned(),
+            key: "secret".to_owned(),
+        }]);
+
+        let mut headers = HeaderMap::new();
+        assert!(let Ok(None) = authenticator.authenticate(&headers, &Extensions::new()));
+
+        headers.insert(X_RESTATE_API_KEY, HeaderValue::from_static("secret"));
+        assert_eq!(
+            authenticator
+                .authenticate(&headers, &Extensions::new())
+                .unwrap(),
+            Some(Principal::new("billing"))
+        );
+
+        headers.insert(X_RESTATE_API_KEY, HeaderValue::from_static("secreT"));
+        assert!(let Err(AuthenticationError::InvalidApiKey) = authenticator.authenticate(&headers, &Extensions::new()));
+    }
+
+    #[test]
+    fn jwt() {
+        let authenticator =
+            JwtAuthenticator::new(jwks(), Some("my-issuer".to_owned()), None, "sub".to_owned());
+
+        let headers = bearer(
+            json!({"sub": "reporting", "iss": "my-issuer", "exp": expiration()}),
+            Some("test-key"),
+        );
+        assert_eq!(
+            authenticator
+                .authenticate(&headers, &Extensions::new())
+                .unwrap(),
+            Some(Principal::new("reporting"))
+        );
+
+        // the only key of the set is used if the token has no kid
+        let headers = bearer(
+            json!({"sub": "reporting", "iss": "my-issuer", "exp": expiration()}),
+            None,
+        );
+        assert!(let Ok(Some(_)) = authenticator.authenticate(&headers, &Extensions::new()));
+
+        let headers = bearer(
+            json!({"sub": "reporting", "iss": "my-issuer", "exp": expiration()}),
+            Some("other-key"),
+        );
+        assert!(let Err(AuthenticationError::UnknownSigningKey) = authenticator.authenticate(&headers, &Extensions::new()));
+
+        let headers = bearer(
+            json!({"sub": "reporting", "iss": "other-issuer", "exp": expiration()}),
+            Some("test-key"),
+        );
+        assert!(let Err(AuthenticationError::InvalidToken(_)) = authenticator.authenticate(&headers, &Extensions::new()));
+
+        let headers = bearer(
+            json!({"iss": "my-issuer", "exp": expiration()}),
+            Some("test-key"),
+        );
+        let_assert!(
+            Err(AuthenticationError::MissingPrincipalClaim(claim)) =
+                authenticator.authenticate(&headers, &Extensions::new())
+        );
+        assert_eq!(claim, "sub");
+    }
+
+    #[test]
+    fn jwt_with_bad_authorization_header() {
+        let authenticator = JwtAuthenticator::new(jwks(), None, None, "sub".to_owned());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic Zm9v"),
+        );
+        assert!(let Err(AuthenticationError::BadAuthorizationHeader) = authenticator.authenticate(&headers, &Extensions::new()));
+    }
+
+    #[test]
+    fn chain_uses_the_first_method_with_credentials() {
+        let authenticator = RequestAuthenticator::from_authenticators(vec![
+            Box::new(ApiKeyAuthenticator::new(vec![ApiKeyOptions {
+                principal: "billing".to_owned(),
+                key: "secret".to_owned(),
+            }])),
+            Box::new(ClientCertificateAuthenticator),
+        ]);
+
+        let mut extensions = Extensions::new();
+        assert!(let Err(AuthenticationError::MissingCredentials) = authenticator.authenticate(&HeaderMap::new(), &extensions));
+
+        extensions.insert(ClientCertificateIdentity {
+            common_name: "reporting".to_owned(),
+        });
+        assert_eq!(
+            authenticator
+                .authenticate(&HeaderMap::new(), &extensions)
+                .unwrap(),
+            Principal::new("reporting")
+        );
+    }
+}
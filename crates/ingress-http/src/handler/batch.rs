@@ -0,0 +1,367 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use bytestring::ByteString;
+use http::{header, Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use metrics::counter;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use tracing::{info, warn};
+
+use restate_ingress_dispatcher::{DispatchIngressRequest, IngressDispatcherRequest};
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::{
+    Header, InvocationTarget, InvocationTargetType, ServiceInvocation, Source, SpanRelation,
+    WorkflowHandlerType,
+};
+use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::ServiceMetadataResolver;
+
+use super::service_handler::{parse_headers, SendResponse, SendStatus};
+use super::tracing::prepare_tracing_span;
+use super::HandlerError;
+use super::{Handler, APPLICATION_JSON};
+use crate::authentication::Principal;
+use crate::metric_definitions::{INGRESS_REQUESTS, REQUEST_DENIED_RATE_LIMITED};
+
+/// Maximum number of entries of a batch.
+const MAX_BATCH_ENTRIES: usize = 1000;
+
+/// Entry of a batch, sent to the target handler like `/:service/:handler/send`.
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchEntry {
+    /// Target handler, in the form `<service>/<handler>`.
+    target: String,
+    /// Key of the virtual object or workflow.
+    #[serde(default)]
+    key: Option<String>,
+    /// JSON body of the invocation, if any.
+    #[serde(default)]
+    body: Option<serde_json::Value>,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    #[serde_as(as = "Option<restate_serde_util::DurationString>")]
+    #[serde(default)]
+    delay: Option<Duration>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize))]
+#[serde(untagged)]
+pub(crate) enum BatchEntryResponse {
+    Sent(SendResponse),
+    Failed { error: String },
+}
+
+impl<Schemas, Dispatcher, StorageReader> Handler<Schemas, Dispatcher, StorageReader>
+where
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Dispatcher: DispatchIngressRequest + Clone + Send + Sync + 'static,
+{
+    /// Sends the entries of the batch, either a JSON array or newline delimited JSON of at most
+    /// [`MAX_BATCH_ENTRIES`] entries. The entries are dispatched together, and the response
+    /// contains the result of every entry in the same order of the request.
+    pub(crate) async fn handle_batch<B: http_body::Body>(
+        self,
+        req: Request<B>,
+    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        if req.method() != Method::POST {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        let (parts, body) = req.into_parts();
+        let body = body
+            .collect()
+            .await
+            .map_err(|e| HandlerError::Body(e.into()))?
+            .to_bytes();
+        let entries = parse_batch(&body)?;
+        // The request is used to link the invocation spans to the batch request
+        let req = Request::from_parts(parts, ());
+        let headers = parse_headers(req.headers().clone())?;
+
+        info!("Processing ingress batch of {} entries", entries.len());
+
+        let mut responses: Vec<Option<BatchEntryResponse>> = Vec::with_capacity(entries.len());
+        let mut ingress_requests = vec![];
+        let mut pending_notifications = vec![];
+        for (index, entry) in entries.into_iter().enumerate() {
+            match self.prepare_batch_entry(&req, &headers, entry) {
+                Ok(service_invocation) => {
+                    let invocation_id = service_invocation.invocation_id;
+                    let execution_time = service_invocation
+                        .execution_time
+                        .map(SystemTime::from)
+                        .map(Into::into);
+                    let (ingress_request, request_id, notification_rx) =
+                        IngressDispatcherRequest::one_way_invocation(service_invocation);
+                    ingress_requests.push(ingress_request);
+                    pending_notifications.push((
+                        index,
+                        invocation_id,
+                        execution_time,
+                        request_id,
+                        notification_rx,
+                    ));
+                    responses.push(None);
+                }
+                Err(e) => responses.push(Some(BatchEntryResponse::Failed {
+                    error: e.to_string(),
+                })),
+            }
+        }
+
+        let dispatch_results = self
+            .dispatcher
+            .dispatch_ingress_requests(ingress_requests)
+            .await;
+
+        for (
+            dispatch_result,
+            (index, invocation_id, execution_time, request_id, notification_rx),
+        ) in dispatch_results.into_iter().zip(pending_notifications)
+        {
+            if let Err(e) = dispatch_result {
+                warn!("Failed to dispatch ingress batch entry {}: {}", index, e);
+                self.dispatcher
+                    .evict_pending_submit_notification(request_id);
+                responses[index] = Some(BatchEntryResponse::Failed {
+                    error: HandlerError::Unavailable.to_string(),
+                });
+                continue;
+            }
+
+            let response = match notification_rx.await {
+                Ok(notification) => BatchEntryResponse::Sent(SendResponse {
+                    invocation_id,
+                    execution_time,
+                    status: if notification.is_new_invocation {
+                        SendStatus::Accepted
+                    } else {
+                        SendStatus::PreviouslyAccepted
+                    },
+                }),
+                Err(_) => {
+                    self.dispatcher
+                        .evict_pending_submit_notification(request_id);
+                    BatchEntryResponse::Failed {
+                        error: HandlerError::Unavailable.to_string(),
+                    }
+                }
+            };
+            responses[index] = Some(response);
+        }
+
+        let responses: Vec<_> = responses
+            .into_iter()
+            .map(|response| response.expect("every entry has a response"))
+            .collect();
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, APPLICATION_JSON)
+            .body(Full::new(serde_json::to_vec(&responses).unwrap().into()))
+            .unwrap())
+    }
+
+    /// Applies the same checks of the single ingress requests to the batch entry.
+    fn prepare_batch_entry(
+        &self,
+        req: &Request<()>,
+        headers: &[Header],
+        entry: BatchEntry,
+    ) -> Result<ServiceInvocation, HandlerError> {
+        let (service_name, handler_name) = entry
+            .target
+            .split_once('/')
+            .ok_or_else(|| HandlerError::BadBatchEntryTarget(entry.target.clone()))?;
+
+        let schemas = self.schemas.pinned();
+        let invocation_target_meta = schemas
+            .resolve_latest_invocation_target(service_name, handler_name)
+            .ok_or_else(|| {
+                HandlerError::ServiceHandlerNotFound(
+                    service_name.to_owned(),
+                    handler_name.to_owned(),
+                )
+            })?;
+        if !invocation_target_meta.public {
+            return Err(HandlerError::PrivateService);
+        }
+        if let Some(ingress_authorization) =
+            schemas.resolve_ingress_authorization(service_name, handler_name)
+        {
+            let principal = req.extensions().get::<Principal>().map(Principal::as_str);
+            if !ingress_authorization.is_allowed(principal) {
+                return Err(HandlerError::Forbidden(
+                    service_name.to_owned(),
+                    handler_name.to_owned(),
+                ));
+            }
+        }
+        if let Some(rate_limit) = schemas.resolve_rate_limit(service_name, handler_name) {
+            if let Err(retry_after) = self.rate_limiter.try_acquire(
+                service_name,
+                handler_name,
+                rate_limit,
+                req.extensions().get::<Principal>().map(Principal::as_str),
+                req.headers(),
+            ) {
+                counter!(
+                    INGRESS_REQUESTS,
+                    "status" => REQUEST_DENIED_RATE_LIMITED,
+                    "rpc.service" => service_name.to_owned(),
+                    "rpc.method" => handler_name.to_owned(),
+                )
+                .increment(1);
+                return Err(HandlerError::RateLimited(
+                    service_name.to_owned(),
+                    handler_name.to_owned(),
+                    retry_after,
+                ));
+            }
+        }
+
+        if entry.idempotency_key.is_some()
+            && invocation_target_meta.target_ty
+                == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow)
+        {
+            return Err(HandlerError::UnsupportedIdempotencyKey);
+        }
+
+        let invocation_target = match (invocation_target_meta.target_ty, entry.key) {
+            (InvocationTargetType::VirtualObject(handler_ty), Some(key)) => {
+                InvocationTarget::virtual_object(service_name, key, handler_name, handler_ty)
+            }
+            (InvocationTargetType::Workflow(handler_ty), Some(key)) => {
+                InvocationTarget::workflow(service_name, key, handler_name, handler_ty)
+            }
+            (InvocationTargetType::Service, None) => {
+                InvocationTarget::service(service_name, handler_name)
+            }
+            _ => return Err(HandlerError::BadBatchEntryKey),
+        };
+
+        let (content_type, body) = match &entry.body {
+            Some(body) => (
+                Some("application/json"),
+                Bytes::from(serde_json::to_vec(body).expect("JSON value can be serialized")),
+            ),
+            None => (None, Bytes::new()),
+        };
+        invocation_target_meta
+            .input_rules
+            .validate(content_type, &body)?;
+        if let Some(input_json_schema) =
+            schemas.resolve_input_json_schema(service_name, handler_name)
+        {
+            self.input_validator
+                .validate(service_name, handler_name, &input_json_schema, &body)?;
+        }
+
+        let idempotency_key = entry.idempotency_key.map(ByteString::from);
+        let invocation_id = InvocationId::generate(&invocation_target, idempotency_key.as_deref());
+        let ingress_span_context = prepare_tracing_span(&invocation_id, &invocation_target, req);
+
+        let mut service_invocation =
+            ServiceInvocation::initialize(invocation_id, invocation_target, Source::Ingress);
+        service_invocation.with_related_span(SpanRelation::Parent(ingress_span_context));
+        service_invocation.completion_retention_duration =
+            invocation_target_meta.compute_retention(idempotency_key.is_some());
+        service_invocation.idempotency_key = idempotency_key;
+        service_invocation.headers = headers.to_vec();
+        if content_type.is_none() {
+            // The content type of the batch request doesn't apply to the entries
+            service_invocation
+                .headers
+                .retain(|header| !header.name.eq_ignore_ascii_case("content-type"));
+        }
+        service_invocation.argument = body;
+        service_invocation.execution_time = entry
+            .delay
+            .map(|delay| SystemTime::now() + delay)
+            .map(Into::into);
+
+        Ok(service_invocation)
+    }
+}
+
+/// Parses either a JSON array of entries, or newline delimited JSON entries.
+fn parse_batch(body: &[u8]) -> Result<Vec<BatchEntry>, HandlerError> {
+    let is_json_array = body
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| *b == b'[');
+    let entries: Vec<BatchEntry> = if is_json_array {
+        serde_json::from_slice(body).map_err(|e| HandlerError::BadBatch(e.to_string()))?
+    } else {
+        body.split(|b| *b == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+            .map(|(line_number, line)| {
+                serde_json::from_slice(line)
+                    .map_err(|e| HandlerError::BadBatch(format!("line {}: {e}", line_number + 1)))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    if entries.len() > MAX_BATCH_ENTRIES {
+        return Err(HandlerError::BatchTooLarge(MAX_BATCH_ENTRIES));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_array_and_ndjson() {
+        let json_array = br#"[
+            {"target": "greeter.Greeter/greet", "body": {"person": "Francesco"}},
+            {"target": "greeter.GreeterObject/greet", "key": "my-key", "delay": "PT1M"}
+        ]"#;
+        let ndjson = b"{\"target\": \"greeter.Greeter/greet\", \"body\": {\"person\": \"Francesco\"}}\n\n{\"target\": \"greeter.GreeterObject/greet\", \"key\": \"my-key\", \"delay\": \"1m\"}\n";
+
+        for body in [&json_array[..], &ndjson[..]] {
+            let entries = parse_batch(body).unwrap();
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[1].key.as_deref(), Some("my-key"));
+            assert_eq!(entries[1].delay, Some(Duration::from_secs(60)));
+        }
+
+        assert!(matches!(
+            parse_batch(b"{\"target\": \"greeter.Greeter/greet\"}\n{\"key\": \"my-key\"}"),
+            Err(HandlerError::BadBatch(message)) if message.starts_with("line 2")
+        ));
+    }
+
+    #[test]
+    fn reject_too_large_batch() {
+        let entry = b"{\"target\": \"greeter.Greeter/greet\"}\n";
+
+        let body = entry.repeat(MAX_BATCH_ENTRIES);
+        assert_eq!(parse_batch(&body).unwrap().len(), MAX_BATCH_ENTRIES);
+
+        let body = entry.repeat(MAX_BATCH_ENTRIES + 1);
+        assert!(matches!(
+            parse_batch(&body),
+            Err(HandlerError::BatchTooLarge(MAX_BATCH_ENTRIES))
+        ));
+    }
+}
@@ -10,12 +10,15 @@
 
 use super::APPLICATION_JSON;
 
+use crate::authentication::AuthenticationError;
+use crate::input_validator::InputSchemaValidationError;
 use bytes::Bytes;
 use http::{header, Response, StatusCode};
 use restate_types::errors::{IdDecodeError, InvocationError};
 use restate_types::schema::invocation_target::InputValidationError;
 use serde::Serialize;
 use std::string;
+use std::time::Duration;
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum HandlerError {
@@ -40,7 +43,7 @@ pub(crate) enum HandlerError {
     )]
     BadInvocationPath,
     #[error(
-    "bad path, expected either /restate/workflow/:workflow_name/:workflow_key/output or /restate/workflow/:workflow_name/:workflow_key/attach"
+    "bad path, expected either /restate/workflow/:workflow_name/:workflow_key/output or /restate/workflow/:workflow_name/:workflow_key/attach or /restate/workflow/:workflow_name/:workflow_key/promise/:promise_key/(resolve|reject|peek)"
     )]
     BadWorkflowPath,
     #[error("not implemented")]
@@ -53,12 +56,22 @@ pub(crate) enum HandlerError {
     UrlDecodingError(string::FromUtf8Error),
     #[error("the invoked service is not public")]
     PrivateService,
+    #[error("unauthenticated: {0}")]
+    Unauthenticated(AuthenticationError),
+    #[error("not allowed to invoke service '{0}' handler '{1}'")]
+    Forbidden(String, String),
+    #[error("rate limit of service '{0}' handler '{1}' exceeded")]
+    RateLimited(String, String, Duration),
     #[error("cannot read body: {0:?}")]
     Body(anyhow::Error),
     #[error("unavailable")]
     Unavailable,
     #[error("the invocation exists but has not completed yet")]
     NotReady,
+    #[error("the promise '{0}' has not been completed yet")]
+    PromiseNotCompleted(String),
+    #[error("service '{0}' is not a workflow")]
+    NotAWorkflow(String),
     #[error("method not allowed")]
     MethodNotAllowed,
     #[error(
@@ -69,6 +82,8 @@ pub(crate) enum HandlerError {
     Invocation(InvocationError),
     #[error("input validation error: {0}")]
     InputValidation(#[from] InputValidationError),
+    #[error("input validation error: {0}")]
+    InputSchemaValidation(#[from] InputSchemaValidationError),
     #[error(
         "cannot use the delay query parameter with calls. The delay is supported only with sends"
     )]
@@ -81,6 +96,14 @@ pub(crate) enum HandlerError {
     BadAwakeableId(String, IdDecodeError),
     #[error("bad invocation id '{0}': {1}")]
     BadInvocationId(String, IdDecodeError),
+    #[error("bad batch, expected a JSON array or newline delimited JSON of entries: {0}")]
+    BadBatch(String),
+    #[error("bad batch entry target '{0}', expected <service>/<handler>")]
+    BadBatchEntryTarget(String),
+    #[error("bad batch entry key, the key must be set only for virtual objects and workflows")]
+    BadBatchEntryKey,
+    #[error("the batch has more than {0} entries")]
+    BatchTooLarge(usize),
 }
 
 #[derive(Debug, Serialize)]
@@ -119,8 +142,17 @@ impl HandlerError {
             | HandlerError::BadInvocationId(_, _)
             | HandlerError::BadWorkflowPath
             | HandlerError::InputValidation(_)
+            | HandlerError::InputSchemaValidation(_)
             | HandlerError::UnsupportedIdempotencyKey
-            | HandlerError::UnsupportedGetOutput => StatusCode::BAD_REQUEST,
+            | HandlerError::UnsupportedGetOutput
+            | HandlerError::BadBatch(_)
+            | HandlerError::BadBatchEntryTarget(_)
+            | HandlerError::BadBatchEntryKey
+            | HandlerError::NotAWorkflow(_) => StatusCode::BAD_REQUEST,
+            HandlerError::BatchTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            HandlerError::Unauthenticated(_) => StatusCode::UNAUTHORIZED,
+            HandlerError::Forbidden(_, _) => StatusCode::FORBIDDEN,
+            HandlerError::RateLimited(_, _, _) => StatusCode::TOO_MANY_REQUESTS,
             HandlerError::Body(_) => StatusCode::INTERNAL_SERVER_ERROR,
             HandlerError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
             HandlerError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
@@ -128,7 +160,19 @@ impl HandlerError {
             HandlerError::Invocation(e) => {
                 StatusCode::from_u16(e.code().into()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
-            HandlerError::NotReady => StatusCode::from_u16(470).unwrap(),
+            HandlerError::NotReady | HandlerError::PromiseNotCompleted(_) => {
+                StatusCode::from_u16(470).unwrap()
+            }
+        };
+
+        let res_builder = if let HandlerError::RateLimited(_, _, retry_after) = &self {
+            // Retry-After is expressed in whole seconds
+            res_builder.header(
+                header::RETRY_AFTER,
+                retry_after.as_secs_f64().ceil().max(1.0) as u64,
+            )
+        } else {
+            res_builder
         };
 
         let error_response = match self {
@@ -9,7 +9,8 @@
 // by the Apache License, Version 2.0.
 
 mod awakeables;
-mod error;
+mod batch;
+pub(crate) mod error;
 mod health;
 mod invocation;
 mod path_parsing;
@@ -37,6 +38,8 @@ use restate_types::schema::invocation_target::InvocationTargetResolver;
 use restate_types::schema::service::ServiceMetadataResolver;
 
 use super::*;
+use crate::input_validator::InputValidator;
+use crate::rate_limiter::RateLimiter;
 
 const APPLICATION_JSON: HeaderValue = HeaderValue::from_static("application/json");
 
@@ -45,6 +48,8 @@ pub(crate) struct Handler<Schemas, Dispatcher, StorageReader> {
     schemas: Live<Schemas>,
     dispatcher: Dispatcher,
     storage_reader: StorageReader,
+    rate_limiter: RateLimiter,
+    input_validator: InputValidator,
 }
 
 impl<Schemas, Dispatcher, StorageReader> Handler<Schemas, Dispatcher, StorageReader> {
@@ -52,11 +57,14 @@ impl<Schemas, Dispatcher, StorageReader> Handler<Schemas, Dispatcher, StorageRea
         schemas: Live<Schemas>,
         dispatcher: Dispatcher,
         storage_reader: StorageReader,
+        rate_limiter: RateLimiter,
     ) -> Self {
         Self {
             schemas,
             dispatcher,
             storage_reader,
+            rate_limiter,
+            input_validator: InputValidator::default(),
         }
     }
 }
@@ -93,6 +101,7 @@ where
                 RequestType::Awakeable(awakeable_request) => {
                     this.handle_awakeable(req, awakeable_request).await
                 }
+                RequestType::Batch => this.handle_batch(req).await,
                 RequestType::Service(service_request) => {
                     this.handle_service_request(req, service_request).await
                 }
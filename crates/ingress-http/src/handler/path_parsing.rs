@@ -16,9 +16,16 @@ use super::Handler;
 use super::HandlerError;
 use crate::InvocationStorageReader;
 
+pub(crate) enum PromiseRequestType {
+    Resolve,
+    Reject,
+    Peek,
+}
+
 pub(crate) enum WorkflowRequestType {
     Attach(String, String),
     GetOutput(String, String),
+    Promise(String, String, String, PromiseRequestType),
 }
 
 impl WorkflowRequestType {
@@ -39,6 +46,25 @@ impl WorkflowRequestType {
         match path_parts.next().ok_or(HandlerError::BadWorkflowPath)? {
             "output" => Ok(WorkflowRequestType::GetOutput(workflow_name, workflow_key)),
             "attach" => Ok(WorkflowRequestType::Attach(workflow_name, workflow_key)),
+            "promise" => {
+                let promise_key =
+                    urlencoding::decode(path_parts.next().ok_or(HandlerError::BadWorkflowPath)?)
+                        .map_err(HandlerError::UrlDecodingError)?
+                        .into_owned();
+                let promise_request_type =
+                    match path_parts.next().ok_or(HandlerError::BadWorkflowPath)? {
+                        "resolve" => PromiseRequestType::Resolve,
+                        "reject" => PromiseRequestType::Reject,
+                        "peek" => PromiseRequestType::Peek,
+                        _ => return Err(HandlerError::NotFound),
+                    };
+                Ok(WorkflowRequestType::Promise(
+                    workflow_name,
+                    workflow_key,
+                    promise_key,
+                    promise_request_type,
+                ))
+            }
             _ => Err(HandlerError::NotFound),
         }
     }
@@ -218,6 +244,7 @@ pub(crate) enum RequestType {
     Health,
     OpenAPI,
     Awakeable(AwakeableRequestType),
+    Batch,
     Invocation(InvocationRequestType),
     Service(ServiceRequestType),
     Workflow(WorkflowRequestType),
@@ -244,6 +271,7 @@ where
                 "invocation" => Ok(RequestType::Invocation(
                     InvocationRequestType::from_path_chunks(path_parts, schema)?,
                 )),
+                "batch" if path_parts.next().is_none() => Ok(RequestType::Batch),
                 "workflow" => Ok(RequestType::Workflow(
                     WorkflowRequestType::from_path_chunks(path_parts)?,
                 )),
@@ -29,13 +29,17 @@ use restate_types::invocation::{
 use restate_types::schema::invocation_target::{
     InvocationTargetMetadata, InvocationTargetResolver,
 };
+use restate_types::schema::service::ServiceMetadataResolver;
 
 use super::path_parsing::{InvokeType, ServiceRequestType, TargetType};
 use super::tracing::prepare_tracing_span;
 use super::HandlerError;
 use super::{Handler, APPLICATION_JSON};
+use crate::authentication::Principal;
 use crate::handler::responses::{IDEMPOTENCY_EXPIRES, X_RESTATE_ID};
-use crate::metric_definitions::{INGRESS_REQUESTS, INGRESS_REQUEST_DURATION, REQUEST_COMPLETED};
+use crate::metric_definitions::{
+    INGRESS_REQUESTS, INGRESS_REQUEST_DURATION, REQUEST_COMPLETED, REQUEST_DENIED_RATE_LIMITED,
+};
 
 pub(crate) const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
 const DELAY_QUERY_PARAM: &str = "delay";
@@ -57,13 +61,13 @@ pub(crate) struct SendResponse {
         skip_serializing_if = "Option::is_none",
         default
     )]
-    execution_time: Option<humantime::Timestamp>,
-    status: SendStatus,
+    pub(crate) execution_time: Option<humantime::Timestamp>,
+    pub(crate) status: SendStatus,
 }
 
 impl<Schemas, Dispatcher, StorageReader> Handler<Schemas, Dispatcher, StorageReader>
 where
-    Schemas: InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
     Dispatcher: DispatchIngressRequest + Clone + Send + Sync + 'static,
 {
     pub(crate) async fn handle_service_request<B: http_body::Body>(
@@ -99,6 +103,52 @@ where
             ));
         };
 
+        // Check the authenticated principal is allowed to invoke the handler
+        if let Some(ingress_authorization) = self
+            .schemas
+            .pinned()
+            .resolve_ingress_authorization(&service_name, &handler_name)
+        {
+            let principal = req.extensions().get::<Principal>().map(Principal::as_str);
+            if !ingress_authorization.is_allowed(principal) {
+                return Err(HandlerError::Forbidden(service_name, handler_name));
+            }
+        }
+
+        // Check the rate limit of the handler
+        if let Some(rate_limit) = self
+            .schemas
+            .pinned()
+            .resolve_rate_limit(&service_name, &handler_name)
+        {
+            if let Err(retry_after) = self.rate_limiter.try_acquire(
+                &service_name,
+                &handler_name,
+                rate_limit,
+                req.extensions().get::<Principal>().map(Principal::as_str),
+                req.headers(),
+            ) {
+                counter!(
+                    INGRESS_REQUESTS,
+                    "status" => REQUEST_DENIED_RATE_LIMITED,
+                    "rpc.service" => service_name.clone(),
+                    "rpc.method" => handler_name.clone(),
+                )
+                .increment(1);
+                return Err(HandlerError::RateLimited(
+                    service_name,
+                    handler_name,
+                    retry_after,
+                ));
+            }
+        }
+
+        // Validate the body against the input JSON Schema only if enabled for the service
+        let input_json_schema = self
+            .schemas
+            .pinned()
+            .resolve_input_json_schema(&service_name, &handler_name);
+
         // Check if Idempotency-Key is available
         let idempotency_key = parse_idempotency(req.headers())?;
         if idempotency_key.is_some()
@@ -171,6 +221,14 @@ where
                     .transpose()?,
                 &body,
             )?;
+            if let Some(input_json_schema) = &input_json_schema {
+                self.input_validator.validate(
+                    invocation_target.service_name(),
+                    invocation_target.handler_name(),
+                    input_json_schema,
+                    &body,
+                )?;
+            }
 
             // Get headers
             let headers = parse_headers(parts.headers)?;
@@ -321,7 +379,7 @@ where
     }
 }
 
-fn parse_headers(headers: HeaderMap) -> Result<Vec<Header>, HandlerError> {
+pub(super) fn parse_headers(headers: HeaderMap) -> Result<Vec<Header>, HandlerError> {
     headers
         .into_iter()
         .filter_map(|(k, v)| k.map(|k| (k, v)))
@@ -30,20 +30,24 @@ use restate_test_util::{assert, assert_eq};
 use restate_types::identifiers::{IdempotencyId, InvocationId, ServiceId};
 use restate_types::ingress::{IngressResponseResult, InvocationResponse};
 use restate_types::invocation::{
-    Header, InvocationQuery, InvocationTarget, InvocationTargetType, VirtualObjectHandlerType,
-    WorkflowHandlerType,
+    Header, InvocationQuery, InvocationTarget, InvocationTargetType, ResponseResult,
+    VirtualObjectHandlerType, WorkflowHandlerType,
 };
 use restate_types::schema::invocation_target::{
     InputContentType, InputRules, InputValidationRule, InvocationTargetMetadata,
     OutputContentTypeRule, OutputRules,
 };
+use restate_types::schema::service::{IngressAuthorization, RateLimit, ServiceMetadataResolver};
 
+use super::batch::BatchEntryResponse;
 use super::health::HealthResponse;
 use super::mocks::*;
 use super::service_handler::*;
 use super::ConnectInfo;
 use super::Handler;
+use crate::authentication::Principal;
 use crate::handler::responses::X_RESTATE_ID;
+use crate::rate_limiter::RateLimiter;
 
 #[tokio::test]
 #[traced_test]
@@ -297,6 +301,48 @@ async fn send_with_delay_service() {
     let _: SendResponse = serde_json::from_slice(&response_bytes).unwrap();
 }
 
+#[tokio::test]
+#[traced_test]
+async fn send_batch() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/restate/batch")
+        .method(Method::POST)
+        .header("content-type", "application/x-ndjson")
+        .body(Full::new(Bytes::from_static(
+            b"{\"target\": \"greeter.Greeter/greet\", \"body\": {\"person\": \"Francesco\"}, \"delay\": \"PT1M\"}\n{\"target\": \"unknown.Service/greet\"}\n",
+        )))
+        .unwrap();
+
+    let response = handle(req, |ingress_req| {
+        // Only the valid entry is dispatched
+        let service_invocation = ingress_req.expect_one_way_invocation();
+        assert_eq!(
+            service_invocation.invocation_target.service_name(),
+            "greeter.Greeter"
+        );
+        assert!(service_invocation.execution_time.is_some());
+
+        let greeting_req: GreetingRequest =
+            serde_json::from_slice(&service_invocation.argument).unwrap();
+        assert_eq!(&greeting_req.person, "Francesco");
+    })
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let (_, response_body) = response.into_parts();
+    let response_bytes = response_body.collect().await.unwrap().to_bytes();
+    let responses: Vec<BatchEntryResponse> = serde_json::from_slice(&response_bytes).unwrap();
+    assert_that!(
+        responses,
+        elements_are![
+            pat!(BatchEntryResponse::Sent(anything())),
+            pat!(BatchEntryResponse::Failed {
+                error: contains_substring("unknown.Service")
+            })
+        ]
+    );
+}
+
 #[tokio::test]
 #[traced_test]
 async fn send_virtual_object() {
@@ -787,6 +833,62 @@ async fn get_output_with_workflow_key() {
     assert_eq!(response_value.greeting, "Igal");
 }
 
+#[tokio::test]
+#[traced_test]
+async fn resolve_workflow_promise() {
+    let service_id = ServiceId::new("MyWorkflow", "my-key");
+
+    let mock_schemas = MockSchemas::default().with_service_and_target(
+        &service_id.service_name,
+        "run",
+        InvocationTargetMetadata::mock(InvocationTargetType::Workflow(
+            WorkflowHandlerType::Workflow,
+        )),
+    );
+
+    let req = hyper::Request::builder()
+        .uri(format!(
+            "http://localhost/restate/workflow/{}/{}/promise/approval/resolve",
+            service_id.service_name, service_id.key
+        ))
+        .method(Method::POST)
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from_static(b"true")))
+        .unwrap();
+
+    let response = handle_with_schemas(req, mock_schemas, move |ingress_req| {
+        let (promise_completion, response_tx) = ingress_req.expect_complete_promise();
+        assert_eq!(
+            promise_completion.workflow_target,
+            InvocationTarget::workflow(
+                service_id.service_name.clone(),
+                service_id.key.clone(),
+                "run",
+                WorkflowHandlerType::Workflow
+            )
+        );
+        assert_eq!(promise_completion.key, "approval");
+        assert_eq!(
+            promise_completion.completion,
+            ResponseResult::Success(Bytes::from_static(b"true"))
+        );
+
+        response_tx
+            .send(IngressInvocationResponse {
+                idempotency_expiry_time: None,
+                invocation_id: None,
+                result: IngressResponseResult::Success(
+                    promise_completion.workflow_target,
+                    Bytes::new(),
+                ),
+            })
+            .unwrap();
+    })
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[tokio::test]
 #[traced_test]
 async fn bad_path_service() {
@@ -898,6 +1000,174 @@ async fn private_service() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+fn mock_schemas_with_ingress_authorization(allowed_principals: &[&str]) -> MockSchemas {
+    let mut schemas = mock_schemas();
+    let mut service_metadata = schemas.resolve_latest_service("greeter.Greeter").unwrap();
+    service_metadata.ingress_authorization = Some(IngressAuthorization {
+        allowed_principals: allowed_principals.iter().map(ToString::to_string).collect(),
+    });
+    schemas.0.add(service_metadata);
+    schemas
+}
+
+#[tokio::test]
+#[traced_test]
+async fn allowed_principal() {
+    let mut req = hyper::Request::get("http://localhost/greeter.Greeter/greet")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("billing"));
+
+    let response = handle_with_schemas(
+        req,
+        mock_schemas_with_ingress_authorization(&["billing"]),
+        expect_invocation_and_reply_with_empty,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn forbidden_principal() {
+    let mut req = hyper::Request::get("http://localhost/greeter.Greeter/greet")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("reporting"));
+
+    let response = handle_with_schemas(
+        req,
+        mock_schemas_with_ingress_authorization(&["billing"]),
+        request_handler_not_reached,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Requests without an authenticated principal are forbidden as well
+    let response = handle_with_schemas(
+        hyper::Request::get("http://localhost/greeter.Greeter/greet")
+            .body(Empty::<Bytes>::default())
+            .unwrap(),
+        mock_schemas_with_ingress_authorization(&["billing"]),
+        request_handler_not_reached,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rate_limited() {
+    let mut schemas = mock_schemas();
+    let mut service_metadata = schemas.resolve_latest_service("greeter.Greeter").unwrap();
+    service_metadata.rate_limit = Some(RateLimit {
+        requests_per_second: 1.try_into().unwrap(),
+        burst: None,
+        per_client: false,
+    });
+    schemas.0.add(service_metadata);
+
+    let node_env = TestCoreEnv::create_with_single_node(1, 1).await;
+    let (ingress_request_tx, mut ingress_request_rx) = mpsc::unbounded_channel();
+    let handler = Handler::new(
+        Live::from_value(schemas),
+        MockDispatcher::new(ingress_request_tx),
+        MockStorageReader::default(),
+        RateLimiter::default(),
+    );
+    let request = || {
+        let mut req = hyper::Request::get("http://localhost/greeter.Greeter/greet")
+            .body(Empty::<Bytes>::default())
+            .unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo::new("0.0.0.0:0".parse().unwrap()));
+        req.extensions_mut().insert(opentelemetry::Context::new());
+        req
+    };
+
+    tokio::spawn(async move {
+        expect_invocation_and_reply_with_empty(ingress_request_rx.recv().await.unwrap());
+    });
+    let response = node_env
+        .tc
+        .run_in_scope("ingress", None, handler.clone().oneshot(request()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The second request exceeds the limit of 1 request per second
+    let response = node_env
+        .tc
+        .run_in_scope("ingress", None, handler.oneshot(request()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        response.headers().get(http::header::RETRY_AFTER).unwrap(),
+        "1"
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn input_json_schema_validation() {
+    let input_json_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "person": { "type": "string" }
+        },
+        "required": ["person"]
+    });
+    let mut schemas = MockSchemas::default().with_service_and_target(
+        "greeter.Greeter",
+        "greet",
+        InvocationTargetMetadata {
+            input_rules: InputRules {
+                input_validation_rules: vec![InputValidationRule::JsonValue {
+                    content_type: InputContentType::Any,
+                    schema: input_json_schema.clone(),
+                }],
+            },
+            ..InvocationTargetMetadata::mock(InvocationTargetType::Service)
+        },
+    );
+    let mut service_metadata = schemas.resolve_latest_service("greeter.Greeter").unwrap();
+    service_metadata.json_schema_validation = true;
+    for handler in &mut service_metadata.handlers {
+        handler.input_json_schema = Some(input_json_schema.clone());
+    }
+    schemas.0.add(service_metadata);
+
+    let request = |body: &'static str| {
+        hyper::Request::post("http://localhost/greeter.Greeter/greet")
+            .header("content-type", "application/json")
+            .body(Full::new(Bytes::from_static(body.as_bytes())))
+            .unwrap()
+    };
+
+    let response = handle_with_schemas(
+        request(r#"{"person": "Francesco"}"#),
+        schemas.clone(),
+        expect_invocation_and_reply_with_empty,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = handle_with_schemas(
+        request(r#"{"person": 42}"#),
+        schemas,
+        request_handler_not_reached,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let message = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["message"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    assert_that!(message, contains_substring("'/person'"));
+}
+
 #[tokio::test]
 #[traced_test]
 async fn invalid_input() {
@@ -1103,6 +1373,7 @@ where
             Live::from_value(schemas),
             dispatcher,
             invocation_storage_reader,
+            RateLimiter::default(),
         )
         .oneshot(req),
     );
@@ -9,24 +9,31 @@
 // by the Apache License, Version 2.0.
 
 use bytes::Bytes;
-use http::{Method, Request, Response};
-use http_body_util::Full;
-use tracing::{info, warn};
+use bytestring::ByteString;
+use http::{Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use tracing::{info, trace, warn};
 
 use restate_ingress_dispatcher::DispatchIngressRequest;
 use restate_ingress_dispatcher::IngressDispatcherRequest;
+use restate_types::errors::{codes, InvocationError};
 use restate_types::identifiers::ServiceId;
-use restate_types::invocation::InvocationQuery;
+use restate_types::ingress::IngressResponseResult;
+use restate_types::invocation::{
+    InvocationQuery, InvocationTarget, ResponseResult, ServiceType, WorkflowHandlerType,
+};
 use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::{HandlerMetadataType, ServiceMetadataResolver};
 
-use super::path_parsing::WorkflowRequestType;
+use super::path_parsing::{PromiseRequestType, WorkflowRequestType};
 use super::Handler;
 use super::HandlerError;
-use crate::{GetOutputResult, InvocationStorageReader};
+use crate::authentication::Principal;
+use crate::{GetOutputResult, GetPromiseResult, InvocationStorageReader};
 
 impl<Schemas, Dispatcher, StorageReader> Handler<Schemas, Dispatcher, StorageReader>
 where
-    Schemas: InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
     Dispatcher: DispatchIngressRequest + Clone + Send + Sync + 'static,
     StorageReader: InvocationStorageReader + Clone + Send + Sync + 'static,
 {
@@ -47,6 +54,19 @@ where
                 self.handle_workflow_get_output(req, ServiceId::new(name, key))
                     .await
             }
+            WorkflowRequestType::Promise(name, key, promise_key, PromiseRequestType::Peek) => {
+                self.handle_workflow_promise_peek(req, ServiceId::new(name, key), promise_key)
+                    .await
+            }
+            WorkflowRequestType::Promise(name, key, promise_key, promise_request_type) => {
+                self.handle_workflow_promise_completion(
+                    req,
+                    ServiceId::new(name, key),
+                    promise_key,
+                    promise_request_type,
+                )
+                .await
+            }
         }
     }
 
@@ -156,4 +176,168 @@ where
             },
         )
     }
+
+    /// Resolves the workflow handler owning the promises of the workflow, checking the caller is
+    /// allowed to invoke it.
+    fn resolve_workflow_target<B>(
+        &self,
+        req: &Request<B>,
+        workflow_id: &ServiceId,
+    ) -> Result<InvocationTarget, HandlerError> {
+        let service_name = workflow_id.service_name.to_string();
+        let schemas = self.schemas.pinned();
+        let service = schemas
+            .resolve_latest_service(&service_name)
+            .ok_or_else(|| HandlerError::ServiceNotFound(service_name.clone()))?;
+        if service.ty != ServiceType::Workflow {
+            return Err(HandlerError::NotAWorkflow(service_name));
+        }
+        if !service.public {
+            return Err(HandlerError::PrivateService);
+        }
+        let handler = service
+            .handlers
+            .iter()
+            .find(|handler| matches!(handler.ty, HandlerMetadataType::Workflow))
+            .ok_or_else(|| HandlerError::NotAWorkflow(service_name.clone()))?;
+
+        // Check the authenticated principal is allowed to invoke the workflow
+        if let Some(ingress_authorization) =
+            schemas.resolve_ingress_authorization(&service_name, &handler.name)
+        {
+            let principal = req.extensions().get::<Principal>().map(Principal::as_str);
+            if !ingress_authorization.is_allowed(principal) {
+                return Err(HandlerError::Forbidden(service_name, handler.name.clone()));
+            }
+        }
+
+        Ok(InvocationTarget::workflow(
+            workflow_id.service_name.clone(),
+            workflow_id.key.clone(),
+            &*handler.name,
+            WorkflowHandlerType::Workflow,
+        ))
+    }
+
+    pub(crate) async fn handle_workflow_promise_completion<B: http_body::Body>(
+        self,
+        req: Request<B>,
+        workflow_id: ServiceId,
+        promise_key: String,
+        promise_request_type: PromiseRequestType,
+    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Check HTTP Method
+        if req.method() != Method::POST {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        let workflow_target = self.resolve_workflow_target(&req, &workflow_id)?;
+
+        // Collect body
+        let body = req
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| HandlerError::Body(e.into()))?
+            .to_bytes();
+        trace!(rpc.request = ?body);
+
+        let completion = match promise_request_type {
+            PromiseRequestType::Resolve => ResponseResult::Success(body),
+            PromiseRequestType::Reject => ResponseResult::Failure(InvocationError::new(
+                codes::UNKNOWN,
+                String::from_utf8_lossy(&body).to_string(),
+            )),
+            PromiseRequestType::Peek => unreachable!("peek doesn't complete the promise"),
+        };
+
+        info!(
+            restate.workflow.id = %workflow_id,
+            restate.promise.key = %promise_key,
+            "Processing workflow promise completion request"
+        );
+
+        let (dispatcher_req, correlation_id, response_rx) =
+            IngressDispatcherRequest::complete_promise(
+                workflow_target,
+                ByteString::from(promise_key),
+                completion,
+            );
+        if let Err(e) = self
+            .dispatcher
+            .dispatch_ingress_request(dispatcher_req)
+            .await
+        {
+            warn!(
+                restate.workflow.id = %workflow_id,
+                "Failed to dispatch: {}",
+                e,
+            );
+            return Err(HandlerError::Unavailable);
+        }
+
+        // Wait on response
+        let response = if let Ok(response) = response_rx.await {
+            response
+        } else {
+            self.dispatcher.evict_pending_response(correlation_id);
+            warn!("Response channel was closed");
+            return Err(HandlerError::Unavailable);
+        };
+
+        match response.result {
+            IngressResponseResult::Success(_, _) => Ok(Response::builder()
+                .status(StatusCode::OK)
+                .body(Full::default())
+                .unwrap()),
+            IngressResponseResult::Failure(e) => Err(HandlerError::Invocation(e)),
+        }
+    }
+
+    pub(crate) async fn handle_workflow_promise_peek<B: http_body::Body>(
+        self,
+        req: Request<B>,
+        workflow_id: ServiceId,
+        promise_key: String,
+    ) -> Result<Response<Full<Bytes>>, HandlerError>
+    where
+        <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Check HTTP Method
+        if req.method() != Method::GET {
+            return Err(HandlerError::MethodNotAllowed);
+        }
+
+        self.resolve_workflow_target(&req, &workflow_id)?;
+
+        match self
+            .storage_reader
+            .get_promise(workflow_id.clone(), ByteString::from(promise_key.as_str()))
+            .await
+        {
+            Ok(GetPromiseResult::Completed(ResponseResult::Success(value))) => {
+                Ok(Response::builder()
+                    .status(StatusCode::OK)
+                    .body(Full::new(value))
+                    .unwrap())
+            }
+            Ok(GetPromiseResult::Completed(ResponseResult::Failure(e))) => {
+                Err(HandlerError::Invocation(e))
+            }
+            Ok(GetPromiseResult::NotCompleted) => {
+                Err(HandlerError::PromiseNotCompleted(promise_key))
+            }
+            Err(e) => {
+                warn!(
+                    restate.workflow.id = %workflow_id,
+                    "Failed to read promise: {}",
+                    e,
+                );
+                Err(HandlerError::Unavailable)
+            }
+        }
+    }
 }
@@ -0,0 +1,213 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use jsonschema::Validator;
+use parking_lot::RwLock;
+use serde_json::Value;
+use tracing::warn;
+
+use restate_types::identifiers::{DeploymentId, ServiceRevision};
+use restate_types::schema::service::InputJsonSchema;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum InputSchemaValidationError {
+    #[error("request body is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("request body does not match the input JSON Schema at '{path}': {message}")]
+    Schema { path: String, message: String },
+}
+
+struct CompiledSchema {
+    deployment_id: DeploymentId,
+    revision: ServiceRevision,
+    // None if the schema cannot be compiled
+    validator: Option<Validator>,
+}
+
+impl CompiledSchema {
+    fn compile(input_json_schema: &InputJsonSchema) -> Self {
+        let validator = jsonschema::validator_for(&input_json_schema.schema)
+            .inspect_err(|e| {
+                warn!(
+                    "Cannot compile the input JSON Schema, request bodies won't be validated: {e}"
+                )
+            })
+            .ok();
+        Self {
+            deployment_id: input_json_schema.deployment_id,
+            revision: input_json_schema.revision,
+            validator,
+        }
+    }
+
+    fn is_compiled_from(&self, input_json_schema: &InputJsonSchema) -> bool {
+        self.deployment_id == input_json_schema.deployment_id
+            && self.revision == input_json_schema.revision
+    }
+}
+
+/// Validates request bodies against the input JSON Schema of the invoked handler.
+///
+/// Compiling a schema is expensive, hence the compiled schemas are cached per handler and
+/// recompiled only when a new revision of the service is registered.
+#[derive(Clone, Default)]
+pub(crate) struct InputValidator {
+    compiled_schemas: Arc<RwLock<HashMap<(String, String), Arc<CompiledSchema>>>>,
+}
+
+impl InputValidator {
+    pub(crate) fn validate(
+        &self,
+        service_name: &str,
+        handler_name: &str,
+        input_json_schema: &InputJsonSchema,
+        body: &Bytes,
+    ) -> Result<(), InputSchemaValidationError> {
+        if body.is_empty() {
+            // Handlers accepting an empty body were already checked by the input rules
+            return Ok(());
+        }
+
+        let compiled_schema = self.compiled_schema(service_name, handler_name, input_json_schema);
+        let Some(validator) = &compiled_schema.validator else {
+            return Ok(());
+        };
+
+        let value: Value = serde_json::from_slice(body)?;
+        validator.validate(&value).map_err(|e| {
+            let path = e.instance_path.to_string();
+            InputSchemaValidationError::Schema {
+                // The root of the document is the empty JSON pointer
+                path: if path.is_empty() {
+                    "/".to_owned()
+                } else {
+                    path
+                },
+                message: e.to_string(),
+            }
+        })
+    }
+
+    fn compiled_schema(
+        &self,
+        service_name: &str,
+        handler_name: &str,
+        input_json_schema: &InputJsonSchema,
+    ) -> Arc<CompiledSchema> {
+        let key = (service_name.to_owned(), handler_name.to_owned());
+        if let Some(compiled_schema) = self
+            .compiled_schemas
+            .read()
+            .get(&key)
+            .filter(|compiled_schema| compiled_schema.is_compiled_from(input_json_schema))
+        {
+            return Arc::clone(compiled_schema);
+        }
+
+        // Compile without holding the lock, concurrent requests might compile the schema twice
+        let compiled_schema = Arc::new(CompiledSchema::compile(input_json_schema));
+        self.compiled_schemas
+            .write()
+            .insert(key, Arc::clone(&compiled_schema));
+        compiled_schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn greeting_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "person": { "type": "string" }
+            },
+            "required": ["person"]
+        })
+    }
+
+    fn input_json_schema(revision: ServiceRevision, schema: Value) -> InputJsonSchema {
+        InputJsonSchema {
+            deployment_id: "dp_15VqmTOnXH3Vv2pl5HOG7UB".parse().unwrap(),
+            revision,
+            schema,
+        }
+    }
+
+    fn validate(
+        validator: &InputValidator,
+        schema: &Value,
+        body: &'static [u8],
+    ) -> Result<(), InputSchemaValidationError> {
+        validate_revision(validator, 1, schema, body)
+    }
+
+    fn validate_revision(
+        validator: &InputValidator,
+        revision: ServiceRevision,
+        schema: &Value,
+        body: &'static [u8],
+    ) -> Result<(), InputSchemaValidationError> {
+        validator.validate(
+            "Greeter",
+            "greet",
+            &input_json_schema(revision, schema.clone()),
+            &Bytes::from_static(body),
+        )
+    }
+
+    #[test]
+    fn valid_body() {
+        let validator = InputValidator::default();
+
+        assert!(validate(
+            &validator,
+            &greeting_schema(),
+            br#"{"person": "Francesco"}"#
+        )
+        .is_ok());
+        // Empty bodies are checked by the input rules
+        assert!(validate(&validator, &greeting_schema(), b"").is_ok());
+    }
+
+    #[test]
+    fn invalid_body() {
+        let validator = InputValidator::default();
+
+        assert!(matches!(
+            validate(&validator, &greeting_schema(), br#"{"person": 42}"#),
+            Err(InputSchemaValidationError::Schema { path, .. }) if path == "/person"
+        ));
+        assert!(matches!(
+            validate(&validator, &greeting_schema(), b"{}"),
+            Err(InputSchemaValidationError::Schema { path, .. }) if path == "/"
+        ));
+        assert!(matches!(
+            validate(&validator, &greeting_schema(), b"{"),
+            Err(InputSchemaValidationError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn recompiles_schema_of_new_revision() {
+        let validator = InputValidator::default();
+        let body = br#"{"person": 42}"#;
+
+        assert!(validate_revision(&validator, 1, &greeting_schema(), body).is_err());
+        assert!(validate_revision(&validator, 2, &json!({"type": "object"}), body).is_ok());
+    }
+}
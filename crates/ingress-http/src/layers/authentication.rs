@@ -0,0 +1,179 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::authentication::{strip_credentials, RequestAuthenticator};
+use crate::handler::error::HandlerError;
+use bytes::Bytes;
+use futures::future::{Either, Ready};
+use http::{Request, Response};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::debug;
+
+// Health checks are served to unauthenticated clients, e.g. load balancers
+const HEALTH_PATH: &str = "/restate/health";
+
+pub struct AuthenticationLayer {
+    authenticator: RequestAuthenticator,
+}
+
+impl AuthenticationLayer {
+    pub(crate) fn new(authenticator: RequestAuthenticator) -> Self {
+        Self { authenticator }
+    }
+}
+
+impl<S> Layer<S> for AuthenticationLayer {
+    type Service = Authentication<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Authentication {
+            inner,
+            authenticator: self.authenticator.clone(),
+        }
+    }
+}
+
+/// Authenticates the requests and stores the authenticated principal in the request extensions,
+/// removing the validated credentials from the headers. Requests which cannot be authenticated
+/// are rejected.
+#[derive(Clone)]
+pub struct Authentication<S> {
+    inner: S,
+    authenticator: RequestAuthenticator,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Authentication<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: http_body::Body + Default + From<Bytes>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Either<S::Future, Ready<Result<Self::Response, Self::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        if !self.authenticator.is_enabled() || req.uri().path() == HEALTH_PATH {
+            return Either::Left(self.inner.call(req));
+        }
+
+        match self
+            .authenticator
+            .authenticate(req.headers(), req.extensions())
+        {
+            Ok(principal) => {
+                strip_credentials(req.headers_mut());
+                req.extensions_mut().insert(principal);
+                Either::Left(self.inner.call(req))
+            }
+            Err(err) => {
+                debug!("Rejecting unauthenticated request: {err}");
+                Either::Right(futures::future::ready(Ok(HandlerError::Unauthenticated(
+                    err,
+                )
+                .into_response())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::authentication::{ApiKeyAuthenticator, Principal, X_RESTATE_API_KEY};
+    use http::StatusCode;
+    use http_body_util::Full;
+    use restate_test_util::assert_eq;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    use restate_types::config::ApiKeyOptions;
+
+    fn authentication_layer() -> AuthenticationLayer {
+        AuthenticationLayer::new(RequestAuthenticator::from_authenticators(vec![Box::new(
+            ApiKeyAuthenticator::new(vec![ApiKeyOptions {
+                principal: "billing".to_owned(),
+                key: "secret".to_owned(),
+            }]),
+        )]))
+    }
+
+    async fn call(req: Request<Full<Bytes>>) -> Response<Full<Bytes>> {
+        authentication_layer()
+            .layer(tower::service_fn(|req: Request<Full<Bytes>>| async move {
+                // The credentials must not reach the handler
+                assert!(req.headers().get(X_RESTATE_API_KEY).is_none());
+                // Echo the authenticated principal
+                let principal = req
+                    .extensions()
+                    .get::<Principal>()
+                    .map(|p| Bytes::copy_from_slice(p.as_str().as_bytes()))
+                    .unwrap_or_default();
+                Ok::<_, Infallible>(Response::new(Full::new(principal)))
+            }))
+            .oneshot(req)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn authenticated_request() {
+        let response = call(
+            Request::post("http://localhost/greeter.Greeter/greet")
+                .header(X_RESTATE_API_KEY, "secret")
+                .body(Full::default())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        assert_eq!(body, Bytes::from_static(b"billing"));
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_request() {
+        let response = call(
+            Request::post("http://localhost/greeter.Greeter/greet")
+                .body(Full::default())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = call(
+            Request::post("http://localhost/greeter.Greeter/greet")
+                .header(X_RESTATE_API_KEY, "wrong")
+                .body(Full::default())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn health_is_not_authenticated() {
+        let response = call(
+            Request::get(format!("http://localhost{HEALTH_PATH}"))
+                .body(Full::default())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
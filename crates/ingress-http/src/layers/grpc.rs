@@ -0,0 +1,933 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use http::uri::PathAndQuery;
+use http::{header, HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode, Uri};
+use http_body::{Frame, SizeHint};
+use http_body_util::{BodyExt, Full};
+use pin_project_lite::pin_project;
+use serde::{Deserialize, Serialize};
+use tower::{Layer, Service};
+
+use restate_types::live::Live;
+use restate_types::schema::invocation_target::{InputRules, InvocationTargetResolver};
+use restate_types::schema::service::ServiceMetadataResolver;
+
+const GRPC_CONTENT_TYPE: &str = "application/grpc";
+const GRPC_WEB_CONTENT_TYPE: &str = "application/grpc-web";
+const CONNECT_STREAMING_CONTENT_TYPE: &str = "application/connect+";
+const CONNECT_PROTOCOL_VERSION: HeaderName = HeaderName::from_static("connect-protocol-version");
+const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+/// Metadata carrying the key of the invoked virtual object or workflow
+const X_RESTATE_KEY: HeaderName = HeaderName::from_static("x-restate-key");
+
+// Length-prefixed message: 1 byte compressed flag + 4 bytes big endian length
+const MESSAGE_HEADER_LEN: usize = 5;
+
+/// gRPC status codes, see https://grpc.github.io/grpc/core/md_doc_statuscodes.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Code {
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    Unauthenticated = 16,
+}
+
+impl Code {
+    /// Translates the status code of the ingress responses, which for failed invocations is
+    /// the failure code of the invocation.
+    fn from_http_status(status: StatusCode) -> Self {
+        match status.as_u16() {
+            400 => Code::InvalidArgument,
+            401 => Code::Unauthenticated,
+            403 => Code::PermissionDenied,
+            404 | 410 => Code::NotFound,
+            405 | 501 => Code::Unimplemented,
+            409 => Code::Aborted,
+            429 => Code::ResourceExhausted,
+            500 => Code::Unknown,
+            503 => Code::Unavailable,
+            504 => Code::DeadlineExceeded,
+            400..=499 => Code::FailedPrecondition,
+            _ => Code::Internal,
+        }
+    }
+
+    fn connect_name(self) -> &'static str {
+        match self {
+            Code::Unknown => "unknown",
+            Code::InvalidArgument => "invalid_argument",
+            Code::DeadlineExceeded => "deadline_exceeded",
+            Code::NotFound => "not_found",
+            Code::PermissionDenied => "permission_denied",
+            Code::ResourceExhausted => "resource_exhausted",
+            Code::FailedPrecondition => "failed_precondition",
+            Code::Aborted => "aborted",
+            Code::Unimplemented => "unimplemented",
+            Code::Internal => "internal",
+            Code::Unavailable => "unavailable",
+            Code::Unauthenticated => "unauthenticated",
+        }
+    }
+
+    /// See https://connectrpc.com/docs/protocol#error-codes
+    fn connect_http_status(self) -> StatusCode {
+        match self {
+            Code::InvalidArgument | Code::FailedPrecondition => StatusCode::BAD_REQUEST,
+            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Code::PermissionDenied => StatusCode::FORBIDDEN,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::Aborted => StatusCode::CONFLICT,
+            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            Code::Unknown | Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Status {
+    code: Code,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponseBody {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct ConnectErrorBody<'a> {
+    code: &'static str,
+    message: &'a str,
+}
+
+impl Status {
+    fn new(code: Code, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn from_error_response(status: StatusCode, body: &[u8]) -> Self {
+        Self::new(
+            Code::from_http_status(status),
+            serde_json::from_slice::<ErrorResponseBody>(body)
+                .map(|body| body.message)
+                .unwrap_or_else(|_| status.canonical_reason().unwrap_or_default().to_owned()),
+        )
+    }
+
+    /// Replies with a trailers-only response, carrying the status in the headers.
+    fn into_grpc_response(self, content_type: HeaderValue) -> Response<ResponseBody> {
+        let mut response = Response::new(ResponseBody::default());
+        let headers = response.headers_mut();
+        headers.insert(header::CONTENT_TYPE, content_type);
+        headers.extend(self.into_trailers());
+        response
+    }
+
+    fn into_trailers(self) -> HeaderMap {
+        let mut trailers = HeaderMap::new();
+        trailers.insert(GRPC_STATUS, HeaderValue::from(self.code as u16));
+        if !self.message.is_empty() {
+            trailers.insert(
+                GRPC_MESSAGE,
+                HeaderValue::try_from(urlencoding::encode(&self.message).as_ref())
+                    .expect("percent-encoded message must be a valid header value"),
+            );
+        }
+        trailers
+    }
+
+    fn into_connect_response(self) -> Response<ResponseBody> {
+        Response::builder()
+            .status(self.code.connect_http_status())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(ResponseBody::from(Bytes::from(
+                serde_json::to_vec(&ConnectErrorBody {
+                    code: self.code.connect_name(),
+                    message: &self.message,
+                })
+                .expect("Serializing ConnectErrorBody should not fail"),
+            )))
+            .unwrap()
+    }
+}
+
+enum Protocol {
+    /// gRPC over HTTP/2. The content type of the messages follows from the codec of the request
+    /// content type, e.g. application/proto for application/grpc+proto.
+    Grpc {
+        content_type: HeaderValue,
+        message_content_type: String,
+    },
+    /// Unary requests of the Connect protocol
+    Connect,
+}
+
+impl Protocol {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .unwrap_or_default();
+
+        if content_type.starts_with(GRPC_CONTENT_TYPE)
+            && !content_type.starts_with(GRPC_WEB_CONTENT_TYPE)
+        {
+            let codec = content_type[GRPC_CONTENT_TYPE.len()..]
+                .strip_prefix('+')
+                .and_then(|codec| codec.split(';').next())
+                .filter(|codec| !codec.is_empty())
+                .unwrap_or("proto");
+            Some(Protocol::Grpc {
+                content_type: headers[header::CONTENT_TYPE].clone(),
+                message_content_type: format!("application/{codec}"),
+            })
+        } else if headers.contains_key(CONNECT_PROTOCOL_VERSION)
+            || content_type.starts_with(CONNECT_STREAMING_CONTENT_TYPE)
+        {
+            Some(Protocol::Connect)
+        } else {
+            None
+        }
+    }
+}
+
+pin_project! {
+    /// Request body of the ingress. The messages of gRPC requests are unwrapped from their
+    /// length-prefixed framing before reaching the handler.
+    #[project = RequestBodyProj]
+    pub enum RequestBody<B> {
+        Http { #[pin] body: B },
+        Grpc { message: Option<Bytes> },
+    }
+}
+
+impl<B> http_body::Body for RequestBody<B>
+where
+    B: http_body::Body<Data = Bytes>,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.project() {
+            RequestBodyProj::Http { body } => body.poll_frame(cx),
+            RequestBodyProj::Grpc { message } => {
+                Poll::Ready(message.take().map(Frame::data).map(Ok))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match self {
+            RequestBody::Http { body } => body.is_end_stream(),
+            RequestBody::Grpc { message } => message.is_none(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self {
+            RequestBody::Http { body } => body.size_hint(),
+            RequestBody::Grpc { message } => {
+                SizeHint::with_exact(message.as_ref().map(Bytes::len).unwrap_or_default() as u64)
+            }
+        }
+    }
+}
+
+/// Response body of the ingress. gRPC responses carry the status in the trailers, after the
+/// response message.
+pub enum ResponseBody {
+    Http(Full<Bytes>),
+    Grpc {
+        message: Option<Bytes>,
+        trailers: Option<HeaderMap>,
+    },
+}
+
+impl Default for ResponseBody {
+    fn default() -> Self {
+        ResponseBody::Http(Full::default())
+    }
+}
+
+impl From<Bytes> for ResponseBody {
+    fn from(value: Bytes) -> Self {
+        ResponseBody::Http(Full::new(value))
+    }
+}
+
+impl http_body::Body for ResponseBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.get_mut() {
+            ResponseBody::Http(body) => Pin::new(body).poll_frame(cx),
+            ResponseBody::Grpc { message, trailers } => Poll::Ready(
+                message
+                    .take()
+                    .map(Frame::data)
+                    .or_else(|| trailers.take().map(Frame::trailers))
+                    .map(Ok),
+            ),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match self {
+            ResponseBody::Http(body) => body.is_end_stream(),
+            ResponseBody::Grpc { message, trailers } => message.is_none() && trailers.is_none(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self {
+            ResponseBody::Http(body) => body.size_hint(),
+            ResponseBody::Grpc { .. } => SizeHint::default(),
+        }
+    }
+}
+
+fn decode_message(mut body: Bytes) -> Result<Bytes, Status> {
+    if body.len() < MESSAGE_HEADER_LEN {
+        return Err(Status::new(Code::InvalidArgument, "malformed gRPC message"));
+    }
+    let compressed = body.get_u8();
+    let len = body.get_u32() as usize;
+    if compressed != 0 {
+        return Err(Status::new(
+            Code::Unimplemented,
+            "compressed messages are not supported",
+        ));
+    }
+    if body.len() < len {
+        return Err(Status::new(Code::InvalidArgument, "malformed gRPC message"));
+    }
+    if body.len() > len {
+        return Err(Status::new(
+            Code::Unimplemented,
+            "client streaming is not supported, requests must carry a single message",
+        ));
+    }
+    Ok(body)
+}
+
+/// The messages of gRPC requests are passed to the handlers as they are, because transcoding
+/// them, e.g. from proto to JSON, requires the message descriptors which the ingress doesn't have.
+/// Returns the content type with which the message is passed to the handler, or rejects the
+/// request if the handler doesn't accept the message. Empty messages are passed as empty input to
+/// handlers without input.
+fn handler_content_type(
+    path: &str,
+    input_rules: Option<&InputRules>,
+    message: &Bytes,
+    message_content_type: String,
+) -> Result<Option<String>, Status> {
+    let Some(input_rules) = input_rules else {
+        // Unknown handlers are reported by the ingress
+        return Ok(Some(message_content_type));
+    };
+
+    if input_rules
+        .validate(Some(message_content_type.as_str()), message)
+        .is_ok()
+    {
+        Ok(Some(message_content_type))
+    } else if message.is_empty() && input_rules.validate(None, message).is_ok() {
+        Ok(None)
+    } else {
+        Err(Status::new(
+            Code::InvalidArgument,
+            format!(
+                "handler '{path}' expects {input_rules} as input, which gRPC messages of content type '{message_content_type}' cannot provide"
+            ),
+        ))
+    }
+}
+
+fn encode_message(message: Bytes) -> Bytes {
+    let mut buf = BytesMut::with_capacity(MESSAGE_HEADER_LEN + message.len());
+    buf.put_u8(0);
+    buf.put_u32(message.len() as u32);
+    buf.put(message);
+    buf.freeze()
+}
+
+pub struct GrpcLayer<Schemas> {
+    enabled: bool,
+    schemas: Live<Schemas>,
+}
+
+impl<Schemas> GrpcLayer<Schemas> {
+    pub(crate) fn new(enabled: bool, schemas: Live<Schemas>) -> Self {
+        Self { enabled, schemas }
+    }
+}
+
+impl<S, Schemas: Clone> Layer<S> for GrpcLayer<Schemas> {
+    type Service = Grpc<S, Schemas>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Grpc {
+            inner,
+            enabled: self.enabled,
+            schemas: self.schemas.clone(),
+        }
+    }
+}
+
+/// Serves the registered services over gRPC and the Connect protocol, by translating the
+/// requests to the HTTP ingress API and the responses back.
+///
+/// `/package.Service/Method` is mapped to the handler `Method` of the service `package.Service`,
+/// and the response status is translated to the gRPC status codes. Only unary calls are
+/// supported, and message payloads are passed to the handlers as they are. gRPC messages are
+/// therefore only accepted by handlers whose input matches the codec of the request, e.g.
+/// `application/grpc+json` for handlers with JSON input.
+#[derive(Clone)]
+pub struct Grpc<S, Schemas> {
+    inner: S,
+    enabled: bool,
+    schemas: Live<Schemas>,
+}
+
+/// Splits the `/package.Service/Method` path into the service and the handler name.
+fn parse_path(uri: &Uri) -> Result<(&str, &str), Status> {
+    let mut path_parts = uri.path().split('/').skip(1);
+    let (Some(service_name), Some(handler_name), None) =
+        (path_parts.next(), path_parts.next(), path_parts.next())
+    else {
+        return Err(Status::new(
+            Code::Unimplemented,
+            format!("unknown method '{}'", uri.path()),
+        ));
+    };
+    Ok((service_name, handler_name))
+}
+
+impl<S, Schemas> Grpc<S, Schemas>
+where
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver,
+{
+    /// Moves the key of virtual objects and workflows from the metadata to the request path.
+    fn rewrite_uri(&mut self, uri: &Uri, headers: &mut HeaderMap) -> Result<Uri, Status> {
+        let (service_name, handler_name) = parse_path(uri)?;
+
+        let key = headers.remove(X_RESTATE_KEY);
+        let is_keyed = self
+            .schemas
+            .live_load()
+            .resolve_latest_service_type(service_name)
+            .is_some_and(|ty| ty.is_keyed());
+        if !is_keyed {
+            return Ok(uri.clone());
+        }
+
+        let key = key
+            .as_ref()
+            .ok_or_else(|| {
+                Status::new(
+                    Code::InvalidArgument,
+                    format!(
+                        "missing '{X_RESTATE_KEY}' metadata, required to invoke '{service_name}'"
+                    ),
+                )
+            })?
+            .to_str()
+            .map_err(|e| {
+                Status::new(
+                    Code::InvalidArgument,
+                    format!("bad '{X_RESTATE_KEY}' metadata: {e}"),
+                )
+            })?;
+
+        let mut path_and_query = format!(
+            "/{service_name}/{}/{handler_name}",
+            urlencoding::encode(key)
+        );
+        if let Some(query) = uri.query() {
+            path_and_query.push('?');
+            path_and_query.push_str(query);
+        }
+
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = Some(
+            PathAndQuery::try_from(path_and_query)
+                .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?,
+        );
+        Uri::from_parts(parts).map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))
+    }
+
+    fn resolve_input_rules(&mut self, uri: &Uri) -> Option<InputRules> {
+        let (service_name, handler_name) = parse_path(uri).ok()?;
+        self.schemas
+            .live_load()
+            .resolve_latest_invocation_target(service_name, handler_name)
+            .map(|invocation_target| invocation_target.input_rules)
+    }
+}
+
+impl<S, Schemas, B> Service<Request<B>> for Grpc<S, Schemas>
+where
+    S: Service<Request<RequestBody<B>>, Response = Response<Full<Bytes>>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: std::error::Error,
+{
+    type Response = Response<ResponseBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let protocol = if self.enabled {
+            Protocol::from_headers(req.headers())
+        } else {
+            None
+        };
+
+        // The inner service is ready, hence we use it and leave the clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let Some(protocol) = protocol else {
+            return inner
+                .call(req.map(|body| RequestBody::Http { body }))
+                .map(|res| res.map(|res| res.map(ResponseBody::Http)))
+                .boxed();
+        };
+
+        let (mut parts, body) = req.into_parts();
+        let input_rules = self.resolve_input_rules(&parts.uri);
+        let uri = self.rewrite_uri(&parts.uri, &mut parts.headers);
+
+        match protocol {
+            Protocol::Grpc {
+                content_type,
+                message_content_type,
+            } => async move {
+                let reply_with_status = |status: Status| {
+                    Ok::<_, Infallible>(status.into_grpc_response(content_type.clone()))
+                };
+
+                let uri = match uri {
+                    Ok(uri) => uri,
+                    Err(status) => return reply_with_status(status),
+                };
+                let message = match body.collect().await {
+                    Ok(body) => body.to_bytes(),
+                    Err(e) => {
+                        return reply_with_status(Status::new(
+                            Code::Internal,
+                            format!("cannot read the request: {e}"),
+                        ))
+                    }
+                };
+                let message = match decode_message(message) {
+                    Ok(message) => message,
+                    Err(status) => return reply_with_status(status),
+                };
+                let handler_content_type = match handler_content_type(
+                    parts.uri.path(),
+                    input_rules.as_ref(),
+                    &message,
+                    message_content_type,
+                ) {
+                    Ok(handler_content_type) => handler_content_type,
+                    Err(status) => return reply_with_status(status),
+                };
+
+                parts.uri = uri;
+                match handler_content_type {
+                    Some(handler_content_type) => parts.headers.insert(
+                        header::CONTENT_TYPE,
+                        HeaderValue::try_from(handler_content_type)
+                            .expect("codec is part of a valid header value"),
+                    ),
+                    None => parts.headers.remove(header::CONTENT_TYPE),
+                };
+                let (parts, body) = inner
+                    .call(Request::from_parts(
+                        parts,
+                        RequestBody::Grpc {
+                            message: Some(message),
+                        },
+                    ))
+                    .await
+                    .unwrap_or_else(|e| match e {})
+                    .into_parts();
+                let body = body
+                    .collect()
+                    .await
+                    .unwrap_or_else(|e| match e {})
+                    .to_bytes();
+
+                if !parts.status.is_success() {
+                    return reply_with_status(Status::from_error_response(parts.status, &body));
+                }
+
+                let mut response = Response::from_parts(
+                    parts,
+                    ResponseBody::Grpc {
+                        message: Some(encode_message(body)),
+                        trailers: Some(HeaderMap::from_iter([(
+                            GRPC_STATUS,
+                            HeaderValue::from_static("0"),
+                        )])),
+                    },
+                );
+                response
+                    .headers_mut()
+                    .insert(header::CONTENT_TYPE, content_type);
+                Ok(response)
+            }
+            .boxed(),
+            Protocol::Connect => async move {
+                if parts
+                    .headers
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|ct| ct.to_str().ok())
+                    .is_some_and(|ct| ct.starts_with(CONNECT_STREAMING_CONTENT_TYPE))
+                {
+                    return Ok::<_, Infallible>(
+                        Status::new(
+                            Code::Unimplemented,
+                            "streaming is not supported, only unary requests are",
+                        )
+                        .into_connect_response(),
+                    );
+                }
+                parts.uri = match uri {
+                    Ok(uri) => uri,
+                    Err(status) => return Ok(status.into_connect_response()),
+                };
+
+                let response = inner
+                    .call(Request::from_parts(parts, RequestBody::Http { body }))
+                    .await
+                    .unwrap_or_else(|e| match e {});
+                if response.status().is_success() {
+                    return Ok(response.map(ResponseBody::Http));
+                }
+
+                let (parts, body) = response.into_parts();
+                let body = body
+                    .collect()
+                    .await
+                    .unwrap_or_else(|e| match e {})
+                    .to_bytes();
+                Ok(Status::from_error_response(parts.status, &body).into_connect_response())
+            }
+            .boxed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use restate_test_util::assert_eq;
+    use restate_types::invocation::{InvocationTargetType, VirtualObjectHandlerType};
+    use restate_types::schema::invocation_target::{
+        InputContentType, InputValidationRule, InvocationTargetMetadata,
+    };
+    use tower::ServiceExt;
+
+    use crate::mocks::MockSchemas;
+
+    fn target_with_input(
+        input_validation_rules: Vec<InputValidationRule>,
+    ) -> InvocationTargetMetadata {
+        let mut target = InvocationTargetMetadata::mock(InvocationTargetType::Service);
+        target.input_rules = InputRules {
+            input_validation_rules,
+        };
+        target
+    }
+
+    fn schemas() -> Live<MockSchemas> {
+        Live::from_value(
+            MockSchemas::default()
+                .with_service_and_target(
+                    "greeter.Greeter",
+                    "Greet",
+                    InvocationTargetMetadata::mock(InvocationTargetType::Service),
+                )
+                .with_service_and_target(
+                    "greeter.JsonGreeter",
+                    "Greet",
+                    target_with_input(vec![InputValidationRule::JsonValue {
+                        content_type: InputContentType::MimeTypeAndSubtype(
+                            "application".into(),
+                            "json".into(),
+                        ),
+                        schema: serde_json::json!({}),
+                    }]),
+                )
+                .with_service_and_target(
+                    "greeter.Pinger",
+                    "Ping",
+                    target_with_input(vec![InputValidationRule::NoBodyAndContentType]),
+                )
+                .with_service_and_target(
+                    "greeter.GreeterObject",
+                    "Greet",
+                    InvocationTargetMetadata::mock(InvocationTargetType::VirtualObject(
+                        VirtualObjectHandlerType::Exclusive,
+                    )),
+                ),
+        )
+    }
+
+    /// Echoes the request path, content type and body, or replies with the given error status.
+    async fn call(
+        req: Request<Full<Bytes>>,
+        error_status: Option<StatusCode>,
+    ) -> Response<ResponseBody> {
+        GrpcLayer::new(true, schemas())
+            .layer(tower::service_fn(
+                move |req: Request<RequestBody<Full<Bytes>>>| async move {
+                    if let Some(status) = error_status {
+                        return Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Full::new(Bytes::from_static(br#"{"message":"boom"}"#)))
+                                .unwrap(),
+                        );
+                    }
+                    let path = req.uri().path().to_owned();
+                    let content_type = req
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .cloned()
+                        .unwrap_or_else(|| HeaderValue::from_static("none"));
+                    let body = req.into_body().collect().await.unwrap().to_bytes();
+                    Ok(Response::builder()
+                        .header("x-path", path)
+                        .header("x-content-type", content_type)
+                        .body(Full::new(body))
+                        .unwrap())
+                },
+            ))
+            .oneshot(req)
+            .await
+            .unwrap()
+    }
+
+    fn grpc_request(path: &str) -> http::request::Builder {
+        Request::post(format!("http://localhost{path}"))
+            .header(header::CONTENT_TYPE, "application/grpc")
+    }
+
+    #[tokio::test]
+    async fn grpc_unary_call() {
+        let response = call(
+            grpc_request("/greeter.Greeter/Greet")
+                .body(Full::new(encode_message(Bytes::from_static(b"hello"))))
+                .unwrap(),
+            None,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-path"], "/greeter.Greeter/Greet");
+        let body = response.into_body().collect().await.unwrap();
+        assert_eq!(body.trailers().unwrap()[GRPC_STATUS], "0");
+        assert_eq!(
+            body.to_bytes(),
+            encode_message(Bytes::from_static(b"hello"))
+        );
+    }
+
+    #[tokio::test]
+    async fn grpc_keyed_call() {
+        let response = call(
+            grpc_request("/greeter.GreeterObject/Greet")
+                .header(X_RESTATE_KEY, "my key")
+                .body(Full::new(encode_message(Bytes::new())))
+                .unwrap(),
+            None,
+        )
+        .await;
+        assert_eq!(
+            response.headers()["x-path"],
+            "/greeter.GreeterObject/my%20key/Greet"
+        );
+
+        // The key is required
+        let response = call(
+            grpc_request("/greeter.GreeterObject/Greet")
+                .body(Full::new(encode_message(Bytes::new())))
+                .unwrap(),
+            None,
+        )
+        .await;
+        assert_eq!(response.headers()[GRPC_STATUS], "3");
+    }
+
+    #[tokio::test]
+    async fn grpc_json_call() {
+        let response = call(
+            Request::post("http://localhost/greeter.JsonGreeter/Greet")
+                .header(header::CONTENT_TYPE, "application/grpc+json")
+                .body(Full::new(encode_message(Bytes::from_static(
+                    br#"{"name":"Till"}"#,
+                ))))
+                .unwrap(),
+            None,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-content-type"], "application/json");
+        let body = response.into_body().collect().await.unwrap();
+        assert_eq!(body.trailers().unwrap()[GRPC_STATUS], "0");
+        assert_eq!(
+            body.to_bytes(),
+            encode_message(Bytes::from_static(br#"{"name":"Till"}"#))
+        );
+    }
+
+    #[tokio::test]
+    async fn grpc_call_with_incompatible_codec() {
+        let response = call(
+            grpc_request("/greeter.JsonGreeter/Greet")
+                .body(Full::new(encode_message(Bytes::from_static(
+                    b"\x0a\x04Till",
+                ))))
+                .unwrap(),
+            None,
+        )
+        .await;
+
+        // Rejected by the layer, the handler would otherwise echo the path
+        assert!(!response.headers().contains_key("x-path"));
+        assert_eq!(response.headers()[GRPC_STATUS], "3");
+    }
+
+    #[tokio::test]
+    async fn grpc_empty_message_to_handler_without_input() {
+        let response = call(
+            grpc_request("/greeter.Pinger/Ping")
+                .body(Full::new(encode_message(Bytes::new())))
+                .unwrap(),
+            None,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-content-type"], "none");
+        let body = response.into_body().collect().await.unwrap();
+        assert_eq!(body.trailers().unwrap()[GRPC_STATUS], "0");
+    }
+
+    #[tokio::test]
+    async fn connect_unary_call() {
+        let response = call(
+            Request::post("http://localhost/greeter.JsonGreeter/Greet")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(CONNECT_PROTOCOL_VERSION, "1")
+                .body(Full::new(Bytes::from_static(br#"{"name":"Till"}"#)))
+                .unwrap(),
+            None,
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-path"], "/greeter.JsonGreeter/Greet");
+        assert_eq!(response.headers()["x-content-type"], "application/json");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, Bytes::from_static(br#"{"name":"Till"}"#));
+    }
+
+    #[tokio::test]
+    async fn grpc_error() {
+        let response = call(
+            grpc_request("/greeter.Greeter/Greet")
+                .body(Full::new(encode_message(Bytes::new())))
+                .unwrap(),
+            Some(StatusCode::NOT_FOUND),
+        )
+        .await;
+
+        // Trailers-only response
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[GRPC_STATUS], "5");
+        assert_eq!(response.headers()[GRPC_MESSAGE], "boom");
+    }
+
+    #[tokio::test]
+    async fn connect_error() {
+        let response = call(
+            Request::post("http://localhost/greeter.Greeter/Greet")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(CONNECT_PROTOCOL_VERSION, "1")
+                .body(Full::new(Bytes::from_static(b"{}")))
+                .unwrap(),
+            Some(StatusCode::CONFLICT),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({"code": "aborted", "message": "boom"})
+        );
+    }
+
+    #[test]
+    fn decode_malformed_message() {
+        assert!(decode_message(Bytes::from_static(b"\0\0\0")).is_err());
+        assert!(decode_message(Bytes::from_static(b"\x01\0\0\0\x01a")).is_err());
+        assert!(decode_message(Bytes::from_static(b"\0\0\0\0\x01ab")).is_err());
+        assert_eq!(
+            decode_message(Bytes::from_static(b"\0\0\0\0\x01a")).unwrap(),
+            Bytes::from_static(b"a")
+        );
+    }
+}
@@ -8,16 +8,22 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod authentication;
 mod handler;
+mod input_validator;
 mod layers;
 mod metric_definitions;
+mod rate_limiter;
 mod server;
 
+pub use authentication::{AuthenticationOptionsError, ClientCertificateIdentity};
 pub use server::{HyperServerIngress, IngressServerError, StartSignal};
 
 use bytes::Bytes;
+use bytestring::ByteString;
+use restate_types::identifiers::ServiceId;
 use restate_types::ingress::InvocationResponse;
-use restate_types::invocation::InvocationQuery;
+use restate_types::invocation::{InvocationQuery, ResponseResult};
 use std::net::{IpAddr, SocketAddr};
 
 /// Client connection information for a given RPC request
@@ -45,11 +51,23 @@ pub enum GetOutputResult {
     Ready(InvocationResponse),
 }
 
+pub enum GetPromiseResult {
+    NotCompleted,
+    Completed(ResponseResult),
+}
+
 pub trait InvocationStorageReader {
     fn get_output(
         &self,
         query: InvocationQuery,
     ) -> impl std::future::Future<Output = Result<GetOutputResult, anyhow::Error>> + Send;
+
+    /// Reads the durable promise `key` of the given workflow.
+    fn get_promise(
+        &self,
+        workflow_id: ServiceId,
+        key: ByteString,
+    ) -> impl std::future::Future<Output = Result<GetPromiseResult, anyhow::Error>> + Send;
 }
 
 // Contains some mocks we use in unit tests in this crate
@@ -108,6 +126,9 @@ mod mocks {
                     output_description: "any".to_string(),
                     input_json_schema: None,
                     output_json_schema: None,
+                    retry_policy: None,
+                    ingress_authorization: None,
+                    rate_limit: None,
                 }],
                 ty: invocation_target_metadata.target_ty.into(),
                 deployment_id: DeploymentId::default(),
@@ -117,6 +138,12 @@ mod mocks {
                 workflow_completion_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                paused: false,
+                retry_policy: None,
+                ingress_authorization: None,
+                rate_limit: None,
+                concurrency_limit: None,
+                json_schema_validation: false,
             });
             self.1
                 .add(service_name, [(handler_name, invocation_target_metadata)]);
@@ -192,5 +219,13 @@ mod mocks {
                 .map(GetOutputResult::Ready)
                 .unwrap_or(GetOutputResult::NotFound))
         }
+
+        async fn get_promise(
+            &self,
+            _workflow_id: ServiceId,
+            _key: ByteString,
+        ) -> Result<GetPromiseResult, Error> {
+            Ok(GetPromiseResult::NotCompleted)
+        }
     }
 }
@@ -0,0 +1,486 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use http::{HeaderMap, HeaderName};
+use moka::policy::EvictionPolicy;
+use moka::sync::{Cache, CacheBuilder};
+use parking_lot::Mutex;
+
+use restate_types::schema::service::{RateLimit, ResolvedRateLimit};
+
+/// Maximum number of token buckets. Buckets are created per client for per-client rate limits,
+/// hence the least recently used ones are evicted. An evicted bucket is recreated full.
+const MAX_BUCKETS: u64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    service_name: String,
+    // None if the bucket is shared by the handlers of the service
+    handler_name: Option<String>,
+    // None if the bucket is shared by all clients
+    client: Option<Client>,
+}
+
+/// Client to which a per-client rate limit applies. Header values are kept apart from principals
+/// so that unauthenticated requests cannot use up the limit of a principal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Client {
+    Principal(String),
+    Header(String),
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate_limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate_limit: RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(rate_limit.burst().get()),
+            rate_limit,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens
+            + elapsed.as_secs_f64() * f64::from(self.rate_limit.requests_per_second.get()))
+        .min(f64::from(self.rate_limit.burst().get()));
+        self.last_refill = now;
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / f64::from(self.rate_limit.requests_per_second.get()),
+            ))
+        }
+    }
+}
+
+/// Token bucket rate limiter of the ingress, keyed by service, handler and client.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    client_header: Option<HeaderName>,
+    buckets: Cache<BucketKey, Arc<Mutex<TokenBucket>>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(client_header: Option<HeaderName>) -> Self {
+        Self::with_max_buckets(client_header, MAX_BUCKETS)
+    }
+
+    fn with_max_buckets(client_header: Option<HeaderName>, max_buckets: u64) -> Self {
+        Self {
+            client_header,
+            buckets: CacheBuilder::new(max_buckets)
+                .name("IngressRateLimiterBuckets")
+                .eviction_policy(EvictionPolicy::lru())
+                .build(),
+        }
+    }
+
+    /// Admits a request to the given handler, or returns the time after which the request would
+    /// be admitted. Per-client limits apply separately to each principal, and to each value of
+    /// the client header for requests without a principal. Requests with neither share a single
+    /// limit.
+    ///
+    /// Every ingress node enforces the limits on its own, hence a cluster with several ingress
+    /// nodes admits up to the configured rate on each of them. Moreover, when more than
+    /// [`MAX_BUCKETS`] buckets are in use, a client whose bucket has been evicted gets a new, full
+    /// bucket, which admits an additional burst.
+    pub(crate) fn try_acquire(
+        &self,
+        service_name: &str,
+        handler_name: &str,
+        rate_limit: ResolvedRateLimit,
+        principal: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Result<(), Duration> {
+        self.try_acquire_at(
+            service_name,
+            handler_name,
+            rate_limit,
+            principal,
+            headers,
+            Instant::now(),
+        )
+    }
+
+    fn try_acquire_at(
+        &self,
+        service_name: &str,
+        handler_name: &str,
+        rate_limit: ResolvedRateLimit,
+        principal: Option<&str>,
+        headers: &HeaderMap,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let (handler_name, rate_limit) = match rate_limit {
+            ResolvedRateLimit::Handler(rate_limit) => (Some(handler_name.to_owned()), rate_limit),
+            ResolvedRateLimit::Service(rate_limit) => (None, rate_limit),
+        };
+        let client = if rate_limit.per_client {
+            self.client(principal, headers)
+        } else {
+            None
+        };
+        let key = BucketKey {
+            service_name: service_name.to_owned(),
+            handler_name,
+            client,
+        };
+
+        let bucket = self.buckets.get_with(key, || {
+            Arc::new(Mutex::new(TokenBucket::new(rate_limit.clone(), now)))
+        });
+        let mut bucket = bucket.lock();
+        if bucket.rate_limit != rate_limit {
+            // The rate limit has been reconfigured
+            *bucket = TokenBucket::new(rate_limit, now);
+        }
+        bucket.try_acquire(now)
+    }
+
+    fn client(&self, principal: Option<&str>, headers: &HeaderMap) -> Option<Client> {
+        if let Some(principal) = principal {
+            return Some(Client::Principal(principal.to_owned()));
+        }
+
+        self.client_header
+            .as_ref()
+            .and_then(|client_header| headers.get(client_header))
+            .and_then(|value| value.to_str().ok())
+            .map(|value| Client::Header(value.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroU32;
+
+    use http::HeaderValue;
+    use restate_test_util::assert_eq;
+
+    const CLIENT_HEADER: HeaderName = HeaderName::from_static("x-client-id");
+
+    fn rate_limit(requests_per_second: u32, burst: u32, per_client: bool) -> RateLimit {
+        RateLimit {
+            requests_per_second: NonZeroU32::new(requests_per_second).unwrap(),
+            burst: Some(NonZeroU32::new(burst).unwrap()),
+            per_client,
+        }
+    }
+
+    fn client_headers(client: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CLIENT_HEADER, HeaderValue::from_static(client));
+        headers
+    }
+
+    #[test]
+    fn admits_burst_then_refills() {
+        let rate_limiter = RateLimiter::default();
+        let limit = ResolvedRateLimit::Service(rate_limit(2, 2, false));
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            assert!(rate_limiter
+                .try_acquire_at(
+                    "Greeter",
+                    "greet",
+                    limit.clone(),
+                    None,
+                    &HeaderMap::new(),
+                    now
+                )
+                .is_ok());
+        }
+        assert_eq!(
+            rate_limiter.try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            ),
+            Err(Duration::from_millis(500))
+        );
+
+        // One token is refilled every 500 millis
+        let now = now + Duration::from_millis(500);
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at("Greeter", "greet", limit, None, &HeaderMap::new(), now)
+            .is_err());
+    }
+
+    #[test]
+    fn service_limit_is_shared_by_handlers() {
+        let rate_limiter = RateLimiter::default();
+        let now = Instant::now();
+
+        let service_limit = ResolvedRateLimit::Service(rate_limit(1, 1, false));
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                service_limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "hello",
+                service_limit,
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_err());
+
+        // Handlers with their own limit use a separate bucket
+        let handler_limit = ResolvedRateLimit::Handler(rate_limit(1, 1, false));
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                handler_limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                handler_limit,
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn per_client_limit() {
+        let rate_limiter = RateLimiter::default();
+        let limit = ResolvedRateLimit::Service(rate_limit(1, 1, true));
+        let now = Instant::now();
+
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("noisy"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("noisy"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_err());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("quiet"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+
+        // Requests without a principal share a single bucket
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at("Greeter", "greet", limit, None, &HeaderMap::new(), now)
+            .is_err());
+    }
+
+    #[test]
+    fn per_client_limit_falls_back_to_client_header() {
+        let rate_limiter = RateLimiter::new(Some(CLIENT_HEADER));
+        let limit = ResolvedRateLimit::Service(rate_limit(1, 1, true));
+        let now = Instant::now();
+
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &client_headers("noisy"),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &client_headers("noisy"),
+                now
+            )
+            .is_err());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &client_headers("quiet"),
+                now
+            )
+            .is_ok());
+
+        // The principal takes precedence over the header, and is limited separately from a
+        // header with the same value
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("noisy"),
+                &client_headers("quiet"),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit,
+                Some("noisy"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn reconfigured_limit_resets_bucket() {
+        let rate_limiter = RateLimiter::default();
+        let now = Instant::now();
+
+        let limit = ResolvedRateLimit::Service(rate_limit(1, 1, false));
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                None,
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at("Greeter", "greet", limit, None, &HeaderMap::new(), now)
+            .is_err());
+
+        let limit = ResolvedRateLimit::Service(rate_limit(10, 10, false));
+        assert!(rate_limiter
+            .try_acquire_at("Greeter", "greet", limit, None, &HeaderMap::new(), now)
+            .is_ok());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_bucket() {
+        let rate_limiter = RateLimiter::with_max_buckets(None, 1);
+        let limit = ResolvedRateLimit::Service(rate_limit(1, 1, true));
+        let now = Instant::now();
+
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("noisy"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit.clone(),
+                Some("quiet"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+        rate_limiter.buckets.run_pending_tasks();
+        assert_eq!(rate_limiter.buckets.entry_count(), 1);
+
+        // The bucket of the evicted principal is recreated full
+        assert!(rate_limiter
+            .try_acquire_at(
+                "Greeter",
+                "greet",
+                limit,
+                Some("noisy"),
+                &HeaderMap::new(),
+                now
+            )
+            .is_ok());
+    }
+}
@@ -10,13 +10,18 @@
 
 use super::*;
 
+use crate::authentication::{
+    AuthenticationOptionsError, ClientCertificateIdentity, RequestAuthenticator,
+};
 use crate::handler::Handler;
+use crate::layers::grpc::{GrpcLayer, ResponseBody};
+use crate::rate_limiter::RateLimiter;
 use codederror::CodedError;
 use http::{Request, Response};
-use http_body_util::Full;
 use hyper::body::Incoming;
 use hyper_util::rt::TokioIo;
 use hyper_util::server::conn::auto;
+use restate_core::network::{TlsAcceptor, TlsError};
 use restate_core::{cancellation_watcher, task_center, TaskKind};
 use restate_ingress_dispatcher::{DispatchIngressRequest, IngressDispatcher};
 use restate_types::config::IngressOptions;
@@ -31,7 +36,7 @@ use tokio::sync::oneshot;
 use tower::{ServiceBuilder, ServiceExt};
 use tower_http::cors::CorsLayer;
 use tower_http::normalize_path::NormalizePathLayer;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 pub type StartSignal = oneshot::Receiver<SocketAddr>;
 
@@ -49,13 +54,23 @@ pub enum IngressServerError {
     #[error("error while running ingress http server: {0}")]
     #[code(unknown)]
     Running(#[from] hyper::Error),
+    #[error("invalid 'ingress.authentication' options: {0}")]
+    #[code(unknown)]
+    Authentication(#[from] AuthenticationOptionsError),
+    #[error("invalid 'ingress.tls' options: {0}")]
+    #[code(unknown)]
+    Tls(#[from] TlsError),
 }
 
 pub struct HyperServerIngress<Schemas, Dispatcher, StorageReader> {
     listening_addr: SocketAddr,
+    tls_acceptor: Option<TlsAcceptor>,
     concurrency_limit: usize,
+    enable_grpc: bool,
 
     // Parameters to build the layers
+    authenticator: RequestAuthenticator,
+    rate_limiter: RateLimiter,
     schemas: Live<Schemas>,
     dispatcher: Dispatcher,
     storage_reader: StorageReader,
@@ -74,17 +89,26 @@ where
         dispatcher: IngressDispatcher,
         schemas: Live<Schemas>,
         storage_reader: StorageReader,
-    ) -> HyperServerIngress<Schemas, IngressDispatcher, StorageReader> {
+    ) -> Result<HyperServerIngress<Schemas, IngressDispatcher, StorageReader>, IngressServerError>
+    {
         crate::metric_definitions::describe_metrics();
         let (hyper_ingress_server, _) = HyperServerIngress::new(
             ingress_options.bind_address,
+            ingress_options
+                .tls
+                .as_ref()
+                .map(TlsAcceptor::from_options)
+                .transpose()?,
             ingress_options.concurrent_api_requests_limit(),
+            ingress_options.enable_grpc,
+            RequestAuthenticator::from_options(&ingress_options.authentication)?,
+            RateLimiter::new(ingress_options.rate_limit_client_header.clone()),
             schemas,
             dispatcher,
             storage_reader,
         );
 
-        hyper_ingress_server
+        Ok(hyper_ingress_server)
     }
 }
 
@@ -96,7 +120,11 @@ where
 {
     pub(crate) fn new(
         listening_addr: SocketAddr,
+        tls_acceptor: Option<TlsAcceptor>,
         concurrency_limit: usize,
+        enable_grpc: bool,
+        authenticator: RequestAuthenticator,
+        rate_limiter: RateLimiter,
         schemas: Live<Schemas>,
         dispatcher: Dispatcher,
         storage_reader: StorageReader,
@@ -105,7 +133,11 @@ where
 
         let ingress = Self {
             listening_addr,
+            tls_acceptor,
             concurrency_limit,
+            enable_grpc,
+            authenticator,
+            rate_limiter,
             schemas,
             dispatcher,
             storage_reader,
@@ -118,7 +150,11 @@ where
     pub async fn run(self) -> anyhow::Result<()> {
         let HyperServerIngress {
             listening_addr,
+            tls_acceptor,
             concurrency_limit,
+            enable_grpc,
+            authenticator,
+            rate_limiter,
             schemas,
             dispatcher,
             storage_reader,
@@ -143,10 +179,19 @@ where
         // Prepare the handler
         let service = ServiceBuilder::new()
             .layer(NormalizePathLayer::trim_trailing_slash())
+            .layer(GrpcLayer::new(enable_grpc, schemas.clone()))
             .layer(layers::load_shed::LoadShedLayer::new(concurrency_limit))
             .layer(CorsLayer::very_permissive())
             .layer(layers::tracing_context_extractor::HttpTraceContextExtractorLayer)
-            .service(Handler::new(schemas, dispatcher, storage_reader));
+            .layer(layers::authentication::AuthenticationLayer::new(
+                authenticator,
+            ))
+            .service(Handler::new(
+                schemas,
+                dispatcher,
+                storage_reader,
+                rate_limiter,
+            ));
 
         info!(
             net.host.addr = %local_addr.ip(),
@@ -165,7 +210,7 @@ where
             tokio::select! {
                 res = listener.accept() => {
                     let (stream, remote_peer) = res?;
-                    Self::handle_connection(stream, remote_peer, service.clone())?;
+                    Self::handle_connection(stream, remote_peer, tls_acceptor.clone(), service.clone())?;
                 }
                   _ = &mut shutdown => {
                     return Ok(());
@@ -177,13 +222,14 @@ where
     fn handle_connection<T, F>(
         stream: TcpStream,
         remote_peer: SocketAddr,
+        tls_acceptor: Option<TlsAcceptor>,
         handler: T,
     ) -> anyhow::Result<()>
     where
         F: Send,
         T: tower::Service<
                 Request<Incoming>,
-                Response = Response<Full<Bytes>>,
+                Response = Response<ResponseBody>,
                 Error = Infallible,
                 Future = F,
             > + Clone
@@ -191,32 +237,85 @@ where
             + 'static,
     {
         let connect_info = ConnectInfo::new(remote_peer);
-        let io = TokioIo::new(stream);
+
+        // Spawn a tokio task to serve the connection
+        task_center().spawn(TaskKind::Ingress, "ingress", None, async move {
+            match tls_acceptor {
+                Some(tls_acceptor) => {
+                    let stream = match tls_acceptor.accept(stream).await {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            debug!("TLS handshake with {remote_peer} failed: {err}");
+                            return Ok(());
+                        }
+                    };
+                    // Only verified certificates are presented, since the client certificates
+                    // are requested only if a client CA is configured.
+                    let client_identity = stream
+                        .get_ref()
+                        .1
+                        .peer_certificates()
+                        .and_then(|certificates| certificates.first())
+                        .and_then(|certificate| {
+                            ClientCertificateIdentity::from_der(certificate.as_ref())
+                        });
+                    Self::serve_connection(
+                        TokioIo::new(stream),
+                        connect_info,
+                        client_identity,
+                        handler,
+                    )
+                    .await;
+                }
+                None => {
+                    Self::serve_connection(TokioIo::new(stream), connect_info, None, handler).await;
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    async fn serve_connection<I, T, F>(
+        io: I,
+        connect_info: ConnectInfo,
+        client_identity: Option<ClientCertificateIdentity>,
+        handler: T,
+    ) where
+        I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+        F: Send,
+        T: tower::Service<
+                Request<Incoming>,
+                Response = Response<ResponseBody>,
+                Error = Infallible,
+                Future = F,
+            > + Clone
+            + Send
+            + 'static,
+    {
         let handler = hyper_util::service::TowerToHyperService::new(handler.map_request(
             move |mut req: Request<Incoming>| {
                 req.extensions_mut().insert(connect_info);
+                if let Some(client_identity) = &client_identity {
+                    req.extensions_mut().insert(client_identity.clone());
+                }
                 req
             },
         ));
 
-        // Spawn a tokio task to serve the connection
-        task_center().spawn(TaskKind::Ingress, "ingress", None, async move {
-            let shutdown = cancellation_watcher();
-            let auto_connection = auto::Builder::new(TaskCenterExecutor);
-            let serve_connection_fut = auto_connection.serve_connection(io, handler);
+        let shutdown = cancellation_watcher();
+        let auto_connection = auto::Builder::new(TaskCenterExecutor);
+        let serve_connection_fut = auto_connection.serve_connection(io, handler);
 
-            tokio::select! {
-                res = serve_connection_fut => {
-                    if let Err(err) = res {
-                        warn!("Error when serving the connection: {:?}", err);
-                    }
+        tokio::select! {
+            res = serve_connection_fut => {
+                if let Err(err) = res {
+                    warn!("Error when serving the connection: {:?}", err);
                 }
-                _ = shutdown => {}
             }
-            Ok(())
-        })?;
-
-        Ok(())
+            _ = shutdown => {}
+        }
     }
 }
 
@@ -345,7 +444,11 @@ mod tests {
         // Create the ingress and start it
         let (ingress, start_signal) = HyperServerIngress::new(
             "0.0.0.0:0".parse().unwrap(),
+            None,
             Semaphore::MAX_PERMITS,
+            false,
+            RequestAuthenticator::default(),
+            RateLimiter::default(),
             Live::from_value(mock_schemas()),
             MockDispatcher::new(ingress_request_tx),
             MockStorageReader::default(),
@@ -9,32 +9,44 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
 use base64::Engine;
 use bytes::Bytes;
-use metrics::counter;
+use metrics::{counter, gauge};
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
 use opentelemetry::trace::TraceContextExt;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use parking_lot::Mutex;
 use rdkafka::consumer::stream_consumer::StreamPartitionQueue;
-use rdkafka::consumer::{Consumer, DefaultConsumerContext, StreamConsumer};
+use rdkafka::consumer::{Consumer, ConsumerContext, StreamConsumer};
 use rdkafka::error::KafkaError;
-use rdkafka::message::BorrowedMessage;
-use rdkafka::{ClientConfig, Message};
+use rdkafka::message::{BorrowedMessage, Headers};
+use rdkafka::statistics::Statistics;
+use rdkafka::{ClientConfig, ClientContext, Message};
 use tokio::sync::oneshot;
-use tracing::{debug, info, info_span, Instrument};
+use tracing::{debug, info, info_span, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use restate_core::{cancellation_watcher, TaskCenter, TaskId, TaskKind};
 use restate_ingress_dispatcher::{
     DeduplicationId, DispatchIngressRequest, IngressDispatcher, IngressDispatcherRequest,
 };
+use restate_types::config::IngressOptions;
 use restate_types::invocation::{Header, SpanRelation};
 use restate_types::message::MessageIndex;
-use restate_types::schema::subscriptions::{EventReceiverServiceType, Sink, Subscription};
+use restate_types::schema::subscriptions::{
+    EventReceiverServiceType, RecordFilter, RecordSelector, Sink, Subscription, SubscriptionRecord,
+    FILTER_OPTION, HEADERS_ALLOW_OPTION, HEADERS_DENY_OPTION, KEY_OPTION,
+};
 
-use crate::metric_definitions::KAFKA_INGRESS_REQUESTS;
+use crate::decoder::{DecodeErrorPolicy, ValueDecoder};
+use crate::metric_definitions::{
+    KAFKA_INGRESS_CONSUMER_LAG, KAFKA_INGRESS_DECODE_FAILURES, KAFKA_INGRESS_FILTERED_RECORDS,
+    KAFKA_INGRESS_REQUESTS,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -56,13 +68,80 @@ pub enum Error {
     TopicPartitionSplit(String, i32),
 }
 
-type MessageConsumer = StreamConsumer<DefaultConsumerContext>;
+type MessageConsumer = StreamConsumer<SubscriptionContext>;
+
+/// Context of the subscription consumers, reporting the consumer lag from the statistics emitted
+/// by librdkafka every `statistics.interval.ms`.
+struct SubscriptionContext {
+    subscription_id: String,
+    // Partitions whose consumer lag is reported, so it can be reset once they are not consumed
+    // anymore, e.g. after a rebalance. None once the consumer is stopped.
+    reported_partitions: Mutex<Option<HashSet<(String, i32)>>>,
+}
+
+impl SubscriptionContext {
+    fn new(subscription_id: String) -> Self {
+        Self {
+            subscription_id,
+            reported_partitions: Mutex::new(Some(HashSet::new())),
+        }
+    }
+
+    fn consumer_lag_gauge(&self, topic: &str, partition: i32) -> metrics::Gauge {
+        gauge!(
+            KAFKA_INGRESS_CONSUMER_LAG,
+            "subscription" => self.subscription_id.clone(),
+            "topic" => topic.to_owned(),
+            "partition" => partition.to_string()
+        )
+    }
+
+    /// Resets the consumer lag of all the partitions consumed so far, and ignores the statistics
+    /// emitted afterwards. Invoked when the consumer is stopped, e.g. because the subscription
+    /// was paused or removed, as the last reported lag would otherwise be exported for good.
+    fn reset_consumer_lag(&self) {
+        for (topic, partition) in self.reported_partitions.lock().take().into_iter().flatten() {
+            self.consumer_lag_gauge(&topic, partition).set(0.0);
+        }
+    }
+}
+
+impl ClientContext for SubscriptionContext {
+    fn stats(&self, statistics: Statistics) {
+        let mut guard = self.reported_partitions.lock();
+        let Some(previously_reported_partitions) = guard.as_mut() else {
+            return;
+        };
+
+        let mut reported_partitions = HashSet::new();
+        for (topic_name, topic) in statistics.topics {
+            for (partition_id, partition) in topic.partitions {
+                // The internal unassigned partition has id -1, while the lag is -1 when unknown
+                if partition_id < 0 || partition.consumer_lag < 0 {
+                    continue;
+                }
+                self.consumer_lag_gauge(&topic_name, partition_id)
+                    .set(partition.consumer_lag as f64);
+                reported_partitions.insert((topic_name.clone(), partition_id));
+            }
+        }
+
+        // Reset the lag of the partitions which were revoked from this consumer
+        for (topic, partition) in previously_reported_partitions.difference(&reported_partitions) {
+            self.consumer_lag_gauge(topic, *partition).set(0.0);
+        }
+        *previously_reported_partitions = reported_partitions;
+    }
+}
+
+impl ConsumerContext for SubscriptionContext {}
 
 #[derive(Debug, Hash)]
 pub struct KafkaDeduplicationId {
     consumer_group: String,
     topic: String,
     partition: i32,
+    offsets_generation: u32,
 }
 
 impl fmt::Display for KafkaDeduplicationId {
@@ -71,7 +150,12 @@ impl fmt::Display for KafkaDeduplicationId {
             f,
             "{}-{}-{}",
             self.consumer_group, self.topic, self.partition
-        )
+        )?;
+        // Ids of subscriptions whose offsets were never reset are unchanged
+        if self.offsets_generation > 0 {
+            write!(f, "@{}", self.offsets_generation)?;
+        }
+        Ok(())
     }
 }
 
@@ -87,28 +171,161 @@ impl DeduplicationId for KafkaDeduplicationId {
     }
 }
 
+/// Selects the Kafka record headers which are forwarded as invocation headers, according to
+/// the allow and deny lists of the subscription.
+#[derive(Clone, Debug, Default)]
+struct HeadersFilter {
+    // None if all headers are allowed
+    allow: Option<Vec<String>>,
+    deny: Vec<String>,
+}
+
+impl HeadersFilter {
+    fn from_subscription(subscription: &Subscription) -> Self {
+        let parse_list = |list: &String| {
+            list.split(',')
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>()
+        };
+        Self {
+            allow: subscription
+                .metadata()
+                .get(HEADERS_ALLOW_OPTION)
+                .map(parse_list),
+            deny: subscription
+                .metadata()
+                .get(HEADERS_DENY_OPTION)
+                .map(parse_list)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_forwarded(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        // The trace context is used to link the ingress span, and the kafka.* and restate.*
+        // headers are reserved for the attributes generated by Restate
+        if name == "traceparent"
+            || name == "tracestate"
+            || name.starts_with("kafka.")
+            || name.starts_with("restate.")
+        {
+            return false;
+        }
+
+        let matches = |pattern: &String| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == *pattern,
+        };
+        !self.deny.iter().any(matches)
+            && self
+                .allow
+                .as_ref()
+                .map_or(true, |allow| allow.iter().any(matches))
+    }
+}
+
+/// Kafka record evaluated by the filter and key expressions of the subscription.
+struct KafkaRecord<'a, M> {
+    msg: &'a M,
+    // None if not needed by the expressions, or if the payload is not valid JSON
+    json_value: Option<serde_json::Value>,
+}
+
+impl<'a, M: Message> SubscriptionRecord for KafkaRecord<'a, M> {
+    fn key(&self) -> Option<&[u8]> {
+        self.msg.key()
+    }
+
+    fn header(&self, name: &str) -> Option<&[u8]> {
+        self.msg
+            .headers()?
+            .iter()
+            .find(|header| header.key.eq_ignore_ascii_case(name))
+            .and_then(|header| header.value)
+    }
+
+    fn json_value(&self) -> Option<&serde_json::Value> {
+        self.json_value.as_ref()
+    }
+}
+
+/// Reads the W3C trace context from the Kafka record headers.
+struct KafkaHeadersExtractor<'a, H>(&'a H);
+
+impl<'a, H: Headers> Extractor for KafkaHeadersExtractor<'a, H> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|header| header.key.eq_ignore_ascii_case(key))
+            .and_then(|header| header.value)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.iter().map(|header| header.key).collect()
+    }
+}
+
 #[derive(Clone)]
 pub struct MessageSender {
     subscription: Subscription,
     dispatcher: IngressDispatcher,
+    headers_filter: HeadersFilter,
+    // None if the payloads are forwarded as they are
+    value_decoder: Option<ValueDecoder>,
+    decode_error_policy: DecodeErrorPolicy,
+    filter: Option<RecordFilter>,
+    // None if the key is the Kafka record key
+    key_selector: Option<RecordSelector>,
 
     subscription_id: String,
     ingress_request_counter: metrics::Counter,
+    decode_failures_counter: metrics::Counter,
+    filtered_records_counter: metrics::Counter,
 }
 
 impl MessageSender {
-    pub fn new(subscription: Subscription, dispatcher: IngressDispatcher) -> Self {
+    pub fn new(
+        subscription: Subscription,
+        dispatcher: IngressDispatcher,
+        options: &IngressOptions,
+    ) -> Self {
         Self {
             subscription_id: subscription.id().to_string(),
             ingress_request_counter: counter!(
                 KAFKA_INGRESS_REQUESTS,
                 "subscription" => subscription.id().to_string()
             ),
+            decode_failures_counter: counter!(
+                KAFKA_INGRESS_DECODE_FAILURES,
+                "subscription" => subscription.id().to_string()
+            ),
+            filtered_records_counter: counter!(
+                KAFKA_INGRESS_FILTERED_RECORDS,
+                "subscription" => subscription.id().to_string()
+            ),
+            // The expressions were validated when creating the subscription
+            filter: subscription
+                .metadata()
+                .get(FILTER_OPTION)
+                .and_then(|filter| filter.parse().ok()),
+            key_selector: subscription
+                .metadata()
+                .get(KEY_OPTION)
+                .and_then(|key| key.parse().ok()),
+            headers_filter: HeadersFilter::from_subscription(&subscription),
+            value_decoder: ValueDecoder::from_subscription(&subscription, options),
+            decode_error_policy: DecodeErrorPolicy::from_subscription(&subscription),
             subscription,
             dispatcher,
         }
     }
 
+    pub fn subscription(&self) -> &Subscription {
+        &self.subscription
+    }
+
     async fn send(&self, consumer_group_id: &str, msg: BorrowedMessage<'_>) -> Result<(), Error> {
         // Prepare ingress span
         let ingress_span = info_span!(
@@ -119,27 +336,85 @@ impl MessageSender {
             messaging.source.name = msg.topic(),
             messaging.destination.name = %self.subscription.sink()
         );
+        if let Some(record_headers) = msg.headers() {
+            let producer_context =
+                TraceContextPropagator::new().extract(&KafkaHeadersExtractor(record_headers));
+            let producer_span_context = producer_context.span().span_context().clone();
+            if producer_span_context.is_valid() {
+                ingress_span.add_link(producer_span_context);
+            }
+        }
         info!(parent: &ingress_span, "Processing Kafka ingress request");
         let ingress_span_context = ingress_span.context().span().span_context().clone();
 
-        let key = if let Some(k) = msg.key() {
-            Bytes::copy_from_slice(k)
-        } else {
-            Bytes::default()
+        let payload = match (msg.payload(), &self.value_decoder) {
+            (Some(p), Some(decoder)) => match decoder.decode(p).await {
+                Ok(payload) => payload,
+                Err(e) => {
+                    // Records whose schema cannot be resolved right now are never skipped
+                    let retryable = e.is_retryable();
+                    return self.on_decode_error(&ingress_span, &msg, e.into(), retryable);
+                }
+            },
+            (Some(p), None) => Bytes::copy_from_slice(p),
+            (None, _) => Bytes::default(),
         };
-        let payload = if let Some(p) = msg.payload() {
-            Bytes::copy_from_slice(p)
-        } else {
-            Bytes::default()
+
+        let needs_json_value = self
+            .filter
+            .as_ref()
+            .is_some_and(RecordFilter::needs_json_value)
+            || self
+                .key_selector
+                .as_ref()
+                .is_some_and(RecordSelector::needs_json_value);
+        let record = KafkaRecord {
+            msg: &msg,
+            json_value: needs_json_value
+                .then(|| serde_json::from_slice(&payload).ok())
+                .flatten(),
         };
-        let headers = Self::generate_events_attributes(&msg, &self.subscription_id);
+        if let Some(filter) = &self.filter {
+            if !filter.matches(&record) {
+                debug!(
+                    parent: &ingress_span,
+                    "Skipping Kafka record at topic {} partition {} offset {}, as it doesn't match the subscription filter",
+                    msg.topic(),
+                    msg.partition(),
+                    msg.offset()
+                );
+                self.filtered_records_counter.increment(1);
+                return Ok(());
+            }
+        }
+
+        let key = match &self.key_selector {
+            Some(key_selector) => match key_selector.select_key(&record) {
+                Some(key) => Bytes::from(key),
+                None => {
+                    return self.on_decode_error(
+                        &ingress_span,
+                        &msg,
+                        anyhow::anyhow!("the key expression selects no string, number or boolean"),
+                        false,
+                    )
+                }
+            },
+            None => msg.key().map(Bytes::copy_from_slice).unwrap_or_default(),
+        };
+        let headers =
+            Self::generate_events_attributes(&msg, &self.subscription_id, &self.headers_filter);
 
         let req = IngressDispatcherRequest::event(
             &self.subscription,
             key,
             payload,
             SpanRelation::Parent(ingress_span_context),
-            Some(Self::generate_deduplication_id(consumer_group_id, &msg)),
+            Some(Self::generate_deduplication_id(
+                consumer_group_id,
+                self.subscription.offsets_generation(),
+                &msg,
+            )),
             headers,
         )
         .map_err(|cause| Error::Event {
@@ -159,7 +434,41 @@ impl MessageSender {
         Ok(())
     }
 
-    fn generate_events_attributes(msg: &impl Message, subscription_id: &str) -> Vec<Header> {
+    /// Applies the decode error policy of the subscription to a record which cannot be turned
+    /// into an event. Retryable failures stop the consumer regardless of the policy.
+    fn on_decode_error(
+        &self,
+        ingress_span: &Span,
+        msg: &BorrowedMessage<'_>,
+        cause: anyhow::Error,
+        retryable: bool,
+    ) -> Result<(), Error> {
+        match self.decode_error_policy {
+            DecodeErrorPolicy::Skip if !retryable => {
+                warn!(
+                    parent: ingress_span,
+                    "Skipping Kafka record at topic {} partition {} offset {}: {cause}",
+                    msg.topic(),
+                    msg.partition(),
+                    msg.offset()
+                );
+                self.decode_failures_counter.increment(1);
+                Ok(())
+            }
+            DecodeErrorPolicy::Skip | DecodeErrorPolicy::Stop => Err(Error::Event {
+                topic: msg.topic().to_string(),
+                partition: msg.partition(),
+                offset: msg.offset(),
+                cause,
+            }),
+        }
+    }
+
+    fn generate_events_attributes(
+        msg: &impl Message,
+        subscription_id: &str,
+        headers_filter: &HeadersFilter,
+    ) -> Vec<Header> {
         let mut headers = Vec::with_capacity(6);
         headers.push(Header::new("kafka.offset", msg.offset().to_string()));
         headers.push(Header::new("kafka.topic", msg.topic()));
@@ -179,11 +488,31 @@ impl MessageSender {
             ));
         }
 
+        if let Some(record_headers) = msg.headers() {
+            for record_header in record_headers.iter() {
+                if !headers_filter.is_forwarded(record_header.key) {
+                    continue;
+                }
+                let Some(value) = record_header
+                    .value
+                    .and_then(|value| std::str::from_utf8(value).ok())
+                else {
+                    debug!(
+                        "Not forwarding the Kafka record header '{}' as its value is not valid UTF-8",
+                        record_header.key
+                    );
+                    continue;
+                };
+                headers.push(Header::new(record_header.key, value));
+            }
+        }
+
         headers
     }
 
     fn generate_deduplication_id(
         consumer_group: &str,
+        offsets_generation: u32,
         msg: &impl Message,
     ) -> (KafkaDeduplicationId, MessageIndex) {
         (
@@ -191,6 +520,7 @@ impl MessageSender {
                 consumer_group: consumer_group.to_owned(),
                 topic: msg.topic().to_owned(),
                 partition: msg.partition(),
+                offsets_generation,
             },
             msg.offset() as u64,
         )
@@ -220,6 +550,10 @@ impl ConsumerTask {
         }
     }
 
+    pub fn subscription(&self) -> &Subscription {
+        self.sender.subscription()
+    }
+
     pub async fn run(self, mut rx: oneshot::Receiver<()>) -> Result<(), Error> {
         // Create the consumer and subscribe to the topic
         let consumer_group_id = self
@@ -232,7 +566,9 @@ impl ConsumerTask {
             self.topics, self.client_config
         );
 
-        let consumer: Arc<MessageConsumer> = Arc::new(self.client_config.create()?);
+        let consumer: Arc<MessageConsumer> = Arc::new(self.client_config.create_with_context(
+            SubscriptionContext::new(self.sender.subscription_id.clone()),
+        )?);
         let topics: Vec<&str> = self.topics.iter().map(|x| &**x).collect();
         consumer.subscribe(&topics)?;
 
@@ -293,6 +629,9 @@ impl ConsumerTask {
         for task_id in topic_partition_tasks.into_values() {
             self.task_center.cancel_task(task_id);
         }
+        // The subscription was paused, removed or failed, so its partitions are not consumed
+        // anymore until the consumer is restarted
+        consumer.context().reset_consumer_lag();
         result
     }
 }
@@ -301,7 +640,7 @@ async fn topic_partition_queue_consumption_loop(
     sender: MessageSender,
     topic: String,
     partition: i32,
-    topic_partition_consumer: StreamPartitionQueue<DefaultConsumerContext>,
+    topic_partition_consumer: StreamPartitionQueue<SubscriptionContext>,
     consumer: Arc<MessageConsumer>,
     consumer_group_id: String,
 ) -> Result<(), anyhow::Error> {
@@ -321,3 +660,60 @@ async fn topic_partition_queue_consumption_loop(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_filter(allow: Option<&str>, deny: Option<&str>) -> HeadersFilter {
+        let mut subscription = Subscription::mock();
+        if let Some(allow) = allow {
+            subscription
+                .metadata_mut()
+                .insert(HEADERS_ALLOW_OPTION.to_owned(), allow.to_owned());
+        }
+        if let Some(deny) = deny {
+            subscription
+                .metadata_mut()
+                .insert(HEADERS_DENY_OPTION.to_owned(), deny.to_owned());
+        }
+        HeadersFilter::from_subscription(&subscription)
+    }
+
+    #[test]
+    fn deduplication_id_changes_with_offsets_generation() {
+        let deduplication_id = |offsets_generation| {
+            KafkaDeduplicationId {
+                consumer_group: "my-group".to_owned(),
+                topic: "my-topic".to_owned(),
+                partition: 3,
+                offsets_generation,
+            }
+            .to_string()
+        };
+
+        assert_eq!(deduplication_id(0), "my-group-my-topic-3");
+        assert_eq!(deduplication_id(2), "my-group-my-topic-3@2");
+    }
+
+    #[test]
+    fn forward_all_headers_by_default() {
+        let filter = headers_filter(None, None);
+
+        assert!(filter.is_forwarded("tenant-id"));
+        assert!(filter.is_forwarded("Schema-Version"));
+        assert!(!filter.is_forwarded("traceparent"));
+        assert!(!filter.is_forwarded("kafka.offset"));
+        assert!(!filter.is_forwarded("restate.subscription.id"));
+    }
+
+    #[test]
+    fn allow_and_deny_lists() {
+        let filter = headers_filter(Some("tenant-id, schema-*"), Some("schema-registry-url"));
+
+        assert!(filter.is_forwarded("Tenant-Id"));
+        assert!(filter.is_forwarded("schema-version"));
+        assert!(!filter.is_forwarded("schema-registry-url"));
+        assert!(!filter.is_forwarded("x-custom"));
+    }
+}
@@ -0,0 +1,468 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of the record values framed with the schema registry wire format: a zero magic byte,
+//! followed by the 4 bytes big endian schema id and the encoded value. Protobuf values have in
+//! addition the indexes of the message within the schema file, between the id and the value.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+use jsonschema::Validator;
+use parking_lot::Mutex;
+use prost_reflect::{DescriptorPool, DynamicMessage, FileDescriptor, MessageDescriptor};
+use protox::file::{ChainFileResolver, File, FileResolver, GoogleFileResolver};
+use serde::Deserialize;
+
+use restate_types::config::IngressOptions;
+use restate_types::schema::subscriptions::{
+    Subscription, ON_DECODE_ERROR_OPTION, PROTOBUF_MESSAGE_OPTION, SCHEMA_DIRECTORY_OPTION,
+    SCHEMA_REGISTRY_URL_OPTION, VALUE_FORMAT_OPTION,
+};
+
+const MAGIC_BYTE: u8 = 0;
+const HEADER_LEN: usize = 5;
+// Name under which the Protobuf schemas are compiled
+const PROTOBUF_SCHEMA_FILE: &str = "schema.proto";
+
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+    #[error("the value is not framed with the schema registry wire format")]
+    MissingFraming,
+    #[error("cannot resolve the schema {0}: {1}")]
+    SchemaResolution(u32, String),
+    #[error("the schema {0} is not a valid {1} schema: {2}")]
+    InvalidSchema(u32, ValueFormat, String),
+    #[error("cannot decode the Avro value: {0}")]
+    Avro(#[from] apache_avro::Error),
+    #[error("cannot decode the Protobuf value: {0}")]
+    Protobuf(String),
+    #[error("the value does not match the JSON Schema at '{path}': {message}")]
+    JsonSchema { path: String, message: String },
+    #[error("the value is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl DecodeError {
+    /// Failing to resolve the schema, for example because the schema registry is unreachable,
+    /// says nothing about the value itself. Such records are retried regardless of the
+    /// [`DecodeErrorPolicy`], rather than being skipped.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, DecodeError::SchemaResolution(..))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueFormat {
+    Avro,
+    Protobuf,
+    JsonSchema,
+}
+
+impl ValueFormat {
+    fn parse(format: &str) -> Option<Self> {
+        match format {
+            "avro" => Some(ValueFormat::Avro),
+            "protobuf" => Some(ValueFormat::Protobuf),
+            "json-schema" => Some(ValueFormat::JsonSchema),
+            _ => None,
+        }
+    }
+
+    fn file_extension(self) -> &'static str {
+        match self {
+            ValueFormat::Avro => "avsc",
+            ValueFormat::Protobuf => "proto",
+            ValueFormat::JsonSchema => "json",
+        }
+    }
+}
+
+impl fmt::Display for ValueFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueFormat::Avro => f.write_str("Avro"),
+            ValueFormat::Protobuf => f.write_str("Protobuf"),
+            ValueFormat::JsonSchema => f.write_str("JSON Schema"),
+        }
+    }
+}
+
+/// What to do with the records whose value cannot be decoded. Retryable errors, see
+/// [`DecodeError::is_retryable`], always stop the consumer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeErrorPolicy {
+    /// Fail the consumer at the record, so that it is retried according to the retry policy of
+    /// the ingress.
+    #[default]
+    Stop,
+    /// Skip the record and commit its offset.
+    Skip,
+}
+
+impl DecodeErrorPolicy {
+    pub fn from_subscription(subscription: &Subscription) -> Self {
+        match subscription
+            .metadata()
+            .get(ON_DECODE_ERROR_OPTION)
+            .map(String::as_str)
+        {
+            Some("skip") => DecodeErrorPolicy::Skip,
+            _ => DecodeErrorPolicy::Stop,
+        }
+    }
+}
+
+enum SchemaSource {
+    Registry {
+        client: reqwest::Client,
+        url: String,
+    },
+    Directory(PathBuf),
+}
+
+#[derive(Deserialize)]
+struct RegistrySchema {
+    schema: String,
+}
+
+impl SchemaSource {
+    async fn fetch(&self, schema_id: u32, format: ValueFormat) -> Result<String, DecodeError> {
+        let resolution_error =
+            |e: &dyn fmt::Display| DecodeError::SchemaResolution(schema_id, e.to_string());
+        match self {
+            SchemaSource::Registry { client, url } => {
+                let response = client
+                    .get(format!(
+                        "{}/schemas/ids/{schema_id}",
+                        url.trim_end_matches('/')
+                    ))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| resolution_error(&e))?;
+                let registry_schema: RegistrySchema =
+                    response.json().await.map_err(|e| resolution_error(&e))?;
+                Ok(registry_schema.schema)
+            }
+            SchemaSource::Directory(directory) => {
+                let path = directory.join(format!("{schema_id}.{}", format.file_extension()));
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|e| resolution_error(&format!("{}: {e}", path.display())))
+            }
+        }
+    }
+}
+
+enum ParsedSchema {
+    Avro(apache_avro::Schema),
+    Protobuf(FileDescriptor),
+    JsonSchema(Validator),
+}
+
+/// Converts the record values to JSON, according to the format configured in the subscription
+/// options. Schemas are resolved on the first record referencing them, and cached afterwards.
+/// Requests to the schema registry are bounded by the timeouts of the [`IngressOptions`].
+#[derive(Clone)]
+pub struct ValueDecoder {
+    format: ValueFormat,
+    source: Arc<SchemaSource>,
+    protobuf_message: Option<String>,
+    schemas: Arc<Mutex<HashMap<u32, Arc<ParsedSchema>>>>,
+}
+
+impl ValueDecoder {
+    /// Returns `None` if the subscription doesn't decode the values. The options have been
+    /// validated when creating the subscription.
+    pub fn from_subscription(
+        subscription: &Subscription,
+        options: &IngressOptions,
+    ) -> Option<Self> {
+        let metadata = subscription.metadata();
+        let format = ValueFormat::parse(metadata.get(VALUE_FORMAT_OPTION)?)?;
+        let source = match (
+            metadata.get(SCHEMA_REGISTRY_URL_OPTION),
+            metadata.get(SCHEMA_DIRECTORY_OPTION),
+        ) {
+            (Some(url), _) => SchemaSource::Registry {
+                client: reqwest::Client::builder()
+                    .connect_timeout(*options.schema_registry_connect_timeout)
+                    .timeout(*options.schema_registry_request_timeout)
+                    .build()
+                    // Fails only if the TLS backend cannot be initialized, like Client::new()
+                    .expect("the schema registry client to be created"),
+                url: url.clone(),
+            },
+            (None, Some(directory)) => SchemaSource::Directory(PathBuf::from(directory)),
+            (None, None) => return None,
+        };
+
+        Some(Self {
+            format,
+            source: Arc::new(source),
+            protobuf_message: metadata.get(PROTOBUF_MESSAGE_OPTION).cloned(),
+            schemas: Default::default(),
+        })
+    }
+
+    pub async fn decode(&self, value: &[u8]) -> Result<Bytes, DecodeError> {
+        if value.len() < HEADER_LEN || value[0] != MAGIC_BYTE {
+            return Err(DecodeError::MissingFraming);
+        }
+        let mut buf = &value[1..];
+        let schema_id = buf.get_u32();
+        let schema = self.schema(schema_id).await?;
+
+        let json = match schema.as_ref() {
+            ParsedSchema::Avro(schema) => {
+                let value = apache_avro::from_avro_datum(schema, &mut buf, None)?;
+                serde_json::to_vec(&serde_json::Value::try_from(value)?)?
+            }
+            ParsedSchema::Protobuf(file) => {
+                let message_descriptor = self.protobuf_message_descriptor(file, &mut buf)?;
+                let message = DynamicMessage::decode(message_descriptor, buf)
+                    .map_err(|e| DecodeError::Protobuf(e.to_string()))?;
+                serde_json::to_vec(&message)?
+            }
+            ParsedSchema::JsonSchema(validator) => {
+                let value: serde_json::Value = serde_json::from_slice(buf)?;
+                validator
+                    .validate(&value)
+                    .map_err(|e| DecodeError::JsonSchema {
+                        path: e.instance_path.to_string(),
+                        message: e.to_string(),
+                    })?;
+                buf.to_vec()
+            }
+        };
+
+        Ok(Bytes::from(json))
+    }
+
+    async fn schema(&self, schema_id: u32) -> Result<Arc<ParsedSchema>, DecodeError> {
+        if let Some(schema) = self.schemas.lock().get(&schema_id) {
+            return Ok(Arc::clone(schema));
+        }
+
+        let source = self.source.fetch(schema_id, self.format).await?;
+        let invalid_schema = |e: &dyn fmt::Display| {
+            DecodeError::InvalidSchema(schema_id, self.format, e.to_string())
+        };
+        let schema = Arc::new(match self.format {
+            ValueFormat::Avro => ParsedSchema::Avro(
+                apache_avro::Schema::parse_str(&source).map_err(|e| invalid_schema(&e))?,
+            ),
+            ValueFormat::Protobuf => ParsedSchema::Protobuf(
+                compile_protobuf_schema(source).map_err(|e| invalid_schema(&e))?,
+            ),
+            ValueFormat::JsonSchema => {
+                let schema: serde_json::Value =
+                    serde_json::from_str(&source).map_err(|e| invalid_schema(&e))?;
+                ParsedSchema::JsonSchema(
+                    jsonschema::validator_for(&schema).map_err(|e| invalid_schema(&e))?,
+                )
+            }
+        });
+
+        self.schemas.lock().insert(schema_id, Arc::clone(&schema));
+        Ok(schema)
+    }
+
+    fn protobuf_message_descriptor(
+        &self,
+        file: &FileDescriptor,
+        buf: &mut &[u8],
+    ) -> Result<MessageDescriptor, DecodeError> {
+        let indexes = read_message_indexes(buf)?;
+
+        if let Some(message_name) = &self.protobuf_message {
+            return file
+                .parent_pool()
+                .get_message_by_name(message_name)
+                .ok_or_else(|| {
+                    DecodeError::Protobuf(format!("the schema has no message '{message_name}'"))
+                });
+        }
+
+        let unknown_message =
+            || DecodeError::Protobuf(format!("the schema has no message at indexes {indexes:?}"));
+        let (first, nested) = indexes.split_first().ok_or_else(unknown_message)?;
+        let mut descriptor = file.messages().nth(*first).ok_or_else(unknown_message)?;
+        for index in nested {
+            descriptor = descriptor
+                .child_messages()
+                .nth(*index)
+                .ok_or_else(unknown_message)?;
+        }
+        Ok(descriptor)
+    }
+}
+
+/// Reads the zig-zag encoded message indexes. The common case of the first message of the file
+/// is encoded as a single zero, rather than as an array containing a single zero.
+fn read_message_indexes(buf: &mut &[u8]) -> Result<Vec<usize>, DecodeError> {
+    let read_index = |buf: &mut &[u8]| {
+        prost::encoding::decode_varint(buf)
+            .map(|n| ((n >> 1) as i64) ^ -((n & 1) as i64))
+            .map_err(|e| DecodeError::Protobuf(format!("invalid message indexes: {e}")))
+    };
+
+    let count = read_index(buf)?;
+    if count == 0 {
+        return Ok(vec![0]);
+    }
+    (0..count)
+        .map(|_| {
+            read_index(buf).and_then(|index| {
+                usize::try_from(index)
+                    .map_err(|_| DecodeError::Protobuf(format!("invalid message index {index}")))
+            })
+        })
+        .collect()
+}
+
+/// Resolves the schema itself, and the well known types it imports.
+struct SchemaFileResolver(String);
+
+impl FileResolver for SchemaFileResolver {
+    fn open_file(&self, name: &str) -> Result<File, protox::Error> {
+        if name == PROTOBUF_SCHEMA_FILE {
+            File::from_source(name, &self.0)
+        } else {
+            Err(protox::Error::file_not_found(name))
+        }
+    }
+}
+
+fn compile_protobuf_schema(source: String) -> Result<FileDescriptor, protox::Error> {
+    let mut resolver = ChainFileResolver::new();
+    resolver.add(SchemaFileResolver(source));
+    resolver.add(GoogleFileResolver::new());
+
+    let mut compiler = protox::Compiler::with_file_resolver(resolver);
+    compiler.open_file(PROTOBUF_SCHEMA_FILE)?;
+    let pool: DescriptorPool = compiler.descriptor_pool();
+    Ok(pool
+        .get_file_by_name(PROTOBUF_SCHEMA_FILE)
+        .expect("schema file was just compiled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn message_indexes() {
+        assert_eq!(read_message_indexes(&mut &[0u8][..]).unwrap(), vec![0]);
+        // [1, 2], zig-zag encoded
+        assert_eq!(
+            read_message_indexes(&mut &[4u8, 2, 4][..]).unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_protobuf_value() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(
+            directory.path().join("7.proto"),
+            r#"
+            syntax = "proto3";
+            package orders;
+            message Order {
+              string id = 1;
+              int64 quantity = 2;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut subscription = Subscription::mock();
+        subscription.metadata_mut().extend([
+            (VALUE_FORMAT_OPTION.to_owned(), "protobuf".to_owned()),
+            (
+                SCHEMA_DIRECTORY_OPTION.to_owned(),
+                directory.path().display().to_string(),
+            ),
+        ]);
+        let decoder =
+            ValueDecoder::from_subscription(&subscription, &IngressOptions::default()).unwrap();
+
+        // id = "o-1", quantity = 3
+        let mut value = vec![MAGIC_BYTE, 0, 0, 0, 7, 0];
+        value.extend_from_slice(&[0x0a, 3, b'o', b'-', b'1', 0x10, 3]);
+        let json: serde_json::Value =
+            serde_json::from_slice(&decoder.decode(&value).await.unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!({"id": "o-1", "quantity": "3"}));
+
+        assert!(matches!(
+            decoder.decode(b"{\"id\": \"o-1\"}").await,
+            Err(DecodeError::MissingFraming)
+        ));
+    }
+
+    #[tokio::test]
+    async fn schema_registry_requests_time_out() {
+        // The registry accepts the connection, but never replies
+        let registry = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+        let mut subscription = Subscription::mock();
+        subscription.metadata_mut().extend([
+            (VALUE_FORMAT_OPTION.to_owned(), "avro".to_owned()),
+            (
+                SCHEMA_REGISTRY_URL_OPTION.to_owned(),
+                format!("http://{}", registry.local_addr().unwrap()),
+            ),
+        ]);
+        let mut options = IngressOptions::default();
+        options.schema_registry_request_timeout = Duration::from_millis(100).into();
+        let decoder = ValueDecoder::from_subscription(&subscription, &options).unwrap();
+
+        let err = decoder
+            .decode(&[MAGIC_BYTE, 0, 0, 0, 7, 0])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DecodeError::SchemaResolution(7, _)));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn only_schema_resolution_errors_are_retryable() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mut subscription = Subscription::mock();
+        subscription.metadata_mut().extend([
+            (VALUE_FORMAT_OPTION.to_owned(), "avro".to_owned()),
+            (
+                SCHEMA_DIRECTORY_OPTION.to_owned(),
+                directory.path().display().to_string(),
+            ),
+        ]);
+        let decoder =
+            ValueDecoder::from_subscription(&subscription, &IngressOptions::default()).unwrap();
+
+        // The schema 7 doesn't exist (yet)
+        let err = decoder
+            .decode(&[MAGIC_BYTE, 0, 0, 0, 7, 0])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DecodeError::SchemaResolution(7, _)));
+        assert!(err.is_retryable());
+
+        let err = decoder.decode(b"not framed").await.unwrap_err();
+        assert!(!err.is_retryable());
+    }
+}
@@ -9,11 +9,14 @@
 // by the Apache License, Version 2.0.
 
 mod consumer_task;
+mod decoder;
 mod metric_definitions;
+mod offsets;
 mod subscription_controller;
 
 use tokio::sync::mpsc;
 
+pub use offsets::{reset_offsets, OffsetsReset, ResetOffsetsError};
 pub use subscription_controller::{Command, Error, Service};
 
 pub type SubscriptionCommandSender = mpsc::Sender<Command>;
@@ -8,9 +8,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use metrics::{describe_counter, Unit};
+use metrics::{describe_counter, describe_gauge, Unit};
 
 pub const KAFKA_INGRESS_REQUESTS: &str = "restate.kafka_ingress.requests.total";
+pub const KAFKA_INGRESS_DECODE_FAILURES: &str = "restate.kafka_ingress.decode_failures.total";
+pub const KAFKA_INGRESS_FILTERED_RECORDS: &str = "restate.kafka_ingress.filtered_records.total";
+pub const KAFKA_INGRESS_CONSUMER_LAG: &str = "restate.kafka_ingress.consumer_lag";
 
 pub(crate) fn describe_metrics() {
     describe_counter!(
@@ -18,4 +21,22 @@ pub(crate) fn describe_metrics() {
         Unit::Count,
         "Number of Kafka ingress requests"
     );
+
+    describe_counter!(
+        KAFKA_INGRESS_DECODE_FAILURES,
+        Unit::Count,
+        "Number of Kafka records skipped because their value could not be decoded, or no key could be selected"
+    );
+
+    describe_counter!(
+        KAFKA_INGRESS_FILTERED_RECORDS,
+        Unit::Count,
+        "Number of Kafka records skipped because they don't match the subscription filter"
+    );
+
+    describe_gauge!(
+        KAFKA_INGRESS_CONSUMER_LAG,
+        Unit::Count,
+        "Number of records of the topic partition not yet consumed by the subscription, 0 if the partition is not consumed by this node"
+    );
 }
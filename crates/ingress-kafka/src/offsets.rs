@@ -0,0 +1,137 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer};
+use rdkafka::error::KafkaError;
+use rdkafka::{Offset, TopicPartitionList};
+
+use restate_types::config::IngressOptions;
+use restate_types::schema::subscriptions::{Source, Subscription};
+
+use crate::subscription_controller::create_client_config;
+
+const KAFKA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Position the consumer offsets of a subscription are reset to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OffsetsReset {
+    Earliest,
+    Latest,
+    /// First record with a timestamp greater than or equal to the given milliseconds since the
+    /// Unix epoch, or the end of the partition if there is none.
+    Timestamp(i64),
+    /// Offsets of the given partitions, the other partitions are left unchanged.
+    Offsets(HashMap<i32, i64>),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResetOffsetsError {
+    #[error("the topic '{0}' has no partition {1}")]
+    UnknownPartition(String, i32),
+    #[error(transparent)]
+    Kafka(#[from] KafkaError),
+}
+
+/// Commits the offsets of the consumer group of the subscription, returning the new offset of
+/// every reset partition.
+///
+/// Kafka rejects the commit while the consumer group has active members, hence the subscription
+/// must be paused on all the nodes beforehand.
+pub async fn reset_offsets(
+    options: &IngressOptions,
+    subscription: &Subscription,
+    reset: OffsetsReset,
+) -> Result<BTreeMap<i32, i64>, ResetOffsetsError> {
+    let mut client_config = create_client_config(options, subscription);
+    client_config.set("enable.auto.commit", "false");
+    let Source::Kafka { topic, .. } = subscription.source().clone();
+
+    // The rdkafka calls are blocking
+    tokio::task::spawn_blocking(move || {
+        let consumer: BaseConsumer = client_config.create()?;
+        let offsets = resolve_offsets(&consumer, &topic, reset)?;
+
+        let mut partitions = TopicPartitionList::new();
+        for (partition, offset) in &offsets {
+            partitions.add_partition_offset(&topic, *partition, Offset::Offset(*offset))?;
+        }
+        consumer.commit(&partitions, CommitMode::Sync)?;
+
+        Ok(offsets)
+    })
+    .await
+    .expect("resetting the offsets doesn't panic")
+}
+
+fn resolve_offsets(
+    consumer: &BaseConsumer,
+    topic: &str,
+    reset: OffsetsReset,
+) -> Result<BTreeMap<i32, i64>, ResetOffsetsError> {
+    let metadata = consumer.fetch_metadata(Some(topic), KAFKA_TIMEOUT)?;
+    let partitions: Vec<i32> = metadata
+        .topics()
+        .iter()
+        .flat_map(|topic| topic.partitions())
+        .map(|partition| partition.id())
+        .collect();
+
+    let mut offsets = BTreeMap::new();
+    match reset {
+        OffsetsReset::Earliest | OffsetsReset::Latest => {
+            for partition in partitions {
+                let (low, high) = consumer.fetch_watermarks(topic, partition, KAFKA_TIMEOUT)?;
+                let offset = if reset == OffsetsReset::Earliest {
+                    low
+                } else {
+                    high
+                };
+                offsets.insert(partition, offset);
+            }
+        }
+        OffsetsReset::Timestamp(timestamp) => {
+            let mut timestamps = TopicPartitionList::new();
+            for partition in &partitions {
+                timestamps.add_partition_offset(topic, *partition, Offset::Offset(timestamp))?;
+            }
+            for element in consumer
+                .offsets_for_times(timestamps, KAFKA_TIMEOUT)?
+                .elements()
+            {
+                let offset = match element.offset() {
+                    Offset::Offset(offset) => offset,
+                    // No record after the timestamp
+                    _ => {
+                        consumer
+                            .fetch_watermarks(topic, element.partition(), KAFKA_TIMEOUT)?
+                            .1
+                    }
+                };
+                offsets.insert(element.partition(), offset);
+            }
+        }
+        OffsetsReset::Offsets(requested_offsets) => {
+            for (partition, offset) in requested_offsets {
+                if !partitions.contains(&partition) {
+                    return Err(ResetOffsetsError::UnknownPartition(
+                        topic.to_owned(),
+                        partition,
+                    ));
+                }
+                offsets.insert(partition, offset);
+            }
+        }
+    }
+
+    Ok(offsets)
+}
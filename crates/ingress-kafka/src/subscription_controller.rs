@@ -20,9 +20,12 @@ use restate_types::config::IngressOptions;
 use restate_types::identifiers::SubscriptionId;
 use restate_types::live::LiveLoad;
 use restate_types::retries::RetryPolicy;
-use restate_types::schema::subscriptions::{Source, Subscription};
+use restate_types::schema::subscriptions::{Source, Subscription, RESTATE_OPTIONS_PREFIX};
 use std::time::Duration;
 use tokio::sync::mpsc;
+use tracing::debug;
+
+const DEFAULT_STATISTICS_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 pub enum Command {
@@ -107,36 +110,34 @@ impl Service {
         subscription: Subscription,
         task_orchestrator: &mut TaskOrchestrator,
     ) {
-        let mut client_config = rdkafka::ClientConfig::new();
-
-        let Source::Kafka { cluster, topic, .. } = subscription.source();
-
-        // Copy cluster options and subscription metadata into client_config
-        let cluster_options = options
-            .get_kafka_cluster(cluster)
-            .unwrap_or_else(|| panic!("KafkaOptions should contain the cluster '{}'", cluster));
-
-        client_config.set("metadata.broker.list", cluster_options.brokers.join(","));
-        for (k, v) in cluster_options.additional_options.clone() {
-            client_config.set(k, v);
-        }
-        for (k, v) in subscription.metadata() {
-            client_config.set(k, v);
+        let subscription_id = subscription.id();
+        if subscription.is_paused() {
+            debug!("Not starting the consumer of the paused subscription {subscription_id}");
+            task_orchestrator.stop(subscription_id);
+            return;
         }
 
+        let Source::Kafka { topic, .. } = subscription.source();
+        let mut client_config = create_client_config(options, &subscription);
+
         // Options required by the business logic of our consumer,
         // see ConsumerTask::run
         client_config.set("enable.auto.commit", "true");
         client_config.set("enable.auto.offset.store", "false");
-
-        let subscription_id = subscription.id();
+        // Statistics are used to report the consumer lag
+        if client_config.get("statistics.interval.ms").is_none() {
+            client_config.set(
+                "statistics.interval.ms",
+                DEFAULT_STATISTICS_INTERVAL.as_millis().to_string(),
+            );
+        }
 
         // Create the consumer task
         let consumer_task = consumer_task::ConsumerTask::new(
             task_center(),
             client_config,
             vec![topic.to_string()],
-            MessageSender::new(subscription, self.dispatcher.clone()),
+            MessageSender::new(subscription, self.dispatcher.clone(), options),
         );
 
         task_orchestrator.start(subscription_id, consumer_task);
@@ -160,10 +161,12 @@ impl Service {
             task_orchestrator.running_subscriptions().cloned().collect();
 
         for subscription in subscriptions {
-            if !running_subscriptions.contains(&subscription.id()) {
+            let is_running = running_subscriptions.remove(&subscription.id());
+            // Subscriptions are restarted when paused or resumed, and when their offsets are reset
+            if !is_running
+                || task_orchestrator.subscription(subscription.id()) != Some(&subscription)
+            {
                 self.handle_start_subscription(options, subscription, task_orchestrator);
-            } else {
-                running_subscriptions.remove(&subscription.id());
             }
         }
 
@@ -173,12 +176,41 @@ impl Service {
     }
 }
 
+/// Creates the Kafka client configuration of the subscription, merging the options of the cluster
+/// with the Kafka options of the subscription.
+pub(crate) fn create_client_config(
+    options: &IngressOptions,
+    subscription: &Subscription,
+) -> rdkafka::ClientConfig {
+    let mut client_config = rdkafka::ClientConfig::new();
+
+    let Source::Kafka { cluster, .. } = subscription.source();
+
+    // Copy cluster options and subscription metadata into client_config
+    let cluster_options = options
+        .get_kafka_cluster(cluster)
+        .unwrap_or_else(|| panic!("KafkaOptions should contain the cluster '{}'", cluster));
+
+    client_config.set("metadata.broker.list", cluster_options.brokers.join(","));
+    for (k, v) in cluster_options.additional_options.clone() {
+        client_config.set(k, v);
+    }
+    for (k, v) in subscription.metadata() {
+        if !k.starts_with(RESTATE_OPTIONS_PREFIX) {
+            client_config.set(k, v);
+        }
+    }
+
+    client_config
+}
+
 mod task_orchestrator {
     use crate::consumer_task;
     use restate_core::task_center;
     use restate_timer_queue::TimerQueue;
     use restate_types::identifiers::SubscriptionId;
     use restate_types::retries::{RetryIter, RetryPolicy};
+    use restate_types::schema::subscriptions::Subscription;
     use std::collections::HashMap;
     use std::time::SystemTime;
     use tokio::sync::oneshot;
@@ -372,5 +404,14 @@ mod task_orchestrator {
         pub(super) fn running_subscriptions(&self) -> impl Iterator<Item = &SubscriptionId> {
             self.subscription_id_to_task_state.keys()
         }
+
+        pub(super) fn subscription(
+            &self,
+            subscription_id: SubscriptionId,
+        ) -> Option<&Subscription> {
+            self.subscription_id_to_task_state
+                .get(&subscription_id)
+                .map(|task_state| task_state.consumer_task_clone.subscription())
+        }
     }
 }
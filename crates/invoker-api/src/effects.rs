@@ -12,13 +12,18 @@ use restate_types::deployment::PinnedDeployment;
 use restate_types::errors::InvocationError;
 use restate_types::identifiers::EntryIndex;
 use restate_types::identifiers::InvocationId;
+use restate_types::invocation::InvocationEpoch;
 use restate_types::journal::enriched::EnrichedRawEntry;
+use restate_types::time::MillisSinceEpoch;
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Effect {
     pub invocation_id: InvocationId,
+    /// Epoch of the invocation attempt which produced this effect.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub invocation_epoch: InvocationEpoch,
     pub kind: EffectKind,
 }
 
@@ -38,4 +43,15 @@ pub enum EffectKind {
     End,
     /// This is sent when the invoker exhausted all its attempts to make progress on the specific invocation.
     Failed(InvocationError),
+    /// This is sent when the invoker exhausted all its attempts, and the retry policy of the invocation asks to pause it
+    /// rather than failing it.
+    Paused,
+    /// This is sent when the invoker exhausted all its attempts, and the retry policy of the invocation asks to record it
+    /// in the dead letter table before failing it.
+    DeadLetter {
+        error: InvocationError,
+        /// Time at which the invoker gave up on the invocation. It is part of the effect, so
+        /// that every replica of the partition records the same time.
+        dead_lettered_at: MillisSinceEpoch,
+    },
 }
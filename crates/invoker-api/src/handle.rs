@@ -14,7 +14,7 @@ use super::JournalMetadata;
 use restate_errors::NotRunningError;
 use restate_types::identifiers::PartitionKey;
 use restate_types::identifiers::{EntryIndex, InvocationId, PartitionLeaderEpoch};
-use restate_types::invocation::InvocationTarget;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
 use restate_types::journal::raw::PlainRawEntry;
 use restate_types::journal::Completion;
 use std::future::Future;
@@ -33,6 +33,7 @@ pub trait InvokerHandle<SR> {
         &mut self,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         journal: InvokeInputJournal,
     ) -> impl Future<Output = Result<(), NotRunningError>> + Send;
@@ -62,6 +63,13 @@ pub trait InvokerHandle<SR> {
         invocation_id: InvocationId,
     ) -> impl Future<Output = Result<(), NotRunningError>> + Send;
 
+    /// Skips the remaining backoff of an invocation which is waiting for its next retry.
+    fn retry_invocation_now(
+        &mut self,
+        partition_leader_epoch: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+    ) -> impl Future<Output = Result<(), NotRunningError>> + Send;
+
     fn register_partition(
         &mut self,
         partition: PartitionLeaderEpoch,
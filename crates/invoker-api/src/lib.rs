@@ -30,7 +30,9 @@ pub mod test_util {
     use restate_types::identifiers::{
         EntryIndex, InvocationId, PartitionKey, PartitionLeaderEpoch, ServiceId,
     };
-    use restate_types::invocation::{InvocationTarget, ServiceInvocationSpanContext};
+    use restate_types::invocation::{
+        InvocationEpoch, InvocationTarget, ServiceInvocationSpanContext,
+    };
     use restate_types::journal::raw::PlainRawEntry;
     use restate_types::journal::Completion;
     use restate_types::time::MillisSinceEpoch;
@@ -93,6 +95,7 @@ pub mod test_util {
             &mut self,
             _partition: PartitionLeaderEpoch,
             _invocation_id: InvocationId,
+            _invocation_epoch: InvocationEpoch,
             _invocation_target: InvocationTarget,
             _journal: InvokeInputJournal,
         ) -> Result<(), NotRunningError> {
@@ -132,6 +135,14 @@ pub mod test_util {
             Ok(())
         }
 
+        async fn retry_invocation_now(
+            &mut self,
+            _partition_leader_epoch: PartitionLeaderEpoch,
+            _invocation_id: InvocationId,
+        ) -> Result<(), NotRunningError> {
+            Ok(())
+        }
+
         async fn register_partition(
             &mut self,
             _partition: PartitionLeaderEpoch,
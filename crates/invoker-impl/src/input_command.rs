@@ -11,7 +11,7 @@
 use restate_errors::NotRunningError;
 use restate_invoker_api::{Effect, InvocationStatusReport, InvokeInputJournal, StatusHandle};
 use restate_types::identifiers::{EntryIndex, InvocationId, PartitionKey, PartitionLeaderEpoch};
-use restate_types::invocation::InvocationTarget;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
 use restate_types::journal::Completion;
 use std::ops::RangeInclusive;
 use tokio::sync::mpsc;
@@ -22,6 +22,7 @@ use tokio::sync::mpsc;
 pub(crate) struct InvokeCommand {
     pub(super) partition: PartitionLeaderEpoch,
     pub(super) invocation_id: InvocationId,
+    pub(super) invocation_epoch: InvocationEpoch,
     pub(super) invocation_target: InvocationTarget,
     #[serde(skip)]
     pub(super) journal: InvokeInputJournal,
@@ -47,6 +48,12 @@ pub(crate) enum InputCommand<SR> {
         invocation_id: InvocationId,
     },
 
+    /// Skip the remaining retry backoff of a specific invocation id
+    RetryNow {
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+    },
+
     /// Command used to clean up internal state when a partition leader is going away
     AbortAllPartition {
         partition: PartitionLeaderEpoch,
@@ -73,6 +80,7 @@ impl<SR: Send> restate_invoker_api::InvokerHandle<SR> for InvokerHandle<SR> {
         &mut self,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         journal: InvokeInputJournal,
     ) -> Result<(), NotRunningError> {
@@ -80,6 +88,7 @@ impl<SR: Send> restate_invoker_api::InvokerHandle<SR> for InvokerHandle<SR> {
             .send(InputCommand::Invoke(InvokeCommand {
                 partition,
                 invocation_id,
+                invocation_epoch,
                 invocation_target,
                 journal,
             }))
@@ -138,6 +147,19 @@ impl<SR: Send> restate_invoker_api::InvokerHandle<SR> for InvokerHandle<SR> {
             .map_err(|_| NotRunningError)
     }
 
+    async fn retry_invocation_now(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+    ) -> Result<(), NotRunningError> {
+        self.input
+            .send(InputCommand::RetryNow {
+                partition,
+                invocation_id,
+            })
+            .map_err(|_| NotRunningError)
+    }
+
     async fn register_partition(
         &mut self,
         partition: PartitionLeaderEpoch,
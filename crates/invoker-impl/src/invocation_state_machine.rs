@@ -10,6 +10,7 @@
 
 use super::*;
 
+use restate_types::invocation::InvocationEpoch;
 use restate_types::journal::Completion;
 use restate_types::retries;
 use std::fmt;
@@ -21,9 +22,21 @@ use tokio::task::AbortHandle;
 #[derive(Debug)]
 pub(super) struct InvocationStateMachine {
     pub(super) invocation_target: InvocationTarget,
+    pub(super) invocation_epoch: InvocationEpoch,
     invocation_state: InvocationState,
     retry_iter: retries::RetryIter<'static>,
+    retry_timer_count: u32,
     pub(super) retry_count_since_last_stored_entry: u32,
+    pub(super) on_max_attempts: OnMaxAttempts,
+}
+
+/// Identifies a retry timer of an invocation. Timers of backoffs which have been skipped by
+/// [`InvocationStateMachine::notify_retry_now`], or which belong to an earlier epoch of the
+/// invocation, don't match the current one when they fire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RetryTimerId {
+    invocation_epoch: InvocationEpoch,
+    retry_timer_count: u32,
 }
 
 /// This struct tracks which entries the invocation task generates,
@@ -130,13 +143,18 @@ impl fmt::Debug for InvocationState {
 impl InvocationStateMachine {
     pub(super) fn create(
         invocation_target: InvocationTarget,
+        invocation_epoch: InvocationEpoch,
         retry_policy: RetryPolicy,
+        on_max_attempts: OnMaxAttempts,
     ) -> InvocationStateMachine {
         Self {
             invocation_target,
+            invocation_epoch,
             invocation_state: InvocationState::New,
             retry_iter: retry_policy.into_iter(),
+            retry_timer_count: 0,
             retry_count_since_last_stored_entry: 0,
+            on_max_attempts,
         }
     }
 
@@ -274,6 +292,24 @@ impl InvocationStateMachine {
         }
     }
 
+    /// Skips the remaining backoff if the invocation is waiting for a retry.
+    pub(super) fn notify_retry_now(&mut self) {
+        if let InvocationState::WaitingRetry { timer_fired, .. } = &mut self.invocation_state {
+            *timer_fired = true;
+        }
+    }
+
+    /// Applies the retry policy currently configured for the invocation target. The attempts
+    /// made so far count against the max attempts of the new policy.
+    pub(super) fn update_retry_policy(
+        &mut self,
+        retry_policy: RetryPolicy,
+        on_max_attempts: OnMaxAttempts,
+    ) {
+        self.retry_iter.set_policy(retry_policy);
+        self.on_max_attempts = on_max_attempts;
+    }
+
     /// Returns Some() with the timer for the next retry, otherwise None if retry limit exhausted
     pub(super) fn handle_task_error(
         &mut self,
@@ -300,6 +336,7 @@ impl InvocationStateMachine {
         let next_timer = next_retry_interval_override.or_else(|| self.retry_iter.next());
         if next_timer.is_some() {
             self.retry_count_since_last_stored_entry += 1;
+            self.retry_timer_count += 1;
             self.invocation_state = InvocationState::WaitingRetry {
                 timer_fired: false,
                 journal_tracker,
@@ -310,6 +347,25 @@ impl InvocationStateMachine {
         }
     }
 
+    /// Id of the retry timer registered for the last task error.
+    pub(super) fn retry_timer_id(&self) -> RetryTimerId {
+        RetryTimerId {
+            invocation_epoch: self.invocation_epoch,
+            retry_timer_count: self.retry_timer_count,
+        }
+    }
+
+    /// Returns true if the invocation is still waiting for the retry timer with the given id.
+    pub(super) fn is_waiting_for_retry_timer(&self, retry_timer_id: RetryTimerId) -> bool {
+        matches!(
+            self.invocation_state,
+            InvocationState::WaitingRetry {
+                timer_fired: false,
+                ..
+            }
+        ) && self.retry_timer_id() == retry_timer_id
+    }
+
     pub(super) fn is_ready_to_retry(&self) -> bool {
         match self.invocation_state {
             InvocationState::WaitingRetry {
@@ -344,7 +400,9 @@ mod tests {
     fn handle_error_when_waiting_for_retry() {
         let mut invocation_state_machine = InvocationStateMachine::create(
             InvocationTarget::mock_virtual_object(),
+            0,
             RetryPolicy::fixed_delay(Duration::from_secs(1), Some(10)),
+            OnMaxAttempts::Kill,
         );
 
         assert!(invocation_state_machine.handle_task_error(None).is_some());
@@ -357,11 +415,70 @@ mod tests {
         check!(let InvocationState::WaitingRetry { .. } = invocation_state_machine.invocation_state);
     }
 
+    #[test]
+    fn updated_retry_policy_counts_previous_attempts() {
+        let mut invocation_state_machine = InvocationStateMachine::create(
+            InvocationTarget::mock_virtual_object(),
+            0,
+            RetryPolicy::fixed_delay(Duration::from_secs(1), None),
+            OnMaxAttempts::Kill,
+        );
+
+        assert!(invocation_state_machine.handle_task_error(None).is_some());
+        invocation_state_machine.notify_retry_timer_fired();
+        assert!(invocation_state_machine.handle_task_error(None).is_some());
+        invocation_state_machine.notify_retry_timer_fired();
+
+        // Two attempts have been made already, the new policy allows only one more
+        invocation_state_machine.update_retry_policy(
+            RetryPolicy::fixed_delay(Duration::from_secs(1), Some(3)),
+            OnMaxAttempts::Pause,
+        );
+        assert!(invocation_state_machine.handle_task_error(None).is_some());
+        invocation_state_machine.notify_retry_timer_fired();
+        assert!(invocation_state_machine.handle_task_error(None).is_none());
+        assert_eq!(
+            invocation_state_machine.on_max_attempts,
+            OnMaxAttempts::Pause
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn retry_now_skips_the_retry_timer() {
+        let mut invocation_state_machine = InvocationStateMachine::create(
+            InvocationTarget::mock_virtual_object(),
+            0,
+            RetryPolicy::fixed_delay(Duration::from_secs(60), Some(10)),
+            OnMaxAttempts::Kill,
+        );
+
+        assert!(invocation_state_machine.handle_task_error(None).is_some());
+        assert!(!invocation_state_machine.is_ready_to_retry());
+        let skipped_retry_timer = invocation_state_machine.retry_timer_id();
+        assert!(invocation_state_machine.is_waiting_for_retry_timer(skipped_retry_timer));
+
+        invocation_state_machine.notify_retry_now();
+        assert!(invocation_state_machine.is_ready_to_retry());
+        assert!(!invocation_state_machine.is_waiting_for_retry_timer(skipped_retry_timer));
+
+        // the skipped timer must not cut short the backoff of the next failure
+        invocation_state_machine.start(
+            tokio::spawn(async {}).abort_handle(),
+            mpsc::unbounded_channel().0,
+        );
+        assert!(invocation_state_machine.handle_task_error(None).is_some());
+        assert!(!invocation_state_machine.is_waiting_for_retry_timer(skipped_retry_timer));
+        assert!(invocation_state_machine
+            .is_waiting_for_retry_timer(invocation_state_machine.retry_timer_id()));
+    }
+
     #[test(tokio::test)]
     async fn handle_error_counts_attempts_on_same_entry() {
         let mut invocation_state_machine = InvocationStateMachine::create(
             InvocationTarget::mock_virtual_object(),
+            0,
             RetryPolicy::fixed_delay(Duration::from_secs(1), Some(10)),
+            OnMaxAttempts::Kill,
         );
 
         // Start invocation
@@ -412,7 +529,9 @@ mod tests {
     async fn handle_requires_ack() {
         let mut invocation_state_machine = InvocationStateMachine::create(
             InvocationTarget::mock_virtual_object(),
+            0,
             RetryPolicy::fixed_delay(Duration::from_secs(1), Some(10)),
+            OnMaxAttempts::Kill,
         );
 
         let abort_handle = tokio::spawn(async {}).abort_handle();
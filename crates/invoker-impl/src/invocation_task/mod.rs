@@ -29,7 +29,7 @@ use restate_service_protocol::message::{EncodingError, MessageType};
 use restate_types::deployment::PinnedDeployment;
 use restate_types::errors::InvocationError;
 use restate_types::identifiers::{DeploymentId, EntryIndex, InvocationId, PartitionLeaderEpoch};
-use restate_types::invocation::InvocationTarget;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
 use restate_types::journal::enriched::EnrichedRawEntry;
 use restate_types::journal::EntryType;
 use restate_types::live::Live;
@@ -226,6 +226,8 @@ impl InvocationTaskError {
 pub(super) struct InvocationTaskOutput {
     pub(super) partition: PartitionLeaderEpoch,
     pub(super) invocation_id: InvocationId,
+    /// Epoch of the attempt which produced this output, see [`InvocationEpoch`].
+    pub(super) invocation_epoch: InvocationEpoch,
     pub(super) inner: InvocationTaskOutputInner,
 }
 
@@ -267,6 +269,7 @@ pub(super) struct InvocationTask<SR, JR, EE, DMR> {
     // Connection params
     partition: PartitionLeaderEpoch,
     invocation_id: InvocationId,
+    invocation_epoch: InvocationEpoch,
     invocation_target: InvocationTarget,
     inactivity_timeout: Duration,
     abort_timeout: Duration,
@@ -328,6 +331,7 @@ where
         client: ServiceClient,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         default_inactivity_timeout: Duration,
         default_abort_timeout: Duration,
@@ -346,6 +350,7 @@ where
             client,
             partition,
             invocation_id,
+            invocation_epoch,
             invocation_target,
             inactivity_timeout: default_inactivity_timeout,
             abort_timeout: default_abort_timeout,
@@ -518,6 +523,7 @@ impl<SR, JR, EE, DMR> InvocationTask<SR, JR, EE, DMR> {
         let _ = self.invoker_tx.send(InvocationTaskOutput {
             partition: self.partition,
             invocation_id: self.invocation_id,
+            invocation_epoch: self.invocation_epoch,
             inner: invocation_task_output_inner,
         });
     }
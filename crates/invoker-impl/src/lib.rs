@@ -16,9 +16,10 @@ mod quota;
 mod state_machine_manager;
 mod status_store;
 
+use futures::future::OptionFuture;
 use futures::Stream;
 use input_command::{InputCommand, InvokeCommand};
-use invocation_state_machine::InvocationStateMachine;
+use invocation_state_machine::{InvocationStateMachine, RetryTimerId};
 use invocation_task::InvocationTask;
 use invocation_task::{InvocationTaskOutput, InvocationTaskOutputInner};
 use metrics::counter;
@@ -39,30 +40,34 @@ use restate_types::journal::Completion;
 use restate_types::live::{Live, LiveLoad};
 use restate_types::retries::RetryPolicy;
 use restate_types::schema::deployment::DeploymentResolver;
+use restate_types::time::MillisSinceEpoch;
 use status_store::InvocationStatusStore;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
 use std::pin::Pin;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use std::{cmp, panic};
 use tokio::sync::mpsc;
 use tokio::task::{AbortHandle, JoinSet};
+use tokio::time::Sleep;
 use tracing::instrument;
 use tracing::{debug, trace};
 
 use crate::invocation_task::InvocationTaskError;
 use crate::metric_definitions::{
-    INVOKER_ENQUEUE, INVOKER_INVOCATION_TASK, TASK_OP_COMPLETED, TASK_OP_FAILED, TASK_OP_STARTED,
-    TASK_OP_SUSPENDED,
+    INVOKER_CONCURRENCY_LIMITED, INVOKER_ENQUEUE, INVOKER_INVOCATION_TASK, TASK_OP_COMPLETED,
+    TASK_OP_FAILED, TASK_OP_STARTED, TASK_OP_SUSPENDED,
 };
+use crate::quota::ConcurrencyGroup;
 pub use input_command::ChannelStatusReader;
 pub use input_command::InvokerHandle;
+pub use quota::ConcurrencyLimiter;
 use restate_service_client::{AssumeRoleCacheMode, ServiceClient};
 use restate_types::deployment::PinnedDeployment;
-use restate_types::invocation::InvocationTarget;
-use restate_types::schema::service::ServiceMetadataResolver;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
+use restate_types::schema::service::{OnMaxAttempts, ServiceMetadataResolver};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Notification {
@@ -79,6 +84,7 @@ trait InvocationTaskRunner<SR> {
         options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         retry_count_since_last_stored_entry: u32,
         storage_reader: SR,
@@ -108,6 +114,7 @@ where
         opts: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         retry_count_since_last_stored_entry: u32,
         storage_reader: SR,
@@ -121,6 +128,7 @@ where
                 self.client.clone(),
                 partition,
                 invocation_id,
+                invocation_epoch,
                 invocation_target,
                 opts.inactivity_timeout.into(),
                 opts.abort_timeout.into(),
@@ -154,7 +162,11 @@ pub struct Service<SR, EntryEnricher, DeploymentRegistry> {
     tmp_dir: PathBuf,
     // We have this level of indirection to hide the InvocationTaskRunner,
     // which is a rather internal thing we have only for mocking.
-    inner: ServiceInner<DefaultInvocationTaskRunner<EntryEnricher, DeploymentRegistry>, SR>,
+    inner: ServiceInner<
+        DefaultInvocationTaskRunner<EntryEnricher, DeploymentRegistry>,
+        DeploymentRegistry,
+        SR,
+    >,
 }
 
 impl<SR, EE, Schemas> Service<SR, EE, Schemas> {
@@ -164,6 +176,7 @@ impl<SR, EE, Schemas> Service<SR, EE, Schemas> {
         deployment_metadata_resolver: Live<Schemas>,
         client: ServiceClient,
         entry_enricher: EE,
+        concurrency_limiter: ConcurrencyLimiter,
     ) -> Service<SR, EE, Schemas>
     where
         SR: JournalReader<JournalStream = JS> + StateReader + Clone + Send + Sync + 'static,
@@ -187,11 +200,16 @@ impl<SR, EE, Schemas> Service<SR, EE, Schemas> {
                 invocation_task_runner: DefaultInvocationTaskRunner {
                     client,
                     entry_enricher,
-                    schemas: deployment_metadata_resolver,
+                    schemas: deployment_metadata_resolver.clone(),
                 },
+                schemas: deployment_metadata_resolver,
                 invocation_tasks: Default::default(),
                 retry_timers: Default::default(),
                 quota: quota::InvokerConcurrencyQuota::new(options.concurrent_invocations_limit()),
+                group_quota: quota::GroupConcurrencyQuota::new(concurrency_limiter),
+                throttled_invocations: Default::default(),
+                slot_released: false,
+                throttled_invocations_recheck: None,
                 status_store: Default::default(),
                 invocation_state_machine_manager: Default::default(),
             },
@@ -203,6 +221,7 @@ impl<SR, EE, Schemas> Service<SR, EE, Schemas> {
         invoker_options: &InvokerOptions,
         entry_enricher: EE,
         schemas: Live<Schemas>,
+        concurrency_limiter: ConcurrencyLimiter,
     ) -> Result<Service<SR, EE, Schemas>, BuildError>
     where
         SR: JournalReader<JournalStream = JS> + StateReader + Clone + Send + Sync + 'static,
@@ -219,6 +238,7 @@ impl<SR, EE, Schemas> Service<SR, EE, Schemas> {
             schemas,
             client,
             entry_enricher,
+            concurrency_limiter,
         ))
     }
 }
@@ -284,8 +304,20 @@ where
     }
 }
 
+/// Interval in which the throttled invocations are checked against the current concurrency
+/// limits, which can be changed while the invocations are held back.
+const THROTTLED_INVOCATIONS_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Invocation held back because its service or deployment reached its concurrency limit.
 #[derive(Debug)]
-struct ServiceInner<InvocationTaskRunner, SR> {
+struct ThrottledInvocation {
+    invoke_command: InvokeCommand,
+    // None if the invocation is not pinned to a deployment yet
+    pinned_deployment: Option<DeploymentId>,
+}
+
+#[derive(Debug)]
+struct ServiceInner<InvocationTaskRunner, Schemas, SR> {
     input_rx: mpsc::UnboundedReceiver<InputCommand<SR>>,
     status_rx: mpsc::UnboundedReceiver<
         restate_futures_util::command::Command<
@@ -301,17 +333,30 @@ struct ServiceInner<InvocationTaskRunner, SR> {
     // Invocation task factory
     invocation_task_runner: InvocationTaskRunner,
 
+    // Used to resolve the retry policy overrides and the concurrency limits
+    schemas: Live<Schemas>,
+
     // Invoker state machine
     invocation_tasks: JoinSet<()>,
-    retry_timers: TimerQueue<(PartitionLeaderEpoch, InvocationId)>,
+    retry_timers: TimerQueue<(PartitionLeaderEpoch, InvocationId, RetryTimerId)>,
     quota: quota::InvokerConcurrencyQuota,
+    group_quota: quota::GroupConcurrencyQuota,
+    // Invocations held back because their service or deployment reached its concurrency limit,
+    // in the order they were dequeued
+    throttled_invocations: VecDeque<ThrottledInvocation>,
+    // Set when a slot is released, to start the throttled invocations at the end of the step
+    slot_released: bool,
+    // Fires while invocations are throttled, to start them if the concurrency limits have been
+    // raised in the meantime
+    throttled_invocations_recheck: Option<Pin<Box<Sleep>>>,
     status_store: InvocationStatusStore,
     invocation_state_machine_manager: state_machine_manager::InvocationStateMachineManager<SR>,
 }
 
-impl<ITR, SR> ServiceInner<ITR, SR>
+impl<ITR, Schemas, SR> ServiceInner<ITR, Schemas, SR>
 where
     ITR: InvocationTaskRunner<SR>,
+    Schemas: DeploymentResolver + ServiceMetadataResolver,
     SR: JournalReader + StateReader + Clone + Send + Sync + 'static,
     <SR as JournalReader>::JournalStream: Unpin + Send + 'static,
     <SR as StateReader>::StateIter: Send,
@@ -353,6 +398,9 @@ where
                     InputCommand::Abort { partition, invocation_id } => {
                         self.handle_abort_invocation(partition, invocation_id);
                     }
+                    InputCommand::RetryNow { partition, invocation_id } => {
+                        self.handle_retry_now(options, partition, invocation_id);
+                    }
                     InputCommand::AbortAllPartition { partition } => {
                         self.handle_abort_partition(partition);
                     }
@@ -366,54 +414,25 @@ where
             },
 
             Some(invoke_input_command) = segmented_input_queue.dequeue(), if !segmented_input_queue.is_empty() && self.quota.is_slot_available() => {
-                self.handle_invoke(options, invoke_input_command.partition, invoke_input_command.invocation_id, invoke_input_command.invocation_target, invoke_input_command.journal);
+                self.handle_invoke(options, invoke_input_command.partition, invoke_input_command.invocation_id, invoke_input_command.invocation_epoch, invoke_input_command.invocation_target, invoke_input_command.journal).await;
+            },
+
+            Some(()) = OptionFuture::from(self.throttled_invocations_recheck.as_mut()) => {
+                self.throttled_invocations_recheck = None;
+                self.slot_released = true;
+            },
+
+            // Slots are shared with the invokers of the other partitions on this node
+            _ = self.group_quota.slot_released(), if !self.throttled_invocations.is_empty() => {
+                self.slot_released = true;
             },
 
             Some(invocation_task_msg) = self.invocation_tasks_rx.recv() => {
-                let InvocationTaskOutput {
-                    invocation_id,
-                    partition,
-                    inner
-                } = invocation_task_msg;
-                match inner {
-                    InvocationTaskOutputInner::PinnedDeployment(deployment_metadata, has_changed) => {
-                        self.handle_pinned_deployment(
-                            partition,
-                            invocation_id,
-                            deployment_metadata,
-                            has_changed,
-                        )
-                    }
-                    InvocationTaskOutputInner::ServerHeaderReceived(x_restate_server_header) => {
-                        self.handle_server_header_received(
-                            partition,
-                            invocation_id,
-                            x_restate_server_header
-                        )
-                    }
-                    InvocationTaskOutputInner::NewEntry {entry_index, entry, requires_ack} => {
-                        self.handle_new_entry(
-                            partition,
-                            invocation_id,
-                            entry_index,
-                            entry,
-                            requires_ack
-                        ).await
-                    },
-                    InvocationTaskOutputInner::Closed => {
-                        self.handle_invocation_task_closed(partition, invocation_id).await
-                    },
-                    InvocationTaskOutputInner::Failed(e) => {
-                        self.handle_invocation_task_failed(partition, invocation_id, e).await
-                    },
-                    InvocationTaskOutputInner::Suspended(indexes) => {
-                        self.handle_invocation_task_suspended(partition, invocation_id, indexes).await
-                    }
-                };
+                self.handle_invocation_task_output(options, invocation_task_msg).await;
             },
             timer = self.retry_timers.await_timer() => {
-                let (partition, fid) = timer.into_inner();
-                self.handle_retry_timer_fired(options, partition, fid);
+                let (partition, fid, retry_timer_id) = timer.into_inner();
+                self.handle_retry_timer_fired(options, partition, fid, retry_timer_id);
             },
             Some(invocation_task_result) = self.invocation_tasks.join_next() => {
                 if let Err(err) = invocation_task_result {
@@ -431,6 +450,16 @@ where
                 return false;
             }
         }
+        if std::mem::take(&mut self.slot_released) {
+            self.handle_throttled_invocations(options);
+        }
+        if self.throttled_invocations.is_empty() {
+            self.throttled_invocations_recheck = None;
+        } else if self.throttled_invocations_recheck.is_none() {
+            self.throttled_invocations_recheck = Some(Box::pin(tokio::time::sleep(
+                THROTTLED_INVOCATIONS_RECHECK_INTERVAL,
+            )));
+        }
         // Execute next loop
         true
     }
@@ -470,11 +499,12 @@ where
             restate.invoker.partition_leader_epoch = ?partition,
         )
     )]
-    fn handle_invoke(
+    async fn handle_invoke(
         &mut self,
         options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         journal: InvokeInputJournal,
     ) {
@@ -486,10 +516,149 @@ where
             .resolve_invocation(partition, &invocation_id)
             .is_none());
 
+        let pinned_deployment = self
+            .read_pinned_deployment(partition, &invocation_id, &journal)
+            .await;
+        let concurrency_group =
+            self.resolve_concurrency_group(&invocation_target, pinned_deployment);
+        if !self
+            .group_quota
+            .try_reserve_slot(partition, invocation_id, &concurrency_group)
+        {
+            trace!("Concurrency limit reached, holding back the invocation");
+            counter!(INVOKER_CONCURRENCY_LIMITED).increment(1);
+            self.throttled_invocations.push_back(ThrottledInvocation {
+                invoke_command: InvokeCommand {
+                    partition,
+                    invocation_id,
+                    invocation_epoch,
+                    invocation_target,
+                    journal,
+                },
+                pinned_deployment,
+            });
+            return;
+        }
+
+        self.start_invocation(
+            options,
+            partition,
+            invocation_id,
+            invocation_epoch,
+            invocation_target,
+            journal,
+        );
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    fn handle_throttled_invocations(&mut self, options: &InvokerOptions) {
+        for throttled_invocation in std::mem::take(&mut self.throttled_invocations) {
+            let invoke_command = &throttled_invocation.invoke_command;
+            // The limits are resolved again since they might have been changed
+            let concurrency_group = self.resolve_concurrency_group(
+                &invoke_command.invocation_target,
+                throttled_invocation.pinned_deployment,
+            );
+            if self.quota.is_slot_available()
+                && self.group_quota.try_reserve_slot(
+                    invoke_command.partition,
+                    invoke_command.invocation_id,
+                    &concurrency_group,
+                )
+            {
+                let invoke_command = throttled_invocation.invoke_command;
+                trace!(
+                    restate.invocation.id = %invoke_command.invocation_id,
+                    restate.invocation.target = %invoke_command.invocation_target,
+                    "Starting throttled invocation"
+                );
+                self.start_invocation(
+                    options,
+                    invoke_command.partition,
+                    invoke_command.invocation_id,
+                    invoke_command.invocation_epoch,
+                    invoke_command.invocation_target,
+                    invoke_command.journal,
+                );
+            } else {
+                self.throttled_invocations.push_back(throttled_invocation);
+            }
+        }
+    }
+
+    /// Reads the deployment the invocation is pinned to, if any.
+    async fn read_pinned_deployment(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: &InvocationId,
+        journal: &InvokeInputJournal,
+    ) -> Option<DeploymentId> {
+        match journal {
+            InvokeInputJournal::CachedJournal(journal_metadata, _) => journal_metadata
+                .pinned_deployment
+                .as_ref()
+                .map(|pinned_deployment| pinned_deployment.deployment_id),
+            InvokeInputJournal::NoCachedJournal => {
+                let mut storage_reader = self
+                    .invocation_state_machine_manager
+                    .partition_storage_reader(partition)?
+                    .clone();
+                match storage_reader.read_journal(invocation_id).await {
+                    Ok((journal_metadata, _)) => journal_metadata
+                        .pinned_deployment
+                        .map(|pinned_deployment| pinned_deployment.deployment_id),
+                    Err(err) => {
+                        // The invocation task will fail reading the journal as well
+                        trace!("Cannot read the pinned deployment: {err}");
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves the concurrency limits of the invocation. Invocations which are not pinned to a
+    /// deployment yet are counted against the latest deployment of their service, which they
+    /// will be pinned to once started.
+    fn resolve_concurrency_group(
+        &self,
+        invocation_target: &InvocationTarget,
+        pinned_deployment: Option<DeploymentId>,
+    ) -> ConcurrencyGroup {
+        let schemas = self.schemas.pinned();
+        let service_name = invocation_target.service_name();
+        let deployment = match pinned_deployment {
+            Some(deployment_id) => schemas.get_deployment(&deployment_id),
+            None => schemas.resolve_latest_deployment_for_service(service_name),
+        };
+        ConcurrencyGroup {
+            service_name: service_name.to_string(),
+            service_limit: schemas.resolve_concurrency_limit(service_name),
+            deployment: deployment.map(|deployment| {
+                (
+                    deployment.id,
+                    deployment.metadata.delivery_options.concurrency_limit,
+                )
+            }),
+        }
+    }
+
+    fn start_invocation(
+        &mut self,
+        options: &InvokerOptions,
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
+        invocation_target: InvocationTarget,
+        journal: InvokeInputJournal,
+    ) {
         let storage_reader = self
             .invocation_state_machine_manager
             .partition_storage_reader(partition)
             .expect("partition is registered");
+        let (retry_policy, on_max_attempts) =
+            self.resolve_retry_policy(options, &invocation_target);
+
         self.quota.reserve_slot();
         self.start_invocation_task(
             options,
@@ -497,7 +666,12 @@ where
             storage_reader.clone(),
             invocation_id,
             journal,
-            InvocationStateMachine::create(invocation_target, options.retry_policy.clone()),
+            InvocationStateMachine::create(
+                invocation_target,
+                invocation_epoch,
+                retry_policy,
+                on_max_attempts,
+            ),
         )
     }
 
@@ -514,13 +688,44 @@ where
         options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
+        retry_timer_id: RetryTimerId,
     ) {
+        if !self
+            .invocation_state_machine_manager
+            .resolve_invocation(partition, &invocation_id)
+            .is_some_and(|(_, ism)| ism.is_waiting_for_retry_timer(retry_timer_id))
+        {
+            // The backoff has been skipped or the invocation has been restarted in the meantime.
+            trace!("Ignoring stale retry timer");
+            return;
+        }
+
         trace!("Retry timeout fired");
         self.handle_retry_event(options, partition, invocation_id, |sm| {
             sm.notify_retry_timer_fired()
         });
     }
 
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            restate.invocation.id = %invocation_id,
+            restate.invoker.partition_leader_epoch = ?partition,
+        )
+    )]
+    fn handle_retry_now(
+        &mut self,
+        options: &InvokerOptions,
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+    ) {
+        trace!("Received retry now command");
+        self.handle_retry_event(options, partition, invocation_id, |sm| {
+            sm.notify_retry_now()
+        });
+    }
+
     #[instrument(
         level = "trace",
         skip_all,
@@ -543,6 +748,68 @@ where
         });
     }
 
+    async fn handle_invocation_task_output(
+        &mut self,
+        options: &InvokerOptions,
+        invocation_task_output: InvocationTaskOutput,
+    ) {
+        let InvocationTaskOutput {
+            partition,
+            invocation_id,
+            invocation_epoch,
+            inner,
+        } = invocation_task_output;
+
+        // An aborted attempt might have produced outputs before it was stopped. If the invocation
+        // has been invoked again in the meantime, e.g. because it has been paused and resumed,
+        // those outputs must not be attributed to the new attempt, otherwise the failure of the
+        // aborted attempt would be counted as a failure of the new one.
+        if self
+            .invocation_state_machine_manager
+            .resolve_invocation(partition, &invocation_id)
+            .is_some_and(|(_, ism)| ism.invocation_epoch != invocation_epoch)
+        {
+            trace!(
+                restate.invocation.id = %invocation_id,
+                restate.invocation.epoch = invocation_epoch,
+                "Ignoring output of an aborted invocation attempt"
+            );
+            return;
+        }
+
+        match inner {
+            InvocationTaskOutputInner::PinnedDeployment(deployment_metadata, has_changed) => self
+                .handle_pinned_deployment(
+                    partition,
+                    invocation_id,
+                    deployment_metadata,
+                    has_changed,
+                ),
+            InvocationTaskOutputInner::ServerHeaderReceived(x_restate_server_header) => self
+                .handle_server_header_received(partition, invocation_id, x_restate_server_header),
+            InvocationTaskOutputInner::NewEntry {
+                entry_index,
+                entry,
+                requires_ack,
+            } => {
+                self.handle_new_entry(partition, invocation_id, entry_index, entry, requires_ack)
+                    .await
+            }
+            InvocationTaskOutputInner::Closed => {
+                self.handle_invocation_task_closed(partition, invocation_id)
+                    .await
+            }
+            InvocationTaskOutputInner::Failed(e) => {
+                self.handle_invocation_task_failed(options, partition, invocation_id, e)
+                    .await
+            }
+            InvocationTaskOutputInner::Suspended(indexes) => {
+                self.handle_invocation_task_suspended(partition, invocation_id, indexes)
+                    .await
+            }
+        };
+    }
+
     #[instrument(
         level = "trace",
         skip_all,
@@ -578,6 +845,19 @@ where
             // If we think this selected deployment has been freshly picked, otherwise
             // we assume that we have stored it previously.
             if has_changed {
+                // The invocation has been counted against the latest deployment of its service
+                // when it was started, which might have changed since then.
+                let concurrency_limit = self
+                    .schemas
+                    .pinned()
+                    .get_deployment(&pinned_deployment.deployment_id)
+                    .and_then(|deployment| deployment.metadata.delivery_options.concurrency_limit);
+                self.group_quota.update_deployment(
+                    partition,
+                    &invocation_id,
+                    pinned_deployment.deployment_id,
+                    concurrency_limit,
+                );
                 ism.notify_pinned_deployment(pinned_deployment);
             }
         } else {
@@ -645,6 +925,7 @@ where
             .resolve_invocation(partition, &invocation_id)
         {
             ism.notify_new_entry(entry_index, requires_ack);
+            let invocation_epoch = ism.invocation_epoch;
             trace!(
                 restate.invocation.target = %ism.invocation_target,
                 "Received a new entry. Invocation state: {:?}",
@@ -654,6 +935,7 @@ where
                 let _ = output_tx
                     .send(Effect {
                         invocation_id,
+                        invocation_epoch,
                         kind: EffectKind::PinnedDeployment(pinned_deployment),
                     })
                     .await;
@@ -661,6 +943,7 @@ where
             let _ = output_tx
                 .send(Effect {
                     invocation_id,
+                    invocation_epoch,
                     kind: EffectKind::JournalEntry { entry_index, entry },
                 })
                 .await;
@@ -721,11 +1004,12 @@ where
             trace!(
                 restate.invocation.target = %ism.invocation_target,
                 "Invocation task closed correctly");
-            self.quota.unreserve_slot();
+            self.unreserve_slot(partition, &invocation_id);
             self.status_store.on_end(&partition, &invocation_id);
             let _ = sender
                 .send(Effect {
                     invocation_id,
+                    invocation_epoch: ism.invocation_epoch,
                     kind: EffectKind::End,
                 })
                 .await;
@@ -757,11 +1041,12 @@ where
             trace!(
                 restate.invocation.target = %ism.invocation_target,
                 "Suspending invocation");
-            self.quota.unreserve_slot();
+            self.unreserve_slot(partition, &invocation_id);
             self.status_store.on_end(&partition, &invocation_id);
             let _ = sender
                 .send(Effect {
                     invocation_id,
+                    invocation_epoch: ism.invocation_epoch,
                     kind: EffectKind::Suspended {
                         waiting_for_completed_entries: entry_indexes,
                     },
@@ -783,6 +1068,7 @@ where
     )]
     async fn handle_invocation_task_failed(
         &mut self,
+        options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
         error: InvocationTaskError,
@@ -791,7 +1077,7 @@ where
             .invocation_state_machine_manager
             .remove_invocation(partition, &invocation_id)
         {
-            self.handle_error_event(partition, invocation_id, error, ism)
+            self.handle_error_event(options, partition, invocation_id, error, ism)
                 .await;
         } else {
             // If no state machine, this might be a result for an aborted invocation.
@@ -820,8 +1106,18 @@ where
                 restate.invocation.target = %ism.invocation_target,
                 "Aborting invocation");
             ism.abort();
-            self.quota.unreserve_slot();
+            self.unreserve_slot(partition, &invocation_id);
             self.status_store.on_end(&partition, &invocation_id);
+        } else if let Some(idx) =
+            self.throttled_invocations
+                .iter()
+                .position(|throttled_invocation| {
+                    throttled_invocation.invoke_command.partition == partition
+                        && throttled_invocation.invoke_command.invocation_id == invocation_id
+                })
+        {
+            trace!("Aborting throttled invocation");
+            self.throttled_invocations.remove(idx);
         } else {
             trace!("Ignoring Abort command because there is no matching partition/invocation");
         }
@@ -835,6 +1131,9 @@ where
         )
     )]
     fn handle_abort_partition(&mut self, partition: PartitionLeaderEpoch) {
+        self.throttled_invocations.retain(|throttled_invocation| {
+            throttled_invocation.invoke_command.partition != partition
+        });
         if let Some(invocation_state_machines) = self
             .invocation_state_machine_manager
             .remove_partition(partition)
@@ -846,7 +1145,7 @@ where
                     "Aborting invocation"
                 );
                 ism.abort();
-                self.quota.unreserve_slot();
+                self.unreserve_slot(partition, &fid);
                 self.status_store.on_end(&partition, &fid);
             }
         } else {
@@ -866,13 +1165,43 @@ where
 
     // --- Helpers
 
+    fn unreserve_slot(&mut self, partition: PartitionLeaderEpoch, invocation_id: &InvocationId) {
+        self.quota.unreserve_slot();
+        self.group_quota.unreserve_slot(partition, invocation_id);
+        self.slot_released = true;
+    }
+
+    /// Resolves the retry policy override of the invocation target, falling back to the retry
+    /// policy of the invoker options.
+    fn resolve_retry_policy(
+        &self,
+        options: &InvokerOptions,
+        invocation_target: &InvocationTarget,
+    ) -> (RetryPolicy, OnMaxAttempts) {
+        self.schemas
+            .pinned()
+            .resolve_invocation_retry_policy(
+                invocation_target.service_name(),
+                invocation_target.handler_name(),
+            )
+            .map(|retry_policy| (retry_policy.policy, retry_policy.on_max_attempts))
+            .unwrap_or_else(|| (options.retry_policy.clone(), OnMaxAttempts::Kill))
+    }
+
     async fn handle_error_event(
         &mut self,
+        options: &InvokerOptions,
         partition: PartitionLeaderEpoch,
         invocation_id: InvocationId,
         error: InvocationTaskError,
         mut ism: InvocationStateMachine,
     ) {
+        // The retry policy override can change while the invocation is retrying, hence it is
+        // resolved on every failure rather than when the invocation is started.
+        let (retry_policy, on_max_attempts) =
+            self.resolve_retry_policy(options, &ism.invocation_target);
+        ism.update_retry_policy(retry_policy, on_max_attempts);
+
         match ism.handle_task_error(error.next_retry_interval_override()) {
             Some(next_retry_timer_duration) if error.is_transient() => {
                 counter!(INVOKER_INVOCATION_TASK,
@@ -888,6 +1217,7 @@ where
                     humantime::format_duration(next_retry_timer_duration));
                 trace!("Invocation state: {:?}.", ism.invocation_state_debug());
                 let next_retry_at = SystemTime::now() + next_retry_timer_duration;
+                let retry_timer_id = ism.retry_timer_id();
 
                 self.status_store.on_failure(
                     partition,
@@ -901,7 +1231,60 @@ where
                     ism,
                 );
                 self.retry_timers
-                    .sleep_until(next_retry_at, (partition, invocation_id));
+                    .sleep_until(next_retry_at, (partition, invocation_id, retry_timer_id));
+            }
+            _ if error.is_transient() && ism.on_max_attempts == OnMaxAttempts::Pause => {
+                counter!(INVOKER_INVOCATION_TASK,
+                    "status" => TASK_OP_FAILED,
+                    "transient" => "true"
+                )
+                .increment(1);
+                warn_it!(
+                    error,
+                    restate.invocation.id = %invocation_id,
+                    restate.invocation.target = %ism.invocation_target,
+                    "Error when executing the invocation, retries are exhausted. Pausing the invocation.");
+                self.unreserve_slot(partition, &invocation_id);
+                self.status_store.on_end(&partition, &invocation_id);
+
+                let _ = self
+                    .invocation_state_machine_manager
+                    .resolve_partition_sender(partition)
+                    .expect("Partition should be registered")
+                    .send(Effect {
+                        invocation_id,
+                        invocation_epoch: ism.invocation_epoch,
+                        kind: EffectKind::Paused,
+                    })
+                    .await;
+            }
+            _ if ism.on_max_attempts == OnMaxAttempts::DeadLetter => {
+                counter!(INVOKER_INVOCATION_TASK,
+                    "status" => TASK_OP_FAILED,
+                    "transient" => "false"
+                )
+                .increment(1);
+                warn_it!(
+                    error,
+                    restate.invocation.id = %invocation_id,
+                    restate.invocation.target = %ism.invocation_target,
+                    "Error when executing the invocation, not going to retry. Moving the invocation to the dead letter table.");
+                self.unreserve_slot(partition, &invocation_id);
+                self.status_store.on_end(&partition, &invocation_id);
+
+                let _ = self
+                    .invocation_state_machine_manager
+                    .resolve_partition_sender(partition)
+                    .expect("Partition should be registered")
+                    .send(Effect {
+                        invocation_id,
+                        invocation_epoch: ism.invocation_epoch,
+                        kind: EffectKind::DeadLetter {
+                            error: error.into_invocation_error(),
+                            dead_lettered_at: MillisSinceEpoch::now(),
+                        },
+                    })
+                    .await;
             }
             _ => {
                 counter!(INVOKER_INVOCATION_TASK,
@@ -914,7 +1297,7 @@ where
                     restate.invocation.id = %invocation_id,
                     restate.invocation.target = %ism.invocation_target,
                     "Error when executing the invocation, not going to retry.");
-                self.quota.unreserve_slot();
+                self.unreserve_slot(partition, &invocation_id);
                 self.status_store.on_end(&partition, &invocation_id);
 
                 let _ = self
@@ -923,6 +1306,7 @@ where
                     .expect("Partition should be registered")
                     .send(Effect {
                         invocation_id,
+                        invocation_epoch: ism.invocation_epoch,
                         kind: EffectKind::Failed(error.into_invocation_error()),
                     })
                     .await;
@@ -1036,21 +1420,22 @@ mod tests {
     use crate::invocation_task::InvocationTaskError;
     use crate::quota::InvokerConcurrencyQuota;
     use restate_invoker_api::entry_enricher;
-    use restate_invoker_api::InvokerHandle;
+    use restate_invoker_api::{InvokerHandle, JournalMetadata};
     use restate_test_util::{check, let_assert};
     use restate_types::identifiers::{LeaderEpoch, PartitionId, ServiceRevision};
-    use restate_types::invocation::ServiceType;
+    use restate_types::invocation::{ServiceInvocationSpanContext, ServiceType};
     use restate_types::journal::enriched::EnrichedEntryHeader;
     use restate_types::journal::raw::RawEntry;
     use restate_types::retries::RetryPolicy;
     use restate_types::schema::deployment::Deployment;
-    use restate_types::schema::service::ServiceMetadata;
+    use restate_types::schema::service::{InvocationRetryPolicy, ServiceMetadata};
+    use restate_types::service_protocol::ServiceProtocolVersion;
 
     // -- Mocks
 
     const MOCK_PARTITION: PartitionLeaderEpoch = (PartitionId::MIN, LeaderEpoch::INITIAL);
 
-    impl<ITR, SR> ServiceInner<ITR, SR>
+    impl<ITR, SR> ServiceInner<ITR, MockSchemas, SR>
     where
         SR: JournalReader + StateReader + Clone + Send + Sync + 'static,
         <SR as JournalReader>::JournalStream: Unpin + Send + 'static,
@@ -1080,9 +1465,14 @@ mod tests {
                 invocation_tasks_tx,
                 invocation_tasks_rx,
                 invocation_task_runner,
+                schemas: Live::from_value(MockSchemas::default()),
                 invocation_tasks: Default::default(),
                 retry_timers: Default::default(),
                 quota: InvokerConcurrencyQuota::new(concurrency_limit),
+                group_quota: Default::default(),
+                throttled_invocations: Default::default(),
+                slot_released: false,
+                throttled_invocations_recheck: None,
                 status_store: Default::default(),
                 invocation_state_machine_manager: Default::default(),
             };
@@ -1125,6 +1515,7 @@ mod tests {
             _options: &InvokerOptions,
             partition: PartitionLeaderEpoch,
             invocation_id: InvocationId,
+            _invocation_epoch: InvocationEpoch,
             invocation_target: InvocationTarget,
             _retry_count_since_last_stored_entry: u32,
             storage_reader: SR,
@@ -1146,7 +1537,11 @@ mod tests {
     }
 
     #[derive(Debug, Clone, Default)]
-    struct MockSchemas;
+    struct MockSchemas(
+        Option<InvocationRetryPolicy>,
+        Option<NonZeroUsize>,
+        Option<Deployment>,
+    );
 
     impl ServiceMetadataResolver for MockSchemas {
         fn resolve_latest_service(&self, _: impl AsRef<str>) -> Option<ServiceMetadata> {
@@ -1160,6 +1555,18 @@ mod tests {
         fn list_services(&self) -> Vec<ServiceMetadata> {
             vec![]
         }
+
+        fn resolve_invocation_retry_policy(
+            &self,
+            _: impl AsRef<str>,
+            _: impl AsRef<str>,
+        ) -> Option<InvocationRetryPolicy> {
+            self.0.clone()
+        }
+
+        fn resolve_concurrency_limit(&self, _: impl AsRef<str>) -> Option<NonZeroUsize> {
+            self.1
+        }
     }
 
     impl DeploymentResolver for MockSchemas {
@@ -1167,8 +1574,10 @@ mod tests {
             None
         }
 
-        fn get_deployment(&self, _: &DeploymentId) -> Option<Deployment> {
-            None
+        fn get_deployment(&self, deployment_id: &DeploymentId) -> Option<Deployment> {
+            self.2
+                .clone()
+                .filter(|deployment| deployment.id == *deployment_id)
         }
 
         fn get_deployment_and_services(
@@ -1200,13 +1609,14 @@ mod tests {
         let service = Service::new(
             &invoker_options,
             // all invocations are unknown leading to immediate retries
-            Live::from_value(MockSchemas),
+            Live::from_value(MockSchemas::default()),
             ServiceClient::from_options(
                 &ServiceClientOptions::default(),
                 AssumeRoleCacheMode::None,
             )
             .unwrap(),
             entry_enricher::test_util::MockEntryEnricher,
+            ConcurrencyLimiter::default(),
         );
 
         let mut handle = service.handle();
@@ -1239,6 +1649,7 @@ mod tests {
             .invoke(
                 partition_leader_epoch,
                 invocation_id,
+                0,
                 invocation_target,
                 InvokeInputJournal::NoCachedJournal,
             )
@@ -1283,6 +1694,7 @@ mod tests {
             .enqueue(InvokeCommand {
                 partition: MOCK_PARTITION,
                 invocation_id: invocation_id_1,
+                invocation_epoch: 0,
                 invocation_target: InvocationTarget::mock_virtual_object(),
                 journal: InvokeInputJournal::NoCachedJournal,
             })
@@ -1291,6 +1703,7 @@ mod tests {
             .enqueue(InvokeCommand {
                 partition: MOCK_PARTITION,
                 invocation_id: invocation_id_2,
+                invocation_epoch: 0,
                 invocation_target: InvocationTarget::mock_virtual_object(),
                 journal: InvokeInputJournal::NoCachedJournal,
             })
@@ -1374,6 +1787,7 @@ mod tests {
                 let _ = invoker_tx.send(InvocationTaskOutput {
                     partition,
                     invocation_id,
+                    invocation_epoch: 0,
                     inner: InvocationTaskOutputInner::NewEntry {
                         entry_index: 1,
                         entry: RawEntry::new(EnrichedEntryHeader::SetState {}, Bytes::default()),
@@ -1387,13 +1801,16 @@ mod tests {
         let _ = service_inner.register_mock_partition(EmptyStorageReader);
 
         // Invoke the service
-        service_inner.handle_invoke(
-            &invoker_options,
-            MOCK_PARTITION,
-            invocation_id,
-            InvocationTarget::mock_virtual_object(),
-            InvokeInputJournal::NoCachedJournal,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
 
         // We should receive the new entry here
         let invoker_effect = service_inner.invocation_tasks_rx.recv().await.unwrap();
@@ -1414,6 +1831,7 @@ mod tests {
         // Handle error coming after the abort (this should be noop)
         service_inner
             .handle_invocation_task_failed(
+                &invoker_options,
                 MOCK_PARTITION,
                 invocation_id,
                 InvocationTaskError::EmptySuspensionMessage, /* any error is fine */
@@ -1424,4 +1842,426 @@ mod tests {
         let_assert!(InvokerConcurrencyQuota::Limited { available_slots } = &service_inner.quota);
         assert_eq!(*available_slots, 2);
     }
+
+    #[test(tokio::test)]
+    async fn service_concurrency_limit_holds_back_invocations() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(1)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_target = InvocationTarget::mock_service();
+        let invocation_id_1 = InvocationId::mock_random();
+        let invocation_id_2 = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), None);
+        service_inner.schemas = Live::from_value(MockSchemas(None, NonZeroUsize::new(1), None));
+        let _ = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_1,
+                0,
+                invocation_target.clone(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_2,
+                0,
+                invocation_target.clone(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // The second invocation exceeds the concurrency limit of the service
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_1)
+            .unwrap()
+            .in_flight());
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_2)
+            .is_none());
+        assert_eq!(service_inner.throttled_invocations.len(), 1);
+
+        // Completing the first invocation starts the second one
+        service_inner.handle_abort_invocation(MOCK_PARTITION, invocation_id_1);
+        assert!(service_inner.slot_released);
+        service_inner.handle_throttled_invocations(&invoker_options);
+
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_2)
+            .unwrap()
+            .in_flight());
+        assert!(service_inner.throttled_invocations.is_empty());
+
+        // Aborting a throttled invocation removes it from the queue
+        let invocation_id_3 = InvocationId::mock_random();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_3,
+                0,
+                invocation_target.clone(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+        assert_eq!(service_inner.throttled_invocations.len(), 1);
+        service_inner.handle_abort_invocation(MOCK_PARTITION, invocation_id_3);
+        assert!(service_inner.throttled_invocations.is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn deployment_concurrency_limit_applies_to_pinned_deployment() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(1)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_target = InvocationTarget::mock_service();
+        let mut deployment = Deployment::mock();
+        deployment.metadata.delivery_options.concurrency_limit = NonZeroUsize::new(1);
+        let pinned_journal = || {
+            InvokeInputJournal::CachedJournal(
+                JournalMetadata::new(
+                    0,
+                    ServiceInvocationSpanContext::empty(),
+                    Some(PinnedDeployment::new(
+                        deployment.id,
+                        ServiceProtocolVersion::V1,
+                    )),
+                    MillisSinceEpoch::UNIX_EPOCH,
+                ),
+                vec![],
+            )
+        };
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), None);
+        // The pinned deployment is not the latest deployment of the service
+        service_inner.schemas = Live::from_value(MockSchemas(None, None, Some(deployment.clone())));
+        let _ = service_inner.register_mock_partition(EmptyStorageReader);
+
+        let invocation_id_1 = InvocationId::mock_random();
+        let invocation_id_2 = InvocationId::mock_random();
+        let invocation_id_3 = InvocationId::mock_random();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_1,
+                0,
+                invocation_target.clone(),
+                pinned_journal(),
+            )
+            .await;
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_2,
+                0,
+                invocation_target.clone(),
+                pinned_journal(),
+            )
+            .await;
+        // Invocations which are not pinned yet don't count against the pinned deployment
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id_3,
+                0,
+                invocation_target.clone(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_2)
+            .is_none());
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_3)
+            .unwrap()
+            .in_flight());
+        assert_eq!(service_inner.throttled_invocations.len(), 1);
+
+        // Raising the limit starts the throttled invocation once it is checked again
+        deployment.metadata.delivery_options.concurrency_limit = NonZeroUsize::new(2);
+        service_inner.schemas = Live::from_value(MockSchemas(None, None, Some(deployment)));
+        service_inner.handle_throttled_invocations(&invoker_options);
+
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id_2)
+            .unwrap()
+            .in_flight());
+        assert!(service_inner.throttled_invocations.is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn failure_after_abort_is_not_counted() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(1)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), Some(1));
+        let mut effects_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // Abort the invocation, e.g. because it has been paused
+        service_inner.handle_abort_invocation(MOCK_PARTITION, invocation_id);
+
+        // The failure of the aborted attempt must neither be retried nor reported
+        service_inner
+            .handle_invocation_task_output(
+                &invoker_options,
+                InvocationTaskOutput {
+                    partition: MOCK_PARTITION,
+                    invocation_id,
+                    invocation_epoch: 0,
+                    inner: InvocationTaskOutputInner::Failed(
+                        InvocationTaskError::EmptySuspensionMessage, /* any error is fine */
+                    ),
+                },
+            )
+            .await;
+
+        assert!(service_inner
+            .status_store
+            .resolve_invocation(MOCK_PARTITION, &invocation_id)
+            .is_none());
+        check!(let Err(_) = effects_rx.try_recv());
+
+        // Resuming the invocation starts a new attempt with the next epoch
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                1,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // A failure of the aborted attempt which is received only now must not be counted as a
+        // failure of the new attempt, which has no retries left
+        service_inner
+            .handle_invocation_task_output(
+                &invoker_options,
+                InvocationTaskOutput {
+                    partition: MOCK_PARTITION,
+                    invocation_id,
+                    invocation_epoch: 0,
+                    inner: InvocationTaskOutputInner::Failed(
+                        InvocationTaskError::EmptySuspensionMessage,
+                    ),
+                },
+            )
+            .await;
+
+        let_assert!(
+            Some((_, ism)) = service_inner
+                .invocation_state_machine_manager
+                .resolve_invocation(MOCK_PARTITION, &invocation_id)
+        );
+        check!(ism.invocation_epoch == 1);
+        check!(let Err(_) = effects_rx.try_recv());
+    }
+
+    #[test(tokio::test)]
+    async fn exhausted_retries_pause_invocation_with_handler_override() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(10)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), Some(1));
+        service_inner.schemas = Live::from_value(MockSchemas(
+            Some(InvocationRetryPolicy {
+                policy: RetryPolicy::None,
+                on_max_attempts: OnMaxAttempts::Pause,
+            }),
+            None,
+            None,
+        ));
+        let mut effects_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // The override doesn't allow any retry, hence the invoker gives up after the first failure
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                InvocationTaskError::EmptySuspensionMessage, /* any error is fine */
+            )
+            .await;
+
+        let effect = effects_rx.try_recv().unwrap();
+        assert_eq!(effect.invocation_id, invocation_id);
+        check!(let EffectKind::Paused = effect.kind);
+        let_assert!(InvokerConcurrencyQuota::Limited { available_slots } = &service_inner.quota);
+        assert_eq!(*available_slots, 1);
+    }
+
+    #[test(tokio::test)]
+    async fn retry_policy_override_applies_to_running_invocation() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(10)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), Some(1));
+        let mut effects_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // The override is configured after the invocation has been started
+        service_inner.schemas = Live::from_value(MockSchemas(
+            Some(InvocationRetryPolicy {
+                policy: RetryPolicy::None,
+                on_max_attempts: OnMaxAttempts::Pause,
+            }),
+            None,
+            None,
+        ));
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                InvocationTaskError::EmptySuspensionMessage, /* any error is fine */
+            )
+            .await;
+
+        let effect = effects_rx.try_recv().unwrap();
+        assert_eq!(effect.invocation_id, invocation_id);
+        check!(let EffectKind::Paused = effect.kind);
+    }
+
+    #[test(tokio::test)]
+    async fn exhausted_retries_dead_letter_invocation_with_handler_override() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .retry_policy(RetryPolicy::fixed_delay(Duration::ZERO, Some(10)))
+            .inactivity_timeout(Duration::ZERO.into())
+            .abort_timeout(Duration::ZERO.into())
+            .disable_eager_state(false)
+            .message_size_warning(NonZeroUsize::new(1024).unwrap())
+            .message_size_limit(None)
+            .build()
+            .unwrap();
+        let invocation_id = InvocationId::mock_random();
+
+        let (_, _status_tx, mut service_inner) =
+            ServiceInner::mock(|_, _, _, _, _, _, _| pending(), Some(1));
+        service_inner.schemas = Live::from_value(MockSchemas(
+            Some(InvocationRetryPolicy {
+                policy: RetryPolicy::None,
+                on_max_attempts: OnMaxAttempts::DeadLetter,
+            }),
+            None,
+            None,
+        ));
+        let mut effects_rx = service_inner.register_mock_partition(EmptyStorageReader);
+
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                InvokeInputJournal::NoCachedJournal,
+            )
+            .await;
+
+        // The override doesn't allow any retry, hence the invoker gives up after the first failure
+        service_inner
+            .handle_invocation_task_failed(
+                &invoker_options,
+                MOCK_PARTITION,
+                invocation_id,
+                InvocationTaskError::EmptySuspensionMessage, /* any error is fine */
+            )
+            .await;
+
+        let effect = effects_rx.try_recv().unwrap();
+        assert_eq!(effect.invocation_id, invocation_id);
+        check!(let EffectKind::DeadLetter { .. } = effect.kind);
+        let_assert!(InvokerConcurrencyQuota::Limited { available_slots } = &service_inner.quota);
+        assert_eq!(*available_slots, 1);
+    }
 }
@@ -16,6 +16,7 @@ pub const INVOKER_ENQUEUE: &str = "restate.invoker.enqueue.total";
 pub const INVOKER_INVOCATION_TASK: &str = "restate.invoker.invocation_task.total";
 pub const INVOKER_AVAILABLE_SLOTS: &str = "restate.invoker.available_slots";
 pub const INVOKER_TASK_DURATION: &str = "restate.invoker.task_duration.seconds";
+pub const INVOKER_CONCURRENCY_LIMITED: &str = "restate.invoker.concurrency_limited.total";
 
 pub const TASK_OP_STARTED: &str = "started";
 pub const TASK_OP_SUSPENDED: &str = "suspended";
@@ -35,6 +36,12 @@ pub(crate) fn describe_metrics() {
         "Invocation task operation"
     );
 
+    describe_counter!(
+        INVOKER_CONCURRENCY_LIMITED,
+        Unit::Count,
+        "Number of invocations held back because their service or deployment reached its concurrency limit"
+    );
+
     describe_gauge!(
         INVOKER_AVAILABLE_SLOTS,
         Unit::Count,
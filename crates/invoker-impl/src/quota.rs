@@ -8,7 +8,17 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
 use metrics::gauge;
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use restate_types::identifiers::{DeploymentId, InvocationId, PartitionLeaderEpoch};
 
 use crate::metric_definitions::INVOKER_AVAILABLE_SLOTS;
 
@@ -51,3 +61,239 @@ impl InvokerConcurrencyQuota {
         }
     }
 }
+
+/// Concurrency limits applying to an invocation, resolved when the invocation is started.
+#[derive(Debug, Clone)]
+pub(super) struct ConcurrencyGroup {
+    pub(super) service_name: String,
+    pub(super) service_limit: Option<NonZeroUsize>,
+    /// Deployment the invocation is pinned to, with its concurrency limit. Invocations which
+    /// are not pinned yet use the latest deployment of their service.
+    pub(super) deployment: Option<(DeploymentId, Option<NonZeroUsize>)>,
+}
+
+/// Limits the concurrently running invocations of each service and deployment on this node.
+///
+/// The limiter is shared by the invokers of all the partition processors running on the node,
+/// so that a limit caps the invocations of the node as a whole rather than those of every
+/// partition. Nodes don't coordinate with each other.
+#[derive(Debug, Clone, Default)]
+pub struct ConcurrencyLimiter {
+    running: Arc<Mutex<RunningInvocations>>,
+    slot_released: Arc<Notify>,
+}
+
+/// Running invocations of all invokers sharing a [`ConcurrencyLimiter`].
+#[derive(Debug, Default)]
+struct RunningInvocations {
+    groups: HashMap<(PartitionLeaderEpoch, InvocationId), ConcurrencyGroup>,
+    per_service: HashMap<String, usize>,
+    per_deployment: HashMap<DeploymentId, usize>,
+}
+
+impl RunningInvocations {
+    fn is_slot_available(&self, group: &ConcurrencyGroup) -> bool {
+        let service_slot_available = group.service_limit.map_or(true, |limit| {
+            self.per_service
+                .get(&group.service_name)
+                .copied()
+                .unwrap_or_default()
+                < limit.get()
+        });
+        let deployment_slot_available = match &group.deployment {
+            Some((deployment_id, Some(limit))) => {
+                self.per_deployment
+                    .get(deployment_id)
+                    .copied()
+                    .unwrap_or_default()
+                    < limit.get()
+            }
+            _ => true,
+        };
+        service_slot_available && deployment_slot_available
+    }
+
+    fn reserve_slot(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+        group: &ConcurrencyGroup,
+    ) {
+        *self
+            .per_service
+            .entry(group.service_name.clone())
+            .or_default() += 1;
+        if let Some((deployment_id, _)) = &group.deployment {
+            *self.per_deployment.entry(*deployment_id).or_default() += 1;
+        }
+        self.groups
+            .insert((partition, invocation_id), group.clone());
+    }
+
+    fn unreserve_slot(&mut self, partition: PartitionLeaderEpoch, invocation_id: &InvocationId) {
+        let Some(group) = self.groups.remove(&(partition, *invocation_id)) else {
+            return;
+        };
+        decrement(&mut self.per_service, &group.service_name);
+        if let Some((deployment_id, _)) = &group.deployment {
+            decrement(&mut self.per_deployment, deployment_id);
+        }
+    }
+}
+
+/// Slots of a single invoker in the [`ConcurrencyLimiter`] of the node. The slots still held
+/// when the invoker goes away are released.
+#[derive(Debug, Default)]
+pub(super) struct GroupConcurrencyQuota {
+    limiter: ConcurrencyLimiter,
+    reserved: HashSet<(PartitionLeaderEpoch, InvocationId)>,
+}
+
+impl GroupConcurrencyQuota {
+    pub(super) fn new(limiter: ConcurrencyLimiter) -> Self {
+        Self {
+            limiter,
+            reserved: HashSet::default(),
+        }
+    }
+
+    /// Reserves a slot for the invocation, unless its service or deployment reached its
+    /// concurrency limit. Returns whether the slot has been reserved.
+    pub(super) fn try_reserve_slot(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: InvocationId,
+        group: &ConcurrencyGroup,
+    ) -> bool {
+        let mut running = self.limiter.running.lock();
+        if !running.is_slot_available(group) {
+            return false;
+        }
+
+        running.reserve_slot(partition, invocation_id, group);
+        self.reserved.insert((partition, invocation_id));
+        true
+    }
+
+    /// Counts a running invocation against the deployment it has been pinned to.
+    pub(super) fn update_deployment(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: &InvocationId,
+        deployment_id: DeploymentId,
+        concurrency_limit: Option<NonZeroUsize>,
+    ) {
+        let mut running = self.limiter.running.lock();
+        let running = &mut *running;
+        let Some(group) = running.groups.get_mut(&(partition, *invocation_id)) else {
+            return;
+        };
+        if let Some((previous_deployment_id, _)) = &group.deployment {
+            if *previous_deployment_id == deployment_id {
+                return;
+            }
+            decrement(&mut running.per_deployment, previous_deployment_id);
+        }
+        *running.per_deployment.entry(deployment_id).or_default() += 1;
+        group.deployment = Some((deployment_id, concurrency_limit));
+    }
+
+    pub(super) fn unreserve_slot(
+        &mut self,
+        partition: PartitionLeaderEpoch,
+        invocation_id: &InvocationId,
+    ) {
+        if !self.reserved.remove(&(partition, *invocation_id)) {
+            return;
+        }
+        self.limiter
+            .running
+            .lock()
+            .unreserve_slot(partition, invocation_id);
+        self.limiter.slot_released.notify_waiters();
+    }
+
+    /// Completes once any invoker of the node released a slot.
+    pub(super) fn slot_released(&self) -> impl Future<Output = ()> + Send + 'static {
+        let slot_released = Arc::clone(&self.limiter.slot_released);
+        async move { slot_released.notified().await }
+    }
+}
+
+impl Drop for GroupConcurrencyQuota {
+    fn drop(&mut self) {
+        if self.reserved.is_empty() {
+            return;
+        }
+
+        let mut running = self.limiter.running.lock();
+        for (partition, invocation_id) in self.reserved.drain() {
+            running.unreserve_slot(partition, &invocation_id);
+        }
+        drop(running);
+        self.limiter.slot_released.notify_waiters();
+    }
+}
+
+fn decrement<K: Eq + Hash>(running: &mut HashMap<K, usize>, key: &K) {
+    if let Some(count) = running.get_mut(key) {
+        *count -= 1;
+        if *count == 0 {
+            running.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use restate_types::identifiers::{LeaderEpoch, PartitionId};
+
+    use super::*;
+
+    fn service_group(limit: usize) -> ConcurrencyGroup {
+        ConcurrencyGroup {
+            service_name: "Greeter".to_owned(),
+            service_limit: NonZeroUsize::new(limit),
+            deployment: None,
+        }
+    }
+
+    #[test]
+    fn limit_is_shared_by_the_invokers_of_a_node() {
+        let limiter = ConcurrencyLimiter::default();
+        let mut quota_1 = GroupConcurrencyQuota::new(limiter.clone());
+        let mut quota_2 = GroupConcurrencyQuota::new(limiter.clone());
+        let partition_1 = (PartitionId::from(1), LeaderEpoch::INITIAL);
+        let partition_2 = (PartitionId::from(2), LeaderEpoch::INITIAL);
+        let group = service_group(2);
+
+        let invocation_id = InvocationId::mock_random();
+        assert!(quota_1.try_reserve_slot(partition_1, invocation_id, &group));
+        assert!(quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+        // the limit has been reached by the two invokers together
+        assert!(!quota_1.try_reserve_slot(partition_1, InvocationId::mock_random(), &group));
+        assert!(!quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+
+        // a slot can only be released by the invoker holding it
+        quota_2.unreserve_slot(partition_1, &invocation_id);
+        assert!(!quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+        quota_1.unreserve_slot(partition_1, &invocation_id);
+        assert!(quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+    }
+
+    #[test]
+    fn slots_are_released_when_the_invoker_goes_away() {
+        let limiter = ConcurrencyLimiter::default();
+        let mut quota_1 = GroupConcurrencyQuota::new(limiter.clone());
+        let mut quota_2 = GroupConcurrencyQuota::new(limiter.clone());
+        let partition_1 = (PartitionId::from(1), LeaderEpoch::INITIAL);
+        let partition_2 = (PartitionId::from(2), LeaderEpoch::INITIAL);
+        let group = service_group(1);
+
+        assert!(quota_1.try_reserve_slot(partition_1, InvocationId::mock_random(), &group));
+        assert!(!quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+
+        drop(quota_1);
+        assert!(quota_2.try_reserve_slot(partition_2, InvocationId::mock_random(), &group));
+    }
+}
@@ -10,7 +10,53 @@
 
 mod grpc_svc;
 pub mod local;
+pub mod raft;
+
+use restate_types::config::{MetadataStoreKind, MetadataStoreOptions, RocksDbOptions};
+use restate_types::health::HealthStatus;
+use restate_types::live::BoxedLiveLoad;
+use restate_types::protobuf::common::MetadataServerStatus;
 
 pub use restate_core::metadata_store::{
     MetadataStoreClient, Precondition, ReadError, ReadModifyWriteError, WriteError,
 };
+
+use crate::local::LocalMetadataStoreService;
+use crate::raft::RaftMetadataStoreService;
+
+/// Metadata store service which runs the implementation selected by [`MetadataStoreKind`].
+pub enum MetadataStoreService {
+    Local(LocalMetadataStoreService),
+    Raft(RaftMetadataStoreService),
+}
+
+impl MetadataStoreService {
+    pub fn from_options(
+        health_status: HealthStatus<MetadataServerStatus>,
+        mut opts: BoxedLiveLoad<MetadataStoreOptions>,
+        rocksdb_options: BoxedLiveLoad<RocksDbOptions>,
+    ) -> Self {
+        match opts.live_load().kind.clone() {
+            MetadataStoreKind::Local => MetadataStoreService::Local(
+                LocalMetadataStoreService::from_options(health_status, opts, rocksdb_options),
+            ),
+            MetadataStoreKind::Raft(raft_options) => {
+                MetadataStoreService::Raft(RaftMetadataStoreService::from_options(
+                    health_status,
+                    opts,
+                    raft_options,
+                    rocksdb_options,
+                ))
+            }
+        }
+    }
+
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self {
+            MetadataStoreService::Local(service) => service.run().await?,
+            MetadataStoreService::Raft(service) => service.run().await?,
+        }
+
+        Ok(())
+    }
+}
@@ -8,15 +8,20 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use bytestring::ByteString;
-use tonic::transport::Channel;
+use tonic::transport::{Channel, ClientTlsConfig};
 use tonic::{Code, Status};
+use tracing::debug;
 
 use restate_core::metadata_store::{
     MetadataStore, Precondition, ReadError, VersionedValue, WriteError,
 };
-use restate_core::network::net_util::create_tonic_channel_from_advertised_address;
+use restate_core::network::net_util::create_tonic_channel;
 use restate_types::net::AdvertisedAddress;
 use restate_types::Version;
 
@@ -24,18 +29,84 @@ use crate::grpc_svc::metadata_store_svc_client::MetadataStoreSvcClient;
 use crate::grpc_svc::{DeleteRequest, GetRequest, PutRequest};
 use crate::local::grpc::pb_conversions::ConversionError;
 
-/// Client end to interact with the [`LocalMetadataStore`].
+/// Client end to interact with the [`LocalMetadataStore`] or the nodes of a replicated metadata
+/// store.
+///
+/// Requests are sent to the node which served the last request. If it is unavailable, the
+/// request fails over to the other nodes in the configured order.
 #[derive(Debug, Clone)]
 pub struct LocalMetadataStoreClient {
-    svc_client: MetadataStoreSvcClient<Channel>,
+    svc_clients: Arc<[(AdvertisedAddress, MetadataStoreSvcClient<Channel>)]>,
+    current: Arc<AtomicUsize>,
 }
 impl LocalMetadataStoreClient {
-    pub fn new(metadata_store_address: AdvertisedAddress) -> Self {
-        let channel = create_tonic_channel_from_advertised_address(metadata_store_address);
+    pub fn new(
+        metadata_store_address: AdvertisedAddress,
+        tls_config: Option<ClientTlsConfig>,
+    ) -> Result<Self, tonic::transport::Error> {
+        Self::with_addresses(vec![metadata_store_address], tls_config)
+    }
+
+    /// Creates a client which fails over between the given addresses.
+    ///
+    /// # Panics
+    ///
+    /// If `metadata_store_addresses` is empty.
+    pub fn with_addresses(
+        metadata_store_addresses: Vec<AdvertisedAddress>,
+        tls_config: Option<ClientTlsConfig>,
+    ) -> Result<Self, tonic::transport::Error> {
+        assert!(
+            !metadata_store_addresses.is_empty(),
+            "at least one metadata store address is required"
+        );
+
+        let svc_clients = metadata_store_addresses
+            .into_iter()
+            .map(|address| {
+                let channel = create_tonic_channel(address.clone(), tls_config.clone())?;
+                Ok((address, MetadataStoreSvcClient::new(channel)))
+            })
+            .collect::<Result<_, tonic::transport::Error>>()?;
+
+        Ok(Self {
+            svc_clients,
+            current: Arc::default(),
+        })
+    }
+
+    /// Sends the request to the current node and fails over to the next ones as long as they
+    /// are unavailable. Returns the last error if no node can serve the request.
+    async fn call<T, F, Fut>(&self, request: F) -> Result<T, Status>
+    where
+        F: Fn(MetadataStoreSvcClient<Channel>) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, Status>>,
+    {
+        let current = self.current.load(Ordering::Relaxed);
+        let mut last_status = None;
 
-        Self {
-            svc_client: MetadataStoreSvcClient::new(channel),
+        for attempt in 0..self.svc_clients.len() {
+            let index = (current + attempt) % self.svc_clients.len();
+            let (address, svc_client) = &self.svc_clients[index];
+
+            match request(svc_client.clone()).await {
+                Err(status) if status.code() == Code::Unavailable => {
+                    debug!(
+                        "Metadata store at {address} is unavailable: {}",
+                        status.message()
+                    );
+                    last_status = Some(status);
+                }
+                result => {
+                    if index != current {
+                        self.current.store(index, Ordering::Relaxed);
+                    }
+                    return result.map(tonic::Response::into_inner);
+                }
+            }
         }
+
+        Err(last_status.expect("at least one metadata store address"))
     }
 }
 
@@ -43,27 +114,28 @@ impl LocalMetadataStoreClient {
 impl MetadataStore for LocalMetadataStoreClient {
     async fn get(&self, key: ByteString) -> Result<Option<VersionedValue>, ReadError> {
         let response = self
-            .svc_client
-            .clone()
-            .get(GetRequest { key: key.into() })
+            .call(|mut svc_client| {
+                let key = key.clone();
+                async move { svc_client.get(GetRequest { key: key.into() }).await }
+            })
             .await
             .map_err(map_status_to_read_error)?;
 
         response
-            .into_inner()
             .try_into()
             .map_err(|err: ConversionError| ReadError::Internal(err.to_string()))
     }
 
     async fn get_version(&self, key: ByteString) -> Result<Option<Version>, ReadError> {
         let response = self
-            .svc_client
-            .clone()
-            .get_version(GetRequest { key: key.into() })
+            .call(|mut svc_client| {
+                let key = key.clone();
+                async move { svc_client.get_version(GetRequest { key: key.into() }).await }
+            })
             .await
             .map_err(map_status_to_read_error)?;
 
-        Ok(response.into_inner().into())
+        Ok(response.into())
     }
 
     async fn put(
@@ -72,28 +144,30 @@ impl MetadataStore for LocalMetadataStoreClient {
         value: VersionedValue,
         precondition: Precondition,
     ) -> Result<(), WriteError> {
-        self.svc_client
-            .clone()
-            .put(PutRequest {
-                key: key.into(),
-                value: Some(value.into()),
-                precondition: Some(precondition.into()),
-            })
-            .await
-            .map_err(map_status_to_write_error)?;
+        self.call(|mut svc_client| {
+            let request = PutRequest {
+                key: key.clone().into(),
+                value: Some(value.clone().into()),
+                precondition: Some(precondition.clone().into()),
+            };
+            async move { svc_client.put(request).await }
+        })
+        .await
+        .map_err(map_status_to_write_error)?;
 
         Ok(())
     }
 
     async fn delete(&self, key: ByteString, precondition: Precondition) -> Result<(), WriteError> {
-        self.svc_client
-            .clone()
-            .delete(DeleteRequest {
-                key: key.into(),
-                precondition: Some(precondition.into()),
-            })
-            .await
-            .map_err(map_status_to_write_error)?;
+        self.call(|mut svc_client| {
+            let request = DeleteRequest {
+                key: key.clone().into(),
+                precondition: Some(precondition.clone().into()),
+            };
+            async move { svc_client.delete(request).await }
+        })
+        .await
+        .map_err(map_status_to_write_error)?;
 
         Ok(())
     }
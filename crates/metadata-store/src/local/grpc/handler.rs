@@ -133,6 +133,7 @@ impl From<Error> for Status {
     fn from(err: Error) -> Self {
         match err {
             Error::FailedPrecondition(msg) => Status::failed_precondition(msg),
+            Error::Unavailable(msg) => Status::unavailable(msg),
             err => Status::internal(err.to_string()),
         }
     }
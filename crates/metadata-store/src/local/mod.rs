@@ -8,12 +8,13 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-mod grpc;
-mod store;
+pub(crate) mod grpc;
+pub(crate) mod store;
 
 mod service;
 
 use restate_core::metadata_store::{providers::EtcdMetadataStore, MetadataStoreClient};
+use restate_core::network::tls::load_client_tls_config;
 use restate_types::{
     config::{MetadataStoreClient as MetadataStoreClientConfig, MetadataStoreClientOptions},
     errors::GenericError,
@@ -29,8 +30,16 @@ pub async fn create_client(
     let backoff_policy = Some(metadata_store_client_options.metadata_store_client_backoff_policy);
 
     let client = match metadata_store_client_options.metadata_store_client {
-        MetadataStoreClientConfig::Embedded { address } => {
-            let store = LocalMetadataStoreClient::new(address);
+        MetadataStoreClientConfig::Embedded { addresses } => {
+            if addresses.is_empty() {
+                return Err("no metadata store address configured".into());
+            }
+            let tls_config = metadata_store_client_options
+                .metadata_store_client_tls
+                .as_ref()
+                .map(load_client_tls_config)
+                .transpose()?;
+            let store = LocalMetadataStoreClient::with_addresses(addresses, tls_config)?;
             MetadataStoreClient::new(store, backoff_policy)
         }
         MetadataStoreClientConfig::Etcd { addresses } => {
@@ -17,7 +17,7 @@ use tonic::server::NamedService;
 use tower::ServiceExt;
 use tower_http::classify::{GrpcCode, GrpcErrorsAsFailures, SharedClassifier};
 
-use restate_core::network::net_util;
+use restate_core::network::{net_util, TlsAcceptor, TlsError};
 use restate_core::{task_center, ShutdownError, TaskKind};
 use restate_rocksdb::RocksError;
 use restate_types::config::{MetadataStoreOptions, RocksDbOptions};
@@ -41,6 +41,8 @@ pub enum Error {
     GrpcServer(#[from] net_util::Error),
     #[error("error while running server server grpc reflection service: {0}")]
     GrpcReflection(#[from] tonic_reflection::server::Error),
+    #[error("failed loading the TLS configuration: {0}")]
+    Tls(#[from] TlsError),
     #[error("system is shutting down")]
     Shutdown(#[from] ShutdownError),
     #[error("rocksdb error: {0}")]
@@ -73,6 +75,11 @@ impl LocalMetadataStoreService {
         } = self;
         let options = opts.live_load();
         let bind_address = options.bind_address.clone();
+        let tls_acceptor = options
+            .tls
+            .as_ref()
+            .map(TlsAcceptor::from_options)
+            .transpose()?;
         let store = LocalMetadataStore::create(options, rocksdb_options).await?;
 
         let trace_layer = tower_http::trace::TraceLayer::new(SharedClassifier::new(
@@ -113,6 +120,7 @@ impl LocalMetadataStoreService {
             async move {
                 net_util::run_hyper_server(
                     &bind_address,
+                    tls_acceptor,
                     service,
                     "metadata-store-grpc",
                     || health_status.update(MetadataServerStatus::Ready),
@@ -72,14 +72,18 @@ pub enum Error {
     Encode(#[from] StorageEncodeError),
     #[error("decode error: {0}")]
     Decode(#[from] StorageDecodeError),
+    #[error("unavailable: {0}")]
+    Unavailable(String),
+    #[error("internal error: {0}")]
+    Internal(String),
 }
 
 impl Error {
-    fn kv_pair_exists() -> Self {
+    pub(crate) fn kv_pair_exists() -> Self {
         Error::FailedPrecondition("key-value pair already exists".to_owned())
     }
 
-    fn version_mismatch(expected: Version, actual: Option<Version>) -> Self {
+    pub(crate) fn version_mismatch(expected: Version, actual: Option<Version>) -> Self {
         Error::FailedPrecondition(format!(
             "Expected version '{}' but found version '{:?}'",
             expected, actual
@@ -0,0 +1,98 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use async_trait::async_trait;
+use protobuf::Message as ProtobufMessage;
+use raft::prelude::Message;
+use tokio::sync::oneshot;
+use tonic::{Request, Response, Status};
+
+use restate_types::net::AdvertisedAddress;
+
+use crate::grpc_svc::raft_metadata_store_svc_server::RaftMetadataStoreSvc;
+use crate::grpc_svc::{AddNodeRequest, RaftMessage, RemoveNodeRequest};
+use crate::raft::store::{MembershipRequest, MembershipRequestSender, RaftSender};
+
+/// Grpc svc handler for the replica-to-replica communication of the [`RaftMetadataStore`].
+#[derive(Debug)]
+pub struct RaftMetadataStoreHandler {
+    raft_tx: RaftSender,
+    membership_tx: MembershipRequestSender,
+}
+
+impl RaftMetadataStoreHandler {
+    pub fn new(raft_tx: RaftSender, membership_tx: MembershipRequestSender) -> Self {
+        Self {
+            raft_tx,
+            membership_tx,
+        }
+    }
+}
+
+#[async_trait]
+impl RaftMetadataStoreSvc for RaftMetadataStoreHandler {
+    async fn raft(&self, request: Request<RaftMessage>) -> Result<Response<()>, Status> {
+        let message = Message::parse_from_bytes(&request.into_inner().message)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        self.raft_tx
+            .send(message)
+            .await
+            .map_err(|_| Status::unavailable("metadata store is shut down"))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn add_node(&self, request: Request<AddNodeRequest>) -> Result<Response<()>, Status> {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        let request = request.into_inner();
+        let address: AdvertisedAddress = request
+            .address
+            .parse()
+            .map_err(|err: http::uri::InvalidUri| Status::invalid_argument(err.to_string()))?;
+
+        self.membership_tx
+            .send(MembershipRequest::AddNode {
+                id: request.id,
+                address,
+                result_tx,
+            })
+            .await
+            .map_err(|_| Status::unavailable("metadata store is shut down"))?;
+
+        result_rx
+            .await
+            .map_err(|_| Status::unavailable("metadata store is shut down"))??;
+
+        Ok(Response::new(()))
+    }
+
+    async fn remove_node(
+        &self,
+        request: Request<RemoveNodeRequest>,
+    ) -> Result<Response<()>, Status> {
+        let (result_tx, result_rx) = oneshot::channel();
+
+        self.membership_tx
+            .send(MembershipRequest::RemoveNode {
+                id: request.into_inner().id,
+                result_tx,
+            })
+            .await
+            .map_err(|_| Status::unavailable("metadata store is shut down"))?;
+
+        result_rx
+            .await
+            .map_err(|_| Status::unavailable("metadata store is shut down"))??;
+
+        Ok(Response::new(()))
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod handler;
+mod network;
+mod service;
+mod storage;
+mod store;
+
+pub use service::{Error, RaftMetadataStoreService};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,143 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{BTreeMap, HashMap};
+
+use protobuf::Message as ProtobufMessage;
+use raft::prelude::Message;
+use tokio::sync::mpsc;
+use tonic::transport::{Channel, ClientTlsConfig};
+use tracing::{debug, trace, warn};
+
+use restate_core::network::net_util::create_tonic_channel;
+use restate_core::network::tls::load_client_tls_config;
+use restate_core::network::TlsError;
+use restate_core::{task_center, TaskKind};
+use restate_types::config::TlsClientOptions;
+use restate_types::net::AdvertisedAddress;
+
+use crate::grpc_svc::raft_metadata_store_svc_client::RaftMetadataStoreSvcClient;
+use crate::grpc_svc::RaftMessage;
+
+/// Number of raft messages which can be buffered per peer before messages are dropped. Raft
+/// recovers from dropped replication messages, but not from dropped proposals and read index
+/// requests forwarded to the leader. The store fails the requests waiting for those once their
+/// timeout expires.
+const PEER_QUEUE_LENGTH: usize = 128;
+
+struct PeerConnection {
+    address: AdvertisedAddress,
+    message_tx: mpsc::Sender<Message>,
+}
+
+/// Sends raft messages to the other replicas of the metadata store.
+pub struct Networking {
+    id: u64,
+    tls_config: Option<ClientTlsConfig>,
+    connections: HashMap<u64, PeerConnection>,
+}
+
+impl Networking {
+    pub fn new(id: u64, tls: Option<&TlsClientOptions>) -> Result<Self, TlsError> {
+        Ok(Self {
+            id,
+            tls_config: tls.map(load_client_tls_config).transpose()?,
+            connections: HashMap::default(),
+        })
+    }
+
+    /// Updates the set of peers to which messages can be sent. Connections to removed peers are
+    /// closed.
+    pub fn update_peers(&mut self, peers: &BTreeMap<u64, AdvertisedAddress>) {
+        self.connections.retain(|peer_id, connection| {
+            peers
+                .get(peer_id)
+                .is_some_and(|address| *address == connection.address)
+        });
+
+        for (peer_id, address) in peers {
+            if *peer_id == self.id || self.connections.contains_key(peer_id) {
+                continue;
+            }
+
+            let channel = match create_tonic_channel(address.clone(), self.tls_config.clone()) {
+                Ok(channel) => channel,
+                Err(err) => {
+                    warn!("Cannot connect to raft peer {peer_id} at {address}: {err}");
+                    continue;
+                }
+            };
+            let (message_tx, message_rx) = mpsc::channel(PEER_QUEUE_LENGTH);
+
+            if task_center()
+                .spawn_child(
+                    TaskKind::MetadataStore,
+                    "raft-peer-connection",
+                    None,
+                    run_peer_connection(*peer_id, channel, message_rx),
+                )
+                .is_err()
+            {
+                // system is shutting down
+                return;
+            }
+
+            debug!("Connecting to raft peer {peer_id} at {address}");
+            self.connections.insert(
+                *peer_id,
+                PeerConnection {
+                    address: address.clone(),
+                    message_tx,
+                },
+            );
+        }
+    }
+
+    pub fn send(&mut self, messages: Vec<Message>) {
+        for message in messages {
+            if let Some(connection) = self.connections.get(&message.to) {
+                if let Err(err) = connection.message_tx.try_send(message) {
+                    trace!("Dropping raft message: {err}");
+                }
+            } else {
+                trace!("Dropping raft message to unknown peer {}", message.to);
+            }
+        }
+    }
+}
+
+async fn run_peer_connection(
+    peer_id: u64,
+    channel: Channel,
+    mut message_rx: mpsc::Receiver<Message>,
+) -> anyhow::Result<()> {
+    let mut client = RaftMetadataStoreSvcClient::new(channel);
+
+    while let Some(message) = message_rx.recv().await {
+        let message = match message.write_to_bytes() {
+            Ok(message) => message,
+            Err(err) => {
+                debug!("Failed encoding raft message for peer {peer_id}: {err}");
+                continue;
+            }
+        };
+
+        if let Err(err) = client
+            .raft(RaftMessage {
+                message: message.into(),
+            })
+            .await
+        {
+            trace!("Failed sending raft message to peer {peer_id}: {err}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,742 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use bytestring::ByteString;
+use protobuf::Message as ProtobufMessage;
+use raft::prelude::{ConfState, Entry, HardState, Snapshot, SnapshotMetadata};
+use raft::{GetEntriesContext, RaftState, StorageError};
+use rocksdb::{BoundColumnFamily, DBCompressionType, IteratorMode, WriteBatch, WriteOptions, DB};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use restate_core::metadata_store::VersionedValue;
+use restate_rocksdb::{
+    CfName, CfPrefixPattern, DbName, DbSpecBuilder, IoMode, Priority, RocksDb, RocksDbManager,
+    RocksError,
+};
+use restate_types::config::{MetadataStoreOptions, RaftOptions, RocksDbOptions};
+use restate_types::flexbuffers_storage_encode_decode;
+use restate_types::live::BoxedLiveLoad;
+use restate_types::net::AdvertisedAddress;
+use restate_types::storage::{
+    StorageCodec, StorageDecode, StorageDecodeError, StorageEncode, StorageEncodeError,
+};
+
+const DB_NAME: &str = "raft-metadata-store";
+const RAFT_LOG: &str = "raft_log";
+const RAFT_METADATA: &str = "raft_metadata";
+const KV_PAIRS: &str = "kv_pairs";
+
+const HARD_STATE_KEY: &[u8] = b"hard_state";
+const CONF_STATE_KEY: &[u8] = b"conf_state";
+const SNAPSHOT_METADATA_KEY: &[u8] = b"snapshot_metadata";
+const APPLIED_INDEX_KEY: &[u8] = b"applied_index";
+const PEERS_KEY: &[u8] = b"peers";
+
+/// Index and term of the synthetic snapshot with which the initial members bootstrap the
+/// metadata store. Starting the log after this index ensures that replicas which join later
+/// always receive a snapshot containing the initial configuration first.
+const RAFT_INIT_INDEX: u64 = 1;
+const RAFT_INIT_TERM: u64 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("storage error: {0}")]
+    Storage(#[from] rocksdb::Error),
+    #[error("rocksdb error: {0}")]
+    RocksDb(#[from] RocksError),
+    #[error("protobuf error: {0}")]
+    Protobuf(#[from] protobuf::ProtobufError),
+    #[error("raft error: {0}")]
+    Raft(#[from] raft::Error),
+    #[error("encode error: {0}")]
+    Encode(#[from] StorageEncodeError),
+    #[error("decode error: {0}")]
+    Decode(#[from] StorageDecodeError),
+}
+
+impl From<Error> for raft::Error {
+    fn from(err: Error) -> Self {
+        raft::Error::Store(StorageError::Other(Box::new(err)))
+    }
+}
+
+/// The Raft peers which are known to a replica.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Peers(Vec<(u64, AdvertisedAddress)>);
+
+flexbuffers_storage_encode_decode!(Peers);
+
+impl From<&BTreeMap<u64, AdvertisedAddress>> for Peers {
+    fn from(value: &BTreeMap<u64, AdvertisedAddress>) -> Self {
+        Peers(
+            value
+                .iter()
+                .map(|(id, address)| (*id, address.clone()))
+                .collect(),
+        )
+    }
+}
+
+impl From<Peers> for BTreeMap<u64, AdvertisedAddress> {
+    fn from(value: Peers) -> Self {
+        value.0.into_iter().collect()
+    }
+}
+
+/// Payload of a Raft [`Snapshot`]. It contains the full state of the key-value pairs and the
+/// known peers at the snapshot index.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotData {
+    kv_pairs: Vec<(ByteString, VersionedValue)>,
+    peers: Peers,
+}
+
+flexbuffers_storage_encode_decode!(SnapshotData);
+
+/// RocksDB based storage of the Raft log and the replicated key-value pairs.
+///
+/// The key-value pairs represent the state machine of the Raft metadata store. Snapshots are
+/// created on demand from the key-value pairs at the applied index.
+pub struct RocksDbStorage {
+    db: Arc<DB>,
+    rocksdb: Arc<RocksDb>,
+    rocksdb_options: BoxedLiveLoad<RocksDbOptions>,
+
+    hard_state: HardState,
+    conf_state: ConfState,
+    snapshot_metadata: SnapshotMetadata,
+    applied_index: u64,
+    first_index: u64,
+    last_index: u64,
+
+    buffer: BytesMut,
+}
+
+impl RocksDbStorage {
+    pub async fn create(
+        options: &MetadataStoreOptions,
+        raft_options: &RaftOptions,
+        rocksdb_options: BoxedLiveLoad<RocksDbOptions>,
+    ) -> Result<Self, Error> {
+        // every replica needs its own db name since tests run multiple replicas in one process
+        let db_name = DbName::new(&format!("{DB_NAME}-{}", raft_options.id));
+        let db_manager = RocksDbManager::get();
+        let cfs = vec![
+            CfName::new(RAFT_LOG),
+            CfName::new(RAFT_METADATA),
+            CfName::new(KV_PAIRS),
+        ];
+        let db_spec = DbSpecBuilder::new(
+            db_name.clone(),
+            options.data_dir(),
+            rocksdb::Options::default(),
+        )
+        .add_cf_pattern(
+            CfPrefixPattern::ANY,
+            cf_options(options.rocksdb_memory_budget()),
+        )
+        .ensure_column_families(cfs)
+        .build()
+        .expect("valid spec");
+
+        let db = db_manager.open_db(rocksdb_options.clone(), db_spec).await?;
+        let rocksdb = db_manager
+            .get_db(db_name)
+            .expect("raft metadata store db is open");
+
+        let mut storage = Self {
+            db,
+            rocksdb,
+            rocksdb_options,
+            hard_state: HardState::default(),
+            conf_state: ConfState::default(),
+            snapshot_metadata: SnapshotMetadata::default(),
+            applied_index: 0,
+            first_index: 1,
+            last_index: 0,
+            buffer: BytesMut::default(),
+        };
+
+        storage.load_state()?;
+
+        Ok(storage)
+    }
+
+    fn load_state(&mut self) -> Result<(), Error> {
+        if let Some(hard_state) = self.get_metadata::<HardState>(HARD_STATE_KEY)? {
+            self.hard_state = hard_state;
+        }
+
+        if let Some(conf_state) = self.get_metadata::<ConfState>(CONF_STATE_KEY)? {
+            self.conf_state = conf_state;
+        }
+
+        if let Some(snapshot_metadata) =
+            self.get_metadata::<SnapshotMetadata>(SNAPSHOT_METADATA_KEY)?
+        {
+            self.snapshot_metadata = snapshot_metadata;
+        }
+
+        let applied_index = self
+            .db
+            .get_pinned_cf(&self.metadata_cf_handle(), APPLIED_INDEX_KEY)?
+            .map(|applied_index| decode_index(applied_index.as_ref()));
+        let last_index = self
+            .db
+            .iterator_cf(&self.log_cf_handle(), IteratorMode::End)
+            .next()
+            .transpose()?
+            .map(|(key, _)| decode_index(&key));
+
+        self.applied_index = applied_index.unwrap_or_default();
+        self.first_index = self.snapshot_metadata.index + 1;
+        self.last_index = last_index.unwrap_or(self.snapshot_metadata.index);
+
+        debug!(
+            first_index = self.first_index,
+            last_index = self.last_index,
+            applied_index = self.applied_index,
+            "Loaded raft storage"
+        );
+
+        Ok(())
+    }
+
+    /// Returns true if this replica has never been initialized, neither by bootstrapping it nor
+    /// by receiving a snapshot from another replica.
+    pub fn is_empty(&self) -> bool {
+        self.last_index == 0 && self.conf_state.voters.is_empty()
+    }
+
+    /// Bootstraps the storage with the initial Raft configuration. All initial members need to
+    /// bootstrap with the same configuration.
+    pub async fn bootstrap(
+        &mut self,
+        conf_state: ConfState,
+        peers: &BTreeMap<u64, AdvertisedAddress>,
+    ) -> Result<(), Error> {
+        let mut snapshot_metadata = SnapshotMetadata::default();
+        snapshot_metadata.index = RAFT_INIT_INDEX;
+        snapshot_metadata.term = RAFT_INIT_TERM;
+        snapshot_metadata.set_conf_state(conf_state.clone());
+
+        let mut hard_state = HardState::default();
+        hard_state.term = RAFT_INIT_TERM;
+        hard_state.commit = RAFT_INIT_INDEX;
+
+        let mut buffer = BytesMut::default();
+        StorageCodec::encode(&Peers::from(peers), &mut buffer)?;
+
+        let mut wb = WriteBatch::default();
+        let metadata_cf = self.metadata_cf_handle();
+        wb.put_cf(&metadata_cf, HARD_STATE_KEY, hard_state.write_to_bytes()?);
+        wb.put_cf(&metadata_cf, CONF_STATE_KEY, conf_state.write_to_bytes()?);
+        wb.put_cf(
+            &metadata_cf,
+            SNAPSHOT_METADATA_KEY,
+            snapshot_metadata.write_to_bytes()?,
+        );
+        wb.put_cf(
+            &metadata_cf,
+            APPLIED_INDEX_KEY,
+            RAFT_INIT_INDEX.to_be_bytes(),
+        );
+        wb.put_cf(&metadata_cf, PEERS_KEY, buffer.as_ref());
+        drop(metadata_cf);
+
+        self.commit_write_batch(wb).await?;
+
+        self.hard_state = hard_state;
+        self.conf_state = conf_state;
+        self.snapshot_metadata = snapshot_metadata;
+        self.applied_index = RAFT_INIT_INDEX;
+        self.first_index = RAFT_INIT_INDEX + 1;
+        self.last_index = RAFT_INIT_INDEX;
+
+        Ok(())
+    }
+
+    pub fn applied_index(&self) -> u64 {
+        self.applied_index
+    }
+
+    pub fn snapshot_index(&self) -> u64 {
+        self.snapshot_metadata.index
+    }
+
+    pub fn peers(&self) -> Result<BTreeMap<u64, AdvertisedAddress>, Error> {
+        Ok(self
+            .get_decoded::<Peers>(&self.metadata_cf_handle(), PEERS_KEY)?
+            .map(Into::into)
+            .unwrap_or_default())
+    }
+
+    pub async fn store_peers(
+        &mut self,
+        peers: &BTreeMap<u64, AdvertisedAddress>,
+    ) -> Result<(), Error> {
+        self.buffer.clear();
+        StorageCodec::encode(&Peers::from(peers), &mut self.buffer)?;
+        let mut wb = WriteBatch::default();
+        wb.put_cf(&self.metadata_cf_handle(), PEERS_KEY, self.buffer.as_ref());
+        self.commit_write_batch(wb).await
+    }
+
+    /// Appends the given entries to the log. Entries which conflict with the given entries are
+    /// removed from the log.
+    pub async fn append(&mut self, entries: &[Entry]) -> Result<(), Error> {
+        let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+            return Ok(());
+        };
+
+        assert!(
+            first.index >= self.first_index,
+            "cannot overwrite compacted raft log entries; first index {} < {}",
+            first.index,
+            self.first_index
+        );
+
+        let log_cf = self.log_cf_handle();
+        let mut wb = WriteBatch::default();
+
+        for entry in entries {
+            wb.put_cf(&log_cf, encode_index(entry.index), entry.write_to_bytes()?);
+        }
+
+        if last.index < self.last_index {
+            // remove the conflicting tail of the log
+            wb.delete_range_cf(
+                &log_cf,
+                encode_index(last.index + 1),
+                encode_index(self.last_index + 1),
+            );
+        }
+        drop(log_cf);
+
+        self.commit_write_batch(wb).await?;
+        self.last_index = last.index;
+
+        Ok(())
+    }
+
+    pub async fn store_hard_state(&mut self, hard_state: HardState) -> Result<(), Error> {
+        let mut wb = WriteBatch::default();
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            HARD_STATE_KEY,
+            hard_state.write_to_bytes()?,
+        );
+        self.commit_write_batch(wb).await?;
+        self.hard_state = hard_state;
+        Ok(())
+    }
+
+    pub async fn store_commit_index(&mut self, commit_index: u64) -> Result<(), Error> {
+        let mut hard_state = self.hard_state.clone();
+        hard_state.commit = commit_index;
+        self.store_hard_state(hard_state).await
+    }
+
+    pub async fn store_conf_state(&mut self, conf_state: ConfState) -> Result<(), Error> {
+        let mut wb = WriteBatch::default();
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            CONF_STATE_KEY,
+            conf_state.write_to_bytes()?,
+        );
+        self.commit_write_batch(wb).await?;
+        self.conf_state = conf_state;
+        Ok(())
+    }
+
+    pub async fn store_applied_index(&mut self, applied_index: u64) -> Result<(), Error> {
+        let mut wb = WriteBatch::default();
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            APPLIED_INDEX_KEY,
+            applied_index.to_be_bytes(),
+        );
+        self.commit_write_batch(wb).await?;
+        self.applied_index = applied_index;
+        Ok(())
+    }
+
+    pub fn get_value(&self, key: &ByteString) -> Result<Option<VersionedValue>, Error> {
+        self.get_decoded(&self.kv_cf_handle(), key)
+    }
+
+    /// Writes the key-value pair together with the applied index of the originating log entry.
+    pub async fn put_value(
+        &mut self,
+        key: &ByteString,
+        value: &VersionedValue,
+        applied_index: u64,
+    ) -> Result<(), Error> {
+        self.buffer.clear();
+        StorageCodec::encode(value, &mut self.buffer)?;
+
+        let mut wb = WriteBatch::default();
+        wb.put_cf(&self.kv_cf_handle(), key, self.buffer.as_ref());
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            APPLIED_INDEX_KEY,
+            applied_index.to_be_bytes(),
+        );
+        self.commit_write_batch(wb).await?;
+        self.applied_index = applied_index;
+        Ok(())
+    }
+
+    /// Deletes the key-value pair together with storing the applied index of the originating log
+    /// entry.
+    pub async fn delete_value(
+        &mut self,
+        key: &ByteString,
+        applied_index: u64,
+    ) -> Result<(), Error> {
+        let mut wb = WriteBatch::default();
+        wb.delete_cf(&self.kv_cf_handle(), key);
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            APPLIED_INDEX_KEY,
+            applied_index.to_be_bytes(),
+        );
+        self.commit_write_batch(wb).await?;
+        self.applied_index = applied_index;
+        Ok(())
+    }
+
+    /// Replaces the complete state of this replica with the given snapshot.
+    pub async fn apply_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        let snapshot_metadata = snapshot.get_metadata().clone();
+        let index = snapshot_metadata.index;
+
+        if index <= self.snapshot_metadata.index {
+            debug!(
+                "Ignoring snapshot at index {} since it is older than the current snapshot at index {}",
+                index, self.snapshot_metadata.index
+            );
+            return Ok(());
+        }
+
+        let snapshot_data = StorageCodec::decode::<SnapshotData, _>(&mut snapshot.get_data())?;
+        let mut buffer = BytesMut::default();
+
+        let log_cf = self.log_cf_handle();
+        let metadata_cf = self.metadata_cf_handle();
+        let kv_cf = self.kv_cf_handle();
+        let mut wb = WriteBatch::default();
+
+        // The snapshot replaces the whole state. We need to keep the hard state's vote though.
+        wb.delete_range_cf(
+            &log_cf,
+            encode_index(self.first_index),
+            encode_index(self.last_index.max(index) + 1),
+        );
+        for kv_pair in self.db.iterator_cf(&kv_cf, IteratorMode::Start) {
+            let (key, _) = kv_pair?;
+            wb.delete_cf(&kv_cf, key);
+        }
+        for (key, value) in &snapshot_data.kv_pairs {
+            buffer.clear();
+            StorageCodec::encode(value, &mut buffer)?;
+            wb.put_cf(&kv_cf, key, buffer.as_ref());
+        }
+
+        let mut hard_state = self.hard_state.clone();
+        hard_state.term = hard_state.term.max(snapshot_metadata.term);
+        hard_state.commit = index;
+
+        wb.put_cf(&metadata_cf, HARD_STATE_KEY, hard_state.write_to_bytes()?);
+        wb.put_cf(
+            &metadata_cf,
+            CONF_STATE_KEY,
+            snapshot_metadata.get_conf_state().write_to_bytes()?,
+        );
+        wb.put_cf(
+            &metadata_cf,
+            SNAPSHOT_METADATA_KEY,
+            snapshot_metadata.write_to_bytes()?,
+        );
+        wb.put_cf(&metadata_cf, APPLIED_INDEX_KEY, index.to_be_bytes());
+        buffer.clear();
+        StorageCodec::encode(&snapshot_data.peers, &mut buffer)?;
+        wb.put_cf(&metadata_cf, PEERS_KEY, buffer.as_ref());
+
+        drop(log_cf);
+        drop(metadata_cf);
+        drop(kv_cf);
+
+        self.commit_write_batch(wb).await?;
+
+        self.hard_state = hard_state;
+        self.conf_state = snapshot_metadata.get_conf_state().clone();
+        self.snapshot_metadata = snapshot_metadata;
+        self.applied_index = index;
+        self.first_index = index + 1;
+        self.last_index = index;
+
+        Ok(())
+    }
+
+    /// Truncates the log up to and including the applied index. Afterwards, replicas which need
+    /// older entries will receive a snapshot of the key-value pairs instead.
+    pub async fn compact(&mut self) -> Result<(), Error> {
+        let compact_index = self.applied_index;
+
+        if compact_index <= self.snapshot_metadata.index {
+            return Ok(());
+        }
+
+        let mut snapshot_metadata = SnapshotMetadata::default();
+        snapshot_metadata.index = compact_index;
+        snapshot_metadata.term = raft::Storage::term(self, compact_index)?;
+        snapshot_metadata.set_conf_state(self.conf_state.clone());
+
+        let mut wb = WriteBatch::default();
+        wb.delete_range_cf(
+            &self.log_cf_handle(),
+            encode_index(self.first_index),
+            encode_index(compact_index + 1),
+        );
+        wb.put_cf(
+            &self.metadata_cf_handle(),
+            SNAPSHOT_METADATA_KEY,
+            snapshot_metadata.write_to_bytes()?,
+        );
+        self.commit_write_batch(wb).await?;
+
+        debug!(
+            "Compacted raft log from index {} up to index {}",
+            self.first_index, compact_index
+        );
+
+        self.snapshot_metadata = snapshot_metadata;
+        self.first_index = compact_index + 1;
+        self.last_index = self.last_index.max(compact_index);
+
+        Ok(())
+    }
+
+    fn create_snapshot(&self) -> Result<Snapshot, Error> {
+        let kv_pairs = self
+            .db
+            .iterator_cf(&self.kv_cf_handle(), IteratorMode::Start)
+            .map(|kv_pair| {
+                let (key, value) = kv_pair?;
+                let key = ByteString::try_from(bytes::Bytes::copy_from_slice(&key))
+                    .map_err(|err| StorageDecodeError::DecodeValue(err.into()))?;
+                let value = StorageCodec::decode::<VersionedValue, _>(&mut value.as_ref())?;
+                Ok::<_, Error>((key, value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let snapshot_data = SnapshotData {
+            kv_pairs,
+            peers: Peers::from(&self.peers()?),
+        };
+
+        let mut buffer = BytesMut::default();
+        StorageCodec::encode(&snapshot_data, &mut buffer)?;
+
+        let mut snapshot = Snapshot::default();
+        let metadata = snapshot.mut_metadata();
+        metadata.index = self.applied_index;
+        metadata.term = raft::Storage::term(self, self.applied_index)?;
+        metadata.set_conf_state(self.conf_state.clone());
+        snapshot.set_data(buffer.to_vec().into());
+
+        Ok(snapshot)
+    }
+
+    async fn commit_write_batch(&mut self, write_batch: WriteBatch) -> Result<(), Error> {
+        let write_options = self.write_options();
+        self.rocksdb
+            .write_batch(
+                "raft-metadata-write-batch",
+                Priority::High,
+                IoMode::default(),
+                write_options,
+                write_batch,
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn write_options(&mut self) -> WriteOptions {
+        let opts = self.rocksdb_options.live_load();
+        let mut write_opts = WriteOptions::default();
+
+        write_opts.disable_wal(opts.rocksdb_disable_wal());
+
+        if !opts.rocksdb_disable_wal() {
+            // always sync if we have wal enabled
+            write_opts.set_sync(true);
+        }
+
+        write_opts
+    }
+
+    fn get_metadata<T: ProtobufMessage>(&self, key: &[u8]) -> Result<Option<T>, Error> {
+        let slice = self.db.get_pinned_cf(&self.metadata_cf_handle(), key)?;
+
+        if let Some(bytes) = slice {
+            Ok(Some(T::parse_from_bytes(bytes.as_ref())?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_decoded<T: StorageDecode>(
+        &self,
+        cf_handle: &Arc<BoundColumnFamily>,
+        key: impl AsRef<[u8]>,
+    ) -> Result<Option<T>, Error> {
+        let slice = self.db.get_pinned_cf(cf_handle, key)?;
+
+        if let Some(bytes) = slice {
+            Ok(Some(StorageCodec::decode(&mut bytes.as_ref())?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_entry(&self, index: u64) -> Result<Entry, Error> {
+        let slice = self
+            .db
+            .get_pinned_cf(&self.log_cf_handle(), encode_index(index))?
+            .unwrap_or_else(|| panic!("raft log entry with index {index} to exist"));
+        Ok(Entry::parse_from_bytes(slice.as_ref())?)
+    }
+
+    fn log_cf_handle(&self) -> Arc<BoundColumnFamily> {
+        self.db
+            .cf_handle(RAFT_LOG)
+            .expect("RAFT_LOG column family exists")
+    }
+
+    fn metadata_cf_handle(&self) -> Arc<BoundColumnFamily> {
+        self.db
+            .cf_handle(RAFT_METADATA)
+            .expect("RAFT_METADATA column family exists")
+    }
+
+    fn kv_cf_handle(&self) -> Arc<BoundColumnFamily> {
+        self.db
+            .cf_handle(KV_PAIRS)
+            .expect("KV_PAIRS column family exists")
+    }
+}
+
+impl raft::Storage for RocksDbStorage {
+    fn initial_state(&self) -> raft::Result<RaftState> {
+        Ok(RaftState::new(
+            self.hard_state.clone(),
+            self.conf_state.clone(),
+        ))
+    }
+
+    fn entries(
+        &self,
+        low: u64,
+        high: u64,
+        max_size: impl Into<Option<u64>>,
+        _context: GetEntriesContext,
+    ) -> raft::Result<Vec<Entry>> {
+        if low < self.first_index {
+            return Err(raft::Error::Store(StorageError::Compacted));
+        }
+
+        if high > self.last_index + 1 {
+            panic!(
+                "index out of bound (last: {}, high: {})",
+                self.last_index + 1,
+                high
+            );
+        }
+
+        let mut entries = Vec::with_capacity((high - low) as usize);
+        for index in low..high {
+            entries.push(self.get_entry(index)?);
+        }
+
+        raft::util::limit_size(&mut entries, max_size.into());
+
+        Ok(entries)
+    }
+
+    fn term(&self, idx: u64) -> raft::Result<u64> {
+        if idx == self.snapshot_metadata.index {
+            return Ok(self.snapshot_metadata.term);
+        }
+
+        if idx < self.first_index {
+            return Err(raft::Error::Store(StorageError::Compacted));
+        }
+
+        if idx > self.last_index {
+            return Err(raft::Error::Store(StorageError::Unavailable));
+        }
+
+        Ok(self.get_entry(idx)?.term)
+    }
+
+    fn first_index(&self) -> raft::Result<u64> {
+        Ok(self.first_index)
+    }
+
+    fn last_index(&self) -> raft::Result<u64> {
+        Ok(self.last_index)
+    }
+
+    fn snapshot(&self, request_index: u64, _to: u64) -> raft::Result<Snapshot> {
+        if self.applied_index < request_index {
+            return Err(raft::Error::Store(
+                StorageError::SnapshotTemporarilyUnavailable,
+            ));
+        }
+
+        Ok(self.create_snapshot()?)
+    }
+}
+
+fn encode_index(index: u64) -> [u8; 8] {
+    index.to_be_bytes()
+}
+
+fn decode_index(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes.try_into().expect("index to be encoded as u64"))
+}
+
+fn cf_options(
+    memory_budget: usize,
+) -> impl Fn(rocksdb::Options) -> rocksdb::Options + Send + Sync + 'static {
+    move |mut opts| {
+        // We set the budget to allow 1 mutable + 3 immutable.
+        opts.set_write_buffer_size(memory_budget / 4);
+        opts.set_min_write_buffer_number_to_merge(2);
+        opts.set_max_write_buffer_number(4);
+        opts.set_compaction_style(rocksdb::DBCompactionStyle::Level);
+        opts.set_num_levels(3);
+
+        opts.set_compression_per_level(&[
+            DBCompressionType::None,
+            DBCompressionType::None,
+            DBCompressionType::Zstd,
+        ]);
+
+        opts
+    }
+}
@@ -0,0 +1,788 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use bytes::BytesMut;
+use bytestring::ByteString;
+use protobuf::{Message as ProtobufMessage, ProtobufError};
+use raft::prelude::{ConfChange, ConfChangeType, ConfState, Entry, EntryType, Message, Snapshot};
+use raft::{RawNode, ReadState, INVALID_ID};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, info, trace, warn};
+
+use restate_core::cancellation_watcher;
+use restate_core::metadata_store::{Precondition, VersionedValue};
+use restate_core::network::TlsError;
+use restate_types::config::{MetadataStoreOptions, RaftOptions, RocksDbOptions};
+use restate_types::flexbuffers_storage_encode_decode;
+use restate_types::live::BoxedLiveLoad;
+use restate_types::net::AdvertisedAddress;
+use restate_types::storage::{StorageCodec, StorageDecodeError};
+use restate_types::Version;
+
+use crate::local::store::{
+    Error as RequestError, MetadataStoreRequest, RequestReceiver, RequestSender,
+};
+use crate::raft::network::Networking;
+use crate::raft::storage::{self, RocksDbStorage};
+
+pub type RaftSender = mpsc::Sender<Message>;
+pub type RaftReceiver = mpsc::Receiver<Message>;
+pub type MembershipRequestSender = mpsc::Sender<MembershipRequest>;
+pub type MembershipRequestReceiver = mpsc::Receiver<MembershipRequest>;
+
+type RequestResult<T> = std::result::Result<T, RequestError>;
+
+#[derive(Debug)]
+pub enum MembershipRequest {
+    AddNode {
+        id: u64,
+        address: AdvertisedAddress,
+        result_tx: oneshot::Sender<RequestResult<()>>,
+    },
+    RemoveNode {
+        id: u64,
+        result_tx: oneshot::Sender<RequestResult<()>>,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("raft error: {0}")]
+    Raft(#[from] raft::Error),
+    #[error(transparent)]
+    Storage(#[from] storage::Error),
+    #[error("protobuf error: {0}")]
+    Protobuf(#[from] ProtobufError),
+    #[error("decode error: {0}")]
+    Decode(#[from] StorageDecodeError),
+    #[error("failed loading the peer TLS configuration: {0}")]
+    Tls(#[from] TlsError),
+}
+
+/// Write request which is replicated via the Raft log.
+#[derive(Debug, Serialize, Deserialize)]
+enum WriteRequest {
+    Put {
+        key: ByteString,
+        value: VersionedValue,
+        precondition: Precondition,
+    },
+    Delete {
+        key: ByteString,
+        precondition: Precondition,
+    },
+}
+
+flexbuffers_storage_encode_decode!(WriteRequest);
+
+#[derive(Debug)]
+enum ReadRequest {
+    Get {
+        key: ByteString,
+        result_tx: oneshot::Sender<RequestResult<Option<VersionedValue>>>,
+    },
+    GetVersion {
+        key: ByteString,
+        result_tx: oneshot::Sender<RequestResult<Option<Version>>>,
+    },
+}
+
+impl ReadRequest {
+    fn fail(self, err: RequestError) {
+        match self {
+            ReadRequest::Get { result_tx, .. } => {
+                let _ = result_tx.send(Err(err));
+            }
+            ReadRequest::GetVersion { result_tx, .. } => {
+                let _ = result_tx.send(Err(err));
+            }
+        }
+    }
+}
+
+/// Request waiting for Raft to commit or serve it. Proposals and read index requests which a
+/// follower forwards to the leader can be lost, and Raft doesn't retransmit them. Requests
+/// therefore fail as unavailable once their deadline passed.
+struct PendingRequest<T> {
+    request: T,
+    deadline: Instant,
+}
+
+/// Metadata store which replicates the key-value pairs via Raft.
+///
+/// Writes are appended to the Raft log and applied once they are committed. Reads use Raft's
+/// read index mechanism to guarantee linearizability. Every replica can serve requests; requests
+/// received by followers are forwarded to the leader by Raft. The replica which received a
+/// request responds once it has applied the corresponding log entry.
+pub struct RaftMetadataStore {
+    id: u64,
+    raw_node: RawNode<RocksDbStorage>,
+    networking: Networking,
+    peers: BTreeMap<u64, AdvertisedAddress>,
+    tick_interval: Duration,
+    snapshot_interval: u64,
+    request_timeout: Duration,
+    leader_id: u64,
+
+    request_rx: RequestReceiver,
+    raft_rx: RaftReceiver,
+    membership_rx: MembershipRequestReceiver,
+
+    next_request_id: u64,
+    pending_writes: HashMap<u64, PendingRequest<oneshot::Sender<RequestResult<()>>>>,
+    pending_reads: HashMap<u64, PendingRequest<ReadRequest>>,
+    // reads which wait for the given index to be applied
+    ready_reads: Vec<(u64, PendingRequest<ReadRequest>)>,
+    pending_conf_change: Option<u64>,
+
+    // for creating other senders
+    request_tx: RequestSender,
+    raft_tx: RaftSender,
+    membership_tx: MembershipRequestSender,
+}
+
+impl RaftMetadataStore {
+    pub async fn create(
+        options: &MetadataStoreOptions,
+        raft_options: &RaftOptions,
+        rocksdb_options: BoxedLiveLoad<RocksDbOptions>,
+    ) -> Result<Self, Error> {
+        let (request_tx, request_rx) = mpsc::channel(options.request_queue_length());
+        let (raft_tx, raft_rx) = mpsc::channel(options.request_queue_length());
+        let (membership_tx, membership_rx) = mpsc::channel(1);
+
+        let id = raft_options.id.get();
+        let mut storage = RocksDbStorage::create(options, raft_options, rocksdb_options).await?;
+
+        if storage.is_empty() && !raft_options.peers.is_empty() {
+            let initial_peers: BTreeMap<_, _> = raft_options
+                .peers
+                .iter()
+                .map(|peer| (peer.id.get(), peer.address.clone()))
+                .collect();
+
+            info!(
+                "Bootstrapping raft metadata store with initial members {:?}",
+                initial_peers.keys()
+            );
+
+            let mut conf_state = ConfState::default();
+            conf_state.voters = initial_peers.keys().copied().collect();
+            storage.bootstrap(conf_state, &initial_peers).await?;
+        }
+
+        let peers = storage.peers()?;
+
+        let config = raft::Config {
+            id,
+            election_tick: raft_options.raft_election_tick.get(),
+            heartbeat_tick: raft_options.raft_heartbeat_tick.get(),
+            applied: storage.applied_index(),
+            check_quorum: true,
+            pre_vote: true,
+            ..Default::default()
+        };
+        config.validate()?;
+
+        let logger = slog::Logger::root(TracingDrain, slog::o!("raft_id" => id));
+        let raw_node = RawNode::new(&config, storage, &logger)?;
+
+        let mut networking = Networking::new(id, raft_options.peer_tls.as_ref())?;
+        networking.update_peers(&peers);
+
+        Ok(Self {
+            id,
+            raw_node,
+            networking,
+            peers,
+            tick_interval: raft_options.raft_tick_interval.into(),
+            snapshot_interval: raft_options.snapshot_interval.get(),
+            request_timeout: raft_options.request_timeout.into(),
+            leader_id: INVALID_ID,
+            request_rx,
+            raft_rx,
+            membership_rx,
+            next_request_id: rand::random(),
+            pending_writes: HashMap::default(),
+            pending_reads: HashMap::default(),
+            ready_reads: Vec::default(),
+            pending_conf_change: None,
+            request_tx,
+            raft_tx,
+            membership_tx,
+        })
+    }
+
+    pub fn request_sender(&self) -> RequestSender {
+        self.request_tx.clone()
+    }
+
+    pub fn raft_sender(&self) -> RaftSender {
+        self.raft_tx.clone()
+    }
+
+    pub fn membership_request_sender(&self) -> MembershipRequestSender {
+        self.membership_tx.clone()
+    }
+
+    pub async fn run(mut self) -> Result<(), Error> {
+        debug!(raft_id = self.id, "Running RaftMetadataStore");
+
+        let mut tick_interval = tokio::time::interval(self.tick_interval);
+        tick_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                message = self.raft_rx.recv() => {
+                    let message = message.expect("receiver should not be closed since we own one clone.");
+                    if let Err(err) = self.raw_node.step(message) {
+                        debug!("Failed to step raft message: {err}");
+                    }
+                }
+                request = self.request_rx.recv() => {
+                    let request = request.expect("receiver should not be closed since we own one clone.");
+                    self.handle_request(request);
+                }
+                request = self.membership_rx.recv() => {
+                    let request = request.expect("receiver should not be closed since we own one clone.");
+                    self.handle_membership_request(request);
+                }
+                _ = tick_interval.tick() => {
+                    self.raw_node.tick();
+                    self.fail_expired_requests();
+                }
+                _ = cancellation_watcher() => {
+                    break;
+                },
+            }
+
+            self.on_ready().await?;
+        }
+
+        debug!(raft_id = self.id, "Stopped RaftMetadataStore");
+
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: MetadataStoreRequest) {
+        trace!("Handle request '{:?}'", request);
+
+        match request {
+            MetadataStoreRequest::Get { key, result_tx } => {
+                self.read(ReadRequest::Get { key, result_tx });
+            }
+            MetadataStoreRequest::GetVersion { key, result_tx } => {
+                self.read(ReadRequest::GetVersion { key, result_tx });
+            }
+            MetadataStoreRequest::Put {
+                key,
+                value,
+                precondition,
+                result_tx,
+            } => {
+                self.write(
+                    WriteRequest::Put {
+                        key,
+                        value,
+                        precondition,
+                    },
+                    result_tx,
+                );
+            }
+            MetadataStoreRequest::Delete {
+                key,
+                precondition,
+                result_tx,
+            } => {
+                self.write(WriteRequest::Delete { key, precondition }, result_tx);
+            }
+        }
+    }
+
+    fn read(&mut self, request: ReadRequest) {
+        if self.leader_id == INVALID_ID {
+            // Raft silently drops read index requests if there is no known leader
+            request.fail(RequestError::Unavailable("no known leader".to_owned()));
+            return;
+        }
+
+        let request_id = self.next_request_id();
+        self.raw_node.read_index(request_id.to_be_bytes().to_vec());
+        let request = self.pending_request(request);
+        self.pending_reads.insert(request_id, request);
+    }
+
+    fn write(&mut self, request: WriteRequest, result_tx: oneshot::Sender<RequestResult<()>>) {
+        let mut buffer = BytesMut::default();
+        if let Err(err) = StorageCodec::encode(&request, &mut buffer) {
+            let _ = result_tx.send(Err(RequestError::Encode(err)));
+            return;
+        }
+
+        let request_id = self.next_request_id();
+        if let Err(err) = self
+            .raw_node
+            .propose(request_id.to_be_bytes().to_vec(), buffer.to_vec())
+        {
+            let _ = result_tx.send(Err(RequestError::Unavailable(err.to_string())));
+            return;
+        }
+
+        let result_tx = self.pending_request(result_tx);
+        self.pending_writes.insert(request_id, result_tx);
+    }
+
+    fn handle_membership_request(&mut self, request: MembershipRequest) {
+        let (conf_change, result_tx) = match request {
+            MembershipRequest::AddNode {
+                id,
+                address,
+                result_tx,
+            } => {
+                let mut conf_change = ConfChange::default();
+                conf_change.set_change_type(ConfChangeType::AddNode);
+                conf_change.node_id = id;
+                conf_change.set_context(address.to_string().into_bytes().into());
+                (conf_change, result_tx)
+            }
+            MembershipRequest::RemoveNode { id, result_tx } => {
+                let mut conf_change = ConfChange::default();
+                conf_change.set_change_type(ConfChangeType::RemoveNode);
+                conf_change.node_id = id;
+                (conf_change, result_tx)
+            }
+        };
+
+        // Raft only accepts a single pending configuration change and silently turns further
+        // proposals into empty entries. Reject them right away to not leave callers hanging.
+        if self.pending_conf_change.is_some() {
+            let _ = result_tx.send(Err(RequestError::Unavailable(
+                "another membership change is in progress".to_owned(),
+            )));
+            return;
+        }
+
+        let request_id = self.next_request_id();
+        if let Err(err) = self
+            .raw_node
+            .propose_conf_change(request_id.to_be_bytes().to_vec(), conf_change)
+        {
+            let _ = result_tx.send(Err(RequestError::Unavailable(err.to_string())));
+            return;
+        }
+
+        self.pending_conf_change = Some(request_id);
+        let result_tx = self.pending_request(result_tx);
+        self.pending_writes.insert(request_id, result_tx);
+    }
+
+    async fn on_ready(&mut self) -> Result<(), Error> {
+        if !self.raw_node.has_ready() {
+            return Ok(());
+        }
+
+        let mut ready = self.raw_node.ready();
+
+        if let Some(soft_state) = ready.ss() {
+            let leader_id = soft_state.leader_id;
+            self.on_leader_change(leader_id);
+        }
+
+        if !ready.messages().is_empty() {
+            self.networking.send(ready.take_messages());
+        }
+
+        if *ready.snapshot() != Snapshot::default() {
+            self.apply_snapshot(ready.snapshot()).await?;
+        }
+
+        self.apply_committed_entries(ready.take_committed_entries())
+            .await?;
+
+        if !ready.entries().is_empty() {
+            self.raw_node.mut_store().append(ready.entries()).await?;
+        }
+
+        if let Some(hard_state) = ready.hs() {
+            self.raw_node
+                .mut_store()
+                .store_hard_state(hard_state.clone())
+                .await?;
+        }
+
+        if !ready.persisted_messages().is_empty() {
+            self.networking.send(ready.take_persisted_messages());
+        }
+
+        for read_state in ready.take_read_states() {
+            self.on_read_state(read_state);
+        }
+
+        let mut light_ready = self.raw_node.advance(ready);
+
+        if let Some(commit_index) = light_ready.commit_index() {
+            self.raw_node
+                .mut_store()
+                .store_commit_index(commit_index)
+                .await?;
+        }
+
+        self.networking.send(light_ready.take_messages());
+        self.apply_committed_entries(light_ready.take_committed_entries())
+            .await?;
+        self.raw_node.advance_apply();
+
+        self.serve_ready_reads()?;
+        self.maybe_compact().await?;
+
+        Ok(())
+    }
+
+    fn on_leader_change(&mut self, leader_id: u64) {
+        if self.leader_id == leader_id {
+            return;
+        }
+
+        debug!(
+            raft_id = self.id,
+            "Raft leader changed from {} to {}", self.leader_id, leader_id
+        );
+        self.leader_id = leader_id;
+
+        // Requests which have not been committed yet might have been dropped by the previous
+        // leader. Since we cannot know their outcome, we fail them and let the callers retry.
+        for (_, pending) in self.pending_writes.drain() {
+            let _ = pending.request.send(Err(RequestError::Unavailable(
+                "leadership changed while processing request".to_owned(),
+            )));
+        }
+        self.pending_conf_change = None;
+
+        for (_, pending) in self.pending_reads.drain() {
+            pending.request.fail(RequestError::Unavailable(
+                "leadership changed while processing request".to_owned(),
+            ));
+        }
+    }
+
+    fn on_read_state(&mut self, read_state: ReadState) {
+        let Some(request_id) = decode_request_id(&read_state.request_ctx) else {
+            return;
+        };
+
+        if let Some(request) = self.pending_reads.remove(&request_id) {
+            self.ready_reads.push((read_state.index, request));
+        }
+    }
+
+    fn serve_ready_reads(&mut self) -> Result<(), Error> {
+        let applied_index = self.raw_node.store().applied_index();
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.ready_reads)
+            .into_iter()
+            .partition(|(index, _)| *index <= applied_index);
+        self.ready_reads = waiting;
+
+        for (_, pending) in ready {
+            match pending.request {
+                ReadRequest::Get { key, result_tx } => {
+                    let result = self.raw_node.store().get_value(&key)?;
+                    let _ = result_tx.send(Ok(result));
+                }
+                ReadRequest::GetVersion { key, result_tx } => {
+                    let result = self.raw_node.store().get_value(&key)?;
+                    let _ = result_tx.send(Ok(result.map(|value| value.version)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn apply_snapshot(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+        debug!(
+            raft_id = self.id,
+            "Applying snapshot at index {}",
+            snapshot.get_metadata().index
+        );
+
+        let storage = self.raw_node.mut_store();
+        storage.apply_snapshot(snapshot).await?;
+        self.peers = storage.peers()?;
+        self.networking.update_peers(&self.peers);
+
+        Ok(())
+    }
+
+    async fn apply_committed_entries(&mut self, entries: Vec<Entry>) -> Result<(), Error> {
+        for entry in entries {
+            if entry.get_data().is_empty() {
+                // empty entries are appended by new leaders
+                self.raw_node
+                    .mut_store()
+                    .store_applied_index(entry.index)
+                    .await?;
+                continue;
+            }
+
+            match entry.get_entry_type() {
+                EntryType::EntryNormal => self.apply_write_request(&entry).await?,
+                EntryType::EntryConfChange => self.apply_conf_change(&entry).await?,
+                EntryType::EntryConfChangeV2 => {
+                    unreachable!("raft metadata store does not propose ConfChangeV2 entries")
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn apply_write_request(&mut self, entry: &Entry) -> Result<(), Error> {
+        let request = StorageCodec::decode::<WriteRequest, _>(&mut entry.get_data())?;
+        let storage = self.raw_node.mut_store();
+
+        let result = match request {
+            WriteRequest::Put {
+                key,
+                value,
+                precondition,
+            } => {
+                let current_version = storage.get_value(&key)?.map(|value| value.version);
+                match check_precondition(current_version, precondition) {
+                    Ok(()) => {
+                        storage.put_value(&key, &value, entry.index).await?;
+                        Ok(())
+                    }
+                    Err(err) => {
+                        storage.store_applied_index(entry.index).await?;
+                        Err(err)
+                    }
+                }
+            }
+            WriteRequest::Delete { key, precondition } => {
+                let current_version = storage.get_value(&key)?.map(|value| value.version);
+                match check_precondition(current_version, precondition) {
+                    Ok(()) => {
+                        storage.delete_value(&key, entry.index).await?;
+                        Ok(())
+                    }
+                    Err(err) => {
+                        storage.store_applied_index(entry.index).await?;
+                        Err(err)
+                    }
+                }
+            }
+        };
+
+        self.respond(entry, result);
+
+        Ok(())
+    }
+
+    async fn apply_conf_change(&mut self, entry: &Entry) -> Result<(), Error> {
+        let conf_change = ConfChange::parse_from_bytes(entry.get_data())?;
+
+        let result = match self.raw_node.apply_conf_change(&conf_change) {
+            Ok(conf_state) => {
+                self.raw_node
+                    .mut_store()
+                    .store_conf_state(conf_state)
+                    .await?;
+
+                match conf_change.get_change_type() {
+                    ConfChangeType::AddNode | ConfChangeType::AddLearnerNode => {
+                        match parse_address(conf_change.get_context()) {
+                            Some(address) => {
+                                info!(
+                                    raft_id = self.id,
+                                    "Added node {} with address {} to the raft configuration",
+                                    conf_change.node_id,
+                                    address
+                                );
+                                self.peers.insert(conf_change.node_id, address);
+                            }
+                            None => warn!(
+                                raft_id = self.id,
+                                "Added node {} without a valid address to the raft configuration",
+                                conf_change.node_id
+                            ),
+                        }
+                    }
+                    ConfChangeType::RemoveNode => {
+                        info!(
+                            raft_id = self.id,
+                            "Removed node {} from the raft configuration", conf_change.node_id
+                        );
+                        self.peers.remove(&conf_change.node_id);
+                    }
+                }
+
+                let storage = self.raw_node.mut_store();
+                storage.store_peers(&self.peers).await?;
+                self.networking.update_peers(&self.peers);
+                Ok(())
+            }
+            Err(err) => Err(RequestError::Internal(err.to_string())),
+        };
+
+        self.raw_node
+            .mut_store()
+            .store_applied_index(entry.index)
+            .await?;
+
+        if let Some(request_id) = decode_request_id(entry.get_context()) {
+            if self.pending_conf_change == Some(request_id) {
+                self.pending_conf_change = None;
+            }
+        }
+
+        self.respond(entry, result);
+
+        Ok(())
+    }
+
+    fn respond(&mut self, entry: &Entry, result: RequestResult<()>) {
+        if let Some(request_id) = decode_request_id(entry.get_context()) {
+            if let Some(pending) = self.pending_writes.remove(&request_id) {
+                let _ = pending.request.send(result);
+            }
+        }
+    }
+
+    async fn maybe_compact(&mut self) -> Result<(), Error> {
+        let storage = self.raw_node.mut_store();
+
+        if storage.applied_index() - storage.snapshot_index() >= self.snapshot_interval {
+            storage.compact().await?;
+        }
+
+        Ok(())
+    }
+
+    fn pending_request<T>(&self, request: T) -> PendingRequest<T> {
+        PendingRequest {
+            request,
+            deadline: Instant::now() + self.request_timeout,
+        }
+    }
+
+    /// Fails the requests whose deadline passed. They might still be committed later on, which
+    /// callers need to account for like for any other unavailable error.
+    fn fail_expired_requests(&mut self) {
+        let now = Instant::now();
+
+        let expired_writes: Vec<_> = self
+            .pending_writes
+            .iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(request_id, _)| *request_id)
+            .collect();
+        for request_id in expired_writes {
+            if let Some(pending) = self.pending_writes.remove(&request_id) {
+                let _ = pending.request.send(Err(request_timed_out()));
+            }
+            if self.pending_conf_change == Some(request_id) {
+                self.pending_conf_change = None;
+            }
+        }
+
+        let expired_reads: Vec<_> = self
+            .pending_reads
+            .iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(request_id, _)| *request_id)
+            .collect();
+        for request_id in expired_reads {
+            if let Some(pending) = self.pending_reads.remove(&request_id) {
+                pending.request.fail(request_timed_out());
+            }
+        }
+
+        let (expired, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.ready_reads)
+            .into_iter()
+            .partition(|(_, pending)| pending.deadline <= now);
+        self.ready_reads = waiting;
+        for (_, pending) in expired {
+            pending.request.fail(request_timed_out());
+        }
+    }
+
+    fn next_request_id(&mut self) -> u64 {
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        request_id
+    }
+}
+
+fn check_precondition(
+    current_version: Option<Version>,
+    precondition: Precondition,
+) -> RequestResult<()> {
+    match precondition {
+        Precondition::None => Ok(()),
+        Precondition::DoesNotExist => {
+            if current_version.is_none() {
+                Ok(())
+            } else {
+                Err(RequestError::kv_pair_exists())
+            }
+        }
+        Precondition::MatchesVersion(version) => {
+            if current_version == Some(version) {
+                Ok(())
+            } else {
+                Err(RequestError::version_mismatch(version, current_version))
+            }
+        }
+    }
+}
+
+fn request_timed_out() -> RequestError {
+    RequestError::Unavailable("request timed out".to_owned())
+}
+
+fn decode_request_id(bytes: &[u8]) -> Option<u64> {
+    bytes.try_into().ok().map(u64::from_be_bytes)
+}
+
+fn parse_address(bytes: &[u8]) -> Option<AdvertisedAddress> {
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
+
+/// Forwards the log records of the raft library to tracing.
+struct TracingDrain;
+
+impl slog::Drain for TracingDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(
+        &self,
+        record: &slog::Record<'_>,
+        _values: &slog::OwnedKVList,
+    ) -> Result<Self::Ok, Self::Err> {
+        match record.level() {
+            slog::Level::Critical | slog::Level::Error => {
+                tracing::error!(target: "raft", "{}", record.msg())
+            }
+            slog::Level::Warning => tracing::warn!(target: "raft", "{}", record.msg()),
+            slog::Level::Info | slog::Level::Debug => {
+                tracing::debug!(target: "raft", "{}", record.msg())
+            }
+            slog::Level::Trace => tracing::trace!(target: "raft", "{}", record.msg()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,580 @@
+// Copyright (c) 2024 - Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::num::NonZeroU64;
+use std::time::Duration;
+
+use bytestring::ByteString;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use test_log::test;
+
+use restate_core::network::FailingConnector;
+use restate_core::{TaskCenter, TaskId, TaskKind, TestCoreEnv, TestCoreEnvBuilder};
+use restate_rocksdb::RocksDbManager;
+use restate_types::config::{
+    Configuration, MetadataStoreKind, MetadataStoreOptions, RaftOptions, RaftPeer,
+};
+use restate_types::health::HealthStatus;
+use restate_types::live::Live;
+use restate_types::net::{AdvertisedAddress, BindAddress};
+use restate_types::protobuf::common::MetadataServerStatus;
+use restate_types::retries::RetryPolicy;
+use restate_types::{flexbuffers_storage_encode_decode, Version, Versioned};
+
+use crate::grpc_svc::raft_metadata_store_svc_client::RaftMetadataStoreSvcClient;
+use crate::grpc_svc::{AddNodeRequest, RemoveNodeRequest};
+use crate::local::grpc::client::LocalMetadataStoreClient;
+use crate::raft::RaftMetadataStoreService;
+use crate::{MetadataStoreClient, Precondition, ReadError, WriteError};
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Serialize, Deserialize)]
+struct Value {
+    version: Version,
+    value: String,
+}
+
+impl Versioned for Value {
+    fn version(&self) -> Version {
+        self.version
+    }
+}
+
+flexbuffers_storage_encode_decode!(Value);
+
+struct Replica {
+    id: u64,
+    address: AdvertisedAddress,
+    client: MetadataStoreClient,
+    task_id: TaskId,
+}
+
+/// Writes through one replica and reads the written values through all replicas.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn replicated_metadata_store_operations() -> anyhow::Result<()> {
+    let (replicas, env) = create_test_environment(3).await?;
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            let key: ByteString = "key".into();
+            let value = Value {
+                version: Version::MIN,
+                value: "test_value".to_owned(),
+            };
+
+            put_with_retry(
+                &replicas[0].client,
+                &key,
+                &value,
+                Precondition::DoesNotExist,
+            )
+            .await?;
+
+            for replica in &replicas {
+                assert_eq!(
+                    get_with_retry::<Value>(&replica.client, &key).await?,
+                    Some(value.clone())
+                );
+            }
+
+            // preconditions are evaluated by the replicated state machine, replicas[1] might not
+            // know the leader yet which results in network errors until leadership has settled
+            assert!(matches!(
+                try_put_with_retry(
+                    &replicas[1].client,
+                    &key,
+                    &value,
+                    Precondition::DoesNotExist
+                )
+                .await,
+                Err(WriteError::FailedPrecondition(_))
+            ));
+
+            replicas[2]
+                .client
+                .delete(key.clone(), Precondition::MatchesVersion(Version::MIN))
+                .await?;
+
+            for replica in &replicas {
+                assert!(get_with_retry::<Value>(&replica.client, &key)
+                    .await?
+                    .is_none());
+            }
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Issues concurrent version increments through all replicas.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn concurrent_operations_on_different_replicas() -> anyhow::Result<()> {
+    let (replicas, env) = create_test_environment(3).await?;
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            let key = ByteString::from_static("counter");
+            let mut concurrent_operations = FuturesUnordered::default();
+
+            for (idx, replica) in replicas.iter().enumerate() {
+                for _ in 0..5 {
+                    let client = replica.client.clone();
+                    let key = key.clone();
+                    concurrent_operations.push(async move {
+                        loop {
+                            let result = match get_with_retry::<Value>(&client, &key).await? {
+                                Some(value) => {
+                                    let previous_version = value.version();
+                                    client
+                                        .put(
+                                            key.clone(),
+                                            &Value {
+                                                version: previous_version.next(),
+                                                value: idx.to_string(),
+                                            },
+                                            Precondition::MatchesVersion(previous_version),
+                                        )
+                                        .await
+                                }
+                                None => {
+                                    client
+                                        .put(
+                                            key.clone(),
+                                            &Value {
+                                                version: Version::MIN,
+                                                value: idx.to_string(),
+                                            },
+                                            Precondition::DoesNotExist,
+                                        )
+                                        .await
+                                }
+                            };
+
+                            match result {
+                                Ok(()) => return Ok::<(), anyhow::Error>(()),
+                                Err(WriteError::FailedPrecondition(_))
+                                | Err(WriteError::Network(_)) => continue,
+                                Err(err) => return Err(err.into()),
+                            }
+                        }
+                    });
+                }
+            }
+
+            while let Some(result) = concurrent_operations.next().await {
+                result?;
+            }
+
+            for replica in &replicas {
+                let version = get_with_retry::<Value>(&replica.client, &key)
+                    .await?
+                    .map(|value| value.version());
+                assert_eq!(version, Some(Version::from(15)));
+            }
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Adds a fourth replica which needs to catch up via a snapshot since the log has been compacted.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn add_replica_catches_up_via_snapshot() -> anyhow::Result<()> {
+    let (replicas, env) = create_test_environment(3).await?;
+    let tc = env.tc.clone();
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            // write more values than the snapshot interval to trigger log compaction
+            for key in 1u32..=20 {
+                put_with_retry(
+                    &replicas[0].client,
+                    &ByteString::from(key.to_string()),
+                    &Value {
+                        version: Version::from(key),
+                        value: key.to_string(),
+                    },
+                    Precondition::None,
+                )
+                .await?;
+            }
+
+            let new_replica = start_replica(4, Vec::new(), &tc).await?;
+
+            let mut raft_client = RaftMetadataStoreSvcClient::new(
+                restate_core::network::net_util::create_tonic_channel(
+                    replicas[0].address.clone(),
+                    None,
+                )?,
+            );
+            raft_client
+                .add_node(AddNodeRequest {
+                    id: 4,
+                    address: new_replica.address.to_string(),
+                })
+                .await?;
+
+            for key in 1u32..=20 {
+                assert_eq!(
+                    get_with_retry(&new_replica.client, &ByteString::from(key.to_string())).await?,
+                    Some(Value {
+                        version: Version::from(key),
+                        value: key.to_string(),
+                    })
+                );
+            }
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Stops the leader. A client configured with all replicas fails over to the remaining ones
+/// once they have elected a new leader.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn losing_the_leader() -> anyhow::Result<()> {
+    let (replicas, env) = create_test_environment_with_leader(3).await?;
+    let tc = env.tc.clone();
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            let client = client_for(&replicas)?;
+            let key = ByteString::from_static("key");
+            let value = Value {
+                version: Version::MIN,
+                value: "before".to_owned(),
+            };
+            put_with_retry(&client, &key, &value, Precondition::None).await?;
+
+            stop_replica(&tc, &replicas[0]).await;
+
+            let value = Value {
+                version: Version::MIN.next(),
+                value: "after".to_owned(),
+            };
+            put_with_retry(&client, &key, &value, Precondition::None).await?;
+            assert_eq!(get_with_retry(&client, &key).await?, Some(value));
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Stops a follower which is the first address of the client. The client fails over to the
+/// other replicas, which still form a quorum.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn losing_a_replica() -> anyhow::Result<()> {
+    let (mut replicas, env) = create_test_environment_with_leader(3).await?;
+    let tc = env.tc.clone();
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            replicas.reverse();
+            let client = client_for(&replicas)?;
+            stop_replica(&tc, &replicas[0]).await;
+
+            let key = ByteString::from_static("key");
+            let value = Value {
+                version: Version::MIN,
+                value: "value".to_owned(),
+            };
+            put_with_retry(&client, &key, &value, Precondition::None).await?;
+            assert_eq!(get_with_retry(&client, &key).await?, Some(value.clone()));
+            assert_eq!(
+                get_with_retry(&replicas[1].client, &key).await?,
+                Some(value)
+            );
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Removes two of three replicas and stops them. The remaining replica forms a quorum on its own.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn remove_replicas() -> anyhow::Result<()> {
+    let (replicas, env) = create_test_environment_with_leader(3).await?;
+    let tc = env.tc.clone();
+
+    env.tc
+        .run_in_scope("test", None, async move {
+            for replica in [&replicas[2], &replicas[1]] {
+                remove_node(&replicas[0].address, replica.id).await?;
+                stop_replica(&tc, replica).await;
+            }
+
+            let key = ByteString::from_static("key");
+            let value = Value {
+                version: Version::MIN,
+                value: "value".to_owned(),
+            };
+            put_with_retry(&replicas[0].client, &key, &value, Precondition::None).await?;
+            assert_eq!(
+                get_with_retry(&replicas[0].client, &key).await?,
+                Some(value)
+            );
+
+            Ok::<(), anyhow::Error>(())
+        })
+        .await?;
+
+    env.tc.shutdown_node("shutdown", 0).await;
+
+    Ok(())
+}
+
+/// Creates a test environment with `num_replicas` in-process replicas of the raft metadata store
+/// which are connected via unix domain sockets.
+async fn create_test_environment(
+    num_replicas: u64,
+) -> anyhow::Result<(Vec<Replica>, TestCoreEnv<FailingConnector>)> {
+    let config = Configuration::default();
+    restate_types::config::set_current_config(config.clone());
+    let config = Live::from_value(config);
+    let env = TestCoreEnvBuilder::with_incoming_only_connector()
+        .build()
+        .await;
+
+    env.tc.run_in_scope_sync("db-manager-init", None, || {
+        RocksDbManager::init(config.clone().map(|c| &c.common))
+    });
+
+    let peers = (1..=num_replicas)
+        .map(|id| {
+            let uds_path = tempfile::tempdir()?.into_path().join("grpc-server");
+            Ok::<_, anyhow::Error>(RaftPeer {
+                id: NonZeroU64::new(id).expect("non zero id"),
+                address: AdvertisedAddress::Uds(uds_path),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut replicas = Vec::with_capacity(peers.len());
+    for peer in &peers {
+        replicas.push(start_replica(peer.id.get(), peers.clone(), &env.tc).await?);
+    }
+
+    Ok((replicas, env))
+}
+
+/// Creates a test environment whose first replica is the leader. It bootstraps the metadata store
+/// on its own and adds the other replicas.
+async fn create_test_environment_with_leader(
+    num_replicas: u64,
+) -> anyhow::Result<(Vec<Replica>, TestCoreEnv<FailingConnector>)> {
+    let (mut replicas, env) = create_test_environment(1).await?;
+    let tc = env.tc.clone();
+
+    let replicas = env
+        .tc
+        .run_in_scope("add-replicas", None, async move {
+            for id in 2..=num_replicas {
+                let replica = start_replica(id, Vec::new(), &tc).await?;
+                add_node(&replicas[0].address, &replica).await?;
+                replicas.push(replica);
+            }
+            Ok::<_, anyhow::Error>(replicas)
+        })
+        .await?;
+
+    Ok((replicas, env))
+}
+
+async fn start_replica(
+    id: u64,
+    peers: Vec<RaftPeer>,
+    task_center: &TaskCenter,
+) -> anyhow::Result<Replica> {
+    let address = peers
+        .iter()
+        .find(|peer| peer.id.get() == id)
+        .map(|peer| peer.address.clone())
+        .unwrap_or_else(|| {
+            AdvertisedAddress::Uds(
+                tempfile::tempdir()
+                    .expect("temp dir")
+                    .into_path()
+                    .join("grpc-server"),
+            )
+        });
+    let AdvertisedAddress::Uds(uds_path) = &address else {
+        unreachable!("test replicas listen on unix domain sockets");
+    };
+
+    let raft_options = RaftOptions {
+        id: NonZeroU64::new(id).expect("non zero id"),
+        peers,
+        raft_tick_interval: Duration::from_millis(20).into(),
+        snapshot_interval: NonZeroU64::new(10).expect("non zero"),
+        request_timeout: Duration::from_secs(1).into(),
+        ..RaftOptions::default()
+    };
+
+    let mut opts = MetadataStoreOptions::default();
+    opts.bind_address = BindAddress::Uds(uds_path.clone());
+    opts.kind = MetadataStoreKind::Raft(raft_options.clone());
+    let opts = Live::from_value(opts);
+
+    let health_status = HealthStatus::default();
+    let service = RaftMetadataStoreService::from_options(
+        health_status.clone(),
+        opts.clone().boxed(),
+        raft_options,
+        opts.map(|c| &c.rocksdb).boxed(),
+    );
+
+    let task_id = task_center.spawn(
+        TaskKind::MetadataStore,
+        "raft-metadata-store",
+        None,
+        async move {
+            service.run().await?;
+            Ok(())
+        },
+    )?;
+
+    health_status
+        .wait_for_value(MetadataServerStatus::Ready)
+        .await;
+
+    let client = MetadataStoreClient::new(
+        LocalMetadataStoreClient::new(address.clone(), None)?,
+        Some(RetryPolicy::fixed_delay(
+            Duration::from_millis(50),
+            Some(100),
+        )),
+    );
+
+    Ok(Replica {
+        id,
+        address,
+        client,
+        task_id,
+    })
+}
+
+/// Stops the replica as if its node crashed.
+async fn stop_replica(task_center: &TaskCenter, replica: &Replica) {
+    if let Some(handle) = task_center.cancel_task(replica.task_id) {
+        let _ = handle.await;
+    }
+}
+
+/// Creates a client which fails over between the given replicas, in their order.
+fn client_for(replicas: &[Replica]) -> anyhow::Result<MetadataStoreClient> {
+    let addresses = replicas
+        .iter()
+        .map(|replica| replica.address.clone())
+        .collect();
+    Ok(MetadataStoreClient::new(
+        LocalMetadataStoreClient::with_addresses(addresses, None)?,
+        None,
+    ))
+}
+
+fn raft_client(
+    address: &AdvertisedAddress,
+) -> anyhow::Result<RaftMetadataStoreSvcClient<tonic::transport::Channel>> {
+    Ok(RaftMetadataStoreSvcClient::new(
+        restate_core::network::net_util::create_tonic_channel(address.clone(), None)?,
+    ))
+}
+
+/// Membership changes fail while the replicas have not yet elected a leader.
+async fn add_node(leader_address: &AdvertisedAddress, replica: &Replica) -> anyhow::Result<()> {
+    let mut raft_client = raft_client(leader_address)?;
+    loop {
+        match raft_client
+            .add_node(AddNodeRequest {
+                id: replica.id,
+                address: replica.address.to_string(),
+            })
+            .await
+        {
+            Err(status) if status.code() == tonic::Code::Unavailable => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            result => return Ok(result.map(|_| ())?),
+        }
+    }
+}
+
+/// Membership changes fail while the replicas have not yet elected a leader.
+async fn remove_node(leader_address: &AdvertisedAddress, id: u64) -> anyhow::Result<()> {
+    let mut raft_client = raft_client(leader_address)?;
+    loop {
+        match raft_client.remove_node(RemoveNodeRequest { id }).await {
+            Err(status) if status.code() == tonic::Code::Unavailable => {
+                tokio::time::sleep(Duration::from_millis(50)).await
+            }
+            result => return Ok(result.map(|_| ())?),
+        }
+    }
+}
+
+/// Reads may fail while the replicas have not yet elected a leader.
+async fn get_with_retry<T: Versioned + restate_types::storage::StorageDecode>(
+    client: &MetadataStoreClient,
+    key: &ByteString,
+) -> anyhow::Result<Option<T>> {
+    loop {
+        match client.get::<T>(key.clone()).await {
+            Err(ReadError::Network(_)) => tokio::time::sleep(Duration::from_millis(50)).await,
+            result => return Ok(result?),
+        }
+    }
+}
+
+/// Writes may fail while the replicas have not yet elected a leader.
+async fn put_with_retry(
+    client: &MetadataStoreClient,
+    key: &ByteString,
+    value: &Value,
+    precondition: Precondition,
+) -> anyhow::Result<()> {
+    Ok(try_put_with_retry(client, key, value, precondition).await?)
+}
+
+/// Retries writes until the replicas have elected a leader and returns the first result which
+/// isn't a network error.
+async fn try_put_with_retry(
+    client: &MetadataStoreClient,
+    key: &ByteString,
+    value: &Value,
+    precondition: Precondition,
+) -> Result<(), WriteError> {
+    loop {
+        match client.put(key.clone(), value, precondition.clone()).await {
+            Err(WriteError::Network(_)) => tokio::time::sleep(Duration::from_millis(50)).await,
+            result => return result,
+        }
+    }
+}
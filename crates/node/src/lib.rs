@@ -27,8 +27,7 @@ use restate_core::{
 use restate_core::{task_center, TaskKind};
 #[cfg(feature = "replicated-loglet")]
 use restate_log_server::LogServerService;
-use restate_metadata_store::local::LocalMetadataStoreService;
-use restate_metadata_store::MetadataStoreClient;
+use restate_metadata_store::{MetadataStoreClient, MetadataStoreService};
 use restate_types::config::{CommonOptions, Configuration};
 use restate_types::errors::GenericError;
 use restate_types::health::Health;
@@ -106,7 +105,7 @@ pub struct Node {
     partition_routing_refresher: PartitionRoutingRefresher,
     metadata_store_client: MetadataStoreClient,
     bifrost: BifrostService,
-    metadata_store_role: Option<LocalMetadataStoreService>,
+    metadata_store_role: Option<MetadataStoreService>,
     base_role: BaseRole,
     admin_role: Option<AdminRole<GrpcConnector>>,
     worker_role: Option<WorkerRole<GrpcConnector>>,
@@ -126,7 +125,7 @@ impl Node {
         cluster_marker::validate_and_update_cluster_marker(config.common.cluster_name())?;
 
         let metadata_store_role = if config.has_role(Role::MetadataStore) {
-            Some(LocalMetadataStoreService::from_options(
+            Some(MetadataStoreService::from_options(
                 health.metadata_server_status(),
                 updateable_config.clone().map(|c| &c.metadata_store).boxed(),
                 updateable_config
@@ -174,7 +173,8 @@ impl Node {
             &mut router_builder,
         );
         let bifrost_svc = BifrostService::new(tc.clone(), metadata.clone())
-            .enable_local_loglet(&updateable_config);
+            .enable_local_loglet(&updateable_config)
+            .enable_tiered_storage_loglet(&updateable_config);
 
         #[cfg(feature = "replicated-loglet")]
         let bifrost_svc = bifrost_svc.with_factory(replicated_loglet_factory);
@@ -299,7 +299,7 @@ impl Node {
         if let Some(metadata_store) = self.metadata_store_role {
             tc.spawn(
                 TaskKind::MetadataStore,
-                "local-metadata-store",
+                "metadata-store",
                 None,
                 async move {
                     metadata_store.run().await?;
@@ -551,6 +551,7 @@ impl Node {
                     // update node_config
                     node_config.roles = common_opts.roles;
                     node_config.address = common_opts.advertised_address.clone();
+                    node_config.location = common_opts.location.clone();
                     node_config.current_generation.bump_generation();
 
                     node_config
@@ -576,6 +577,7 @@ impl Node {
                         common_opts.advertised_address.clone(),
                         common_opts.roles,
                         LogServerConfig::default(),
+                        common_opts.location.clone(),
                     )
                 };
 
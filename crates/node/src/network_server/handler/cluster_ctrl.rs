@@ -20,8 +20,8 @@ use restate_admin::cluster_controller::protobuf::{
     ClusterStateRequest, ClusterStateResponse, CreatePartitionSnapshotRequest,
     CreatePartitionSnapshotResponse, DescribeLogRequest, DescribeLogResponse, FindTailRequest,
     FindTailResponse, ListLogsRequest, ListLogsResponse, ListNodesRequest, ListNodesResponse,
-    SealAndExtendChainRequest, SealAndExtendChainResponse, SealedSegment, TailState,
-    TrimLogRequest,
+    OffloadLogSegmentRequest, SealAndExtendChainRequest, SealAndExtendChainResponse, SealedSegment,
+    TailState, TrimLogRequest,
 };
 use restate_admin::cluster_controller::ClusterControllerHandle;
 use restate_bifrost::{Bifrost, BifrostAdmin, Error as BiforstError};
@@ -282,6 +282,28 @@ impl ClusterCtrlSvc for ClusterCtrlSvcHandler {
 
         Ok(Response::new(response))
     }
+
+    async fn offload_log_segment(
+        &self,
+        request: Request<OffloadLogSegmentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let admin = BifrostAdmin::new(
+            &self.bifrost_handle,
+            &self.metadata_writer,
+            &self.metadata_store_client,
+        );
+
+        let request = request.into_inner();
+        admin
+            .offload_segment(
+                request.log_id.into(),
+                SegmentIndex::from(request.segment_index),
+            )
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(()))
+    }
 }
 
 fn serialize_value<T: StorageEncode>(value: T) -> Bytes {
@@ -22,7 +22,7 @@ use restate_admin::cluster_controller::ClusterControllerHandle;
 use restate_bifrost::Bifrost;
 use restate_core::network::net_util::run_hyper_server;
 use restate_core::network::protobuf::node_svc::node_svc_server::NodeSvcServer;
-use restate_core::network::{ConnectionManager, GrpcConnector};
+use restate_core::network::{ConnectionManager, GrpcConnector, TlsAcceptor};
 use restate_core::{task_center, MetadataWriter};
 use restate_metadata_store::MetadataStoreClient;
 use restate_storage_query_datafusion::context::QueryContext;
@@ -128,8 +128,15 @@ impl NetworkServer {
                 .map_request(|req: Request<Incoming>| req.map(boxed)),
         );
 
+        let tls_acceptor = options
+            .tls
+            .as_ref()
+            .map(TlsAcceptor::from_options)
+            .transpose()?;
+
         run_hyper_server(
             &options.bind_address,
+            tls_acceptor,
             service,
             "node-grpc",
             || node_status.update(NodeStatus::Alive),
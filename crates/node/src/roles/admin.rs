@@ -19,8 +19,9 @@ use restate_admin::cluster_controller::ClusterControllerHandle;
 use restate_admin::service::AdminService;
 use restate_bifrost::Bifrost;
 use restate_core::metadata_store::MetadataStoreClient;
-use restate_core::network::net_util::create_tonic_channel_from_advertised_address;
+use restate_core::network::net_util::create_tonic_channel;
 use restate_core::network::protobuf::node_svc::node_svc_client::NodeSvcClient;
+use restate_core::network::tls::load_client_tls_config;
 use restate_core::network::MessageRouterBuilder;
 use restate_core::network::Networking;
 use restate_core::network::TransportConnect;
@@ -117,6 +118,16 @@ impl<T: TransportConnect> AdminRole<T> {
     ) -> Result<(), anyhow::Error> {
         let tc = task_center();
 
+        let tls_config = self
+            .updateable_config
+            .pinned()
+            .networking
+            .tls
+            .as_ref()
+            .map(load_client_tls_config)
+            .transpose()?;
+        let node_svc_channel = create_tonic_channel(node_address, tls_config)?;
+
         if let Some(cluster_controller) = self.controller {
             tc.spawn_child(
                 TaskKind::SystemService,
@@ -132,7 +143,7 @@ impl<T: TransportConnect> AdminRole<T> {
             None,
             self.admin.run(
                 self.updateable_config.map(|c| &c.admin),
-                NodeSvcClient::new(create_tonic_channel_from_advertised_address(node_address)),
+                NodeSvcClient::new(node_svc_channel),
                 bifrost,
             ),
         )?;
@@ -9,18 +9,18 @@
 // by the Apache License, Version 2.0.
 
 use crate::keys::{define_table_key, KeyKind, TableKey};
-use crate::TableKind::Inbox;
-use crate::{PartitionStore, PartitionStoreTransaction, StorageAccess};
+use crate::TableKind::{HeldInvocation as HeldInvocationTable, Inbox};
+use crate::{PaddedPartitionId, PartitionStore, PartitionStoreTransaction, StorageAccess};
 use crate::{TableScan, TableScanIterationDecision};
 use bytestring::ByteString;
 use futures::Stream;
 use futures_util::stream;
 use restate_rocksdb::RocksDbPerfGuard;
 use restate_storage_api::inbox_table::{
-    InboxEntry, InboxTable, ReadOnlyInboxTable, SequenceNumberInboxEntry,
+    HeldInvocation, InboxEntry, InboxTable, ReadOnlyInboxTable, SequenceNumberInboxEntry,
 };
 use restate_storage_api::{Result, StorageError};
-use restate_types::identifiers::{PartitionKey, ServiceId, WithPartitionKey};
+use restate_types::identifiers::{PartitionId, PartitionKey, ServiceId, WithPartitionKey};
 use restate_types::message::MessageIndex;
 use restate_types::storage::StorageCodec;
 use std::future::Future;
@@ -38,6 +38,16 @@ define_table_key!(
     )
 );
 
+define_table_key!(
+    HeldInvocationTable,
+    KeyKind::HeldInvocation,
+    HeldInvocationKey(
+        partition_id: PaddedPartitionId,
+        service_name: ByteString,
+        sequence_number: u64
+    )
+);
+
 fn peek_inbox<S: StorageAccess>(
     storage: &mut S,
     service_id: &ServiceId,
@@ -90,6 +100,40 @@ fn all_inboxes<S: StorageAccess>(
     ))
 }
 
+fn held_invocations<S: StorageAccess>(
+    storage: &mut S,
+    partition_id: PartitionId,
+    service_name: &str,
+) -> impl Stream<Item = Result<(MessageIndex, HeldInvocation)>> + Send {
+    let start = HeldInvocationKey::default()
+        .partition_id(partition_id.into())
+        .service_name(ByteString::from(service_name))
+        .sequence_number(0);
+    let end = HeldInvocationKey::default()
+        .partition_id(partition_id.into())
+        .service_name(ByteString::from(service_name))
+        .sequence_number(u64::MAX);
+
+    stream::iter(storage.for_each_key_value_in_place(
+        TableScan::KeyRangeInclusiveInSinglePartition(partition_id, start, end),
+        |k, v| {
+            let held_invocation = decode_held_invocation_key_value(k, v);
+            TableScanIterationDecision::Emit(held_invocation)
+        },
+    ))
+}
+
+fn held_invocation_key(
+    partition_id: PartitionId,
+    service_name: &str,
+    sequence_number: MessageIndex,
+) -> HeldInvocationKey {
+    HeldInvocationKey::default()
+        .partition_id(partition_id.into())
+        .service_name(ByteString::from(service_name))
+        .sequence_number(sequence_number)
+}
+
 impl ReadOnlyInboxTable for PartitionStore {
     fn peek_inbox(
         &mut self,
@@ -113,6 +157,14 @@ impl ReadOnlyInboxTable for PartitionStore {
     ) -> impl Stream<Item = Result<SequenceNumberInboxEntry>> + Send {
         all_inboxes(self, range)
     }
+
+    fn held_invocations(
+        &mut self,
+        service_name: &str,
+    ) -> impl Stream<Item = Result<(MessageIndex, HeldInvocation)>> + Send {
+        let partition_id = self.partition_id();
+        held_invocations(self, partition_id, service_name)
+    }
 }
 
 impl<'a> ReadOnlyInboxTable for PartitionStoreTransaction<'a> {
@@ -138,6 +190,14 @@ impl<'a> ReadOnlyInboxTable for PartitionStoreTransaction<'a> {
     ) -> impl Stream<Item = Result<SequenceNumberInboxEntry>> + Send {
         all_inboxes(self, range)
     }
+
+    fn held_invocations(
+        &mut self,
+        service_name: &str,
+    ) -> impl Stream<Item = Result<(MessageIndex, HeldInvocation)>> + Send {
+        let partition_id = self.partition_id();
+        held_invocations(self, partition_id, service_name)
+    }
 }
 
 impl<'a> InboxTable for PartitionStoreTransaction<'a> {
@@ -177,6 +237,21 @@ impl<'a> InboxTable for PartitionStoreTransaction<'a> {
 
         result
     }
+
+    async fn put_held_invocation(
+        &mut self,
+        service_name: &str,
+        sequence_number: MessageIndex,
+        held_invocation: &HeldInvocation,
+    ) {
+        let key = held_invocation_key(self.partition_id(), service_name, sequence_number);
+        self.put_kv(key, held_invocation);
+    }
+
+    async fn delete_held_invocation(&mut self, service_name: &str, sequence_number: MessageIndex) {
+        let key = held_invocation_key(self.partition_id(), service_name, sequence_number);
+        self.delete_key(&key);
+    }
 }
 
 fn delete_inbox_entry(
@@ -203,6 +278,19 @@ fn decode_inbox_key_value(k: &[u8], mut v: &[u8]) -> Result<SequenceNumberInboxE
     Ok(SequenceNumberInboxEntry::new(sequence_number, inbox_entry))
 }
 
+fn decode_held_invocation_key_value(
+    k: &[u8],
+    mut v: &[u8],
+) -> Result<(MessageIndex, HeldInvocation)> {
+    let key = HeldInvocationKey::deserialize_from(&mut Cursor::new(k))?;
+    let sequence_number = *key.sequence_number_ok_or()?;
+
+    let held_invocation = StorageCodec::decode::<HeldInvocation, _>(&mut v)
+        .map_err(|error| StorageError::Generic(error.into()))?;
+
+    Ok((sequence_number, held_invocation))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::inbox_table::InboxKey;
@@ -21,7 +21,7 @@ use restate_storage_api::invocation_status_table::{
 };
 use restate_storage_api::{Result, StorageError};
 use restate_types::identifiers::{InvocationId, InvocationUuid, PartitionKey, WithPartitionKey};
-use restate_types::invocation::InvocationTarget;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
 use restate_types::storage::StorageCodec;
 use std::ops::RangeInclusive;
 use tracing::trace;
@@ -169,7 +169,7 @@ fn delete_invocation_status<S: StorageAccess>(storage: &mut S, invocation_id: &I
 fn invoked_invocations<S: StorageAccess>(
     storage: &mut S,
     partition_key_range: RangeInclusive<PartitionKey>,
-) -> Vec<Result<(InvocationId, InvocationTarget)>> {
+) -> Vec<Result<(InvocationId, InvocationTarget, InvocationEpoch)>> {
     let _x = RocksDbPerfGuard::new("invoked-invocations");
     let mut invocations = storage.for_each_key_value_in_place(
         FullScanPartitionKeyRange::<InvocationStatusKeyV1>(partition_key_range.clone()),
@@ -239,12 +239,16 @@ fn all_invocation_status<S: StorageAccess>(
 fn read_invoked_v1_full_invocation_id(
     mut k: &mut &[u8],
     v: &mut &[u8],
-) -> Result<Option<(InvocationId, InvocationTarget)>> {
+) -> Result<Option<(InvocationId, InvocationTarget, InvocationEpoch)>> {
     let invocation_id = invocation_id_from_v1_key_bytes(&mut k)?;
     let invocation_status = StorageCodec::decode::<InvocationStatusV1, _>(v)
         .map_err(|err| StorageError::Generic(err.into()))?;
     if let InvocationStatus::Invoked(invocation_meta) = invocation_status.0 {
-        Ok(Some((invocation_id, invocation_meta.invocation_target)))
+        Ok(Some((
+            invocation_id,
+            invocation_meta.invocation_target,
+            invocation_meta.current_invocation_epoch,
+        )))
     } else {
         Ok(None)
     }
@@ -253,13 +257,17 @@ fn read_invoked_v1_full_invocation_id(
 fn read_invoked_full_invocation_id(
     mut k: &mut &[u8],
     v: &mut &[u8],
-) -> Result<Option<(InvocationId, InvocationTarget)>> {
+) -> Result<Option<(InvocationId, InvocationTarget, InvocationEpoch)>> {
     // TODO this can be improved by simply parsing InvocationTarget and the Status enum
     let invocation_id = invocation_id_from_key_bytes(&mut k)?;
     let invocation_status = StorageCodec::decode::<InvocationStatus, _>(v)
         .map_err(|err| StorageError::Generic(err.into()))?;
     if let InvocationStatus::Invoked(invocation_meta) = invocation_status {
-        Ok(Some((invocation_id, invocation_meta.invocation_target)))
+        Ok(Some((
+            invocation_id,
+            invocation_meta.invocation_target,
+            invocation_meta.current_invocation_epoch,
+        )))
     } else {
         Ok(None)
     }
@@ -276,7 +284,7 @@ impl ReadOnlyInvocationStatusTable for PartitionStore {
 
     fn all_invoked_invocations(
         &mut self,
-    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget)>> + Send {
+    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget, InvocationEpoch)>> + Send {
         stream::iter(invoked_invocations(
             self,
             self.partition_key_range().clone(),
@@ -302,7 +310,7 @@ impl<'a> ReadOnlyInvocationStatusTable for PartitionStoreTransaction<'a> {
 
     fn all_invoked_invocations(
         &mut self,
-    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget)>> + Send {
+    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget, InvocationEpoch)>> + Send {
         stream::iter(invoked_invocations(
             self,
             self.partition_key_range().clone(),
@@ -0,0 +1,141 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::io::Cursor;
+
+use bytestring::ByteString;
+use futures::Stream;
+use futures_util::stream;
+use restate_rocksdb::RocksDbPerfGuard;
+use restate_storage_api::kafka_sink_outbox_table::{
+    KafkaSinkOutboxTable, KafkaSinkRecord, ReadOnlyKafkaSinkOutboxTable,
+};
+use restate_storage_api::{Result, StorageError};
+use restate_types::identifiers::PartitionId;
+use restate_types::message::MessageIndex;
+use restate_types::storage::StorageCodec;
+
+use crate::keys::{define_table_key, KeyKind, TableKey};
+use crate::TableKind::KafkaSinkOutbox;
+use crate::{
+    PaddedPartitionId, PartitionStore, PartitionStoreTransaction, StorageAccess, TableScan,
+    TableScanIterationDecision,
+};
+
+define_table_key!(
+    KafkaSinkOutbox,
+    KeyKind::KafkaSinkOutbox,
+    KafkaSinkOutboxKey(
+        partition_id: PaddedPartitionId,
+        sink_id: ByteString,
+        record_index: u64
+    )
+);
+
+fn all_kafka_sink_records<S: StorageAccess>(
+    storage: &mut S,
+    partition_id: PartitionId,
+) -> impl Stream<Item = Result<(MessageIndex, KafkaSinkRecord)>> + Send {
+    stream::iter(storage.for_each_key_value_in_place(
+        TableScan::SinglePartition::<KafkaSinkOutboxKey>(partition_id),
+        |k, v| TableScanIterationDecision::Emit(decode_key_value(k, v)),
+    ))
+}
+
+fn put_kafka_sink_record<S: StorageAccess>(
+    storage: &mut S,
+    partition_id: PartitionId,
+    record_index: MessageIndex,
+    record: &KafkaSinkRecord,
+) {
+    let key = KafkaSinkOutboxKey::default()
+        .partition_id(partition_id.into())
+        .sink_id(ByteString::from(record.sink_id.as_str()))
+        .record_index(record_index);
+
+    storage.put_kv(key, record);
+}
+
+fn truncate_kafka_sink_outbox<S: StorageAccess>(
+    storage: &mut S,
+    partition_id: PartitionId,
+    sink_id: &str,
+    record_index: MessageIndex,
+) -> Result<()> {
+    let _x = RocksDbPerfGuard::new("truncate-kafka-sink-outbox");
+    let start = KafkaSinkOutboxKey::default()
+        .partition_id(partition_id.into())
+        .sink_id(ByteString::from(sink_id))
+        .record_index(0);
+    let end = KafkaSinkOutboxKey::default()
+        .partition_id(partition_id.into())
+        .sink_id(ByteString::from(sink_id))
+        .record_index(record_index);
+
+    let keys = storage.for_each_key_value_in_place(
+        TableScan::KeyRangeInclusiveInSinglePartition(partition_id, start, end),
+        |k, _| {
+            TableScanIterationDecision::Emit(KafkaSinkOutboxKey::deserialize_from(
+                &mut Cursor::new(k),
+            ))
+        },
+    );
+    for key in keys {
+        storage.delete_key(&key?);
+    }
+
+    Ok(())
+}
+
+impl ReadOnlyKafkaSinkOutboxTable for PartitionStore {
+    fn all_kafka_sink_records(
+        &mut self,
+    ) -> impl Stream<Item = Result<(MessageIndex, KafkaSinkRecord)>> + Send {
+        let partition_id = self.partition_id();
+        all_kafka_sink_records(self, partition_id)
+    }
+}
+
+impl<'a> ReadOnlyKafkaSinkOutboxTable for PartitionStoreTransaction<'a> {
+    fn all_kafka_sink_records(
+        &mut self,
+    ) -> impl Stream<Item = Result<(MessageIndex, KafkaSinkRecord)>> + Send {
+        let partition_id = self.partition_id();
+        all_kafka_sink_records(self, partition_id)
+    }
+}
+
+impl<'a> KafkaSinkOutboxTable for PartitionStoreTransaction<'a> {
+    async fn put_kafka_sink_record(
+        &mut self,
+        record_index: MessageIndex,
+        record: &KafkaSinkRecord,
+    ) {
+        put_kafka_sink_record(self, self.partition_id(), record_index, record)
+    }
+
+    async fn truncate_kafka_sink_outbox(
+        &mut self,
+        sink_id: &str,
+        record_index: MessageIndex,
+    ) -> Result<()> {
+        truncate_kafka_sink_outbox(self, self.partition_id(), sink_id, record_index)
+    }
+}
+
+fn decode_key_value(k: &[u8], mut v: &[u8]) -> Result<(MessageIndex, KafkaSinkRecord)> {
+    let key = KafkaSinkOutboxKey::deserialize_from(&mut Cursor::new(k))?;
+    let record_index = *key.record_index_ok_or()?;
+
+    let record = StorageCodec::decode::<KafkaSinkRecord, _>(&mut v)
+        .map_err(|error| StorageError::Generic(error.into()))?;
+
+    Ok((record_index, record))
+}
@@ -38,6 +38,10 @@ pub enum KeyKind {
     State,
     Timers,
     Promise,
+    DeadLetter,
+    Schedule,
+    HeldInvocation,
+    KafkaSinkOutbox,
 }
 
 impl KeyKind {
@@ -78,6 +82,10 @@ impl KeyKind {
             KeyKind::State => b"st",
             KeyKind::Timers => b"ti",
             KeyKind::Promise => b"pr",
+            KeyKind::DeadLetter => b"dl",
+            KeyKind::Schedule => b"sc",
+            KeyKind::HeldInvocation => b"hi",
+            KeyKind::KafkaSinkOutbox => b"ko",
         }
     }
 
@@ -103,6 +111,10 @@ impl KeyKind {
             b"st" => Some(KeyKind::State),
             b"ti" => Some(KeyKind::Timers),
             b"pr" => Some(KeyKind::Promise),
+            b"dl" => Some(KeyKind::DeadLetter),
+            b"sc" => Some(KeyKind::Schedule),
+            b"hi" => Some(KeyKind::HeldInvocation),
+            b"ko" => Some(KeyKind::KafkaSinkOutbox),
             _ => None,
         }
     }
@@ -531,6 +543,10 @@ impl KeyCodec for TimerKeyKind {
                 target.put_u8(3);
                 invocation_uuid.encode(target);
             }
+            TimerKeyKind::FireSchedule { invocation_uuid } => {
+                target.put_u8(4);
+                invocation_uuid.encode(target);
+            }
         }
     }
 
@@ -562,6 +578,10 @@ impl KeyCodec for TimerKeyKind {
                 let invocation_uuid = InvocationUuid::decode(source)?;
                 TimerKeyKind::NeoInvoke { invocation_uuid }
             }
+            4 => {
+                let invocation_uuid = InvocationUuid::decode(source)?;
+                TimerKeyKind::FireSchedule { invocation_uuid }
+            }
             i => {
                 return Err(StorageError::Generic(anyhow!(
                     "Unknown discriminator for TimerKind: '{}'",
@@ -589,6 +609,9 @@ impl KeyCodec for TimerKeyKind {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 KeyCodec::serialized_length(invocation_uuid)
             }
+            TimerKeyKind::FireSchedule { invocation_uuid } => {
+                KeyCodec::serialized_length(invocation_uuid)
+            }
         }
     }
 }
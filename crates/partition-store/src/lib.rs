@@ -8,12 +8,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod dead_letter_table;
 pub mod deduplication_table;
 pub mod fsm_table;
 pub mod idempotency_table;
 pub mod inbox_table;
 pub mod invocation_status_table;
 pub mod journal_table;
+pub mod kafka_sink_outbox_table;
 pub mod keys;
 pub mod outbox_table;
 mod owned_iter;
@@ -21,6 +23,7 @@ mod partition_store;
 mod partition_store_manager;
 pub mod promise_table;
 pub mod scan;
+pub mod schedule_table;
 pub mod service_status_table;
 pub mod snapshots;
 pub mod state_table;
@@ -120,6 +120,8 @@ pub enum TableKind {
     Deduplication,
     Outbox,
     Timers,
+    HeldInvocation,
+    KafkaSinkOutbox,
     // By Partition Key
     State,
     InvocationStatus,
@@ -128,6 +130,8 @@ pub enum TableKind {
     Inbox,
     Journal,
     Promise,
+    DeadLetter,
+    Schedule,
 }
 
 impl TableKind {
@@ -144,6 +148,10 @@ impl TableKind {
             Self::Timers => &[KeyKind::Timers],
             Self::Journal => &[KeyKind::Journal],
             Self::Promise => &[KeyKind::Promise],
+            Self::DeadLetter => &[KeyKind::DeadLetter],
+            Self::Schedule => &[KeyKind::Schedule],
+            Self::HeldInvocation => &[KeyKind::HeldInvocation],
+            Self::KafkaSinkOutbox => &[KeyKind::KafkaSinkOutbox],
         }
     }
 
@@ -474,8 +482,9 @@ impl Storage for PartitionStore {
 
 impl StorageAccess for PartitionStore {
     type DBAccess<'a>
-    = DB where
-        Self: 'a,;
+        = DB
+    where
+        Self: 'a;
 
     fn iterator_from<K: TableKey>(
         &self,
@@ -646,7 +655,10 @@ impl<'a> Transaction for PartitionStoreTransaction<'a> {
 }
 
 impl<'a> StorageAccess for PartitionStoreTransaction<'a> {
-    type DBAccess<'b> = DB where Self: 'b;
+    type DBAccess<'b>
+        = DB
+    where
+        Self: 'b;
 
     fn iterator_from<K: TableKey>(
         &self,
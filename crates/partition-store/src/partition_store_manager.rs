@@ -130,6 +130,15 @@ impl PartitionStoreManager {
         Ok(partition_store)
     }
 
+    /// Returns true if the database contains the partition store, regardless of whether it is
+    /// currently open.
+    pub fn partition_store_exists(&self, partition_id: PartitionId) -> bool {
+        self.rocksdb
+            .inner()
+            .cf_handle(&cf_for_partition(partition_id))
+            .is_some()
+    }
+
     /// Imports a partition snapshot and opens it as a partition store.
     /// The database must not have an existing column family for the partition id;
     /// it will be created based on the supplied snapshot.
@@ -0,0 +1,141 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::keys::{define_table_key, KeyKind, TableKey};
+use crate::owned_iter::OwnedIterator;
+use crate::scan::TableScan;
+use crate::{PartitionStore, TableKind};
+use crate::{PartitionStoreTransaction, StorageAccess};
+use bytestring::ByteString;
+use futures::Stream;
+use futures_util::stream;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus, ScheduleTable};
+use restate_storage_api::{Result, StorageError};
+use restate_types::identifiers::{PartitionKey, WithPartitionKey};
+use restate_types::storage::StorageCodec;
+use std::ops::RangeInclusive;
+
+define_table_key!(
+    TableKind::Schedule,
+    KeyKind::Schedule,
+    ScheduleKey(
+        partition_key: PartitionKey,
+        schedule_id: ByteString
+    )
+);
+
+/// Partition key of a schedule, used to check that the schedule is owned by the partition.
+struct SchedulePartitionKey(PartitionKey);
+
+impl WithPartitionKey for SchedulePartitionKey {
+    fn partition_key(&self) -> PartitionKey {
+        self.0
+    }
+}
+
+fn create_key(partition_key: PartitionKey, schedule_id: &str) -> ScheduleKey {
+    ScheduleKey::default()
+        .partition_key(partition_key)
+        .schedule_id(ByteString::from(schedule_id))
+}
+
+fn get_schedule_status<S: StorageAccess>(
+    storage: &mut S,
+    partition_key: PartitionKey,
+    schedule_id: &str,
+) -> Result<Option<ScheduleStatus>> {
+    storage.get_value(create_key(partition_key, schedule_id))
+}
+
+fn all_schedule_statuses<S: StorageAccess>(
+    storage: &S,
+    range: RangeInclusive<PartitionKey>,
+) -> impl Stream<Item = Result<(PartitionKey, ScheduleStatus)>> + Send + '_ {
+    let iter = storage.iterator_from(TableScan::FullScanPartitionKeyRange::<ScheduleKey>(range));
+    stream::iter(OwnedIterator::new(iter).map(|(mut k, mut v)| {
+        let key = ScheduleKey::deserialize_from(&mut k)?;
+        let schedule_status = StorageCodec::decode::<ScheduleStatus, _>(&mut v)
+            .map_err(|err| StorageError::Generic(err.into()))?;
+
+        Ok((*key.partition_key_ok_or()?, schedule_status))
+    }))
+}
+
+fn put_schedule_status<S: StorageAccess>(
+    storage: &mut S,
+    partition_key: PartitionKey,
+    schedule_status: &ScheduleStatus,
+) {
+    storage.put_kv(
+        create_key(partition_key, schedule_status.schedule.id()),
+        schedule_status,
+    );
+}
+
+fn delete_schedule_status<S: StorageAccess>(
+    storage: &mut S,
+    partition_key: PartitionKey,
+    schedule_id: &str,
+) {
+    let key = create_key(partition_key, schedule_id);
+    storage.delete_key(&key);
+}
+
+impl ReadOnlyScheduleTable for PartitionStore {
+    async fn get_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_id: &str,
+    ) -> Result<Option<ScheduleStatus>> {
+        self.assert_partition_key(&SchedulePartitionKey(partition_key));
+        get_schedule_status(self, partition_key, schedule_id)
+    }
+
+    fn all_schedule_statuses(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> impl Stream<Item = Result<(PartitionKey, ScheduleStatus)>> + Send {
+        all_schedule_statuses(self, range)
+    }
+}
+
+impl<'a> ReadOnlyScheduleTable for PartitionStoreTransaction<'a> {
+    async fn get_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_id: &str,
+    ) -> Result<Option<ScheduleStatus>> {
+        self.assert_partition_key(&SchedulePartitionKey(partition_key));
+        get_schedule_status(self, partition_key, schedule_id)
+    }
+
+    fn all_schedule_statuses(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> impl Stream<Item = Result<(PartitionKey, ScheduleStatus)>> + Send {
+        all_schedule_statuses(self, range)
+    }
+}
+
+impl<'a> ScheduleTable for PartitionStoreTransaction<'a> {
+    async fn put_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_status: &ScheduleStatus,
+    ) {
+        self.assert_partition_key(&SchedulePartitionKey(partition_key));
+        put_schedule_status(self, partition_key, schedule_status)
+    }
+
+    async fn delete_schedule_status(&mut self, partition_key: PartitionKey, schedule_id: &str) {
+        self.assert_partition_key(&SchedulePartitionKey(partition_key));
+        delete_schedule_status(self, partition_key, schedule_id)
+    }
+}
@@ -1,14 +1,18 @@
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rocksdb::LiveFile;
 use serde::{Deserialize, Serialize};
 use serde_with::hex::Hex;
 use serde_with::{serde_as, DeserializeAs, SerializeAs};
+use tracing::warn;
 
 use restate_types::identifiers::{PartitionId, PartitionKey, SnapshotId};
 use restate_types::logs::Lsn;
 
+/// Name of the file storing the [PartitionSnapshotMetadata] in a snapshot directory.
+pub const SNAPSHOT_METADATA_FILE_NAME: &str = "metadata.json";
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SnapshotFormatVersion {
     #[default]
@@ -62,6 +66,81 @@ pub struct LocalPartitionSnapshot {
     pub files: Vec<LiveFile>,
 }
 
+impl LocalPartitionSnapshot {
+    /// Finds the snapshot with the highest applied LSN among the snapshots of a partition.
+    pub async fn find_latest(
+        partition_id: PartitionId,
+        partition_snapshots_dir: &Path,
+    ) -> std::io::Result<Option<LocalPartitionSnapshot>> {
+        Ok(Self::list(partition_id, partition_snapshots_dir)
+            .await?
+            .pop())
+    }
+
+    /// Lists the snapshots of a partition, ordered by their applied LSN. Every snapshot is stored
+    /// in its own sub-directory of `partition_snapshots_dir`, next to its metadata file. Snapshots
+    /// whose metadata cannot be read are skipped.
+    pub async fn list(
+        partition_id: PartitionId,
+        partition_snapshots_dir: &Path,
+    ) -> std::io::Result<Vec<LocalPartitionSnapshot>> {
+        let mut entries = match tokio::fs::read_dir(partition_snapshots_dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut snapshots = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let snapshot_dir = entry.path();
+            let metadata_path = snapshot_dir.join(SNAPSHOT_METADATA_FILE_NAME);
+            let metadata = match tokio::fs::read(&metadata_path).await {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            let metadata: PartitionSnapshotMetadata = match serde_json::from_slice(&metadata) {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    warn!(path = ?metadata_path, %err, "Ignoring snapshot with invalid metadata");
+                    continue;
+                }
+            };
+
+            if metadata.partition_id == partition_id {
+                snapshots.push(LocalPartitionSnapshot::from_metadata(
+                    metadata,
+                    snapshot_dir,
+                ));
+            }
+        }
+
+        snapshots.sort_by_key(|snapshot| snapshot.min_applied_lsn);
+        Ok(snapshots)
+    }
+
+    /// Creates a local snapshot out of the metadata of a snapshot stored in `base_dir`.
+    pub fn from_metadata(metadata: PartitionSnapshotMetadata, base_dir: PathBuf) -> Self {
+        let directory = base_dir.to_string_lossy().into_owned();
+        let files = metadata
+            .files
+            .into_iter()
+            .map(|mut file| {
+                // the snapshot might have been moved since it was created
+                file.directory.clone_from(&directory);
+                file
+            })
+            .collect();
+
+        LocalPartitionSnapshot {
+            base_dir,
+            min_applied_lsn: metadata.min_applied_lsn,
+            db_comparator_name: metadata.db_comparator_name,
+            files,
+        }
+    }
+}
+
 /// RocksDB SST file that is part of a snapshot. Serialization wrapper around [LiveFile].
 #[serde_as]
 #[derive(Serialize, Deserialize)]
@@ -0,0 +1,76 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::storage_test_environment;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use restate_storage_api::dead_letter_table::{
+    DeadLetter, DeadLetterTable, ReadOnlyDeadLetterTable,
+};
+use restate_storage_api::Transaction;
+use restate_types::errors::{codes, InvocationError};
+use restate_types::identifiers::{InvocationId, InvocationUuid};
+use restate_types::invocation::{Header, InvocationTarget};
+use restate_types::time::MillisSinceEpoch;
+
+const INVOCATION_ID_1: InvocationId =
+    InvocationId::from_parts(10, InvocationUuid::from_u128(12345678900001));
+const INVOCATION_ID_2: InvocationId =
+    InvocationId::from_parts(11, InvocationUuid::from_u128(12345678900002));
+
+fn mock_dead_letter(argument: &'static str) -> DeadLetter {
+    DeadLetter {
+        invocation_target: InvocationTarget::mock_service(),
+        argument: Bytes::from_static(argument.as_bytes()),
+        headers: vec![Header::new("content-type", "application/json")],
+        last_error: InvocationError::new(codes::INTERNAL, "boom"),
+        journal_length: 3,
+        dead_lettered_at: MillisSinceEpoch::new(1000),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_dead_letters() {
+    let mut rocksdb = storage_test_environment().await;
+
+    let mut txn = rocksdb.transaction();
+    txn.put_dead_letter(&INVOCATION_ID_1, &mock_dead_letter("one"))
+        .await;
+    txn.put_dead_letter(&INVOCATION_ID_2, &mock_dead_letter("two"))
+        .await;
+    txn.commit().await.unwrap();
+
+    assert_eq!(
+        rocksdb.get_dead_letter(&INVOCATION_ID_1).await.unwrap(),
+        Some(mock_dead_letter("one"))
+    );
+    assert_eq!(
+        rocksdb
+            .all_dead_letters(10..=10)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![(INVOCATION_ID_1, mock_dead_letter("one"))]
+    );
+
+    let mut txn = rocksdb.transaction();
+    txn.delete_dead_letter(&INVOCATION_ID_1).await;
+    txn.commit().await.unwrap();
+
+    assert_eq!(
+        rocksdb.get_dead_letter(&INVOCATION_ID_1).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        rocksdb.get_dead_letter(&INVOCATION_ID_2).await.unwrap(),
+        Some(mock_dead_letter("two"))
+    );
+}
@@ -13,7 +13,7 @@ use super::{assert_stream_eq, mock_state_mutation};
 use crate::PartitionStore;
 use once_cell::sync::Lazy;
 use restate_storage_api::inbox_table::{
-    InboxEntry, InboxTable, ReadOnlyInboxTable, SequenceNumberInboxEntry,
+    HeldInvocation, InboxEntry, InboxTable, ReadOnlyInboxTable, SequenceNumberInboxEntry,
 };
 use restate_storage_api::Transaction;
 use restate_types::identifiers::{InvocationId, ServiceId};
@@ -90,6 +90,35 @@ async fn peek_after_delete<T: InboxTable + ReadOnlyInboxTable>(table: &mut T) {
     assert_eq!(result.unwrap(), Some(INBOX_ENTRIES[1].clone()));
 }
 
+async fn held_invocations_of_a_service<T: InboxTable + ReadOnlyInboxTable>(table: &mut T) {
+    let invocation_id = InvocationId::mock_random();
+    let service_id = ServiceId::new("svc-1", "key-1");
+
+    table
+        .put_held_invocation("svc-1", 12, &HeldInvocation::Invocation(invocation_id))
+        .await;
+    table
+        .put_held_invocation("svc-2", 11, &HeldInvocation::Invocation(invocation_id))
+        .await;
+    table
+        .put_held_invocation("svc-1", 11, &HeldInvocation::Inbox(service_id.clone()))
+        .await;
+
+    assert_stream_eq(
+        table.held_invocations("svc-1"),
+        vec![
+            (11, HeldInvocation::Inbox(service_id)),
+            (12, HeldInvocation::Invocation(invocation_id)),
+        ],
+    )
+    .await;
+
+    table.delete_held_invocation("svc-1", 11).await;
+    table.delete_held_invocation("svc-1", 12).await;
+
+    assert_stream_eq(table.held_invocations("svc-1"), vec![]).await;
+}
+
 pub(crate) async fn run_tests(mut rocksdb: PartitionStore) {
     let mut txn = rocksdb.transaction();
     populate_data(&mut txn).await;
@@ -97,6 +126,7 @@ pub(crate) async fn run_tests(mut rocksdb: PartitionStore) {
     find_the_next_message_in_an_inbox(&mut txn).await;
     get_svc_inbox(&mut txn).await;
     delete_entry(&mut txn).await;
+    held_invocations_of_a_service(&mut txn).await;
 
     txn.commit().await.expect("should not fail");
 
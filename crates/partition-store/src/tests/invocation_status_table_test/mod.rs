@@ -88,6 +88,7 @@ fn invoked_status(invocation_target: InvocationTarget) -> InvocationStatus {
         source: Source::Ingress,
         completion_retention_duration: Duration::ZERO,
         idempotency_key: None,
+        current_invocation_epoch: 0,
     })
 }
 
@@ -102,6 +103,7 @@ fn suspended_status(invocation_target: InvocationTarget) -> InvocationStatus {
             source: Source::Ingress,
             completion_retention_duration: Duration::ZERO,
             idempotency_key: None,
+            current_invocation_epoch: 0,
         },
         waiting_for_completed_entries: HashSet::default(),
     }
@@ -164,9 +166,9 @@ async fn verify_all_svc_with_status_invoked<T: InvocationStatusTable>(txn: &mut
     assert_that!(
         actual,
         unordered_elements_are![
-            eq((*INVOCATION_ID_1, INVOCATION_TARGET_1.clone())),
-            eq((*INVOCATION_ID_2, INVOCATION_TARGET_2.clone())),
-            eq((*INVOCATION_ID_4, INVOCATION_TARGET_4.clone()))
+            eq((*INVOCATION_ID_1, INVOCATION_TARGET_1.clone(), 0)),
+            eq((*INVOCATION_ID_2, INVOCATION_TARGET_2.clone(), 0)),
+            eq((*INVOCATION_ID_4, INVOCATION_TARGET_4.clone(), 0))
         ]
     );
 }
@@ -237,3 +239,25 @@ async fn test_migration() {
         rocksdb.get_invocation_status(&invocation_id).await.unwrap()
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_paused_invocation_status() {
+    let mut rocksdb = storage_test_environment().await;
+    let mut txn = rocksdb.transaction();
+
+    let invocation_id = InvocationId::mock_generate(&INVOCATION_TARGET_1);
+    let status = InvocationStatus::Paused(InFlightInvocationMetadata::mock());
+    txn.put_invocation_status(&invocation_id, &status).await;
+
+    assert_eq!(
+        txn.get_invocation_status(&invocation_id).await.unwrap(),
+        status
+    );
+    // paused invocations must not be picked up again when a leader starts
+    assert!(txn
+        .all_invoked_invocations()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::storage_test_environment;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use restate_storage_api::kafka_sink_outbox_table::{
+    KafkaSinkOutboxTable, KafkaSinkRecord, ReadOnlyKafkaSinkOutboxTable,
+};
+use restate_storage_api::Transaction;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::InvocationTarget;
+
+fn mock_kafka_sink_record(sink_id: &str) -> KafkaSinkRecord {
+    let invocation_target = InvocationTarget::mock_service();
+    KafkaSinkRecord {
+        sink_id: sink_id.to_owned(),
+        invocation_id: InvocationId::mock_generate(&invocation_target),
+        invocation_target,
+        value: Bytes::from_static(b"{}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_kafka_sink_outbox() {
+    let mut rocksdb = storage_test_environment().await;
+
+    let orders_0 = mock_kafka_sink_record("orders");
+    let audit_1 = mock_kafka_sink_record("audit");
+    let orders_2 = mock_kafka_sink_record("orders");
+
+    let mut txn = rocksdb.transaction();
+    txn.put_kafka_sink_record(0, &orders_0).await;
+    txn.put_kafka_sink_record(1, &audit_1).await;
+    txn.put_kafka_sink_record(2, &orders_2).await;
+    txn.commit().await.unwrap();
+
+    // Ordered by sink first
+    assert_eq!(
+        rocksdb
+            .all_kafka_sink_records()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![(1, audit_1.clone()), (0, orders_0), (2, orders_2.clone())]
+    );
+
+    // Truncation only affects the given sink
+    let mut txn = rocksdb.transaction();
+    txn.truncate_kafka_sink_outbox("orders", 1).await.unwrap();
+    txn.commit().await.unwrap();
+
+    assert_eq!(
+        rocksdb
+            .all_kafka_sink_records()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![(1, audit_1), (2, orders_2)]
+    );
+}
@@ -26,12 +26,15 @@ use restate_types::invocation::{InvocationTarget, ServiceInvocation, Source};
 use restate_types::live::{Constant, Live};
 use restate_types::state_mut::ExternalStateMutation;
 
+mod dead_letter_table_test;
 mod idempotency_table_test;
 mod inbox_table_test;
 mod invocation_status_table_test;
 mod journal_table_test;
+mod kafka_sink_outbox_table_test;
 mod outbox_table_test;
 mod promise_table_test;
+mod schedule_table_test;
 mod snapshots_test;
 mod state_table_test;
 mod timer_table_test;
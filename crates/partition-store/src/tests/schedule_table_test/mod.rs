@@ -0,0 +1,90 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::storage_test_environment;
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus, ScheduleTable};
+use restate_storage_api::Transaction;
+use restate_types::identifiers::{InvocationId, InvocationUuid, WithPartitionKey};
+use restate_types::invocation::InvocationTarget;
+use restate_types::schema::schedules::Schedule;
+use restate_types::time::MillisSinceEpoch;
+
+fn mock_schedule_status(id: &str) -> ScheduleStatus {
+    let schedule = Schedule::new(
+        id.to_owned(),
+        "0 2 * * *".to_owned(),
+        "UTC".to_owned(),
+        InvocationTarget::mock_service(),
+        Bytes::from_static(b"{}"),
+    )
+    .unwrap();
+    ScheduleStatus {
+        last_invocation_id: Some(InvocationId::from_parts(
+            schedule.partition_key(),
+            InvocationUuid::from_u128(12345678900001),
+        )),
+        schedule,
+        next_fire_time: Some(MillisSinceEpoch::new(1000)),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_schedule_statuses() {
+    let mut rocksdb = storage_test_environment().await;
+
+    let status_1 = mock_schedule_status("nightly-billing");
+    let status_2 = mock_schedule_status("hourly-report");
+    let partition_key_1 = status_1.schedule.partition_key();
+    let partition_key_2 = status_2.schedule.partition_key();
+
+    let mut txn = rocksdb.transaction();
+    txn.put_schedule_status(partition_key_1, &status_1).await;
+    txn.put_schedule_status(partition_key_2, &status_2).await;
+    txn.commit().await.unwrap();
+
+    assert_eq!(
+        rocksdb
+            .get_schedule_status(partition_key_1, "nightly-billing")
+            .await
+            .unwrap(),
+        Some(status_1.clone())
+    );
+    assert_eq!(
+        rocksdb
+            .all_schedule_statuses(partition_key_1..=partition_key_1)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![(partition_key_1, status_1)]
+    );
+
+    let mut txn = rocksdb.transaction();
+    txn.delete_schedule_status(partition_key_1, "nightly-billing")
+        .await;
+    txn.commit().await.unwrap();
+
+    assert_eq!(
+        rocksdb
+            .get_schedule_status(partition_key_1, "nightly-billing")
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        rocksdb
+            .get_schedule_status(partition_key_2, "hourly-report")
+            .await
+            .unwrap(),
+        Some(status_2)
+    );
+}
@@ -2,7 +2,10 @@ use std::ops::RangeInclusive;
 use std::time::SystemTime;
 use tempfile::tempdir;
 
-use crate::snapshots::{LocalPartitionSnapshot, PartitionSnapshotMetadata, SnapshotFormatVersion};
+use crate::snapshots::{
+    LocalPartitionSnapshot, PartitionSnapshotMetadata, SnapshotFormatVersion,
+    SNAPSHOT_METADATA_FILE_NAME,
+};
 use crate::{PartitionStore, PartitionStoreManager};
 use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
 use restate_storage_api::Transaction;
@@ -20,7 +23,10 @@ pub(crate) async fn run_tests(manager: PartitionStoreManager, mut partition_stor
     let partition_id = partition_store.partition_id();
     let path_buf = snapshots_dir.path().to_path_buf().join("sn1");
 
-    let snapshot = partition_store.create_snapshot(path_buf).await.unwrap();
+    let snapshot = partition_store
+        .create_snapshot(path_buf.clone())
+        .await
+        .unwrap();
 
     let snapshot_meta = PartitionSnapshotMetadata {
         version: SnapshotFormatVersion::V1,
@@ -35,20 +41,21 @@ pub(crate) async fn run_tests(manager: PartitionStoreManager, mut partition_stor
         files: snapshot.files.clone(),
     };
     let metadata_json = serde_json::to_string_pretty(&snapshot_meta).unwrap();
+    tokio::fs::write(path_buf.join(SNAPSHOT_METADATA_FILE_NAME), metadata_json)
+        .await
+        .unwrap();
 
     drop(partition_store);
     drop(snapshot);
 
     manager.drop_partition(partition_id).await;
+    assert!(!manager.partition_store_exists(partition_id));
 
-    let snapshot_meta: PartitionSnapshotMetadata = serde_json::from_str(&metadata_json).unwrap();
-
-    let snapshot = LocalPartitionSnapshot {
-        base_dir: snapshots_dir.path().into(),
-        min_applied_lsn: snapshot_meta.min_applied_lsn,
-        db_comparator_name: snapshot_meta.db_comparator_name.clone(),
-        files: snapshot_meta.files.clone(),
-    };
+    let snapshot = LocalPartitionSnapshot::find_latest(partition_id, snapshots_dir.path())
+        .await
+        .unwrap()
+        .expect("snapshot exists");
+    assert_eq!(Lsn::new(100), snapshot.min_applied_lsn);
 
     let worker_options = Live::from_value(WorkerOptions::default());
 
@@ -111,6 +111,15 @@ fn exclusive_start_key_range(
                     },
                 }
             }
+            TimerKeyKind::FireSchedule { invocation_uuid } => {
+                let incremented_invocation_uuid = increment_invocation_uuid(invocation_uuid);
+                TimerKey {
+                    timestamp: timer_key.timestamp,
+                    kind: TimerKeyKind::FireSchedule {
+                        invocation_uuid: incremented_invocation_uuid,
+                    },
+                }
+            }
         };
 
         let lower_bound = write_timer_key(partition_id, &next_timer_key);
@@ -288,6 +297,21 @@ mod tests {
         assert_eq!(got, key);
     }
 
+    #[test]
+    fn round_trip_fire_schedule() {
+        let key = TimerKey {
+            kind: TimerKeyKind::FireSchedule {
+                invocation_uuid: FIXTURE_INVOCATION,
+            },
+            timestamp: 87654321,
+        };
+
+        let key_bytes = write_timer_key(PartitionId::from(1337), &key).serialize();
+        let got = timer_key_from_key_slice(&key_bytes).expect("should not fail");
+
+        assert_eq!(got, key);
+    }
+
     #[test]
     fn test_lexicographical_sorting_by_timestamp() {
         let kinds = [
@@ -304,6 +328,9 @@ mod tests {
             TimerKeyKind::NeoInvoke {
                 invocation_uuid: FIXTURE_INVOCATION,
             },
+            TimerKeyKind::FireSchedule {
+                invocation_uuid: FIXTURE_INVOCATION,
+            },
         ];
 
         for first_kind in &kinds {
@@ -516,6 +543,9 @@ mod tests {
                         invocation_uuid: InvocationUuid::mock_random(),
                     }
                 }
+                TimerKeyKindDiscriminants::FireSchedule => TimerKeyKind::FireSchedule {
+                    invocation_uuid: InvocationUuid::mock_random(),
+                },
             }
         };
 
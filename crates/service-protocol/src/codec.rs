@@ -364,9 +364,15 @@ mod test_util {
             }
         }
 
-        fn serialize_input_entry(InputEntry { value }: InputEntry) -> Bytes {
+        fn serialize_input_entry(InputEntry { headers, value }: InputEntry) -> Bytes {
             InputEntryMessage {
-                headers: vec![],
+                headers: headers
+                    .into_iter()
+                    .map(|h| service_protocol::Header {
+                        key: h.name.to_string(),
+                        value: h.value.to_string(),
+                    })
+                    .collect(),
                 value,
                 ..Default::default()
             }
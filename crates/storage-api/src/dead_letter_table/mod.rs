@@ -0,0 +1,59 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::{protobuf_storage_encode_decode, Result};
+
+use bytes::Bytes;
+use futures_util::Stream;
+use restate_types::errors::InvocationError;
+use restate_types::identifiers::{EntryIndex, InvocationId, PartitionKey};
+use restate_types::invocation::{Header, InvocationTarget};
+use restate_types::time::MillisSinceEpoch;
+use std::future::Future;
+use std::ops::RangeInclusive;
+
+/// Invocation which exhausted all its attempts, recorded together with its input so that it can be
+/// replayed or discarded later on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub invocation_target: InvocationTarget,
+    pub argument: Bytes,
+    pub headers: Vec<Header>,
+    pub last_error: InvocationError,
+    pub journal_length: EntryIndex,
+    pub dead_lettered_at: MillisSinceEpoch,
+}
+
+protobuf_storage_encode_decode!(DeadLetter);
+
+pub trait ReadOnlyDeadLetterTable {
+    fn get_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> impl Future<Output = Result<Option<DeadLetter>>> + Send;
+
+    fn all_dead_letters(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> impl Stream<Item = Result<(InvocationId, DeadLetter)>> + Send;
+}
+
+pub trait DeadLetterTable: ReadOnlyDeadLetterTable {
+    fn put_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+        dead_letter: &DeadLetter,
+    ) -> impl Future<Output = ()> + Send;
+
+    fn delete_dead_letter(
+        &mut self,
+        invocation_id: &InvocationId,
+    ) -> impl Future<Output = ()> + Send;
+}
@@ -9,10 +9,14 @@
 // by the Apache License, Version 2.0.
 
 use crate::{protobuf_storage_encode_decode, Result};
+use bytestring::ByteString;
 use futures_util::FutureExt;
+use restate_types::invocation::InvocationTarget;
 use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
+use restate_types::schema::kafka_sinks::KafkaSinkSource;
 use restate_types::storage::{StorageDecode, StorageEncode};
+use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
 
 #[derive(Debug, Clone, Copy, derive_more::From, derive_more::Into)]
@@ -20,11 +24,75 @@ pub struct SequenceNumber(u64);
 
 protobuf_storage_encode_decode!(SequenceNumber);
 
+/// Names of the services which are paused on a partition.
+#[derive(Debug, Clone, Default, PartialEq, Eq, derive_more::From)]
+pub struct PausedServices(BTreeSet<ByteString>);
+
+impl PausedServices {
+    pub fn contains(&self, service_name: &str) -> bool {
+        self.0.contains(service_name)
+    }
+
+    /// Returns `true` if the service was not paused before.
+    pub fn insert(&mut self, service_name: ByteString) -> bool {
+        self.0.insert(service_name)
+    }
+
+    /// Returns `true` if the service was paused before.
+    pub fn remove(&mut self, service_name: &str) -> bool {
+        self.0.remove(service_name)
+    }
+
+    pub fn into_inner(self) -> BTreeSet<ByteString> {
+        self.0
+    }
+}
+
+protobuf_storage_encode_decode!(PausedServices);
+
+/// Kafka sinks registered on a partition, by sink id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, derive_more::From)]
+pub struct KafkaSinkRoutes(BTreeMap<String, KafkaSinkSource>);
+
+impl KafkaSinkRoutes {
+    pub fn register(&mut self, sink_id: String, source: KafkaSinkSource) {
+        self.0.insert(sink_id, source);
+    }
+
+    /// Returns `true` if the sink was registered before.
+    pub fn remove(&mut self, sink_id: &str) -> bool {
+        self.0.remove(sink_id).is_some()
+    }
+
+    /// Ids of the sinks publishing the outputs of the given target.
+    pub fn matching_sinks<'a>(
+        &'a self,
+        invocation_target: &'a InvocationTarget,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(|(_, source)| source.matches(invocation_target))
+            .map(|(sink_id, _)| sink_id.as_str())
+    }
+
+    pub fn into_inner(self) -> BTreeMap<String, KafkaSinkSource> {
+        self.0
+    }
+}
+
+protobuf_storage_encode_decode!(KafkaSinkRoutes);
+
 mod fsm_variable {
     pub(crate) const INBOX_SEQ_NUMBER: u64 = 0;
     pub(crate) const OUTBOX_SEQ_NUMBER: u64 = 1;
 
     pub(crate) const APPLIED_LSN: u64 = 2;
+
+    pub(crate) const PAUSED_SERVICES: u64 = 3;
+
+    pub(crate) const KAFKA_SINK_ROUTES: u64 = 4;
+
+    pub(crate) const KAFKA_SINK_OUTBOX_SEQ_NUMBER: u64 = 5;
 }
 
 pub trait ReadOnlyFsmTable {
@@ -42,6 +110,13 @@ pub trait ReadOnlyFsmTable {
             .map(|result| result.map(|seq_number| seq_number.map(Into::into).unwrap_or_default()))
     }
 
+    fn get_kafka_sink_outbox_seq_number(
+        &mut self,
+    ) -> impl Future<Output = Result<MessageIndex>> + Send + '_ {
+        self.get::<SequenceNumber>(fsm_variable::KAFKA_SINK_OUTBOX_SEQ_NUMBER)
+            .map(|result| result.map(|seq_number| seq_number.map(Into::into).unwrap_or_default()))
+    }
+
     fn get_applied_lsn(&mut self) -> impl Future<Output = Result<Option<Lsn>>> + Send + '_ {
         self.get::<SequenceNumber>(fsm_variable::APPLIED_LSN)
             .map(|result| {
@@ -49,6 +124,18 @@ pub trait ReadOnlyFsmTable {
                     .map(|seq_number| seq_number.map(|seq_number| Lsn::from(u64::from(seq_number))))
             })
     }
+
+    fn get_paused_services(&mut self) -> impl Future<Output = Result<PausedServices>> + Send + '_ {
+        self.get::<PausedServices>(fsm_variable::PAUSED_SERVICES)
+            .map(|result| result.map(Option::unwrap_or_default))
+    }
+
+    fn get_kafka_sink_routes(
+        &mut self,
+    ) -> impl Future<Output = Result<KafkaSinkRoutes>> + Send + '_ {
+        self.get::<KafkaSinkRoutes>(fsm_variable::KAFKA_SINK_ROUTES)
+            .map(|result| result.map(Option::unwrap_or_default))
+    }
 }
 
 pub trait FsmTable: ReadOnlyFsmTable {
@@ -77,6 +164,20 @@ pub trait FsmTable: ReadOnlyFsmTable {
         )
     }
 
+    fn put_paused_services(
+        &mut self,
+        paused_services: PausedServices,
+    ) -> impl Future<Output = ()> + Send {
+        self.put(fsm_variable::PAUSED_SERVICES, paused_services)
+    }
+
+    fn put_kafka_sink_routes(
+        &mut self,
+        kafka_sink_routes: KafkaSinkRoutes,
+    ) -> impl Future<Output = ()> + Send {
+        self.put(fsm_variable::KAFKA_SINK_ROUTES, kafka_sink_routes)
+    }
+
     fn put_outbox_seq_number(
         &mut self,
         seq_number: MessageIndex,
@@ -86,4 +187,14 @@ pub trait FsmTable: ReadOnlyFsmTable {
             SequenceNumber::from(seq_number),
         )
     }
+
+    fn put_kafka_sink_outbox_seq_number(
+        &mut self,
+        seq_number: MessageIndex,
+    ) -> impl Future<Output = ()> + Send {
+        self.put(
+            fsm_variable::KAFKA_SINK_OUTBOX_SEQ_NUMBER,
+            SequenceNumber::from(seq_number),
+        )
+    }
 }
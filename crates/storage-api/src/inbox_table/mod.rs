@@ -80,6 +80,20 @@ impl SequenceNumberInboxEntry {
     }
 }
 
+/// Invocation of a paused service which is held back on the partition until the service is
+/// resumed. Held invocations are indexed by service, so that resuming a service doesn't need to
+/// scan all invocations of the partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeldInvocation {
+    /// Invocation which has not been started, it is stored as scheduled invocation without
+    /// execution time.
+    Invocation(InvocationId),
+    /// Virtual object whose inbox has not been consumed.
+    Inbox(ServiceId),
+}
+
+protobuf_storage_encode_decode!(HeldInvocation);
+
 pub trait ReadOnlyInboxTable {
     fn peek_inbox(
         &mut self,
@@ -95,6 +109,12 @@ pub trait ReadOnlyInboxTable {
         &self,
         range: RangeInclusive<PartitionKey>,
     ) -> impl Stream<Item = Result<SequenceNumberInboxEntry>> + Send;
+
+    /// Held invocations of the given service, in the order in which they have been held.
+    fn held_invocations(
+        &mut self,
+        service_name: &str,
+    ) -> impl Stream<Item = Result<(MessageIndex, HeldInvocation)>> + Send;
 }
 
 pub trait InboxTable: ReadOnlyPromiseTable {
@@ -114,4 +134,17 @@ pub trait InboxTable: ReadOnlyPromiseTable {
         &mut self,
         service_id: &ServiceId,
     ) -> impl Future<Output = Result<Option<SequenceNumberInboxEntry>>> + Send;
+
+    fn put_held_invocation(
+        &mut self,
+        service_name: &str,
+        sequence_number: MessageIndex,
+        held_invocation: &HeldInvocation,
+    ) -> impl Future<Output = ()> + Send;
+
+    fn delete_held_invocation(
+        &mut self,
+        service_name: &str,
+        sequence_number: MessageIndex,
+    ) -> impl Future<Output = ()> + Send;
 }
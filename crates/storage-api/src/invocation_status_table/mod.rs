@@ -15,7 +15,7 @@ use futures_util::Stream;
 use restate_types::deployment::PinnedDeployment;
 use restate_types::identifiers::{EntryIndex, InvocationId, PartitionKey};
 use restate_types::invocation::{
-    Header, InvocationInput, InvocationTarget, ResponseResult, ServiceInvocation,
+    Header, InvocationEpoch, InvocationInput, InvocationTarget, ResponseResult, ServiceInvocation,
     ServiceInvocationResponseSink, ServiceInvocationSpanContext, Source,
 };
 use restate_types::time::MillisSinceEpoch;
@@ -189,6 +189,9 @@ pub enum InvocationStatus {
         metadata: InFlightInvocationMetadata,
         waiting_for_completed_entries: HashSet<EntryIndex>,
     },
+    /// Invocation has been paused by the user. It is not running, and it won't be invoked again
+    /// until it is explicitly resumed.
+    Paused(InFlightInvocationMetadata),
     Completed(CompletedInvocation),
     /// Service instance is currently not invoked
     #[default]
@@ -203,6 +206,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.invocation_target),
             InvocationStatus::Invoked(metadata) => Some(&metadata.invocation_target),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.invocation_target),
+            InvocationStatus::Paused(metadata) => Some(&metadata.invocation_target),
             InvocationStatus::Completed(completed) => Some(&completed.invocation_target),
             _ => None,
         }
@@ -215,6 +219,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => metadata.metadata.idempotency_key.as_ref(),
             InvocationStatus::Invoked(metadata) => metadata.idempotency_key.as_ref(),
             InvocationStatus::Suspended { metadata, .. } => metadata.idempotency_key.as_ref(),
+            InvocationStatus::Paused(metadata) => metadata.idempotency_key.as_ref(),
             InvocationStatus::Completed(completed) => completed.idempotency_key.as_ref(),
             _ => None,
         }
@@ -225,6 +230,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata.journal_metadata),
             _ => None,
         }
     }
@@ -234,6 +240,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(&metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(&metadata.journal_metadata),
             _ => None,
         }
     }
@@ -243,6 +250,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.journal_metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.journal_metadata),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.journal_metadata),
             _ => None,
         }
     }
@@ -252,6 +260,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -261,6 +270,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -270,6 +280,7 @@ impl InvocationStatus {
         match self {
             InvocationStatus::Invoked(metadata) => Some(metadata),
             InvocationStatus::Suspended { metadata, .. } => Some(metadata),
+            InvocationStatus::Paused(metadata) => Some(metadata),
             _ => None,
         }
     }
@@ -283,6 +294,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&mut metadata.metadata.response_sinks),
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.response_sinks),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.response_sinks),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.response_sinks),
             _ => None,
         }
     }
@@ -294,6 +306,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.response_sinks),
             InvocationStatus::Invoked(metadata) => Some(&metadata.response_sinks),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.response_sinks),
+            InvocationStatus::Paused(metadata) => Some(&metadata.response_sinks),
             _ => None,
         }
     }
@@ -305,6 +318,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&metadata.metadata.timestamps),
             InvocationStatus::Invoked(metadata) => Some(&metadata.timestamps),
             InvocationStatus::Suspended { metadata, .. } => Some(&metadata.timestamps),
+            InvocationStatus::Paused(metadata) => Some(&metadata.timestamps),
             InvocationStatus::Completed(completed) => Some(&completed.timestamps),
             _ => None,
         }
@@ -317,6 +331,7 @@ impl InvocationStatus {
             InvocationStatus::Inboxed(metadata) => Some(&mut metadata.metadata.timestamps),
             InvocationStatus::Invoked(metadata) => Some(&mut metadata.timestamps),
             InvocationStatus::Suspended { metadata, .. } => Some(&mut metadata.timestamps),
+            InvocationStatus::Paused(metadata) => Some(&mut metadata.timestamps),
             InvocationStatus::Completed(completed) => Some(&mut completed.timestamps),
             _ => None,
         }
@@ -447,6 +462,8 @@ pub struct InFlightInvocationMetadata {
     /// If zero, the invocation completion will not be retained.
     pub completion_retention_duration: Duration,
     pub idempotency_key: Option<ByteString>,
+    /// Incremented every time the invocation is restarted, effects of earlier epochs are dropped.
+    pub current_invocation_epoch: InvocationEpoch,
 }
 
 impl InFlightInvocationMetadata {
@@ -470,6 +487,7 @@ impl InFlightInvocationMetadata {
                 completion_retention_duration: pre_flight_invocation_metadata
                     .completion_retention_duration,
                 idempotency_key: pre_flight_invocation_metadata.idempotency_key,
+                current_invocation_epoch: 0,
             },
             InvocationInput {
                 argument: pre_flight_invocation_metadata.argument,
@@ -535,7 +553,7 @@ pub trait ReadOnlyInvocationStatusTable {
 
     fn all_invoked_invocations(
         &mut self,
-    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget)>> + Send;
+    ) -> impl Stream<Item = Result<(InvocationId, InvocationTarget, InvocationEpoch)>> + Send;
 
     fn all_invocation_statuses(
         &self,
@@ -578,6 +596,7 @@ mod test_util {
                 source: Source::Ingress,
                 completion_retention_duration: Duration::ZERO,
                 idempotency_key: None,
+                current_invocation_epoch: 0,
             }
         }
     }
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::{protobuf_storage_encode_decode, Result};
+
+use bytes::Bytes;
+use futures_util::Stream;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::InvocationTarget;
+use restate_types::message::MessageIndex;
+use std::future::Future;
+
+/// Output of a completed invocation, to be produced to the Kafka topic of the given sink.
+///
+/// Records are kept apart from the outbox and indexed by sink, so that an unavailable Kafka
+/// cluster holds back neither the messages to the other partitions nor the records of the other
+/// sinks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaSinkRecord {
+    pub sink_id: String,
+    pub invocation_id: InvocationId,
+    pub invocation_target: InvocationTarget,
+    pub value: Bytes,
+}
+
+protobuf_storage_encode_decode!(KafkaSinkRecord);
+
+pub trait ReadOnlyKafkaSinkOutboxTable {
+    /// Records of all the sinks, ordered by sink and then by index.
+    fn all_kafka_sink_records(
+        &mut self,
+    ) -> impl Stream<Item = Result<(MessageIndex, KafkaSinkRecord)>> + Send;
+}
+
+pub trait KafkaSinkOutboxTable: ReadOnlyKafkaSinkOutboxTable {
+    fn put_kafka_sink_record(
+        &mut self,
+        record_index: MessageIndex,
+        record: &KafkaSinkRecord,
+    ) -> impl Future<Output = ()> + Send;
+
+    /// Deletes the records of the sink up to, and including, the given index.
+    fn truncate_kafka_sink_outbox(
+        &mut self,
+        sink_id: &str,
+        record_index: MessageIndex,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
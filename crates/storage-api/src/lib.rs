@@ -25,14 +25,17 @@ pub enum StorageError {
 
 pub type Result<T> = std::result::Result<T, StorageError>;
 
+pub mod dead_letter_table;
 pub mod deduplication_table;
 pub mod fsm_table;
 pub mod idempotency_table;
 pub mod inbox_table;
 pub mod invocation_status_table;
 pub mod journal_table;
+pub mod kafka_sink_outbox_table;
 pub mod outbox_table;
 pub mod promise_table;
+pub mod schedule_table;
 pub mod service_status_table;
 pub mod state_table;
 mod storage;
@@ -58,6 +61,9 @@ pub trait Transaction:
     + timer_table::TimerTable
     + idempotency_table::IdempotencyTable
     + promise_table::PromiseTable
+    + dead_letter_table::DeadLetterTable
+    + schedule_table::ScheduleTable
+    + kafka_sink_outbox_table::KafkaSinkOutboxTable
     + Send
 {
     fn commit(self) -> impl Future<Output = Result<()>> + Send;
@@ -0,0 +1,58 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::{protobuf_storage_encode_decode, Result};
+
+use futures_util::Stream;
+use restate_types::identifiers::{InvocationId, PartitionKey};
+use restate_types::schema::schedules::Schedule;
+use restate_types::time::MillisSinceEpoch;
+use std::future::Future;
+use std::ops::RangeInclusive;
+
+/// Schedule owned by the partition, together with the state needed to fire it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleStatus {
+    pub schedule: Schedule,
+    /// Time of the next occurrence, for which a timer is registered. `None` if the cron
+    /// expression has no further occurrences.
+    pub next_fire_time: Option<MillisSinceEpoch>,
+    /// Invocation fired by the last occurrence which wasn't skipped.
+    pub last_invocation_id: Option<InvocationId>,
+}
+
+protobuf_storage_encode_decode!(ScheduleStatus);
+
+pub trait ReadOnlyScheduleTable {
+    fn get_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_id: &str,
+    ) -> impl Future<Output = Result<Option<ScheduleStatus>>> + Send;
+
+    fn all_schedule_statuses(
+        &self,
+        range: RangeInclusive<PartitionKey>,
+    ) -> impl Stream<Item = Result<(PartitionKey, ScheduleStatus)>> + Send;
+}
+
+pub trait ScheduleTable: ReadOnlyScheduleTable {
+    fn put_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_status: &ScheduleStatus,
+    ) -> impl Future<Output = ()> + Send;
+
+    fn delete_schedule_status(
+        &mut self,
+        partition_key: PartitionKey,
+        schedule_id: &str,
+    ) -> impl Future<Output = ()> + Send;
+}
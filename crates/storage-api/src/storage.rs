@@ -71,7 +71,7 @@ pub mod v1 {
     ));
 
     pub mod pb_conversion {
-        use std::collections::HashSet;
+        use std::collections::{BTreeMap, BTreeSet, HashSet};
         use std::str::FromStr;
 
         use anyhow::anyhow;
@@ -96,14 +96,16 @@ pub mod v1 {
             Ingress, PartitionProcessor, ResponseSink,
         };
         use crate::storage::v1::{
-            enriched_entry_header, entry_result, inbox_entry, invocation_resolution_result,
-            invocation_status, invocation_status_v2, invocation_target, outbox_message, promise,
-            response_result, source, span_relation, submit_notification_sink, timer,
-            virtual_object_status, BackgroundCallResolutionResult, DedupSequenceNumber, Duration,
-            EnrichedEntryHeader, EntryResult, EpochSequenceNumber, Header, IdempotencyMetadata,
-            InboxEntry, InvocationId, InvocationResolutionResult, InvocationStatus,
-            InvocationStatusV2, InvocationTarget, JournalEntry, JournalEntryId, JournalMeta,
-            KvPair, OutboxMessage, Promise, ResponseResult, SequenceNumber, ServiceId,
+            enriched_entry_header, entry_result, held_invocation, inbox_entry,
+            invocation_resolution_result, invocation_status, invocation_status_v2,
+            invocation_target, kafka_sink_routes, outbox_message, promise, response_result, source,
+            span_relation, submit_notification_sink, timer, virtual_object_status,
+            BackgroundCallResolutionResult, DeadLetter, DedupSequenceNumber, Duration,
+            EnrichedEntryHeader, EntryResult, EpochSequenceNumber, Header, HeldInvocation,
+            IdempotencyMetadata, InboxEntry, InvocationId, InvocationResolutionResult,
+            InvocationStatus, InvocationStatusV2, InvocationTarget, JournalEntry, JournalEntryId,
+            JournalMeta, KafkaSinkRecord, KafkaSinkRoutes, KvPair, OutboxMessage, PausedServices,
+            Promise, ResponseResult, Schedule, ScheduleStatus, SequenceNumber, ServiceId,
             ServiceInvocation, ServiceInvocationResponseSink, Source, SpanContext, SpanRelation,
             StateMutation, SubmitNotificationSink, Timer, VirtualObjectStatus,
         };
@@ -112,6 +114,7 @@ pub mod v1 {
         use restate_types::identifiers::{WithInvocationId, WithPartitionKey};
         use restate_types::invocation::{InvocationTermination, TerminationFlavor};
         use restate_types::journal::enriched::AwakeableEnrichmentResult;
+        use restate_types::schema::kafka_sinks::KafkaSinkSource;
         use restate_types::service_protocol::ServiceProtocolVersion;
         use restate_types::storage::{
             StorageCodecKind, StorageDecode, StorageDecodeError, StorageEncode, StorageEncodeError,
@@ -322,6 +325,7 @@ pub mod v1 {
                     journal_length,
                     deployment_id,
                     service_protocol_version,
+                    current_invocation_epoch,
                     waiting_for_completed_entries,
                     result,
                 } = value;
@@ -416,6 +420,7 @@ pub mod v1 {
                                     .unwrap_or_default()
                                     .try_into()?,
                                 idempotency_key: idempotency_key.map(ByteString::from),
+                                current_invocation_epoch,
                             },
                         ))
                     }
@@ -438,12 +443,36 @@ pub mod v1 {
                                     .unwrap_or_default()
                                     .try_into()?,
                                 idempotency_key: idempotency_key.map(ByteString::from),
+                                current_invocation_epoch,
                             },
                             waiting_for_completed_entries: waiting_for_completed_entries
                                 .into_iter()
                                 .collect(),
                         },
                     ),
+                    invocation_status_v2::Status::Paused => {
+                        Ok(crate::invocation_status_table::InvocationStatus::Paused(
+                            crate::invocation_status_table::InFlightInvocationMetadata {
+                                response_sinks,
+                                timestamps,
+                                invocation_target,
+                                journal_metadata: crate::invocation_status_table::JournalMetadata {
+                                    length: journal_length,
+                                    span_context: expect_or_fail!(span_context)?.try_into()?,
+                                },
+                                pinned_deployment: derive_pinned_deployment(
+                                    deployment_id,
+                                    service_protocol_version,
+                                )?,
+                                source,
+                                completion_retention_duration: completion_retention_duration
+                                    .unwrap_or_default()
+                                    .try_into()?,
+                                idempotency_key: idempotency_key.map(ByteString::from),
+                                current_invocation_epoch,
+                            },
+                        ))
+                    }
                     invocation_status_v2::Status::Completed => {
                         Ok(crate::invocation_status_table::InvocationStatus::Completed(
                             crate::invocation_status_table::CompletedInvocation {
@@ -518,6 +547,7 @@ pub mod v1 {
                         journal_length: 0,
                         deployment_id: None,
                         service_protocol_version: None,
+                        current_invocation_epoch: 0,
                         waiting_for_completed_entries: vec![],
                         result: None,
                     },
@@ -570,6 +600,7 @@ pub mod v1 {
                         journal_length: 0,
                         deployment_id: None,
                         service_protocol_version: None,
+                        current_invocation_epoch: 0,
                         waiting_for_completed_entries: vec![],
                         result: None,
                     },
@@ -583,6 +614,7 @@ pub mod v1 {
                             source,
                             completion_retention_duration,
                             idempotency_key,
+                            current_invocation_epoch,
                         },
                     ) => {
                         let (deployment_id, service_protocol_version) = match pinned_deployment {
@@ -631,6 +663,7 @@ pub mod v1 {
                             journal_length: journal_metadata.length,
                             deployment_id,
                             service_protocol_version,
+                            current_invocation_epoch,
                             waiting_for_completed_entries: vec![],
                             result: None,
                         }
@@ -646,6 +679,7 @@ pub mod v1 {
                                 source,
                                 completion_retention_duration,
                                 idempotency_key,
+                                current_invocation_epoch,
                             },
                         waiting_for_completed_entries,
                     } => {
@@ -695,6 +729,7 @@ pub mod v1 {
                             journal_length: journal_metadata.length,
                             deployment_id,
                             service_protocol_version,
+                            current_invocation_epoch,
                             waiting_for_completed_entries: waiting_for_completed_entries
                                 .into_iter()
                                 .collect(),
@@ -740,9 +775,19 @@ pub mod v1 {
                         journal_length: 0,
                         deployment_id: None,
                         service_protocol_version: None,
+                        current_invocation_epoch: 0,
                         waiting_for_completed_entries: vec![],
                         result: Some(response_result.into()),
                     },
+                    crate::invocation_status_table::InvocationStatus::Paused(metadata) => {
+                        // Paused invocations carry the same information as invoked ones
+                        InvocationStatusV2 {
+                            status: invocation_status_v2::Status::Paused.into(),
+                            ..InvocationStatusV2::from(
+                                crate::invocation_status_table::InvocationStatus::Invoked(metadata),
+                            )
+                        }
+                    }
                     crate::invocation_status_table::InvocationStatus::Free => {
                         panic!("Unexpected serialization of Free status. This is a bug of the invocation status table")
                     }
@@ -829,6 +874,9 @@ pub mod v1 {
                     crate::invocation_status_table::InvocationStatus::Scheduled(_) => {
                         panic!("Unexpected conversion to old InvocationStatus when using Scheduled variant. This is a bug in the table implementation.")
                     }
+                    crate::invocation_status_table::InvocationStatus::Paused(_) => {
+                        panic!("Unexpected conversion to old InvocationStatus when using Paused variant. This is a bug in the table implementation.")
+                    }
                 };
 
                 InvocationStatus {
@@ -924,6 +972,7 @@ pub mod v1 {
                     source,
                     completion_retention_duration: completion_retention_time,
                     idempotency_key,
+                    current_invocation_epoch: 0,
                 })
             }
         }
@@ -939,6 +988,7 @@ pub mod v1 {
                     source,
                     completion_retention_duration: completion_retention_time,
                     idempotency_key,
+                    current_invocation_epoch: _,
                 } = value;
 
                 let (deployment_id, service_protocol_version) = match pinned_deployment {
@@ -1034,6 +1084,7 @@ pub mod v1 {
                         source: caller,
                         completion_retention_duration: completion_retention_time,
                         idempotency_key,
+                        current_invocation_epoch: 0,
                     },
                     waiting_for_completed_entries,
                 ))
@@ -1383,6 +1434,42 @@ pub mod v1 {
             }
         }
 
+        impl TryFrom<HeldInvocation> for crate::inbox_table::HeldInvocation {
+            type Error = ConversionError;
+
+            fn try_from(value: HeldInvocation) -> Result<Self, Self::Error> {
+                Ok(
+                    match value.held.ok_or(ConversionError::missing_field("held"))? {
+                        held_invocation::Held::InvocationId(invocation_id) => {
+                            crate::inbox_table::HeldInvocation::Invocation(
+                                restate_types::identifiers::InvocationId::try_from(invocation_id)?,
+                            )
+                        }
+                        held_invocation::Held::Inbox(service_id) => {
+                            crate::inbox_table::HeldInvocation::Inbox(
+                                restate_types::identifiers::ServiceId::try_from(service_id)?,
+                            )
+                        }
+                    },
+                )
+            }
+        }
+
+        impl From<crate::inbox_table::HeldInvocation> for HeldInvocation {
+            fn from(value: crate::inbox_table::HeldInvocation) -> Self {
+                let held = match value {
+                    crate::inbox_table::HeldInvocation::Invocation(invocation_id) => {
+                        held_invocation::Held::InvocationId(InvocationId::from(invocation_id))
+                    }
+                    crate::inbox_table::HeldInvocation::Inbox(service_id) => {
+                        held_invocation::Held::Inbox(ServiceId::from(service_id))
+                    }
+                };
+
+                HeldInvocation { held: Some(held) }
+            }
+        }
+
         impl From<crate::inbox_table::InboxEntry> for InboxEntry {
             fn from(inbox_entry: crate::inbox_table::InboxEntry) -> Self {
                 let inbox_entry = match inbox_entry {
@@ -2635,6 +2722,16 @@ pub mod v1 {
                                 )?,
                             )
                         }
+                        timer::Value::FireSchedule(fire_schedule) => {
+                            crate::timer_table::Timer::FireSchedule(
+                                restate_types::identifiers::InvocationId::try_from(
+                                    fire_schedule
+                                        .invocation_id
+                                        .ok_or(ConversionError::missing_field("invocation_id"))?,
+                                )?,
+                                fire_schedule.schedule_id,
+                            )
+                        }
                     },
                 )
             }
@@ -2662,6 +2759,12 @@ pub mod v1 {
                                 invocation_id: Some(InvocationId::from(invocation_id)),
                             })
                         }
+                        crate::timer_table::Timer::FireSchedule(invocation_id, schedule_id) => {
+                            timer::Value::FireSchedule(timer::FireSchedule {
+                                invocation_id: Some(InvocationId::from(invocation_id)),
+                                schedule_id,
+                            })
+                        }
                     }),
                 }
             }
@@ -2829,5 +2932,202 @@ pub mod v1 {
                 Self::from(value.sequence_number)
             }
         }
+
+        impl From<crate::fsm_table::PausedServices> for PausedServices {
+            fn from(value: crate::fsm_table::PausedServices) -> Self {
+                PausedServices {
+                    service_names: value
+                        .into_inner()
+                        .into_iter()
+                        .map(|service_name| service_name.to_string())
+                        .collect(),
+                }
+            }
+        }
+
+        impl From<PausedServices> for crate::fsm_table::PausedServices {
+            fn from(value: PausedServices) -> Self {
+                Self::from(
+                    value
+                        .service_names
+                        .into_iter()
+                        .map(ByteString::from)
+                        .collect::<BTreeSet<_>>(),
+                )
+            }
+        }
+
+        impl From<crate::fsm_table::KafkaSinkRoutes> for KafkaSinkRoutes {
+            fn from(value: crate::fsm_table::KafkaSinkRoutes) -> Self {
+                KafkaSinkRoutes {
+                    routes: value
+                        .into_inner()
+                        .into_iter()
+                        .map(|(sink_id, source)| match source {
+                            KafkaSinkSource::Service { name, handler } => {
+                                kafka_sink_routes::Route {
+                                    sink_id,
+                                    service_name: name,
+                                    handler_name: handler,
+                                }
+                            }
+                        })
+                        .collect(),
+                }
+            }
+        }
+
+        impl From<KafkaSinkRoutes> for crate::fsm_table::KafkaSinkRoutes {
+            fn from(value: KafkaSinkRoutes) -> Self {
+                Self::from(
+                    value
+                        .routes
+                        .into_iter()
+                        .map(|route| {
+                            (
+                                route.sink_id,
+                                KafkaSinkSource::Service {
+                                    name: route.service_name,
+                                    handler: route.handler_name,
+                                },
+                            )
+                        })
+                        .collect::<BTreeMap<_, _>>(),
+                )
+            }
+        }
+
+        impl From<crate::dead_letter_table::DeadLetter> for DeadLetter {
+            fn from(value: crate::dead_letter_table::DeadLetter) -> Self {
+                DeadLetter {
+                    invocation_target: Some(InvocationTarget::from(value.invocation_target)),
+                    argument: value.argument,
+                    headers: value.headers.into_iter().map(Into::into).collect(),
+                    failure_code: value.last_error.code().into(),
+                    failure_message: Bytes::copy_from_slice(value.last_error.message().as_ref()),
+                    journal_length: value.journal_length,
+                    dead_lettered_at: value.dead_lettered_at.as_u64(),
+                }
+            }
+        }
+
+        impl TryFrom<DeadLetter> for crate::dead_letter_table::DeadLetter {
+            type Error = ConversionError;
+
+            fn try_from(value: DeadLetter) -> Result<Self, Self::Error> {
+                Ok(crate::dead_letter_table::DeadLetter {
+                    invocation_target: restate_types::invocation::InvocationTarget::try_from(
+                        value
+                            .invocation_target
+                            .ok_or(ConversionError::missing_field("invocation_target"))?,
+                    )?,
+                    argument: value.argument,
+                    headers: value
+                        .headers
+                        .into_iter()
+                        .map(TryInto::try_into)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    last_error: InvocationError::new(
+                        value.failure_code,
+                        ByteString::try_from(value.failure_message)
+                            .map_err(ConversionError::invalid_data)?,
+                    ),
+                    journal_length: value.journal_length,
+                    dead_lettered_at: MillisSinceEpoch::new(value.dead_lettered_at),
+                })
+            }
+        }
+
+        impl From<restate_types::schema::schedules::Schedule> for Schedule {
+            fn from(value: restate_types::schema::schedules::Schedule) -> Self {
+                Schedule {
+                    id: value.id().to_owned(),
+                    cron: value.cron().to_owned(),
+                    timezone: value.timezone().to_owned(),
+                    target: Some(InvocationTarget::from(value.target().clone())),
+                    payload: value.payload().clone(),
+                }
+            }
+        }
+
+        impl TryFrom<Schedule> for restate_types::schema::schedules::Schedule {
+            type Error = ConversionError;
+
+            fn try_from(value: Schedule) -> Result<Self, Self::Error> {
+                restate_types::schema::schedules::Schedule::new(
+                    value.id,
+                    value.cron,
+                    value.timezone,
+                    restate_types::invocation::InvocationTarget::try_from(
+                        value
+                            .target
+                            .ok_or(ConversionError::missing_field("target"))?,
+                    )?,
+                    value.payload,
+                )
+                .map_err(ConversionError::invalid_data)
+            }
+        }
+
+        impl From<crate::kafka_sink_outbox_table::KafkaSinkRecord> for KafkaSinkRecord {
+            fn from(value: crate::kafka_sink_outbox_table::KafkaSinkRecord) -> Self {
+                KafkaSinkRecord {
+                    sink_id: value.sink_id,
+                    invocation_id: Some(InvocationId::from(value.invocation_id)),
+                    invocation_target: Some(InvocationTarget::from(value.invocation_target)),
+                    value: value.value,
+                }
+            }
+        }
+
+        impl TryFrom<KafkaSinkRecord> for crate::kafka_sink_outbox_table::KafkaSinkRecord {
+            type Error = ConversionError;
+
+            fn try_from(value: KafkaSinkRecord) -> Result<Self, Self::Error> {
+                Ok(crate::kafka_sink_outbox_table::KafkaSinkRecord {
+                    sink_id: value.sink_id,
+                    invocation_id: restate_types::identifiers::InvocationId::try_from(
+                        value
+                            .invocation_id
+                            .ok_or(ConversionError::missing_field("invocation_id"))?,
+                    )?,
+                    invocation_target: restate_types::invocation::InvocationTarget::try_from(
+                        value
+                            .invocation_target
+                            .ok_or(ConversionError::missing_field("invocation_target"))?,
+                    )?,
+                    value: value.value,
+                })
+            }
+        }
+
+        impl From<crate::schedule_table::ScheduleStatus> for ScheduleStatus {
+            fn from(value: crate::schedule_table::ScheduleStatus) -> Self {
+                ScheduleStatus {
+                    schedule: Some(Schedule::from(value.schedule)),
+                    next_fire_time: value.next_fire_time.map(|time| time.as_u64()),
+                    last_invocation_id: value.last_invocation_id.map(InvocationId::from),
+                }
+            }
+        }
+
+        impl TryFrom<ScheduleStatus> for crate::schedule_table::ScheduleStatus {
+            type Error = ConversionError;
+
+            fn try_from(value: ScheduleStatus) -> Result<Self, Self::Error> {
+                Ok(crate::schedule_table::ScheduleStatus {
+                    schedule: restate_types::schema::schedules::Schedule::try_from(
+                        value
+                            .schedule
+                            .ok_or(ConversionError::missing_field("schedule"))?,
+                    )?,
+                    next_fire_time: value.next_fire_time.map(MillisSinceEpoch::new),
+                    last_invocation_id: value
+                        .last_invocation_id
+                        .map(restate_types::identifiers::InvocationId::try_from)
+                        .transpose()?,
+                })
+            }
+        }
     }
 }
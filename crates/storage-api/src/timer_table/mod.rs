@@ -62,6 +62,13 @@ impl TimerKey {
             kind: TimerKeyKind::CleanInvocationStatus { invocation_uuid },
         }
     }
+
+    pub fn fire_schedule(timestamp: u64, invocation_uuid: InvocationUuid) -> Self {
+        TimerKey {
+            timestamp,
+            kind: TimerKeyKind::FireSchedule { invocation_uuid },
+        }
+    }
 }
 
 impl PartialOrd for TimerKey {
@@ -101,6 +108,8 @@ pub enum TimerKeyKind {
     },
     /// Cleaning of invocation status
     CleanInvocationStatus { invocation_uuid: InvocationUuid },
+    /// Occurrence of a schedule, identified by the invocation it fires
+    FireSchedule { invocation_uuid: InvocationUuid },
 }
 
 impl TimerKeyKind {
@@ -112,6 +121,7 @@ impl TimerKeyKind {
             } => invocation_uuid,
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => invocation_uuid,
             TimerKeyKind::NeoInvoke { invocation_uuid } => invocation_uuid,
+            TimerKeyKind::FireSchedule { invocation_uuid } => invocation_uuid,
         }
     }
 }
@@ -131,7 +141,8 @@ impl Ord for TimerKeyKind {
                 } => invocation_uuid.cmp(other_invocation_uuid),
                 TimerKeyKind::CompleteJournalEntry { .. }
                 | TimerKeyKind::CleanInvocationStatus { .. }
-                | TimerKeyKind::NeoInvoke { .. } => Ordering::Less,
+                | TimerKeyKind::NeoInvoke { .. }
+                | TimerKeyKind::FireSchedule { .. } => Ordering::Less,
             },
             TimerKeyKind::CompleteJournalEntry {
                 invocation_uuid,
@@ -144,9 +155,9 @@ impl Ord for TimerKeyKind {
                 } => invocation_uuid
                     .cmp(other_invocation_uuid)
                     .then_with(|| journal_index.cmp(other_journal_index)),
-                TimerKeyKind::CleanInvocationStatus { .. } | TimerKeyKind::NeoInvoke { .. } => {
-                    Ordering::Less
-                }
+                TimerKeyKind::CleanInvocationStatus { .. }
+                | TimerKeyKind::NeoInvoke { .. }
+                | TimerKeyKind::FireSchedule { .. } => Ordering::Less,
             },
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => match other {
                 TimerKeyKind::Invoke { .. } | TimerKeyKind::CompleteJournalEntry { .. } => {
@@ -155,7 +166,9 @@ impl Ord for TimerKeyKind {
                 TimerKeyKind::CleanInvocationStatus {
                     invocation_uuid: other_invocation_uuid,
                 } => invocation_uuid.cmp(other_invocation_uuid),
-                TimerKeyKind::NeoInvoke { .. } => Ordering::Less,
+                TimerKeyKind::NeoInvoke { .. } | TimerKeyKind::FireSchedule { .. } => {
+                    Ordering::Less
+                }
             },
             TimerKeyKind::NeoInvoke { invocation_uuid } => match other {
                 TimerKeyKind::Invoke { .. }
@@ -164,6 +177,16 @@ impl Ord for TimerKeyKind {
                 TimerKeyKind::NeoInvoke {
                     invocation_uuid: other_invocation_uuid,
                 } => invocation_uuid.cmp(other_invocation_uuid),
+                TimerKeyKind::FireSchedule { .. } => Ordering::Less,
+            },
+            TimerKeyKind::FireSchedule { invocation_uuid } => match other {
+                TimerKeyKind::Invoke { .. }
+                | TimerKeyKind::CompleteJournalEntry { .. }
+                | TimerKeyKind::CleanInvocationStatus { .. }
+                | TimerKeyKind::NeoInvoke { .. } => Ordering::Greater,
+                TimerKeyKind::FireSchedule {
+                    invocation_uuid: other_invocation_uuid,
+                } => invocation_uuid.cmp(other_invocation_uuid),
             },
         }
     }
@@ -183,6 +206,8 @@ pub enum Timer {
     // TODO remove this variant when removing the old invocation status table
     CleanInvocationStatus(InvocationId),
     NeoInvoke(InvocationId),
+    /// Occurrence of the schedule with the given id, firing the given invocation
+    FireSchedule(InvocationId, String),
 }
 
 impl Timer {
@@ -228,12 +253,24 @@ impl Timer {
         )
     }
 
+    pub fn fire_schedule(
+        timestamp: u64,
+        invocation_id: InvocationId,
+        schedule_id: String,
+    ) -> (TimerKey, Self) {
+        (
+            TimerKey::fire_schedule(timestamp, invocation_id.invocation_uuid()),
+            Timer::FireSchedule(invocation_id, schedule_id),
+        )
+    }
+
     pub fn invocation_id(&self) -> InvocationId {
         match self {
             Timer::Invoke(service_invocation) => service_invocation.invocation_id,
             Timer::CompleteJournalEntry(invocation_id, _) => *invocation_id,
             Timer::CleanInvocationStatus(invocation_id) => *invocation_id,
             Timer::NeoInvoke(invocation_id) => *invocation_id,
+            Timer::FireSchedule(invocation_id, _) => *invocation_id,
         }
     }
 }
@@ -245,6 +282,7 @@ impl WithPartitionKey for Timer {
             Timer::Invoke(service_invocation) => service_invocation.partition_key(),
             Timer::CleanInvocationStatus(invocation_id) => invocation_id.partition_key(),
             Timer::NeoInvoke(invocation_id) => invocation_id.partition_key(),
+            Timer::FireSchedule(invocation_id, _) => invocation_id.partition_key(),
         }
     }
 }
@@ -19,6 +19,7 @@ use datafusion::error::DataFusionError;
 use datafusion::execution::context::SQLOptions;
 use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion::execution::SessionStateBuilder;
+use datafusion::logical_expr::LogicalPlan;
 use datafusion::physical_optimizer::optimizer::PhysicalOptimizer;
 use datafusion::physical_plan::SendableRecordBatchStream;
 use datafusion::prelude::{SessionConfig, SessionContext};
@@ -76,6 +77,7 @@ const SYS_INVOCATION_VIEW: &str = "CREATE VIEW sys_invocation as SELECT
                 WHEN ss.status = 'scheduled' THEN 'scheduled'
                 WHEN ss.status = 'completed' THEN 'completed'
                 WHEN ss.status = 'suspended' THEN 'suspended'
+                WHEN ss.status = 'paused' THEN 'paused'
                 WHEN sis.in_flight THEN 'running'
                 WHEN ss.status = 'invoked' AND retry_count > 0 THEN 'backing-off'
                 ELSE 'ready'
@@ -161,8 +163,14 @@ impl QueryContext {
         crate::promise::register_self(
             &ctx,
             partition_selector.clone(),
-            local_partition_store_manager,
+            local_partition_store_manager.clone(),
+        )?;
+        crate::dead_letter::register_self(
+            &ctx,
+            partition_selector.clone(),
+            local_partition_store_manager.clone(),
         )?;
+        crate::schedule::register_self(&ctx, partition_selector, local_partition_store_manager)?;
 
         let ctx = ctx
             .datafusion_context
@@ -301,10 +309,25 @@ impl QueryContext {
         &self,
         sql: &str,
     ) -> datafusion::common::Result<SendableRecordBatchStream> {
+        let plan = self.create_logical_plan(sql).await?;
+        self.execute_logical_plan(plan).await
+    }
+
+    /// Creates the verified logical plan for the given sql statement. The statement may contain
+    /// placeholders (`$1`, `$2`, ...) which need to be replaced with values before executing the
+    /// plan.
+    pub async fn create_logical_plan(&self, sql: &str) -> datafusion::common::Result<LogicalPlan> {
         let state = self.datafusion_context.state();
         let statement = state.sql_to_statement(sql, "postgres")?;
         let plan = state.statement_to_plan(statement).await?;
         self.sql_options.verify_plan(&plan)?;
+        Ok(plan)
+    }
+
+    pub async fn execute_logical_plan(
+        &self,
+        plan: LogicalPlan,
+    ) -> datafusion::common::Result<SendableRecordBatchStream> {
         let df = self.datafusion_context.execute_logical_plan(plan).await?;
         df.execute_stream().await
     }
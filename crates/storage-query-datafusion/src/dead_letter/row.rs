@@ -0,0 +1,63 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::schema::SysDeadLetterBuilder;
+
+use crate::table_util::format_using;
+use restate_storage_api::dead_letter_table::DeadLetter;
+use restate_types::identifiers::{InvocationId, WithPartitionKey};
+use restate_types::invocation::ServiceType;
+
+#[inline]
+pub(crate) fn append_dead_letter_row(
+    builder: &mut SysDeadLetterBuilder,
+    output: &mut String,
+    invocation_id: InvocationId,
+    dead_letter: DeadLetter,
+) {
+    let mut row = builder.row();
+    row.partition_key(invocation_id.partition_key());
+
+    if row.is_id_defined() {
+        row.id(format_using(output, &invocation_id));
+    }
+
+    let invocation_target = dead_letter.invocation_target;
+    row.target_service_name(invocation_target.service_name());
+    if let Some(key) = invocation_target.key() {
+        row.target_service_key(key);
+    }
+    row.target_handler_name(invocation_target.handler_name());
+    if row.is_target_defined() {
+        row.target(format_using(output, &invocation_target));
+    }
+    row.target_service_ty(match invocation_target.service_ty() {
+        ServiceType::Service => "service",
+        ServiceType::VirtualObject => "virtual_object",
+        ServiceType::Workflow => "workflow",
+    });
+
+    row.argument(&dead_letter.argument);
+    if row.is_headers_defined() {
+        let headers: serde_json::Map<_, _> = dead_letter
+            .headers
+            .into_iter()
+            .map(|header| (header.name.to_string(), header.value.to_string().into()))
+            .collect();
+        row.headers(serde_json::Value::Object(headers).to_string());
+    }
+
+    if row.is_last_failure_defined() {
+        row.last_failure(format_using(output, &dead_letter.last_error));
+    }
+    row.last_failure_error_code(dead_letter.last_error.code().into());
+    row.journal_size(dead_letter.journal_length);
+    row.dead_lettered_at(dead_letter.dead_lettered_at.as_u64() as i64);
+}
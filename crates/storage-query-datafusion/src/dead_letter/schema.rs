@@ -0,0 +1,58 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+#![allow(dead_code)]
+
+use crate::table_macro::*;
+
+use datafusion::arrow::datatypes::DataType;
+
+define_table!(sys_dead_letter(
+    /// Internal column that is used for partitioning the services invocations. Can be ignored.
+    partition_key: DataType::UInt64,
+
+    /// [Invocation ID](/operate/invocation#invocation-identifier) of the dead-lettered invocation.
+    id: DataType::LargeUtf8,
+
+    /// Invocation Target. Format for plain services: `ServiceName/HandlerName`, e.g.
+    /// `Greeter/greet`. Format for virtual objects/workflows: `VirtualObjectName/Key/HandlerName`,
+    /// e.g. `Greeter/Francesco/greet`.
+    target: DataType::LargeUtf8,
+
+    /// The name of the invoked service.
+    target_service_name: DataType::LargeUtf8,
+
+    /// The key of the virtual object or the workflow ID. Null for regular services.
+    target_service_key: DataType::LargeUtf8,
+
+    /// The invoked handler.
+    target_handler_name: DataType::LargeUtf8,
+
+    /// The service type. Either `service` or `virtual_object` or `workflow`.
+    target_service_ty: DataType::LargeUtf8,
+
+    /// The input of the invocation.
+    argument: DataType::LargeBinary,
+
+    /// The headers of the invocation, as a JSON object.
+    headers: DataType::LargeUtf8,
+
+    /// An error message describing the last failed attempt of this invocation.
+    last_failure: DataType::LargeUtf8,
+
+    /// The error code of the last failed attempt of this invocation.
+    last_failure_error_code: DataType::UInt32,
+
+    /// The number of journal entries the invocation had when it exhausted its attempts.
+    journal_size: DataType::UInt32,
+
+    /// Timestamp indicating when the invocation was moved to the dead letter table.
+    dead_lettered_at: DataType::Date64
+));
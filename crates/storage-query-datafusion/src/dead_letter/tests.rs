@@ -0,0 +1,84 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::mocks::*;
+use crate::row;
+use bytes::Bytes;
+use datafusion::arrow::array::{LargeBinaryArray, LargeStringArray, UInt32Array};
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::StreamExt;
+use googletest::all;
+use googletest::prelude::{assert_that, eq};
+use restate_core::TaskCenterBuilder;
+use restate_storage_api::dead_letter_table::{DeadLetter, DeadLetterTable};
+use restate_storage_api::Transaction;
+use restate_types::errors::{codes, InvocationError};
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::{Header, InvocationTarget, VirtualObjectHandlerType};
+use restate_types::time::MillisSinceEpoch;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_dead_letter() {
+    let tc = TaskCenterBuilder::default()
+        .default_runtime_handle(tokio::runtime::Handle::current())
+        .build()
+        .expect("task_center builds");
+    let mut engine = tc
+        .run_in_scope("mock-query-engine", None, MockQueryEngine::create())
+        .await;
+
+    let invocation_target = InvocationTarget::virtual_object(
+        "my-service",
+        "my-key",
+        "my-handler",
+        VirtualObjectHandlerType::Exclusive,
+    );
+    let invocation_id = InvocationId::mock_generate(&invocation_target);
+
+    let mut tx = engine.partition_store().transaction();
+    tx.put_dead_letter(
+        &invocation_id,
+        &DeadLetter {
+            invocation_target,
+            argument: Bytes::from_static(b"my-input"),
+            headers: vec![Header::new("x-my-header", "my-value")],
+            last_error: InvocationError::new(codes::INTERNAL, "my-error"),
+            journal_length: 2,
+            dead_lettered_at: MillisSinceEpoch::now(),
+        },
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let records = engine
+        .execute("SELECT * FROM sys_dead_letter")
+        .await
+        .unwrap()
+        .collect::<Vec<Result<RecordBatch, _>>>()
+        .await
+        .remove(0)
+        .unwrap();
+
+    assert_that!(
+        records,
+        all!(row!(
+            0,
+            {
+                "id" => LargeStringArray: eq(invocation_id.to_string()),
+                "target" => LargeStringArray: eq("my-service/my-key/my-handler"),
+                "target_service_key" => LargeStringArray: eq("my-key"),
+                "argument" => LargeBinaryArray: eq(b"my-input".as_slice()),
+                "headers" => LargeStringArray: eq(r#"{"x-my-header":"my-value"}"#),
+                "last_failure_error_code" => UInt32Array: eq(500),
+                "journal_size" => UInt32Array: eq(2),
+            }
+        ))
+    );
+}
@@ -46,6 +46,7 @@ async fn get_entries() {
         0,
         &JournalEntry::Entry(ProtobufRawEntryCodec::serialize_enriched(Entry::Input(
             InputEntry {
+                headers: vec![],
                 value: Default::default(),
             },
         ))),
@@ -148,6 +149,7 @@ async fn select_count_star() {
         0,
         &JournalEntry::Entry(ProtobufRawEntryCodec::serialize_enriched(Entry::Input(
             InputEntry {
+                headers: vec![],
                 value: Default::default(),
             },
         ))),
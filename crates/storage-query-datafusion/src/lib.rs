@@ -13,6 +13,7 @@ pub mod context;
 
 pub mod remote_query_scanner_server;
 
+mod dead_letter;
 mod deployment;
 mod idempotency;
 mod inbox;
@@ -23,6 +24,7 @@ mod keyed_service_status;
 mod partition_store_scanner;
 mod physical_optimizer;
 mod promise;
+mod schedule;
 mod service;
 mod state;
 #[cfg(feature = "table_docs")]
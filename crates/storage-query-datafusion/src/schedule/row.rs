@@ -0,0 +1,51 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::schema::SysScheduleBuilder;
+
+use crate::table_util::format_using;
+use restate_storage_api::schedule_table::ScheduleStatus;
+use restate_types::identifiers::PartitionKey;
+
+#[inline]
+pub(crate) fn append_schedule_row(
+    builder: &mut SysScheduleBuilder,
+    output: &mut String,
+    partition_key: PartitionKey,
+    schedule_status: ScheduleStatus,
+) {
+    let mut row = builder.row();
+    row.partition_key(partition_key);
+
+    let schedule = schedule_status.schedule;
+    row.id(schedule.id());
+    row.cron(schedule.cron());
+    row.timezone(schedule.timezone());
+
+    let invocation_target = schedule.target();
+    row.target_service_name(invocation_target.service_name());
+    if let Some(key) = invocation_target.key() {
+        row.target_service_key(key);
+    }
+    row.target_handler_name(invocation_target.handler_name());
+    if row.is_target_defined() {
+        row.target(format_using(output, invocation_target));
+    }
+
+    row.payload(schedule.payload());
+    if let Some(next_fire_time) = schedule_status.next_fire_time {
+        row.next_fire_time(next_fire_time.as_u64() as i64);
+    }
+    if let Some(last_invocation_id) = schedule_status.last_invocation_id {
+        if row.is_last_invocation_id_defined() {
+            row.last_invocation_id(format_using(output, &last_invocation_id));
+        }
+    }
+}
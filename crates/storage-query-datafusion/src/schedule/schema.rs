@@ -0,0 +1,54 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+#![allow(dead_code)]
+
+use crate::table_macro::*;
+
+use datafusion::arrow::datatypes::DataType;
+
+define_table!(sys_schedule(
+    /// Internal column that is used for partitioning the schedules. Can be ignored.
+    partition_key: DataType::UInt64,
+
+    /// Identifier of the schedule.
+    id: DataType::LargeUtf8,
+
+    /// Cron expression of the schedule.
+    cron: DataType::LargeUtf8,
+
+    /// IANA timezone the cron expression is evaluated in, e.g. `Europe/Berlin`.
+    timezone: DataType::LargeUtf8,
+
+    /// Invocation Target. Format for plain services: `ServiceName/HandlerName`, e.g.
+    /// `Greeter/greet`. Format for virtual objects/workflows: `VirtualObjectName/Key/HandlerName`,
+    /// e.g. `Greeter/Francesco/greet`.
+    target: DataType::LargeUtf8,
+
+    /// The name of the invoked service.
+    target_service_name: DataType::LargeUtf8,
+
+    /// The key of the virtual object or the workflow ID. Null for regular services.
+    target_service_key: DataType::LargeUtf8,
+
+    /// The invoked handler.
+    target_handler_name: DataType::LargeUtf8,
+
+    /// The payload every invocation of the schedule is sent with.
+    payload: DataType::LargeBinary,
+
+    /// Timestamp of the next occurrence of the schedule. Null if the cron expression has no
+    /// further occurrences.
+    next_fire_time: DataType::Date64,
+
+    /// [Invocation ID](/operate/invocation#invocation-identifier) of the last invocation fired
+    /// by the schedule.
+    last_invocation_id: DataType::LargeUtf8
+));
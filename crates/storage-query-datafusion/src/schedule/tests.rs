@@ -0,0 +1,88 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::mocks::*;
+use crate::row;
+use bytes::Bytes;
+use datafusion::arrow::array::{LargeBinaryArray, LargeStringArray};
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::StreamExt;
+use googletest::all;
+use googletest::prelude::{assert_that, eq};
+use restate_core::TaskCenterBuilder;
+use restate_storage_api::schedule_table::{ScheduleStatus, ScheduleTable};
+use restate_storage_api::Transaction;
+use restate_types::identifiers::WithPartitionKey;
+use restate_types::invocation::{InvocationTarget, VirtualObjectHandlerType};
+use restate_types::schema::schedules::Schedule;
+use restate_types::time::MillisSinceEpoch;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_schedule() {
+    let tc = TaskCenterBuilder::default()
+        .default_runtime_handle(tokio::runtime::Handle::current())
+        .build()
+        .expect("task_center builds");
+    let mut engine = tc
+        .run_in_scope("mock-query-engine", None, MockQueryEngine::create())
+        .await;
+
+    let schedule = Schedule::new(
+        "my-schedule".to_owned(),
+        "0 2 * * *".to_owned(),
+        "Europe/Berlin".to_owned(),
+        InvocationTarget::virtual_object(
+            "my-service",
+            "my-key",
+            "my-handler",
+            VirtualObjectHandlerType::Exclusive,
+        ),
+        Bytes::from_static(b"my-payload"),
+    )
+    .unwrap();
+    let last_invocation_id = schedule.invocation_id(MillisSinceEpoch::new(0));
+
+    let mut tx = engine.partition_store().transaction();
+    tx.put_schedule_status(
+        schedule.partition_key(),
+        &ScheduleStatus {
+            schedule: schedule.clone(),
+            next_fire_time: schedule.next_fire_time(MillisSinceEpoch::now()),
+            last_invocation_id: Some(last_invocation_id),
+        },
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let records = engine
+        .execute("SELECT * FROM sys_schedule")
+        .await
+        .unwrap()
+        .collect::<Vec<Result<RecordBatch, _>>>()
+        .await
+        .remove(0)
+        .unwrap();
+
+    assert_that!(
+        records,
+        all!(row!(
+            0,
+            {
+                "id" => LargeStringArray: eq("my-schedule"),
+                "cron" => LargeStringArray: eq("0 2 * * *"),
+                "timezone" => LargeStringArray: eq("Europe/Berlin"),
+                "target" => LargeStringArray: eq("my-service/my-key/my-handler"),
+                "target_service_key" => LargeStringArray: eq("my-key"),
+                "payload" => LargeBinaryArray: eq(b"my-payload".as_slice()),
+                "last_invocation_id" => LargeStringArray: eq(last_invocation_id.to_string()),
+            }
+        ))
+    );
+}
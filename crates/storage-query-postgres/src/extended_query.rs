@@ -8,74 +8,712 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use pgwire::api::portal::Portal;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::common::{ParamValues, ScalarValue};
+use datafusion::logical_expr::expr::Placeholder;
+use datafusion::logical_expr::{Expr, LogicalPlan};
+use futures::stream::BoxStream;
+use futures::{Sink, SinkExt, StreamExt};
+use pgwire::api::portal::{Format, Portal};
 use pgwire::api::query::ExtendedQueryHandler;
-use pgwire::api::results::{DescribePortalResponse, DescribeStatementResponse, Response};
-use pgwire::api::stmt::{NoopQueryParser, StoredStatement};
-use pgwire::api::ClientInfo;
-use pgwire::error::{PgWireError, PgWireResult};
-use std::sync::Arc;
+use pgwire::api::results::{
+    DescribePortalResponse, DescribeStatementResponse, QueryResponse, Response, Tag,
+};
+use pgwire::api::stmt::{QueryParser, StoredStatement};
+use pgwire::api::store::PortalStore;
+use pgwire::api::{ClientInfo, ClientPortalStore, Type, DEFAULT_NAME};
+use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
+use pgwire::messages::data::DataRow;
+use pgwire::messages::extendedquery::{Execute, PortalSuspended};
+use pgwire::messages::PgWireBackendMessage;
+
+use restate_storage_query_datafusion::context::QueryContext;
+
+use crate::pgwire_server::{arrow_to_pg_encoder, into_pg_fields, into_pg_type, DfSessionService};
 
-#[derive(Debug, Clone)]
-pub struct NoopExtendedQueryHandler {
-    query_parser: Arc<NoopQueryParser>,
+/// Parses the sql of a `Parse` message into a DataFusion [`LogicalPlan`] which may still contain
+/// placeholders. The placeholders are replaced with the portal parameters when executing the plan.
+pub struct DfQueryParser {
+    session_context: QueryContext,
 }
 
-impl NoopExtendedQueryHandler {
-    pub(crate) fn new() -> Self {
-        NoopExtendedQueryHandler {
-            query_parser: Arc::new(Default::default()),
-        }
+impl DfQueryParser {
+    pub(crate) fn new(session_context: QueryContext) -> Self {
+        Self { session_context }
+    }
+}
+
+#[async_trait]
+impl QueryParser for DfQueryParser {
+    type Statement = LogicalPlan;
+
+    async fn parse_sql(&self, sql: &str, types: &[Type]) -> PgWireResult<Self::Statement> {
+        let plan = self
+            .session_context
+            .create_logical_plan(sql)
+            .await
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+        apply_declared_parameter_types(plan, types)
+    }
+}
+
+type DataRows = BoxStream<'static, PgWireResult<DataRow>>;
+
+/// Portal whose execution stopped after the `max_rows` requested by the client. The next
+/// `Execute` of the same portal continues with the remaining rows.
+pub(crate) struct SuspendedPortal {
+    portal: Arc<Portal<LogicalPlan>>,
+    data_rows: DataRows,
+}
+
+impl DfSessionService {
+    async fn execute_portal(
+        &self,
+        portal: &Portal<LogicalPlan>,
+    ) -> PgWireResult<QueryResponse<'static>> {
+        let plan = &portal.statement.statement;
+        let inferred_types = inferred_parameter_types(plan)?;
+        let param_values = decode_parameters(portal, &inferred_types)?;
+        let plan = plan
+            .clone()
+            .replace_params_with_values(&param_values)
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+        let stream = self
+            .session_context
+            .execute_logical_plan(plan)
+            .await
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+        arrow_to_pg_encoder(stream, Some(&portal.result_column_format)).await
     }
 }
 
 #[async_trait]
-impl ExtendedQueryHandler for NoopExtendedQueryHandler {
-    type Statement = String;
-    type QueryParser = NoopQueryParser;
+impl ExtendedQueryHandler for DfSessionService {
+    type Statement = LogicalPlan;
+    type QueryParser = DfQueryParser;
 
     fn query_parser(&self) -> Arc<Self::QueryParser> {
         self.query_parser.clone()
     }
 
+    /// Unlike the default implementation, this one honors the `max_rows` of the `Execute` message
+    /// by suspending the portal, which lets clients fetch large results in chunks.
+    async fn on_execute<C>(&self, client: &mut C, message: Execute) -> PgWireResult<()>
+    where
+        C: ClientInfo + ClientPortalStore + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::PortalStore: PortalStore<Statement = Self::Statement>,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        let portal_name = message.name.as_deref().unwrap_or(DEFAULT_NAME);
+        let portal = client
+            .portal_store()
+            .get_portal(portal_name)
+            .ok_or_else(|| PgWireError::PortalNotFound(portal_name.to_owned()))?;
+
+        // A portal which has been bound again under the same name starts from the beginning
+        let suspended_rows = self
+            .suspended_portals
+            .lock()
+            .expect("suspended portals lock not poisoned")
+            .remove(portal_name)
+            .filter(|suspended_portal| Arc::ptr_eq(&suspended_portal.portal, &portal))
+            .map(|suspended_portal| suspended_portal.data_rows);
+        let data_rows = match suspended_rows {
+            Some(data_rows) => data_rows,
+            None => self.execute_portal(&portal).await?.data_rows(),
+        };
+
+        // Zero means no limit
+        let max_rows = usize::try_from(message.max_rows).unwrap_or(0);
+        if let Some(data_rows) = send_data_rows(client, data_rows, max_rows).await? {
+            self.suspended_portals
+                .lock()
+                .expect("suspended portals lock not poisoned")
+                .insert(
+                    portal_name.to_owned(),
+                    SuspendedPortal { portal, data_rows },
+                );
+        }
+
+        Ok(())
+    }
+
     async fn do_describe_statement<C>(
         &self,
         _client: &mut C,
-        _statement: &StoredStatement<Self::Statement>,
+        statement: &StoredStatement<Self::Statement>,
     ) -> PgWireResult<DescribeStatementResponse>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        Err(PgWireError::ApiError(
-            "Extended Query is not implemented on this server.".into(),
-        ))
+        let inferred_types = inferred_parameter_types(&statement.statement)?;
+        let parameter_types = inferred_types
+            .iter()
+            .enumerate()
+            .map(|(idx, inferred_type)| {
+                parameter_type(statement.parameter_types.get(idx), inferred_type.as_ref())
+            })
+            .collect::<PgWireResult<Vec<_>>>()?;
+        let fields = into_pg_fields(
+            statement.statement.schema().as_arrow(),
+            Some(&Format::UnifiedText),
+        )?;
+
+        Ok(DescribeStatementResponse::new(parameter_types, fields))
     }
 
     async fn do_describe_portal<C>(
         &self,
         _client: &mut C,
-        _portal: &Portal<Self::Statement>,
+        portal: &Portal<Self::Statement>,
     ) -> PgWireResult<DescribePortalResponse>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        Err(PgWireError::ApiError(
-            "Extended Query is not implemented on this server.".into(),
-        ))
+        let fields = into_pg_fields(
+            portal.statement.statement.schema().as_arrow(),
+            Some(&portal.result_column_format),
+        )?;
+
+        Ok(DescribePortalResponse::new(fields))
     }
 
+    /// Returns all rows of the portal. Limiting them to `max_rows` is done by
+    /// [`Self::on_execute`] which doesn't use this method.
     async fn do_query<'a, 'b: 'a, C>(
         &'b self,
         _client: &mut C,
-        _portal: &'a Portal<Self::Statement>,
+        portal: &'a Portal<Self::Statement>,
         _max_rows: usize,
     ) -> PgWireResult<Response<'a>>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        Err(PgWireError::ApiError(
-            "Extended Query is not implemented on this server.".into(),
-        ))
+        Ok(Response::Query(self.execute_portal(portal).await?))
+    }
+}
+
+/// Sends the rows of a portal to the client. If `max_rows` is not zero, at most `max_rows` rows
+/// are sent before the portal is suspended. Returns the remaining rows of a suspended portal.
+async fn send_data_rows<C>(
+    client: &mut C,
+    mut data_rows: DataRows,
+    max_rows: usize,
+) -> PgWireResult<Option<DataRows>>
+where
+    C: Sink<PgWireBackendMessage> + Unpin + Send,
+    PgWireError: From<C::Error>,
+{
+    let mut rows = 0;
+    while max_rows == 0 || rows < max_rows {
+        let Some(row) = data_rows.next().await else {
+            let tag = Tag::new("SELECT").with_rows(rows);
+            client
+                .send(PgWireBackendMessage::CommandComplete(tag.into()))
+                .await?;
+            return Ok(None);
+        };
+
+        client.feed(PgWireBackendMessage::DataRow(row?)).await?;
+        rows += 1;
+    }
+
+    client
+        .send(PgWireBackendMessage::PortalSuspended(PortalSuspended))
+        .await?;
+    Ok(Some(data_rows))
+}
+
+/// Clients can declare parameter types in the `Parse` message instead of relying on the types
+/// DataFusion infers. The declared types are assigned to the placeholders, so that they are
+/// described and decoded as the client expects. Parameters declared as `unknown` keep the
+/// inferred type.
+fn apply_declared_parameter_types(plan: LogicalPlan, types: &[Type]) -> PgWireResult<LogicalPlan> {
+    let declared_types = types
+        .iter()
+        .enumerate()
+        .map(|(idx, pg_type)| declared_data_type(idx, pg_type))
+        .collect::<PgWireResult<Vec<_>>>()?;
+
+    if declared_types.iter().all(Option::is_none) {
+        return Ok(plan);
+    }
+
+    plan.transform_up_with_subqueries(|plan| {
+        plan.map_expressions(|expr| {
+            expr.transform_up(|expr| match expr {
+                Expr::Placeholder(Placeholder { id, data_type }) => {
+                    let declared_type = placeholder_position(&id)
+                        .and_then(|position| declared_types.get(position - 1))
+                        .cloned()
+                        .flatten();
+                    let transformed = declared_type.is_some();
+                    let placeholder = Placeholder::new(id, declared_type.or(data_type));
+
+                    Ok(Transformed::new_transformed(
+                        Expr::Placeholder(placeholder),
+                        transformed,
+                    ))
+                }
+                _ => Ok(Transformed::no(expr)),
+            })
+        })?
+        .map_data(LogicalPlan::recompute_schema)
+    })
+    .map(|transformed| transformed.data)
+    .map_err(|e| PgWireError::ApiError(Box::new(e)))
+}
+
+/// Supports the same types as the parameter decoding.
+fn declared_data_type(idx: usize, pg_type: &Type) -> PgWireResult<Option<DataType>> {
+    Ok(Some(match *pg_type {
+        Type::UNKNOWN => return Ok(None),
+        Type::BOOL => DataType::Boolean,
+        Type::INT2 => DataType::Int16,
+        Type::INT4 => DataType::Int32,
+        Type::INT8 => DataType::Int64,
+        Type::FLOAT4 => DataType::Float32,
+        Type::FLOAT8 => DataType::Float64,
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => DataType::Utf8,
+        Type::BYTEA => DataType::Binary,
+        _ => return Err(unsupported_parameter_type(idx, pg_type)),
+    }))
+}
+
+/// Returns the one-based position of a `$<position>` placeholder.
+fn placeholder_position(placeholder: &str) -> Option<usize> {
+    placeholder
+        .strip_prefix('$')
+        .and_then(|position| position.parse::<usize>().ok())
+        .filter(|position| *position > 0)
+}
+
+/// Returns the types DataFusion inferred for the placeholders `$1`, `$2`, ... of the given plan,
+/// ordered by their position. Placeholders whose type could not be inferred are `None`.
+fn inferred_parameter_types(plan: &LogicalPlan) -> PgWireResult<Vec<Option<DataType>>> {
+    let parameter_types: HashMap<String, Option<DataType>> = plan
+        .get_parameter_types()
+        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+
+    let mut ordered_types = Vec::with_capacity(parameter_types.len());
+    for (placeholder, data_type) in parameter_types {
+        let position = placeholder_position(&placeholder).ok_or_else(|| {
+            user_error(
+                "42P02",
+                format!("Unsupported placeholder '{placeholder}', expected '$<position>'"),
+            )
+        })?;
+
+        if ordered_types.len() < position {
+            ordered_types.resize(position, None);
+        }
+        ordered_types[position - 1] = data_type;
+    }
+
+    Ok(ordered_types)
+}
+
+/// The type a client specified in the `Parse` message takes precedence over the inferred type.
+/// Parameters of unknown type are treated as text.
+fn parameter_type(
+    specified_type: Option<&Type>,
+    inferred_type: Option<&DataType>,
+) -> PgWireResult<Type> {
+    match (specified_type, inferred_type) {
+        (Some(specified_type), _) if *specified_type != Type::UNKNOWN => Ok(specified_type.clone()),
+        (_, Some(inferred_type)) => into_pg_type(inferred_type),
+        _ => Ok(Type::TEXT),
+    }
+}
+
+fn decode_parameters(
+    portal: &Portal<LogicalPlan>,
+    inferred_types: &[Option<DataType>],
+) -> PgWireResult<ParamValues> {
+    let mut values = Vec::with_capacity(portal.parameter_len());
+
+    for idx in 0..portal.parameter_len() {
+        let inferred_type = inferred_types.get(idx).and_then(Option::as_ref);
+        let pg_type = parameter_type(portal.statement.parameter_types.get(idx), inferred_type)?;
+
+        let value = if portal.parameter_format.is_binary(idx) {
+            decode_binary_parameter(portal, idx, &pg_type)?
+        } else {
+            decode_text_parameter(portal, idx, &pg_type)?
+        };
+
+        values.push(cast_to_inferred_type(value, inferred_type)?);
+    }
+
+    Ok(ParamValues::List(values))
+}
+
+fn decode_binary_parameter(
+    portal: &Portal<LogicalPlan>,
+    idx: usize,
+    pg_type: &Type,
+) -> PgWireResult<ScalarValue> {
+    Ok(match *pg_type {
+        Type::BOOL => ScalarValue::Boolean(portal.parameter(idx, pg_type)?),
+        Type::INT2 => ScalarValue::Int16(portal.parameter(idx, pg_type)?),
+        Type::INT4 => ScalarValue::Int32(portal.parameter(idx, pg_type)?),
+        Type::INT8 => ScalarValue::Int64(portal.parameter(idx, pg_type)?),
+        Type::FLOAT4 => ScalarValue::Float32(portal.parameter(idx, pg_type)?),
+        Type::FLOAT8 => ScalarValue::Float64(portal.parameter(idx, pg_type)?),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+            ScalarValue::Utf8(portal.parameter(idx, pg_type)?)
+        }
+        Type::BYTEA => ScalarValue::Binary(portal.parameter(idx, pg_type)?),
+        _ => return Err(unsupported_parameter_type(idx, pg_type)),
+    })
+}
+
+/// Most clients send their parameters in text format which [`Portal::parameter`] does not support.
+fn decode_text_parameter(
+    portal: &Portal<LogicalPlan>,
+    idx: usize,
+    pg_type: &Type,
+) -> PgWireResult<ScalarValue> {
+    let text = portal
+        .parameters
+        .get(idx)
+        .ok_or(PgWireError::ParameterIndexOutOfBound(idx))?
+        .as_ref()
+        .map(|bytes| std::str::from_utf8(bytes))
+        .transpose()
+        .map_err(|e| PgWireError::FailedToParseParameter(Box::new(e)))?;
+
+    Ok(match *pg_type {
+        Type::BOOL => ScalarValue::Boolean(text.map(parse_bool).transpose()?),
+        Type::INT2 => ScalarValue::Int16(parse_text(text)?),
+        Type::INT4 => ScalarValue::Int32(parse_text(text)?),
+        Type::INT8 => ScalarValue::Int64(parse_text(text)?),
+        Type::FLOAT4 => ScalarValue::Float32(parse_text(text)?),
+        Type::FLOAT8 => ScalarValue::Float64(parse_text(text)?),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+            ScalarValue::Utf8(text.map(str::to_owned))
+        }
+        Type::BYTEA => ScalarValue::Binary(text.map(parse_bytea).transpose()?),
+        _ => return Err(unsupported_parameter_type(idx, pg_type)),
+    })
+}
+
+fn parse_text<T>(text: Option<&str>) -> PgWireResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    text.map(|text| text.trim().parse::<T>())
+        .transpose()
+        .map_err(|e| PgWireError::FailedToParseParameter(Box::new(e)))
+}
+
+fn parse_bool(text: &str) -> PgWireResult<bool> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" | "on" | "1" => Ok(true),
+        "f" | "false" | "n" | "no" | "off" | "0" => Ok(false),
+        _ => Err(PgWireError::FailedToParseParameter(
+            format!("invalid boolean value '{text}'").into(),
+        )),
+    }
+}
+
+/// Parses the hex format (`\x0a0b`) of bytea values. Other values are taken verbatim.
+fn parse_bytea(text: &str) -> PgWireResult<Vec<u8>> {
+    let Some(hex) = text.strip_prefix("\\x") else {
+        return Ok(text.as_bytes().to_vec());
+    };
+
+    if !hex.is_ascii() || hex.len() % 2 != 0 {
+        return Err(PgWireError::FailedToParseParameter(
+            format!("invalid hex bytea value '{text}'").into(),
+        ));
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| PgWireError::FailedToParseParameter(Box::new(e)))
+        })
+        .collect()
+}
+
+fn unsupported_parameter_type(idx: usize, pg_type: &Type) -> PgWireError {
+    user_error(
+        "42804",
+        format!(
+            "Unsupported parameter type {pg_type} for parameter ${}",
+            idx + 1
+        ),
+    )
+}
+
+/// DataFusion requires the parameter values to match the inferred placeholder types exactly.
+/// Clients frequently send parameters with a wider type (e.g. `int8` for an `int4` column), so
+/// the decoded values are cast to the inferred types.
+fn cast_to_inferred_type(
+    value: ScalarValue,
+    inferred_type: Option<&DataType>,
+) -> PgWireResult<ScalarValue> {
+    match inferred_type {
+        Some(inferred_type) if value.data_type() != *inferred_type => value
+            .cast_to(inferred_type)
+            .map_err(|e| PgWireError::ApiError(Box::new(e))),
+        _ => Ok(value),
+    }
+}
+
+fn user_error(code: &str, message: String) -> PgWireError {
+    PgWireError::UserError(Box::new(ErrorInfo::new(
+        "ERROR".to_owned(),
+        code.to_owned(),
+        message,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::logical_expr::{col, table_scan};
+    use futures::stream;
+    use pgwire::api::results::{DataRowEncoder, FieldFormat, FieldInfo};
+
+    /// Collects the messages sent to a client.
+    #[derive(Default)]
+    struct Client {
+        messages: Vec<PgWireBackendMessage>,
+    }
+
+    impl Sink<PgWireBackendMessage> for Client {
+        type Error = PgWireError;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<PgWireResult<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: PgWireBackendMessage) -> PgWireResult<()> {
+            self.get_mut().messages.push(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<PgWireResult<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<PgWireResult<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl Client {
+        /// Returns the number of data rows and the message which concluded them.
+        fn take_response(&mut self) -> (usize, PgWireBackendMessage) {
+            let mut messages = std::mem::take(&mut self.messages);
+            let conclusion = messages.pop().expect("response was sent");
+            assert!(messages
+                .iter()
+                .all(|message| matches!(message, PgWireBackendMessage::DataRow(_))));
+
+            (messages.len(), conclusion)
+        }
+    }
+
+    fn data_rows(count: i32) -> DataRows {
+        let fields = Arc::new(vec![FieldInfo::new(
+            "n".to_owned(),
+            None,
+            None,
+            Type::INT4,
+            FieldFormat::Text,
+        )]);
+
+        stream::iter(0..count)
+            .map(move |n| {
+                let mut encoder = DataRowEncoder::new(fields.clone());
+                encoder.encode_field(&n)?;
+                encoder.finish()
+            })
+            .boxed()
+    }
+
+    /// Plan of `SELECT * FROM t WHERE a = $<a> AND b = $<b>`.
+    fn plan_with_placeholders(a: Placeholder, b: Placeholder) -> LogicalPlan {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+
+        table_scan(Some("t"), &schema, None)
+            .unwrap()
+            .filter(
+                col("a")
+                    .eq(Expr::Placeholder(a))
+                    .and(col("b").eq(Expr::Placeholder(b))),
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_bool_literals() {
+        for text in ["t", "TRUE", "y", "yes", "on", "1", " true "] {
+            assert!(parse_bool(text).unwrap(), "{text}");
+        }
+        for text in ["f", "FALSE", "n", "no", "off", "0"] {
+            assert!(!parse_bool(text).unwrap(), "{text}");
+        }
+        assert!(parse_bool("maybe").is_err());
+        assert!(parse_bool("").is_err());
+    }
+
+    #[test]
+    fn parse_bytea_hex_format() {
+        assert_eq!(parse_bytea("\\x0aff").unwrap(), vec![0x0a, 0xff]);
+        assert_eq!(parse_bytea("\\x").unwrap(), Vec::<u8>::new());
+        assert_eq!(parse_bytea("abc").unwrap(), b"abc".to_vec());
+        assert!(parse_bytea("\\x0").is_err());
+        assert!(parse_bytea("\\xzz").is_err());
+        assert!(parse_bytea("\\xé0").is_err());
+    }
+
+    #[test]
+    fn cast_to_inferred_types() {
+        assert_eq!(
+            cast_to_inferred_type(ScalarValue::Int64(Some(42)), Some(&DataType::Int32)).unwrap(),
+            ScalarValue::Int32(Some(42))
+        );
+        assert_eq!(
+            cast_to_inferred_type(ScalarValue::Int64(None), Some(&DataType::Int32)).unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            cast_to_inferred_type(ScalarValue::Int64(Some(42)), None).unwrap(),
+            ScalarValue::Int64(Some(42))
+        );
+        assert!(cast_to_inferred_type(
+            ScalarValue::Utf8(Some("forty-two".to_owned())),
+            Some(&DataType::Int32)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn inferred_parameter_types_are_ordered_by_position() {
+        let plan = plan_with_placeholders(
+            Placeholder::new("$3".to_owned(), Some(DataType::Int64)),
+            Placeholder::new("$1".to_owned(), Some(DataType::Utf8)),
+        );
+
+        assert_eq!(
+            inferred_parameter_types(&plan).unwrap(),
+            vec![Some(DataType::Utf8), None, Some(DataType::Int64)]
+        );
+    }
+
+    #[test]
+    fn inferred_parameter_types_reject_named_placeholders() {
+        let plan = plan_with_placeholders(
+            Placeholder::new("$a".to_owned(), Some(DataType::Int64)),
+            Placeholder::new("$1".to_owned(), Some(DataType::Utf8)),
+        );
+
+        assert!(inferred_parameter_types(&plan).is_err());
+    }
+
+    #[test]
+    fn declared_parameter_types_take_precedence() {
+        let plan = plan_with_placeholders(
+            Placeholder::new("$1".to_owned(), Some(DataType::Int64)),
+            Placeholder::new("$2".to_owned(), Some(DataType::Utf8)),
+        );
+
+        let plan = apply_declared_parameter_types(plan, &[Type::INT4, Type::UNKNOWN]).unwrap();
+
+        assert_eq!(
+            inferred_parameter_types(&plan).unwrap(),
+            vec![Some(DataType::Int32), Some(DataType::Utf8)]
+        );
+    }
+
+    #[test]
+    fn unsupported_declared_parameter_types_are_rejected() {
+        let plan = plan_with_placeholders(
+            Placeholder::new("$1".to_owned(), Some(DataType::Int64)),
+            Placeholder::new("$2".to_owned(), Some(DataType::Utf8)),
+        );
+
+        assert!(apply_declared_parameter_types(plan, &[Type::JSON]).is_err());
+    }
+
+    #[tokio::test]
+    async fn portal_is_suspended_after_max_rows() {
+        let mut client = Client::default();
+
+        let remaining_rows = send_data_rows(&mut client, data_rows(5), 2)
+            .await
+            .unwrap()
+            .expect("portal is suspended");
+        let (rows, conclusion) = client.take_response();
+        assert_eq!(rows, 2);
+        assert!(matches!(
+            conclusion,
+            PgWireBackendMessage::PortalSuspended(_)
+        ));
+
+        let remaining_rows = send_data_rows(&mut client, remaining_rows, 2)
+            .await
+            .unwrap()
+            .expect("portal is suspended");
+        let (rows, conclusion) = client.take_response();
+        assert_eq!(rows, 2);
+        assert!(matches!(
+            conclusion,
+            PgWireBackendMessage::PortalSuspended(_)
+        ));
+
+        assert!(send_data_rows(&mut client, remaining_rows, 2)
+            .await
+            .unwrap()
+            .is_none());
+        let (rows, conclusion) = client.take_response();
+        assert_eq!(rows, 1);
+        assert!(matches!(
+            conclusion,
+            PgWireBackendMessage::CommandComplete(command_complete) if command_complete.tag == "SELECT 1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn all_rows_are_sent_without_max_rows() {
+        let mut client = Client::default();
+
+        assert!(send_data_rows(&mut client, data_rows(5), 0)
+            .await
+            .unwrap()
+            .is_none());
+        let (rows, conclusion) = client.take_response();
+        assert_eq!(rows, 5);
+        assert!(matches!(
+            conclusion,
+            PgWireBackendMessage::CommandComplete(command_complete) if command_complete.tag == "SELECT 5"
+        ));
     }
 }
@@ -8,8 +8,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use datafusion::arrow::array::{
@@ -30,11 +31,12 @@ use datafusion::arrow::temporal_conversions::{date32_to_datetime, date64_to_date
 use datafusion::physical_plan::SendableRecordBatchStream;
 use futures::{stream, StreamExt};
 use tokio::net::TcpStream;
-use tokio::sync::Mutex;
 
-use crate::extended_query::NoopExtendedQueryHandler;
+use crate::extended_query::{DfQueryParser, SuspendedPortal};
+use datafusion::arrow::datatypes::Schema;
 use pgwire::api::auth::noop::NoopStartupHandler;
 use pgwire::api::copy::NoopCopyHandler;
+use pgwire::api::portal::Format;
 use pgwire::api::query::SimpleQueryHandler;
 use pgwire::api::results::{DataRowEncoder, FieldFormat, FieldInfo, QueryResponse, Response};
 use pgwire::api::{ClientInfo, PgWireHandlerFactory, Type};
@@ -46,7 +48,6 @@ use tracing::warn;
 
 pub(crate) struct HandlerFactory {
     processor: Arc<DfSessionService>,
-    placeholder: Arc<NoopExtendedQueryHandler>,
     authenticator: Arc<NoopStartupHandler>,
     copy_handler: Arc<NoopCopyHandler>,
 }
@@ -54,7 +55,7 @@ pub(crate) struct HandlerFactory {
 impl PgWireHandlerFactory for HandlerFactory {
     type StartupHandler = NoopStartupHandler;
     type SimpleQueryHandler = DfSessionService;
-    type ExtendedQueryHandler = NoopExtendedQueryHandler;
+    type ExtendedQueryHandler = DfSessionService;
     type CopyHandler = NoopCopyHandler;
 
     fn simple_query_handler(&self) -> Arc<Self::SimpleQueryHandler> {
@@ -62,7 +63,7 @@ impl PgWireHandlerFactory for HandlerFactory {
     }
 
     fn extended_query_handler(&self) -> Arc<Self::ExtendedQueryHandler> {
-        self.placeholder.clone()
+        self.processor.clone()
     }
 
     fn startup_handler(&self) -> Arc<Self::StartupHandler> {
@@ -77,14 +78,11 @@ impl PgWireHandlerFactory for HandlerFactory {
 impl HandlerFactory {
     pub fn new(ctx: QueryContext) -> Self {
         let processor = Arc::new(DfSessionService::new(ctx));
-        // We have not implemented extended query in this server, use placeholder instead
-        let placeholder = Arc::new(NoopExtendedQueryHandler::new());
         let authenticator = Arc::new(NoopStartupHandler);
         let copy_handler = Arc::new(NoopCopyHandler);
 
         Self {
             processor,
-            placeholder,
             authenticator,
             copy_handler,
         }
@@ -106,13 +104,18 @@ pub fn spawn_connection(
 }
 
 pub struct DfSessionService {
-    session_context: Mutex<QueryContext>,
+    pub(crate) session_context: QueryContext,
+    pub(crate) query_parser: Arc<DfQueryParser>,
+    /// Suspended portals of the connection by portal name.
+    pub(crate) suspended_portals: Mutex<HashMap<String, SuspendedPortal>>,
 }
 
 impl DfSessionService {
     pub fn new(ctx: QueryContext) -> DfSessionService {
         DfSessionService {
-            session_context: Mutex::new(ctx),
+            query_parser: Arc::new(DfQueryParser::new(ctx.clone())),
+            session_context: ctx,
+            suspended_portals: Mutex::default(),
         }
     }
 }
@@ -127,18 +130,18 @@ impl SimpleQueryHandler for DfSessionService {
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        let ctx = self.session_context.lock().await;
-        let df = ctx
+        let df = self
+            .session_context
             .execute(query)
             .await
             .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
 
-        let resp = arrow_to_pg_encoder(df).await?;
+        let resp = arrow_to_pg_encoder(df, None).await?;
         Ok(vec![Response::Query(resp)])
     }
 }
 
-fn into_pg_type(df_type: &DataType) -> PgWireResult<Type> {
+pub(crate) fn into_pg_type(df_type: &DataType) -> PgWireResult<Type> {
     Ok(match df_type {
         DataType::Null => Type::UNKNOWN,
         DataType::Boolean => Type::BOOL,
@@ -148,7 +151,7 @@ fn into_pg_type(df_type: &DataType) -> PgWireResult<Type> {
         DataType::Int64 => Type::INT8,
         DataType::UInt8 => Type::CHAR,
         DataType::UInt16 => Type::INT2,
-        DataType::UInt32 => Type::INT4,
+        DataType::UInt32 => Type::INT8,
         DataType::UInt64 => Type::INT8,
         DataType::Timestamp(_, _) => Type::TIMESTAMP,
         DataType::Time32(_) | DataType::Time64(_) => Type::TIME,
@@ -169,26 +172,35 @@ fn into_pg_type(df_type: &DataType) -> PgWireResult<Type> {
     })
 }
 
-async fn arrow_to_pg_encoder<'a>(
+/// Converts the arrow schema into the row description. The `result_format` is the result column
+/// format requested by an extended query portal; simple queries don't specify it.
+pub(crate) fn into_pg_fields(
+    schema: &Schema,
+    result_format: Option<&Format>,
+) -> PgWireResult<Vec<FieldInfo>> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| {
+            let pg_type = into_pg_type(f.data_type())?;
+            let format = match result_format {
+                Some(result_format) => result_format.format_for(idx),
+                None if matches!(f.data_type(), DataType::Binary) => FieldFormat::Binary,
+                None => FieldFormat::Text,
+            };
+
+            Ok(FieldInfo::new(f.name().into(), None, None, pg_type, format))
+        })
+        .collect()
+}
+
+pub(crate) async fn arrow_to_pg_encoder<'a>(
     recordbatch_stream: SendableRecordBatchStream,
+    result_format: Option<&Format>,
 ) -> PgWireResult<QueryResponse<'a>> {
     let schema = recordbatch_stream.schema();
-    let fields = Arc::new(
-        schema
-            .fields()
-            .iter()
-            .map(|f| {
-                let pg_type = into_pg_type(f.data_type())?;
-                let format = if matches!(f.data_type(), DataType::Binary) {
-                    FieldFormat::Binary
-                } else {
-                    FieldFormat::Text
-                };
-
-                Ok(FieldInfo::new(f.name().into(), None, None, pg_type, format))
-            })
-            .collect::<PgWireResult<Vec<FieldInfo>>>()?,
-    );
+    let fields = Arc::new(into_pg_fields(&schema, result_format)?);
 
     let fields_ref = fields.clone();
     let pg_row_stream = recordbatch_stream
@@ -320,7 +332,7 @@ fn encode_value(
         DataType::Int16 => encoder.encode_field(&get_i16_value(arr, idx))?,
         DataType::Int32 => encoder.encode_field(&get_i32_value(arr, idx))?,
         DataType::Int64 => encoder.encode_field(&get_i64_value(arr, idx))?,
-        DataType::UInt32 => encoder.encode_field(&get_u32_value(arr, idx))?,
+        DataType::UInt32 => encoder.encode_field(&i64::from(get_u32_value(arr, idx)))?,
         DataType::UInt64 => encoder.encode_field(&(get_u64_value(arr, idx) as i64))?,
         DataType::Float32 => encoder.encode_field(&get_f32_value(arr, idx))?,
         DataType::Float64 => encoder.encode_field(&get_f64_value(arr, idx))?,
@@ -344,3 +356,82 @@ fn encode_value(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion::arrow::datatypes::Field;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("bool", DataType::Boolean, false),
+            Field::new("int4", DataType::Int32, false),
+            Field::new("uint32", DataType::UInt32, false),
+            Field::new("int8", DataType::Int64, true),
+            Field::new("float8", DataType::Float64, false),
+            Field::new("utf8", DataType::Utf8, false),
+            Field::new("binary", DataType::Binary, false),
+            Field::new("date32", DataType::Date32, false),
+        ])
+    }
+
+    fn types_and_formats(fields: &[FieldInfo]) -> Vec<(&str, Type, FieldFormat)> {
+        fields
+            .iter()
+            .map(|field| (field.name(), field.datatype().clone(), field.format()))
+            .collect()
+    }
+
+    #[test]
+    fn row_description_of_simple_query() {
+        let fields = into_pg_fields(&schema(), None).unwrap();
+
+        assert_eq!(
+            types_and_formats(&fields),
+            vec![
+                ("bool", Type::BOOL, FieldFormat::Text),
+                ("int4", Type::INT4, FieldFormat::Text),
+                ("uint32", Type::INT8, FieldFormat::Text),
+                ("int8", Type::INT8, FieldFormat::Text),
+                ("float8", Type::FLOAT8, FieldFormat::Text),
+                ("utf8", Type::VARCHAR, FieldFormat::Text),
+                ("binary", Type::BYTEA, FieldFormat::Binary),
+                ("date32", Type::VARCHAR, FieldFormat::Text),
+            ]
+        );
+    }
+
+    #[test]
+    fn row_description_uses_requested_result_format() {
+        let fields = into_pg_fields(&schema(), Some(&Format::UnifiedText)).unwrap();
+        assert!(fields
+            .iter()
+            .all(|field| field.format() == FieldFormat::Text));
+
+        let fields = into_pg_fields(&schema(), Some(&Format::UnifiedBinary)).unwrap();
+        assert!(fields
+            .iter()
+            .all(|field| field.format() == FieldFormat::Binary));
+
+        let fields = into_pg_fields(
+            &schema(),
+            Some(&Format::Individual(vec![1, 0, 0, 0, 0, 0, 0, 1])),
+        )
+        .unwrap();
+        assert_eq!(fields[0].format(), FieldFormat::Binary);
+        assert_eq!(fields[6].format(), FieldFormat::Text);
+        assert_eq!(fields[7].format(), FieldFormat::Binary);
+    }
+
+    #[test]
+    fn row_description_rejects_unsupported_types() {
+        let schema = Schema::new(vec![Field::new(
+            "list",
+            DataType::new_list(DataType::Int32, true),
+            false,
+        )]);
+
+        assert!(into_pg_fields(&schema, None).is_err());
+    }
+}
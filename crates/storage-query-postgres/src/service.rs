@@ -64,12 +64,16 @@ impl PostgresQueryService {
         let shutdown = cancellation_watcher();
         tokio::pin!(shutdown);
 
-        let factory = Arc::new(HandlerFactory::new(query_context));
         loop {
             select! {
                 incoming_socket = listener.accept() => {
                     match incoming_socket {
-                        Ok((stream, addr)) => spawn_connection(factory.clone(), stream, addr),
+                        Ok((stream, addr)) => {
+                            // Every connection gets its own handlers because they keep the
+                            // suspended portals of the connection
+                            let factory = Arc::new(HandlerFactory::new(query_context.clone()));
+                            spawn_connection(factory, stream, addr)
+                        },
                         Err(err) => {
                             warn!("Failed to accept storage query connection: {err}");
                         }
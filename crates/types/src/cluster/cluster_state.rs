@@ -128,6 +128,8 @@ pub struct PartitionProcessorStatus {
     pub last_persisted_log_lsn: Option<Lsn>,
     // Set if replay_status is CatchingUp
     pub target_tail_lsn: Option<Lsn>,
+    // The applied lsn of the latest partition snapshot, if any
+    pub last_archived_log_lsn: Option<Lsn>,
 }
 
 impl Default for PartitionProcessorStatus {
@@ -144,6 +146,7 @@ impl Default for PartitionProcessorStatus {
             replay_status: ReplayStatus::Starting,
             last_persisted_log_lsn: None,
             target_tail_lsn: None,
+            last_archived_log_lsn: None,
         }
     }
 }
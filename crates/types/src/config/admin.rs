@@ -8,7 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use super::QueryEngineOptions;
+use super::{QueryEngineOptions, TlsServerOptions};
 use crate::cluster_controller::ReplicationStrategy;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -31,6 +31,12 @@ pub struct AdminOptions {
     /// Address to bind for the Admin APIs.
     pub bind_address: SocketAddr,
 
+    /// # TLS
+    ///
+    /// If set, the Admin APIs are served over TLS with the given certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsServerOptions>,
+
     /// # Concurrency limit
     ///
     /// Concurrency limit for the Admin APIs. Default is unlimited.
@@ -103,6 +109,7 @@ impl Default for AdminOptions {
     fn default() -> Self {
         Self {
             bind_address: "0.0.0.0:9070".parse().unwrap(),
+            tls: None,
             // max is limited by Tower's LoadShedLayer.
             concurrent_api_requests_limit: None,
             query_engine: Default::default(),
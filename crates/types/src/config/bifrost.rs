@@ -9,7 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -40,6 +40,8 @@ pub struct BifrostOptions {
     /// [IN DEVELOPMENT]
     /// Configuration of replicated loglet provider
     pub replicated_loglet: ReplicatedLogletOptions,
+    /// Configuration of tiered storage loglet provider
+    pub tiered_storage: TieredStorageLogletOptions,
 
     /// # Read retry policy
     ///
@@ -95,6 +97,7 @@ impl Default for BifrostOptions {
             #[cfg(feature = "replicated-loglet")]
             replicated_loglet: ReplicatedLogletOptions::default(),
             local: LocalLogletOptions::default(),
+            tiered_storage: TieredStorageLogletOptions::default(),
             read_retry_policy: RetryPolicy::exponential(
                 Duration::from_millis(50),
                 2.0,
@@ -207,6 +210,58 @@ impl Default for LocalLogletOptions {
     }
 }
 
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(rename = "TieredStorageLoglet", default)
+)]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct TieredStorageLogletOptions {
+    /// # Segments directory
+    ///
+    /// Directory in which the immutable files of offloaded log segments are stored. Any node
+    /// might have to read an offloaded segment, so this must be a location which is shared by
+    /// all nodes, e.g. a mounted blob/object store or shared file system. Segments can only be
+    /// offloaded once it has been configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segments_dir: Option<PathBuf>,
+
+    /// # Compression level
+    ///
+    /// The zstd compression level used when writing segment files.
+    pub compression_level: i32,
+
+    /// # Offloaded loglet trim delay
+    ///
+    /// Time to wait after a segment has been offloaded before the cluster controller trims its
+    /// original loglet. This gives all nodes the chance to observe that the segment is served
+    /// from tiered storage, while readers which haven't done so yet keep reading from the
+    /// original loglet. Offloaded loglets are recorded in the logs metadata, so they are trimmed
+    /// even if the node which offloaded them restarts.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub offloaded_loglet_trim_delay: humantime::Duration,
+}
+
+impl TieredStorageLogletOptions {
+    pub fn segments_dir(&self) -> Option<&Path> {
+        self.segments_dir.as_deref()
+    }
+}
+
+impl Default for TieredStorageLogletOptions {
+    fn default() -> Self {
+        Self {
+            segments_dir: None,
+            compression_level: 3,
+            offloaded_loglet_trim_delay: Duration::from_secs(10 * 60).into(),
+        }
+    }
+}
+
 #[cfg(feature = "replicated-loglet")]
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
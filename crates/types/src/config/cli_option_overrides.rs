@@ -15,6 +15,7 @@ use humantime::Duration;
 use serde::Serialize;
 use serde_with::{serde_as, skip_serializing_none};
 
+use crate::locality::NodeLocation;
 use crate::net::{AdvertisedAddress, BindAddress};
 use crate::nodes_config::Role;
 use crate::PlainNodeId;
@@ -42,6 +43,10 @@ pub struct CommonOptionCliOverride {
     #[clap(long, global = true)]
     pub force_node_id: Option<PlainNodeId>,
 
+    /// The location of this node in the form `region.zone`, e.g. `us-east-1.use1-az1`.
+    #[clap(long, env = "RESTATE_LOCATION", global = true)]
+    pub location: Option<NodeLocation>,
+
     /// A unique identifier for the cluster. All nodes in the same cluster should
     /// have the same.
     #[clap(long, env = "RESTATE_CLUSTER_NAME", global = true)]
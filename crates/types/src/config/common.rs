@@ -18,7 +18,10 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
-use super::{AwsOptions, HttpOptions, PerfStatsLevel, RocksDbOptions};
+use super::{
+    AwsOptions, HttpOptions, PerfStatsLevel, RocksDbOptions, TlsClientOptions, TlsServerOptions,
+};
+use crate::locality::NodeLocation;
 use crate::net::{AdvertisedAddress, BindAddress};
 use crate::nodes_config::Role;
 use crate::retries::RetryPolicy;
@@ -46,6 +49,14 @@ pub struct CommonOptions {
     /// If set, the node insists on acquiring this node ID.
     pub force_node_id: Option<PlainNodeId>,
 
+    /// # Node Location
+    ///
+    /// The location of this node in the form `region.zone`, for example `us-east-1.use1-az1`.
+    /// Replicated loglets use it to place copies of records in distinct zones or regions when the
+    /// replication property is zone- or region-scoped. It's unset by default.
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub location: NodeLocation,
+
     /// # Cluster Name
     ///
     /// A unique identifier for the cluster. All nodes in the same cluster should
@@ -72,6 +83,15 @@ pub struct CommonOptions {
     #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub advertised_address: AdvertisedAddress,
 
+    /// # TLS
+    ///
+    /// If set, the Node server terminates TLS with the given certificate. Set `client-ca-file` to
+    /// require other nodes to authenticate with a client certificate. Unix domain sockets are
+    /// always served without TLS. Other nodes connect
+    /// with TLS if the advertised address uses the `https` scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsServerOptions>,
+
     /// # Partitions
     ///
     /// Number of partitions that will be provisioned during cluster bootstrap,
@@ -329,6 +349,7 @@ impl Default for CommonOptions {
             roles: EnumSet::all() - Role::LogServer,
             node_name: None,
             force_node_id: None,
+            location: NodeLocation::default(),
             cluster_name: "localcluster".to_owned(),
             // boot strap the cluster by default. This is very likely to change in the future to be
             // false by default. For now, this is true to make the converged deployment backward
@@ -338,6 +359,7 @@ impl Default for CommonOptions {
             metadata_store_client: MetadataStoreClientOptions::default(),
             bind_address: "0.0.0.0:5122".parse().unwrap(),
             advertised_address: AdvertisedAddress::from_str("http://127.0.0.1:5122/").unwrap(),
+            tls: None,
             bootstrap_num_partitions: NonZeroU16::new(24).unwrap(),
             histogram_inactivity_timeout: None,
             disable_prometheus: false,
@@ -439,8 +461,16 @@ pub struct MetadataStoreClientOptions {
     /// Backoff policy used by the metadata store client when it encounters concurrent
     /// modifications.
     pub metadata_store_client_backoff_policy: RetryPolicy,
+
+    /// # TLS of the metadata store client
+    ///
+    /// If set, connections to an embedded metadata store advertising an `https` address use TLS,
+    /// presenting the client certificate if configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_store_client_tls: Option<TlsClientOptions>,
 }
 
+#[serde_as]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(
     tag = "type",
@@ -457,9 +487,13 @@ pub struct MetadataStoreClientOptions {
 )]
 pub enum MetadataStoreClient {
     /// Connects to an embedded metadata store that is run by nodes that run with the MetadataStore role.
+    /// With a replicated metadata store, list the addresses of all its nodes: requests fail over
+    /// to the next address if a node is unavailable.
     Embedded {
-        #[cfg_attr(feature = "schemars", schemars(with = "String"))]
-        address: AdvertisedAddress,
+        #[serde(alias = "address")]
+        #[serde_as(as = "serde_with::OneOrMany<_>")]
+        #[cfg_attr(feature = "schemars", schemars(with = "Vec<String>"))]
+        addresses: Vec<AdvertisedAddress>,
     },
     /// Uses external etcd as metadata store.
     /// The addresses are formatted as `host:port`
@@ -473,9 +507,9 @@ impl Default for MetadataStoreClientOptions {
     fn default() -> Self {
         Self {
             metadata_store_client: MetadataStoreClient::Embedded {
-                address: "http://127.0.0.1:5123"
+                addresses: vec!["http://127.0.0.1:5123"
                     .parse()
-                    .expect("valid metadata store address"),
+                    .expect("valid metadata store address")],
             },
             metadata_store_client_backoff_policy: RetryPolicy::exponential(
                 Duration::from_millis(10),
@@ -483,6 +517,7 @@ impl Default for MetadataStoreClientOptions {
                 None,
                 Some(Duration::from_millis(100)),
             ),
+            metadata_store_client_tls: None,
         }
     }
 }
@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -117,6 +118,57 @@ impl Http2KeepAliveOptions {
     }
 }
 
+/// # TLS server options
+///
+/// Certificate and key used to terminate TLS. The files are watched, and the certificates are
+/// reloaded without restarting the server when they change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TlsServerOptions {
+    /// # Certificate file
+    ///
+    /// Path of the PEM encoded certificate chain presented to the clients.
+    pub cert_file: PathBuf,
+
+    /// # Key file
+    ///
+    /// Path of the PEM encoded private key of the certificate.
+    pub key_file: PathBuf,
+
+    /// # Client CA file
+    ///
+    /// Path of the PEM encoded certificates of the authorities used to verify client
+    /// certificates. If set, clients must present a certificate signed by one of these
+    /// authorities (mutual TLS).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_file: Option<PathBuf>,
+}
+
+/// # TLS client options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TlsClientOptions {
+    /// # CA file
+    ///
+    /// Path of the PEM encoded certificates of the authorities used to verify the server
+    /// certificates.
+    pub ca_file: PathBuf,
+
+    /// # Certificate file
+    ///
+    /// Path of the PEM encoded certificate chain presented to the servers requiring mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_file: Option<PathBuf>,
+
+    /// # Key file
+    ///
+    /// Path of the PEM encoded private key of the client certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<PathBuf>,
+}
+
 #[derive(Clone, Debug, thiserror::Error)]
 #[error("invalid proxy Uri (must have scheme, authority, and path): {0}")]
 pub struct InvalidProxyUri(Uri);
@@ -10,13 +10,18 @@
 
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use http::HeaderName;
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use tokio::sync::Semaphore;
 
-use super::KafkaClusterOptions;
+use super::{KafkaClusterOptions, TlsServerOptions};
 
 /// # Ingress options
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "schemars", schemars(rename = "IngressOptions"))]
@@ -29,6 +34,12 @@ pub struct IngressOptions {
     /// The address to bind for the ingress.
     pub bind_address: SocketAddr,
 
+    /// # TLS
+    ///
+    /// If set, the ingress terminates TLS with the given certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsServerOptions>,
+
     /// # Concurrency limit
     ///
     /// Local concurrency limit to use to limit the amount of concurrent requests. If exceeded,
@@ -36,6 +47,57 @@ pub struct IngressOptions {
     concurrent_api_requests_limit: Option<NonZeroUsize>,
 
     kafka_clusters: Vec<KafkaClusterOptions>,
+
+    /// # Authentication
+    ///
+    /// Authentication of the requests received by the ingress. Requests are authenticated
+    /// with the first configured method for which they carry credentials. If no method is
+    /// configured, requests are not authenticated.
+    #[serde(default)]
+    pub authentication: IngressAuthenticationOptions,
+
+    /// # Rate limit client header
+    ///
+    /// Header identifying the client of a request which didn't authenticate with the ingress,
+    /// used by the rate limits configured with `per-client` to limit each client separately.
+    /// Authenticated requests are always limited per principal.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub rate_limit_client_header: Option<HeaderName>,
+
+    /// # Enable gRPC
+    ///
+    /// If true, the ingress additionally serves the registered services over gRPC and the
+    /// Connect protocol, routing the requests by their `/package.Service/Method` path. The key of
+    /// virtual objects and workflows is passed in the `x-restate-key` metadata. Messages are passed
+    /// to the handlers as they are, hence handlers with JSON input must be called with the
+    /// `application/grpc+json` codec.
+    #[serde(default)]
+    pub enable_grpc: bool,
+
+    /// # Schema registry connect timeout
+    ///
+    /// How long to wait for a TCP connection to the schema registry of a Kafka subscription to
+    /// be established, before failing the resolution of the schema.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(default = "IngressOptions::default_schema_registry_connect_timeout")]
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub schema_registry_connect_timeout: humantime::Duration,
+
+    /// # Schema registry request timeout
+    ///
+    /// How long to wait for the schema registry of a Kafka subscription to reply, including the
+    /// time to connect, before failing the resolution of the schema. The record is then retried
+    /// according to the retry policy of the subscription consumers.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(default = "IngressOptions::default_schema_registry_request_timeout")]
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub schema_registry_request_timeout: humantime::Duration,
 }
 
 impl IngressOptions {
@@ -56,15 +118,114 @@ impl IngressOptions {
             Semaphore::MAX_PERMITS - 1,
         )
     }
+
+    fn default_schema_registry_connect_timeout() -> humantime::Duration {
+        Duration::from_secs(10).into()
+    }
+
+    fn default_schema_registry_request_timeout() -> humantime::Duration {
+        Duration::from_secs(30).into()
+    }
 }
 
 impl Default for IngressOptions {
     fn default() -> Self {
         Self {
             bind_address: "0.0.0.0:8080".parse().unwrap(),
+            tls: None,
             // max is limited by Tower's LoadShedLayer.
             concurrent_api_requests_limit: None,
             kafka_clusters: Default::default(),
+            authentication: Default::default(),
+            rate_limit_client_header: None,
+            enable_grpc: false,
+            schema_registry_connect_timeout: Self::default_schema_registry_connect_timeout(),
+            schema_registry_request_timeout: Self::default_schema_registry_request_timeout(),
         }
     }
 }
+
+/// # Ingress authentication options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressAuthenticationOptions {
+    /// # API keys
+    ///
+    /// Static API keys accepted in the `x-restate-api-key` header.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<ApiKeyOptions>,
+
+    /// # JWT
+    ///
+    /// Validation of the JWT bearer tokens sent in the `authorization` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<JwtAuthenticationOptions>,
+
+    /// # Client certificate
+    ///
+    /// If true, requests are authenticated with the common name of the TLS client certificate.
+    /// This requires the ingress to terminate TLS with a client CA file.
+    #[serde(default)]
+    pub client_certificate: bool,
+}
+
+impl IngressAuthenticationOptions {
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || self.jwt.is_some() || self.client_certificate
+    }
+}
+
+/// # API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeyOptions {
+    /// # Principal
+    ///
+    /// Principal of the requests authenticated with this key.
+    pub principal: String,
+
+    /// # Key
+    ///
+    /// The API key.
+    pub key: String,
+}
+
+/// # JWT authentication options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct JwtAuthenticationOptions {
+    /// # JWKS file
+    ///
+    /// Path of the JSON Web Key Set used to verify the token signatures. Tokens are verified
+    /// with the key matching their `kid` header, or with the only key of the set if the token
+    /// has no `kid`.
+    pub jwks_file: PathBuf,
+
+    /// # Issuer
+    ///
+    /// If set, tokens must have a matching `iss` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+
+    /// # Audience
+    ///
+    /// If set, tokens must have a matching `aud` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audience: Option<String>,
+
+    /// # Principal claim
+    ///
+    /// Name of the string claim used as principal of the authenticated requests.
+    #[serde(default = "JwtAuthenticationOptions::default_principal_claim")]
+    pub principal_claim: String,
+}
+
+impl JwtAuthenticationOptions {
+    fn default_principal_claim() -> String {
+        "sub".to_owned()
+    }
+}
@@ -8,8 +8,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -17,8 +18,11 @@ use serde_with::serde_as;
 use restate_serde_util::NonZeroByteCount;
 use tracing::warn;
 
-use super::{data_dir, CommonOptions, RocksDbOptions, RocksDbOptionsBuilder};
-use crate::net::BindAddress;
+use super::{
+    data_dir, CommonOptions, RocksDbOptions, RocksDbOptionsBuilder, TlsClientOptions,
+    TlsServerOptions,
+};
+use crate::net::{AdvertisedAddress, BindAddress};
 
 /// # Metadata store options
 #[serde_as]
@@ -37,6 +41,12 @@ pub struct MetadataStoreOptions {
     #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub bind_address: BindAddress,
 
+    /// # TLS
+    ///
+    /// If set, the metadata store is served over TLS with the given certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsServerOptions>,
+
     /// # Limit number of in-flight requests
     ///
     /// Number of in-flight metadata store requests.
@@ -60,6 +70,133 @@ pub struct MetadataStoreOptions {
     ///
     /// The RocksDB options which will be used to configure the metadata store's RocksDB instance.
     pub rocksdb: RocksDbOptions,
+
+    /// # Metadata store kind
+    ///
+    /// Defines whether the metadata store runs as a single node store (`local`) or as a
+    /// Raft-replicated store (`raft`) which forms a quorum with the other metadata store nodes.
+    pub kind: MetadataStoreKind,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(
+        title = "Metadata store kind",
+        description = "Definition of the metadata store implementation"
+    )
+)]
+pub enum MetadataStoreKind {
+    /// Single node metadata store which stores the key-value pairs in RocksDB.
+    #[default]
+    Local,
+    /// Raft-replicated metadata store which stores the key-value pairs in RocksDB.
+    Raft(RaftOptions),
+}
+
+/// # Raft options
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+pub struct RaftOptions {
+    /// # Raft id
+    ///
+    /// Unique id of this metadata store replica. Must not be reused by other replicas.
+    pub id: NonZeroU64,
+
+    /// # Initial peers
+    ///
+    /// The replicas which form the initial Raft configuration. All replicas which bootstrap the
+    /// metadata store need to be configured with the same set of peers (including themselves).
+    /// Replicas which join an existing metadata store should leave this list empty and need to be
+    /// added via the `AddNode` call of the `RaftMetadataStoreSvc`.
+    pub peers: Vec<RaftPeer>,
+
+    /// # Raft tick interval
+    ///
+    /// The interval at which the Raft state machine is ticked.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub raft_tick_interval: humantime::Duration,
+
+    /// # Raft election tick
+    ///
+    /// The number of ticks after which a follower starts a new election if it has not heard
+    /// from the leader. Must be larger than `raft-heartbeat-tick`.
+    pub raft_election_tick: NonZeroUsize,
+
+    /// # Raft heartbeat tick
+    ///
+    /// The number of ticks after which the leader sends heartbeats to its followers.
+    pub raft_heartbeat_tick: NonZeroUsize,
+
+    /// # Snapshot interval
+    ///
+    /// Number of applied log entries after which a snapshot of the key-value pairs is created
+    /// and the Raft log is truncated.
+    pub snapshot_interval: NonZeroU64,
+
+    /// # Request timeout
+    ///
+    /// Time after which requests which have not been committed or served fail as unavailable.
+    /// Requests forwarded to the leader can be lost, for example if the leader is unreachable,
+    /// and Raft doesn't retransmit them.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(default = "RaftOptions::default_request_timeout")]
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub request_timeout: humantime::Duration,
+
+    /// # Peer TLS
+    ///
+    /// If set, connections to replicas advertising an `https` address use TLS, presenting the
+    /// client certificate if configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_tls: Option<TlsClientOptions>,
+}
+
+impl Default for RaftOptions {
+    fn default() -> Self {
+        Self {
+            id: NonZeroU64::new(1).expect("1 to be non zero"),
+            peers: Vec::default(),
+            raft_tick_interval: Duration::from_millis(100).into(),
+            raft_election_tick: NonZeroUsize::new(10).expect("10 to be non zero"),
+            raft_heartbeat_tick: NonZeroUsize::new(2).expect("2 to be non zero"),
+            snapshot_interval: NonZeroU64::new(1000).expect("1000 to be non zero"),
+            request_timeout: Self::default_request_timeout(),
+            peer_tls: None,
+        }
+    }
+}
+
+impl RaftOptions {
+    fn default_request_timeout() -> humantime::Duration {
+        Duration::from_secs(5).into()
+    }
+}
+
+/// # Raft peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RaftPeer {
+    /// Raft id of the peer.
+    pub id: NonZeroU64,
+    /// Address under which the metadata store of the peer can be reached.
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub address: AdvertisedAddress,
 }
 
 impl MetadataStoreOptions {
@@ -91,7 +228,12 @@ impl MetadataStoreOptions {
     }
 
     pub fn data_dir(&self) -> PathBuf {
-        data_dir("local-metadata-store")
+        match &self.kind {
+            MetadataStoreKind::Local => data_dir("local-metadata-store"),
+            MetadataStoreKind::Raft(raft_options) => {
+                data_dir("raft-metadata-store").join(raft_options.id.to_string())
+            }
+        }
     }
 
     pub fn request_queue_length(&self) -> usize {
@@ -107,11 +249,13 @@ impl Default for MetadataStoreOptions {
             .expect("valid RocksDbOptions");
         Self {
             bind_address: "0.0.0.0:5123".parse().expect("valid bind address"),
+            tls: None,
             request_queue_length: NonZeroUsize::new(32).unwrap(),
             // set by apply_common in runtime
             rocksdb_memory_budget: None,
             rocksdb_memory_ratio: 0.01,
             rocksdb,
+            kind: MetadataStoreKind::default(),
         }
     }
 }
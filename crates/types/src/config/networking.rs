@@ -14,6 +14,7 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
+use super::TlsClientOptions;
 use crate::retries::RetryPolicy;
 
 /// # Networking options
@@ -41,6 +42,13 @@ pub struct NetworkingOptions {
     #[serde_as(as = "serde_with::DisplayFromStr")]
     #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub handshake_timeout: humantime::Duration,
+
+    /// # TLS
+    ///
+    /// If set, connections to nodes advertising an `https` address use TLS, presenting the client
+    /// certificate if configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsClientOptions>,
 }
 
 impl Default for NetworkingOptions {
@@ -55,6 +63,7 @@ impl Default for NetworkingOptions {
 
             outbound_queue_length: NonZeroUsize::new(1000).expect("Non zero number"),
             handshake_timeout: Duration::from_secs(3).into(),
+            tls: None,
         }
     }
 }
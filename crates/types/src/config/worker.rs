@@ -10,7 +10,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::num::{NonZeroU16, NonZeroUsize};
+use std::num::{NonZeroU16, NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
 use std::time::Duration;
 use tracing::warn;
@@ -350,12 +350,59 @@ impl Default for StorageOptions {
 /// # Snapshot options.
 /// Configures the worker store partition snapshot mechanism.
 #[serde_as]
-#[derive(Default, Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "schemars", schemars(rename = "SnapshotsOptions", default))]
 #[serde(rename_all = "kebab-case")]
 #[builder(default)]
-pub struct SnapshotsOptions {}
+pub struct SnapshotsOptions {
+    /// # Snapshot repository destination
+    ///
+    /// Base URL of the repository to which partition snapshots are uploaded. The repository is
+    /// shared by all nodes of the cluster, so that partition stores can be bootstrapped from it
+    /// on nodes which don't have a local copy of them. Supported destinations are S3-compatible
+    /// object stores (`s3://bucket/prefix`) and shared file systems (`file:///mnt/snapshots`).
+    ///
+    /// Object store credentials and settings are read from the standard `AWS_*` environment
+    /// variables, e.g. `AWS_REGION` or `AWS_ENDPOINT` for S3-compatible stores.
+    ///
+    /// If unset, snapshots are only kept in the node-local snapshots directory.
+    pub destination: Option<String>,
+
+    /// # Retained snapshots
+    ///
+    /// Number of snapshots per partition to retain in the snapshot repository, or in the
+    /// node-local snapshots directory if no repository is configured. Older snapshots are pruned
+    /// after a new snapshot has been created.
+    pub retained_snapshots: NonZeroUsize,
+
+    /// # Snapshot interval
+    ///
+    /// Interval at which the leader of a partition automatically creates a snapshot, if records
+    /// have been applied since the last snapshot. Time-based snapshots are disabled if unset.
+    ///
+    /// Can be configured using the [`humantime`](https://docs.rs/humantime/latest/humantime/fn.parse_duration.html) format.
+    #[serde(with = "serde_with::As::<Option<serde_with::DisplayFromStr>>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub snapshot_interval: Option<humantime::Duration>,
+
+    /// # Snapshot interval in number of records
+    ///
+    /// Number of log records after which the leader of a partition automatically creates a
+    /// snapshot. Record-based snapshots are disabled if unset.
+    pub snapshot_interval_num_records: Option<NonZeroU64>,
+}
+
+impl Default for SnapshotsOptions {
+    fn default() -> Self {
+        Self {
+            destination: None,
+            retained_snapshots: NonZeroUsize::new(3).expect("Non zero number"),
+            snapshot_interval: None,
+            snapshot_interval_num_records: None,
+        }
+    }
+}
 
 impl SnapshotsOptions {
     pub fn snapshots_base_dir(&self) -> PathBuf {
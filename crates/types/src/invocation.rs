@@ -12,8 +12,8 @@
 
 use crate::errors::InvocationError;
 use crate::identifiers::{
-    EntryIndex, IdempotencyId, IngressRequestId, InvocationId, PartitionKey, ServiceId,
-    WithPartitionKey,
+    EntryIndex, IdempotencyId, IngressRequestId, InvocationId, InvocationUuid, PartitionKey,
+    ServiceId, WithPartitionKey,
 };
 use crate::time::MillisSinceEpoch;
 use crate::GenerationalNodeId;
@@ -26,6 +26,7 @@ use std::hash::Hash;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::time::Duration;
+use ulid::Ulid;
 
 // Re-exporting opentelemetry [`TraceId`] to avoid having to import opentelemetry in all crates.
 pub use opentelemetry::trace::TraceId;
@@ -706,6 +707,131 @@ pub struct PurgeInvocationRequest {
     pub invocation_id: InvocationId,
 }
 
+/// Epoch of an in-flight invocation. It is incremented every time the invocation is restarted from
+/// scratch, so that effects produced by earlier attempts can be told apart from the current ones.
+pub type InvocationEpoch = u32;
+
+/// Message to retry an invocation.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InvocationRetry {
+    pub invocation_id: InvocationId,
+    pub flavor: RetryFlavor,
+}
+
+impl InvocationRetry {
+    pub const fn retry_now(invocation_id: InvocationId) -> Self {
+        Self {
+            invocation_id,
+            flavor: RetryFlavor::RetryNow,
+        }
+    }
+
+    pub const fn restart(invocation_id: InvocationId) -> Self {
+        Self {
+            invocation_id,
+            flavor: RetryFlavor::Restart,
+        }
+    }
+}
+
+/// Flavor of the retry. Can be an immediate retry or a restart from scratch.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum RetryFlavor {
+    /// skip the remaining backoff of a retrying invocation
+    RetryNow,
+    /// truncate the journal back to the input entry and re-invoke with the same input
+    Restart,
+}
+
+/// Message to pause or resume an invocation.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InvocationPause {
+    pub invocation_id: InvocationId,
+    pub flavor: PauseFlavor,
+}
+
+impl InvocationPause {
+    pub const fn pause(invocation_id: InvocationId) -> Self {
+        Self {
+            invocation_id,
+            flavor: PauseFlavor::Pause,
+        }
+    }
+
+    pub const fn resume(invocation_id: InvocationId) -> Self {
+        Self {
+            invocation_id,
+            flavor: PauseFlavor::Resume,
+        }
+    }
+}
+
+/// Message to pause or resume a service on a partition. While a service is paused, new
+/// invocations of it are held back instead of being invoked.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ServicePause {
+    pub service_name: ByteString,
+    pub flavor: PauseFlavor,
+}
+
+impl ServicePause {
+    pub fn pause(service_name: impl Into<ByteString>) -> Self {
+        Self {
+            service_name: service_name.into(),
+            flavor: PauseFlavor::Pause,
+        }
+    }
+
+    pub fn resume(service_name: impl Into<ByteString>) -> Self {
+        Self {
+            service_name: service_name.into(),
+            flavor: PauseFlavor::Resume,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum PauseFlavor {
+    Pause,
+    Resume,
+}
+
+/// Message to replay or discard an invocation recorded in the dead letter table.
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeadLetterRequest {
+    pub invocation_id: InvocationId,
+    pub flavor: DeadLetterFlavor,
+}
+
+impl DeadLetterRequest {
+    pub fn replay(invocation_id: InvocationId) -> Self {
+        // The replayed invocation must be processed by the same partition owning the dead letter
+        let new_invocation_id = InvocationId::from_parts(
+            invocation_id.partition_key(),
+            InvocationUuid::from_u128(Ulid::new().into()),
+        );
+        Self {
+            invocation_id,
+            flavor: DeadLetterFlavor::Replay { new_invocation_id },
+        }
+    }
+
+    pub const fn discard(invocation_id: InvocationId) -> Self {
+        Self {
+            invocation_id,
+            flavor: DeadLetterFlavor::Discard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DeadLetterFlavor {
+    /// re-submit the recorded input as a new invocation with the given id
+    Replay { new_invocation_id: InvocationId },
+    /// drop the dead letter
+    Discard,
+}
+
 // A hack to allow spancontext to be serialized.
 // Details in https://github.com/open-telemetry/opentelemetry-rust/issues/576#issuecomment-1253396100
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -849,6 +975,32 @@ impl WithPartitionKey for AttachInvocationRequest {
     }
 }
 
+/// Represents a request to complete a durable promise of a workflow, from outside the workflow
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PromiseCompletionRequest {
+    /// Target of the workflow handler owning the promise, used to reply to the response sink.
+    pub workflow_target: InvocationTarget,
+    pub key: ByteString,
+    pub completion: ResponseResult,
+    pub response_sink: ServiceInvocationResponseSink,
+}
+
+impl PromiseCompletionRequest {
+    pub fn workflow_query(&self) -> InvocationQuery {
+        InvocationQuery::Workflow(
+            self.workflow_target
+                .as_keyed_service_id()
+                .expect("workflow targets have a key"),
+        )
+    }
+}
+
+impl WithPartitionKey for PromiseCompletionRequest {
+    fn partition_key(&self) -> PartitionKey {
+        self.workflow_query().partition_key()
+    }
+}
+
 #[cfg(any(test, feature = "test-util"))]
 mod mocks {
     use super::*;
@@ -49,6 +49,7 @@ pub enum Entry {
 impl Entry {
     pub fn input(result: impl Into<Bytes>) -> Self {
         Entry::Input(InputEntry {
+            headers: vec![],
             value: result.into(),
         })
     }
@@ -215,6 +216,17 @@ impl From<EntryResult> for ResponseResult {
     }
 }
 
+impl From<ResponseResult> for EntryResult {
+    fn from(value: ResponseResult) -> Self {
+        match value {
+            ResponseResult::Success(bytes) => EntryResult::Success(bytes),
+            ResponseResult::Failure(err) => {
+                EntryResult::Failure(err.code(), err.message().to_owned().into())
+            }
+        }
+    }
+}
+
 pub trait CompletableEntry: private::Sealed {
     /// Returns true if the entry is completed.
     fn is_completed(&self) -> bool;
@@ -237,6 +249,7 @@ mod private {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InputEntry {
+    pub headers: Vec<Header>,
     pub value: Bytes,
 }
 
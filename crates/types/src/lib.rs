@@ -32,6 +32,7 @@ pub mod ingress;
 pub mod invocation;
 pub mod journal;
 pub mod live;
+pub mod locality;
 pub mod logs;
 pub mod message;
 pub mod metadata_store;
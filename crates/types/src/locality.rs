@@ -0,0 +1,153 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::replicated_loglet::LocationScope;
+
+const SEPARATOR: char = '.';
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid node location '{0}', expected either 'region' or 'region.zone'")]
+pub struct NodeLocationParseError(String);
+
+/// Hierarchical location of a node in the cluster, in the form `region.zone`. Each label is
+/// optional, an empty location means that the node's location is unknown. A zone can only be
+/// set together with its region, since zone names are only unique within a region.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    Eq,
+    PartialEq,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct NodeLocation {
+    region: Option<String>,
+    zone: Option<String>,
+}
+
+impl NodeLocation {
+    pub fn new(region: impl Into<String>, zone: Option<String>) -> Self {
+        Self {
+            region: Some(region.into()),
+            zone,
+        }
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    pub fn zone(&self) -> Option<&str> {
+        self.zone.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.region.is_none()
+    }
+
+    /// Returns true if the location defines a label for the given scope. The node scope is
+    /// always defined, since it's identified by the node id.
+    pub fn is_scope_defined(&self, scope: LocationScope) -> bool {
+        match scope {
+            LocationScope::Node => true,
+            LocationScope::Zone => self.zone.is_some(),
+            LocationScope::Region => self.region.is_some(),
+        }
+    }
+
+    /// Returns the failure domain of this location at the given scope, that is the fully
+    /// qualified label of the scope (`region` or `region.zone`). Nodes in the same domain share
+    /// the failure domain at that scope. Returns `None` for the node scope, or if the location
+    /// doesn't define the scope.
+    pub fn domain(&self, scope: LocationScope) -> Option<String> {
+        match scope {
+            LocationScope::Node => None,
+            LocationScope::Zone => Some(format!(
+                "{}{SEPARATOR}{}",
+                self.region.as_ref()?,
+                self.zone.as_ref()?
+            )),
+            LocationScope::Region => self.region.clone(),
+        }
+    }
+}
+
+impl Display for NodeLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(region) = &self.region {
+            write!(f, "{region}")?;
+            if let Some(zone) = &self.zone {
+                write!(f, "{SEPARATOR}{zone}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NodeLocation {
+    type Err = NodeLocationParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut labels = s.split(SEPARATOR);
+        let region = labels.next().expect("split yields at least one label");
+        let zone = labels.next();
+        if labels.next().is_some() || region.is_empty() || zone.is_some_and(|zone| zone.is_empty())
+        {
+            return Err(NodeLocationParseError(s.to_owned()));
+        }
+
+        Ok(Self::new(region, zone.map(ToOwned::to_owned)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_node_location() {
+        let location: NodeLocation = "us-east-1.use1-az1".parse().unwrap();
+        assert_eq!(location.region(), Some("us-east-1"));
+        assert_eq!(location.zone(), Some("use1-az1"));
+        assert_eq!(
+            location.domain(LocationScope::Zone).as_deref(),
+            Some("us-east-1.use1-az1")
+        );
+        assert_eq!(
+            location.domain(LocationScope::Region).as_deref(),
+            Some("us-east-1")
+        );
+        assert_eq!(location.domain(LocationScope::Node), None);
+        assert_eq!(location.to_string(), "us-east-1.use1-az1");
+
+        let location: NodeLocation = "us-east-1".parse().unwrap();
+        assert!(!location.is_scope_defined(LocationScope::Zone));
+        assert_eq!(location.domain(LocationScope::Zone), None);
+        assert_eq!(location.to_string(), "us-east-1");
+
+        let location: NodeLocation = "".parse().unwrap();
+        assert!(location.is_empty());
+        assert_eq!(location.to_string(), "");
+
+        assert!("us-east-1.".parse::<NodeLocation>().is_err());
+        assert!(".use1-az1".parse::<NodeLocation>().is_err());
+        assert!("us-east-1.use1-az1.rack".parse::<NodeLocation>().is_err());
+    }
+}
@@ -12,9 +12,11 @@ use std::num::NonZeroU32;
 use std::ops::Deref;
 
 use super::metadata::{
-    Chain, LogletConfig, LogletParams, Logs, MaybeSegment, ProviderKind, SegmentIndex,
+    Chain, LogletConfig, LogletParams, Logs, MaybeSegment, ProviderKind, RetiredLoglet,
+    SegmentIndex,
 };
 use super::{LogId, Lsn};
+use crate::time::MillisSinceEpoch;
 use crate::Version;
 
 #[derive(Debug, Default, Clone)]
@@ -29,6 +31,10 @@ pub enum BuilderError {
     LogAlreadyExists(LogId),
     #[error("Segment conflicts with existing (base_lsn={0})")]
     SegmentConflict(Lsn),
+    #[error("Segment {0} doesn't exist in the chain")]
+    UnknownSegment(SegmentIndex),
+    #[error("Segment {0} is the tail segment")]
+    TailSegment(SegmentIndex),
 }
 
 impl LogsBuilder {
@@ -156,6 +162,49 @@ impl<'a> ChainBuilder<'a> {
             }
         }
     }
+
+    /// Replaces the loglet of a sealed segment while keeping its base_lsn and index. Returns the
+    /// base_lsn of the replaced segment. The new loglet must hold the same records as the one it
+    /// replaces, this is used to move sealed segments to a different provider. The replaced loglet
+    /// is kept as retired loglet until its records have been released.
+    ///
+    /// By design, the API protects against replacing the tail segment.
+    pub fn replace_sealed_segment(
+        &mut self,
+        segment_index: SegmentIndex,
+        provider: ProviderKind,
+        params: LogletParams,
+    ) -> Result<Lsn, BuilderError> {
+        if self.inner.tail_index() == segment_index {
+            return Err(BuilderError::TailSegment(segment_index));
+        }
+
+        let (base_lsn, config) = self
+            .inner
+            .chain
+            .iter_mut()
+            .find(|(_, config)| config.index() == segment_index)
+            .ok_or(BuilderError::UnknownSegment(segment_index))?;
+        let retired = std::mem::replace(config, LogletConfig::new(segment_index, provider, params));
+        self.inner.retired_loglets.push(RetiredLoglet {
+            config: retired,
+            retired_at: MillisSinceEpoch::now(),
+        });
+        *self.modified = true;
+        Ok(*base_lsn)
+    }
+
+    /// Removes the retired loglet of the given segment once its records have been released.
+    /// Returns whether there was such a loglet.
+    pub fn remove_retired_loglet(&mut self, segment_index: SegmentIndex) -> bool {
+        let num_retired_loglets = self.inner.retired_loglets.len();
+        self.inner
+            .retired_loglets
+            .retain(|retired| retired.config.index() != segment_index);
+        let removed = self.inner.retired_loglets.len() != num_retired_loglets;
+        *self.modified |= removed;
+        removed
+    }
 }
 
 impl Deref for ChainBuilder<'_> {
@@ -546,4 +595,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_replace_sealed_segment() -> googletest::Result<()> {
+        let log_id = LogId::new(1);
+        let mut builder = LogsBuilder::default();
+        let mut chain = builder.add_log(
+            log_id,
+            Chain::new(ProviderKind::Local, LogletParams::from("test1")),
+        )?;
+        chain.append_segment(
+            Lsn::from(10),
+            ProviderKind::Local,
+            LogletParams::from("test2"),
+        )?;
+
+        let base_lsn = chain.replace_sealed_segment(
+            SegmentIndex(0),
+            ProviderKind::TieredStorage,
+            LogletParams::from("1/0.segment"),
+        )?;
+        assert_eq!(Lsn::OLDEST, base_lsn);
+        assert_eq!(2, chain.num_segments());
+        assert_that!(
+            chain.head(),
+            pat!(Segment {
+                base_lsn: eq(Lsn::OLDEST),
+                tail_lsn: eq(Some(Lsn::from(10))),
+            })
+        );
+        assert_eq!(SegmentIndex(0), chain.head().index());
+        assert_that!(chain.head().config.kind, eq(ProviderKind::TieredStorage));
+        assert_eq!(SegmentIndex(1), chain.tail_index());
+
+        // the replaced loglet is retained until its records have been released
+        assert_eq!(1, chain.retired_loglets().len());
+        let retired = &chain.retired_loglets()[0].config;
+        assert_eq!(SegmentIndex(0), retired.index());
+        assert_that!(retired.kind, eq(ProviderKind::Local));
+        assert_eq!(LogletParams::from("test1"), retired.params);
+        assert!(!chain.remove_retired_loglet(SegmentIndex(1)));
+        assert!(chain.remove_retired_loglet(SegmentIndex(0)));
+        assert!(chain.retired_loglets().is_empty());
+
+        // the tail segment can't be replaced
+        assert_that!(
+            chain.replace_sealed_segment(
+                SegmentIndex(1),
+                ProviderKind::TieredStorage,
+                LogletParams::from("1/1.segment")
+            ),
+            err(pat!(BuilderError::TailSegment(eq(SegmentIndex(1)))))
+        );
+        assert_that!(
+            chain.replace_sealed_segment(
+                SegmentIndex(5),
+                ProviderKind::TieredStorage,
+                LogletParams::from("1/5.segment")
+            ),
+            err(pat!(BuilderError::UnknownSegment(eq(SegmentIndex(5)))))
+        );
+
+        Ok(())
+    }
 }
@@ -19,6 +19,7 @@ use serde_with::serde_as;
 
 use super::builder::LogsBuilder;
 use crate::logs::{LogId, Lsn, SequenceNumber};
+use crate::time::MillisSinceEpoch;
 use crate::{flexbuffers_storage_encode_decode, Version, Versioned};
 
 // Starts with 0 being the oldest loglet in the chain.
@@ -81,6 +82,18 @@ pub struct Chain {
     // flexbuffers only supports string-keyed maps :-( --> so we store it as vector of kv pairs
     #[serde_as(as = "serde_with::Seq<(_, _)>")]
     pub(super) chain: BTreeMap<Lsn, LogletConfig>,
+    /// Loglets of sealed segments which have been replaced by a copy of their records, e.g. when
+    /// offloading them to tiered storage. They still hold the records until these are released.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) retired_loglets: Vec<RetiredLoglet>,
+}
+
+/// A loglet which no longer serves its segment of the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetiredLoglet {
+    pub config: LogletConfig,
+    /// When the loglet has been replaced in the chain
+    pub retired_at: MillisSinceEpoch,
 }
 
 #[derive(Debug, Clone)]
@@ -178,6 +191,9 @@ pub enum ProviderKind {
     /// Replicated loglet implementation. This requires log-server role to run on
     /// enough nodes in the cluster.
     Replicated,
+    /// A read-only loglet backed by an immutable segment file. Sealed segments of other providers
+    /// can be offloaded to this provider, it can't be used for new or writeable segments.
+    TieredStorage,
 }
 
 impl FromStr for ProviderKind {
@@ -189,6 +205,7 @@ impl FromStr for ProviderKind {
             "in-memory" | "in_memory" | "memory" => Ok(Self::InMemory),
             #[cfg(feature = "replicated-loglet")]
             "replicated" => Ok(Self::Replicated),
+            "tiered-storage" | "tiered_storage" | "tiered" => Ok(Self::TieredStorage),
             _ => Err("Unknown provider kind"),
         }
     }
@@ -270,7 +287,10 @@ impl Chain {
             base_lsn,
             LogletConfig::new(SegmentIndex::default(), kind, config),
         );
-        Self { chain }
+        Self {
+            chain,
+            retired_loglets: Vec::default(),
+        }
     }
 
     #[track_caller]
@@ -312,6 +332,11 @@ impl Chain {
         self.chain.len()
     }
 
+    /// Loglets which have been replaced in the chain but whose records haven't been released yet.
+    pub fn retired_loglets(&self) -> &[RetiredLoglet] {
+        &self.retired_loglets
+    }
+
     /// Finds the segment that contains the given Lsn.
     /// Returns `MaybeSegment::Trim` if the Lsn is behind the oldest segment (trimmed).
     pub fn find_segment_for_lsn(&self, lsn: Lsn) -> MaybeSegment<'_> {
@@ -372,6 +397,9 @@ pub fn new_single_node_loglet_params(default_provider: ProviderKind) -> LogletPa
         ProviderKind::Replicated => panic!(
             "replicated-loglet is still in development and cannot be used as default-provider in this version. Pleae use 'local' instead."
         ),
+        ProviderKind::TieredStorage => panic!(
+            "tiered-storage is read-only and cannot be used as default-provider. Please use 'local' instead."
+        ),
     }
 }
 
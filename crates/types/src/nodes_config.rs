@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use enumset::{EnumSet, EnumSetType};
 use serde_with::serde_as;
 
+use crate::locality::NodeLocation;
 use crate::net::AdvertisedAddress;
 use crate::{flexbuffers_storage_encode_decode, GenerationalNodeId, NodeId, PlainNodeId};
 use crate::{Version, Versioned};
@@ -86,6 +87,8 @@ pub struct NodeConfig {
     pub roles: EnumSet<Role>,
     #[serde(default)]
     pub log_server_config: LogServerConfig,
+    #[serde(default)]
+    pub location: NodeLocation,
 }
 
 impl NodeConfig {
@@ -95,6 +98,7 @@ impl NodeConfig {
         address: AdvertisedAddress,
         roles: EnumSet<Role>,
         log_server_config: LogServerConfig,
+        location: NodeLocation,
     ) -> Self {
         Self {
             name,
@@ -102,6 +106,7 @@ impl NodeConfig {
             address,
             roles,
             log_server_config,
+            location,
         }
     }
 
@@ -345,6 +350,7 @@ mod tests {
             address.clone(),
             roles,
             LogServerConfig::default(),
+            NodeLocation::default(),
         );
         config.upsert_node(node.clone());
 
@@ -392,6 +398,7 @@ mod tests {
             address,
             roles,
             LogServerConfig::default(),
+            NodeLocation::default(),
         );
         config.upsert_node(node.clone());
 
@@ -54,7 +54,7 @@ const DEFAULT_JITTER_MULTIPLIER: f32 = 0.3;
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(
     tag = "type",
     rename_all = "kebab-case",
@@ -235,6 +235,22 @@ pub struct RetryIter<'a> {
     last_retry: Option<Duration>,
 }
 
+impl RetryIter<'static> {
+    /// Switches to the given policy, continuing from the attempts made so far.
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        if *self.policy == policy {
+            return;
+        }
+
+        let attempts = self.attempts;
+        *self = policy.into_iter();
+        // Replay the previous attempts to continue the backoff of the new policy
+        while self.attempts < attempts {
+            self.next();
+        }
+    }
+}
+
 impl<'a> Iterator for RetryIter<'a> {
     type Item = Duration;
 
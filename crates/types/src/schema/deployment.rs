@@ -11,6 +11,7 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
 
 use bytestring::ByteString;
@@ -39,11 +40,21 @@ pub struct DeliveryOptions {
     )]
     #[cfg_attr(feature = "schemars", schemars(with = "HashMap<String, String>"))]
     pub additional_headers: HashMap<HeaderName, HeaderValue>,
+    /// Maximum number of invocations a worker node executes concurrently against the
+    /// deployment, across all the partitions it leads.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub concurrency_limit: Option<NonZeroUsize>,
 }
 
 impl DeliveryOptions {
-    pub fn new(additional_headers: HashMap<HeaderName, HeaderValue>) -> Self {
-        Self { additional_headers }
+    pub fn new(
+        additional_headers: HashMap<HeaderName, HeaderValue>,
+        concurrency_limit: Option<NonZeroUsize>,
+    ) -> Self {
+        Self {
+            additional_headers,
+            concurrency_limit,
+        }
     }
 }
 
@@ -188,7 +188,7 @@ pub enum InputValidationRule {
     JsonValue {
         // Can use wildcards
         content_type: InputContentType,
-        // Right now we don't use this schema for anything except printing,
+        // The ingress compiles this schema when validating the request bodies,
         // so no need to use a more specialized type (we validate the schema is valid inside the schema registry updater)
         schema: serde_json::Value,
     },
@@ -240,7 +240,7 @@ impl InputValidationRule {
                     return Err(InputValidationError::EmptyValue);
                 }
 
-                // TODO add additional json validation.
+                // The body is validated against the schema by the ingress, if enabled for the service.
             }
         }
         Ok(())
@@ -0,0 +1,199 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::Schema;
+use crate::invocation::InvocationTarget;
+use crate::message::MessageIndex;
+
+/// Handlers whose outputs are published by a Kafka sink.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KafkaSinkSource {
+    /// All the handlers of the service, or only the given handler.
+    Service {
+        name: String,
+        handler: Option<String>,
+    },
+}
+
+impl KafkaSinkSource {
+    pub fn matches(&self, invocation_target: &InvocationTarget) -> bool {
+        match self {
+            KafkaSinkSource::Service { name, handler } => {
+                invocation_target.service_name() == name
+                    && handler
+                        .as_ref()
+                        .map_or(true, |handler| invocation_target.handler_name() == handler)
+            }
+        }
+    }
+}
+
+impl fmt::Display for KafkaSinkSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KafkaSinkSource::Service {
+                name,
+                handler: Some(handler),
+            } => write!(f, "service://{}/{}", name, handler),
+            KafkaSinkSource::Service {
+                name,
+                handler: None,
+            } => write!(f, "service://{}", name),
+        }
+    }
+}
+
+/// Publishes the outputs of the completed invocations of a service to a Kafka topic.
+///
+/// Handlers publish events by sending a one-way call to a handler whose outputs are published.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KafkaSink {
+    id: String,
+    source: KafkaSinkSource,
+    cluster: String,
+    topic: String,
+    options: HashMap<String, String>,
+}
+
+impl KafkaSink {
+    pub fn new(
+        id: String,
+        source: KafkaSinkSource,
+        cluster: String,
+        topic: String,
+        options: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            id,
+            source,
+            cluster,
+            topic,
+            options,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn source(&self) -> &KafkaSinkSource {
+        &self.source
+    }
+
+    pub fn cluster(&self) -> &str {
+        &self.cluster
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Kafka producer options, in the same form of rdkafka.
+    pub fn options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+
+    /// Destination of the sink, in the form `kafka://<cluster>/<topic>`.
+    pub fn destination(&self) -> String {
+        format!("kafka://{}/{}", self.cluster, self.topic)
+    }
+}
+
+/// Message to register or remove a Kafka sink on a partition. The partition processors don't have
+/// access to the schemas, hence every partition is told which handlers are published.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkRequest {
+    pub sink_id: String,
+    pub flavor: KafkaSinkFlavor,
+}
+
+impl KafkaSinkRequest {
+    pub fn register(sink: &KafkaSink) -> Self {
+        Self {
+            sink_id: sink.id.clone(),
+            flavor: KafkaSinkFlavor::Register(sink.source.clone()),
+        }
+    }
+
+    pub fn remove(sink_id: String) -> Self {
+        Self {
+            sink_id,
+            flavor: KafkaSinkFlavor::Remove,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkFlavor {
+    Register(KafkaSinkSource),
+    Remove,
+}
+
+/// Message to truncate the records of a Kafka sink up to, and including, the given index, once
+/// the leader of the partition has produced them.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkTruncation {
+    pub sink_id: String,
+    pub index: MessageIndex,
+}
+
+pub trait KafkaSinkResolver {
+    fn get_kafka_sink(&self, id: &str) -> Option<KafkaSink>;
+
+    fn list_kafka_sinks(&self) -> Vec<KafkaSink>;
+}
+
+impl KafkaSinkResolver for Schema {
+    fn get_kafka_sink(&self, id: &str) -> Option<KafkaSink> {
+        self.kafka_sinks.get(id).cloned()
+    }
+
+    fn list_kafka_sinks(&self) -> Vec<KafkaSink> {
+        self.kafka_sinks.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_matches_service_and_handler() {
+        let target = InvocationTarget::service("Orders", "checkout");
+
+        assert!(KafkaSinkSource::Service {
+            name: "Orders".to_owned(),
+            handler: None
+        }
+        .matches(&target));
+        assert!(KafkaSinkSource::Service {
+            name: "Orders".to_owned(),
+            handler: Some("checkout".to_owned())
+        }
+        .matches(&target));
+        assert!(!KafkaSinkSource::Service {
+            name: "Orders".to_owned(),
+            handler: Some("cancel".to_owned())
+        }
+        .matches(&target));
+        assert!(!KafkaSinkSource::Service {
+            name: "Payments".to_owned(),
+            handler: None
+        }
+        .matches(&target));
+    }
+}
@@ -10,6 +10,8 @@
 
 pub mod deployment;
 pub mod invocation_target;
+pub mod kafka_sinks;
+pub mod schedules;
 pub mod service;
 pub mod subscriptions;
 
@@ -19,6 +21,8 @@ use serde_with::serde_as;
 
 use self::deployment::DeploymentSchemas;
 use self::deployment::DeploymentType;
+use self::kafka_sinks::KafkaSink;
+use self::schedules::Schedule;
 use self::service::ServiceSchemas;
 use self::subscriptions::Subscription;
 use crate::identifiers::{DeploymentId, SubscriptionId};
@@ -37,6 +41,10 @@ pub struct Schema {
     // flexbuffers only supports string-keyed maps :-( --> so we store it as vector of kv pairs
     #[serde_as(as = "serde_with::Seq<(_, _)>")]
     pub subscriptions: HashMap<SubscriptionId, Subscription>,
+    #[serde(default)]
+    pub schedules: HashMap<String, Schedule>,
+    #[serde(default)]
+    pub kafka_sinks: HashMap<String, KafkaSink>,
 }
 
 impl Default for Schema {
@@ -46,6 +54,8 @@ impl Default for Schema {
             services: HashMap::default(),
             deployments: HashMap::default(),
             subscriptions: HashMap::default(),
+            schedules: HashMap::default(),
+            kafka_sinks: HashMap::default(),
         }
     }
 }
@@ -0,0 +1,280 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use bytes::Bytes;
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use super::Schema;
+use crate::identifiers::partitioner::HashPartitioner;
+use crate::identifiers::{InvocationId, InvocationUuid, PartitionKey, WithPartitionKey};
+use crate::invocation::InvocationTarget;
+use crate::time::MillisSinceEpoch;
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidScheduleError {
+    #[error("invalid cron expression '{0}': {1}")]
+    Cron(String, cron::error::Error),
+    #[error("unknown timezone '{0}', expected an IANA timezone name such as 'Europe/Berlin'")]
+    Timezone(String),
+}
+
+/// Invocation of a handler which is repeated according to a cron expression.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    id: String,
+    cron: String,
+    timezone: String,
+    target: InvocationTarget,
+    payload: Bytes,
+}
+
+impl Schedule {
+    pub fn new(
+        id: String,
+        cron: String,
+        timezone: String,
+        target: InvocationTarget,
+        payload: Bytes,
+    ) -> Result<Self, InvalidScheduleError> {
+        parse_cron(&cron).map_err(|e| InvalidScheduleError::Cron(cron.clone(), e))?;
+        Tz::from_str(&timezone).map_err(|_| InvalidScheduleError::Timezone(timezone.clone()))?;
+
+        Ok(Self {
+            id,
+            cron,
+            timezone,
+            target,
+            payload,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn cron(&self) -> &str {
+        &self.cron
+    }
+
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    pub fn target(&self) -> &InvocationTarget {
+        &self.target
+    }
+
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    /// Returns the first occurrence strictly after the given time, or `None` if the cron
+    /// expression has no further occurrences.
+    pub fn next_fire_time(&self, after: MillisSinceEpoch) -> Option<MillisSinceEpoch> {
+        // Both have been validated when creating the schedule
+        let cron = parse_cron(&self.cron).ok()?;
+        let timezone = Tz::from_str(&self.timezone).ok()?;
+
+        let after = timezone
+            .timestamp_millis_opt(i64::try_from(after.as_u64()).ok()?)
+            .single()?;
+        cron.after(&after)
+            .next()
+            .and_then(|next| u64::try_from(next.timestamp_millis()).ok())
+            .map(MillisSinceEpoch::new)
+    }
+
+    /// Id of the invocation fired at the given time. Ids are derived from the schedule, so that
+    /// every replica of the partition generates the same id for the same occurrence.
+    pub fn invocation_id(&self, fire_time: MillisSinceEpoch) -> InvocationId {
+        InvocationId::from_parts(
+            self.partition_key(),
+            InvocationUuid::generate(
+                &self.target,
+                Some(&format!("{}/{}", self.id, fire_time.as_u64())),
+            ),
+        )
+    }
+}
+
+/// Schedules are owned by the partition of their target, so that the partition processor can
+/// check whether the previous occurrence is still running. Schedules targeting services without
+/// a key are spread across partitions by their id.
+impl WithPartitionKey for Schedule {
+    fn partition_key(&self) -> PartitionKey {
+        match self.target.key() {
+            Some(key) => HashPartitioner::compute_partition_key(&**key),
+            None => HashPartitioner::compute_partition_key(&self.id),
+        }
+    }
+}
+
+/// Accepts both the standard five fields expressions and the expressions with a leading
+/// seconds field.
+fn parse_cron(expression: &str) -> Result<cron::Schedule, cron::error::Error> {
+    if expression.split_whitespace().count() == 5 {
+        cron::Schedule::from_str(&format!("0 {expression}"))
+    } else {
+        cron::Schedule::from_str(expression)
+    }
+}
+
+/// Message to register or remove a schedule on the partition owning it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleRequest {
+    pub schedule_id: String,
+    pub partition_key: PartitionKey,
+    pub flavor: ScheduleFlavor,
+}
+
+impl ScheduleRequest {
+    pub fn create(schedule: Schedule, first_fire_time: MillisSinceEpoch) -> Self {
+        Self {
+            schedule_id: schedule.id.clone(),
+            partition_key: schedule.partition_key(),
+            flavor: ScheduleFlavor::Create {
+                schedule,
+                first_fire_time,
+            },
+        }
+    }
+
+    pub fn delete(schedule: &Schedule) -> Self {
+        Self {
+            schedule_id: schedule.id.clone(),
+            partition_key: schedule.partition_key(),
+            flavor: ScheduleFlavor::Delete,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ScheduleFlavor {
+    /// Register the schedule, replacing any schedule with the same id. The first fire time is
+    /// computed by the sender, as the partition processors must not depend on their clock.
+    Create {
+        schedule: Schedule,
+        first_fire_time: MillisSinceEpoch,
+    },
+    Delete,
+}
+
+pub trait ScheduleResolver {
+    fn get_schedule(&self, id: &str) -> Option<Schedule>;
+
+    fn list_schedules(&self) -> Vec<Schedule>;
+}
+
+impl ScheduleResolver for Schema {
+    fn get_schedule(&self, id: &str) -> Option<Schedule> {
+        self.schedules.get(id).cloned()
+    }
+
+    fn list_schedules(&self) -> Vec<Schedule> {
+        self.schedules.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::invocation::VirtualObjectHandlerType;
+    use restate_test_util::assert_eq;
+
+    fn schedule(cron: &str, timezone: &str) -> Result<Schedule, InvalidScheduleError> {
+        Schedule::new(
+            "nightly-billing".to_owned(),
+            cron.to_owned(),
+            timezone.to_owned(),
+            InvocationTarget::service("Billing", "run"),
+            Bytes::new(),
+        )
+    }
+
+    fn millis(rfc3339: &str) -> MillisSinceEpoch {
+        MillisSinceEpoch::new(
+            chrono::DateTime::parse_from_rfc3339(rfc3339)
+                .unwrap()
+                .timestamp_millis() as u64,
+        )
+    }
+
+    #[test]
+    fn next_fire_time() {
+        let schedule = schedule("0 2 * * *", DEFAULT_TIMEZONE).unwrap();
+
+        assert_eq!(
+            schedule.next_fire_time(millis("2024-10-01T01:00:00Z")),
+            Some(millis("2024-10-01T02:00:00Z"))
+        );
+        // Occurrences are strictly after the given time
+        assert_eq!(
+            schedule.next_fire_time(millis("2024-10-01T02:00:00Z")),
+            Some(millis("2024-10-02T02:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn next_fire_time_with_seconds_and_timezone() {
+        let schedule = schedule("30 0 2 * * *", "Europe/Berlin").unwrap();
+
+        assert_eq!(
+            schedule.next_fire_time(millis("2024-10-01T00:00:00Z")),
+            Some(millis("2024-10-01T02:00:30+02:00"))
+        );
+    }
+
+    #[test]
+    fn invalid_schedule() {
+        assert!(matches!(
+            schedule("every day", DEFAULT_TIMEZONE),
+            Err(InvalidScheduleError::Cron(..))
+        ));
+        assert!(matches!(
+            schedule("0 2 * * *", "Mars/Olympus_Mons"),
+            Err(InvalidScheduleError::Timezone(_))
+        ));
+    }
+
+    #[test]
+    fn keyed_targets_are_owned_by_the_object_partition() {
+        let target = InvocationTarget::virtual_object(
+            "Counter",
+            "my-key",
+            "reset",
+            VirtualObjectHandlerType::Exclusive,
+        );
+        let schedule = Schedule::new(
+            "reset-counter".to_owned(),
+            "0 * * * *".to_owned(),
+            DEFAULT_TIMEZONE.to_owned(),
+            target.clone(),
+            Bytes::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            schedule.partition_key(),
+            InvocationId::generate(&target, None).partition_key()
+        );
+        let fire_time = millis("2024-10-01T01:00:00Z");
+        assert_eq!(
+            schedule.invocation_id(fire_time),
+            schedule.invocation_id(fire_time)
+        );
+    }
+}
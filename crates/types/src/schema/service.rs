@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::HashMap;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::time::Duration;
 
 use serde::Deserialize;
@@ -21,6 +22,7 @@ use crate::identifiers::{DeploymentId, ServiceRevision};
 use crate::invocation::{
     InvocationTargetType, ServiceType, VirtualObjectHandlerType, WorkflowHandlerType,
 };
+use crate::retries::RetryPolicy;
 
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +112,53 @@ pub struct ServiceMetadata {
     )]
     #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
     pub abort_timeout: Option<humantime::Duration>,
+
+    /// # Paused
+    ///
+    /// If true, new invocations of this service are held back and the inboxes of its
+    /// virtual objects are not processed until the service is resumed.
+    /// Invocations which are already running are not affected.
+    #[serde(default)]
+    pub paused: bool,
+
+    /// # Retry policy
+    ///
+    /// Retry policy applied to the invocations of this service.
+    ///
+    /// This overrides the default retry policy set in invoker options.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+
+    /// # Ingress authorization
+    ///
+    /// Principals allowed to invoke this service through the ingress.
+    /// If unset, every authenticated principal can invoke the service.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ingress_authorization: Option<IngressAuthorization>,
+
+    /// # Rate limit
+    ///
+    /// Rate limit applied by the ingress to the requests invoking this service. The limit is
+    /// shared by all the handlers of the service without a rate limit of their own.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rate_limit: Option<RateLimit>,
+
+    /// # Concurrency limit
+    ///
+    /// Maximum number of invocations of this service executed concurrently on a worker node.
+    /// Invocations over the limit stay queued until one of the running invocations completes.
+    ///
+    /// All the partitions led by a node share the limit, but nodes don't coordinate: a cluster
+    /// with several worker nodes can run up to the limit times the number of nodes.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub concurrency_limit: Option<NonZeroUsize>,
+
+    /// # JSON Schema validation
+    ///
+    /// If true, the ingress validates the request bodies against the input JSON Schema of the
+    /// invoked handler, and rejects the requests which don't match it.
+    #[serde(default)]
+    pub json_schema_validation: bool,
 }
 
 // This type is used only for exposing the handler metadata, and not internally. See [ServiceAndHandlerType].
@@ -165,6 +214,135 @@ pub struct HandlerMetadata {
     /// JSON Schema of the handler output
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_json_schema: Option<serde_json::Value>,
+
+    /// # Retry policy
+    ///
+    /// Retry policy applied to the invocations of this handler.
+    ///
+    /// This overrides the retry policy of the service.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+
+    /// # Ingress authorization
+    ///
+    /// Principals allowed to invoke this handler through the ingress.
+    ///
+    /// This overrides the ingress authorization of the service.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ingress_authorization: Option<IngressAuthorization>,
+
+    /// # Rate limit
+    ///
+    /// Rate limit applied by the ingress to the requests invoking this handler.
+    ///
+    /// This overrides the rate limit of the service.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// # Invocation retry policy
+///
+/// Retry policy of the invocations of a service or handler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct InvocationRetryPolicy {
+    /// # Policy
+    ///
+    /// Backoff and max attempts of the retries.
+    pub policy: RetryPolicy,
+
+    /// # On max attempts
+    ///
+    /// What to do with the invocation once the max attempts are exhausted.
+    #[serde(default)]
+    pub on_max_attempts: OnMaxAttempts,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum OnMaxAttempts {
+    /// # Kill
+    ///
+    /// Fail the invocation with the last error.
+    #[default]
+    Kill,
+    /// # Pause
+    ///
+    /// Pause the invocation, so that it can be resumed later on.
+    Pause,
+    /// # Dead letter
+    ///
+    /// Fail the invocation with the last error, and record its input in the dead letter table,
+    /// so that it can be replayed or discarded later on.
+    DeadLetter,
+}
+
+/// # Ingress authorization
+///
+/// Authorization rule applied by the ingress to the requests invoking a service or handler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressAuthorization {
+    /// # Allowed principals
+    ///
+    /// Principals, as established by the ingress authentication, which are allowed to invoke
+    /// the service or handler. An empty list denies every request.
+    #[serde(default)]
+    pub allowed_principals: Vec<String>,
+}
+
+impl IngressAuthorization {
+    pub fn is_allowed(&self, principal: Option<&str>) -> bool {
+        principal.is_some_and(|principal| self.allowed_principals.iter().any(|p| p == principal))
+    }
+}
+
+/// # Rate limit
+///
+/// Token bucket rate limit applied by the ingress to the requests invoking a service or handler.
+/// Requests exceeding the limit are rejected with `429 Too Many Requests`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimit {
+    /// # Requests per second
+    ///
+    /// Number of requests per second admitted on average.
+    pub requests_per_second: NonZeroU32,
+
+    /// # Burst
+    ///
+    /// Maximum number of requests admitted at once. Defaults to the requests per second.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub burst: Option<NonZeroU32>,
+
+    /// # Per client
+    ///
+    /// If true, the limit applies separately to each client, identified by the principal it
+    /// authenticated as with the ingress. Unauthenticated requests are identified by the value of
+    /// the header configured in `ingress.rate-limit-client-header`, and share a single limit
+    /// without it.
+    #[serde(default)]
+    pub per_client: bool,
+}
+
+impl RateLimit {
+    pub fn burst(&self) -> NonZeroU32 {
+        self.burst.unwrap_or(self.requests_per_second)
+    }
+}
+
+/// Rate limit applying to the requests of a handler, see
+/// [ServiceMetadataResolver::resolve_rate_limit].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedRateLimit {
+    /// Rate limit configured on the handler, applying to the requests of this handler only.
+    Handler(RateLimit),
+    /// Rate limit configured on the service, shared by the handlers without a rate limit of
+    /// their own.
+    Service(RateLimit),
 }
 
 /// This API will return services registered by the user.
@@ -174,11 +352,103 @@ pub trait ServiceMetadataResolver {
     fn resolve_latest_service_type(&self, service_name: impl AsRef<str>) -> Option<ServiceType>;
 
     fn list_services(&self) -> Vec<ServiceMetadata>;
+
+    /// Returns the retry policy of the given handler, falling back to the retry policy of its
+    /// service. Returns `None` if neither has been configured.
+    fn resolve_invocation_retry_policy(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<InvocationRetryPolicy> {
+        let service = self.resolve_latest_service(service_name)?;
+        service
+            .handlers
+            .into_iter()
+            .find(|h| h.name == handler_name.as_ref())
+            .and_then(|h| h.retry_policy)
+            .or(service.retry_policy)
+    }
+
+    /// Returns the ingress authorization of the given handler, falling back to the ingress
+    /// authorization of its service. Returns `None` if neither has been configured.
+    fn resolve_ingress_authorization(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<IngressAuthorization> {
+        let service = self.resolve_latest_service(service_name)?;
+        service
+            .handlers
+            .into_iter()
+            .find(|h| h.name == handler_name.as_ref())
+            .and_then(|h| h.ingress_authorization)
+            .or(service.ingress_authorization)
+    }
+
+    /// Returns the rate limit of the given handler, falling back to the rate limit of its
+    /// service. Returns `None` if neither has been configured.
+    fn resolve_rate_limit(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<ResolvedRateLimit> {
+        let service = self.resolve_latest_service(service_name)?;
+        service
+            .handlers
+            .into_iter()
+            .find(|h| h.name == handler_name.as_ref())
+            .and_then(|h| h.rate_limit)
+            .map(ResolvedRateLimit::Handler)
+            .or(service.rate_limit.map(ResolvedRateLimit::Service))
+    }
+
+    /// Returns the maximum number of concurrent invocations of the given service, if any.
+    fn resolve_concurrency_limit(&self, service_name: impl AsRef<str>) -> Option<NonZeroUsize> {
+        self.resolve_latest_service(service_name)?.concurrency_limit
+    }
+
+    /// Returns the input JSON Schema of the given handler if the ingress should validate the
+    /// request bodies against it.
+    fn resolve_input_json_schema(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<InputJsonSchema> {
+        let service = self.resolve_latest_service(service_name)?;
+        if !service.json_schema_validation {
+            return None;
+        }
+        let schema = service
+            .handlers
+            .into_iter()
+            .find(|h| h.name == handler_name.as_ref())?
+            .input_json_schema?;
+        Some(InputJsonSchema {
+            deployment_id: service.deployment_id,
+            revision: service.revision,
+            schema,
+        })
+    }
+}
+
+/// Input JSON Schema of a handler. The schema can only change with a new revision of the
+/// service, hence the deployment and revision of the service identify it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputJsonSchema {
+    pub deployment_id: DeploymentId,
+    pub revision: ServiceRevision,
+    pub schema: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HandlerSchemas {
     pub target_meta: InvocationTargetMetadata,
+    #[serde(default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+    #[serde(default)]
+    pub ingress_authorization: Option<IngressAuthorization>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -191,6 +461,18 @@ pub struct ServiceSchemas {
     pub workflow_completion_retention: Option<Duration>,
     pub inactivity_timeout: Option<Duration>,
     pub abort_timeout: Option<Duration>,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub retry_policy: Option<InvocationRetryPolicy>,
+    #[serde(default)]
+    pub ingress_authorization: Option<IngressAuthorization>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    #[serde(default)]
+    pub concurrency_limit: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub json_schema_validation: bool,
 }
 
 impl ServiceSchemas {
@@ -207,6 +489,9 @@ impl ServiceSchemas {
                     output_description: h_schemas.target_meta.output_rules.to_string(),
                     input_json_schema: h_schemas.target_meta.input_rules.json_schema(),
                     output_json_schema: h_schemas.target_meta.output_rules.json_schema(),
+                    retry_policy: h_schemas.retry_policy.clone(),
+                    ingress_authorization: h_schemas.ingress_authorization.clone(),
+                    rate_limit: h_schemas.rate_limit.clone(),
                 })
                 .collect(),
             ty: self.ty,
@@ -217,6 +502,12 @@ impl ServiceSchemas {
             workflow_completion_retention: self.workflow_completion_retention.map(Into::into),
             inactivity_timeout: self.inactivity_timeout.map(Into::into),
             abort_timeout: self.abort_timeout.map(Into::into),
+            paused: self.paused,
+            retry_policy: self.retry_policy.clone(),
+            ingress_authorization: self.ingress_authorization.clone(),
+            rate_limit: self.rate_limit.clone(),
+            concurrency_limit: self.concurrency_limit,
+            json_schema_validation: self.json_schema_validation,
         }
     }
 }
@@ -247,6 +538,87 @@ impl ServiceMetadataResolver for Schema {
             })
             .collect()
     }
+
+    fn resolve_invocation_retry_policy(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<InvocationRetryPolicy> {
+        self.use_service_schema(service_name, |service_schemas| {
+            service_schemas
+                .handlers
+                .get(handler_name.as_ref())
+                .and_then(|h| h.retry_policy.clone())
+                .or_else(|| service_schemas.retry_policy.clone())
+        })
+        .flatten()
+    }
+
+    fn resolve_ingress_authorization(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<IngressAuthorization> {
+        self.use_service_schema(service_name, |service_schemas| {
+            service_schemas
+                .handlers
+                .get(handler_name.as_ref())
+                .and_then(|h| h.ingress_authorization.clone())
+                .or_else(|| service_schemas.ingress_authorization.clone())
+        })
+        .flatten()
+    }
+
+    fn resolve_rate_limit(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<ResolvedRateLimit> {
+        self.use_service_schema(service_name, |service_schemas| {
+            service_schemas
+                .handlers
+                .get(handler_name.as_ref())
+                .and_then(|h| h.rate_limit.clone())
+                .map(ResolvedRateLimit::Handler)
+                .or_else(|| {
+                    service_schemas
+                        .rate_limit
+                        .clone()
+                        .map(ResolvedRateLimit::Service)
+                })
+        })
+        .flatten()
+    }
+
+    fn resolve_concurrency_limit(&self, service_name: impl AsRef<str>) -> Option<NonZeroUsize> {
+        self.use_service_schema(service_name, |service_schemas| {
+            service_schemas.concurrency_limit
+        })
+        .flatten()
+    }
+
+    fn resolve_input_json_schema(
+        &self,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<InputJsonSchema> {
+        self.use_service_schema(service_name, |service_schemas| {
+            if !service_schemas.json_schema_validation {
+                return None;
+            }
+            Some(InputJsonSchema {
+                deployment_id: service_schemas.location.latest_deployment,
+                revision: service_schemas.revision,
+                schema: service_schemas
+                    .handlers
+                    .get(handler_name.as_ref())?
+                    .target_meta
+                    .input_rules
+                    .json_schema()?,
+            })
+        })
+        .flatten()
+    }
 }
 
 #[cfg(feature = "test-util")]
@@ -299,6 +671,9 @@ pub mod test_util {
                         output_description: "any".to_string(),
                         input_json_schema: None,
                         output_json_schema: None,
+                        retry_policy: None,
+                        ingress_authorization: None,
+                        rate_limit: None,
                     })
                     .collect(),
                 ty: ServiceType::Service,
@@ -309,6 +684,12 @@ pub mod test_util {
                 workflow_completion_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                paused: false,
+                retry_policy: None,
+                ingress_authorization: None,
+                rate_limit: None,
+                concurrency_limit: None,
+                json_schema_validation: false,
             }
         }
 
@@ -327,6 +708,9 @@ pub mod test_util {
                         output_description: "any".to_string(),
                         input_json_schema: None,
                         output_json_schema: None,
+                        retry_policy: None,
+                        ingress_authorization: None,
+                        rate_limit: None,
                     })
                     .collect(),
                 ty: ServiceType::VirtualObject,
@@ -337,6 +721,12 @@ pub mod test_util {
                 workflow_completion_retention: None,
                 inactivity_timeout: None,
                 abort_timeout: None,
+                paused: false,
+                retry_policy: None,
+                ingress_authorization: None,
+                rate_limit: None,
+                concurrency_limit: None,
+                json_schema_validation: false,
             }
         }
     }
@@ -20,6 +20,42 @@ use crate::config::IngressOptions;
 use crate::errors::GenericError;
 use crate::identifiers::SubscriptionId;
 
+/// Subscription options starting with this prefix configure how Restate processes the
+/// subscription, and are not passed to the Kafka client.
+pub const RESTATE_OPTIONS_PREFIX: &str = "restate.";
+/// Comma separated list of the Kafka record headers to forward as invocation headers. Entries
+/// ending with `*` match all the headers with the given prefix. If unset, all headers are
+/// forwarded.
+pub const HEADERS_ALLOW_OPTION: &str = "restate.headers.allow";
+/// Comma separated list of the Kafka record headers which must not be forwarded as invocation
+/// headers. Takes precedence over the allow list.
+pub const HEADERS_DENY_OPTION: &str = "restate.headers.deny";
+/// Format of the record values, which are converted to JSON before being dispatched. One of
+/// [`VALUE_FORMATS`]. If unset, values are dispatched unchanged.
+pub const VALUE_FORMAT_OPTION: &str = "restate.value.format";
+pub const VALUE_FORMATS: &[&str] = &["avro", "protobuf", "json-schema"];
+/// Base URL of the Confluent compatible schema registry resolving the schema ids of the values.
+pub const SCHEMA_REGISTRY_URL_OPTION: &str = "restate.schema.registry.url";
+/// Directory containing the schemas of the values, named after their schema id, e.g.
+/// `42.avsc`, `42.proto` or `42.json`. Used when no schema registry is configured.
+pub const SCHEMA_DIRECTORY_OPTION: &str = "restate.schema.directory";
+/// Fully qualified name of the Protobuf message of the values. If unset, the message is
+/// resolved from the message indexes of the record framing.
+pub const PROTOBUF_MESSAGE_OPTION: &str = "restate.value.protobuf.message";
+/// What to do with records whose value cannot be decoded. One of [`ON_DECODE_ERROR_POLICIES`],
+/// defaults to `stop`, which stops the consumer at the failing record. Records whose schema
+/// cannot be resolved, for example because the schema registry is unreachable, always stop the
+/// consumer.
+pub const ON_DECODE_ERROR_OPTION: &str = "restate.on-decode-error";
+pub const ON_DECODE_ERROR_POLICIES: &[&str] = &["stop", "skip"];
+/// Filter expression selecting the records to dispatch, see [`RecordFilter`]. The records which
+/// don't match are acknowledged and skipped.
+pub const FILTER_OPTION: &str = "restate.filter";
+/// Selector of the virtual object or workflow key, see [`RecordSelector`]. If unset, the key is
+/// the Kafka record key. Records for which the selector selects no key are handled according to
+/// [`ON_DECODE_ERROR_OPTION`].
+pub const KEY_OPTION: &str = "restate.key";
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Source {
@@ -84,6 +120,13 @@ pub struct Subscription {
     source: Source,
     sink: Sink,
     metadata: HashMap<String, String>,
+    /// Paused subscriptions are not consumed, until they are resumed.
+    #[serde(default)]
+    paused: bool,
+    /// Incremented every time the consumer offsets are reset. It's part of the deduplication id of
+    /// the Kafka records, so that the records replayed after a reset are not deduplicated.
+    #[serde(default)]
+    offsets_generation: u32,
 }
 
 impl Subscription {
@@ -98,6 +141,8 @@ impl Subscription {
             source,
             sink,
             metadata,
+            paused: false,
+            offsets_generation: 0,
         }
     }
 
@@ -120,6 +165,22 @@ impl Subscription {
     pub fn metadata_mut(&mut self) -> &mut HashMap<String, String> {
         &mut self.metadata
     }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn offsets_generation(&self) -> u32 {
+        self.offsets_generation
+    }
+
+    pub fn increment_offsets_generation(&mut self) {
+        self.offsets_generation += 1;
+    }
 }
 
 pub enum ListSubscriptionFilter {
@@ -136,6 +197,213 @@ impl ListSubscriptionFilter {
     }
 }
 
+/// Kafka record, as seen by the filter and key expressions.
+pub trait SubscriptionRecord {
+    fn key(&self) -> Option<&[u8]>;
+
+    /// Value of the header, header names are case-insensitive.
+    fn header(&self, name: &str) -> Option<&[u8]>;
+
+    /// Value of the record parsed as JSON, or `None` if it's not valid JSON.
+    fn json_value(&self) -> Option<&serde_json::Value>;
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid expression '{0}'")]
+pub struct InvalidExpressionError(String);
+
+/// Selects a part of a Kafka record, in one of the forms:
+///
+/// * `key`: the record key.
+/// * `header.<name>`: the value of the header, e.g. `header.tenant-id`.
+/// * `value.<path>`: the field of the JSON value at the dot separated path, e.g.
+///   `value.customer.id`. Array elements are selected by their index, e.g. `value.items.0.sku`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordSelector {
+    Key,
+    Header(String),
+    Value(Vec<String>),
+}
+
+impl RecordSelector {
+    pub fn needs_json_value(&self) -> bool {
+        matches!(self, RecordSelector::Value(_))
+    }
+
+    /// Selects the part of the record, returning `None` if missing. Keys and headers are
+    /// selected as JSON strings, and must be valid UTF-8.
+    pub fn select(&self, record: &impl SubscriptionRecord) -> Option<serde_json::Value> {
+        let utf8_string = |bytes: &[u8]| {
+            std::str::from_utf8(bytes)
+                .ok()
+                .map(|s| serde_json::Value::String(s.to_owned()))
+        };
+        match self {
+            RecordSelector::Key => record.key().and_then(utf8_string),
+            RecordSelector::Header(name) => record.header(name).and_then(utf8_string),
+            RecordSelector::Value(path) => {
+                let mut value = record.json_value()?;
+                for field in path {
+                    value = match value {
+                        serde_json::Value::Object(object) => object.get(field)?,
+                        serde_json::Value::Array(array) => {
+                            array.get(field.parse::<usize>().ok()?)?
+                        }
+                        _ => return None,
+                    };
+                }
+                Some(value.clone())
+            }
+        }
+    }
+
+    /// Selects the key of the virtual object or workflow. Strings are used as they are, while
+    /// numbers and booleans are converted to their JSON representation.
+    pub fn select_key(&self, record: &impl SubscriptionRecord) -> Option<String> {
+        match self.select(record)? {
+            serde_json::Value::String(key) => Some(key),
+            key @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_)) => {
+                Some(key.to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for RecordSelector {
+    type Err = InvalidExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || InvalidExpressionError(s.to_owned());
+        if s == "key" {
+            return Ok(RecordSelector::Key);
+        }
+        if let Some(name) = s.strip_prefix("header.") {
+            if name.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(RecordSelector::Header(name.to_owned()));
+        }
+        if s == "value" {
+            return Ok(RecordSelector::Value(vec![]));
+        }
+        if let Some(path) = s.strip_prefix("value.") {
+            let path: Vec<String> = path.split('.').map(str::to_owned).collect();
+            if path.iter().any(String::is_empty) {
+                return Err(invalid());
+            }
+            return Ok(RecordSelector::Value(path));
+        }
+        Err(invalid())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterClause {
+    Exists(RecordSelector),
+    Equals(RecordSelector, serde_json::Value),
+    NotEquals(RecordSelector, serde_json::Value),
+}
+
+/// Conjunction of clauses joined by `&&`, where every clause is one of:
+///
+/// * `<selector>`: the selected part of the record exists.
+/// * `<selector> == <literal>`: the selected part of the record is equal to the JSON literal.
+/// * `<selector> != <literal>`: the selected part of the record is missing, or not equal to the
+///   JSON literal.
+///
+/// For example: `header.event-type == "OrderCreated" && value.region != "eu"`. See
+/// [`RecordSelector`] for the selectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordFilter {
+    clauses: Vec<FilterClause>,
+}
+
+impl RecordFilter {
+    pub fn needs_json_value(&self) -> bool {
+        self.clauses.iter().any(|clause| match clause {
+            FilterClause::Exists(selector)
+            | FilterClause::Equals(selector, _)
+            | FilterClause::NotEquals(selector, _) => selector.needs_json_value(),
+        })
+    }
+
+    pub fn matches(&self, record: &impl SubscriptionRecord) -> bool {
+        self.clauses.iter().all(|clause| match clause {
+            FilterClause::Exists(selector) => selector.select(record).is_some(),
+            FilterClause::Equals(selector, literal) => {
+                selector.select(record).as_ref() == Some(literal)
+            }
+            FilterClause::NotEquals(selector, literal) => {
+                selector.select(record).as_ref() != Some(literal)
+            }
+        })
+    }
+}
+
+impl std::str::FromStr for RecordFilter {
+    type Err = InvalidExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_literal = |literal: &str| {
+            serde_json::from_str::<serde_json::Value>(literal.trim())
+                .map_err(|_| InvalidExpressionError(literal.trim().to_owned()))
+        };
+
+        let clauses = split_clauses(s)
+            .into_iter()
+            .map(|clause| {
+                let clause = clause.trim();
+                // The first operator separates the selector from the literal
+                let operator = [clause.find("=="), clause.find("!=")]
+                    .into_iter()
+                    .flatten()
+                    .min();
+                match operator {
+                    Some(i) if clause[i..].starts_with("==") => Ok(FilterClause::Equals(
+                        clause[..i].parse()?,
+                        parse_literal(&clause[i + 2..])?,
+                    )),
+                    Some(i) => Ok(FilterClause::NotEquals(
+                        clause[..i].parse()?,
+                        parse_literal(&clause[i + 2..])?,
+                    )),
+                    None => Ok(FilterClause::Exists(clause.parse()?)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(RecordFilter { clauses })
+    }
+}
+
+/// Splits the filter on the `&&` which are not within string literals.
+fn split_clauses(filter: &str) -> Vec<&str> {
+    let mut clauses = vec![];
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut clause_start = 0;
+    let bytes = filter.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            b'&' if !in_string && bytes.get(i + 1) == Some(&b'&') => {
+                clauses.push(&filter[clause_start..i]);
+                clause_start = i + 2;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    clauses.push(&filter[clause_start..]);
+    clauses
+}
+
 pub trait SubscriptionResolver {
     fn get_subscription(&self, id: SubscriptionId) -> Option<Subscription>;
 
@@ -200,6 +468,63 @@ impl SubscriptionValidator for IngressOptions {
             warn!("The configuration option enable.auto.offset.store should not be set and it will be ignored.");
         }
 
+        let options = subscription.metadata();
+        if let Some(format) = options.get(VALUE_FORMAT_OPTION) {
+            if !VALUE_FORMATS.contains(&format.as_str()) {
+                return Err(ValidationError {
+                    name: VALUE_FORMAT_OPTION,
+                    reason: "supported formats are 'avro', 'protobuf' and 'json-schema'",
+                });
+            }
+            if !options.contains_key(SCHEMA_REGISTRY_URL_OPTION)
+                && !options.contains_key(SCHEMA_DIRECTORY_OPTION)
+            {
+                return Err(ValidationError {
+                    name: VALUE_FORMAT_OPTION,
+                    reason: "decoding the values requires either 'restate.schema.registry.url' or 'restate.schema.directory'",
+                });
+            }
+        }
+        if options
+            .get(ON_DECODE_ERROR_OPTION)
+            .is_some_and(|policy| !ON_DECODE_ERROR_POLICIES.contains(&policy.as_str()))
+        {
+            return Err(ValidationError {
+                name: ON_DECODE_ERROR_OPTION,
+                reason: "supported policies are 'stop' and 'skip'",
+            });
+        }
+
+        if options
+            .get(FILTER_OPTION)
+            .is_some_and(|filter| filter.parse::<RecordFilter>().is_err())
+        {
+            return Err(ValidationError {
+                name: FILTER_OPTION,
+                reason: "expected clauses joined by '&&', in the form '<selector>', '<selector> == <JSON literal>' or '<selector> != <JSON literal>', where the selector is one of 'key', 'header.<name>' or 'value.<field path>'",
+            });
+        }
+        if let Some(key) = options.get(KEY_OPTION) {
+            if key.parse::<RecordSelector>().is_err() {
+                return Err(ValidationError {
+                    name: KEY_OPTION,
+                    reason: "expected one of 'key', 'header.<name>' or 'value.<field path>'",
+                });
+            }
+            if matches!(
+                subscription.sink(),
+                Sink::Service {
+                    ty: EventReceiverServiceType::Service,
+                    ..
+                }
+            ) {
+                return Err(ValidationError {
+                    name: KEY_OPTION,
+                    reason: "the key can be set only for virtual object and workflow sinks",
+                });
+            }
+        }
+
         // Set the group.id if unset
         if !(cluster_options.contains_key("group.id")
             || subscription.metadata().contains_key("group.id"))
@@ -224,6 +549,93 @@ impl SubscriptionValidator for IngressOptions {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockRecord {
+        key: Option<&'static [u8]>,
+        headers: Vec<(&'static str, &'static [u8])>,
+        value: Option<serde_json::Value>,
+    }
+
+    impl SubscriptionRecord for MockRecord {
+        fn key(&self) -> Option<&[u8]> {
+            self.key
+        }
+
+        fn header(&self, name: &str) -> Option<&[u8]> {
+            self.headers
+                .iter()
+                .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        }
+
+        fn json_value(&self) -> Option<&serde_json::Value> {
+            self.value.as_ref()
+        }
+    }
+
+    fn order_record() -> MockRecord {
+        MockRecord {
+            key: Some(b"partition-key"),
+            headers: vec![("Event-Type", b"OrderCreated")],
+            value: Some(serde_json::json!({
+                "customer": {"id": "customer-1"},
+                "region": "us",
+                "items": [{"sku": 42}]
+            })),
+        }
+    }
+
+    #[test]
+    fn select_key() {
+        let record = order_record();
+        let select_key = |selector: &str| {
+            selector
+                .parse::<RecordSelector>()
+                .unwrap()
+                .select_key(&record)
+        };
+
+        assert_eq!(select_key("key").as_deref(), Some("partition-key"));
+        assert_eq!(
+            select_key("header.event-type").as_deref(),
+            Some("OrderCreated")
+        );
+        assert_eq!(
+            select_key("value.customer.id").as_deref(),
+            Some("customer-1")
+        );
+        assert_eq!(select_key("value.items.0.sku").as_deref(), Some("42"));
+        assert_eq!(select_key("value.customer"), None);
+        assert_eq!(select_key("value.missing"), None);
+
+        assert!("header.".parse::<RecordSelector>().is_err());
+        assert!("value..id".parse::<RecordSelector>().is_err());
+        assert!("payload.id".parse::<RecordSelector>().is_err());
+    }
+
+    #[test]
+    fn filter_records() {
+        let record = order_record();
+        let matches = |filter: &str| filter.parse::<RecordFilter>().unwrap().matches(&record);
+
+        assert!(matches(r#"header.event-type == "OrderCreated""#));
+        assert!(matches(
+            r#"header.event-type == "OrderCreated" && value.region != "eu""#
+        ));
+        assert!(matches("value.items.0.sku == 42 && value.customer.id"));
+        assert!(matches(r#"value.note != "a && b""#));
+        assert!(!matches(r#"header.event-type == "OrderCancelled""#));
+        assert!(!matches(r#"value.region == "us" && value.note"#));
+
+        assert!("value.region == us".parse::<RecordFilter>().is_err());
+        assert!("value.region == ".parse::<RecordFilter>().is_err());
+    }
+}
+
 #[cfg(feature = "test-util")]
 pub mod mocks {
     use std::str::FromStr;
@@ -246,6 +658,8 @@ pub mod mocks {
                     ty: EventReceiverServiceType::Service,
                 },
                 metadata: Default::default(),
+                paused: false,
+                offsets_generation: 0,
             }
         }
     }
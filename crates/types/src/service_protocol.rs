@@ -91,7 +91,14 @@ mod pb_into {
         type Error = &'static str;
 
         fn try_from(msg: InputEntryMessage) -> Result<Self, Self::Error> {
-            Ok(Self::Input(InputEntry { value: msg.value }))
+            Ok(Self::Input(InputEntry {
+                headers: msg
+                    .headers
+                    .into_iter()
+                    .map(|h| crate::invocation::Header::new(h.key, h.value))
+                    .collect(),
+                value: msg.value,
+            }))
         }
     }
 
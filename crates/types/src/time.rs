@@ -112,6 +112,12 @@ impl From<MillisSinceEpoch> for SystemTime {
     }
 }
 
+impl From<NanosSinceEpoch> for MillisSinceEpoch {
+    fn from(value: NanosSinceEpoch) -> Self {
+        MillisSinceEpoch::new(value.as_u64() / 1_000_000)
+    }
+}
+
 /// Nanos since the unix epoch. Used internally to get rough latency measurements across nodes.
 /// It's vulnerable to clock skews and sync issues, so use with care. That said, it's fairly
 /// accurate when used on the same node. This roughly maps to std::time::Instant except that the
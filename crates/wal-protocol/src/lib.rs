@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use bytes::{Bytes, BytesMut};
@@ -16,10 +17,13 @@ use restate_core::{metadata, ShutdownError};
 use restate_storage_api::deduplication_table::DedupInformation;
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, WithPartitionKey};
 use restate_types::invocation::{
-    AttachInvocationRequest, InvocationResponse, InvocationTermination, PurgeInvocationRequest,
-    ServiceInvocation,
+    AttachInvocationRequest, DeadLetterRequest, InvocationPause, InvocationResponse,
+    InvocationRetry, InvocationTermination, PromiseCompletionRequest, PurgeInvocationRequest,
+    ServiceInvocation, ServicePause,
 };
 use restate_types::message::MessageIndex;
+use restate_types::schema::kafka_sinks::{KafkaSinkRequest, KafkaSinkTruncation};
+use restate_types::schema::schedules::ScheduleRequest;
 use restate_types::state_mut::ExternalStateMutation;
 use restate_types::{flexbuffers_storage_encode_decode, logs, PlainNodeId, Version};
 
@@ -146,10 +150,26 @@ pub enum Command {
     Invoke(ServiceInvocation),
     /// Truncate the message outbox up to, and including, the specified index.
     TruncateOutbox(MessageIndex),
+    /// Truncate the records of a Kafka sink which have been produced by the leader
+    TruncateKafkaSinkOutbox(KafkaSinkTruncation),
     /// Proxy a service invocation through this partition processor, to reuse the deduplication id map.
     ProxyThrough(ServiceInvocation),
     /// Attach to an existing invocation
     AttachInvocation(AttachInvocationRequest),
+    /// Complete a durable promise of a workflow
+    CompletePromise(PromiseCompletionRequest),
+    /// Retry an ongoing invocation immediately or restart it from scratch
+    RetryInvocation(InvocationRetry),
+    /// Pause an ongoing invocation or resume a paused one
+    PauseInvocation(InvocationPause),
+    /// Pause or resume a service on the partition
+    PauseService(ServicePause),
+    /// Replay or discard an invocation recorded in the dead letter table
+    DeadLetter(DeadLetterRequest),
+    /// Register or remove a schedule owned by the partition
+    Schedule(ScheduleRequest),
+    /// Register or remove a Kafka sink on the partition
+    KafkaSink(KafkaSinkRequest),
 
     // -- Partition processor events for PP
     /// Invoker is reporting effect(s) from an ongoing invocation.
@@ -193,12 +213,23 @@ impl HasRecordKeys for Envelope {
                 Keys::Single(terminate.invocation_id.partition_key())
             }
             Command::PurgeInvocation(purge) => Keys::Single(purge.invocation_id.partition_key()),
+            Command::RetryInvocation(retry) => Keys::Single(retry.invocation_id.partition_key()),
+            Command::PauseInvocation(pause) => Keys::Single(pause.invocation_id.partition_key()),
+            // Sent to every partition, addressed via the start of its partition key range
+            Command::PauseService(_) => Keys::Single(self.partition_key()),
+            Command::KafkaSink(_) => Keys::Single(self.partition_key()),
+            Command::DeadLetter(dead_letter) => {
+                Keys::Single(dead_letter.invocation_id.partition_key())
+            }
+            Command::Schedule(schedule) => Keys::Single(schedule.partition_key),
             Command::Invoke(invoke) => Keys::Single(invoke.partition_key()),
             // todo: Remove this, or pass the partition key range but filter based on partition-id
             // on read if needed.
             Command::TruncateOutbox(_) => Keys::Single(self.partition_key()),
+            Command::TruncateKafkaSinkOutbox(_) => Keys::Single(self.partition_key()),
             Command::ProxyThrough(_) => Keys::Single(self.partition_key()),
             Command::AttachInvocation(_) => Keys::Single(self.partition_key()),
+            Command::CompletePromise(completion) => Keys::Single(completion.partition_key()),
             // todo: Handle journal entries that request cross-partition invocations
             Command::InvokerEffect(effect) => Keys::Single(effect.invocation_id.partition_key()),
             Command::Timer(timer) => Keys::Single(timer.invocation_id().partition_key()),
@@ -248,3 +279,77 @@ pub async fn append_envelope_to_bifrost(
 
     Ok((log_id, lsn))
 }
+
+/// Maximum number of envelopes appended to a log in a single batch.
+pub const MAX_APPEND_BATCH_SIZE: usize = 1000;
+
+/// Appends the given envelopes to the provided Bifrost instance, batching the envelopes of the
+/// same log in batches of at most [`MAX_APPEND_BATCH_SIZE`] envelopes. The order of the
+/// envelopes of the same log is preserved: once a batch fails, the following envelopes of its
+/// log are not appended.
+///
+/// Returns the result of every envelope, in the order of the given envelopes. The envelopes
+/// which have not been appended because of the same failure share its error.
+///
+/// Important: This method must only be called in the context of a [`TaskCenter`] task because
+/// it needs access to [`metadata()`].
+pub async fn append_envelopes_to_bifrost(
+    bifrost: &Bifrost,
+    envelopes: Vec<Arc<Envelope>>,
+) -> Vec<Result<(LogId, Lsn), Arc<Error>>> {
+    let mut results: Vec<Option<Result<(LogId, Lsn), Arc<Error>>>> =
+        envelopes.iter().map(|_| None).collect();
+
+    let batches = {
+        // make sure we drop pinned partition table before awaiting
+        let partition_table = match metadata().wait_for_partition_table(Version::MIN).await {
+            Ok(partition_table) => partition_table,
+            Err(err) => {
+                let err = Arc::new(Error::from(err));
+                return envelopes.iter().map(|_| Err(Arc::clone(&err))).collect();
+            }
+        };
+        let mut batches: HashMap<LogId, Vec<(usize, Arc<Envelope>)>> = HashMap::new();
+        for (index, envelope) in envelopes.into_iter().enumerate() {
+            match partition_table.find_partition_id(envelope.partition_key()) {
+                Ok(partition_id) => batches
+                    .entry(LogId::from(*partition_id))
+                    .or_default()
+                    .push((index, envelope)),
+                Err(err) => results[index] = Some(Err(Arc::new(err.into()))),
+            }
+        }
+        batches
+    };
+
+    for (log_id, batch) in batches {
+        let mut failure: Option<Arc<Error>> = None;
+        for chunk in batch.chunks(MAX_APPEND_BATCH_SIZE) {
+            let (indexes, chunk): (Vec<_>, Vec<_>) = chunk.iter().cloned().unzip();
+            if failure.is_none() {
+                match bifrost.append_batch(log_id, chunk).await {
+                    Ok(last_lsn) => {
+                        // The envelopes of the batch have consecutive LSNs
+                        let first_lsn = u64::from(last_lsn) + 1 - indexes.len() as u64;
+                        for (offset, index) in indexes.into_iter().enumerate() {
+                            results[index] =
+                                Some(Ok((log_id, Lsn::from(first_lsn + offset as u64))));
+                        }
+                        continue;
+                    }
+                    Err(err) => failure = Some(Arc::new(err.into())),
+                }
+            }
+
+            let err = failure.as_ref().expect("batch failed");
+            for index in indexes {
+                results[index] = Some(Err(Arc::clone(err)));
+            }
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every envelope has a result"))
+        .collect()
+}
@@ -60,6 +60,16 @@ impl TimerKeyValue {
         Self { timer_key, value }
     }
 
+    pub fn fire_schedule(
+        wake_up_time: MillisSinceEpoch,
+        invocation_id: InvocationId,
+        schedule_id: String,
+    ) -> Self {
+        let (timer_key, value) =
+            Timer::fire_schedule(wake_up_time.as_u64(), invocation_id, schedule_id);
+        Self { timer_key, value }
+    }
+
     pub fn into_inner(self) -> (TimerKey, Timer) {
         (self.timer_key, self.value)
     }
@@ -134,6 +144,9 @@ impl<'a> fmt::Display for TimerKeyDisplay<'a> {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 write!(f, "Clean invocation status '{}'", invocation_uuid)
             }
+            TimerKeyKind::FireSchedule { invocation_uuid } => {
+                write!(f, "Scheduled invocation '{}'", invocation_uuid)
+            }
         }
     }
 }
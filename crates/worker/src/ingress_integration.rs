@@ -9,17 +9,19 @@
 // by the Apache License, Version 2.0.
 
 use anyhow::{anyhow, Error};
+use bytestring::ByteString;
 use restate_core::metadata;
-use restate_ingress_http::{GetOutputResult, InvocationStorageReader};
-use restate_partition_store::PartitionStoreManager;
+use restate_ingress_http::{GetOutputResult, GetPromiseResult, InvocationStorageReader};
+use restate_partition_store::{PartitionStore, PartitionStoreManager};
 use restate_storage_api::idempotency_table::ReadOnlyIdempotencyTable;
 use restate_storage_api::invocation_status_table::{
     InvocationStatus, ReadOnlyInvocationStatusTable,
 };
+use restate_storage_api::promise_table::{Promise, PromiseState, ReadOnlyPromiseTable};
 use restate_storage_api::service_status_table::{
     ReadOnlyVirtualObjectStatusTable, VirtualObjectStatus,
 };
-use restate_types::identifiers::WithPartitionKey;
+use restate_types::identifiers::{PartitionKey, ServiceId, WithPartitionKey};
 use restate_types::ingress::{IngressResponseResult, InvocationResponse};
 use restate_types::invocation::{
     InvocationQuery, InvocationTarget, InvocationTargetType, ResponseResult, WorkflowHandlerType,
@@ -37,15 +39,15 @@ impl InvocationStorageReaderImpl {
             partition_store_manager,
         }
     }
-}
 
-impl InvocationStorageReader for InvocationStorageReaderImpl {
-    async fn get_output(&self, query: InvocationQuery) -> Result<GetOutputResult, Error> {
+    async fn get_partition_store(
+        &self,
+        partition_key: PartitionKey,
+    ) -> Result<PartitionStore, Error> {
         let partition_id = metadata()
             .partition_table_ref()
-            .find_partition_id(query.partition_key())?;
-        let mut partition_storage = self
-            .partition_store_manager
+            .find_partition_id(partition_key)?;
+        self.partition_store_manager
             .get_partition_store(partition_id)
             .await
             .ok_or_else(|| {
@@ -53,7 +55,13 @@ impl InvocationStorageReader for InvocationStorageReaderImpl {
                     "Can't find partition store for partition id {}",
                     partition_id
                 )
-            })?;
+            })
+    }
+}
+
+impl InvocationStorageReader for InvocationStorageReaderImpl {
+    async fn get_output(&self, query: InvocationQuery) -> Result<GetOutputResult, Error> {
+        let mut partition_storage = self.get_partition_store(query.partition_key()).await?;
 
         let invocation_id = match query {
             InvocationQuery::Invocation(invocation_id) => invocation_id,
@@ -114,4 +122,21 @@ impl InvocationStorageReader for InvocationStorageReaderImpl {
             _ => Ok(GetOutputResult::NotReady),
         }
     }
+
+    async fn get_promise(
+        &self,
+        workflow_id: ServiceId,
+        key: ByteString,
+    ) -> Result<GetPromiseResult, Error> {
+        let mut partition_storage = self
+            .get_partition_store(workflow_id.partition_key())
+            .await?;
+
+        match partition_storage.get_promise(&workflow_id, &key).await? {
+            Some(Promise {
+                state: PromiseState::Completed(result),
+            }) => Ok(GetPromiseResult::Completed(result.into())),
+            _ => Ok(GetPromiseResult::NotCompleted),
+        }
+    }
 }
@@ -48,6 +48,7 @@ use restate_types::schema::Schema;
 
 use crate::ingress_integration::InvocationStorageReaderImpl;
 use crate::partition::invoker_storage_reader::InvokerStorageReader;
+use crate::partition::snapshot_repository::SnapshotRepository;
 use crate::partition_processor_manager::PartitionProcessorManager;
 
 pub use self::error::*;
@@ -84,6 +85,15 @@ pub enum BuildError {
     ),
     #[code(unknown)]
     Invoker(#[from] restate_invoker_impl::BuildError),
+    #[error("failed creating ingress: {0}")]
+    Ingress(
+        #[from]
+        #[code]
+        restate_ingress_http::IngressServerError,
+    ),
+    #[error("failed creating snapshot repository: {0}")]
+    #[code(unknown)]
+    SnapshotRepository(anyhow::Error),
 }
 
 #[derive(Debug, thiserror::Error, CodedError)]
@@ -150,7 +160,11 @@ impl<T: TransportConnect> Worker<T> {
             ingress_dispatcher.clone(),
             schema.clone(),
             InvocationStorageReaderImpl::new(partition_store_manager.clone()),
-        );
+        )?;
+
+        let snapshot_repository =
+            SnapshotRepository::create_if_configured(&config.worker.snapshots)
+                .map_err(BuildError::SnapshotRepository)?;
 
         let partition_processor_manager = PartitionProcessorManager::new(
             task_center(),
@@ -162,6 +176,7 @@ impl<T: TransportConnect> Worker<T> {
             router_builder,
             networking.clone(),
             bifrost,
+            snapshot_repository,
         );
 
         // handle RPCs
@@ -26,6 +26,8 @@ pub const PARTITION_LAST_APPLIED_LOG_LSN: &str = "restate.partition.last_applied
 pub const PARTITION_LAST_PERSISTED_LOG_LSN: &str = "restate.partition.last_persisted_lsn";
 pub const PARTITION_IS_EFFECTIVE_LEADER: &str = "restate.partition.is_effective_leader";
 pub const PARTITION_IS_ACTIVE: &str = "restate.partition.is_active";
+pub const PARTITION_TIME_SINCE_LAST_SNAPSHOT: &str = "restate.partition.time_since_last_snapshot";
+pub const PARTITION_LAST_SNAPSHOT_SIZE: &str = "restate.partition.last_snapshot_size.bytes";
 
 pub const PP_APPLY_COMMAND_DURATION: &str = "restate.partition.apply_command_duration.seconds";
 pub const PP_APPLY_COMMAND_BATCH_SIZE: &str = "restate.partition.apply_command_batch_size";
@@ -124,4 +126,16 @@ pub(crate) fn describe_metrics() {
         Unit::Seconds,
         "Number of seconds since the last record was applied"
     );
+
+    describe_gauge!(
+        PARTITION_TIME_SINCE_LAST_SNAPSHOT,
+        Unit::Seconds,
+        "Number of seconds since the last partition snapshot created by this node"
+    );
+
+    describe_gauge!(
+        PARTITION_LAST_SNAPSHOT_SIZE,
+        Unit::Bytes,
+        "Size of the last partition snapshot created by this node"
+    );
 }
@@ -175,7 +175,7 @@ mod tests {
         CompletedInvocation, InFlightInvocationMetadata, InvocationStatus,
     };
     use restate_types::identifiers::{InvocationId, InvocationUuid};
-    use restate_types::invocation::InvocationTarget;
+    use restate_types::invocation::{InvocationEpoch, InvocationTarget};
     use restate_types::partition_table::{FindPartition, PartitionTable};
     use restate_types::Version;
     use std::future::Future;
@@ -196,8 +196,9 @@ mod tests {
 
         fn all_invoked_invocations(
             &mut self,
-        ) -> impl Stream<Item = restate_storage_api::Result<(InvocationId, InvocationTarget)>> + Send
-        {
+        ) -> impl Stream<
+            Item = restate_storage_api::Result<(InvocationId, InvocationTarget, InvocationEpoch)>,
+        > + Send {
             todo!();
             #[allow(unreachable_code)]
             stream::empty()
@@ -0,0 +1,220 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use futures::{stream, StreamExt};
+use tokio::sync::Notify;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, instrument, warn};
+
+use restate_bifrost::Bifrost;
+use restate_core::cancellation_watcher;
+use restate_egress_kafka::KafkaSinkProducer;
+use restate_storage_api::kafka_sink_outbox_table::{KafkaSinkRecord, ReadOnlyKafkaSinkOutboxTable};
+use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey};
+use restate_types::message::MessageIndex;
+use restate_types::schema::kafka_sinks::KafkaSinkTruncation;
+use restate_types::GenerationalNodeId;
+use restate_wal_protocol::{
+    append_envelope_to_bifrost, Command, Destination, Envelope, Header, Source,
+};
+
+/// Interval in which the failed records are retried if no new record has been added.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of records of a sink which are produced in one round.
+const MAX_RECORDS_PER_SINK: usize = 128;
+
+/// Maximum number of records of a sink which are in flight at the same time.
+const MAX_IN_FLIGHT_PER_SINK: usize = 16;
+
+/// Produces the records of the Kafka sink outbox of a partition, in order per sink. Every sink
+/// makes progress independently, so that an unavailable Kafka cluster only holds back the records
+/// of its own sinks. Produced records are truncated through the log, and retried until then.
+pub(super) struct KafkaSinkEgress<Storage> {
+    partition_id: PartitionId,
+    leader_epoch: LeaderEpoch,
+    node_id: GenerationalNodeId,
+    partition_key: PartitionKey,
+    storage: Storage,
+    bifrost: Bifrost,
+    producer: KafkaSinkProducer,
+    hint: Arc<Notify>,
+    /// Index of the last record produced per sink, whose truncation might not be applied yet.
+    produced: HashMap<String, MessageIndex>,
+}
+
+impl<Storage> KafkaSinkEgress<Storage>
+where
+    Storage: ReadOnlyKafkaSinkOutboxTable + Send + Sync + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        partition_id: PartitionId,
+        leader_epoch: LeaderEpoch,
+        node_id: GenerationalNodeId,
+        partition_key: PartitionKey,
+        storage: Storage,
+        bifrost: Bifrost,
+        producer: KafkaSinkProducer,
+        hint: Arc<Notify>,
+    ) -> Self {
+        Self {
+            partition_id,
+            leader_epoch,
+            node_id,
+            partition_key,
+            storage,
+            bifrost,
+            producer,
+            hint,
+            produced: HashMap::default(),
+        }
+    }
+
+    #[instrument(skip_all, fields(restate.node = %self.node_id, restate.partition.id = %self.partition_id))]
+    pub(super) async fn run(mut self) -> anyhow::Result<()> {
+        debug!("Running Kafka sink egress");
+
+        let hint = Arc::clone(&self.hint);
+        let mut interval = tokio::time::interval(RETRY_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = hint.notified() => {},
+                _ = interval.tick() => {},
+                _ = cancellation_watcher() => {
+                    break;
+                }
+            }
+
+            if let Err(e) = self.produce_pending_records().await {
+                warn!("Error when trying to produce the Kafka sink records: {e:?}");
+            }
+        }
+
+        debug!("Stopping Kafka sink egress");
+
+        Ok(())
+    }
+
+    async fn produce_pending_records(&mut self) -> anyhow::Result<()> {
+        let mut pending_records: BTreeMap<String, Vec<(MessageIndex, KafkaSinkRecord)>> =
+            BTreeMap::new();
+        {
+            let records = self.storage.all_kafka_sink_records();
+            tokio::pin!(records);
+
+            while let Some((record_index, record)) = records
+                .next()
+                .await
+                .transpose()
+                .context("Cannot read the next item of the Kafka sink outbox")?
+            {
+                if self
+                    .produced
+                    .get(&record.sink_id)
+                    .is_some_and(|produced_index| record_index <= *produced_index)
+                {
+                    continue;
+                }
+
+                let sink_records = pending_records.entry(record.sink_id.clone()).or_default();
+                if sink_records.len() < MAX_RECORDS_PER_SINK {
+                    sink_records.push((record_index, record));
+                }
+            }
+        }
+
+        let produced_indexes = futures::future::join_all(
+            pending_records
+                .into_iter()
+                .map(|(sink_id, sink_records)| self.produce_sink_records(sink_id, sink_records)),
+        )
+        .await;
+
+        for (sink_id, produced_index) in produced_indexes.into_iter().flatten() {
+            self.append_truncation(KafkaSinkTruncation {
+                sink_id: sink_id.clone(),
+                index: produced_index,
+            })
+            .await?;
+            self.produced.insert(sink_id, produced_index);
+        }
+
+        Ok(())
+    }
+
+    /// Produces the records of a sink in order, returning the index of the last record which has
+    /// been produced before the first failure.
+    async fn produce_sink_records(
+        &self,
+        sink_id: String,
+        sink_records: Vec<(MessageIndex, KafkaSinkRecord)>,
+    ) -> Option<(String, MessageIndex)> {
+        let results = stream::iter(sink_records.iter())
+            .map(|(record_index, record)| async move {
+                self.producer
+                    .send(record)
+                    .await
+                    .map(|_| *record_index)
+                    .map_err(|err| (*record_index, err))
+            })
+            .buffered(MAX_IN_FLIGHT_PER_SINK);
+        tokio::pin!(results);
+
+        let mut produced_index = None;
+        while let Some(result) = results.next().await {
+            match result {
+                Ok(record_index) => produced_index = Some(record_index),
+                Err((record_index, err)) => {
+                    warn!(
+                        restate.kafka_sink.id = %sink_id,
+                        "Failed to produce the Kafka sink record {record_index}, retrying later: {err}"
+                    );
+                    break;
+                }
+            }
+        }
+
+        produced_index.map(|produced_index| (sink_id, produced_index))
+    }
+
+    async fn append_truncation(&self, truncation: KafkaSinkTruncation) -> anyhow::Result<()> {
+        append_envelope_to_bifrost(
+            &self.bifrost,
+            Arc::new(Envelope {
+                header: Header {
+                    source: Source::Processor {
+                        partition_id: self.partition_id,
+                        partition_key: None,
+                        leader_epoch: self.leader_epoch,
+                        node_id: self.node_id.as_plain(),
+                        generational_node_id: Some(self.node_id),
+                    },
+                    dest: Destination::Processor {
+                        partition_key: self.partition_key,
+                        dedup: None,
+                    },
+                },
+                command: Command::TruncateKafkaSinkOutbox(truncation),
+            }),
+        )
+        .await
+        .context("Cannot append to bifrost")?;
+
+        Ok(())
+    }
+}
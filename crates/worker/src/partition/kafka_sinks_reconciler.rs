@@ -0,0 +1,81 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use tracing::debug;
+
+use restate_core::metadata;
+use restate_storage_api::fsm_table::ReadOnlyFsmTable;
+use restate_types::identifiers::PartitionKey;
+use restate_types::schema::kafka_sinks::{KafkaSinkRequest, KafkaSinkResolver};
+use restate_wal_protocol::Command;
+
+use crate::partition::schema_reconciler::SchemaDiff;
+
+/// Kafka sinks registered on a partition. The admin service tells every partition about a
+/// registered or removed sink.
+pub(super) struct KafkaSinksDiff<Storage> {
+    partition_key: PartitionKey,
+    storage: Storage,
+}
+
+impl<Storage> KafkaSinksDiff<Storage> {
+    pub(super) fn new(partition_key: PartitionKey, storage: Storage) -> Self {
+        Self {
+            partition_key,
+            storage,
+        }
+    }
+}
+
+impl<Storage> SchemaDiff for KafkaSinksDiff<Storage>
+where
+    Storage: ReadOnlyFsmTable + Send + Sync + 'static,
+{
+    const NAME: &'static str = "Kafka sinks";
+
+    async fn diff(&mut self) -> anyhow::Result<Vec<(PartitionKey, Command)>> {
+        let mut partition_sinks: BTreeMap<_, _> = self
+            .storage
+            .get_kafka_sink_routes()
+            .await
+            .context("Cannot read the Kafka sink routes")?
+            .into_inner();
+
+        let mut kafka_sink_requests = Vec::new();
+        for sink in metadata().schema().list_kafka_sinks() {
+            if partition_sinks
+                .remove(sink.id())
+                .is_some_and(|source| &source == sink.source())
+            {
+                continue;
+            }
+
+            kafka_sink_requests.push(KafkaSinkRequest::register(&sink));
+        }
+
+        // Whatever is left has been removed from the schema
+        kafka_sink_requests.extend(partition_sinks.into_keys().map(KafkaSinkRequest::remove));
+
+        Ok(kafka_sink_requests
+            .into_iter()
+            .map(|kafka_sink_request| {
+                debug!(
+                    restate.kafka_sink.id = %kafka_sink_request.sink_id,
+                    "Reconciling Kafka sink: {:?}",
+                    kafka_sink_request.flavor
+                );
+                (self.partition_key, Command::KafkaSink(kafka_sink_request))
+            })
+            .collect())
+    }
+}
@@ -19,7 +19,7 @@ use std::time::Duration;
 use futures::future::OptionFuture;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use metrics::counter;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, info, instrument, trace, warn};
 
@@ -28,6 +28,7 @@ use restate_core::network::{NetworkSender, Networking, Outgoing, TransportConnec
 use restate_core::{
     current_task_partition_id, metadata, task_center, ShutdownError, TaskHandle, TaskId, TaskKind,
 };
+use restate_egress_kafka::KafkaSinkProducer;
 use restate_errors::NotRunningError;
 use restate_invoker_api::InvokeInputJournal;
 use restate_partition_store::PartitionStore;
@@ -51,6 +52,11 @@ use crate::metric_definitions::PARTITION_HANDLE_LEADER_ACTIONS;
 use crate::partition::action_effect_handler::ActionEffectHandler;
 use crate::partition::cleaner::Cleaner;
 use crate::partition::invoker_storage_reader::InvokerStorageReader;
+use crate::partition::kafka_sink_egress::KafkaSinkEgress;
+use crate::partition::kafka_sinks_reconciler::KafkaSinksDiff;
+use crate::partition::paused_services_reconciler::PausedServicesDiff;
+use crate::partition::schedules_reconciler::SchedulesDiff;
+use crate::partition::schema_reconciler::SchemaReconciler;
 use crate::partition::shuffle;
 use crate::partition::shuffle::{HintSender, OutboxReaderError, Shuffle, ShuffleMetadata};
 use crate::partition::state_machine::Action;
@@ -92,6 +98,11 @@ pub(crate) struct LeaderState {
     invoker_stream: ReceiverStream<restate_invoker_api::Effect>,
     shuffle_stream: ReceiverStream<shuffle::OutboxTruncation>,
     cleaner_task_id: TaskId,
+    paused_services_reconciler_task_id: TaskId,
+    schedules_reconciler_task_id: TaskId,
+    kafka_sinks_reconciler_task_id: TaskId,
+    kafka_sink_egress_task_id: TaskId,
+    kafka_sink_egress_hint: Arc<Notify>,
 }
 
 pub enum State {
@@ -144,6 +155,7 @@ pub(crate) struct LeadershipState<I, T> {
     invoker_tx: I,
     network_tx: Networking<T>,
     bifrost: Bifrost,
+    kafka_sink_producer: KafkaSinkProducer,
 }
 
 impl<I, T> LeadershipState<I, T>
@@ -159,6 +171,7 @@ where
         channel_size: usize,
         invoker_tx: I,
         bifrost: Bifrost,
+        kafka_sink_producer: KafkaSinkProducer,
         network_tx: Networking<T>,
         last_seen_leader_epoch: Option<LeaderEpoch>,
     ) -> Self {
@@ -170,6 +183,7 @@ where
             channel_size,
             invoker_tx,
             bifrost,
+            kafka_sink_producer,
             network_tx,
             last_seen_leader_epoch,
         }
@@ -413,10 +427,99 @@ where
                 cleaner.run(),
             )?;
 
+            let paused_services_reconciler = SchemaReconciler::new(
+                self.partition_processor_metadata.partition_id,
+                leader_epoch,
+                self.partition_processor_metadata.node_id,
+                PausedServicesDiff::new(
+                    *self
+                        .partition_processor_metadata
+                        .partition_key_range
+                        .start(),
+                    partition_store.clone(),
+                ),
+                self.bifrost.clone(),
+            );
+
+            let paused_services_reconciler_task_id = task_center().spawn_child(
+                TaskKind::PausedServicesReconciler,
+                "paused-services-reconciler",
+                Some(self.partition_processor_metadata.partition_id),
+                paused_services_reconciler.run(),
+            )?;
+
+            let schedules_reconciler = SchemaReconciler::new(
+                self.partition_processor_metadata.partition_id,
+                leader_epoch,
+                self.partition_processor_metadata.node_id,
+                SchedulesDiff::new(
+                    self.partition_processor_metadata
+                        .partition_key_range
+                        .clone(),
+                    partition_store.clone(),
+                ),
+                self.bifrost.clone(),
+            );
+
+            let schedules_reconciler_task_id = task_center().spawn_child(
+                TaskKind::SchedulesReconciler,
+                "schedules-reconciler",
+                Some(self.partition_processor_metadata.partition_id),
+                schedules_reconciler.run(),
+            )?;
+
+            let kafka_sinks_reconciler = SchemaReconciler::new(
+                self.partition_processor_metadata.partition_id,
+                leader_epoch,
+                self.partition_processor_metadata.node_id,
+                KafkaSinksDiff::new(
+                    *self
+                        .partition_processor_metadata
+                        .partition_key_range
+                        .start(),
+                    partition_store.clone(),
+                ),
+                self.bifrost.clone(),
+            );
+
+            let kafka_sinks_reconciler_task_id = task_center().spawn_child(
+                TaskKind::KafkaSinksReconciler,
+                "kafka-sinks-reconciler",
+                Some(self.partition_processor_metadata.partition_id),
+                kafka_sinks_reconciler.run(),
+            )?;
+
+            let kafka_sink_egress_hint = Arc::new(Notify::new());
+            let kafka_sink_egress = KafkaSinkEgress::new(
+                self.partition_processor_metadata.partition_id,
+                leader_epoch,
+                self.partition_processor_metadata.node_id,
+                *self
+                    .partition_processor_metadata
+                    .partition_key_range
+                    .start(),
+                partition_store.clone(),
+                self.bifrost.clone(),
+                self.kafka_sink_producer.clone(),
+                Arc::clone(&kafka_sink_egress_hint),
+            );
+
+            let kafka_sink_egress_task_id = task_center().spawn_child(
+                TaskKind::KafkaSinkEgress,
+                "kafka-sink-egress",
+                Some(self.partition_processor_metadata.partition_id),
+                kafka_sink_egress.run(),
+            )?;
+
             self.state = State::Leader(LeaderState {
                 leader_epoch,
                 shuffle_task_id,
                 cleaner_task_id,
+                paused_services_reconciler_task_id,
+                schedules_reconciler_task_id,
+                kafka_sinks_reconciler_task_id,
+                kafka_sink_egress_task_id,
+                kafka_sink_egress_hint,
                 shuffle_hint_tx,
                 timer_service,
                 action_effect_handler,
@@ -456,11 +559,13 @@ where
 
             let mut count = 0;
             while let Some(invocation_id_and_target) = invoked_invocations.next().await {
-                let (invocation_id, invocation_target) = invocation_id_and_target?;
+                let (invocation_id, invocation_target, invocation_epoch) =
+                    invocation_id_and_target?;
                 invoker_handle
                     .invoke(
                         partition_leader_epoch,
                         invocation_id,
+                        invocation_epoch,
                         invocation_target,
                         InvokeInputJournal::NoCachedJournal,
                     )
@@ -484,16 +589,41 @@ where
                 leader_epoch,
                 shuffle_task_id,
                 cleaner_task_id,
+                paused_services_reconciler_task_id,
+                schedules_reconciler_task_id,
+                kafka_sinks_reconciler_task_id,
+                kafka_sink_egress_task_id,
                 ..
             }) => {
                 let shuffle_handle =
                     OptionFuture::from(task_center().cancel_task(*shuffle_task_id));
                 let cleaner_handle =
                     OptionFuture::from(task_center().cancel_task(*cleaner_task_id));
-
-                let (shuffle_result, cleaner_result, abort_result) = tokio::join!(
+                let paused_services_reconciler_handle = OptionFuture::from(
+                    task_center().cancel_task(*paused_services_reconciler_task_id),
+                );
+                let schedules_reconciler_handle =
+                    OptionFuture::from(task_center().cancel_task(*schedules_reconciler_task_id));
+                let kafka_sinks_reconciler_handle =
+                    OptionFuture::from(task_center().cancel_task(*kafka_sinks_reconciler_task_id));
+                let kafka_sink_egress_handle =
+                    OptionFuture::from(task_center().cancel_task(*kafka_sink_egress_task_id));
+
+                let (
+                    shuffle_result,
+                    cleaner_result,
+                    paused_services_reconciler_result,
+                    schedules_reconciler_result,
+                    kafka_sinks_reconciler_result,
+                    kafka_sink_egress_result,
+                    abort_result,
+                ) = tokio::join!(
                     shuffle_handle,
                     cleaner_handle,
+                    paused_services_reconciler_handle,
+                    schedules_reconciler_handle,
+                    kafka_sinks_reconciler_handle,
+                    kafka_sink_egress_handle,
                     self.invoker_tx.abort_all_partition((
                         self.partition_processor_metadata.partition_id,
                         *leader_epoch
@@ -508,6 +638,22 @@ where
                 if let Some(cleaner_result) = cleaner_result {
                     cleaner_result.expect("graceful termination of cleaner task");
                 }
+                if let Some(paused_services_reconciler_result) = paused_services_reconciler_result {
+                    paused_services_reconciler_result
+                        .expect("graceful termination of paused services reconciler task");
+                }
+                if let Some(schedules_reconciler_result) = schedules_reconciler_result {
+                    schedules_reconciler_result
+                        .expect("graceful termination of schedules reconciler task");
+                }
+                if let Some(kafka_sinks_reconciler_result) = kafka_sinks_reconciler_result {
+                    kafka_sinks_reconciler_result
+                        .expect("graceful termination of Kafka sinks reconciler task");
+                }
+                if let Some(kafka_sink_egress_result) = kafka_sink_egress_result {
+                    kafka_sink_egress_result
+                        .expect("graceful termination of Kafka sink egress task");
+                }
             }
         }
 
@@ -570,6 +716,7 @@ where
                         ),
                         &mut self.invoker_tx,
                         &leader_state.shuffle_hint_tx,
+                        &leader_state.kafka_sink_egress_hint,
                         leader_state.timer_service.as_mut(),
                         &mut leader_state.action_effects,
                         &self.network_tx,
@@ -588,6 +735,7 @@ where
         partition_leader_epoch: PartitionLeaderEpoch,
         invoker_tx: &mut I,
         shuffle_hint_tx: &HintSender,
+        kafka_sink_egress_hint: &Notify,
         mut timer_service: Pin<&mut TimerService>,
         actions_effects: &mut VecDeque<ActionEffect>,
         network_tx: &Networking<T>,
@@ -595,12 +743,14 @@ where
         match action {
             Action::Invoke {
                 invocation_id,
+                invocation_epoch,
                 invocation_target,
                 invoke_input_journal,
             } => invoker_tx
                 .invoke(
                     partition_leader_epoch,
                     invocation_id,
+                    invocation_epoch,
                     invocation_target,
                     invoke_input_journal,
                 )
@@ -610,6 +760,7 @@ where
                 seq_number,
                 message,
             } => shuffle_hint_tx.send(shuffle::NewOutboxMessage::new(seq_number, message)),
+            Action::NewKafkaSinkRecord => kafka_sink_egress_hint.notify_one(),
             Action::RegisterTimer { timer_value } => timer_service.as_mut().add_timer(timer_value),
             Action::DeleteTimer { timer_key } => timer_service.as_mut().remove_timer(timer_key),
             Action::AckStoredEntry {
@@ -632,6 +783,10 @@ where
                 .abort_invocation(partition_leader_epoch, invocation_id)
                 .await
                 .map_err(Error::Invoker)?,
+            Action::RetryInvocationNow(invocation_id) => invoker_tx
+                .retry_invocation_now(partition_leader_epoch, invocation_id)
+                .await
+                .map_err(Error::Invoker)?,
             Action::IngressResponse(ingress_response) => {
                 Self::send_ingress_message(
                     network_tx.clone(),
@@ -798,6 +953,7 @@ mod tests {
     use assert2::let_assert;
     use restate_bifrost::Bifrost;
     use restate_core::TestCoreEnv;
+    use restate_egress_kafka::KafkaSinkProducer;
     use restate_invoker_api::test_util::MockInvokerHandle;
     use restate_partition_store::{OpenMode, PartitionStoreManager};
     use restate_rocksdb::RocksDbManager;
@@ -854,6 +1010,7 @@ mod tests {
                 42,
                 invoker_tx,
                 bifrost.clone(),
+                KafkaSinkProducer::default(),
                 env.networking.clone(),
                 None,
             );
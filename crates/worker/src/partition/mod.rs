@@ -9,14 +9,17 @@
 // by the Apache License, Version 2.0.
 
 use std::fmt::Debug;
+use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{anyhow, Context};
 use assert2::let_assert;
+use futures::future::OptionFuture;
 use futures::{FutureExt, Stream, StreamExt, TryStreamExt as _};
-use metrics::{counter, histogram};
+use metrics::{counter, gauge, histogram};
 use tokio::sync::{mpsc, oneshot, watch};
 use tokio::time::MissedTickBehavior;
 use tracing::{debug, error, info, instrument, trace, warn, Instrument, Span};
@@ -24,6 +27,8 @@ use tracing::{debug, error, info, instrument, trace, warn, Instrument, Span};
 use restate_bifrost::{Bifrost, FindTailAttributes};
 use restate_core::network::{Networking, TransportConnect};
 use restate_core::{cancellation_watcher, metadata, TaskHandle, TaskKind};
+use restate_egress_kafka::KafkaSinkProducer;
+use restate_partition_store::snapshots::{LocalPartitionSnapshot, PartitionSnapshotMetadata};
 use restate_partition_store::{PartitionStore, PartitionStoreTransaction};
 use restate_storage_api::deduplication_table::{
     DedupInformation, DedupSequenceNumber, DeduplicationTable, ProducerId,
@@ -33,7 +38,7 @@ use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
 use restate_storage_api::outbox_table::ReadOnlyOutboxTable;
 use restate_storage_api::{StorageError, Transaction};
 use restate_types::cluster::cluster_state::{PartitionProcessorStatus, ReplayStatus, RunMode};
-use restate_types::config::{Configuration, WorkerOptions};
+use restate_types::config::{Configuration, SnapshotsOptions, WorkerOptions};
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, SnapshotId};
 use restate_types::journal::raw::RawEntryCodec;
 use restate_types::live::Live;
@@ -46,20 +51,28 @@ use restate_wal_protocol::control::AnnounceLeader;
 use restate_wal_protocol::{Command, Destination, Envelope, Header, Source};
 
 use crate::metric_definitions::{
-    PARTITION_ACTUATOR_HANDLED, PARTITION_LABEL, PARTITION_LEADER_HANDLE_ACTION_BATCH_DURATION,
+    PARTITION_ACTUATOR_HANDLED, PARTITION_LABEL, PARTITION_LAST_SNAPSHOT_SIZE,
+    PARTITION_LEADER_HANDLE_ACTION_BATCH_DURATION, PARTITION_TIME_SINCE_LAST_SNAPSHOT,
     PP_APPLY_COMMAND_BATCH_SIZE, PP_APPLY_COMMAND_DURATION,
 };
 use crate::partition::invoker_storage_reader::InvokerStorageReader;
 use crate::partition::leadership::{LeadershipState, PartitionProcessorMetadata};
 use crate::partition::snapshot_producer::{SnapshotProducer, SnapshotSource};
+use crate::partition::snapshot_repository::SnapshotRepository;
 use crate::partition::state_machine::{ActionCollector, StateMachine};
 
 mod action_effect_handler;
 mod cleaner;
 pub mod invoker_storage_reader;
+mod kafka_sink_egress;
+mod kafka_sinks_reconciler;
 mod leadership;
+mod paused_services_reconciler;
+mod schedules_reconciler;
+mod schema_reconciler;
 pub mod shuffle;
-mod snapshot_producer;
+pub mod snapshot_producer;
+pub mod snapshot_repository;
 mod state_machine;
 pub mod types;
 
@@ -82,7 +95,8 @@ pub(super) struct PartitionProcessorBuilder<InvokerInputSender> {
     channel_size: usize,
     max_command_batch_size: usize,
 
-    status: PartitionProcessorStatus,
+    pub status: PartitionProcessorStatus,
+    snapshot_repository: Option<SnapshotRepository>,
     invoker_tx: InvokerInputSender,
     control_rx: mpsc::Receiver<PartitionProcessorControlCommand>,
     status_watch_tx: watch::Sender<PartitionProcessorStatus>,
@@ -103,6 +117,7 @@ where
         control_rx: mpsc::Receiver<PartitionProcessorControlCommand>,
         status_watch_tx: watch::Sender<PartitionProcessorStatus>,
         invoker_tx: InvokerInputSender,
+        snapshot_repository: Option<SnapshotRepository>,
     ) -> Self {
         Self {
             node_id,
@@ -114,6 +129,7 @@ where
             cleanup_interval: options.cleanup_interval(),
             channel_size: options.internal_queue_length(),
             max_command_batch_size: options.max_command_batch_size(),
+            snapshot_repository,
             invoker_tx,
             control_rx,
             status_watch_tx,
@@ -124,6 +140,7 @@ where
         self,
         networking: Networking<T>,
         bifrost: Bifrost,
+        kafka_sink_producer: KafkaSinkProducer,
         mut partition_store: PartitionStore,
         configuration: Live<Configuration>,
     ) -> Result<PartitionProcessor<Codec, InvokerInputSender, T>, StorageError> {
@@ -139,6 +156,7 @@ where
             control_rx,
             status_watch_tx,
             status,
+            snapshot_repository,
             ..
         } = self;
 
@@ -171,6 +189,7 @@ where
             channel_size,
             invoker_tx,
             bifrost.clone(),
+            kafka_sink_producer,
             networking,
             last_seen_leader_epoch,
         );
@@ -186,8 +205,12 @@ where
             configuration,
             control_rx,
             status_watch_tx,
+            last_snapshot_attempt_lsn: status.last_archived_log_lsn.unwrap_or(Lsn::INVALID),
             status,
+            snapshot_repository,
             inflight_create_snapshot_task: None,
+            last_snapshot_attempt_at: Instant::now(),
+            last_snapshot_created_at: None,
         })
     }
 
@@ -202,12 +225,16 @@ where
         let inbox_seq_number = partition_store.get_inbox_seq_number().await?;
         let outbox_seq_number = partition_store.get_outbox_seq_number().await?;
         let outbox_head_seq_number = partition_store.get_outbox_head_seq_number().await?;
+        let paused_services = partition_store.get_paused_services().await?;
+        let kafka_sink_routes = partition_store.get_kafka_sink_routes().await?;
 
         let state_machine = StateMachine::new(
             inbox_seq_number,
             outbox_seq_number,
             outbox_head_seq_number,
             partition_key_range,
+            paused_services,
+            kafka_sink_routes,
             disable_idempotency_table,
         );
 
@@ -227,7 +254,11 @@ pub struct PartitionProcessor<Codec, InvokerSender, T> {
     status: PartitionProcessorStatus,
     max_command_batch_size: usize,
     partition_store: PartitionStore,
-    inflight_create_snapshot_task: Option<TaskHandle<anyhow::Result<()>>>,
+    snapshot_repository: Option<SnapshotRepository>,
+    inflight_create_snapshot_task: Option<TaskHandle<Option<PartitionSnapshotMetadata>>>,
+    last_snapshot_attempt_at: Instant,
+    last_snapshot_attempt_lsn: Lsn,
+    last_snapshot_created_at: Option<SystemTime>,
 }
 
 impl<Codec, InvokerSender, T> PartitionProcessor<Codec, InvokerSender, T>
@@ -313,11 +344,20 @@ where
             .map_ok(|entry| {
                 trace!(?entry, "Read entry");
                 let lsn = entry.sequence_number();
-                let Some(envelope) = entry.try_decode_arc::<Envelope>() else {
-                    // trim-gap
-                    unimplemented!("Handling trim gap is currently not supported")
+                let trim_gap_to = entry.trim_gap_to_sequence_number();
+                let Some(record) = entry.into_record() else {
+                    // trim-gap, the partition store needs to be restored from a snapshot
+                    anyhow::bail!(
+                        "Encountered a trim gap in the log from lsn={} to lsn={:?}, the partition store has to be restored from a snapshot",
+                        lsn,
+                        trim_gap_to
+                    );
                 };
-                anyhow::Ok((lsn, envelope?))
+                anyhow::Ok((
+                    lsn,
+                    MillisSinceEpoch::from(record.created_at()),
+                    record.decode_arc::<Envelope>()?,
+                ))
             })
             .try_take_while(|entry| {
                 // a catch-all safety net if all lower layers didn't filter this record out. This
@@ -325,9 +365,11 @@ where
                 //
                 // At some point, we should remove this and trust that stored records have Keys
                 // stored correctly.
+                //
+                // Errors are passed through to stop the partition processor.
                 std::future::ready(Ok(entry
                     .as_ref()
-                    .is_ok_and(|(_, envelope)| envelope.matches_key_query(&key_query))))
+                    .map_or(true, |(_, _, envelope)| envelope.matches_key_query(&key_query))))
             });
 
         info!("PartitionProcessor starting up.");
@@ -358,7 +400,26 @@ where
                         warn!("Failed executing command: {err}");
                     }
                 }
+                Some(result) = OptionFuture::from(self.inflight_create_snapshot_task.as_mut()) => {
+                    self.inflight_create_snapshot_task = None;
+                    if let Ok(Some(metadata)) = result {
+                        self.status.last_archived_log_lsn = Some(metadata.min_applied_lsn);
+                        self.last_snapshot_created_at = Some(metadata.created_at.into());
+                        let snapshot_size: usize = metadata.files.iter().map(|file| file.size).sum();
+                        gauge!(PARTITION_LAST_SNAPSHOT_SIZE, PARTITION_LABEL => partition_id_str)
+                            .set(snapshot_size as f64);
+                    }
+                }
                 _ = status_update_timer.tick() => {
+                    if self.should_create_snapshot() {
+                        if let Err(err) = self.create_snapshot(None) {
+                            warn!("Failed to create periodic partition snapshot: {err}");
+                        }
+                    }
+                    if let Some(created_at) = self.last_snapshot_created_at {
+                        gauge!(PARTITION_TIME_SINCE_LAST_SNAPSHOT, PARTITION_LABEL => partition_id_str)
+                            .set(created_at.elapsed().unwrap_or_default().as_secs_f64());
+                    }
                     self.status_watch_tx.send_modify(|old| {
                         old.clone_from(&self.status);
                         old.updated_at = MillisSinceEpoch::now();
@@ -375,13 +436,14 @@ where
                     // clear buffers used when applying the next record
                     action_collector.clear();
 
-                    for (lsn, envelope) in command_buffer.drain(..) {
+                    for (lsn, created_at, envelope) in command_buffer.drain(..) {
                         let command_start = Instant::now();
 
                         trace!(%lsn, "Processing bifrost record for '{}': {:?}", envelope.command.name(), envelope.header);
 
                         let leadership_change = self.apply_record(
                             lsn,
+                            created_at,
                             envelope,
                             &mut transaction,
                             &mut action_collector).await?;
@@ -465,49 +527,97 @@ where
                 self.status.effective_mode = RunMode::Follower;
             }
             PartitionProcessorControlCommand::CreateSnapshot(maybe_sender) => {
-                if self
-                    .inflight_create_snapshot_task
-                    .as_ref()
-                    .is_some_and(|task| !task.is_finished())
-                {
-                    warn!("Snapshot creation already in progress, rejecting request");
-                    maybe_sender
-                        .and_then(|tx| tx.send(Err(anyhow!("Snapshot creation in progress"))).ok());
-                    return Ok(());
-                }
+                self.create_snapshot(maybe_sender)?;
+            }
+        }
 
-                let config = self.configuration.live_load();
-                let snapshot_source = SnapshotSource {
-                    cluster_name: config.common.cluster_name().into(),
-                    node_name: config.common.node_name().into(),
-                };
-                let snapshot_base_path = config.worker.snapshots.snapshots_dir(self.partition_id);
-                let partition_store = self.partition_store.clone();
-                let snapshot_span = tracing::info_span!("create-snapshot");
-                let inflight_create_snapshot_task = restate_core::task_center().spawn_unmanaged(
-                    TaskKind::PartitionSnapshotProducer,
-                    "create-snapshot",
-                    Some(self.partition_id),
-                    async move {
-                        let result = SnapshotProducer::create(
-                            snapshot_source,
-                            partition_store,
-                            snapshot_base_path,
-                        )
-                        .await;
-
-                        if let Some(tx) = maybe_sender {
-                            tx.send(result.map(|metadata| metadata.snapshot_id)).ok();
-                        }
-                        Ok(())
+        Ok(())
+    }
+
+    /// Returns true if the leader should create a periodic snapshot, see [`is_snapshot_due`].
+    fn should_create_snapshot(&mut self) -> bool {
+        self.inflight_create_snapshot_task.is_none()
+            && is_snapshot_due(
+                &self.status,
+                &self.configuration.live_load().worker.snapshots,
+                self.last_snapshot_attempt_lsn,
+                self.last_snapshot_attempt_at.elapsed(),
+            )
+    }
+
+    fn create_snapshot(
+        &mut self,
+        maybe_sender: Option<oneshot::Sender<anyhow::Result<SnapshotId>>>,
+    ) -> anyhow::Result<()> {
+        if self
+            .inflight_create_snapshot_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
+            warn!("Snapshot creation already in progress, rejecting request");
+            maybe_sender.and_then(|tx| tx.send(Err(anyhow!("Snapshot creation in progress"))).ok());
+            return Ok(());
+        }
+
+        let config = self.configuration.live_load();
+        let snapshot_source = SnapshotSource {
+            cluster_name: config.common.cluster_name().into(),
+            node_name: config.common.node_name().into(),
+        };
+        let snapshot_base_path = config.worker.snapshots.snapshots_dir(self.partition_id);
+        let retained_snapshots = config.worker.snapshots.retained_snapshots;
+        let partition_store = self.partition_store.clone();
+        let snapshot_repository = self.snapshot_repository.clone();
+        let partition_id = self.partition_id;
+        let snapshot_span = tracing::info_span!("create-snapshot");
+        let inflight_create_snapshot_task = restate_core::task_center().spawn_unmanaged(
+            TaskKind::PartitionSnapshotProducer,
+            "create-snapshot",
+            Some(self.partition_id),
+            async move {
+                let result = async {
+                    let metadata = SnapshotProducer::create(
+                        snapshot_source,
+                        partition_store,
+                        snapshot_base_path.clone(),
+                    )
+                    .await?;
+
+                    let prune_result = if let Some(snapshot_repository) = snapshot_repository {
+                        let snapshot_dir =
+                            snapshot_base_path.join(metadata.snapshot_id.to_string());
+                        snapshot_repository.put(&metadata, &snapshot_dir).await?;
+                        // the repository is the source of truth for snapshots from now on
+                        tokio::fs::remove_dir_all(&snapshot_dir).await?;
+
+                        snapshot_repository
+                            .prune(partition_id, retained_snapshots)
+                            .await
+                    } else {
+                        prune_local_snapshots(partition_id, &snapshot_base_path, retained_snapshots)
+                            .await
+                    };
+                    if let Err(err) = prune_result {
+                        warn!(%err, "Failed to prune old partition snapshots");
                     }
-                    .instrument(snapshot_span),
-                )?;
 
-                self.inflight_create_snapshot_task
-                    .replace(inflight_create_snapshot_task);
+                    anyhow::Ok(metadata)
+                }
+                .await;
+                let metadata = result.as_ref().ok().cloned();
+
+                if let Some(tx) = maybe_sender {
+                    tx.send(result.map(|metadata| metadata.snapshot_id)).ok();
+                }
+                metadata
             }
-        }
+            .instrument(snapshot_span),
+        )?;
+
+        self.inflight_create_snapshot_task
+            .replace(inflight_create_snapshot_task);
+        self.last_snapshot_attempt_at = Instant::now();
+        self.last_snapshot_attempt_lsn = self.status.last_applied_log_lsn.unwrap_or(Lsn::INVALID);
 
         Ok(())
     }
@@ -515,6 +625,7 @@ where
     async fn apply_record<'a, 'b: 'a>(
         &mut self,
         lsn: Lsn,
+        created_at: MillisSinceEpoch,
         envelope: Arc<Envelope>,
         transaction: &mut PartitionStoreTransaction<'b>,
         action_collector: &mut ActionCollector,
@@ -565,6 +676,7 @@ where
                 self.state_machine
                     .apply(
                         envelope.command,
+                        created_at,
                         transaction,
                         action_collector,
                         self.leadership_state.is_leader(),
@@ -619,11 +731,15 @@ where
     async fn read_commands<S>(
         log_reader: &mut S,
         max_batching_size: usize,
-        record_buffer: &mut Vec<(Lsn, Arc<Envelope>)>,
+        record_buffer: &mut Vec<(Lsn, MillisSinceEpoch, Arc<Envelope>)>,
     ) -> anyhow::Result<()>
     where
-        S: Stream<Item = Result<anyhow::Result<(Lsn, Arc<Envelope>)>, restate_bifrost::Error>>
-            + Unpin,
+        S: Stream<
+                Item = Result<
+                    anyhow::Result<(Lsn, MillisSinceEpoch, Arc<Envelope>)>,
+                    restate_bifrost::Error,
+                >,
+            > + Unpin,
     {
         // beyond this point we must not await; otherwise we are no longer cancellation safe
         let first_record = log_reader.next().await;
@@ -654,3 +770,253 @@ where
         Ok(())
     }
 }
+
+/// Returns true if a periodic snapshot is due. Snapshots are only created by the leader if records
+/// have been applied since the last snapshot, and once either the configured number of records
+/// or the configured interval has passed since the last attempt.
+fn is_snapshot_due(
+    status: &PartitionProcessorStatus,
+    options: &SnapshotsOptions,
+    last_snapshot_attempt_lsn: Lsn,
+    elapsed_since_last_attempt: Duration,
+) -> bool {
+    if status.effective_mode != RunMode::Leader {
+        return false;
+    }
+
+    let last_applied_lsn = status.last_applied_log_lsn.unwrap_or(Lsn::INVALID);
+    let last_archived_lsn = status.last_archived_log_lsn.unwrap_or(Lsn::INVALID);
+    if last_applied_lsn <= last_archived_lsn {
+        return false;
+    }
+
+    let records_since_last_attempt = last_applied_lsn
+        .as_u64()
+        .saturating_sub(last_snapshot_attempt_lsn.max(last_archived_lsn).as_u64());
+
+    options
+        .snapshot_interval_num_records
+        .is_some_and(|num_records| records_since_last_attempt >= num_records.get())
+        || options
+            .snapshot_interval
+            .as_ref()
+            .is_some_and(|interval| elapsed_since_last_attempt >= **interval)
+}
+
+/// Deletes all but the `retained_snapshots` most recent local snapshots of the partition.
+async fn prune_local_snapshots(
+    partition_id: PartitionId,
+    partition_snapshots_path: &Path,
+    retained_snapshots: NonZeroUsize,
+) -> anyhow::Result<()> {
+    let snapshots = LocalPartitionSnapshot::list(partition_id, partition_snapshots_path).await?;
+    for snapshot in snapshots.iter().rev().skip(retained_snapshots.get()) {
+        debug!(path = ?snapshot.base_dir, "Deleting partition snapshot");
+        tokio::fs::remove_dir_all(&snapshot.base_dir).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use test_log::test;
+
+    use restate_partition_store::snapshots::{SnapshotFormatVersion, SNAPSHOT_METADATA_FILE_NAME};
+
+    use super::*;
+
+    fn leader_status(
+        last_applied_lsn: u64,
+        last_archived_lsn: Option<u64>,
+    ) -> PartitionProcessorStatus {
+        PartitionProcessorStatus {
+            effective_mode: RunMode::Leader,
+            last_applied_log_lsn: Some(Lsn::new(last_applied_lsn)),
+            last_archived_log_lsn: last_archived_lsn.map(Lsn::new),
+            ..PartitionProcessorStatus::default()
+        }
+    }
+
+    #[test]
+    fn snapshot_is_due_after_number_of_records() {
+        let options = SnapshotsOptions {
+            snapshot_interval_num_records: NonZeroU64::new(100),
+            ..SnapshotsOptions::default()
+        };
+        let elapsed = Duration::from_secs(3600);
+
+        assert!(!is_snapshot_due(
+            &leader_status(99, None),
+            &options,
+            Lsn::INVALID,
+            elapsed
+        ));
+        assert!(is_snapshot_due(
+            &leader_status(100, None),
+            &options,
+            Lsn::INVALID,
+            elapsed
+        ));
+        // the records are counted from the last attempt or the last snapshot, whichever is later
+        assert!(!is_snapshot_due(
+            &leader_status(150, None),
+            &options,
+            Lsn::new(100),
+            elapsed
+        ));
+        assert!(!is_snapshot_due(
+            &leader_status(150, Some(100)),
+            &options,
+            Lsn::INVALID,
+            elapsed
+        ));
+        assert!(is_snapshot_due(
+            &leader_status(200, Some(100)),
+            &options,
+            Lsn::new(50),
+            elapsed
+        ));
+    }
+
+    #[test]
+    fn snapshot_is_due_after_interval() {
+        let options = SnapshotsOptions {
+            snapshot_interval: Some(Duration::from_secs(60).into()),
+            ..SnapshotsOptions::default()
+        };
+        let status = leader_status(10, Some(5));
+
+        assert!(!is_snapshot_due(
+            &status,
+            &options,
+            Lsn::new(5),
+            Duration::from_secs(59)
+        ));
+        assert!(is_snapshot_due(
+            &status,
+            &options,
+            Lsn::new(5),
+            Duration::from_secs(60)
+        ));
+        // nothing to snapshot if no records have been applied since the last snapshot
+        assert!(!is_snapshot_due(
+            &leader_status(5, Some(5)),
+            &options,
+            Lsn::new(5),
+            Duration::from_secs(60)
+        ));
+    }
+
+    #[test]
+    fn snapshot_is_only_due_on_leader() {
+        let options = SnapshotsOptions {
+            snapshot_interval: Some(Duration::from_secs(60).into()),
+            snapshot_interval_num_records: NonZeroU64::new(1),
+            ..SnapshotsOptions::default()
+        };
+        let elapsed = Duration::from_secs(3600);
+        let status = leader_status(100, None);
+        assert!(is_snapshot_due(&status, &options, Lsn::INVALID, elapsed));
+
+        let status = PartitionProcessorStatus {
+            effective_mode: RunMode::Follower,
+            ..status
+        };
+        assert!(!is_snapshot_due(&status, &options, Lsn::INVALID, elapsed));
+    }
+
+    #[test]
+    fn snapshot_is_not_due_without_configured_interval() {
+        let elapsed = Duration::from_secs(3600);
+        assert!(!is_snapshot_due(
+            &leader_status(100, None),
+            &SnapshotsOptions::default(),
+            Lsn::INVALID,
+            elapsed
+        ));
+    }
+
+    async fn write_local_snapshot(
+        partition_snapshots_path: &Path,
+        partition_id: PartitionId,
+        lsn: u64,
+    ) -> anyhow::Result<()> {
+        let snapshot_id = SnapshotId::new();
+        let snapshot_dir = partition_snapshots_path.join(snapshot_id.to_string());
+        tokio::fs::create_dir_all(&snapshot_dir).await?;
+        let metadata = PartitionSnapshotMetadata {
+            version: SnapshotFormatVersion::V1,
+            cluster_name: "test-cluster".to_owned(),
+            node_name: "test-node".to_owned(),
+            partition_id,
+            created_at: humantime::Timestamp::from(SystemTime::now()),
+            snapshot_id,
+            key_range: 0..=PartitionKey::MAX,
+            min_applied_lsn: Lsn::new(lsn),
+            db_comparator_name: "leveldb.BytewiseComparator".to_owned(),
+            files: vec![],
+        };
+        tokio::fs::write(
+            snapshot_dir.join(SNAPSHOT_METADATA_FILE_NAME),
+            serde_json::to_vec_pretty(&metadata)?,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn local_snapshot_lsns(
+        partition_snapshots_path: &Path,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Vec<Lsn>> {
+        Ok(
+            LocalPartitionSnapshot::list(partition_id, partition_snapshots_path)
+                .await?
+                .into_iter()
+                .map(|snapshot| snapshot.min_applied_lsn)
+                .collect(),
+        )
+    }
+
+    #[test(tokio::test)]
+    async fn prune_local_snapshots_retains_most_recent_ones() -> anyhow::Result<()> {
+        let partition_snapshots_dir = tempfile::tempdir()?;
+        let partition_id = PartitionId::from(1);
+        let other_partition_id = PartitionId::from(2);
+        for lsn in [10, 40, 30, 20] {
+            write_local_snapshot(partition_snapshots_dir.path(), partition_id, lsn).await?;
+        }
+        write_local_snapshot(partition_snapshots_dir.path(), other_partition_id, 5).await?;
+
+        let retained_snapshots = NonZeroUsize::new(2).expect("non zero");
+        prune_local_snapshots(
+            partition_id,
+            partition_snapshots_dir.path(),
+            retained_snapshots,
+        )
+        .await?;
+        assert_eq!(
+            vec![Lsn::new(30), Lsn::new(40)],
+            local_snapshot_lsns(partition_snapshots_dir.path(), partition_id).await?
+        );
+        assert_eq!(
+            vec![Lsn::new(5)],
+            local_snapshot_lsns(partition_snapshots_dir.path(), other_partition_id).await?
+        );
+
+        // pruning is idempotent
+        prune_local_snapshots(
+            partition_id,
+            partition_snapshots_dir.path(),
+            retained_snapshots,
+        )
+        .await?;
+        assert_eq!(
+            vec![Lsn::new(30), Lsn::new(40)],
+            local_snapshot_lsns(partition_snapshots_dir.path(), partition_id).await?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeSet;
+
+use anyhow::Context;
+use bytestring::ByteString;
+use tracing::debug;
+
+use restate_core::metadata;
+use restate_storage_api::fsm_table::ReadOnlyFsmTable;
+use restate_types::identifiers::PartitionKey;
+use restate_types::invocation::ServicePause;
+use restate_types::schema::service::ServiceMetadataResolver;
+use restate_wal_protocol::Command;
+
+use crate::partition::schema_reconciler::SchemaDiff;
+
+/// Paused services of a partition. The admin service tells every partition about a paused or
+/// resumed service.
+pub(super) struct PausedServicesDiff<Storage> {
+    partition_key: PartitionKey,
+    storage: Storage,
+}
+
+impl<Storage> PausedServicesDiff<Storage> {
+    pub(super) fn new(partition_key: PartitionKey, storage: Storage) -> Self {
+        Self {
+            partition_key,
+            storage,
+        }
+    }
+}
+
+impl<Storage> SchemaDiff for PausedServicesDiff<Storage>
+where
+    Storage: ReadOnlyFsmTable + Send + Sync + 'static,
+{
+    const NAME: &'static str = "paused services";
+
+    async fn diff(&mut self) -> anyhow::Result<Vec<(PartitionKey, Command)>> {
+        let schema_paused_services: BTreeSet<ByteString> = metadata()
+            .schema()
+            .list_services()
+            .into_iter()
+            .filter(|service| service.paused)
+            .map(|service| ByteString::from(service.name))
+            .collect();
+        let partition_paused_services = self
+            .storage
+            .get_paused_services()
+            .await
+            .context("Cannot read the paused services")?
+            .into_inner();
+
+        // Services which no longer exist in the schema are resumed as well, to not keep their
+        // invocations held forever.
+        let service_pauses = schema_paused_services
+            .difference(&partition_paused_services)
+            .cloned()
+            .map(ServicePause::pause)
+            .chain(
+                partition_paused_services
+                    .difference(&schema_paused_services)
+                    .cloned()
+                    .map(ServicePause::resume),
+            );
+
+        Ok(service_pauses
+            .map(|service_pause| {
+                debug!(
+                    rpc.service = %service_pause.service_name,
+                    "Reconciling paused service: {:?}",
+                    service_pause.flavor
+                );
+                (self.partition_key, Command::PauseService(service_pause))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use anyhow::Context;
+use futures::StreamExt;
+use tracing::debug;
+
+use restate_core::metadata;
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus};
+use restate_types::identifiers::{PartitionKey, WithPartitionKey};
+use restate_types::schema::schedules::{ScheduleRequest, ScheduleResolver};
+use restate_types::time::MillisSinceEpoch;
+use restate_wal_protocol::Command;
+
+use crate::partition::schema_reconciler::SchemaDiff;
+
+/// Schedules owned by a partition. The admin service tells the owning partition about a created
+/// or deleted schedule.
+pub(super) struct SchedulesDiff<Storage> {
+    partition_key_range: RangeInclusive<PartitionKey>,
+    storage: Storage,
+}
+
+impl<Storage> SchedulesDiff<Storage> {
+    pub(super) fn new(partition_key_range: RangeInclusive<PartitionKey>, storage: Storage) -> Self {
+        Self {
+            partition_key_range,
+            storage,
+        }
+    }
+}
+
+impl<Storage> SchemaDiff for SchedulesDiff<Storage>
+where
+    Storage: ReadOnlyScheduleTable + Send + Sync + 'static,
+{
+    const NAME: &'static str = "schedules";
+
+    async fn diff(&mut self) -> anyhow::Result<Vec<(PartitionKey, Command)>> {
+        let mut partition_schedules: HashMap<String, ScheduleStatus> = HashMap::new();
+        {
+            let schedule_statuses = self
+                .storage
+                .all_schedule_statuses(self.partition_key_range.clone());
+            tokio::pin!(schedule_statuses);
+
+            while let Some((_, schedule_status)) = schedule_statuses
+                .next()
+                .await
+                .transpose()
+                .context("Cannot read the next item of the schedule table")?
+            {
+                partition_schedules
+                    .insert(schedule_status.schedule.id().to_owned(), schedule_status);
+            }
+        }
+
+        let now = MillisSinceEpoch::now();
+        let mut schedule_requests = Vec::new();
+        for schedule in metadata().schema().list_schedules() {
+            if !self.partition_key_range.contains(&schedule.partition_key()) {
+                continue;
+            }
+
+            if partition_schedules
+                .remove(schedule.id())
+                .is_some_and(|schedule_status| schedule_status.schedule == schedule)
+            {
+                continue;
+            }
+
+            // Cron expressions without further occurrences never need to be fired
+            if let Some(first_fire_time) = schedule.next_fire_time(now) {
+                schedule_requests.push(ScheduleRequest::create(schedule, first_fire_time));
+            }
+        }
+
+        // Whatever is left has been deleted from the schema
+        schedule_requests.extend(
+            partition_schedules
+                .values()
+                .map(|schedule_status| ScheduleRequest::delete(&schedule_status.schedule)),
+        );
+
+        Ok(schedule_requests
+            .into_iter()
+            .map(|schedule_request| {
+                debug!(
+                    "Reconciling schedule '{}': {:?}",
+                    schedule_request.schedule_id, schedule_request.flavor
+                );
+                (
+                    schedule_request.partition_key,
+                    Command::Schedule(schedule_request),
+                )
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, instrument, warn};
+
+use restate_bifrost::Bifrost;
+use restate_core::{cancellation_watcher, metadata, MetadataKind};
+use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey};
+use restate_types::GenerationalNodeId;
+use restate_wal_protocol::{
+    append_envelope_to_bifrost, Command, Destination, Envelope, Header, Source,
+};
+
+/// Interval in which the partition state is reconciled even if the schema did not change.
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Compares partition state which mirrors the schema metadata, e.g. the paused services, with
+/// the schema.
+pub(super) trait SchemaDiff: Send + 'static {
+    /// Name of the reconciled state, used for logging.
+    const NAME: &'static str;
+
+    /// Returns the commands which bring the partition state in line with the schema, together
+    /// with the partition key to which each command is addressed.
+    fn diff(&mut self)
+        -> impl Future<Output = anyhow::Result<Vec<(PartitionKey, Command)>>> + Send;
+}
+
+/// Brings partition state which mirrors the schema metadata in line with the schema, which is
+/// the source of truth. The admin service tells the partitions about schema changes right away,
+/// but it can fail to do so after the schema has been updated. The reconciler therefore
+/// proposes the missing changes whenever the schema changes, and periodically.
+pub(super) struct SchemaReconciler<Diff> {
+    partition_id: PartitionId,
+    leader_epoch: LeaderEpoch,
+    node_id: GenerationalNodeId,
+    diff: Diff,
+    bifrost: Bifrost,
+}
+
+impl<Diff> SchemaReconciler<Diff>
+where
+    Diff: SchemaDiff,
+{
+    pub(super) fn new(
+        partition_id: PartitionId,
+        leader_epoch: LeaderEpoch,
+        node_id: GenerationalNodeId,
+        diff: Diff,
+        bifrost: Bifrost,
+    ) -> Self {
+        Self {
+            partition_id,
+            leader_epoch,
+            node_id,
+            diff,
+            bifrost,
+        }
+    }
+
+    #[instrument(skip_all, fields(restate.node = %self.node_id, restate.partition.id = %self.partition_id))]
+    pub(super) async fn run(mut self) -> anyhow::Result<()> {
+        debug!("Running {} reconciler", Diff::NAME);
+
+        let mut schema_watch = metadata().watch(MetadataKind::Schema);
+        let mut interval = tokio::time::interval(RECONCILE_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                result = schema_watch.changed() => {
+                    if result.is_err() {
+                        break;
+                    }
+                },
+                _ = cancellation_watcher() => {
+                    break;
+                }
+            }
+
+            if let Err(e) = self.reconcile().await {
+                warn!("Error when trying to reconcile the {}: {e:?}", Diff::NAME);
+            }
+        }
+
+        debug!("Stopping {} reconciler", Diff::NAME);
+
+        Ok(())
+    }
+
+    async fn reconcile(&mut self) -> anyhow::Result<()> {
+        for (partition_key, command) in self.diff.diff().await? {
+            self.append_command(partition_key, command).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn append_command(
+        &self,
+        partition_key: PartitionKey,
+        command: Command,
+    ) -> anyhow::Result<()> {
+        append_envelope_to_bifrost(
+            &self.bifrost,
+            Arc::new(Envelope {
+                header: Header {
+                    source: Source::Processor {
+                        partition_id: self.partition_id,
+                        partition_key: None,
+                        leader_epoch: self.leader_epoch,
+                        node_id: self.node_id.as_plain(),
+                        generational_node_id: Some(self.node_id),
+                    },
+                    dest: Destination::Processor {
+                        partition_key,
+                        dedup: None,
+                    },
+                },
+                command,
+            }),
+        )
+        .await
+        .context("Cannot append to bifrost")?;
+
+        Ok(())
+    }
+}
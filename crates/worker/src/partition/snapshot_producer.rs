@@ -14,7 +14,9 @@ use std::time::SystemTime;
 use anyhow::bail;
 use tracing::{info, warn};
 
-use restate_partition_store::snapshots::{PartitionSnapshotMetadata, SnapshotFormatVersion};
+use restate_partition_store::snapshots::{
+    PartitionSnapshotMetadata, SnapshotFormatVersion, SNAPSHOT_METADATA_FILE_NAME,
+};
 use restate_partition_store::PartitionStore;
 use restate_types::identifiers::SnapshotId;
 
@@ -61,7 +63,7 @@ impl SnapshotProducer {
         };
         let metadata_json = serde_json::to_string_pretty(&snapshot_meta)?;
 
-        let metadata_path = snapshot_path.join("metadata.json");
+        let metadata_path = snapshot_path.join(SNAPSHOT_METADATA_FILE_NAME);
         tokio::fs::write(metadata_path.clone(), metadata_json).await?;
         info!(
             lsn = %snapshot.min_applied_lsn,
@@ -0,0 +1,435 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use futures::TryStreamExt;
+use object_store::aws::AmazonS3Builder;
+use object_store::buf::BufWriter;
+use object_store::local::LocalFileSystem;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutPayload};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info};
+use url::Url;
+
+use restate_partition_store::snapshots::{
+    LocalPartitionSnapshot, PartitionSnapshotMetadata, SNAPSHOT_METADATA_FILE_NAME,
+};
+use restate_types::config::SnapshotsOptions;
+use restate_types::identifiers::PartitionId;
+
+/// Repository of partition snapshots which is shared by all nodes of the cluster. It is backed
+/// by an object store, which can either be an S3-compatible object store or a shared file system.
+///
+/// Every snapshot is stored under `<prefix>/<partition_id>/<lsn>_<snapshot_id>/` next to its
+/// metadata file. The applied lsn is zero-padded so that the snapshots of a partition are ordered
+/// by it. The metadata file is uploaded last and deleted first, which makes sure that only
+/// complete snapshots are visible to readers.
+#[derive(Debug, Clone)]
+pub struct SnapshotRepository {
+    object_store: Arc<dyn ObjectStore>,
+    prefix: ObjectPath,
+}
+
+/// Objects of a single snapshot in the repository.
+#[derive(Default)]
+struct SnapshotObjects {
+    metadata: Option<ObjectPath>,
+    files: Vec<ObjectPath>,
+}
+
+impl SnapshotRepository {
+    /// Creates the snapshot repository for the configured destination. Returns `None` if no
+    /// destination is configured.
+    pub fn create_if_configured(options: &SnapshotsOptions) -> anyhow::Result<Option<Self>> {
+        let Some(destination) = &options.destination else {
+            return Ok(None);
+        };
+        let url = Url::parse(destination)
+            .with_context(|| format!("invalid snapshot repository destination '{destination}'"))?;
+
+        let (object_store, prefix): (Arc<dyn ObjectStore>, ObjectPath) = match url.scheme() {
+            "s3" => {
+                let object_store = AmazonS3Builder::from_env()
+                    .with_url(destination.as_str())
+                    .build()?;
+                (Arc::new(object_store), ObjectPath::parse(url.path())?)
+            }
+            "file" => {
+                let path = url
+                    .to_file_path()
+                    .map_err(|_| anyhow!("invalid snapshot repository path '{destination}'"))?;
+                std::fs::create_dir_all(&path).with_context(|| {
+                    format!("failed creating snapshot repository directory {path:?}")
+                })?;
+                (
+                    Arc::new(LocalFileSystem::new_with_prefix(path)?),
+                    ObjectPath::default(),
+                )
+            }
+            scheme => bail!(
+                "unsupported snapshot repository scheme '{scheme}', expected either 's3' or 'file'"
+            ),
+        };
+
+        info!(%destination, "Using snapshot repository");
+        Ok(Some(Self {
+            object_store,
+            prefix,
+        }))
+    }
+
+    /// Uploads the snapshot which has been written to `local_snapshot_dir` to the repository.
+    pub async fn put(
+        &self,
+        metadata: &PartitionSnapshotMetadata,
+        local_snapshot_dir: &Path,
+    ) -> anyhow::Result<()> {
+        let snapshot_prefix = self.snapshot_prefix(metadata);
+
+        for file in &metadata.files {
+            let file_name = file.name.trim_start_matches('/');
+            let mut local_file = tokio::fs::File::open(local_snapshot_dir.join(file_name))
+                .await
+                .with_context(|| format!("failed opening snapshot file {file_name}"))?;
+            let mut writer = BufWriter::new(
+                Arc::clone(&self.object_store),
+                snapshot_prefix.child(file_name),
+            );
+            tokio::io::copy(&mut local_file, &mut writer)
+                .await
+                .with_context(|| format!("failed uploading snapshot file {file_name}"))?;
+            writer.shutdown().await?;
+        }
+
+        let metadata_json = serde_json::to_vec_pretty(metadata)?;
+        self.object_store
+            .put(
+                &snapshot_prefix.child(SNAPSHOT_METADATA_FILE_NAME),
+                PutPayload::from(metadata_json),
+            )
+            .await?;
+
+        info!(
+            snapshot_id = %metadata.snapshot_id,
+            lsn = %metadata.min_applied_lsn,
+            location = %snapshot_prefix,
+            "Partition snapshot uploaded"
+        );
+        Ok(())
+    }
+
+    /// Returns the metadata of the snapshot of the partition with the highest applied lsn.
+    pub async fn get_latest(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<Option<PartitionSnapshotMetadata>> {
+        let snapshots = self.list_snapshots(partition_id).await?;
+        let Some(metadata_path) = snapshots
+            .into_values()
+            .rev()
+            .find_map(|snapshot| snapshot.metadata)
+        else {
+            return Ok(None);
+        };
+
+        let metadata = self.object_store.get(&metadata_path).await?.bytes().await?;
+        let metadata = serde_json::from_slice(&metadata)
+            .with_context(|| format!("invalid snapshot metadata {metadata_path}"))?;
+        Ok(Some(metadata))
+    }
+
+    /// Downloads the snapshot files into `target_dir`, from where the partition store can be
+    /// restored.
+    pub async fn get(
+        &self,
+        metadata: PartitionSnapshotMetadata,
+        target_dir: PathBuf,
+    ) -> anyhow::Result<LocalPartitionSnapshot> {
+        let snapshot_prefix = self.snapshot_prefix(&metadata);
+
+        // remove the leftovers of a previously interrupted download
+        if tokio::fs::try_exists(&target_dir).await? {
+            tokio::fs::remove_dir_all(&target_dir).await?;
+        }
+        tokio::fs::create_dir_all(&target_dir).await?;
+
+        for file in &metadata.files {
+            let file_name = file.name.trim_start_matches('/');
+            let mut stream = self
+                .object_store
+                .get(&snapshot_prefix.child(file_name))
+                .await
+                .with_context(|| format!("failed downloading snapshot file {file_name}"))?
+                .into_stream();
+            let mut local_file = tokio::fs::File::create(target_dir.join(file_name)).await?;
+            while let Some(chunk) = stream.try_next().await? {
+                local_file.write_all(&chunk).await?;
+            }
+            local_file.sync_all().await?;
+        }
+
+        info!(
+            snapshot_id = %metadata.snapshot_id,
+            lsn = %metadata.min_applied_lsn,
+            location = %snapshot_prefix,
+            "Partition snapshot downloaded"
+        );
+        Ok(LocalPartitionSnapshot::from_metadata(metadata, target_dir))
+    }
+
+    /// Deletes all but the `retained_snapshots` most recent snapshots of the partition. Incomplete
+    /// snapshots which are older than the retained ones are deleted as well.
+    pub async fn prune(
+        &self,
+        partition_id: PartitionId,
+        retained_snapshots: NonZeroUsize,
+    ) -> anyhow::Result<()> {
+        let snapshots = self.list_snapshots(partition_id).await?;
+        let Some(oldest_retained) = snapshots
+            .iter()
+            .rev()
+            .filter(|(_, snapshot)| snapshot.metadata.is_some())
+            .nth(retained_snapshots.get() - 1)
+            .map(|(name, _)| name.clone())
+        else {
+            return Ok(());
+        };
+
+        for (name, snapshot) in snapshots.range(..oldest_retained) {
+            debug!(%partition_id, snapshot = %name, "Deleting partition snapshot");
+            for location in snapshot.metadata.iter().chain(snapshot.files.iter()) {
+                self.object_store.delete(location).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the snapshots of the partition, ordered by their applied lsn.
+    async fn list_snapshots(
+        &self,
+        partition_id: PartitionId,
+    ) -> anyhow::Result<BTreeMap<String, SnapshotObjects>> {
+        let partition_prefix = self.prefix.child(partition_id.to_string());
+        let mut objects = self.object_store.list(Some(&partition_prefix));
+
+        let mut snapshots: BTreeMap<String, SnapshotObjects> = BTreeMap::new();
+        while let Some(object) = objects.try_next().await? {
+            let (snapshot_name, is_metadata) = {
+                let Some(mut parts) = object.location.prefix_match(&partition_prefix) else {
+                    continue;
+                };
+                let (Some(snapshot_name), Some(file_name)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+                (
+                    snapshot_name.as_ref().to_owned(),
+                    file_name.as_ref() == SNAPSHOT_METADATA_FILE_NAME,
+                )
+            };
+
+            let snapshot = snapshots.entry(snapshot_name).or_default();
+            if is_metadata {
+                snapshot.metadata = Some(object.location);
+            } else {
+                snapshot.files.push(object.location);
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    fn snapshot_prefix(&self, metadata: &PartitionSnapshotMetadata) -> ObjectPath {
+        self.prefix
+            .child(metadata.partition_id.to_string())
+            .child(format!(
+                "{:020}_{}",
+                metadata.min_applied_lsn.as_u64(),
+                metadata.snapshot_id
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use test_log::test;
+
+    use restate_core::TestCoreEnv;
+    use restate_partition_store::snapshots::SnapshotFormatVersion;
+    use restate_partition_store::{OpenMode, PartitionStoreManager};
+    use restate_rocksdb::RocksDbManager;
+    use restate_storage_api::fsm_table::FsmTable;
+    use restate_storage_api::Transaction;
+    use restate_types::config::{CommonOptions, RocksDbOptions, StorageOptions};
+    use restate_types::identifiers::{PartitionKey, SnapshotId};
+    use restate_types::live::Constant;
+    use restate_types::logs::Lsn;
+
+    use super::*;
+    use crate::partition::snapshot_producer::{SnapshotProducer, SnapshotSource};
+
+    fn snapshot_metadata(partition_id: PartitionId, lsn: u64) -> PartitionSnapshotMetadata {
+        PartitionSnapshotMetadata {
+            version: SnapshotFormatVersion::V1,
+            cluster_name: "test-cluster".to_owned(),
+            node_name: "test-node".to_owned(),
+            partition_id,
+            created_at: humantime::Timestamp::from(SystemTime::now()),
+            snapshot_id: SnapshotId::new(),
+            key_range: 0..=PartitionKey::MAX,
+            min_applied_lsn: Lsn::new(lsn),
+            db_comparator_name: "leveldb.BytewiseComparator".to_owned(),
+            files: vec![],
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn put_get_latest_and_prune_snapshots() -> anyhow::Result<()> {
+        let repository_dir = tempfile::tempdir()?;
+        let local_snapshot_dir = tempfile::tempdir()?;
+        let options = SnapshotsOptions {
+            destination: Some(
+                Url::from_directory_path(repository_dir.path())
+                    .expect("absolute path")
+                    .to_string(),
+            ),
+            retained_snapshots: NonZeroUsize::new(2).expect("non zero"),
+            ..SnapshotsOptions::default()
+        };
+        let repository =
+            SnapshotRepository::create_if_configured(&options)?.expect("destination is set");
+
+        let partition_id = PartitionId::from(1);
+        for lsn in [10, 30, 20] {
+            repository
+                .put(
+                    &snapshot_metadata(partition_id, lsn),
+                    local_snapshot_dir.path(),
+                )
+                .await?;
+        }
+
+        let latest = repository.get_latest(partition_id).await?;
+        assert_eq!(
+            Some(Lsn::new(30)),
+            latest.map(|metadata| metadata.min_applied_lsn)
+        );
+        assert!(repository.get_latest(PartitionId::from(2)).await?.is_none());
+
+        repository
+            .prune(partition_id, options.retained_snapshots)
+            .await?;
+        let retained_lsns: Vec<_> = repository
+            .list_snapshots(partition_id)
+            .await?
+            .into_keys()
+            .map(|name| name.split_once('_').expect("lsn prefix").0.to_owned())
+            .collect();
+        assert_eq!(
+            vec![format!("{:020}", 20), format!("{:020}", 30)],
+            retained_lsns
+        );
+
+        Ok(())
+    }
+
+    #[test(tokio::test)]
+    async fn put_and_get_snapshot_with_sst_files() -> anyhow::Result<()> {
+        let node_env = TestCoreEnv::create_with_single_node(1, 1).await;
+        let rocksdb_options = RocksDbOptions::default();
+
+        node_env.tc.run_in_scope_sync("db-manager-init", None, || {
+            RocksDbManager::init(Constant::new(CommonOptions::default()))
+        });
+
+        let repository_dir = tempfile::tempdir()?;
+        let local_snapshots_dir = tempfile::tempdir()?;
+        let download_dir = tempfile::tempdir()?;
+        let repository = SnapshotRepository::create_if_configured(&SnapshotsOptions {
+            destination: Some(
+                Url::from_directory_path(repository_dir.path())
+                    .expect("absolute path")
+                    .to_string(),
+            ),
+            ..SnapshotsOptions::default()
+        })?
+        .expect("destination is set");
+
+        let partition_id = PartitionId::from(11);
+        let key_range = 0..=PartitionKey::MAX;
+        let partition_store_manager = PartitionStoreManager::create(
+            Constant::new(StorageOptions::default()).boxed(),
+            Constant::new(rocksdb_options.clone()).boxed(),
+            &[(partition_id, key_range.clone())],
+        )
+        .await?;
+        let mut partition_store = partition_store_manager
+            .open_partition_store(
+                partition_id,
+                key_range,
+                OpenMode::CreateIfMissing,
+                &rocksdb_options,
+            )
+            .await?;
+        let mut txn = partition_store.transaction();
+        txn.put_applied_lsn(Lsn::new(100)).await;
+        txn.commit().await?;
+
+        let metadata = SnapshotProducer::create(
+            SnapshotSource {
+                cluster_name: "test-cluster".to_owned(),
+                node_name: "test-node".to_owned(),
+            },
+            partition_store,
+            local_snapshots_dir.path().to_path_buf(),
+        )
+        .await?;
+        assert!(metadata
+            .files
+            .iter()
+            .any(|file| file.name.ends_with(".sst")));
+
+        let local_snapshot_dir = local_snapshots_dir
+            .path()
+            .join(metadata.snapshot_id.to_string());
+        repository.put(&metadata, &local_snapshot_dir).await?;
+
+        let latest = repository
+            .get_latest(partition_id)
+            .await?
+            .expect("snapshot exists");
+        assert_eq!(metadata.snapshot_id, latest.snapshot_id);
+
+        let snapshot = repository
+            .get(latest, download_dir.path().join("snapshot"))
+            .await?;
+        assert_eq!(Lsn::new(100), snapshot.min_applied_lsn);
+        assert_eq!(metadata.files.len(), snapshot.files.len());
+        for file in &snapshot.files {
+            let file_name = file.name.trim_start_matches('/');
+            let downloaded = tokio::fs::read(snapshot.base_dir.join(file_name)).await?;
+            assert!(!downloaded.is_empty());
+            assert_eq!(
+                tokio::fs::read(local_snapshot_dir.join(file_name)).await?,
+                downloaded
+            );
+        }
+
+        Ok(())
+    }
+}
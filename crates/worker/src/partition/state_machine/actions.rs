@@ -14,7 +14,7 @@ use restate_storage_api::timer_table::TimerKey;
 use restate_types::identifiers::{EntryIndex, InvocationId};
 use restate_types::ingress;
 use restate_types::ingress::IngressResponseEnvelope;
-use restate_types::invocation::InvocationTarget;
+use restate_types::invocation::{InvocationEpoch, InvocationTarget};
 use restate_types::journal::Completion;
 use restate_types::message::MessageIndex;
 use restate_wal_protocol::timer::TimerKeyValue;
@@ -26,6 +26,7 @@ pub type ActionCollector = Vec<Action>;
 pub enum Action {
     Invoke {
         invocation_id: InvocationId,
+        invocation_epoch: InvocationEpoch,
         invocation_target: InvocationTarget,
         invoke_input_journal: InvokeInputJournal,
     },
@@ -33,6 +34,8 @@ pub enum Action {
         seq_number: MessageIndex,
         message: OutboxMessage,
     },
+    /// A record has been added to the Kafka sink outbox.
+    NewKafkaSinkRecord,
     RegisterTimer {
         timer_value: TimerKeyValue,
     },
@@ -48,6 +51,7 @@ pub enum Action {
         completion: Completion,
     },
     AbortInvocation(InvocationId),
+    RetryInvocationNow(InvocationId),
     IngressResponse(IngressResponseEnvelope<ingress::InvocationResponse>),
     IngressSubmitNotification(IngressResponseEnvelope<ingress::SubmittedInvocationNotification>),
     ScheduleInvocationStatusCleanup {
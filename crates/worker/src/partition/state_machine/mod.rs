@@ -22,10 +22,13 @@ use futures::{StreamExt, TryStreamExt};
 use metrics::{histogram, Histogram};
 use restate_invoker_api::InvokeInputJournal;
 use restate_service_protocol::codec::ProtobufRawEntryCodec;
-use restate_storage_api::fsm_table::FsmTable;
+use restate_storage_api::dead_letter_table::{
+    DeadLetter, DeadLetterTable, ReadOnlyDeadLetterTable,
+};
+use restate_storage_api::fsm_table::{FsmTable, KafkaSinkRoutes, PausedServices};
 use restate_storage_api::idempotency_table::IdempotencyMetadata;
 use restate_storage_api::idempotency_table::{IdempotencyTable, ReadOnlyIdempotencyTable};
-use restate_storage_api::inbox_table::{InboxEntry, InboxTable};
+use restate_storage_api::inbox_table::{HeldInvocation, InboxEntry, InboxTable};
 use restate_storage_api::invocation_status_table::{
     CompletedInvocation, InFlightInvocationMetadata, InboxedInvocation, InvocationStatusTable,
     PreFlightInvocationMetadata, ReadOnlyInvocationStatusTable,
@@ -33,8 +36,10 @@ use restate_storage_api::invocation_status_table::{
 use restate_storage_api::invocation_status_table::{InvocationStatus, ScheduledInvocation};
 use restate_storage_api::journal_table::ReadOnlyJournalTable;
 use restate_storage_api::journal_table::{JournalEntry, JournalTable};
+use restate_storage_api::kafka_sink_outbox_table::{KafkaSinkOutboxTable, KafkaSinkRecord};
 use restate_storage_api::outbox_table::{OutboxMessage, OutboxTable};
 use restate_storage_api::promise_table::{Promise, PromiseState, PromiseTable};
+use restate_storage_api::schedule_table::{ScheduleStatus, ScheduleTable};
 use restate_storage_api::service_status_table::{
     ReadOnlyVirtualObjectStatusTable, VirtualObjectStatus, VirtualObjectStatusTable,
 };
@@ -56,12 +61,14 @@ use restate_types::identifiers::{
 use restate_types::ingress;
 use restate_types::ingress::{IngressResponseEnvelope, IngressResponseResult};
 use restate_types::invocation::{
-    AttachInvocationRequest, InvocationQuery, InvocationResponse, InvocationTarget,
-    InvocationTargetType, InvocationTermination, ResponseResult, ServiceInvocation,
-    ServiceInvocationResponseSink, ServiceInvocationSpanContext, Source, SubmitNotificationSink,
-    TerminationFlavor, VirtualObjectHandlerType, WorkflowHandlerType,
+    AttachInvocationRequest, DeadLetterFlavor, DeadLetterRequest, InvocationPause, InvocationQuery,
+    InvocationResponse, InvocationRetry, InvocationTarget, InvocationTargetType,
+    InvocationTermination, PauseFlavor, PromiseCompletionRequest, ResponseResult, RetryFlavor,
+    ServiceInvocation, ServiceInvocationResponseSink, ServiceInvocationSpanContext, ServicePause,
+    Source, SubmitNotificationSink, TerminationFlavor, VirtualObjectHandlerType,
+    WorkflowHandlerType,
 };
-use restate_types::invocation::{InvocationInput, SpanRelation};
+use restate_types::invocation::{Header, InvocationInput, SpanRelation};
 use restate_types::journal::enriched::EnrichedRawEntry;
 use restate_types::journal::enriched::{
     AwakeableEnrichmentResult, CallEnrichmentResult, EnrichedEntryHeader,
@@ -72,6 +79,8 @@ use restate_types::journal::CompletionResult;
 use restate_types::journal::EntryType;
 use restate_types::journal::*;
 use restate_types::message::MessageIndex;
+use restate_types::schema::kafka_sinks::{KafkaSinkFlavor, KafkaSinkRequest, KafkaSinkTruncation};
+use restate_types::schema::schedules::{ScheduleFlavor, ScheduleRequest};
 use restate_types::state_mut::ExternalStateMutation;
 use restate_types::state_mut::StateMutationVersion;
 use restate_types::time::MillisSinceEpoch;
@@ -95,6 +104,10 @@ pub struct StateMachine<Codec> {
     /// Sequence number of the next outbox message to be appended.
     outbox_seq_number: MessageIndex,
     partition_key_range: RangeInclusive<PartitionKey>,
+    /// Services whose new invocations are held back until they are resumed.
+    paused_services: PausedServices,
+    /// Kafka sinks publishing the outputs of the completed invocations.
+    kafka_sink_routes: KafkaSinkRoutes,
     latency: Histogram,
 
     /// This is used to disable writing to idempotency table/virtual object status table for idempotent invocations/workflow invocations.
@@ -157,6 +170,8 @@ impl<Codec> StateMachine<Codec> {
         outbox_seq_number: MessageIndex,
         outbox_head_seq_number: Option<MessageIndex>,
         partition_key_range: RangeInclusive<PartitionKey>,
+        paused_services: PausedServices,
+        kafka_sink_routes: KafkaSinkRoutes,
         disable_idempotency_table: bool,
     ) -> Self {
         let latency =
@@ -166,6 +181,8 @@ impl<Codec> StateMachine<Codec> {
             outbox_seq_number,
             outbox_head_seq_number,
             partition_key_range,
+            paused_services,
+            kafka_sink_routes,
             latency,
             disable_idempotency_table,
             _codec: PhantomData,
@@ -176,6 +193,9 @@ impl<Codec> StateMachine<Codec> {
 pub(crate) struct StateMachineApplyContext<'a, S> {
     storage: &'a mut S,
     action_collector: &'a mut ActionCollector,
+    /// Time at which the applied record has been appended to the log. Unlike the local clock,
+    /// it is the same on every replica of the partition.
+    record_created_at: MillisSinceEpoch,
     is_leader: bool,
 }
 
@@ -201,6 +221,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
     pub async fn apply<TransactionType: restate_storage_api::Transaction + Send>(
         &mut self,
         command: Command,
+        record_created_at: MillisSinceEpoch,
         transaction: &mut TransactionType,
         action_collector: &mut ActionCollector,
         is_leader: bool,
@@ -215,6 +236,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                     StateMachineApplyContext {
                         storage: transaction,
                         action_collector,
+                        record_created_at,
                         is_leader,
                     },
                     command,
@@ -229,6 +251,9 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
     async fn on_apply<
         State: IdempotencyTable
+            + DeadLetterTable
+            + ScheduleTable
+            + KafkaSinkOutboxTable
             + PromiseTable
             + JournalTable
             + InvocationStatusTable
@@ -272,6 +297,10 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 self.handle_attach_invocation_request(&mut ctx, attach_invocation_request)
                     .await
             }
+            Command::CompletePromise(promise_completion_request) => {
+                self.handle_promise_completion_request(&mut ctx, promise_completion_request)
+                    .await
+            }
             Command::InvokerEffect(effect) => self.try_invoker_effect(&mut ctx, effect).await,
             Command::TruncateOutbox(index) => {
                 Self::do_truncate_outbox(
@@ -282,6 +311,9 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 self.outbox_head_seq_number = Some(index + 1);
                 Ok(())
             }
+            Command::TruncateKafkaSinkOutbox(KafkaSinkTruncation { sink_id, index }) => {
+                Self::do_truncate_kafka_sink_outbox(&mut ctx, &sink_id, index).await
+            }
             Command::Timer(timer) => self.on_timer(&mut ctx, timer).await,
             Command::TerminateInvocation(invocation_termination) => {
                 self.try_terminate_invocation(&mut ctx, invocation_termination)
@@ -291,6 +323,27 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 self.try_purge_invocation(&mut ctx, purge_invocation_request.invocation_id)
                     .await
             }
+            Command::RetryInvocation(invocation_retry) => {
+                self.try_retry_invocation(&mut ctx, invocation_retry).await
+            }
+            Command::PauseInvocation(invocation_pause) => {
+                self.try_pause_invocation(&mut ctx, invocation_pause).await
+            }
+            Command::PauseService(service_pause) => {
+                self.on_service_pause(&mut ctx, service_pause).await
+            }
+            Command::KafkaSink(kafka_sink_request) => {
+                self.on_kafka_sink_request(&mut ctx, kafka_sink_request)
+                    .await;
+                Ok(())
+            }
+            Command::DeadLetter(dead_letter_request) => {
+                self.on_dead_letter_request(&mut ctx, dead_letter_request)
+                    .await
+            }
+            Command::Schedule(schedule_request) => {
+                self.on_schedule_request(&mut ctx, schedule_request).await
+            }
             Command::PatchState(mutation) => {
                 self.handle_external_state_mutation(&mut ctx, mutation)
                     .await
@@ -332,7 +385,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Span::current().record_invocation_target(&service_invocation.invocation_target);
         // Phases of an invocation
         // 1. Try deduplicate it first
-        // 2. Check if we need to schedule it
+        // 2. Check if we need to schedule it, or hold it because its service is paused
         // 3. Check if we need to inbox it (only for exclusive handlers of virtual objects services)
         // 4. Execute it
 
@@ -368,6 +421,23 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             );
             return Ok(());
         };
+        let Some(pre_flight_invocation_metadata) = self
+            .handle_service_invocation_paused_service(
+                ctx,
+                invocation_id,
+                pre_flight_invocation_metadata,
+            )
+            .await?
+        else {
+            // Invocation was held, send back the ingress attach notification and return
+            Self::send_submit_notification_if_needed(
+                ctx,
+                invocation_id,
+                true,
+                submit_notification_sink,
+            );
+            return Ok(());
+        };
 
         // 3. Check if we need to inbox it (only for exclusive methods of virtual objects)
         let Some(pre_flight_invocation_metadata) = self
@@ -536,6 +606,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             match previous_invocation_status {
                 is @ InvocationStatus::Invoked { .. }
                 | is @ InvocationStatus::Suspended { .. }
+                | is @ InvocationStatus::Paused { .. }
                 | is @ InvocationStatus::Inboxed { .. }
                 | is @ InvocationStatus::Scheduled { .. } => {
                     if let Some(ref response_sink) = service_invocation.response_sink {
@@ -622,6 +693,66 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(Some(metadata))
     }
 
+    /// Returns the invocation in case its service is not paused
+    async fn handle_service_invocation_paused_service<
+        State: InvocationStatusTable + InboxTable + FsmTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+        mut metadata: PreFlightInvocationMetadata,
+    ) -> Result<Option<PreFlightInvocationMetadata>, Error> {
+        if !self
+            .paused_services
+            .contains(metadata.invocation_target.service_name())
+        {
+            return Ok(Some(metadata));
+        }
+
+        debug_if_leader!(
+            ctx.is_leader,
+            "Hold invocation because the service is paused"
+        );
+
+        // Held invocations are stored as scheduled invocations without an execution time. This
+        // tells them apart from the scheduled invocations which are still waiting for their timer.
+        metadata.execution_time = None;
+        let service_name = metadata.invocation_target.service_name().clone();
+        ctx.storage
+            .put_invocation_status(
+                &invocation_id,
+                &InvocationStatus::Scheduled(
+                    ScheduledInvocation::from_pre_flight_invocation_metadata(metadata),
+                ),
+            )
+            .await;
+        self.hold_invocation(
+            ctx,
+            &service_name,
+            HeldInvocation::Invocation(invocation_id),
+        )
+        .await;
+
+        Ok(None)
+    }
+
+    /// Adds the held invocation to the index of its service, which is used to release the held
+    /// invocations in order once the service is resumed.
+    async fn hold_invocation<State: InboxTable + FsmTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        service_name: &str,
+        held_invocation: HeldInvocation,
+    ) {
+        // Held invocations share the sequence numbers of the inbox
+        let seq_number = self.inbox_seq_number;
+        ctx.storage
+            .put_held_invocation(service_name, seq_number, &held_invocation)
+            .await;
+        ctx.storage.put_inbox_seq_number(seq_number + 1).await;
+        self.inbox_seq_number += 1;
+    }
+
     /// Returns the invocation in case the invocation was not inboxed
     async fn handle_service_invocation_exclusive_handler<
         State: VirtualObjectStatusTable + InvocationStatusTable + InboxTable + FsmTable,
@@ -759,6 +890,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
         ctx.action_collector.push(Action::Invoke {
             invocation_id,
+            invocation_epoch: in_flight_invocation_metadata.current_invocation_epoch,
             invocation_target: in_flight_invocation_metadata.invocation_target.clone(),
             invoke_input_journal,
         });
@@ -854,7 +986,9 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         let status = ctx.get_invocation_status(&invocation_id).await?;
 
         match status {
-            InvocationStatus::Invoked(metadata) | InvocationStatus::Suspended { metadata, .. } => {
+            InvocationStatus::Invoked(metadata)
+            | InvocationStatus::Suspended { metadata, .. }
+            | InvocationStatus::Paused(metadata) => {
                 self.kill_invocation(ctx, invocation_id, metadata).await?;
             }
             InvocationStatus::Inboxed(inboxed) => {
@@ -923,6 +1057,17 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                     Self::do_resume_service(ctx, invocation_id, metadata).await?;
                 }
             }
+            InvocationStatus::Paused(metadata) => {
+                self.cancel_journal_leaves(
+                    ctx,
+                    invocation_id,
+                    InvocationStatusProjection::Paused,
+                    metadata.journal_metadata.length,
+                )
+                .await?;
+                // The invocation needs to run in order to handle the cancellation
+                Self::do_resume_service(ctx, invocation_id, metadata).await?;
+            }
             InvocationStatus::Inboxed(inboxed) => {
                 self.terminate_inboxed_invocation(
                     ctx,
@@ -1179,6 +1324,15 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 )
                 .await
             }
+            InvocationStatusProjection::Paused => {
+                Self::store_completion(
+                    ctx,
+                    invocation_id,
+                    Completion::new(journal_index, canceled_result),
+                )
+                .await?;
+                Ok(false)
+            }
         }
     }
 
@@ -1237,8 +1391,525 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(())
     }
 
+    async fn try_retry_invocation<State: InvocationStatusTable + JournalTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        InvocationRetry {
+            invocation_id,
+            flavor: retry_flavor,
+        }: InvocationRetry,
+    ) -> Result<(), Error> {
+        match retry_flavor {
+            RetryFlavor::RetryNow => Self::try_retry_invocation_now(ctx, invocation_id).await,
+            RetryFlavor::Restart => Self::try_restart_invocation(ctx, invocation_id).await,
+        }
+    }
+
+    async fn try_retry_invocation_now<State: InvocationStatusTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) -> Result<(), Error> {
+        match ctx.get_invocation_status(&invocation_id).await? {
+            InvocationStatus::Invoked(_) => {
+                Self::do_send_retry_now_to_invoker(ctx, invocation_id);
+            }
+            _ => {
+                trace!(
+                    "Ignoring retry now command as the invocation '{invocation_id}' is not invoked."
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn try_restart_invocation<State: InvocationStatusTable + JournalTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) -> Result<(), Error> {
+        let mut metadata = match ctx.get_invocation_status(&invocation_id).await? {
+            InvocationStatus::Invoked(metadata)
+            | InvocationStatus::Suspended { metadata, .. }
+            | InvocationStatus::Paused(metadata) => metadata,
+            _ => {
+                trace!(
+                    "Ignoring restart command as the invocation '{invocation_id}' is not running."
+                );
+                return Ok(());
+            }
+        };
+        let journal_length = metadata.journal_metadata.length;
+
+        // Completions of entries which are still awaiting their result would otherwise end up
+        // on the entries of the restarted journal.
+        let uncompleted_entries: Vec<EntryIndex> = ctx
+            .storage
+            .get_journal(&invocation_id, journal_length)
+            .try_filter_map(|(journal_index, journal_entry)| async move {
+                if let JournalEntry::Entry(journal_entry) = journal_entry {
+                    if journal_entry.header().is_completed() == Some(false) {
+                        return Ok(Some(journal_index));
+                    }
+                }
+
+                Ok(None)
+            })
+            .try_collect()
+            .await?;
+
+        if !uncompleted_entries.is_empty() {
+            warn!(
+                restate.invocation.id = %invocation_id,
+                "Ignoring restart command because the journal entries {uncompleted_entries:?} are still awaiting their completion. Cancel or kill the invocation instead."
+            );
+            return Ok(());
+        }
+
+        let Some(input_entry) = ctx.storage.get_journal_entry(&invocation_id, 0).await? else {
+            warn!(
+                restate.invocation.id = %invocation_id,
+                "Ignoring restart command because the input entry is missing."
+            );
+            return Ok(());
+        };
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.journal.length = journal_length,
+            "Restart invocation from the input entry"
+        );
+
+        // The previous attempt must not produce any further effects
+        Self::do_send_abort_invocation_to_invoker(ctx, invocation_id);
+
+        Self::do_drop_journal(ctx, invocation_id, journal_length).await;
+        ctx.storage
+            .put_journal_entry(&invocation_id, 0, &input_entry)
+            .await;
+        metadata.journal_metadata.length = 1;
+        // Effects which the previous attempt has produced in the meantime are dropped
+        metadata.current_invocation_epoch += 1;
+
+        Self::do_resume_service(ctx, invocation_id, metadata).await
+    }
+
+    async fn try_pause_invocation<State: InvocationStatusTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        InvocationPause {
+            invocation_id,
+            flavor: pause_flavor,
+        }: InvocationPause,
+    ) -> Result<(), Error> {
+        match pause_flavor {
+            PauseFlavor::Pause => Self::try_pause_running_invocation(ctx, invocation_id).await,
+            PauseFlavor::Resume => Self::try_resume_paused_invocation(ctx, invocation_id).await,
+        }
+    }
+
+    async fn try_pause_running_invocation<State: InvocationStatusTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) -> Result<(), Error> {
+        match ctx.get_invocation_status(&invocation_id).await? {
+            InvocationStatus::Invoked(metadata) => {
+                // The ongoing attempt is aborted without counting as a failed attempt
+                Self::do_send_abort_invocation_to_invoker(ctx, invocation_id);
+                Self::do_pause_invocation(ctx, invocation_id, metadata).await;
+            }
+            InvocationStatus::Suspended { metadata, .. } => {
+                Self::do_pause_invocation(ctx, invocation_id, metadata).await;
+            }
+            InvocationStatus::Paused(_) => {
+                trace!(
+                    "Ignoring pause command as the invocation '{invocation_id}' is already paused."
+                );
+            }
+            _ => {
+                trace!(
+                    "Ignoring pause command as the invocation '{invocation_id}' is not running."
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn try_resume_paused_invocation<State: InvocationStatusTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) -> Result<(), Error> {
+        match ctx.get_invocation_status(&invocation_id).await? {
+            InvocationStatus::Paused(mut metadata) => {
+                // The attempt aborted by the pause might still report to the invoker, the new
+                // epoch fences it off from the resumed attempt.
+                metadata.current_invocation_epoch += 1;
+                Self::do_resume_service(ctx, invocation_id, metadata).await?;
+            }
+            _ => {
+                trace!(
+                    "Ignoring resume command as the invocation '{invocation_id}' is not paused."
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_service_pause<
+        State: FsmTable
+            + InvocationStatusTable
+            + InboxTable
+            + VirtualObjectStatusTable
+            + JournalTable
+            + StateTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        ServicePause {
+            service_name,
+            flavor: pause_flavor,
+        }: ServicePause,
+    ) -> Result<(), Error> {
+        match pause_flavor {
+            PauseFlavor::Pause => {
+                if self.paused_services.insert(service_name.clone()) {
+                    debug_if_leader!(
+                        ctx.is_leader,
+                        rpc.service = %service_name,
+                        "Effect: Pause service"
+                    );
+                    ctx.storage
+                        .put_paused_services(self.paused_services.clone())
+                        .await;
+                }
+            }
+            PauseFlavor::Resume => {
+                if self.paused_services.remove(&service_name) {
+                    debug_if_leader!(
+                        ctx.is_leader,
+                        rpc.service = %service_name,
+                        "Effect: Resume service"
+                    );
+                    ctx.storage
+                        .put_paused_services(self.paused_services.clone())
+                        .await;
+                    self.release_held_invocations(ctx, &service_name).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_kafka_sink_request<State: FsmTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        KafkaSinkRequest { sink_id, flavor }: KafkaSinkRequest,
+    ) {
+        match flavor {
+            KafkaSinkFlavor::Register(source) => {
+                debug_if_leader!(
+                    ctx.is_leader,
+                    restate.kafka_sink.id = %sink_id,
+                    "Effect: Register Kafka sink for {}",
+                    source
+                );
+                self.kafka_sink_routes.register(sink_id, source);
+            }
+            KafkaSinkFlavor::Remove => {
+                if !self.kafka_sink_routes.remove(&sink_id) {
+                    return;
+                }
+                debug_if_leader!(
+                    ctx.is_leader,
+                    restate.kafka_sink.id = %sink_id,
+                    "Effect: Remove Kafka sink"
+                );
+            }
+        }
+
+        ctx.storage
+            .put_kafka_sink_routes(self.kafka_sink_routes.clone())
+            .await;
+    }
+
+    /// Starts the invocations which have been held back while the given service was paused.
+    async fn release_held_invocations<
+        State: FsmTable
+            + InvocationStatusTable
+            + InboxTable
+            + VirtualObjectStatusTable
+            + JournalTable
+            + StateTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        service_name: &ByteString,
+    ) -> Result<(), Error> {
+        let held_invocations: Vec<_> = ctx
+            .storage
+            .held_invocations(service_name)
+            .try_collect()
+            .await?;
+
+        let mut invocation_ids = Vec::with_capacity(held_invocations.len());
+        // Virtual objects whose inbox has not been consumed while the service was paused go
+        // first, they have been enqueued before any invocation was held.
+        for (seq_number, held_invocation) in held_invocations {
+            ctx.storage
+                .delete_held_invocation(service_name, seq_number)
+                .await;
+            match held_invocation {
+                HeldInvocation::Inbox(keyed_service_id) => {
+                    if ctx
+                        .storage
+                        .get_virtual_object_status(&keyed_service_id)
+                        .await?
+                        == VirtualObjectStatus::Unlocked
+                    {
+                        self.consume_virtual_object_inbox(ctx, keyed_service_id)
+                            .await?;
+                    }
+                }
+                HeldInvocation::Invocation(invocation_id) => invocation_ids.push(invocation_id),
+            }
+        }
+
+        for invocation_id in invocation_ids {
+            // The held invocation might have been cancelled or killed in the meantime
+            match ctx.get_invocation_status(&invocation_id).await? {
+                InvocationStatus::Scheduled(ScheduledInvocation { metadata })
+                    if metadata.execution_time.is_none() =>
+                {
+                    self.start_pre_flight_invocation(ctx, invocation_id, metadata)
+                        .await?;
+                }
+                _ => {
+                    trace!("Ignoring held invocation '{invocation_id}' as it is no longer held.");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_dead_letter_request<
+        State: DeadLetterTable
+            + IdempotencyTable
+            + InvocationStatusTable
+            + OutboxTable
+            + FsmTable
+            + VirtualObjectStatusTable
+            + TimerTable
+            + InboxTable
+            + JournalTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        DeadLetterRequest {
+            invocation_id,
+            flavor,
+        }: DeadLetterRequest,
+    ) -> Result<(), Error> {
+        let Some(dead_letter) = ctx.storage.get_dead_letter(&invocation_id).await? else {
+            trace!("Ignoring dead letter command as there is no dead letter for the invocation '{invocation_id}'.");
+            return Ok(());
+        };
+
+        let DeadLetterFlavor::Replay { new_invocation_id } = flavor else {
+            Self::do_delete_dead_letter(ctx, invocation_id).await;
+            return Ok(());
+        };
+
+        // Workflow runs are identified by their workflow id, hence they can be replayed only under
+        // the original invocation id, once the completed run has been purged.
+        let replay_invocation_id = if dead_letter.invocation_target.invocation_target_ty()
+            == InvocationTargetType::Workflow(WorkflowHandlerType::Workflow)
+        {
+            if !matches!(
+                ctx.get_invocation_status(&invocation_id).await?,
+                InvocationStatus::Free
+            ) {
+                warn!("Ignoring replay of the dead-lettered workflow run '{invocation_id}' as its previous run has not been purged yet.");
+                return Ok(());
+            }
+            invocation_id
+        } else {
+            new_invocation_id
+        };
+
+        Self::do_delete_dead_letter(ctx, invocation_id).await;
+
+        let mut service_invocation = ServiceInvocation::initialize(
+            replay_invocation_id,
+            dead_letter.invocation_target,
+            Source::Ingress,
+        );
+        service_invocation.argument = dead_letter.argument;
+        service_invocation.headers = dead_letter.headers;
+        self.on_service_invocation(ctx, service_invocation).await
+    }
+
+    async fn on_schedule_request<State: ScheduleTable + TimerTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        ScheduleRequest {
+            schedule_id,
+            partition_key,
+            flavor,
+        }: ScheduleRequest,
+    ) -> Result<(), Error> {
+        let previous_schedule_status = ctx
+            .storage
+            .get_schedule_status(partition_key, &schedule_id)
+            .await?;
+
+        let mut last_invocation_id = None;
+        if let Some(previous_schedule_status) = previous_schedule_status {
+            if let Some(timer_value) = Self::schedule_timer(&previous_schedule_status) {
+                Self::do_delete_timer(ctx, timer_value.into_inner().0).await?;
+            }
+            // Keep track of the last invocation when replacing the schedule, so that the
+            // replacement won't overlap with it.
+            last_invocation_id = previous_schedule_status.last_invocation_id;
+        }
+
+        match flavor {
+            ScheduleFlavor::Create {
+                schedule,
+                first_fire_time,
+            } => {
+                debug_if_leader!(
+                    ctx.is_leader,
+                    "Register schedule '{schedule_id}' with cron expression '{}'",
+                    schedule.cron()
+                );
+                let schedule_status = ScheduleStatus {
+                    schedule,
+                    next_fire_time: Some(first_fire_time),
+                    last_invocation_id,
+                };
+                if let Some(timer_value) = Self::schedule_timer(&schedule_status) {
+                    Self::register_timer(ctx, timer_value, Default::default()).await?;
+                }
+                ctx.storage
+                    .put_schedule_status(partition_key, &schedule_status)
+                    .await;
+            }
+            ScheduleFlavor::Delete => {
+                debug_if_leader!(ctx.is_leader, "Delete schedule '{schedule_id}'");
+                ctx.storage
+                    .delete_schedule_status(partition_key, &schedule_id)
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Timer of the next occurrence of the schedule. Its key can be derived from the schedule
+    /// status, hence it can be deleted when the schedule is replaced or removed.
+    fn schedule_timer(schedule_status: &ScheduleStatus) -> Option<TimerKeyValue> {
+        schedule_status.next_fire_time.map(|fire_time| {
+            TimerKeyValue::fire_schedule(
+                fire_time,
+                schedule_status.schedule.invocation_id(fire_time),
+                schedule_status.schedule.id().to_owned(),
+            )
+        })
+    }
+
+    async fn on_fire_schedule_timer<
+        State: IdempotencyTable
+            + ScheduleTable
+            + InvocationStatusTable
+            + OutboxTable
+            + FsmTable
+            + VirtualObjectStatusTable
+            + TimerTable
+            + InboxTable
+            + JournalTable
+            + PromiseTable
+            + StateTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        fire_time: MillisSinceEpoch,
+        invocation_id: InvocationId,
+        schedule_id: String,
+    ) -> Result<(), Error> {
+        let partition_key = invocation_id.partition_key();
+        let Some(mut schedule_status) = ctx
+            .storage
+            .get_schedule_status(partition_key, &schedule_id)
+            .await?
+        else {
+            trace!(
+                "Ignoring timer of the schedule '{schedule_id}' as the schedule has been deleted."
+            );
+            return Ok(());
+        };
+        // The timer might have been proposed before the schedule was replaced
+        if schedule_status.next_fire_time != Some(fire_time)
+            || schedule_status.schedule.invocation_id(fire_time) != invocation_id
+        {
+            trace!("Ignoring stale timer of the schedule '{schedule_id}'.");
+            return Ok(());
+        }
+
+        let previous_in_flight = match schedule_status.last_invocation_id {
+            Some(last_invocation_id) => !matches!(
+                ctx.get_invocation_status(&last_invocation_id).await?,
+                InvocationStatus::Completed(_) | InvocationStatus::Free
+            ),
+            None => false,
+        };
+
+        if previous_in_flight {
+            debug_if_leader!(
+                ctx.is_leader,
+                restate.invocation.id = %invocation_id,
+                "Skip occurrence of schedule '{schedule_id}' as its previous invocation is still in flight"
+            );
+        } else {
+            debug_if_leader!(
+                ctx.is_leader,
+                restate.invocation.id = %invocation_id,
+                "Fire schedule '{schedule_id}'"
+            );
+            let schedule = &schedule_status.schedule;
+            let mut service_invocation = ServiceInvocation::initialize(
+                invocation_id,
+                schedule.target().clone(),
+                Source::Ingress,
+            );
+            if !schedule.payload().is_empty() {
+                service_invocation.argument = schedule.payload().clone();
+                service_invocation.headers = vec![Header::new("content-type", "application/json")];
+            }
+            self.on_service_invocation(ctx, service_invocation).await?;
+            schedule_status.last_invocation_id = Some(invocation_id);
+        }
+
+        // Occurrences which have been missed, e.g. because the partition had no leader, are
+        // skipped rather than fired in a burst.
+        schedule_status.next_fire_time = schedule_status
+            .schedule
+            .next_fire_time(fire_time.max(ctx.record_created_at));
+        if let Some(timer_value) = Self::schedule_timer(&schedule_status) {
+            Self::register_timer(ctx, timer_value, Default::default()).await?;
+        }
+        ctx.storage
+            .put_schedule_status(partition_key, &schedule_status)
+            .await;
+
+        Ok(())
+    }
+
     async fn on_timer<
         State: IdempotencyTable
+            + ScheduleTable
             + InvocationStatusTable
             + OutboxTable
             + FsmTable
@@ -1255,6 +1926,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         ctx: &mut StateMachineApplyContext<'_, State>,
         timer_value: TimerKeyValue,
     ) -> Result<(), Error> {
+        let wake_up_time = timer_value.wake_up_time();
         let (key, value) = timer_value.into_inner();
         Self::do_delete_timer(ctx, key).await?;
 
@@ -1282,6 +1954,10 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 self.try_purge_invocation(ctx, invocation_id).await
             }
             Timer::NeoInvoke(invocation_id) => self.on_neo_invoke_timer(ctx, invocation_id).await,
+            Timer::FireSchedule(invocation_id, schedule_id) => {
+                self.on_fire_schedule_timer(ctx, wake_up_time, invocation_id, schedule_id)
+                    .await
+            }
         }
     }
 
@@ -1311,12 +1987,38 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
         // Scheduled invocations have been deduplicated already in on_service_invocation, and they already sent back the submit notification.
 
+        // 2. Check if we need to hold it because its service is paused
+        let Some(pre_flight_invocation_metadata) = self
+            .handle_service_invocation_paused_service(
+                ctx,
+                invocation_id,
+                scheduled_invocation.metadata,
+            )
+            .await?
+        else {
+            // Invocation was held, nothing else to do here
+            return Ok(());
+        };
+
+        self.start_pre_flight_invocation(ctx, invocation_id, pre_flight_invocation_metadata)
+            .await
+    }
+
+    /// Inboxes or executes an invocation which has been scheduled or held before.
+    async fn start_pre_flight_invocation<
+        State: VirtualObjectStatusTable + InvocationStatusTable + InboxTable + FsmTable + JournalTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+        pre_flight_invocation_metadata: PreFlightInvocationMetadata,
+    ) -> Result<(), Error> {
         // 3. Check if we need to inbox it (only for exclusive methods of virtual objects)
         let Some(pre_flight_invocation_metadata) = self
             .handle_service_invocation_exclusive_handler(
                 ctx,
                 invocation_id,
-                scheduled_invocation.metadata,
+                pre_flight_invocation_metadata,
             )
             .await?
         else {
@@ -1341,6 +2043,8 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
     async fn try_invoker_effect<
         State: InvocationStatusTable
+            + DeadLetterTable
+            + KafkaSinkOutboxTable
             + JournalTable
             + StateTable
             + PromiseTable
@@ -1360,6 +2064,18 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             .await?;
 
         match status {
+            InvocationStatus::Invoked(invocation_metadata)
+                if invoker_effect.invocation_epoch
+                    < invocation_metadata.current_invocation_epoch =>
+            {
+                // The invocation has been restarted while the previous attempt was still running.
+                debug!(
+                    restate.invocation.id = %invoker_effect.invocation_id,
+                    restate.invocation.epoch = invocation_metadata.current_invocation_epoch,
+                    "Ignoring invoker effect of the earlier invocation epoch {}.",
+                    invoker_effect.invocation_epoch
+                );
+            }
             InvocationStatus::Invoked(invocation_metadata) => {
                 self.on_invoker_effect(ctx, invoker_effect, invocation_metadata)
                     .await?
@@ -1376,6 +2092,8 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
     async fn on_invoker_effect<
         State: InvocationStatusTable
+            + DeadLetterTable
+            + KafkaSinkOutboxTable
             + JournalTable
             + StateTable
             + PromiseTable
@@ -1390,6 +2108,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         InvokerEffect {
             invocation_id,
             kind,
+            ..
         }: InvokerEffect,
         invocation_metadata: InFlightInvocationMetadata,
     ) -> Result<(), Error> {
@@ -1453,9 +2172,27 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 self.end_invocation(ctx, invocation_id, invocation_metadata)
                     .await?;
             }
-            InvokerEffectKind::Failed(e) => {
-                self.fail_invocation(ctx, invocation_id, invocation_metadata, e)
-                    .await?;
+            InvokerEffectKind::Failed(e) => {
+                self.fail_invocation(ctx, invocation_id, invocation_metadata, e)
+                    .await?;
+            }
+            InvokerEffectKind::DeadLetter {
+                error,
+                dead_lettered_at,
+            } => {
+                self.dead_letter_invocation(
+                    ctx,
+                    invocation_id,
+                    invocation_metadata,
+                    error,
+                    dead_lettered_at,
+                )
+                .await?;
+            }
+            InvokerEffectKind::Paused => {
+                // Retries were exhausted, but the retry policy asks to keep the invocation around
+                // until it gets resumed manually.
+                Self::do_pause_invocation(ctx, invocation_id, invocation_metadata).await;
             }
         }
 
@@ -1464,6 +2201,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
     async fn end_invocation<
         State: InboxTable
+            + KafkaSinkOutboxTable
             + VirtualObjectStatusTable
             + JournalTable
             + OutboxTable
@@ -1489,11 +2227,21 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         );
 
         // Pop from inbox
-        Self::consume_inbox(ctx, &invocation_metadata.invocation_target).await?;
+        self.consume_inbox(ctx, &invocation_metadata.invocation_target)
+            .await?;
 
-        // If there are any response sinks, or we need to store back the completed status,
-        //  we need to find the latest output entry
-        if !invocation_metadata.response_sinks.is_empty() || !completion_retention_time.is_zero() {
+        let kafka_sink_ids: Vec<_> = self
+            .kafka_sink_routes
+            .matching_sinks(&invocation_metadata.invocation_target)
+            .map(str::to_owned)
+            .collect();
+
+        // If there are any response sinks, Kafka sinks, or we need to store back the completed
+        //  status, we need to find the latest output entry
+        if !invocation_metadata.response_sinks.is_empty()
+            || !kafka_sink_ids.is_empty()
+            || !completion_retention_time.is_zero()
+        {
             let result = if let Some(output_entry) = self
                 .read_last_output_entry(ctx, &invocation_id, journal_length)
                 .await?
@@ -1515,6 +2263,22 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             )
             .await?;
 
+            // Publish the output to the Kafka sinks, failures are not published
+            if let ResponseResult::Success(value) = &result {
+                for sink_id in kafka_sink_ids {
+                    Self::do_enqueue_kafka_sink_record(
+                        ctx,
+                        KafkaSinkRecord {
+                            sink_id,
+                            invocation_id,
+                            invocation_target: invocation_metadata.invocation_target.clone(),
+                            value: value.clone(),
+                        },
+                    )
+                    .await?;
+                }
+            }
+
             // Store the completed status, if needed
             if !completion_retention_time.is_zero() {
                 let completed_invocation = CompletedInvocation::from_in_flight_invocation_metadata(
@@ -1574,7 +2338,8 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         .await?;
 
         // Pop from inbox
-        Self::consume_inbox(ctx, &invocation_metadata.invocation_target).await?;
+        self.consume_inbox(ctx, &invocation_metadata.invocation_target)
+            .await?;
 
         // Store the completed status or free it
         if !invocation_metadata.completion_retention_duration.is_zero() {
@@ -1592,6 +2357,56 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(())
     }
 
+    async fn dead_letter_invocation<
+        State: InboxTable
+            + VirtualObjectStatusTable
+            + InvocationStatusTable
+            + StateTable
+            + JournalTable
+            + OutboxTable
+            + FsmTable
+            + DeadLetterTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+        invocation_metadata: InFlightInvocationMetadata,
+        error: InvocationError,
+        dead_lettered_at: MillisSinceEpoch,
+    ) -> Result<(), Error> {
+        if let Some(JournalEntry::Entry(input_entry)) =
+            ctx.storage.get_journal_entry(&invocation_id, 0).await?
+        {
+            let_assert!(
+                Entry::Input(InputEntry { headers, value }) =
+                    input_entry.deserialize_entry_ref::<Codec>()?
+            );
+
+            Self::do_store_dead_letter(
+                ctx,
+                invocation_id,
+                DeadLetter {
+                    invocation_target: invocation_metadata.invocation_target.clone(),
+                    argument: value,
+                    headers,
+                    last_error: error.clone(),
+                    journal_length: invocation_metadata.journal_metadata.length,
+                    dead_lettered_at,
+                },
+            )
+            .await;
+        } else {
+            warn!(
+                restate.invocation.id = %invocation_id,
+                "Cannot record the invocation in the dead letter table because the input entry is missing."
+            );
+        }
+
+        // Callers still get the failure, the dead letter only retains the input for later replay
+        self.fail_invocation(ctx, invocation_id, invocation_metadata, error)
+            .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn send_response_to_sinks<State: OutboxTable + FsmTable>(
         &mut self,
@@ -1637,8 +2452,10 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             + InvocationStatusTable
             + VirtualObjectStatusTable
             + StateTable
-            + JournalTable,
+            + JournalTable
+            + FsmTable,
     >(
+        &mut self,
         ctx: &mut StateMachineApplyContext<'_, State>,
         invocation_target: &InvocationTarget,
     ) -> Result<(), Error> {
@@ -1650,64 +2467,101 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                 "When the handler type is Exclusive, the invocation target must have a key",
             );
 
+            self.consume_virtual_object_inbox(ctx, keyed_service_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn consume_virtual_object_inbox<
+        State: InboxTable
+            + VirtualObjectStatusTable
+            + InvocationStatusTable
+            + StateTable
+            + JournalTable
+            + FsmTable,
+    >(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        keyed_service_id: ServiceId,
+    ) -> Result<(), Error> {
+        if self
+            .paused_services
+            .contains(&keyed_service_id.service_name)
+        {
+            // The inbox is consumed once the service is resumed
             debug_if_leader!(
                 ctx.is_leader,
                 rpc.service = %keyed_service_id,
-                "Consume inbox"
+                "Service is paused, keeping the inbox"
             );
+            ctx.storage
+                .put_virtual_object_status(&keyed_service_id, &VirtualObjectStatus::Unlocked)
+                .await;
+            let service_name = keyed_service_id.service_name.clone();
+            self.hold_invocation(ctx, &service_name, HeldInvocation::Inbox(keyed_service_id))
+                .await;
+            return Ok(());
+        }
 
-            // Pop until we find the first inbox entry.
-            // Note: the inbox seq numbers can have gaps.
-            while let Some(inbox_entry) = ctx.storage.pop_inbox(&keyed_service_id).await? {
-                match inbox_entry.inbox_entry {
-                    InboxEntry::Invocation(_, invocation_id) => {
-                        let inboxed_status = ctx.get_invocation_status(&invocation_id).await?;
-
-                        let_assert!(
-                            InvocationStatus::Inboxed(inboxed_invocation) = inboxed_status,
-                            "InvocationStatus must contain an Inboxed invocation for the id {}",
-                            invocation_id
-                        );
+        debug_if_leader!(
+            ctx.is_leader,
+            rpc.service = %keyed_service_id,
+            "Consume inbox"
+        );
 
-                        debug_if_leader!(
-                            ctx.is_leader,
-                            rpc.service = %keyed_service_id,
-                            "Invoke inboxed"
-                        );
+        // Pop until we find the first inbox entry.
+        // Note: the inbox seq numbers can have gaps.
+        while let Some(inbox_entry) = ctx.storage.pop_inbox(&keyed_service_id).await? {
+            match inbox_entry.inbox_entry {
+                InboxEntry::Invocation(_, invocation_id) => {
+                    let inboxed_status = ctx.get_invocation_status(&invocation_id).await?;
 
-                        // Lock the service
-                        ctx.storage
-                            .put_virtual_object_status(
-                                &keyed_service_id,
-                                &VirtualObjectStatus::Locked(invocation_id),
-                            )
-                            .await;
+                    let_assert!(
+                        InvocationStatus::Inboxed(inboxed_invocation) = inboxed_status,
+                        "InvocationStatus must contain an Inboxed invocation for the id {}",
+                        invocation_id
+                    );
 
-                        let (in_flight_invocation_meta, invocation_input) =
-                            InFlightInvocationMetadata::from_inboxed_invocation(inboxed_invocation);
-                        Self::init_journal_and_invoke(
-                            ctx,
-                            invocation_id,
-                            in_flight_invocation_meta,
-                            invocation_input,
+                    debug_if_leader!(
+                        ctx.is_leader,
+                        rpc.service = %keyed_service_id,
+                        "Invoke inboxed"
+                    );
+
+                    // Lock the service
+                    ctx.storage
+                        .put_virtual_object_status(
+                            &keyed_service_id,
+                            &VirtualObjectStatus::Locked(invocation_id),
                         )
-                        .await?;
+                        .await;
 
-                        // Started a new invocation
-                        return Ok(());
-                    }
-                    InboxEntry::StateMutation(state_mutation) => {
-                        Self::mutate_state(ctx.storage, state_mutation).await?;
-                    }
+                    let (in_flight_invocation_meta, invocation_input) =
+                        InFlightInvocationMetadata::from_inboxed_invocation(inboxed_invocation);
+                    Self::init_journal_and_invoke(
+                        ctx,
+                        invocation_id,
+                        in_flight_invocation_meta,
+                        invocation_input,
+                    )
+                    .await?;
+
+                    // Started a new invocation
+                    return Ok(());
+                }
+                InboxEntry::StateMutation(state_mutation) => {
+                    Self::mutate_state(ctx.storage, state_mutation).await?;
                 }
             }
-
-            // We consumed the inbox, nothing else to do here
-            ctx.storage
-                .put_virtual_object_status(&keyed_service_id, &VirtualObjectStatus::Unlocked)
-                .await;
         }
 
+        // We consumed the inbox, nothing else to do here
+        ctx.storage
+            .put_virtual_object_status(&keyed_service_id, &VirtualObjectStatus::Unlocked)
+            .await;
+
         Ok(())
     }
 
@@ -2017,57 +2871,12 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                     if let Some(service_id) =
                         invocation_metadata.invocation_target.as_keyed_service_id()
                     {
-                        // Load state and write completion
-                        let promise_metadata = ctx.storage.get_promise(&service_id, &key).await?;
-
-                        let completion_result = match promise_metadata {
-                            None => {
-                                // Just register the promise completion
-                                Self::do_put_promise(
-                                    ctx,
-                                    service_id,
-                                    key,
-                                    Promise {
-                                        state: PromiseState::Completed(completion),
-                                    },
-                                )
-                                .await;
-                                CompletionResult::Empty
-                            }
-                            Some(Promise {
-                                state: PromiseState::NotCompleted(listeners),
-                            }) => {
-                                // Send response to listeners
-                                for listener in listeners {
-                                    self.handle_outgoing_message(
-                                        ctx,
-                                        OutboxMessage::ServiceResponse(InvocationResponse {
-                                            id: listener.invocation_id(),
-                                            entry_index: listener.journal_index(),
-                                            result: completion.clone().into(),
-                                        }),
-                                    )
-                                    .await?;
-                                }
-
-                                // Now register the promise completion
-                                Self::do_put_promise(
-                                    ctx,
-                                    service_id,
-                                    key,
-                                    Promise {
-                                        state: PromiseState::Completed(completion),
-                                    },
-                                )
-                                .await;
-                                CompletionResult::Empty
-                            }
-                            Some(Promise {
-                                state: PromiseState::Completed(_),
-                            }) => {
-                                // Conflict!
-                                (&ALREADY_COMPLETED_INVOCATION_ERROR).into()
-                            }
+                        let completion_result = match self
+                            .do_complete_promise(ctx, service_id, key, completion)
+                            .await?
+                        {
+                            Ok(()) => CompletionResult::Empty,
+                            Err(err) => (&err).into(),
                         };
 
                         Codec::write_completion(&mut journal_entry, completion_result.clone())?;
@@ -2452,6 +3261,10 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                     Self::do_resume_service(ctx, invocation_id, metadata).await?;
                 }
             }
+            InvocationStatus::Paused(_) => {
+                // The completion is delivered with the journal once the invocation is resumed
+                Self::store_completion(ctx, invocation_id, completion).await?;
+            }
             _ => {
                 debug!(
                     rectx.storage.invocation.id = %invocation_id,
@@ -2642,6 +3455,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             }
             is @ InvocationStatus::Invoked(_)
             | is @ InvocationStatus::Suspended { .. }
+            | is @ InvocationStatus::Paused(_)
             | is @ InvocationStatus::Inboxed(_)
             | is @ InvocationStatus::Scheduled(_) => {
                 Self::do_append_response_sink(
@@ -2667,6 +3481,44 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(())
     }
 
+    async fn handle_promise_completion_request<State: PromiseTable + OutboxTable + FsmTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        promise_completion_request: PromiseCompletionRequest,
+    ) -> Result<(), Error> {
+        debug_assert!(
+            self.partition_key_range.contains(&promise_completion_request.partition_key()),
+            "Promise completion request with partition key '{}' has been delivered to a partition processor with key range '{:?}'. This indicates a bug.",
+            promise_completion_request.partition_key(),
+            self.partition_key_range);
+
+        let PromiseCompletionRequest {
+            workflow_target,
+            key,
+            completion,
+            response_sink,
+        } = promise_completion_request;
+        let service_id = workflow_target
+            .as_keyed_service_id()
+            .expect("workflow targets have a key");
+
+        let response = match self
+            .do_complete_promise(ctx, service_id, key, completion.into())
+            .await?
+        {
+            Ok(()) => ResponseResult::Success(Bytes::new()),
+            Err(err) => ResponseResult::Failure(err),
+        };
+        self.send_response_to_sinks(
+            ctx,
+            vec![response_sink],
+            response,
+            None,
+            Some(&workflow_target),
+        )
+        .await
+    }
+
     fn send_ingress_response<State>(
         ctx: &mut StateMachineApplyContext<'_, State>,
         ingress_response: IngressResponseEnvelope<ingress::InvocationResponse>,
@@ -2738,6 +3590,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         );
 
         metadata.timestamps.update();
+        let invocation_epoch = metadata.current_invocation_epoch;
         let invocation_target = metadata.invocation_target.clone();
         ctx.storage
             .put_invocation_status(&invocation_id, &InvocationStatus::Invoked(metadata))
@@ -2745,6 +3598,7 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
 
         ctx.action_collector.push(Action::Invoke {
             invocation_id,
+            invocation_epoch,
             invocation_target,
             invoke_input_journal: InvokeInputJournal::NoCachedJournal,
         });
@@ -2786,6 +3640,52 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             .await;
     }
 
+    async fn do_pause_invocation<State: InvocationStatusTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+        mut metadata: InFlightInvocationMetadata,
+    ) {
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.journal.length = metadata.journal_metadata.length,
+            "Effect: Pause invocation"
+        );
+
+        metadata.timestamps.update();
+        ctx.storage
+            .put_invocation_status(&invocation_id, &InvocationStatus::Paused(metadata))
+            .await;
+    }
+
+    async fn do_store_dead_letter<State: DeadLetterTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+        dead_letter: DeadLetter,
+    ) {
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %invocation_id,
+            "Effect: Store dead letter"
+        );
+
+        ctx.storage
+            .put_dead_letter(&invocation_id, &dead_letter)
+            .await;
+    }
+
+    async fn do_delete_dead_letter<State: DeadLetterTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) {
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %invocation_id,
+            "Effect: Delete dead letter"
+        );
+
+        ctx.storage.delete_dead_letter(&invocation_id).await;
+    }
+
     async fn do_store_completed_invocation<State: InvocationStatusTable>(
         ctx: &mut StateMachineApplyContext<'_, State>,
         invocation_id: InvocationId,
@@ -3039,6 +3939,15 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
                     "Register background invoke timer"
                 )
             }
+            Timer::FireSchedule(invocation_id, schedule_id) => {
+                debug_if_leader!(
+                    ctx.is_leader,
+                    restate.invocation.id = %invocation_id,
+                    restate.timer.wake_up_time = %timer_value.wake_up_time(),
+                    restate.timer.key = %TimerKeyDisplay(timer_value.key()),
+                    "Register timer of schedule '{schedule_id}'"
+                )
+            }
             Timer::CleanInvocationStatus(_) => {
                 debug_if_leader!(
                     ctx.is_leader,
@@ -3173,6 +4082,50 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(())
     }
 
+    async fn do_enqueue_kafka_sink_record<State: KafkaSinkOutboxTable + FsmTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        record: KafkaSinkRecord,
+    ) -> Result<(), Error> {
+        let record_index = ctx.storage.get_kafka_sink_outbox_seq_number().await?;
+
+        debug_if_leader!(
+            ctx.is_leader,
+            restate.invocation.id = %record.invocation_id,
+            restate.kafka_sink.id = %record.sink_id,
+            "Effect: Publish invocation output to Kafka sink with index {}",
+            record_index
+        );
+
+        ctx.storage
+            .put_kafka_sink_record(record_index, &record)
+            .await;
+        ctx.storage
+            .put_kafka_sink_outbox_seq_number(record_index + 1)
+            .await;
+
+        ctx.action_collector.push(Action::NewKafkaSinkRecord);
+
+        Ok(())
+    }
+
+    async fn do_truncate_kafka_sink_outbox<State: KafkaSinkOutboxTable>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        sink_id: &str,
+        index: MessageIndex,
+    ) -> Result<(), Error> {
+        trace!(
+            restate.kafka_sink.id = %sink_id,
+            "Effect: Truncate Kafka sink outbox up to index {}",
+            index
+        );
+
+        ctx.storage
+            .truncate_kafka_sink_outbox(sink_id, index)
+            .await?;
+
+        Ok(())
+    }
+
     /// Returns `true` if the completion should be forwarded.
     async fn store_completion<State: JournalTable>(
         ctx: &mut StateMachineApplyContext<'_, State>,
@@ -3341,6 +4294,16 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
             .push(Action::AbortInvocation(invocation_id));
     }
 
+    fn do_send_retry_now_to_invoker<State>(
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        invocation_id: InvocationId,
+    ) {
+        debug_if_leader!(ctx.is_leader, restate.invocation.id = %invocation_id, "Effect: Send retry now command to invoker");
+
+        ctx.action_collector
+            .push(Action::RetryInvocationNow(invocation_id));
+    }
+
     async fn do_mutate_state<State: StateTable>(
         ctx: &mut StateMachineApplyContext<'_, State>,
         state_mutation: ExternalStateMutation,
@@ -3356,6 +4319,69 @@ impl<Codec: RawEntryCodec> StateMachine<Codec> {
         Ok(())
     }
 
+    /// Completes the promise, notifying the invocations waiting on it. Fails with a conflict if
+    /// the promise has already been completed.
+    async fn do_complete_promise<State: PromiseTable + OutboxTable + FsmTable>(
+        &mut self,
+        ctx: &mut StateMachineApplyContext<'_, State>,
+        service_id: ServiceId,
+        key: ByteString,
+        completion: EntryResult,
+    ) -> Result<Result<(), InvocationError>, Error> {
+        // Load state and write completion
+        let promise_metadata = ctx.storage.get_promise(&service_id, &key).await?;
+
+        match promise_metadata {
+            None => {
+                // Just register the promise completion
+                Self::do_put_promise(
+                    ctx,
+                    service_id,
+                    key,
+                    Promise {
+                        state: PromiseState::Completed(completion),
+                    },
+                )
+                .await;
+            }
+            Some(Promise {
+                state: PromiseState::NotCompleted(listeners),
+            }) => {
+                // Send response to listeners
+                for listener in listeners {
+                    self.handle_outgoing_message(
+                        ctx,
+                        OutboxMessage::ServiceResponse(InvocationResponse {
+                            id: listener.invocation_id(),
+                            entry_index: listener.journal_index(),
+                            result: completion.clone().into(),
+                        }),
+                    )
+                    .await?;
+                }
+
+                // Now register the promise completion
+                Self::do_put_promise(
+                    ctx,
+                    service_id,
+                    key,
+                    Promise {
+                        state: PromiseState::Completed(completion),
+                    },
+                )
+                .await;
+            }
+            Some(Promise {
+                state: PromiseState::Completed(_),
+            }) => {
+                // Conflict!
+                return Ok(Err(ALREADY_COMPLETED_INVOCATION_ERROR));
+            }
+        }
+
+        Ok(Ok(()))
+    }
+
     async fn do_put_promise<State: PromiseTable>(
         ctx: &mut StateMachineApplyContext<'_, State>,
         service_id: ServiceId,
@@ -3440,6 +4466,7 @@ impl<'a> fmt::Display for CompletionResultFmt<'a> {
 enum InvocationStatusProjection {
     Invoked,
     Suspended(HashSet<EntryIndex>),
+    Paused,
 }
 
 #[cfg(test)]
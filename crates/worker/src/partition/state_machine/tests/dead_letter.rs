@@ -0,0 +1,133 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use assert2::assert;
+use restate_storage_api::dead_letter_table::ReadOnlyDeadLetterTable;
+use restate_types::invocation::DeadLetterRequest;
+use restate_types::time::MillisSinceEpoch;
+
+async fn dead_letter_invocation(
+    test_env: &mut TestEnv,
+    invocation_target: InvocationTarget,
+) -> InvocationId {
+    let invocation_id = InvocationId::mock_generate(&invocation_target);
+
+    let _ = test_env
+        .apply(Command::Invoke(ServiceInvocation {
+            invocation_id,
+            invocation_target,
+            argument: Bytes::from_static(b"input"),
+            headers: vec![Header::new("x-custom", "value")],
+            ..ServiceInvocation::mock()
+        }))
+        .await;
+
+    let actions = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::DeadLetter {
+                error: InvocationError::internal("exhausted"),
+                dead_lettered_at: MillisSinceEpoch::new(1000),
+            },
+        }))
+        .await;
+    assert_that!(
+        actions,
+        not(contains(pat!(Action::Invoke {
+            invocation_id: eq(invocation_id)
+        })))
+    );
+
+    invocation_id
+}
+
+#[test(tokio::test)]
+async fn dead_letter_effect_stores_input_and_fails_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let invocation_id = dead_letter_invocation(&mut test_env, invocation_target.clone()).await;
+
+    assert!(
+        let InvocationStatus::Free = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    let dead_letter = test_env
+        .storage()
+        .get_dead_letter(&invocation_id)
+        .await?
+        .expect("dead letter must be stored");
+    assert_eq!(dead_letter.invocation_target, invocation_target);
+    assert_eq!(dead_letter.argument, Bytes::from_static(b"input"));
+    assert_eq!(dead_letter.headers, vec![Header::new("x-custom", "value")]);
+    assert_eq!(dead_letter.last_error.message(), "exhausted");
+    assert_eq!(dead_letter.dead_lettered_at, MillisSinceEpoch::new(1000));
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn replay_dead_letter_invokes_new_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let invocation_id = dead_letter_invocation(&mut test_env, invocation_target.clone()).await;
+
+    let replay_request = DeadLetterRequest::replay(invocation_id);
+    let DeadLetterFlavor::Replay { new_invocation_id } = replay_request.flavor else {
+        panic!("expected a replay request");
+    };
+
+    let actions = test_env.apply(Command::DeadLetter(replay_request)).await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::Invoke {
+            invocation_id: eq(new_invocation_id),
+            invocation_target: eq(invocation_target),
+        }))
+    );
+    assert!(test_env
+        .storage()
+        .get_dead_letter(&invocation_id)
+        .await?
+        .is_none());
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn discard_dead_letter() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id =
+        dead_letter_invocation(&mut test_env, InvocationTarget::mock_service()).await;
+
+    let actions = test_env
+        .apply(Command::DeadLetter(DeadLetterRequest::discard(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(actions, empty());
+    assert!(test_env
+        .storage()
+        .get_dead_letter(&invocation_id)
+        .await?
+        .is_none());
+
+    test_env.shutdown().await;
+    Ok(())
+}
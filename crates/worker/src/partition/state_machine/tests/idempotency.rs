@@ -91,6 +91,7 @@ async fn start_and_complete_idempotent_invocation(#[case] disable_idempotency_ta
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -100,6 +101,7 @@ async fn start_and_complete_idempotent_invocation(#[case] disable_idempotency_ta
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -208,6 +210,7 @@ async fn start_and_complete_idempotent_invocation_neo_table(
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -217,6 +220,7 @@ async fn start_and_complete_idempotent_invocation_neo_table(
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -388,6 +392,7 @@ async fn attach_with_service_invocation_command_while_executing(
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -397,6 +402,7 @@ async fn attach_with_service_invocation_command_while_executing(
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -512,6 +518,7 @@ async fn attach_with_send_service_invocation(#[case] disable_idempotency_table:
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -521,6 +528,7 @@ async fn attach_with_send_service_invocation(#[case] disable_idempotency_table:
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -724,6 +732,7 @@ async fn attach_command(#[case] disable_idempotency_table: bool) {
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -733,6 +742,7 @@ async fn attach_command(#[case] disable_idempotency_table: bool) {
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -0,0 +1,131 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use restate_storage_api::kafka_sink_outbox_table::{KafkaSinkRecord, ReadOnlyKafkaSinkOutboxTable};
+use restate_types::schema::kafka_sinks::{KafkaSink, KafkaSinkSource, KafkaSinkTruncation};
+use test_log::test;
+
+async fn complete_invocation(
+    test_env: &mut TestEnv,
+    invocation_target: InvocationTarget,
+    output: Bytes,
+) -> (InvocationId, Vec<Action>) {
+    let invocation_id =
+        fixtures::mock_start_invocation_with_invocation_target(test_env, invocation_target).await;
+
+    let _ = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::JournalEntry {
+                entry_index: 1,
+                entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
+                    EntryResult::Success(output),
+                )),
+            },
+        }))
+        .await;
+    let actions = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::End,
+        }))
+        .await;
+
+    (invocation_id, actions)
+}
+
+#[test(tokio::test)]
+async fn publish_output_of_completed_invocation() {
+    let mut test_env = TestEnv::create().await;
+    let invocation_target = InvocationTarget::mock_virtual_object();
+    let sink = KafkaSink::new(
+        "orders".to_owned(),
+        KafkaSinkSource::Service {
+            name: invocation_target.service_name().to_string(),
+            handler: None,
+        },
+        "my-cluster".to_owned(),
+        "orders".to_owned(),
+        Default::default(),
+    );
+
+    let _ = test_env
+        .apply(Command::KafkaSink(KafkaSinkRequest::register(&sink)))
+        .await;
+
+    let output = Bytes::from_static(b"{\"order\":1}");
+    let (invocation_id, actions) =
+        complete_invocation(&mut test_env, invocation_target.clone(), output.clone()).await;
+    assert_that!(
+        actions,
+        contains(predicate(|action: &Action| matches!(
+            action,
+            Action::NewKafkaSinkRecord
+        )))
+    );
+    // Kafka sink records are not sent through the outbox
+    assert_that!(
+        actions,
+        not(contains(pat!(Action::NewOutboxMessage { .. })))
+    );
+    assert_eq!(
+        test_env
+            .storage()
+            .all_kafka_sink_records()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap(),
+        vec![(
+            0,
+            KafkaSinkRecord {
+                sink_id: sink.id().to_owned(),
+                invocation_id,
+                invocation_target: invocation_target.clone(),
+                value: output.clone(),
+            }
+        )]
+    );
+
+    // The leader truncates the records once they have been produced
+    let _ = test_env
+        .apply(Command::TruncateKafkaSinkOutbox(KafkaSinkTruncation {
+            sink_id: sink.id().to_owned(),
+            index: 0,
+        }))
+        .await;
+    assert!(test_env
+        .storage()
+        .all_kafka_sink_records()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Nothing is published once the sink is removed
+    let _ = test_env
+        .apply(Command::KafkaSink(KafkaSinkRequest::remove(
+            sink.id().to_owned(),
+        )))
+        .await;
+    let (_, actions) = complete_invocation(&mut test_env, invocation_target, output).await;
+    assert_that!(
+        actions,
+        not(contains(predicate(|action: &Action| matches!(
+            action,
+            Action::NewKafkaSinkRecord
+        ))))
+    );
+
+    test_env.shutdown().await;
+}
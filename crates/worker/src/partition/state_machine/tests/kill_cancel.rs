@@ -491,6 +491,7 @@ async fn cancel_invocation_entry_referring_to_previous_entry() {
         .apply_multiple(vec![
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 3,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::cancel_invocation(
@@ -500,6 +501,7 @@ async fn cancel_invocation_entry_referring_to_previous_entry() {
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 4,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::cancel_invocation(
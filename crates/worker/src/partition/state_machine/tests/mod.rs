@@ -10,11 +10,16 @@
 
 use super::*;
 
+mod dead_letter;
 mod delayed_send;
 mod fixtures;
 mod idempotency;
+mod kafka_sink;
 mod kill_cancel;
 mod matchers;
+mod pause;
+mod retry;
+mod schedule;
 mod workflow;
 
 use crate::partition::state_machine::tests::fixtures::{
@@ -95,6 +100,8 @@ impl TestEnv {
             0,    /* outbox_seq_number */
             None, /* outbox_head_seq_number */
             PartitionKey::MIN..=PartitionKey::MAX,
+            PausedServices::default(),
+            KafkaSinkRoutes::default(),
             disable_idempotency_table,
         ))
         .await
@@ -165,10 +172,25 @@ impl TestEnv {
     }
 
     pub async fn apply(&mut self, command: Command) -> Vec<Action> {
+        self.apply_at(command, MillisSinceEpoch::now()).await
+    }
+
+    /// Applies the command as if its record had been appended to the log at the given time.
+    pub async fn apply_at(
+        &mut self,
+        command: Command,
+        record_created_at: MillisSinceEpoch,
+    ) -> Vec<Action> {
         let mut transaction = self.storage.transaction();
         let mut action_collector = ActionCollector::default();
         self.state_machine
-            .apply(command, &mut transaction, &mut action_collector, true)
+            .apply(
+                command,
+                record_created_at,
+                &mut transaction,
+                &mut action_collector,
+                true,
+            )
             .await
             .unwrap();
 
@@ -282,6 +304,7 @@ async fn awakeable_completion_received_before_entry() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::JournalEntry {
                 entry_index: 1,
                 entry: ProtobufRawEntryCodec::serialize_enriched(Entry::awakeable(None)),
@@ -339,6 +362,7 @@ async fn awakeable_completion_received_before_entry() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::Suspended {
                 waiting_for_completed_entries: HashSet::from([1]),
             },
@@ -374,6 +398,7 @@ async fn complete_awakeable_with_success() {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: EffectKind::JournalEntry {
                 entry_index: 1,
                 entry,
@@ -417,6 +442,7 @@ async fn complete_awakeable_with_failure() {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: EffectKind::JournalEntry {
                 entry_index: 1,
                 entry,
@@ -454,6 +480,7 @@ async fn invoke_with_headers() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::JournalEntry {
                 entry_index: 1,
                 entry: ProtobufRawEntryCodec::serialize_enriched(Entry::invoke(
@@ -540,6 +567,7 @@ async fn mutate_state() -> anyhow::Result<()> {
     test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::End,
         }))
         .await;
@@ -575,6 +603,7 @@ async fn clear_all_user_states() -> anyhow::Result<()> {
     test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::JournalEntry {
                 entry_index: 1,
                 entry: ProtobufRawEntryCodec::serialize_enriched(Entry::clear_all_state()),
@@ -609,6 +638,7 @@ async fn get_state_keys() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::JournalEntry {
                 entry_index: 1,
                 entry: ProtobufRawEntryCodec::serialize_enriched(Entry::get_state_keys(None)),
@@ -659,6 +689,7 @@ async fn get_invocation_id_entry() {
         .apply_multiple(vec![
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 3,
                     entry: ProtobufRawEntryCodec::serialize_enriched(
@@ -668,6 +699,7 @@ async fn get_invocation_id_entry() {
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 4,
                     entry: ProtobufRawEntryCodec::serialize_enriched(
@@ -785,6 +817,7 @@ async fn send_ingress_response_to_multiple_targets() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::JournalEntry {
                 entry_index: 1,
                 entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -800,6 +833,7 @@ async fn send_ingress_response_to_multiple_targets() -> TestResult {
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::End,
         }))
         .await;
@@ -973,6 +1007,7 @@ async fn consecutive_exclusive_handler_invocations_will_use_inbox() -> TestResul
     let actions = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id: first_invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::End,
         }))
         .await;
@@ -992,6 +1027,7 @@ async fn consecutive_exclusive_handler_invocations_will_use_inbox() -> TestResul
     let _ = test_env
         .apply(Command::InvokerEffect(InvokerEffect {
             invocation_id: second_invocation_id,
+            invocation_epoch: 0,
             kind: InvokerEffectKind::End,
         }))
         .await;
@@ -0,0 +1,162 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::{fixtures, *};
+
+use assert2::assert;
+use restate_types::invocation::{InvocationPause, ServicePause};
+use test_log::test;
+
+#[test(tokio::test)]
+async fn pause_and_resume_invoked_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let actions = test_env
+        .apply(Command::PauseInvocation(InvocationPause::pause(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::AbortInvocation(eq(invocation_id))))
+    );
+    assert!(
+        let InvocationStatus::Paused(_) = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    let actions = test_env
+        .apply(Command::PauseInvocation(InvocationPause::resume(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::Invoke {
+            invocation_id: eq(invocation_id),
+            invocation_epoch: eq(1),
+            invoke_input_journal: pat!(InvokeInputJournal::NoCachedJournal)
+        }))
+    );
+    assert!(
+        let InvocationStatus::Invoked(_) = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn invoker_paused_effect_pauses_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let actions = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::Paused,
+        }))
+        .await;
+
+    assert_that!(actions, empty());
+    assert!(
+        let InvocationStatus::Paused(_) = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn resume_not_paused_invocation_is_ignored() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let actions = test_env
+        .apply(Command::PauseInvocation(InvocationPause::resume(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(actions, empty());
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn paused_service_holds_new_invocations() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_target = InvocationTarget::mock_service();
+    let invocation_id = InvocationId::mock_generate(&invocation_target);
+
+    let actions = test_env
+        .apply(Command::PauseService(ServicePause::pause(
+            invocation_target.service_name().clone(),
+        )))
+        .await;
+    assert_that!(actions, empty());
+
+    let actions = test_env
+        .apply(Command::Invoke(ServiceInvocation {
+            invocation_id,
+            invocation_target: invocation_target.clone(),
+            ..ServiceInvocation::mock()
+        }))
+        .await;
+
+    assert_that!(
+        actions,
+        not(contains(pat!(Action::Invoke {
+            invocation_id: eq(invocation_id)
+        })))
+    );
+    assert!(
+        let InvocationStatus::Scheduled(_) = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    let actions = test_env
+        .apply(Command::PauseService(ServicePause::resume(
+            invocation_target.service_name().clone(),
+        )))
+        .await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::Invoke {
+            invocation_id: eq(invocation_id),
+            invocation_target: eq(invocation_target),
+        }))
+    );
+    assert!(
+        let InvocationStatus::Invoked(_) = test_env
+            .storage()
+            .get_invocation_status(&invocation_id)
+            .await?
+    );
+
+    test_env.shutdown().await;
+    Ok(())
+}
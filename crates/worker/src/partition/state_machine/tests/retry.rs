@@ -0,0 +1,205 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::{fixtures, *};
+
+use assert2::assert;
+use restate_storage_api::journal_table::JournalTable;
+use restate_types::invocation::InvocationRetry;
+use test_log::test;
+
+#[test(tokio::test)]
+async fn retry_now_invoked_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let actions = test_env
+        .apply(Command::RetryInvocation(InvocationRetry::retry_now(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(
+        actions,
+        contains(pat!(Action::RetryInvocationNow(eq(invocation_id))))
+    );
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn retry_now_unknown_invocation() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+
+    let actions = test_env
+        .apply(Command::RetryInvocation(InvocationRetry::retry_now(
+            InvocationId::mock_random(),
+        )))
+        .await;
+
+    assert_that!(actions, empty());
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn restart_invocation_truncates_journal() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let input_entry = test_env
+        .storage()
+        .get_journal_entry(&invocation_id, 0)
+        .await?;
+
+    let mut tx = test_env.storage.transaction();
+    tx.put_journal_entry(
+        &invocation_id,
+        1,
+        &fixtures::completed_invoke_entry(InvocationId::mock_random()),
+    )
+    .await;
+    tx.put_journal_entry(
+        &invocation_id,
+        2,
+        &fixtures::background_invoke_entry(InvocationId::mock_random()),
+    )
+    .await;
+    let mut invocation_status = tx.get_invocation_status(&invocation_id).await?;
+    invocation_status.get_journal_metadata_mut().unwrap().length = 3;
+    tx.put_invocation_status(&invocation_id, &invocation_status)
+        .await;
+    tx.commit().await?;
+
+    let actions = test_env
+        .apply(Command::RetryInvocation(InvocationRetry::restart(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(
+        actions,
+        all!(
+            contains(pat!(Action::AbortInvocation(eq(invocation_id)))),
+            contains(pat!(Action::Invoke {
+                invocation_id: eq(invocation_id),
+                invoke_input_journal: pat!(InvokeInputJournal::NoCachedJournal)
+            }))
+        )
+    );
+
+    let invocation_status = test_env
+        .storage()
+        .get_invocation_status(&invocation_id)
+        .await?;
+    assert!(let InvocationStatus::Invoked(_) = invocation_status);
+    assert_eq!(invocation_status.get_journal_metadata().unwrap().length, 1);
+
+    let journal = test_env
+        .storage()
+        .get_journal(&invocation_id, 3)
+        .map_ok(|(_, entry)| entry)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(journal, Vec::from_iter(input_entry));
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn restart_invocation_with_uncompleted_entries_is_ignored() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let mut tx = test_env.storage.transaction();
+    tx.put_journal_entry(
+        &invocation_id,
+        1,
+        &fixtures::incomplete_invoke_entry(InvocationId::mock_random()),
+    )
+    .await;
+    let mut invocation_status = tx.get_invocation_status(&invocation_id).await?;
+    invocation_status.get_journal_metadata_mut().unwrap().length = 2;
+    tx.put_invocation_status(&invocation_id, &invocation_status)
+        .await;
+    tx.commit().await?;
+
+    let actions = test_env
+        .apply(Command::RetryInvocation(InvocationRetry::restart(
+            invocation_id,
+        )))
+        .await;
+
+    assert_that!(actions, empty());
+
+    let invocation_status = test_env
+        .storage()
+        .get_invocation_status(&invocation_id)
+        .await?;
+    assert!(let InvocationStatus::Invoked(_) = invocation_status);
+    assert_eq!(invocation_status.get_journal_metadata().unwrap().length, 2);
+
+    test_env.shutdown().await;
+    Ok(())
+}
+
+#[test(tokio::test)]
+async fn restart_invocation_ignores_effects_of_previous_epoch() -> anyhow::Result<()> {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = fixtures::mock_start_invocation(&mut test_env).await;
+
+    let actions = test_env
+        .apply(Command::RetryInvocation(InvocationRetry::restart(
+            invocation_id,
+        )))
+        .await;
+    assert_that!(
+        actions,
+        contains(pat!(Action::Invoke {
+            invocation_id: eq(invocation_id),
+            invocation_epoch: eq(1)
+        }))
+    );
+
+    // the aborted attempt still reports its end
+    let actions = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::End,
+        }))
+        .await;
+    assert_that!(actions, empty());
+    let invocation_status = test_env
+        .storage()
+        .get_invocation_status(&invocation_id)
+        .await?;
+    assert!(let InvocationStatus::Invoked(_) = invocation_status);
+
+    // the restarted attempt ends the invocation
+    let _ = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id,
+            invocation_epoch: 1,
+            kind: InvokerEffectKind::End,
+        }))
+        .await;
+    let invocation_status = test_env
+        .storage()
+        .get_invocation_status(&invocation_id)
+        .await?;
+    assert!(let InvocationStatus::Free = invocation_status);
+
+    test_env.shutdown().await;
+    Ok(())
+}
@@ -0,0 +1,215 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use restate_storage_api::schedule_table::{ReadOnlyScheduleTable, ScheduleStatus};
+use restate_types::schema::schedules::{Schedule, ScheduleRequest, DEFAULT_TIMEZONE};
+use restate_types::time::MillisSinceEpoch;
+use test_log::test;
+
+const SCHEDULE_ID: &str = "hourly-report";
+
+fn hourly_schedule() -> Schedule {
+    Schedule::new(
+        SCHEDULE_ID.to_owned(),
+        "0 * * * *".to_owned(),
+        DEFAULT_TIMEZONE.to_owned(),
+        InvocationTarget::mock_service(),
+        Bytes::from_static(b"{}"),
+    )
+    .unwrap()
+}
+
+async fn schedule_status(test_env: &mut TestEnv, schedule: &Schedule) -> Option<ScheduleStatus> {
+    test_env
+        .storage()
+        .get_schedule_status(schedule.partition_key(), schedule.id())
+        .await
+        .unwrap()
+}
+
+async fn fire(
+    test_env: &mut TestEnv,
+    schedule: &Schedule,
+    fire_time: MillisSinceEpoch,
+) -> Vec<Action> {
+    test_env
+        .apply(Command::Timer(TimerKeyValue::fire_schedule(
+            fire_time,
+            schedule.invocation_id(fire_time),
+            schedule.id().to_owned(),
+        )))
+        .await
+}
+
+#[test(tokio::test)]
+async fn fire_schedule_invokes_target_and_registers_next_timer() {
+    let mut test_env = TestEnv::create().await;
+    let schedule = hourly_schedule();
+    let first_fire_time = schedule.next_fire_time(MillisSinceEpoch::now()).unwrap();
+
+    let actions = test_env
+        .apply(Command::Schedule(ScheduleRequest::create(
+            schedule.clone(),
+            first_fire_time,
+        )))
+        .await;
+    assert_that!(actions, contains(pat!(Action::RegisterTimer { .. })));
+    assert_eq!(
+        schedule_status(&mut test_env, &schedule)
+            .await
+            .unwrap()
+            .next_fire_time,
+        Some(first_fire_time)
+    );
+
+    let first_invocation_id = schedule.invocation_id(first_fire_time);
+    let actions = fire(&mut test_env, &schedule, first_fire_time).await;
+    assert_that!(
+        actions,
+        all!(
+            contains(matchers::actions::invoke_for_id(first_invocation_id)),
+            contains(pat!(Action::RegisterTimer { .. }))
+        )
+    );
+
+    let status = schedule_status(&mut test_env, &schedule).await.unwrap();
+    assert_eq!(
+        status.next_fire_time,
+        schedule.next_fire_time(first_fire_time)
+    );
+    assert_eq!(status.last_invocation_id, Some(first_invocation_id));
+
+    test_env.shutdown().await;
+}
+
+#[test(tokio::test)]
+async fn skip_occurrence_while_previous_invocation_is_in_flight() {
+    let mut test_env = TestEnv::create().await;
+    let schedule = hourly_schedule();
+    let first_fire_time = schedule.next_fire_time(MillisSinceEpoch::now()).unwrap();
+    let second_fire_time = schedule.next_fire_time(first_fire_time).unwrap();
+    let third_fire_time = schedule.next_fire_time(second_fire_time).unwrap();
+    let first_invocation_id = schedule.invocation_id(first_fire_time);
+
+    let _ = test_env
+        .apply(Command::Schedule(ScheduleRequest::create(
+            schedule.clone(),
+            first_fire_time,
+        )))
+        .await;
+    let _ = fire(&mut test_env, &schedule, first_fire_time).await;
+
+    // The first invocation is still running
+    let actions = fire(&mut test_env, &schedule, second_fire_time).await;
+    assert_that!(
+        actions,
+        not(contains(matchers::actions::invoke_for_id(
+            schedule.invocation_id(second_fire_time)
+        )))
+    );
+    let status = schedule_status(&mut test_env, &schedule).await.unwrap();
+    assert_eq!(status.next_fire_time, Some(third_fire_time));
+    assert_eq!(status.last_invocation_id, Some(first_invocation_id));
+
+    let _ = test_env
+        .apply(Command::InvokerEffect(InvokerEffect {
+            invocation_id: first_invocation_id,
+            invocation_epoch: 0,
+            kind: InvokerEffectKind::End,
+        }))
+        .await;
+
+    let actions = fire(&mut test_env, &schedule, third_fire_time).await;
+    assert_that!(
+        actions,
+        contains(matchers::actions::invoke_for_id(
+            schedule.invocation_id(third_fire_time)
+        ))
+    );
+
+    test_env.shutdown().await;
+}
+
+#[test(tokio::test)]
+async fn fire_late_schedule_skips_missed_occurrences() {
+    let mut test_env = TestEnv::create().await;
+    let schedule = hourly_schedule();
+    let first_fire_time = schedule.next_fire_time(MillisSinceEpoch::now()).unwrap();
+
+    let _ = test_env
+        .apply(Command::Schedule(ScheduleRequest::create(
+            schedule.clone(),
+            first_fire_time,
+        )))
+        .await;
+
+    // The timer fires five and a half hours late, e.g. because the partition had no leader
+    let fired_at = MillisSinceEpoch::new(first_fire_time.as_u64() + 330 * 60 * 1000);
+    let actions = test_env
+        .apply_at(
+            Command::Timer(TimerKeyValue::fire_schedule(
+                first_fire_time,
+                schedule.invocation_id(first_fire_time),
+                schedule.id().to_owned(),
+            )),
+            fired_at,
+        )
+        .await;
+    assert_that!(
+        actions,
+        contains(matchers::actions::invoke_for_id(
+            schedule.invocation_id(first_fire_time)
+        ))
+    );
+
+    let status = schedule_status(&mut test_env, &schedule).await.unwrap();
+    assert_eq!(status.next_fire_time, schedule.next_fire_time(fired_at));
+    assert_eq!(
+        status.next_fire_time,
+        Some(MillisSinceEpoch::new(
+            first_fire_time.as_u64() + 6 * 60 * 60 * 1000
+        ))
+    );
+
+    test_env.shutdown().await;
+}
+
+#[test(tokio::test)]
+async fn delete_schedule_removes_its_timer() {
+    let mut test_env = TestEnv::create().await;
+    let schedule = hourly_schedule();
+    let first_fire_time = schedule.next_fire_time(MillisSinceEpoch::now()).unwrap();
+
+    let _ = test_env
+        .apply(Command::Schedule(ScheduleRequest::create(
+            schedule.clone(),
+            first_fire_time,
+        )))
+        .await;
+
+    let actions = test_env
+        .apply(Command::Schedule(ScheduleRequest::delete(&schedule)))
+        .await;
+    assert_that!(actions, contains(pat!(Action::DeleteTimer { .. })));
+    assert!(schedule_status(&mut test_env, &schedule).await.is_none());
+
+    // A timer which was proposed before the deletion is ignored
+    let actions = fire(&mut test_env, &schedule, first_fire_time).await;
+    assert_that!(
+        actions,
+        not(contains(matchers::actions::invoke_for_id(
+            schedule.invocation_id(first_fire_time)
+        )))
+    );
+
+    test_env.shutdown().await;
+}
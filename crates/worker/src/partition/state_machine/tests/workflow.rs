@@ -11,10 +11,17 @@
 use super::*;
 
 use restate_storage_api::invocation_status_table::CompletedInvocation;
+use restate_storage_api::promise_table::{
+    Promise, PromiseState, PromiseTable, ReadOnlyPromiseTable,
+};
 use restate_storage_api::service_status_table::ReadOnlyVirtualObjectStatusTable;
-use restate_types::errors::WORKFLOW_ALREADY_INVOKED_INVOCATION_ERROR;
+use restate_types::errors::{
+    ALREADY_COMPLETED_INVOCATION_ERROR, WORKFLOW_ALREADY_INVOKED_INVOCATION_ERROR,
+};
+use restate_types::identifiers::JournalEntryId;
 use restate_types::invocation::{
-    AttachInvocationRequest, InvocationQuery, InvocationTarget, PurgeInvocationRequest,
+    AttachInvocationRequest, InvocationQuery, InvocationTarget, PromiseCompletionRequest,
+    PurgeInvocationRequest,
 };
 use rstest::*;
 use std::time::Duration;
@@ -115,6 +122,7 @@ async fn start_workflow_method(#[case] disable_idempotency_table: bool) {
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -124,6 +132,7 @@ async fn start_workflow_method(#[case] disable_idempotency_table: bool) {
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -271,6 +280,7 @@ async fn attach_by_workflow_key(#[case] disable_idempotency_table: bool) {
         .apply_multiple([
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::JournalEntry {
                     entry_index: 1,
                     entry: ProtobufRawEntryCodec::serialize_enriched(Entry::output(
@@ -280,6 +290,7 @@ async fn attach_by_workflow_key(#[case] disable_idempotency_table: bool) {
             }),
             Command::InvokerEffect(InvokerEffect {
                 invocation_id,
+                invocation_epoch: 0,
                 kind: InvokerEffectKind::End,
             }),
         ])
@@ -414,3 +425,105 @@ async fn purge_completed_workflow(#[case] disable_idempotency_table: bool) {
     );
     test_env.shutdown().await;
 }
+
+#[tokio::test]
+async fn complete_promise_from_ingress() {
+    let mut test_env = TestEnv::create().await;
+
+    let workflow_target = InvocationTarget::mock_workflow();
+    let service_id = workflow_target.as_keyed_service_id().unwrap();
+    let promise_key = ByteString::from_static("approval");
+    let listener = JournalEntryId::from_parts(InvocationId::mock_random(), 2);
+    let node_id = GenerationalNodeId::new(1, 1);
+    let request_id_1 = IngressRequestId::default();
+    let request_id_2 = IngressRequestId::default();
+
+    // The workflow is waiting on the promise
+    let mut txn = test_env.storage().transaction();
+    txn.put_promise(
+        &service_id,
+        &promise_key,
+        &Promise {
+            state: PromiseState::NotCompleted(vec![listener]),
+        },
+    )
+    .await;
+    txn.commit().await.unwrap();
+
+    let value = Bytes::from_static(b"true");
+    let actions = test_env
+        .apply(Command::CompletePromise(PromiseCompletionRequest {
+            workflow_target: workflow_target.clone(),
+            key: promise_key.clone(),
+            completion: ResponseResult::Success(value.clone()),
+            response_sink: ServiceInvocationResponseSink::Ingress {
+                node_id,
+                request_id: request_id_1,
+            },
+        }))
+        .await;
+    assert_that!(
+        actions,
+        all!(
+            contains(pat!(Action::NewOutboxMessage {
+                message: pat!(
+                    restate_storage_api::outbox_table::OutboxMessage::ServiceResponse(pat!(
+                        InvocationResponse {
+                            id: eq(listener.invocation_id()),
+                            entry_index: eq(listener.journal_index()),
+                            result: eq(ResponseResult::Success(value.clone()))
+                        }
+                    ))
+                )
+            })),
+            contains(pat!(Action::IngressResponse(pat!(
+                IngressResponseEnvelope {
+                    target_node: eq(node_id),
+                    inner: pat!(ingress::InvocationResponse {
+                        request_id: eq(request_id_1),
+                        response: pat!(IngressResponseResult::Success(anything(), anything()))
+                    })
+                }
+            ))))
+        )
+    );
+    assert_that!(
+        test_env
+            .storage()
+            .get_promise(&service_id, &promise_key)
+            .await
+            .unwrap(),
+        some(eq(Promise {
+            state: PromiseState::Completed(EntryResult::Success(value))
+        }))
+    );
+
+    // Completing it again fails
+    let actions = test_env
+        .apply(Command::CompletePromise(PromiseCompletionRequest {
+            workflow_target,
+            key: promise_key,
+            completion: ResponseResult::Success(Bytes::from_static(b"false")),
+            response_sink: ServiceInvocationResponseSink::Ingress {
+                node_id,
+                request_id: request_id_2,
+            },
+        }))
+        .await;
+    assert_that!(
+        actions,
+        contains(pat!(Action::IngressResponse(pat!(
+            IngressResponseEnvelope {
+                target_node: eq(node_id),
+                inner: pat!(ingress::InvocationResponse {
+                    request_id: eq(request_id_2),
+                    response: eq(IngressResponseResult::Failure(
+                        ALREADY_COMPLETED_INVOCATION_ERROR
+                    ))
+                })
+            }
+        ))))
+    );
+
+    test_env.shutdown().await;
+}
@@ -32,24 +32,26 @@ use restate_core::network::{MessageHandler, Networking, TransportConnect};
 use restate_core::worker_api::{ProcessorsManagerCommand, ProcessorsManagerHandle};
 use restate_core::{cancellation_watcher, task_center, Metadata, ShutdownError, TaskId, TaskKind};
 use restate_core::{RuntimeError, TaskCenter};
+use restate_egress_kafka::KafkaSinkProducer;
 use restate_invoker_api::StatusHandle;
 use restate_invoker_impl::Service as InvokerService;
-use restate_invoker_impl::{BuildError, ChannelStatusReader};
+use restate_invoker_impl::{BuildError, ChannelStatusReader, ConcurrencyLimiter};
 use restate_metadata_store::{MetadataStoreClient, ReadModifyWriteError};
+use restate_partition_store::snapshots::{LocalPartitionSnapshot, PartitionSnapshotMetadata};
 use restate_partition_store::{OpenMode, PartitionStore, PartitionStoreManager};
 use restate_service_protocol::codec::ProtobufRawEntryCodec;
 use restate_storage_api::fsm_table::ReadOnlyFsmTable;
 use restate_storage_api::StorageError;
 use restate_types::cluster::cluster_state::ReplayStatus;
 use restate_types::cluster::cluster_state::{PartitionProcessorStatus, RunMode};
-use restate_types::config::{Configuration, StorageOptions};
+use restate_types::config::{Configuration, StorageOptions, WorkerOptions};
 use restate_types::epoch::EpochMetadata;
 use restate_types::health::HealthStatus;
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, SnapshotId};
 use restate_types::live::Live;
 use restate_types::live::LiveLoad;
-use restate_types::logs::Lsn;
 use restate_types::logs::SequenceNumber;
+use restate_types::logs::{LogId, Lsn};
 use restate_types::metadata_store::keys::partition_processor_epoch_key;
 use restate_types::net::cluster_controller::AttachRequest;
 use restate_types::net::cluster_controller::{Action, AttachResponse};
@@ -60,6 +62,7 @@ use restate_types::net::partition_processor_manager::{
 };
 use restate_types::partition_table::PartitionTable;
 use restate_types::protobuf::common::WorkerStatus;
+use restate_types::retries::RetryPolicy;
 use restate_types::schema::Schema;
 use restate_types::time::MillisSinceEpoch;
 use restate_types::GenerationalNodeId;
@@ -74,6 +77,7 @@ use crate::metric_definitions::PARTITION_LAST_PERSISTED_LOG_LSN;
 use crate::metric_definitions::PARTITION_TIME_SINCE_LAST_RECORD;
 use crate::metric_definitions::PARTITION_TIME_SINCE_LAST_STATUS_UPDATE;
 use crate::partition::invoker_storage_reader::InvokerStorageReader;
+use crate::partition::snapshot_repository::SnapshotRepository;
 use crate::partition::PartitionProcessorControlCommand;
 use crate::PartitionProcessorBuilder;
 
@@ -92,12 +96,15 @@ pub struct PartitionProcessorManager<T> {
         Pin<Box<dyn Stream<Item = Incoming<ControlProcessors>> + Send + Sync + 'static>>,
     networking: Networking<T>,
     bifrost: Bifrost,
+    kafka_sink_producer: KafkaSinkProducer,
+    concurrency_limiter: ConcurrencyLimiter,
     rx: mpsc::Receiver<ProcessorsManagerCommand>,
     tx: mpsc::Sender<ProcessorsManagerCommand>,
     latest_attach_response: Option<(GenerationalNodeId, AttachResponse)>,
 
     persisted_lsns_rx: Option<watch::Receiver<BTreeMap<PartitionId, Lsn>>>,
     invokers_status_reader: MultiplexedInvokerStatusReader,
+    snapshot_repository: Option<SnapshotRepository>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -384,6 +391,7 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
         router_builder: &mut MessageRouterBuilder,
         networking: Networking<T>,
         bifrost: Bifrost,
+        snapshot_repository: Option<SnapshotRepository>,
     ) -> Self {
         let attach_router = RpcRouter::new(router_builder);
         let incoming_update_processors = router_builder.subscribe_to_stream(2);
@@ -401,12 +409,15 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
             incoming_update_processors,
             networking,
             bifrost,
+            kafka_sink_producer: KafkaSinkProducer::default(),
+            concurrency_limiter: ConcurrencyLimiter::default(),
             attach_router,
             rx,
             tx,
             latest_attach_response: None,
             persisted_lsns_rx: None,
             invokers_status_reader: MultiplexedInvokerStatusReader::default(),
+            snapshot_repository,
         }
     }
 
@@ -743,6 +754,7 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
 
         let networking = self.networking.clone();
         let bifrost = self.bifrost.clone();
+        let kafka_sink_producer = self.kafka_sink_producer.clone();
         let node_id = self.metadata.my_node_id();
 
         let schema = self.metadata.updateable_schema();
@@ -756,6 +768,7 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
             &config.worker.invoker,
             EntryEnricher::new(schema.clone()),
             schema,
+            self.concurrency_limiter.clone(),
         )?;
 
         self.invokers_status_reader
@@ -770,6 +783,7 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
             control_rx,
             watch_tx,
             invoker.handle(),
+            self.snapshot_repository.clone(),
         );
 
         // the name is also used as thread names for the corresponding tokio runtimes, let's keep
@@ -789,17 +803,21 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
             Some(pp_builder.partition_id),
             {
                 let storage_manager = self.partition_store_manager.clone();
+                let snapshot_repository = self.snapshot_repository.clone();
                 let options = options.clone();
                 let key_range = key_range.clone();
                 move || async move {
-                    let partition_store = storage_manager
-                        .open_partition_store(
-                            partition_id,
-                            key_range,
-                            OpenMode::CreateIfMissing,
-                            &options.storage.rocksdb,
-                        )
-                        .await?;
+                    let mut pp_builder = pp_builder;
+                    let (partition_store, last_archived_log_lsn) = open_partition_store(
+                        &storage_manager,
+                        &bifrost,
+                        snapshot_repository.as_ref(),
+                        partition_id,
+                        key_range,
+                        &options,
+                    )
+                    .await?;
+                    pp_builder.status.last_archived_log_lsn = last_archived_log_lsn;
 
                     restate_core::task_center().spawn_child(
                         TaskKind::SystemService,
@@ -812,6 +830,7 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
                         .build::<ProtobufRawEntryCodec, T>(
                             networking,
                             bifrost,
+                            kafka_sink_producer,
                             partition_store,
                             configuration,
                         )
@@ -843,6 +862,153 @@ impl<T: TransportConnect> PartitionProcessorManager<T> {
     }
 }
 
+/// A partition snapshot from which a partition store can be restored.
+enum PartitionSnapshot<'a> {
+    Local(LocalPartitionSnapshot),
+    Repository(&'a SnapshotRepository, PartitionSnapshotMetadata),
+}
+
+impl PartitionSnapshot<'_> {
+    fn min_applied_lsn(&self) -> Lsn {
+        match self {
+            PartitionSnapshot::Local(snapshot) => snapshot.min_applied_lsn,
+            PartitionSnapshot::Repository(_, metadata) => metadata.min_applied_lsn,
+        }
+    }
+}
+
+/// Opens the partition store of the given partition. The partition store is restored from the
+/// latest snapshot if it doesn't exist yet, or if it cannot catch up with the log anymore because
+/// the records it still has to apply have been trimmed. The latest snapshot is either a local
+/// one, or it is downloaded from the snapshot repository. Returns the partition store together
+/// with the applied lsn of the latest snapshot, if there is any.
+async fn open_partition_store(
+    partition_store_manager: &PartitionStoreManager,
+    bifrost: &Bifrost,
+    snapshot_repository: Option<&SnapshotRepository>,
+    partition_id: PartitionId,
+    key_range: RangeInclusive<PartitionKey>,
+    options: &WorkerOptions,
+) -> anyhow::Result<(PartitionStore, Option<Lsn>)> {
+    let local_snapshot = LocalPartitionSnapshot::find_latest(
+        partition_id,
+        &options.snapshots.snapshots_dir(partition_id),
+    )
+    .await?
+    .map(PartitionSnapshot::Local);
+    let repository_snapshot = match snapshot_repository {
+        // Without knowing the latest snapshot, we might replay a log which has been trimmed
+        // already. That's why we fail opening the partition store, so that it is retried later.
+        Some(repository) => RetryPolicy::exponential(
+            Duration::from_millis(100),
+            2.0,
+            Some(10),
+            Some(Duration::from_secs(10)),
+        )
+        .retry(|| repository.get_latest(partition_id))
+        .await
+        .context("failed looking up the latest snapshot in the snapshot repository")?
+        .map(|metadata| PartitionSnapshot::Repository(repository, metadata)),
+        None => None,
+    };
+    let latest_snapshot = match (local_snapshot, repository_snapshot) {
+        (Some(local), Some(repository))
+            if repository.min_applied_lsn() > local.min_applied_lsn() =>
+        {
+            Some(repository)
+        }
+        (local, repository) => local.or(repository),
+    };
+
+    let last_archived_log_lsn = latest_snapshot
+        .as_ref()
+        .map(PartitionSnapshot::min_applied_lsn);
+    let trim_point = bifrost.get_trim_point(LogId::from(partition_id)).await?;
+    // only a snapshot which includes all trimmed records can be used to catch up with the log
+    let latest_snapshot =
+        latest_snapshot.filter(|snapshot| snapshot.min_applied_lsn() >= trim_point);
+
+    let snapshot = if partition_store_manager.partition_store_exists(partition_id) {
+        let mut partition_store = partition_store_manager
+            .open_partition_store(
+                partition_id,
+                key_range.clone(),
+                OpenMode::OpenExisting,
+                &options.storage.rocksdb,
+            )
+            .await?;
+        let applied_lsn = partition_store
+            .get_applied_lsn()
+            .await?
+            .unwrap_or(Lsn::INVALID);
+
+        if applied_lsn >= trim_point {
+            return Ok((partition_store, last_archived_log_lsn));
+        }
+
+        let Some(snapshot) = latest_snapshot else {
+            anyhow::bail!(
+                "The partition store has applied lsn={} which is behind the log trim point lsn={}, and there is no snapshot to restore it from",
+                applied_lsn,
+                trim_point
+            );
+        };
+
+        warn!(
+            %applied_lsn,
+            %trim_point,
+            "The partition store is behind the log trim point, restoring it from the latest snapshot"
+        );
+        partition_store_manager.drop_partition(partition_id).await;
+        snapshot
+    } else if let Some(snapshot) = latest_snapshot {
+        snapshot
+    } else if trim_point == Lsn::INVALID {
+        let partition_store = partition_store_manager
+            .open_partition_store(
+                partition_id,
+                key_range,
+                OpenMode::CreateIfMissing,
+                &options.storage.rocksdb,
+            )
+            .await?;
+        return Ok((partition_store, last_archived_log_lsn));
+    } else {
+        anyhow::bail!(
+            "The log has been trimmed up to lsn={}, and there is no snapshot to bootstrap the partition store from",
+            trim_point
+        );
+    };
+
+    let (snapshot, download_dir) = match snapshot {
+        PartitionSnapshot::Local(snapshot) => (snapshot, None),
+        PartitionSnapshot::Repository(repository, metadata) => {
+            let download_dir = options
+                .snapshots
+                .snapshots_dir(partition_id)
+                .join(metadata.snapshot_id.to_string());
+            let snapshot = repository.get(metadata, download_dir.clone()).await?;
+            (snapshot, Some(download_dir))
+        }
+    };
+
+    let partition_store = partition_store_manager
+        .restore_partition_store_snapshot(
+            partition_id,
+            key_range,
+            snapshot,
+            &options.storage.rocksdb,
+        )
+        .await?;
+
+    if let Some(download_dir) = download_dir {
+        // the snapshot files have been imported into the partition store
+        tokio::fs::remove_dir_all(&download_dir).await?;
+    }
+
+    Ok((partition_store, last_archived_log_lsn))
+}
+
 /// Monitors the persisted log lsns and notifies the partition processor manager about it. The
 /// current approach requires flushing the memtables to make sure that data has been persisted.
 /// An alternative approach could be to register an event listener on flush events and using
@@ -975,13 +1141,18 @@ impl PersistedLogLsnWatchdog {
 
 #[cfg(test)]
 mod tests {
-    use crate::partition_processor_manager::PersistedLogLsnWatchdog;
+    use crate::partition::snapshot_producer::{SnapshotProducer, SnapshotSource};
+    use crate::partition::snapshot_repository::SnapshotRepository;
+    use crate::partition_processor_manager::{open_partition_store, PersistedLogLsnWatchdog};
+    use restate_bifrost::Bifrost;
     use restate_core::{TaskKind, TestCoreEnv};
     use restate_partition_store::{OpenMode, PartitionStoreManager};
     use restate_rocksdb::RocksDbManager;
-    use restate_storage_api::fsm_table::FsmTable;
+    use restate_storage_api::fsm_table::{FsmTable, ReadOnlyFsmTable};
     use restate_storage_api::Transaction;
-    use restate_types::config::{CommonOptions, RocksDbOptions, StorageOptions};
+    use restate_types::config::{
+        CommonOptions, RocksDbOptions, SnapshotsOptions, StorageOptions, WorkerOptions,
+    };
     use restate_types::identifiers::{PartitionId, PartitionKey};
     use restate_types::live::Constant;
     use restate_types::logs::{Lsn, SequenceNumber};
@@ -991,6 +1162,7 @@ mod tests {
     use test_log::test;
     use tokio::sync::watch;
     use tokio::time::Instant;
+    use url::Url;
 
     #[test(tokio::test(start_paused = true))]
     async fn persisted_log_lsn_watchdog_detects_applied_lsns() -> anyhow::Result<()> {
@@ -1084,4 +1256,101 @@ mod tests {
 
         Ok(())
     }
+
+    #[test(tokio::test)]
+    async fn open_partition_store_restores_snapshot_from_repository() -> anyhow::Result<()> {
+        let node_env = TestCoreEnv::create_with_single_node(1, 1).await;
+        let worker_options = WorkerOptions::default();
+        let rocksdb_options = &worker_options.storage.rocksdb;
+
+        node_env.tc.run_in_scope_sync("db-manager-init", None, || {
+            RocksDbManager::init(Constant::new(CommonOptions::default()))
+        });
+        let bifrost = node_env
+            .tc
+            .run_in_scope(
+                "init bifrost",
+                None,
+                Bifrost::init_in_memory(node_env.metadata.clone()),
+            )
+            .await;
+
+        node_env
+            .tc
+            .run_in_scope("test", None, async {
+                let repository_dir = tempfile::tempdir()?;
+                let local_snapshots_dir = tempfile::tempdir()?;
+                let repository = SnapshotRepository::create_if_configured(&SnapshotsOptions {
+                    destination: Some(
+                        Url::from_directory_path(repository_dir.path())
+                            .expect("absolute path")
+                            .to_string(),
+                    ),
+                    ..SnapshotsOptions::default()
+                })?
+                .expect("destination is set");
+
+                let partition_id = PartitionId::from(12);
+                let key_range = RangeInclusive::new(0, PartitionKey::MAX);
+                let partition_store_manager = PartitionStoreManager::create(
+                    Constant::new(worker_options.storage.clone()).boxed(),
+                    Constant::new(rocksdb_options.clone()).boxed(),
+                    &[(partition_id, key_range.clone())],
+                )
+                .await?;
+
+                let mut partition_store = partition_store_manager
+                    .open_partition_store(
+                        partition_id,
+                        key_range.clone(),
+                        OpenMode::CreateIfMissing,
+                        rocksdb_options,
+                    )
+                    .await?;
+                let mut txn = partition_store.transaction();
+                txn.put_applied_lsn(Lsn::new(100)).await;
+                txn.commit().await?;
+
+                let metadata = SnapshotProducer::create(
+                    SnapshotSource {
+                        cluster_name: "test-cluster".to_owned(),
+                        node_name: "test-node".to_owned(),
+                    },
+                    partition_store,
+                    local_snapshots_dir.path().to_path_buf(),
+                )
+                .await?;
+                repository
+                    .put(
+                        &metadata,
+                        &local_snapshots_dir
+                            .path()
+                            .join(metadata.snapshot_id.to_string()),
+                    )
+                    .await?;
+
+                // without a partition store, it has to be restored from the snapshot repository
+                partition_store_manager.drop_partition(partition_id).await;
+                assert!(!partition_store_manager.partition_store_exists(partition_id));
+
+                let (mut partition_store, last_archived_log_lsn) = open_partition_store(
+                    &partition_store_manager,
+                    &bifrost,
+                    Some(&repository),
+                    partition_id,
+                    key_range,
+                    &worker_options,
+                )
+                .await?;
+
+                assert_eq!(Some(Lsn::new(100)), last_archived_log_lsn);
+                assert_eq!(
+                    Some(Lsn::new(100)),
+                    partition_store.get_applied_lsn().await?
+                );
+
+                anyhow::Ok(())
+            })
+            .await
+    }
 }
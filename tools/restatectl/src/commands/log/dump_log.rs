@@ -98,7 +98,8 @@ async fn dump_log(opts: &DumpLogOpts) -> anyhow::Result<()> {
         )?;
 
         let bifrost_svc = BifrostService::new(tc.clone(), metadata.clone())
-            .enable_local_loglet(&Configuration::updateable());
+            .enable_local_loglet(&Configuration::updateable())
+            .enable_tiered_storage_loglet(&Configuration::updateable());
 
         let bifrost = bifrost_svc.handle();
         // Ensures bifrost has initial metadata synced up before starting the worker.
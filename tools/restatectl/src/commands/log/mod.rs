@@ -13,6 +13,7 @@ mod dump_log;
 mod find_tail;
 mod gen_metadata;
 pub mod list_logs;
+mod offload_segment;
 mod reconfigure;
 mod trim_log;
 
@@ -43,6 +44,8 @@ pub enum Logs {
     Reconfigure(reconfigure::ReconfigureOpts),
     /// Find and show tail state of a log
     FindTail(find_tail::FindTailOpts),
+    /// Offload a sealed segment of a log to tiered storage
+    Offload(offload_segment::OffloadSegmentOpts),
 }
 
 #[derive(Parser, Collect, Clone, Debug)]
@@ -0,0 +1,67 @@
+// Copyright (c) 2024 -  Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Context;
+use cling::prelude::*;
+use tonic::codec::CompressionEncoding;
+
+use restate_admin::cluster_controller::protobuf::cluster_ctrl_svc_client::ClusterCtrlSvcClient;
+use restate_admin::cluster_controller::protobuf::OffloadLogSegmentRequest;
+use restate_cli_util::c_println;
+
+use crate::app::ConnectionInfo;
+use crate::util::grpc_connect;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[clap()]
+#[cling(run = "offload_segment")]
+pub struct OffloadSegmentOpts {
+    /// The log of the segment
+    #[arg(short, long)]
+    log_id: u32,
+
+    /// The index of the sealed segment to offload
+    #[arg(short, long)]
+    segment_index: u32,
+}
+
+async fn offload_segment(
+    connection: &ConnectionInfo,
+    opts: &OffloadSegmentOpts,
+) -> anyhow::Result<()> {
+    let channel = grpc_connect(connection.cluster_controller.clone())
+        .await
+        .with_context(|| {
+            format!(
+                "cannot connect to cluster controller at {}",
+                connection.cluster_controller
+            )
+        })?;
+    let mut client =
+        ClusterCtrlSvcClient::new(channel).accept_compressed(CompressionEncoding::Gzip);
+
+    let offload_request = OffloadLogSegmentRequest {
+        log_id: opts.log_id,
+        segment_index: opts.segment_index,
+    };
+    client
+        .offload_log_segment(offload_request)
+        .await
+        .with_context(|| "failed to offload segment")?
+        .into_inner();
+
+    c_println!(
+        "Offloaded segment {} of log {} to tiered storage",
+        opts.segment_index,
+        opts.log_id
+    );
+
+    Ok(())
+}
@@ -84,6 +84,9 @@ async fn reconfigure(connection: &ConnectionInfo, opts: &ReconfigureOpts) -> any
         ProviderKind::InMemory => rand::random::<u64>().to_string(),
         #[cfg(feature = "replicated-loglet")]
         ProviderKind::Replicated => replicated_loglet_params(&mut client, opts).await?,
+        ProviderKind::TieredStorage => {
+            anyhow::bail!("tiered-storage segments can only be created by offloading sealed segments, use 'log offload' instead")
+        }
     };
 
     let response = client
@@ -36,7 +36,7 @@ pub enum Metadata {
 #[derive(Args, Clone, Debug)]
 #[clap()]
 pub struct MetadataCommonOpts {
-    /// Metadata store server address; for Etcd addresses use comma-separated list
+    /// Metadata store server address; use a comma-separated list for multiple addresses
     #[arg(
         short,
         long = "address",
@@ -109,7 +109,11 @@ pub async fn create_metadata_store_client(
 ) -> anyhow::Result<MetadataStoreClient> {
     let client = match opts.remote_service_type {
         RemoteServiceType::Restate => restate_types::config::MetadataStoreClient::Embedded {
-            address: AdvertisedAddress::from_str(opts.address.as_str())
+            addresses: opts
+                .address
+                .split(',')
+                .map(AdvertisedAddress::from_str)
+                .collect::<Result<Vec<_>, _>>()
                 .map_err(|e| anyhow::anyhow!("Failed to parse address: {}", e))?,
         },
         RemoteServiceType::Etcd => restate_types::config::MetadataStoreClient::Etcd {
@@ -142,6 +142,7 @@ pub async fn list_partitions(
         "SEQNCR",
         "APPLIED",
         "PERSISTED",
+        "ARCHIVED",
         "SKIPPED",
         "LAST-UPDATE",
     ]);
@@ -270,6 +271,13 @@ pub async fn list_partitions(
                         .map(|x| x.to_string())
                         .unwrap_or("-".to_owned()),
                 ),
+                Cell::new(
+                    processor
+                        .status
+                        .last_archived_log_lsn
+                        .map(|x| x.to_string())
+                        .unwrap_or("-".to_owned()),
+                ),
                 Cell::new(processor.status.num_skipped_records),
                 render_as_duration(processor.status.updated_at, Tense::Past),
             ]);